@@ -7,10 +7,12 @@ use std::fs;
 use std::path::{Path, PathBuf};
 use tokio;
 use tracing_subscriber::{EnvFilter, fmt};
-use tracing::{ info, debug, error, trace, warn };
+use tracing::{ info, error, trace, warn };
 use sha3::{Digest, Keccak256};
 use hex;
 
+use abi_fetcher::blockscout::{BlockscoutClient, Implementation, SmartContractItem};
+
 // Configuration structure for the app
 #[derive(Debug, Deserialize)]
 struct AppConfig {
@@ -134,57 +136,6 @@ struct EventSignature {
     event: String, // Extended event signature
 }
 
-// API response structures for smart contracts list
-#[derive(Debug, Deserialize)]
-struct SmartContractsResponse {
-    items: Vec<SmartContractItem>,
-    next_page_params: Option<NextPageParams>,
-}
-
-#[derive(Debug, Deserialize)]
-struct SmartContractItem {
-    address: ContractAddressResponse,
-    verified_at: Option<String>,
-}
-
-#[derive(Debug, Deserialize)]
-struct ContractAddressResponse {
-    hash: String,
-    implementations: Option<Vec<Implementation>>,
-    is_contract: Option<bool>,
-    is_verified: Option<bool>,
-    name: Option<String>,
-}
-
-#[derive(Debug, Deserialize)]
-struct Implementation {
-    address: String,
-    name: Option<String>,
-}
-
-#[derive(Debug, Deserialize)]
-struct NextPageParams {
-    items_count: u64,
-    hash: String,
-    #[serde(default)]
-    coin_balance: Option<String>,
-    #[serde(default)]
-    transaction_count: Option<u64>,
-    #[serde(default)]
-    transactions_count: Option<u64>,
-}
-
-// API response structure for individual contract details
-#[derive(Debug, Deserialize)]
-struct ContractDetailsResponse {
-    is_verified: Option<bool>,
-    is_fully_verified: Option<bool>,
-    implementations: Option<Vec<Implementation>>,
-    name: Option<String>,
-    abi: Option<Value>,
-    verified_at: Option<String>,
-}
-
 // Output structures for YAML
 #[derive(Debug, Serialize)]
 struct ContractsOutput {
@@ -246,149 +197,6 @@ struct AbiFileCounters {
     unverified_implementations: usize,
 }
 
-struct BlockscoutClient {
-    client: reqwest::Client,
-    base_url: String,
-    max_retries: u32,
-    auth_user: Option<String>,
-    auth_password: Option<String>,
-}
-
-impl BlockscoutClient {
-    fn new(
-        server: &str,
-        api_path: &str,
-        timeout_seconds: u64,
-        max_retries: u32,
-        auth_user: Option<String>,
-        auth_password: Option<String>,
-    ) -> Self {
-        let client = reqwest::Client::builder()
-            .timeout(std::time::Duration::from_secs(timeout_seconds))
-            .build()
-            .expect("Failed to create HTTP client");
-
-        let base_url = format!("{}/{}", server.trim_end_matches('/'), api_path.trim_start_matches('/'));
-
-        Self {
-            client,
-            base_url,
-            max_retries,
-            auth_user,
-            auth_password,
-        }
-    }
-
-    async fn fetch_all_verified_contracts(&self) -> Result<Vec<SmartContractItem>> {
-        let mut all_contracts = Vec::new();
-        let mut next_page_params: Option<NextPageParams> = None;
-        let mut initial_page_size: Option<u64> = None;
-        let mut page_multiplier: u64 = 1;
-
-        loop {
-            let url = if let Some(ref params) = next_page_params {
-                // Use initial page size from first response multiplied by page number
-                let page_size = initial_page_size.unwrap_or(params.items_count);
-                page_multiplier += 1;
-                let items_count = page_size * page_multiplier;
-
-                format!("{}/smart-contracts?items_count={}&hash={}",
-                    self.base_url, items_count, params.hash)
-            } else {
-                // First request without pagination parameters
-                format!("{}/smart-contracts", self.base_url)
-            };
-
-            info!("Fetching contracts from: {}", url);
-
-            let response = self.fetch_with_retry(&url).await
-                .context("Failed to fetch smart contracts list")?;
-
-            let contracts_response: SmartContractsResponse = response.json().await
-                .context("Failed to parse smart contracts response")?;
-
-            let items_count = contracts_response.items.len();
-            info!("Fetched {} contracts in this page", items_count);
-
-            all_contracts.extend(contracts_response.items);
-
-            // Check if there's a next page
-            if let Some(next_params) = contracts_response.next_page_params {
-                // Save initial page size from the first response
-                if initial_page_size.is_none() {
-                    initial_page_size = Some(next_params.items_count);
-                    info!("Initial page size: {}", next_params.items_count);
-                }
-
-                debug!("Next page params - items_count: {}, hash: {}, page_multiplier: {}",
-                    next_params.items_count, next_params.hash, page_multiplier + 1);
-                next_page_params = Some(next_params);
-            } else {
-                info!("No more pages (next_page_params is null), pagination complete");
-                break;
-            }
-        }
-
-        info!("Total contracts fetched: {}", all_contracts.len());
-        Ok(all_contracts)
-    }
-
-    async fn fetch_contract_details(&self, address: &str) -> Result<ContractDetailsResponse> {
-        let url = format!("{}/smart-contracts/{}", self.base_url, address);
-
-        debug!("Fetching contract details for: {}", address);
-
-        let response = self.fetch_with_retry(&url).await
-            .with_context(|| format!("Failed to fetch contract details for {}", address))?;
-
-        let contract_details: ContractDetailsResponse = response.json().await
-            .with_context(|| format!("Failed to parse contract details for {}", address))?;
-
-        Ok(contract_details)
-    }
-
-    async fn fetch_with_retry(&self, url: &str) -> Result<reqwest::Response> {
-        let mut last_error = None;
-
-        for attempt in 0..=self.max_retries {
-            let mut request = self.client.get(url);
-
-            // Add Basic Auth if credentials are provided
-            if let (Some(user), Some(password)) = (&self.auth_user, &self.auth_password) {
-                request = request.basic_auth(user, Some(password));
-            }
-
-            match request.send().await {
-                Ok(response) => {
-                    if response.status().is_success() {
-                        return Ok(response);
-                    } else {
-                        let status = response.status();
-                        let error = anyhow::anyhow!("HTTP error: {}", status);
-                        last_error = Some(error);
-
-                        if attempt < self.max_retries {
-                            warn!("Request failed with status {}, retrying... (attempt {}/{})",
-                                  status, attempt + 1, self.max_retries);
-                            tokio::time::sleep(tokio::time::Duration::from_millis(1000 * (attempt + 1) as u64)).await;
-                        }
-                    }
-                }
-                Err(e) => {
-                    last_error = Some(e.into());
-                    if attempt < self.max_retries {
-                        warn!("Request failed: {:?}, retrying... (attempt {}/{})",
-                              last_error, attempt + 1, self.max_retries);
-                        tokio::time::sleep(tokio::time::Duration::from_millis(1000 * (attempt + 1) as u64)).await;
-                    }
-                }
-            }
-        }
-
-        Err(last_error.unwrap_or_else(|| anyhow::anyhow!("Max retries exceeded")))
-    }
-}
-
 // Helper function to parse RFC3339 timestamp and convert to Unix timestamp for comparison
 fn parse_verified_at_timestamp(verified_at: &Option<String>) -> Option<i64> {
     verified_at.as_ref().and_then(|timestamp_str| {
@@ -0,0 +1,200 @@
+//! Blockscout v2 API client
+
+use anyhow::{Context, Result};
+use serde::Deserialize;
+use serde_json::Value;
+use tracing::{info, debug, warn};
+
+// API response structures for smart contracts list
+#[derive(Debug, Deserialize)]
+pub struct SmartContractsResponse {
+    pub items: Vec<SmartContractItem>,
+    pub next_page_params: Option<NextPageParams>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct SmartContractItem {
+    pub address: ContractAddressResponse,
+    pub verified_at: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ContractAddressResponse {
+    pub hash: String,
+    pub implementations: Option<Vec<Implementation>>,
+    pub is_contract: Option<bool>,
+    pub is_verified: Option<bool>,
+    pub name: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Implementation {
+    pub address: String,
+    pub name: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct NextPageParams {
+    pub items_count: u64,
+    pub hash: String,
+    #[serde(default)]
+    pub coin_balance: Option<String>,
+    #[serde(default)]
+    pub transaction_count: Option<u64>,
+    #[serde(default)]
+    pub transactions_count: Option<u64>,
+}
+
+// API response structure for individual contract details
+#[derive(Debug, Deserialize)]
+pub struct ContractDetailsResponse {
+    pub is_verified: Option<bool>,
+    pub is_fully_verified: Option<bool>,
+    pub implementations: Option<Vec<Implementation>>,
+    pub name: Option<String>,
+    pub abi: Option<Value>,
+    pub verified_at: Option<String>,
+}
+
+pub struct BlockscoutClient {
+    client: reqwest::Client,
+    base_url: String,
+    max_retries: u32,
+    auth_user: Option<String>,
+    auth_password: Option<String>,
+}
+
+impl BlockscoutClient {
+    pub fn new(
+        server: &str,
+        api_path: &str,
+        timeout_seconds: u64,
+        max_retries: u32,
+        auth_user: Option<String>,
+        auth_password: Option<String>,
+    ) -> Self {
+        let client = reqwest::Client::builder()
+            .timeout(std::time::Duration::from_secs(timeout_seconds))
+            .build()
+            .expect("Failed to create HTTP client");
+
+        let base_url = format!("{}/{}", server.trim_end_matches('/'), api_path.trim_start_matches('/'));
+
+        Self {
+            client,
+            base_url,
+            max_retries,
+            auth_user,
+            auth_password,
+        }
+    }
+
+    pub async fn fetch_all_verified_contracts(&self) -> Result<Vec<SmartContractItem>> {
+        let mut all_contracts = Vec::new();
+        let mut next_page_params: Option<NextPageParams> = None;
+        let mut initial_page_size: Option<u64> = None;
+        let mut page_multiplier: u64 = 1;
+
+        loop {
+            let url = if let Some(ref params) = next_page_params {
+                // Use initial page size from first response multiplied by page number
+                let page_size = initial_page_size.unwrap_or(params.items_count);
+                page_multiplier += 1;
+                let items_count = page_size * page_multiplier;
+
+                format!("{}/smart-contracts?items_count={}&hash={}",
+                    self.base_url, items_count, params.hash)
+            } else {
+                // First request without pagination parameters
+                format!("{}/smart-contracts", self.base_url)
+            };
+
+            info!("Fetching contracts from: {}", url);
+
+            let response = self.fetch_with_retry(&url).await
+                .context("Failed to fetch smart contracts list")?;
+
+            let contracts_response: SmartContractsResponse = response.json().await
+                .context("Failed to parse smart contracts response")?;
+
+            let items_count = contracts_response.items.len();
+            info!("Fetched {} contracts in this page", items_count);
+
+            all_contracts.extend(contracts_response.items);
+
+            // Check if there's a next page
+            if let Some(next_params) = contracts_response.next_page_params {
+                // Save initial page size from the first response
+                if initial_page_size.is_none() {
+                    initial_page_size = Some(next_params.items_count);
+                    info!("Initial page size: {}", next_params.items_count);
+                }
+
+                debug!("Next page params - items_count: {}, hash: {}, page_multiplier: {}",
+                    next_params.items_count, next_params.hash, page_multiplier + 1);
+                next_page_params = Some(next_params);
+            } else {
+                info!("No more pages (next_page_params is null), pagination complete");
+                break;
+            }
+        }
+
+        info!("Total contracts fetched: {}", all_contracts.len());
+        Ok(all_contracts)
+    }
+
+    pub async fn fetch_contract_details(&self, address: &str) -> Result<ContractDetailsResponse> {
+        let url = format!("{}/smart-contracts/{}", self.base_url, address);
+
+        debug!("Fetching contract details for: {}", address);
+
+        let response = self.fetch_with_retry(&url).await
+            .with_context(|| format!("Failed to fetch contract details for {}", address))?;
+
+        let contract_details: ContractDetailsResponse = response.json().await
+            .with_context(|| format!("Failed to parse contract details for {}", address))?;
+
+        Ok(contract_details)
+    }
+
+    async fn fetch_with_retry(&self, url: &str) -> Result<reqwest::Response> {
+        let mut last_error = None;
+
+        for attempt in 0..=self.max_retries {
+            let mut request = self.client.get(url);
+
+            // Add Basic Auth if credentials are provided
+            if let (Some(user), Some(password)) = (&self.auth_user, &self.auth_password) {
+                request = request.basic_auth(user, Some(password));
+            }
+
+            match request.send().await {
+                Ok(response) => {
+                    if response.status().is_success() {
+                        return Ok(response);
+                    } else {
+                        let status = response.status();
+                        let error = anyhow::anyhow!("HTTP error: {}", status);
+                        last_error = Some(error);
+
+                        if attempt < self.max_retries {
+                            warn!("Request failed with status {}, retrying... (attempt {}/{})",
+                                  status, attempt + 1, self.max_retries);
+                            tokio::time::sleep(tokio::time::Duration::from_millis(1000 * (attempt + 1) as u64)).await;
+                        }
+                    }
+                }
+                Err(e) => {
+                    last_error = Some(e.into());
+                    if attempt < self.max_retries {
+                        warn!("Request failed: {:?}, retrying... (attempt {}/{})",
+                              last_error, attempt + 1, self.max_retries);
+                        tokio::time::sleep(tokio::time::Duration::from_millis(1000 * (attempt + 1) as u64)).await;
+                    }
+                }
+            }
+        }
+
+        Err(last_error.unwrap_or_else(|| anyhow::anyhow!("Max retries exceeded")))
+    }
+}
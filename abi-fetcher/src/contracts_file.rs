@@ -0,0 +1,48 @@
+//! Reader of the contracts YAML files written by `abi-fetcher` and `contracts-fetcher`
+
+use anyhow::{Context, Result};
+use serde::Deserialize;
+
+/// Contracts file, only the contracts names and addresses are read
+#[derive(Debug, Default, Deserialize)]
+pub struct ContractsFile {
+    #[serde(default)]
+    pub verified_contracts: Vec<ContractEntry>,
+    #[serde(default)]
+    pub unverified_contracts: Vec<ContractEntry>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ContractEntry {
+    pub name: Option<String>,
+    pub address: String,
+    #[serde(default)]
+    pub implementations: Option<Vec<ContractEntry>>, // Written by `abi-fetcher` only
+}
+
+impl ContractsFile {
+    pub fn load(path: &str) -> Result<Self> {
+        let content = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read contracts file {}", path))?;
+        serde_yaml::from_str(&content)
+            .with_context(|| format!("Failed to parse contracts file {}", path))
+    }
+
+    /// (address, name) of the named contracts and their implementations
+    pub fn contract_names(&self) -> Vec<(String, String)> {
+        let mut names = Vec::new();
+        for contract in self.verified_contracts.iter().chain(&self.unverified_contracts) {
+            collect_names(contract, &mut names);
+        }
+        names
+    }
+}
+
+fn collect_names(contract: &ContractEntry, names: &mut Vec<(String, String)>) {
+    if let Some(name) = contract.name.as_ref().filter(|name| !name.is_empty()) {
+        names.push((contract.address.clone(), name.clone()));
+    }
+    for implementation in contract.implementations.iter().flatten() {
+        collect_names(implementation, names);
+    }
+}
@@ -0,0 +1,5 @@
+//! Blockscout API client and contracts files reader, shared by the fetcher tools and
+//! the monitors resolving ABIs of contracts at run-time and labelling addresses
+
+pub mod blockscout;
+pub mod contracts_file;
@@ -1,15 +1,24 @@
 use anyhow::{Context, Result};
 use serde::{Deserialize, Serialize};
 use std::path::Path;
+use std::time::Duration;
 use tokio::sync::Mutex;
 
-use crate::config::AgentConfig;
+use crate::config::{AgentConfig, ModelBackend};
+use crate::money::{AmountError, Money, Rounding};
+
+mod prompt;
+
+use prompt::PromptTemplate;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AgentRequest {
     pub prompt: String,
     pub context: Option<String>,
     pub max_tokens: Option<usize>,
+    /// Id of the HTTP request, correlating the agent logs with the payment stages
+    #[serde(default)]
+    pub request_id: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -30,24 +39,46 @@ pub struct PaymentAction {
     pub protocol_params: serde_json::Value,
 }
 
+impl PaymentAction {
+    /// Amount of the action in the currency minor units, the model `f64` amount
+    /// rounded with the rounding rule (see [`Money::from_f64`])
+    pub fn money(&self, rounding: Rounding) -> Result<Money, AmountError> {
+        Money::from_f64(self.amount, &self.currency, rounding)
+    }
+}
+
 pub struct AgentRunner {
     config: AgentConfig,
+    backend: ModelBackend,
+    prompt_template: PromptTemplate,
     model: Mutex<Option<Box<dyn ModelInference + Send>>>,
 }
 
 impl AgentRunner {
     pub fn new(config: &AgentConfig) -> Result<Self> {
-        let model_path = Path::new(&config.model_path);
-        
-        if !model_path.exists() {
-            tracing::warn!(
-                "Model file not found at {}. Agent will run in mock mode.",
-                config.model_path
-            );
-        }
+        let backend = match &config.backend {
+            Some(ModelBackend::LocalGguf { path }) if !Path::new(path).exists() => {
+                anyhow::bail!("Model file not found at {}", path);
+            }
+            Some(backend) => backend.clone(),
+            None if Path::new(&config.model_path).exists() => ModelBackend::LocalGguf {
+                path: config.model_path.clone(),
+            },
+            None => {
+                tracing::warn!(
+                    "No model backend configured and model file not found at {}. Agent will run in mock mode.",
+                    config.model_path
+                );
+                ModelBackend::Mock
+            }
+        };
+
+        let prompt_template = PromptTemplate::load(&config.prompt)?;
 
         Ok(Self {
             config: config.clone(),
+            backend,
+            prompt_template,
             model: Mutex::new(None),
         })
     }
@@ -59,25 +90,37 @@ impl AgentRunner {
             return Ok(());
         }
 
-        let model_path = Path::new(&self.config.model_path);
-        
-        if !model_path.exists() {
-            *model = Some(Box::new(MockModel::new()));
-            tracing::info!("Using mock model for development");
-            return Ok(());
-        }
+        let inference: Box<dyn ModelInference + Send> = match &self.backend {
+            ModelBackend::Mock => {
+                tracing::info!("Using mock model for development");
+                Box::new(MockModel::new())
+            }
+            ModelBackend::LocalGguf { path } => {
+                let inference = LlamaModel::load(
+                    path,
+                    self.config.context_size,
+                    self.config.inference.threads,
+                    self.config.inference.gpu_layers,
+                )?;
+                tracing::info!("Model loaded successfully from {}", path);
+                Box::new(inference)
+            }
+            ModelBackend::OpenAiCompatible { url, api_key, model: model_name, timeout_seconds } => {
+                let inference = OpenAiCompatibleModel::new(
+                    url,
+                    api_key.as_deref(),
+                    model_name,
+                    self.config.temperature,
+                    self.config.top_p,
+                    Duration::from_secs(*timeout_seconds),
+                )?;
+                tracing::info!("Using model {} of OpenAI compatible API at {}", model_name, url);
+                Box::new(inference)
+            }
+        };
+
+        *model = Some(inference);
 
-        // Initialize actual model inference
-        let inference = LlamaModel::load(
-            &self.config.model_path,
-            self.config.context_size,
-            self.config.inference.threads,
-            self.config.inference.gpu_layers,
-        )?;
-        
-        *model = Some(Box::new(inference));
-        tracing::info!("Model loaded successfully from {}", self.config.model_path);
-        
         Ok(())
     }
 
@@ -87,6 +130,7 @@ impl AgentRunner {
         let model = self.model.lock().await;
         let model = model.as_ref().context("Model not initialized")?;
 
+        tracing::debug!(request_id = ?request.request_id, "Agent processing request");
         let prompt = self.build_prompt(&request);
         
         let response = model.generate(&prompt, self.config.max_tokens).await?;
@@ -95,31 +139,9 @@ impl AgentRunner {
     }
 
     fn build_prompt(&self, request: &AgentRequest) -> String {
-        let system_prompt = r#"You are a payment processing agent. Analyze user requests and generate structured payment actions.
-
-When processing a payment request:
-1. Extract: amount, currency, recipient, payment method
-2. Determine the appropriate protocol (X402 or AP2)
-3. Generate action in JSON format
-
-Response format:
-{
-  "protocol": "x402" or "ap2",
-  "action": {
-    "action_type": "transfer|request|approve",
-    "amount": numeric,
-    "currency": "USD|EUR|ETH|etc",
-    "recipient": "address or identifier",
-    "memo": "optional description"
-  }
-}"#;
-
         let context = request.context.as_deref().unwrap_or("");
-        
-        format!(
-            "{}\n\nContext: {}\n\nUser Request: {}\n\nResponse:",
-            system_prompt, context, request.prompt
-        )
+
+        self.prompt_template.render(&request.prompt, context)
     }
 
     fn parse_response(&self, text: String) -> Result<AgentResponse> {
@@ -242,3 +264,107 @@ impl ModelInference for LlamaModel {
         Ok("Model response placeholder".to_string())
     }
 }
+
+// Remote model served with the OpenAI chat completions API
+struct OpenAiCompatibleModel {
+    client: reqwest::Client,
+    endpoint: String,
+    api_key: Option<String>,
+    model: String,
+    temperature: f32,
+    top_p: f32,
+}
+
+#[derive(Serialize)]
+struct ChatCompletionRequest<'a> {
+    model: &'a str,
+    messages: [ChatMessage<'a>; 1],
+    max_tokens: usize,
+    temperature: f32,
+    top_p: f32,
+}
+
+#[derive(Serialize)]
+struct ChatMessage<'a> {
+    role: &'a str,
+    content: &'a str,
+}
+
+#[derive(Deserialize)]
+struct ChatCompletionResponse {
+    choices: Vec<ChatCompletionChoice>,
+}
+
+#[derive(Deserialize)]
+struct ChatCompletionChoice {
+    message: ChatCompletionMessage,
+}
+
+#[derive(Deserialize)]
+struct ChatCompletionMessage {
+    #[serde(default)]
+    content: Option<String>,
+}
+
+impl OpenAiCompatibleModel {
+    fn new(
+        url: &str,
+        api_key: Option<&str>,
+        model: &str,
+        temperature: f32,
+        top_p: f32,
+        timeout: Duration,
+    ) -> Result<Self> {
+        let client = reqwest::Client::builder()
+            .timeout(timeout)
+            .build()
+            .context("Failed to build model API client")?;
+
+        Ok(Self {
+            client,
+            endpoint: format!("{}/chat/completions", url.trim_end_matches('/')),
+            api_key: api_key.filter(|key| !key.is_empty()).map(String::from),
+            model: model.to_string(),
+            temperature,
+            top_p,
+        })
+    }
+}
+
+#[async_trait::async_trait]
+impl ModelInference for OpenAiCompatibleModel {
+    async fn generate(&self, prompt: &str, max_tokens: usize) -> Result<String> {
+        tracing::debug!("Requesting completion from {}, prompt length: {}, max_tokens: {}",
+            self.endpoint, prompt.len(), max_tokens);
+
+        let request = ChatCompletionRequest {
+            model: &self.model,
+            messages: [ChatMessage { role: "user", content: prompt }],
+            max_tokens,
+            temperature: self.temperature,
+            top_p: self.top_p,
+        };
+
+        let mut http_request = self.client.post(&self.endpoint).json(&request);
+        if let Some(api_key) = &self.api_key {
+            http_request = http_request.bearer_auth(api_key);
+        }
+
+        let response: ChatCompletionResponse = http_request
+            .send()
+            .await
+            .context("Failed to send completion request")?
+            .error_for_status()
+            .context("Completion request rejected")?
+            .json()
+            .await
+            .context("Invalid completion response")?;
+
+        response
+            .choices
+            .into_iter()
+            .next()
+            .and_then(|choice| choice.message.content)
+            .context("Completion response has no message content")
+    }
+}
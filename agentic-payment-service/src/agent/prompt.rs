@@ -0,0 +1,218 @@
+use anyhow::{bail, Context, Result};
+
+use crate::config::PromptConfig;
+
+/// Built-in template, used when no template is configured
+const DEFAULT_TEMPLATE: &str = r#"You are a payment processing agent. Analyze user requests and generate structured payment actions.
+
+When processing a payment request:
+1. Extract: amount, currency, recipient, payment method
+2. Determine the appropriate protocol (X402 or AP2)
+3. Generate action in JSON format
+
+Response format:
+{
+  "protocol": "x402" or "ap2",
+  "action": {
+    "action_type": "transfer|request|approve",
+    "amount": numeric,
+    "currency": "USD|EUR|ETH|etc",
+    "recipient": "address or identifier",
+    "memo": "optional description"
+  }
+}
+
+Context: {context}
+
+User Request: {prompt}
+
+Response:"#;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Placeholder {
+    Prompt,
+    Context,
+}
+
+impl Placeholder {
+    const ALL: [Placeholder; 2] = [Placeholder::Prompt, Placeholder::Context];
+
+    fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "prompt" => Some(Self::Prompt),
+            "context" => Some(Self::Context),
+            _ => None,
+        }
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            Self::Prompt => "prompt",
+            Self::Context => "context",
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+enum Segment {
+    Text(String),
+    Placeholder(Placeholder),
+}
+
+/// Agent prompt template with the `{prompt}` and `{context}` placeholders, both required.
+/// Other braces (e.g. of a JSON response format) are kept as is, only `{name}` with
+/// a lowercase identifier name is a placeholder, so a misspelled one is rejected.
+#[derive(Debug, Clone)]
+pub struct PromptTemplate {
+    segments: Vec<Segment>,
+}
+
+impl PromptTemplate {
+    /// Template of the inline `template` or the `template_file`, the built-in one if neither is set
+    pub fn load(config: &PromptConfig) -> Result<Self> {
+        match (&config.template, &config.template_file) {
+            (Some(_), Some(_)) => bail!("Only one of agent prompt template and template_file may be set"),
+            (Some(template), None) => Self::parse(template).context("Invalid agent prompt template"),
+            (None, Some(path)) => {
+                let template = std::fs::read_to_string(path)
+                    .with_context(|| format!("Failed to read agent prompt template file {}", path))?;
+                Self::parse(&template)
+                    .with_context(|| format!("Invalid agent prompt template file {}", path))
+            }
+            (None, None) => Self::parse(DEFAULT_TEMPLATE),
+        }
+    }
+
+    fn parse(template: &str) -> Result<Self> {
+        let mut segments = Vec::new();
+        let mut text = String::new();
+        let mut rest = template;
+
+        while let Some(start) = rest.find('{') {
+            text.push_str(&rest[..start]);
+            rest = &rest[start..];
+
+            let name = rest[1..]
+                .find('}')
+                .map(|end| &rest[1..=end])
+                .filter(|name| is_placeholder_name(name));
+
+            match name {
+                Some(name) => {
+                    let placeholder = Placeholder::from_name(name)
+                        .with_context(|| format!("Unknown placeholder {{{}}}", name))?;
+                    if !text.is_empty() {
+                        segments.push(Segment::Text(std::mem::take(&mut text)));
+                    }
+                    segments.push(Segment::Placeholder(placeholder));
+                    rest = &rest[name.len() + 2..];
+                }
+                None => {
+                    text.push('{');
+                    rest = &rest[1..];
+                }
+            }
+        }
+        text.push_str(rest);
+        if !text.is_empty() {
+            segments.push(Segment::Text(text));
+        }
+
+        for placeholder in Placeholder::ALL {
+            let present = segments
+                .iter()
+                .any(|segment| matches!(segment, Segment::Placeholder(p) if *p == placeholder));
+            if !present {
+                bail!("Missing required placeholder {{{}}}", placeholder.name());
+            }
+        }
+
+        Ok(Self { segments })
+    }
+
+    /// Interpolate the placeholders in a single pass, so placeholders in the user
+    /// prompt or context are not expanded
+    pub fn render(&self, prompt: &str, context: &str) -> String {
+        self.segments
+            .iter()
+            .map(|segment| match segment {
+                Segment::Text(text) => text.as_str(),
+                Segment::Placeholder(Placeholder::Prompt) => prompt,
+                Segment::Placeholder(Placeholder::Context) => context,
+            })
+            .collect()
+    }
+}
+
+fn is_placeholder_name(name: &str) -> bool {
+    !name.is_empty() && name.bytes().all(|byte| byte.is_ascii_lowercase() || byte == b'_')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse_error(template: &str) -> String {
+        PromptTemplate::parse(template).unwrap_err().to_string()
+    }
+
+    #[test]
+    fn test_parse_and_render() {
+        let template = PromptTemplate::parse("Context: {context}\nRequest: {prompt}").unwrap();
+        assert_eq!(template.render("pay bob", "none"), "Context: none\nRequest: pay bob");
+
+        // Placeholders adjacent, at the ends and repeated
+        let template = PromptTemplate::parse("{prompt}{context}{prompt}").unwrap();
+        assert_eq!(template.render("a", "b"), "aba");
+    }
+
+    #[test]
+    fn test_other_braces_kept() {
+        let template = PromptTemplate::parse(
+            r#"{"amount": numeric, "currency": "USD|ETH"} {} {Prompt} {{prompt}} {context} {unclosed"#,
+        )
+        .unwrap();
+        assert_eq!(
+            template.render("P", "C"),
+            r#"{"amount": numeric, "currency": "USD|ETH"} {} {Prompt} {P} C {unclosed"#,
+        );
+    }
+
+    #[test]
+    fn test_placeholders_in_values_not_expanded() {
+        let template = PromptTemplate::parse("{context} / {prompt}").unwrap();
+        assert_eq!(template.render("{context}", "{prompt}"), "{prompt} / {context}");
+    }
+
+    #[test]
+    fn test_malformed_templates_rejected() {
+        assert_eq!(parse_error("{prompt} {contxt}"), "Unknown placeholder {contxt}");
+        assert_eq!(parse_error("{prompt} {context} {user_name}"), "Unknown placeholder {user_name}");
+        assert_eq!(parse_error("Request: {prompt}"), "Missing required placeholder {context}");
+        assert_eq!(parse_error("Context: {context}"), "Missing required placeholder {prompt}");
+        assert_eq!(parse_error(""), "Missing required placeholder {prompt}");
+    }
+
+    #[test]
+    fn test_load() {
+        let default = PromptTemplate::load(&PromptConfig::default()).unwrap();
+        assert!(default.render("pay bob", "none").ends_with("User Request: pay bob\n\nResponse:"));
+
+        let both = PromptConfig {
+            template: Some("{prompt} {context}".to_string()),
+            template_file: Some("prompt.txt".to_string()),
+        };
+        assert!(PromptTemplate::load(&both).is_err());
+
+        let path = std::env::temp_dir().join(format!("agent_prompt_{}.txt", std::process::id()));
+        std::fs::write(&path, "{prompt} with {contex}").unwrap();
+        let from_file = PromptConfig {
+            template: None,
+            template_file: Some(path.to_string_lossy().into_owned()),
+        };
+        let error = format!("{:#}", PromptTemplate::load(&from_file).unwrap_err());
+        std::fs::remove_file(&path).unwrap();
+        assert!(error.contains("Invalid agent prompt template file"));
+        assert!(error.contains("Unknown placeholder {contex}"));
+    }
+}
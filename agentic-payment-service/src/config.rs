@@ -1,6 +1,10 @@
-use anyhow::Result;
+use anyhow::{Context, Result};
 use serde::{Deserialize, Serialize};
-use std::fs;
+use std::collections::HashMap;
+use std::fmt;
+use std::net::IpAddr;
+
+use crate::money::{AmountError, Money, Rounding};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Config {
@@ -10,6 +14,12 @@ pub struct Config {
     pub payment_gateways: PaymentGatewaysConfig,
     pub middleware: MiddlewareConfig,
     pub security: SecurityConfig,
+    #[serde(default)]
+    pub payment_store: PaymentStoreConfig,
+    #[serde(default)]
+    pub reconciliation: ReconciliationConfig,
+    #[serde(default)]
+    pub webhooks: WebhooksConfig,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -48,6 +58,11 @@ pub struct AP2Config {
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AgentConfig {
+    /// Model the agent runs on. Unset, the GGUF file at `model_path` is used if present,
+    /// the mock model otherwise
+    #[serde(default)]
+    pub backend: Option<ModelBackend>,
+    #[serde(default)]
     pub model_path: String,
     pub model_type: String,
     pub context_size: usize,
@@ -55,6 +70,61 @@ pub struct AgentConfig {
     pub top_p: f32,
     pub max_tokens: usize,
     pub inference: InferenceConfig,
+    #[serde(default)]
+    pub prompt: PromptConfig,
+}
+
+/// Prompt the agent sends to the model, a template with the `{prompt}` (user request)
+/// and `{context}` placeholders. The built-in template is used when neither is set.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct PromptConfig {
+    /// Inline template
+    #[serde(default)]
+    pub template: Option<String>,
+    /// Template file, read at startup
+    #[serde(default)]
+    pub template_file: Option<String>,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ModelBackend {
+    /// Canned responses, for development without a model
+    Mock,
+    /// GGUF model file loaded in process
+    LocalGguf { path: String },
+    /// Remote server with the OpenAI chat completions API (OpenAI, vLLM, llama.cpp server, Ollama, ...)
+    #[serde(rename = "openai_compatible")]
+    OpenAiCompatible {
+        /// Base URL of the API, e.g. `http://localhost:8000/v1`
+        url: String,
+        #[serde(default)]
+        api_key: Option<String>,
+        model: String,
+        #[serde(default = "default_model_timeout_seconds")]
+        timeout_seconds: u64,
+    },
+}
+
+// Written out so logging the config never shows the API key
+impl fmt::Debug for ModelBackend {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Mock => f.write_str("Mock"),
+            Self::LocalGguf { path } => f.debug_struct("LocalGguf").field("path", path).finish(),
+            Self::OpenAiCompatible { url, api_key, model, timeout_seconds } => f
+                .debug_struct("OpenAiCompatible")
+                .field("url", url)
+                .field("api_key", &api_key.as_ref().map(|_| "<redacted>"))
+                .field("model", model)
+                .field("timeout_seconds", timeout_seconds)
+                .finish(),
+        }
+    }
+}
+
+fn default_model_timeout_seconds() -> u64 {
+    60
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -98,12 +168,52 @@ pub struct MiddlewareConfig {
 pub struct RateLimitConfig {
     pub enabled: bool,
     pub requests_per_minute: u32,
+    /// Bucket capacity; defaults to `requests_per_minute` when unset
+    #[serde(default)]
+    pub burst: Option<u32>,
+    /// What the buckets are keyed on: `api_key` (configured `authentication.api_keys` only,
+    /// falls back to IP) or `ip`
+    #[serde(default)]
+    pub key_by: RateLimitKeyBy,
+    /// Proxies trusted to set `X-Forwarded-For`, e.g. the load balancer. Requests of other
+    /// peers are keyed on the peer address
+    #[serde(default)]
+    pub trusted_proxies: Vec<IpAddr>,
+    /// How often the background task refills the buckets
+    #[serde(default = "default_refill_interval_ms")]
+    pub refill_interval_ms: u64,
+    /// Per-API-key overrides of the default limit
+    #[serde(default)]
+    pub per_key: HashMap<String, KeyRateLimitConfig>,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RateLimitKeyBy {
+    #[default]
+    ApiKey,
+    Ip,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct KeyRateLimitConfig {
+    pub requests_per_minute: u32,
+    #[serde(default)]
+    pub burst: Option<u32>,
+}
+
+fn default_refill_interval_ms() -> u64 {
+    1000
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AuthConfig {
     pub enabled: bool,
     pub jwt_secret: String,
+    /// Accepted bearer tokens, also the keys of the API key rate limit buckets.
+    /// Empty, any non-empty token is accepted (development) and requests are limited by IP
+    #[serde(default)]
+    pub api_keys: Vec<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -114,15 +224,135 @@ pub struct LoggingConfig {
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SecurityConfig {
-    pub max_payment_amount: f64,
+    /// Largest payment amount per currency, as exact decimal major units (e.g. `"10000.00"` USD).
+    /// Payments in unlisted currencies are not limited
+    #[serde(default)]
+    pub max_payment_amounts: HashMap<String, String>,
+    /// Rounding of the agent amounts to the currency minor units
+    #[serde(default)]
+    pub amount_rounding: Rounding,
     pub require_confirmation: bool,
     pub allowed_origins: Vec<String>,
 }
 
+impl SecurityConfig {
+    /// Largest payment amount in the currency, `None` if the currency is not limited
+    pub fn max_payment_amount(&self, currency: &str) -> Result<Option<Money>, AmountError> {
+        self.max_payment_amounts
+            .iter()
+            .find(|(limit_currency, _)| limit_currency.trim().eq_ignore_ascii_case(currency))
+            .map(|(limit_currency, amount)| Money::from_decimal_str(amount, limit_currency, Rounding::Exact))
+            .transpose()
+    }
+}
+
+/// Background re-checking of payments left non-terminal after the gateway call
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReconciliationConfig {
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+    /// How often pending payments are scanned
+    #[serde(default = "default_reconciliation_interval_secs")]
+    pub interval_secs: u64,
+    /// Pending payments not updated for this long are re-checked against their gateway
+    #[serde(default = "default_stale_after_secs")]
+    pub stale_after_secs: u64,
+}
+
+impl Default for ReconciliationConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            interval_secs: default_reconciliation_interval_secs(),
+            stale_after_secs: default_stale_after_secs(),
+        }
+    }
+}
+
+/// In-memory store of the executed payments
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PaymentStoreConfig {
+    /// Payments are kept this long after their last update once terminal,
+    /// or after their creation if they never reach a terminal status
+    #[serde(default = "default_payment_retention_secs")]
+    pub retention_secs: u64,
+}
+
+impl Default for PaymentStoreConfig {
+    fn default() -> Self {
+        Self {
+            retention_secs: default_payment_retention_secs(),
+        }
+    }
+}
+
+fn default_payment_retention_secs() -> u64 {
+    24 * 60 * 60
+}
+
+fn default_true() -> bool {
+    true
+}
+
+fn default_reconciliation_interval_secs() -> u64 {
+    60
+}
+
+fn default_stale_after_secs() -> u64 {
+    120
+}
+
+/// Endpoints notified of payment status changes
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WebhooksConfig {
+    #[serde(default)]
+    pub urls: Vec<String>,
+    #[serde(default = "default_webhook_timeout_seconds")]
+    pub timeout_seconds: u64,
+}
+
+impl Default for WebhooksConfig {
+    fn default() -> Self {
+        Self {
+            urls: Vec::new(),
+            timeout_seconds: default_webhook_timeout_seconds(),
+        }
+    }
+}
+
+fn default_webhook_timeout_seconds() -> u64 {
+    10
+}
+
 impl Config {
     pub fn load(path: &str) -> Result<Self> {
-        let content = fs::read_to_string(path)?;
-        let config: Config = serde_yaml::from_str(&content)?;
+        let config: Config = config_loader::load(path)?;
+
+        for (currency, amount) in &config.security.max_payment_amounts {
+            Money::from_decimal_str(amount, currency, Rounding::Exact)
+                .with_context(|| format!("Invalid security.max_payment_amounts entry for {}", currency))?;
+        }
+
         Ok(config)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_model_backend_debug_redacts_api_key() {
+        let backend = ModelBackend::OpenAiCompatible {
+            url: "http://localhost:8000/v1".to_string(),
+            api_key: Some("sk-secret".to_string()),
+            model: "llama".to_string(),
+            timeout_seconds: 60,
+        };
+
+        let debug = format!("{:?}", backend);
+        assert!(!debug.contains("sk-secret"));
+        assert!(debug.contains("<redacted>"));
+        assert!(debug.contains("http://localhost:8000/v1"));
+    }
+}
@@ -1,15 +1,16 @@
 use axum::{
-    extract::{Path, State},
+    extract::{Extension, Path, State},
     http::StatusCode,
     response::{IntoResponse, Json},
 };
 use serde::{Deserialize, Serialize};
-use uuid::Uuid;
 
 use crate::{
     agent::{AgentRequest, AgentResponse},
+    middleware::request_id::RequestId,
+    money::{AmountError, Money},
     protocols::PaymentRequest,
-    payment::GatewayPaymentRequest,
+    payment::{store::PaymentRecord, GatewayPaymentRequest},
     AppState,
 };
 
@@ -23,6 +24,8 @@ pub struct PaymentPromptRequest {
 
 #[derive(Debug, Serialize)]
 pub struct PaymentPromptResponse {
+    /// Request id of the prompt, to pass as the execute request id and `X-Request-Id` header,
+    /// correlating the logs of the whole payment
     pub request_id: String,
     pub agent_response: AgentResponse,
     pub suggested_protocol: Option<String>,
@@ -35,10 +38,14 @@ pub struct ExecutePaymentRequest {
     pub protocol: String,
     pub gateway: String,
     pub confirmation: bool,
+    /// Amount to pay, a demonstration amount the gateway can transfer if not given
+    #[serde(default)]
+    pub amount: Option<Money>,
 }
 
 #[derive(Debug, Serialize)]
 pub struct ExecutePaymentResponse {
+    pub request_id: String,
     pub transaction_id: String,
     pub status: String,
     pub message: String,
@@ -55,6 +62,7 @@ pub async fn health_check() -> impl IntoResponse {
 
 pub async fn process_payment_prompt(
     State(state): State<AppState>,
+    Extension(request_id): Extension<RequestId>,
     Json(payload): Json<PaymentPromptRequest>,
 ) -> Result<Json<PaymentPromptResponse>, (StatusCode, String)> {
     tracing::info!("Processing payment prompt: {}", payload.prompt);
@@ -63,6 +71,7 @@ pub async fn process_payment_prompt(
         prompt: payload.prompt.clone(),
         context: payload.context.clone(),
         max_tokens: None,
+        request_id: Some(request_id.0.clone()),
     };
 
     let agent_response = state
@@ -81,9 +90,13 @@ pub async fn process_payment_prompt(
     let estimated_fees = if let Some(action) = &agent_response.action {
         let protocol = suggested_protocol.as_ref().unwrap();
         let gateway = payload.preferred_gateway.as_deref().unwrap_or("web2");
-        
+
+        let amount = action
+            .money(state.config.security.amount_rounding)
+            .map_err(|e| (StatusCode::UNPROCESSABLE_ENTITY, format!("Invalid agent payment amount: {}", e)))?;
+
         if let Ok(gw) = state.gateway_manager.get(gateway) {
-            gw.estimate_fees(action.amount, &action.currency)
+            gw.estimate_fees(&amount)
                 .await
                 .ok()
         } else {
@@ -93,10 +106,8 @@ pub async fn process_payment_prompt(
         None
     };
 
-    let request_id = Uuid::new_v4().to_string();
-
     Ok(Json(PaymentPromptResponse {
-        request_id,
+        request_id: request_id.0,
         agent_response,
         suggested_protocol,
         estimated_fees,
@@ -105,10 +116,11 @@ pub async fn process_payment_prompt(
 
 pub async fn execute_payment(
     State(state): State<AppState>,
+    Extension(request_id): Extension<RequestId>,
     Json(payload): Json<ExecutePaymentRequest>,
 ) -> Result<Json<ExecutePaymentResponse>, (StatusCode, String)> {
-    tracing::info!("Executing payment with protocol: {}, gateway: {}", 
-        payload.protocol, payload.gateway);
+    tracing::info!("Executing payment {} with protocol: {}, gateway: {}",
+        payload.request_id, payload.protocol, payload.gateway);
 
     if !payload.confirmation {
         return Err((
@@ -117,15 +129,35 @@ pub async fn execute_payment(
         ));
     }
 
+    let amount = match payload.amount.clone() {
+        Some(amount) => amount,
+        None => default_amount(&payload.gateway)
+            .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?,
+    };
+
+    let max_amount = state
+        .config
+        .security
+        .max_payment_amount(amount.currency())
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+    if let Some(max_amount) = max_amount {
+        if amount.minor_units() > max_amount.minor_units() {
+            return Err((
+                StatusCode::UNPROCESSABLE_ENTITY,
+                format!("Payment amount {} exceeds the maximum of {}", amount, max_amount),
+            ));
+        }
+    }
+
     // Create mock payment request for demonstration
     let payment_request = PaymentRequest {
         id: payload.request_id.clone(),
-        amount: 100.0,
-        currency: "USD".to_string(),
+        amount,
         sender: "agent_001".to_string(),
         recipient: "agent_002".to_string(),
         memo: Some("Payment via agentic service".to_string()),
-        metadata: serde_json::json!({}),
+        // Sent along to the protocol and the gateway, correlating their records with the service logs
+        metadata: serde_json::json!({ "request_id": request_id.as_str() }),
     };
 
     // Process through protocol
@@ -137,8 +169,7 @@ pub async fn execute_payment(
 
     // Execute through gateway
     let gateway_request = GatewayPaymentRequest {
-        amount: payment_request.amount,
-        currency: payment_request.currency.clone(),
+        amount: payment_request.amount.clone(),
         from: payment_request.sender.clone(),
         to: payment_request.recipient.clone(),
         memo: payment_request.memo.clone(),
@@ -151,7 +182,22 @@ pub async fn execute_payment(
         .await
         .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
 
+    // Tracked for the reconciliation of payments the gateway leaves pending
+    let now = chrono::Utc::now();
+    state.payment_store.insert(PaymentRecord {
+        transaction_id: protocol_response.transaction_id.clone(),
+        request_id: request_id.0.clone(),
+        protocol: payload.protocol.clone(),
+        gateway: payload.gateway.clone(),
+        gateway_tx_hash: gateway_response.transaction_hash.clone(),
+        amount: payment_request.amount.clone(),
+        status: gateway_response.status.clone(),
+        created_at: now,
+        updated_at: now,
+    }).await;
+
     Ok(Json(ExecutePaymentResponse {
+        request_id: request_id.0,
         transaction_id: protocol_response.transaction_id.clone(),
         status: format!("{:?}", protocol_response.status),
         message: protocol_response.message.clone(),
@@ -162,14 +208,32 @@ pub async fn execute_payment(
     }))
 }
 
+/// Demonstration amount of execute requests without one: 0.01 ETH for the Web3 gateway,
+/// which only transfers native ETH, 100.00 USD for the others
+fn default_amount(gateway: &str) -> Result<Money, AmountError> {
+    match gateway {
+        "web3" => Money::new(10_000_000_000_000_000, "ETH"),
+        _ => Money::new(10_000, "USD"),
+    }
+}
+
 pub async fn get_payment_status(
     State(state): State<AppState>,
     Path(id): Path<String>,
 ) -> Result<Json<serde_json::Value>, (StatusCode, String)> {
     tracing::info!("Checking payment status: {}", id);
 
-    // In a real implementation, you'd track payments in a database
-    // For now, return a mock response
+    if let Some(payment) = state.payment_store.get(&id).await {
+        return Ok(Json(serde_json::json!({
+            "transaction_id": id,
+            "status": format!("{:?}", payment.status),
+            "gateway": payment.gateway,
+            "gateway_tx_hash": payment.gateway_tx_hash,
+            "last_updated": payment.updated_at.to_rfc3339(),
+        })));
+    }
+
+    // Payments not executed by this instance are checked with the protocol
     let protocol = state
         .protocol_manager
         .get("x402")
@@ -195,6 +259,7 @@ pub struct AgentQueryRequest {
 
 pub async fn agent_query(
     State(state): State<AppState>,
+    Extension(request_id): Extension<RequestId>,
     Json(payload): Json<AgentQueryRequest>,
 ) -> Result<Json<AgentResponse>, (StatusCode, String)> {
     tracing::info!("Processing agent query");
@@ -203,6 +268,7 @@ pub async fn agent_query(
         prompt: payload.query,
         context: payload.context,
         max_tokens: None,
+        request_id: Some(request_id.0),
     };
 
     let response = state
@@ -4,7 +4,9 @@ use axum::{
     routing::{get, post},
     middleware as axum_middleware,
 };
+use std::net::SocketAddr;
 use std::sync::Arc;
+use std::time::Duration;
 use tower_http::{cors::CorsLayer, trace::TraceLayer};
 use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
 
@@ -15,11 +17,16 @@ mod middleware;
 mod handlers;
 mod payment;
 mod error;
+mod money;
 
 use config::Config;
 use agent::AgentRunner;
 use protocols::{ProtocolManager, x402::X402Protocol, ap2::AP2Protocol};
-use payment::{PaymentGatewayManager, web3::Web3Gateway, web2::Web2Gateway};
+use payment::{
+    PaymentGatewayManager, web3::Web3Gateway, web2::Web2Gateway,
+    store::PaymentStore, webhook::WebhookNotifier, reconciler::PaymentReconciler,
+};
+use middleware::rate_limit::RateLimiter;
 
 #[derive(Clone)]
 pub struct AppState {
@@ -27,6 +34,8 @@ pub struct AppState {
     agent: Arc<AgentRunner>,
     protocol_manager: Arc<ProtocolManager>,
     gateway_manager: Arc<PaymentGatewayManager>,
+    payment_store: Arc<PaymentStore>,
+    rate_limiter: Arc<RateLimiter>,
 }
 
 #[tokio::main]
@@ -80,12 +89,37 @@ async fn main() -> Result<()> {
         tracing::info!("Web2 gateway registered");
     }
 
+    // Initialize rate limiter with background bucket refill
+    let rate_limiter = Arc::new(RateLimiter::new(&config.middleware.rate_limiting));
+    rate_limiter.spawn_refill();
+
+    // Initialize payment store with background reconciliation of pending payments
+    let gateway_manager = Arc::new(gateway_manager);
+    let payment_store = Arc::new(PaymentStore::new(Duration::from_secs(config.payment_store.retention_secs)));
+    let notifier = Arc::new(WebhookNotifier::new(&config.webhooks)?);
+
+    if config.reconciliation.enabled {
+        PaymentReconciler::new(
+            &config.reconciliation,
+            Arc::clone(&payment_store),
+            Arc::clone(&gateway_manager),
+            notifier,
+        )
+        .spawn();
+        tracing::info!(
+            "Payment reconciliation enabled: every {}s for payments pending over {}s",
+            config.reconciliation.interval_secs, config.reconciliation.stale_after_secs
+        );
+    }
+
     // Create shared state
     let state = AppState {
         config: Arc::new(config.clone()),
         agent: Arc::new(agent),
         protocol_manager: Arc::new(protocol_manager),
-        gateway_manager: Arc::new(gateway_manager),
+        gateway_manager,
+        payment_store,
+        rate_limiter,
     };
 
     // Build application router
@@ -99,9 +133,15 @@ async fn main() -> Result<()> {
             state.clone(),
             middleware::auth::auth_middleware,
         ))
-        .layer(axum_middleware::from_fn(middleware::rate_limit::rate_limit_middleware))
+        // Rate limiting runs before auth, so requests failing auth are throttled too
+        .layer(axum_middleware::from_fn_with_state(
+            state.clone(),
+            middleware::rate_limit::rate_limit_middleware,
+        ))
         .layer(CorsLayer::permissive())
-        .layer(TraceLayer::new_for_http())
+        .layer(TraceLayer::new_for_http().make_span_with(middleware::request_id::make_request_span))
+        // Outermost, so the trace span and the rejected requests responses have the request id
+        .layer(axum_middleware::from_fn(middleware::request_id_middleware))
         .with_state(state);
 
     // Start server
@@ -110,7 +150,7 @@ async fn main() -> Result<()> {
     
     tracing::info!("Server listening on {}", addr);
     
-    axum::serve(listener, app).await?;
+    axum::serve(listener, app.into_make_service_with_connect_info::<SocketAddr>()).await?;
 
     Ok(())
 }
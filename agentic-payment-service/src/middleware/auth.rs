@@ -1,12 +1,27 @@
 use axum::{
     extract::{Request, State},
-    http::{HeaderMap, StatusCode},
+    http::{header, HeaderMap, StatusCode},
     middleware::Next,
     response::Response,
 };
 
+use crate::config::AuthConfig;
 use crate::AppState;
 
+/// Non-empty bearer token of the `Authorization` header
+fn bearer_token(headers: &HeaderMap) -> Option<&str> {
+    headers
+        .get(header::AUTHORIZATION)
+        .and_then(|h| h.to_str().ok())
+        .and_then(|h| h.strip_prefix("Bearer "))
+        .filter(|token| !token.is_empty())
+}
+
+/// Bearer token of the request if it's one of the configured API keys
+pub fn configured_api_key<'a>(config: &AuthConfig, headers: &'a HeaderMap) -> Option<&'a str> {
+    bearer_token(headers).filter(|token| config.api_keys.iter().any(|key| key == token))
+}
+
 pub async fn auth_middleware(
     State(state): State<AppState>,
     headers: HeaderMap,
@@ -18,30 +33,21 @@ pub async fn auth_middleware(
         return Ok(next.run(request).await);
     }
 
-    if !state.config.middleware.authentication.enabled {
+    let config = &state.config.middleware.authentication;
+    if !config.enabled {
         return Ok(next.run(request).await);
     }
 
-    // Extract Authorization header
-    let auth_header = headers
-        .get("Authorization")
-        .and_then(|h| h.to_str().ok())
-        .ok_or(StatusCode::UNAUTHORIZED)?;
-
     // Simple bearer token validation
-    if !auth_header.starts_with("Bearer ") {
-        return Err(StatusCode::UNAUTHORIZED);
-    }
-
-    let token = &auth_header[7..];
+    let token = bearer_token(&headers).ok_or(StatusCode::UNAUTHORIZED)?;
 
-    // In production, validate JWT token here
-    if token.is_empty() {
+    // Only the configured API keys are accepted. In production, validate JWT token here;
+    // for development, without configured keys any non-empty token is accepted
+    if !config.api_keys.is_empty() && !config.api_keys.iter().any(|key| key == token) {
         return Err(StatusCode::UNAUTHORIZED);
     }
 
-    // For development, accept any non-empty token
     tracing::debug!("Request authenticated");
 
     Ok(next.run(request).await)
-}
\ No newline at end of file
+}
@@ -1,6 +1,8 @@
 pub mod auth;
 pub mod rate_limit;
+pub mod request_id;
 
 // Re-export for convenience
 pub use auth::auth_middleware;
 pub use rate_limit::rate_limit_middleware;
+pub use request_id::request_id_middleware;
@@ -1,51 +1,168 @@
 use axum::{
-    extract::Request,
-    http::StatusCode,
+    extract::{ConnectInfo, Request, State},
+    http::{header, HeaderValue, StatusCode},
     middleware::Next,
-    response::Response,
+    response::{IntoResponse, Response},
 };
 use std::collections::HashMap;
+use std::net::{IpAddr, SocketAddr};
 use std::sync::Arc;
 use tokio::sync::Mutex;
-use std::time::{Duration, Instant};
+use std::time::Duration;
 
-// Simple in-memory rate limiter
-lazy_static::lazy_static! {
-    static ref RATE_LIMITER: Arc<Mutex<RateLimiter>> = Arc::new(Mutex::new(RateLimiter::new()));
+use crate::config::{RateLimitConfig, RateLimitKeyBy};
+use crate::middleware::auth::configured_api_key;
+use crate::AppState;
+
+/// Buckets that stay full for this many refill ticks are dropped
+const IDLE_TICKS_BEFORE_EVICTION: u32 = 300;
+
+#[derive(Debug, Clone, Copy)]
+struct BucketLimit {
+    capacity: f64,
+    tokens_per_sec: f64,
+}
+
+impl BucketLimit {
+    fn new(requests_per_minute: u32, burst: Option<u32>) -> Self {
+        Self {
+            capacity: burst.unwrap_or(requests_per_minute).max(1) as f64,
+            tokens_per_sec: requests_per_minute as f64 / 60.0,
+        }
+    }
+}
+
+#[derive(Debug)]
+struct TokenBucket {
+    tokens: f64,
+    limit: BucketLimit,
+    idle_ticks: u32,
 }
 
-struct RateLimiter {
-    requests: HashMap<String, Vec<Instant>>,
-    window: Duration,
-    max_requests: usize,
+/// Token-bucket rate limiter keyed by configured API key or client IP.
+/// Buckets are refilled by a background task (see [`RateLimiter::spawn_refill`]).
+pub struct RateLimiter {
+    buckets: Mutex<HashMap<String, TokenBucket>>,
+    default_limit: BucketLimit,
+    per_key: HashMap<String, BucketLimit>,
+    key_by: RateLimitKeyBy,
+    trusted_proxies: Vec<IpAddr>,
+    refill_interval: Duration,
 }
 
 impl RateLimiter {
-    fn new() -> Self {
+    pub fn new(config: &RateLimitConfig) -> Self {
+        let per_key = config
+            .per_key
+            .iter()
+            .map(|(key, limit)| (key.clone(), BucketLimit::new(limit.requests_per_minute, limit.burst)))
+            .collect();
+
         Self {
-            requests: HashMap::new(),
-            window: Duration::from_secs(60),
-            max_requests: 60,
+            buckets: Mutex::new(HashMap::new()),
+            default_limit: BucketLimit::new(config.requests_per_minute, config.burst),
+            per_key,
+            key_by: config.key_by,
+            trusted_proxies: config.trusted_proxies.clone(),
+            refill_interval: Duration::from_millis(config.refill_interval_ms.max(1)),
         }
     }
 
-    fn check_rate_limit(&mut self, key: &str) -> bool {
-        let now = Instant::now();
-        let requests = self.requests.entry(key.to_string()).or_insert_with(Vec::new);
+    /// Spawn the background task that tops up every bucket once per refill interval
+    pub fn spawn_refill(self: &Arc<Self>) -> tokio::task::JoinHandle<()> {
+        let limiter = Arc::clone(self);
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(limiter.refill_interval);
+            loop {
+                ticker.tick().await;
+                limiter.refill().await;
+            }
+        })
+    }
 
-        // Remove old requests outside the window
-        requests.retain(|&t| now.duration_since(t) < self.window);
+    async fn refill(&self) {
+        let elapsed = self.refill_interval.as_secs_f64();
+        let mut buckets = self.buckets.lock().await;
 
-        if requests.len() >= self.max_requests {
-            return false;
+        buckets.retain(|_, bucket| {
+            if bucket.tokens >= bucket.limit.capacity {
+                bucket.idle_ticks += 1;
+            } else {
+                bucket.tokens =
+                    (bucket.tokens + bucket.limit.tokens_per_sec * elapsed).min(bucket.limit.capacity);
+                bucket.idle_ticks = 0;
+            }
+            bucket.idle_ticks < IDLE_TICKS_BEFORE_EVICTION
+        });
+    }
+
+    /// Take a token for `key`. On rejection returns the number of seconds
+    /// until a token becomes available.
+    async fn try_acquire(&self, key: &str, api_key: Option<&str>) -> Result<(), u64> {
+        let limit = api_key
+            .and_then(|k| self.per_key.get(k))
+            .copied()
+            .unwrap_or(self.default_limit);
+
+        let mut buckets = self.buckets.lock().await;
+        let bucket = buckets.entry(key.to_string()).or_insert_with(|| TokenBucket {
+            tokens: limit.capacity,
+            limit,
+            idle_ticks: 0,
+        });
+
+        if bucket.tokens >= 1.0 {
+            bucket.tokens -= 1.0;
+            bucket.idle_ticks = 0;
+            return Ok(());
         }
 
-        requests.push(now);
-        true
+        let retry_after = if bucket.limit.tokens_per_sec > 0.0 {
+            ((1.0 - bucket.tokens) / bucket.limit.tokens_per_sec).ceil() as u64
+        } else {
+            60
+        };
+        Err(retry_after.max(1))
     }
 }
 
+/// Client IP of the request: the peer address, or if the peer is a trusted proxy, the rightmost
+/// X-Forwarded-For hop not of a trusted proxy. Proxies append the hops, so the ones left of it
+/// are set by the client and never used.
+fn client_ip(request: &Request, trusted_proxies: &[IpAddr]) -> String {
+    let Some(peer) = request
+        .extensions()
+        .get::<ConnectInfo<SocketAddr>>()
+        .map(|ConnectInfo(addr)| addr.ip())
+    else {
+        return "unknown".to_string();
+    };
+
+    if !trusted_proxies.contains(&peer) {
+        return peer.to_string();
+    }
+
+    let hops: Vec<&str> = request
+        .headers()
+        .get_all("x-forwarded-for")
+        .iter()
+        .filter_map(|h| h.to_str().ok())
+        .flat_map(|h| h.split(','))
+        .map(str::trim)
+        .collect();
+
+    // An unparsable hop falls back to the peer address
+    hops.iter()
+        .rev()
+        .map(|hop| hop.parse::<IpAddr>().ok())
+        .find(|hop| !hop.is_some_and(|ip| trusted_proxies.contains(&ip)))
+        .flatten()
+        .unwrap_or(peer)
+        .to_string()
+}
+
 pub async fn rate_limit_middleware(
+    State(state): State<AppState>,
     request: Request,
     next: Next,
 ) -> Result<Response, StatusCode> {
@@ -54,21 +171,100 @@ pub async fn rate_limit_middleware(
         return Ok(next.run(request).await);
     }
 
-    // Use IP address as rate limit key
-    let key = request
-        .headers()
-        .get("x-forwarded-for")
-        .and_then(|h| h.to_str().ok())
-        .unwrap_or("unknown")
-        .to_string();
-
-    let mut limiter = RATE_LIMITER.lock().await;
-    
-    if !limiter.check_rate_limit(&key) {
-        tracing::warn!("Rate limit exceeded for: {}", key);
-        return Err(StatusCode::TOO_MANY_REQUESTS);
+    if !state.config.middleware.rate_limiting.enabled {
+        return Ok(next.run(request).await);
+    }
+
+    let limiter = &state.rate_limiter;
+
+    // Key on the API key only if it's a configured one, so rotating bearer tokens doesn't
+    // get fresh buckets, falling back to client IP. Runs before auth, so requests failing
+    // auth are throttled too.
+    let api_key = match limiter.key_by {
+        RateLimitKeyBy::ApiKey => {
+            configured_api_key(&state.config.middleware.authentication, request.headers()).map(String::from)
+        }
+        RateLimitKeyBy::Ip => None,
+    };
+
+    let key = match &api_key {
+        Some(api_key) => format!("key:{}", api_key),
+        None => format!("ip:{}", client_ip(&request, &limiter.trusted_proxies)),
+    };
+
+    if let Err(retry_after) = limiter.try_acquire(&key, api_key.as_deref()).await {
+        // Never log the full API key
+        let label = match key.split_once(':') {
+            Some(("key", api_key)) => format!("key:{}...", api_key.chars().take(6).collect::<String>()),
+            _ => key.clone(),
+        };
+        tracing::warn!("Rate limit exceeded for: {} (retry after {}s)", label, retry_after);
+        let mut response = StatusCode::TOO_MANY_REQUESTS.into_response();
+        response
+            .headers_mut()
+            .insert(header::RETRY_AFTER, HeaderValue::from(retry_after));
+        return Ok(response);
     }
 
-    drop(limiter);
     Ok(next.run(request).await)
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::body::Body;
+
+    fn request(peer: &str, forwarded_for: &[&str]) -> Request {
+        let mut builder = axum::http::Request::builder().uri("/api/v1/agent/query");
+        for value in forwarded_for {
+            builder = builder.header("x-forwarded-for", *value);
+        }
+        let mut request = builder.body(Body::empty()).unwrap();
+        request
+            .extensions_mut()
+            .insert(ConnectInfo(SocketAddr::new(peer.parse().unwrap(), 40000)));
+        request
+    }
+
+    #[test]
+    fn test_client_ip_forwarded_for_only_from_trusted_proxies() {
+        let proxies: Vec<IpAddr> = vec!["10.0.0.1".parse().unwrap(), "10.0.0.2".parse().unwrap()];
+
+        // Untrusted peers can't pick their bucket
+        assert_eq!(client_ip(&request("203.0.113.7", &["198.51.100.1"]), &proxies), "203.0.113.7");
+        assert_eq!(client_ip(&request("203.0.113.7", &["198.51.100.1"]), &[]), "203.0.113.7");
+
+        // Behind trusted proxies, the hops the client forged are skipped
+        assert_eq!(
+            client_ip(&request("10.0.0.1", &["198.51.100.1, 203.0.113.7"]), &proxies),
+            "203.0.113.7",
+        );
+        assert_eq!(
+            client_ip(&request("10.0.0.1", &["198.51.100.1", "203.0.113.7, 10.0.0.2"]), &proxies),
+            "203.0.113.7",
+        );
+
+        // Without a usable hop the proxy address is the key
+        assert_eq!(client_ip(&request("10.0.0.1", &[]), &proxies), "10.0.0.1");
+        assert_eq!(client_ip(&request("10.0.0.1", &["not-an-ip"]), &proxies), "10.0.0.1");
+    }
+
+    #[tokio::test]
+    async fn test_per_key_limits() {
+        let config: RateLimitConfig = serde_json::from_value(serde_json::json!({
+            "enabled": true,
+            "requests_per_minute": 60,
+            "burst": 1,
+            "per_key": { "partner": { "requests_per_minute": 60, "burst": 2 } },
+        }))
+        .unwrap();
+        let limiter = RateLimiter::new(&config);
+
+        assert!(limiter.try_acquire("ip:203.0.113.7", None).await.is_ok());
+        assert_eq!(limiter.try_acquire("ip:203.0.113.7", None).await, Err(1));
+
+        assert!(limiter.try_acquire("key:partner", Some("partner")).await.is_ok());
+        assert!(limiter.try_acquire("key:partner", Some("partner")).await.is_ok());
+        assert!(limiter.try_acquire("key:partner", Some("partner")).await.is_err());
+    }
+}
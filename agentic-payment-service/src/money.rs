@@ -0,0 +1,301 @@
+//! Payment amounts as integer minor units of a currency with known decimals,
+//! e.g. cents of USD or wei of ETH, so amounts are never rounded by floating point math
+
+use serde::{Deserialize, Serialize};
+use serde_json::value::RawValue;
+use std::fmt;
+use thiserror::Error;
+
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum AmountError {
+    #[error("Unsupported currency: {0}")]
+    UnsupportedCurrency(String),
+
+    #[error("Amount must be a finite number")]
+    NotFinite,
+
+    #[error("Amount must not be negative: {0}")]
+    Negative(String),
+
+    #[error("Invalid amount: {0}")]
+    Invalid(String),
+
+    #[error("Amount {amount} has more than the {decimals} decimals of {currency}")]
+    NotRepresentable { amount: String, currency: String, decimals: u8 },
+
+    #[error("Amount {0} overflows")]
+    Overflow(String),
+}
+
+/// Decimals of the currency minor unit: ISO 4217 minor units of fiat currencies,
+/// base units of crypto currencies (satoshi, wei)
+pub fn currency_decimals(currency: &str) -> Option<u8> {
+    match currency {
+        "JPY" | "KRW" => Some(0),
+        "USD" | "EUR" | "GBP" | "CHF" | "CAD" | "AUD" | "CNY" | "INR" | "SGD" => Some(2),
+        "USDC" | "USDT" => Some(6),
+        "BTC" => Some(8),
+        "ETH" | "DAI" => Some(18),
+        _ => None,
+    }
+}
+
+/// Rounding of amounts with more decimals than the currency has
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Rounding {
+    /// Reject amounts not representable in minor units
+    Exact,
+    /// Round to the nearest minor unit, ties to the even one (banker's rounding)
+    #[default]
+    HalfEven,
+    /// Truncate the extra decimals
+    Down,
+}
+
+/// Non-negative amount in integer minor units of a supported currency
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(try_from = "RawMoney", into = "RawMoney")]
+pub struct Money {
+    minor_units: u128,
+    currency: String,
+}
+
+/// Serialized form, minor units as a decimal string as they may exceed the JSON safe integers
+#[derive(Serialize, Deserialize)]
+struct RawMoney {
+    minor_units: String,
+    currency: String,
+}
+
+impl TryFrom<RawMoney> for Money {
+    type Error = AmountError;
+
+    fn try_from(raw: RawMoney) -> Result<Self, Self::Error> {
+        let minor_units = raw.minor_units
+            .parse()
+            .map_err(|_| AmountError::Invalid(raw.minor_units.clone()))?;
+        Money::new(minor_units, &raw.currency)
+    }
+}
+
+impl From<Money> for RawMoney {
+    fn from(money: Money) -> Self {
+        Self {
+            minor_units: money.minor_units.to_string(),
+            currency: money.currency,
+        }
+    }
+}
+
+impl Money {
+    /// Amount in minor units of the currency, case-insensitive currency code
+    pub fn new(minor_units: u128, currency: &str) -> Result<Self, AmountError> {
+        let currency = currency.trim().to_uppercase();
+        currency_decimals(&currency).ok_or_else(|| AmountError::UnsupportedCurrency(currency.clone()))?;
+        Ok(Self { minor_units, currency })
+    }
+
+    /// Parse a decimal amount in major units (e.g. `"12.50"` USD), rounding the decimals
+    /// beyond the currency decimals with the rounding rule
+    pub fn from_decimal_str(amount: &str, currency: &str, rounding: Rounding) -> Result<Self, AmountError> {
+        let money = Self::new(0, currency)?;
+        let decimals = money.decimals() as usize;
+
+        let amount = amount.trim();
+        if amount.starts_with('-') {
+            return Err(AmountError::Negative(amount.to_string()));
+        }
+
+        let (integer, fraction) = amount.split_once('.').unwrap_or((amount, ""));
+        if (integer.is_empty() && fraction.is_empty())
+            || !integer.bytes().chain(fraction.bytes()).all(|byte| byte.is_ascii_digit())
+        {
+            return Err(AmountError::Invalid(amount.to_string()));
+        }
+
+        let (kept, dropped) = fraction.split_at(fraction.len().min(decimals));
+        let digits = format!("{}{:0<width$}", integer, kept, width = decimals);
+        let overflow = || AmountError::Overflow(amount.to_string());
+        let mut minor_units = if digits.is_empty() {
+            0
+        } else {
+            digits.parse::<u128>().map_err(|_| overflow())?
+        };
+
+        if dropped.bytes().any(|byte| byte != b'0') {
+            let round_up = match rounding {
+                Rounding::Exact => {
+                    return Err(AmountError::NotRepresentable {
+                        amount: amount.to_string(),
+                        currency: money.currency,
+                        decimals: decimals as u8,
+                    });
+                }
+                Rounding::Down => false,
+                Rounding::HalfEven => {
+                    let first_dropped = dropped.as_bytes()[0];
+                    let beyond_half = dropped[1..].bytes().any(|byte| byte != b'0');
+                    first_dropped > b'5' || (first_dropped == b'5' && (beyond_half || minor_units % 2 == 1))
+                }
+            };
+            if round_up {
+                minor_units = minor_units.checked_add(1).ok_or_else(overflow)?;
+            }
+        }
+
+        Ok(Self { minor_units, ..money })
+    }
+
+    /// Convert a floating point amount in major units, e.g. of the agent output. The amount
+    /// is first taken as its shortest decimal representation (so `0.1 + 0.2` is
+    /// `0.30000000000000004`, and `1.005` stays `1.005` rather than `1.00499999...`),
+    /// then rounded to the currency decimals with the rounding rule.
+    pub fn from_f64(amount: f64, currency: &str, rounding: Rounding) -> Result<Self, AmountError> {
+        if !amount.is_finite() {
+            return Err(AmountError::NotFinite);
+        }
+        if amount < 0.0 {
+            return Err(AmountError::Negative(amount.to_string()));
+        }
+
+        // `Display` of f64 never uses the exponent notation, `abs` turns -0.0 into 0.0
+        Self::from_decimal_str(&amount.abs().to_string(), currency, rounding)
+    }
+
+    pub fn minor_units(&self) -> u128 {
+        self.minor_units
+    }
+
+    /// Upper case currency code
+    pub fn currency(&self) -> &str {
+        &self.currency
+    }
+
+    pub fn decimals(&self) -> u8 {
+        currency_decimals(&self.currency).unwrap_or_default()
+    }
+
+    /// Exact decimal amount in major units, with all the currency decimals (e.g. `"12.50"`)
+    pub fn to_decimal_string(&self) -> String {
+        let decimals = self.decimals() as usize;
+        let digits = format!("{:0>width$}", self.minor_units, width = decimals + 1);
+        let (integer, fraction) = digits.split_at(digits.len() - decimals);
+
+        if fraction.is_empty() {
+            integer.to_string()
+        } else {
+            format!("{}.{}", integer, fraction)
+        }
+    }
+
+    /// Exact amount in major units as a JSON number (e.g. `12.50`), for the payloads
+    /// of protocols taking numeric amounts, written from the decimal digits instead of an `f64`
+    pub fn to_json_number(&self) -> Box<RawValue> {
+        RawValue::from_string(self.to_decimal_string())
+            .expect("decimal amount string is a valid JSON number")
+    }
+
+    /// Approximate amount in major units, for estimates and display only
+    pub fn to_f64_lossy(&self) -> f64 {
+        self.minor_units as f64 / 10f64.powi(self.decimals() as i32)
+    }
+}
+
+impl fmt::Display for Money {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", self.to_decimal_string(), self.currency)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn minor_units(amount: &str, currency: &str, rounding: Rounding) -> u128 {
+        Money::from_decimal_str(amount, currency, rounding).unwrap().minor_units()
+    }
+
+    #[test]
+    fn test_half_even_rounding() {
+        assert_eq!(minor_units("1.005", "USD", Rounding::HalfEven), 100);
+        assert_eq!(minor_units("1.015", "USD", Rounding::HalfEven), 102);
+        assert_eq!(minor_units("1.0051", "USD", Rounding::HalfEven), 101);
+        assert_eq!(minor_units("1.0049", "USD", Rounding::HalfEven), 100);
+        assert_eq!(minor_units("1.006", "USD", Rounding::HalfEven), 101);
+        assert_eq!(minor_units("2.5", "JPY", Rounding::HalfEven), 2);
+        assert_eq!(minor_units("3.5", "JPY", Rounding::HalfEven), 4);
+        assert_eq!(minor_units("0.0000001", "USDC", Rounding::HalfEven), 0);
+    }
+
+    #[test]
+    fn test_down_and_exact_rounding() {
+        assert_eq!(minor_units("1.009", "USD", Rounding::Down), 100);
+        assert_eq!(minor_units("12.5", "USD", Rounding::Exact), 1250);
+        assert_eq!(minor_units("12.500000", "USD", Rounding::Exact), 1250);
+        assert!(matches!(
+            Money::from_decimal_str("12.505", "USD", Rounding::Exact),
+            Err(AmountError::NotRepresentable { decimals: 2, .. })
+        ));
+    }
+
+    #[test]
+    fn test_decimal_str_validation() {
+        assert_eq!(minor_units("0.1", "eth", Rounding::Exact), 100_000_000_000_000_000);
+        assert_eq!(minor_units(".5", "USD", Rounding::Exact), 50);
+        assert_eq!(minor_units("7.", "USD", Rounding::Exact), 700);
+
+        assert!(matches!(Money::from_decimal_str("-1", "USD", Rounding::Exact), Err(AmountError::Negative(_))));
+        assert!(matches!(Money::from_decimal_str("1e3", "USD", Rounding::Exact), Err(AmountError::Invalid(_))));
+        assert!(matches!(Money::from_decimal_str(".", "USD", Rounding::Exact), Err(AmountError::Invalid(_))));
+        assert!(matches!(Money::from_decimal_str("1", "XYZ", Rounding::Exact), Err(AmountError::UnsupportedCurrency(_))));
+        assert!(matches!(
+            Money::from_decimal_str(&"9".repeat(40), "USD", Rounding::Exact),
+            Err(AmountError::Overflow(_))
+        ));
+        // Rounding up the largest amount overflows
+        let max = u128::MAX.to_string();
+        let (integer, fraction) = max.split_at(max.len() - 2);
+        assert!(matches!(
+            Money::from_decimal_str(&format!("{}.{}9", integer, fraction), "USD", Rounding::HalfEven),
+            Err(AmountError::Overflow(_))
+        ));
+    }
+
+    #[test]
+    fn test_from_f64() {
+        // Shortest representation, not the binary value 1.00499999...
+        assert_eq!(Money::from_f64(1.005, "USD", Rounding::HalfEven).unwrap().minor_units(), 100);
+        assert_eq!(Money::from_f64(0.1 + 0.2, "USD", Rounding::HalfEven).unwrap().minor_units(), 30);
+        assert!(Money::from_f64(0.1 + 0.2, "USD", Rounding::Exact).is_err());
+        assert_eq!(Money::from_f64(-0.0, "USD", Rounding::Exact).unwrap().minor_units(), 0);
+        assert_eq!(Money::from_f64(1e21, "JPY", Rounding::Exact).unwrap().minor_units(), 10u128.pow(21));
+
+        assert_eq!(Money::from_f64(f64::NAN, "USD", Rounding::HalfEven), Err(AmountError::NotFinite));
+        assert_eq!(Money::from_f64(f64::INFINITY, "USD", Rounding::HalfEven), Err(AmountError::NotFinite));
+        assert!(matches!(Money::from_f64(-0.01, "USD", Rounding::HalfEven), Err(AmountError::Negative(_))));
+    }
+
+    #[test]
+    fn test_decimal_string_and_json() {
+        let money = Money::new(1250, "usd").unwrap();
+        assert_eq!(money.to_decimal_string(), "12.50");
+        assert_eq!(money.to_string(), "12.50 USD");
+        assert_eq!(Money::new(5, "USD").unwrap().to_decimal_string(), "0.05");
+        assert_eq!(Money::new(42, "JPY").unwrap().to_decimal_string(), "42");
+        assert_eq!(Money::new(1, "ETH").unwrap().to_decimal_string(), "0.000000000000000001");
+
+        assert_eq!(money.to_json_number().get(), "12.50");
+        #[derive(Serialize)]
+        struct Payload {
+            amount: Box<RawValue>,
+        }
+        let payload = serde_json::to_string(&Payload { amount: money.to_json_number() }).unwrap();
+        assert_eq!(payload, r#"{"amount":12.50}"#);
+
+        let serialized = serde_json::to_value(&money).unwrap();
+        assert_eq!(serialized, serde_json::json!({ "minor_units": "1250", "currency": "USD" }));
+        assert_eq!(serde_json::from_value::<Money>(serialized).unwrap(), money);
+        assert!(serde_json::from_value::<Money>(serde_json::json!({ "minor_units": "-1", "currency": "USD" })).is_err());
+    }
+}
@@ -3,13 +3,17 @@ use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
+use crate::money::Money;
+
 pub mod web3;
 pub mod web2;
+pub mod store;
+pub mod webhook;
+pub mod reconciler;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct GatewayPaymentRequest {
-    pub amount: f64,
-    pub currency: String,
+    pub amount: Money,
     pub from: String,
     pub to: String,
     pub memo: Option<String>,
@@ -34,11 +38,19 @@ pub enum GatewayStatus {
     Failed,
 }
 
+impl GatewayStatus {
+    /// Confirmed or failed, no longer changing
+    pub fn is_terminal(&self) -> bool {
+        matches!(self, GatewayStatus::Confirmed | GatewayStatus::Failed)
+    }
+}
+
 #[async_trait]
 pub trait PaymentGateway: Send + Sync {
     async fn execute_payment(&self, request: GatewayPaymentRequest) -> Result<GatewayPaymentResponse>;
     async fn verify_transaction(&self, tx_hash: &str) -> Result<GatewayStatus>;
-    async fn estimate_fees(&self, amount: f64, currency: &str) -> Result<f64>;
+    /// Estimated fees in major units, approximate
+    async fn estimate_fees(&self, amount: &Money) -> Result<f64>;
     fn gateway_name(&self) -> &str;
 }
 
@@ -0,0 +1,243 @@
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::Instrument;
+
+use crate::config::ReconciliationConfig;
+use super::{store::PaymentStore, webhook::WebhookNotifier, PaymentGatewayManager};
+
+/// Re-checks the payments left pending after the gateway call against their gateway
+/// `verify_transaction`, so a missed confirmation still brings them to a terminal status
+pub struct PaymentReconciler {
+    store: Arc<PaymentStore>,
+    gateway_manager: Arc<PaymentGatewayManager>,
+    notifier: Arc<WebhookNotifier>,
+    interval: Duration,
+    stale_after: Duration,
+}
+
+impl PaymentReconciler {
+    pub fn new(
+        config: &ReconciliationConfig,
+        store: Arc<PaymentStore>,
+        gateway_manager: Arc<PaymentGatewayManager>,
+        notifier: Arc<WebhookNotifier>,
+    ) -> Self {
+        Self {
+            store,
+            gateway_manager,
+            notifier,
+            interval: Duration::from_secs(config.interval_secs.max(1)),
+            stale_after: Duration::from_secs(config.stale_after_secs),
+        }
+    }
+
+    /// Spawn the background task reconciling the stale pending payments once per interval
+    pub fn spawn(self) -> tokio::task::JoinHandle<()> {
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(self.interval);
+            ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+            loop {
+                ticker.tick().await;
+                self.reconcile().await;
+            }
+        })
+    }
+
+    /// Verify each stale pending payment with its gateway and update its status,
+    /// returning the number of payments whose status changed
+    pub async fn reconcile(&self) -> usize {
+        let Some(updated_before) = chrono::Duration::from_std(self.stale_after)
+            .ok()
+            .and_then(|stale_after| chrono::Utc::now().checked_sub_signed(stale_after))
+        else {
+            return 0;
+        };
+
+        let payments = self.store.stale_pending(updated_before).await;
+        if payments.is_empty() {
+            return 0;
+        }
+
+        tracing::debug!("Reconciling {} stale pending payments", payments.len());
+
+        let mut updated = 0;
+        for payment in payments {
+            let span = tracing::info_span!("reconcile", request_id = %payment.request_id);
+            let status = async {
+                let gateway = self.gateway_manager.get(&payment.gateway)?;
+                gateway.verify_transaction(&payment.gateway_tx_hash).await
+            }
+            .instrument(span.clone())
+            .await;
+
+            let status = match status {
+                Ok(status) => status,
+                Err(e) => {
+                    span.in_scope(|| tracing::warn!(
+                        "Failed to verify payment {} with gateway {}: {:#}",
+                        payment.transaction_id, payment.gateway, e
+                    ));
+                    continue;
+                }
+            };
+
+            if let Some(record) = self.store.update_status(&payment.transaction_id, status).await {
+                span.in_scope(|| tracing::info!(
+                    "Payment {} status {:?} -> {:?}",
+                    record.transaction_id, payment.status, record.status
+                ));
+                self.notifier
+                    .notify_status_change(&record, payment.status.clone())
+                    .instrument(span)
+                    .await;
+                updated += 1;
+            }
+        }
+
+        updated
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::WebhooksConfig;
+    use crate::money::Money;
+    use crate::payment::{
+        store::PaymentRecord, GatewayPaymentRequest, GatewayPaymentResponse, GatewayStatus, PaymentGateway,
+    };
+    use anyhow::{anyhow, Result};
+    use std::collections::HashMap;
+    use std::sync::Mutex;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    /// Gateway reporting fixed statuses of the known transactions, failing others
+    struct FixedStatusGateway {
+        statuses: HashMap<String, GatewayStatus>,
+    }
+
+    #[async_trait::async_trait]
+    impl PaymentGateway for FixedStatusGateway {
+        async fn execute_payment(&self, _request: GatewayPaymentRequest) -> Result<GatewayPaymentResponse> {
+            Err(anyhow!("Not supported"))
+        }
+
+        async fn verify_transaction(&self, tx_hash: &str) -> Result<GatewayStatus> {
+            self.statuses.get(tx_hash).cloned().ok_or_else(|| anyhow!("Unknown transaction {}", tx_hash))
+        }
+
+        async fn estimate_fees(&self, _amount: &Money) -> Result<f64> {
+            Ok(0.0)
+        }
+
+        fn gateway_name(&self) -> &str {
+            "fixed"
+        }
+    }
+
+    /// Local webhook endpoint collecting the received requests, headers and body
+    async fn serve_webhook() -> (String, Arc<Mutex<Vec<String>>>) {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/webhook", listener.local_addr().unwrap());
+        let requests = Arc::new(Mutex::new(Vec::new()));
+
+        let received = Arc::clone(&requests);
+        tokio::spawn(async move {
+            while let Ok((mut stream, _)) = listener.accept().await {
+                let mut request = Vec::new();
+                let mut buffer = [0u8; 4096];
+                loop {
+                    let read = stream.read(&mut buffer).await.unwrap_or(0);
+                    if read == 0 {
+                        break;
+                    }
+                    request.extend_from_slice(&buffer[..read]);
+
+                    let text = String::from_utf8_lossy(&request).to_string();
+                    if let Some(headers_end) = text.find("\r\n\r\n") {
+                        let content_length = text[..headers_end]
+                            .lines()
+                            .find_map(|line| line.to_lowercase().strip_prefix("content-length:").map(|value| value.trim().to_string()))
+                            .and_then(|value| value.parse::<usize>().ok())
+                            .unwrap_or(0);
+                        if request.len() >= headers_end + 4 + content_length {
+                            break;
+                        }
+                    }
+                }
+
+                received.lock().unwrap().push(String::from_utf8_lossy(&request).to_string());
+                let _ = stream.write_all(b"HTTP/1.1 200 OK\r\nContent-Length: 0\r\nConnection: close\r\n\r\n").await;
+            }
+        });
+
+        (url, requests)
+    }
+
+    fn pending_record(transaction_id: &str, age: chrono::Duration) -> PaymentRecord {
+        let at = chrono::Utc::now() - age;
+        PaymentRecord {
+            transaction_id: transaction_id.to_string(),
+            request_id: format!("request-{}", transaction_id),
+            protocol: "x402".to_string(),
+            gateway: "fixed".to_string(),
+            gateway_tx_hash: format!("tx-{}", transaction_id),
+            amount: Money::new(100, "USD").unwrap(),
+            status: GatewayStatus::Pending,
+            created_at: at,
+            updated_at: at,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_reconcile_stale_pending_payments() {
+        let store = Arc::new(PaymentStore::new(Duration::from_secs(3600)));
+        let stale = chrono::Duration::seconds(300);
+        store.insert(pending_record("confirmed", stale)).await;
+        store.insert(pending_record("still_pending", stale)).await;
+        store.insert(pending_record("unverifiable", stale)).await;
+        store.insert(pending_record("fresh", chrono::Duration::zero())).await;
+
+        let mut gateway_manager = PaymentGatewayManager::new();
+        gateway_manager.register("fixed", Box::new(FixedStatusGateway {
+            statuses: HashMap::from([
+                ("tx-confirmed".to_string(), GatewayStatus::Confirmed),
+                ("tx-still_pending".to_string(), GatewayStatus::Pending),
+                ("tx-fresh".to_string(), GatewayStatus::Failed),
+            ]),
+        }));
+
+        let (webhook_url, webhook_requests) = serve_webhook().await;
+        let notifier = WebhookNotifier::new(&WebhooksConfig { urls: vec![webhook_url], timeout_seconds: 5 }).unwrap();
+
+        let reconciler = PaymentReconciler::new(
+            &ReconciliationConfig { enabled: true, interval_secs: 60, stale_after_secs: 120 },
+            Arc::clone(&store),
+            Arc::new(gateway_manager),
+            Arc::new(notifier),
+        );
+
+        assert_eq!(reconciler.reconcile().await, 1);
+
+        assert_eq!(store.get("confirmed").await.unwrap().status, GatewayStatus::Confirmed);
+        // Unchanged and unverifiable payments stay pending, fresh ones aren't checked yet
+        assert_eq!(store.get("still_pending").await.unwrap().status, GatewayStatus::Pending);
+        assert_eq!(store.get("unverifiable").await.unwrap().status, GatewayStatus::Pending);
+        assert_eq!(store.get("fresh").await.unwrap().status, GatewayStatus::Pending);
+
+        {
+            let requests = webhook_requests.lock().unwrap();
+            assert_eq!(requests.len(), 1);
+            let request = requests[0].to_lowercase();
+            assert!(request.contains("x-request-id: request-confirmed"), "{}", request);
+            assert!(request.contains(r#""event":"payment.status_changed""#), "{}", request);
+            assert!(request.contains(r#""previous_status":"pending""#), "{}", request);
+            assert!(request.contains(r#""status":"confirmed""#), "{}", request);
+        }
+
+        // Only the unverifiable payment is still stale, the unchanged one was refreshed
+        assert_eq!(store.stale_pending(chrono::Utc::now() - chrono::Duration::seconds(120)).await.len(), 1);
+        assert_eq!(reconciler.reconcile().await, 0);
+        assert_eq!(webhook_requests.lock().unwrap().len(), 1);
+    }
+}
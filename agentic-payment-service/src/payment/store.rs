@@ -0,0 +1,163 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::time::Duration;
+use tokio::sync::RwLock;
+
+use crate::money::Money;
+use super::GatewayStatus;
+
+/// Payment executed through a gateway, tracked until its gateway status is terminal
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PaymentRecord {
+    /// Protocol transaction id
+    pub transaction_id: String,
+    /// Id of the request which executed the payment
+    pub request_id: String,
+    pub protocol: String,
+    pub gateway: String,
+    /// Gateway transaction hash, checked with the gateway `verify_transaction`
+    pub gateway_tx_hash: String,
+    pub amount: Money,
+    pub status: GatewayStatus,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// In-memory store of the executed payments, keyed by protocol transaction id.
+/// Payments are evicted once past the retention period, so the store stays bounded.
+pub struct PaymentStore {
+    payments: RwLock<HashMap<String, PaymentRecord>>,
+    retention: Duration,
+}
+
+impl PaymentStore {
+    pub fn new(retention: Duration) -> Self {
+        Self {
+            payments: RwLock::new(HashMap::new()),
+            retention,
+        }
+    }
+
+    pub async fn insert(&self, record: PaymentRecord) {
+        let mut payments = self.payments.write().await;
+        Self::evict_expired(&mut payments, self.retention, Utc::now());
+        payments.insert(record.transaction_id.clone(), record);
+    }
+
+    /// Drop terminal payments not updated for the retention period, and payments
+    /// still not terminal the retention period after their creation, which are given up on
+    fn evict_expired(payments: &mut HashMap<String, PaymentRecord>, retention: Duration, now: DateTime<Utc>) {
+        let Some(expired_before) = chrono::Duration::from_std(retention)
+            .ok()
+            .and_then(|retention| now.checked_sub_signed(retention))
+        else {
+            return;
+        };
+
+        payments.retain(|transaction_id, record| {
+            if record.status.is_terminal() {
+                return record.updated_at >= expired_before;
+            }
+            if record.created_at < expired_before {
+                tracing::warn!(
+                    request_id = %record.request_id,
+                    "Evicting payment {} still {:?} after the retention period", transaction_id, record.status
+                );
+                return false;
+            }
+            true
+        });
+    }
+
+    pub async fn get(&self, transaction_id: &str) -> Option<PaymentRecord> {
+        self.payments.read().await.get(transaction_id).cloned()
+    }
+
+    /// Non-terminal payments not updated since the given time
+    pub async fn stale_pending(&self, updated_before: DateTime<Utc>) -> Vec<PaymentRecord> {
+        self.payments
+            .read()
+            .await
+            .values()
+            .filter(|record| !record.status.is_terminal() && record.updated_at < updated_before)
+            .cloned()
+            .collect()
+    }
+
+    /// Set the payment status, returning the updated record if the status changed.
+    /// The update time is refreshed either way, so an unchanged payment is re-checked
+    /// only once stale again.
+    pub async fn update_status(&self, transaction_id: &str, status: GatewayStatus) -> Option<PaymentRecord> {
+        let mut payments = self.payments.write().await;
+        let record = payments.get_mut(transaction_id)?;
+
+        record.updated_at = Utc::now();
+        if record.status == status {
+            return None;
+        }
+
+        record.status = status;
+        Some(record.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::money::Money;
+
+    fn record(transaction_id: &str, status: GatewayStatus, age: chrono::Duration) -> PaymentRecord {
+        let at = Utc::now() - age;
+        PaymentRecord {
+            transaction_id: transaction_id.to_string(),
+            request_id: format!("request-{}", transaction_id),
+            protocol: "x402".to_string(),
+            gateway: "web2".to_string(),
+            gateway_tx_hash: format!("tx-{}", transaction_id),
+            amount: Money::new(100, "USD").unwrap(),
+            status,
+            created_at: at,
+            updated_at: at,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_stale_pending_and_status_update() {
+        let store = PaymentStore::new(Duration::from_secs(3600));
+        store.insert(record("stale", GatewayStatus::Pending, chrono::Duration::seconds(300))).await;
+        store.insert(record("fresh", GatewayStatus::Pending, chrono::Duration::zero())).await;
+        store.insert(record("done", GatewayStatus::Confirmed, chrono::Duration::seconds(300))).await;
+
+        let stale = store.stale_pending(Utc::now() - chrono::Duration::seconds(120)).await;
+        assert_eq!(stale.len(), 1);
+        assert_eq!(stale[0].transaction_id, "stale");
+
+        // Unchanged status only refreshes the update time
+        assert!(store.update_status("stale", GatewayStatus::Pending).await.is_none());
+        assert!(store.stale_pending(Utc::now() - chrono::Duration::seconds(120)).await.is_empty());
+
+        let updated = store.update_status("stale", GatewayStatus::Confirmed).await.unwrap();
+        assert_eq!(updated.status, GatewayStatus::Confirmed);
+        assert_eq!(store.get("stale").await.unwrap().status, GatewayStatus::Confirmed);
+        assert!(store.update_status("missing", GatewayStatus::Failed).await.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_expired_payments_evicted() {
+        let store = PaymentStore::new(Duration::from_secs(3600));
+        let expired = chrono::Duration::seconds(7200);
+
+        store.insert(record("old_terminal", GatewayStatus::Failed, expired)).await;
+        store.insert(record("old_pending", GatewayStatus::Pending, expired)).await;
+        store.insert(record("recent", GatewayStatus::Confirmed, chrono::Duration::seconds(60))).await;
+
+        // Expired payments are evicted on the next insert
+        store.insert(record("new", GatewayStatus::Initiated, chrono::Duration::zero())).await;
+
+        assert!(store.get("old_terminal").await.is_none());
+        assert!(store.get("old_pending").await.is_none());
+        assert!(store.get("recent").await.is_some());
+        assert!(store.get("new").await.is_some());
+    }
+}
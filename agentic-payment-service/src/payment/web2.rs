@@ -3,6 +3,7 @@ use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
 
 use crate::config::Web2Config;
+use crate::money::Money;
 use super::{PaymentGateway, GatewayPaymentRequest, GatewayPaymentResponse, GatewayStatus};
 
 /// Web2 Payment Gateway
@@ -94,20 +95,25 @@ impl Web2Gateway {
         }
     }
 
-    fn to_cents(amount: f64) -> i64 {
-        (amount * 100.0) as i64
+    /// Fee of the typical Stripe fee structure: 2.9% + 0.30
+    fn fee(amount: &Money) -> f64 {
+        amount.to_f64_lossy() * 0.029 + 0.30
     }
 }
 
 #[async_trait]
 impl PaymentGateway for Web2Gateway {
     async fn execute_payment(&self, request: GatewayPaymentRequest) -> Result<GatewayPaymentResponse> {
-        tracing::info!("Executing Web2 payment via {}: {} {}",
-            self.config.provider, request.amount, request.currency);
+        tracing::info!("Executing Web2 payment via {}: {}",
+            self.config.provider, request.amount);
+
+        // Provider amounts are in the currency minor units, e.g. cents, or yen for zero-decimal currencies
+        let amount = i64::try_from(request.amount.minor_units())
+            .map_err(|_| anyhow::anyhow!("Amount {} exceeds the provider amount range", request.amount))?;
 
         let payment_intent = StripePaymentIntent {
-            amount: Self::to_cents(request.amount),
-            currency: request.currency.to_lowercase(),
+            amount,
+            currency: request.amount.currency().to_lowercase(),
             payment_method_types: vec!["card".to_string()],
             metadata: request.metadata.clone(),
         };
@@ -123,7 +129,7 @@ impl PaymentGateway for Web2Gateway {
             status: Self::map_status(&result.status),
             confirmation_url,
             estimated_completion: Some("Instant".to_string()),
-            fees: Some(request.amount * 0.029 + 0.30), // Stripe's typical fee
+            fees: Some(Self::fee(&request.amount)),
         })
     }
 
@@ -132,9 +138,8 @@ impl PaymentGateway for Web2Gateway {
         Ok(Self::map_status(&payment.status))
     }
 
-    async fn estimate_fees(&self, amount: f64, _currency: &str) -> Result<f64> {
-        // Typical Stripe fee structure: 2.9% + $0.30
-        Ok(amount * 0.029 + 0.30)
+    async fn estimate_fees(&self, amount: &Money) -> Result<f64> {
+        Ok(Self::fee(amount))
     }
 
     fn gateway_name(&self) -> &str {
@@ -3,6 +3,7 @@ use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
 
 use crate::config::Web3Config;
+use crate::money::Money;
 use super::{PaymentGateway, GatewayPaymentRequest, GatewayPaymentResponse, GatewayStatus};
 
 /// Web3 Payment Gateway
@@ -117,8 +118,12 @@ impl Web3Gateway {
         })
     }
 
-    fn wei_to_eth(wei: f64) -> String {
-        format!("0x{:x}", (wei * 1e18) as u128)
+    /// Transaction value in wei of a native ETH amount
+    fn wei_value(amount: &Money) -> Result<String> {
+        if amount.currency() != "ETH" {
+            anyhow::bail!("Web3 gateway transfers native ETH only, got {}", amount.currency());
+        }
+        Ok(format!("0x{:x}", amount.minor_units()))
     }
 
     fn map_status(status: &str) -> GatewayStatus {
@@ -134,13 +139,13 @@ impl Web3Gateway {
 #[async_trait]
 impl PaymentGateway for Web3Gateway {
     async fn execute_payment(&self, request: GatewayPaymentRequest) -> Result<GatewayPaymentResponse> {
-        tracing::info!("Executing Web3 payment: {} {} from {} to {}",
-            request.amount, request.currency, request.from, request.to);
+        tracing::info!("Executing Web3 payment: {} from {} to {}",
+            request.amount, request.from, request.to);
 
         let tx = Web3TransactionRequest {
             from: request.from.clone(),
             to: request.to.clone(),
-            value: Self::wei_to_eth(request.amount),
+            value: Self::wei_value(&request.amount)?,
             gas: format!("0x{:x}", self.config.gas_limit),
             gas_price: None,  // Use network default
             data: None,
@@ -150,7 +155,7 @@ impl PaymentGateway for Web3Gateway {
         let result = self.send_transaction(tx).await?;
         
         // Estimate fees (simplified)
-        let fees = self.estimate_fees(request.amount, &request.currency).await?;
+        let fees = self.estimate_fees(&request.amount).await?;
 
         Ok(GatewayPaymentResponse {
             transaction_hash: result.tx_hash,
@@ -166,7 +171,7 @@ impl PaymentGateway for Web3Gateway {
         Ok(Self::map_status(&receipt.status))
     }
 
-    async fn estimate_fees(&self, _amount: f64, _currency: &str) -> Result<f64> {
+    async fn estimate_fees(&self, _amount: &Money) -> Result<f64> {
         // Simplified fee estimation
         // In production, query gas price and calculate: gasPrice * gasLimit
         let rpc_request = serde_json::json!({
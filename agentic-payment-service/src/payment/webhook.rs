@@ -0,0 +1,62 @@
+use anyhow::{Context, Result};
+use serde::Serialize;
+use std::time::Duration;
+
+use crate::config::WebhooksConfig;
+use super::{store::PaymentRecord, GatewayStatus};
+
+/// Payment status change event, posted as JSON to the webhook URLs
+#[derive(Debug, Serialize)]
+pub struct PaymentStatusEvent<'a> {
+    pub event: &'static str,
+    pub previous_status: GatewayStatus,
+    pub payment: &'a PaymentRecord,
+}
+
+/// Notifies the configured webhook URLs of payment status changes
+pub struct WebhookNotifier {
+    client: reqwest::Client,
+    urls: Vec<String>,
+}
+
+impl WebhookNotifier {
+    pub fn new(config: &WebhooksConfig) -> Result<Self> {
+        let client = reqwest::Client::builder()
+            .timeout(Duration::from_secs(config.timeout_seconds))
+            .build()?;
+
+        Ok(Self {
+            client,
+            urls: config.urls.clone(),
+        })
+    }
+
+    /// Post the status change to every webhook URL. Failed deliveries are logged, not retried.
+    pub async fn notify_status_change(&self, payment: &PaymentRecord, previous_status: GatewayStatus) {
+        let event = PaymentStatusEvent {
+            event: "payment.status_changed",
+            previous_status,
+            payment,
+        };
+
+        for url in &self.urls {
+            if let Err(e) = self.post(url, &event).await {
+                tracing::warn!("Webhook {} failed for payment {}: {:#}", url, payment.transaction_id, e);
+            }
+        }
+    }
+
+    async fn post(&self, url: &str, event: &PaymentStatusEvent<'_>) -> Result<()> {
+        self.client
+            .post(url)
+            .header("X-Request-Id", &event.payment.request_id)
+            .json(event)
+            .send()
+            .await
+            .context("Failed to send webhook request")?
+            .error_for_status()
+            .context("Webhook request rejected")?;
+
+        Ok(())
+    }
+}
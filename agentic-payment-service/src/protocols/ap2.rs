@@ -1,6 +1,7 @@
 use anyhow::{Context, Result};
 use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
+use serde_json::value::RawValue;
 use std::time::Duration;
 
 use crate::config::AP2Config;
@@ -23,7 +24,8 @@ struct AP2TransactionRequest {
     protocol_version: String,
     transaction_type: String,  // "direct", "escrow", "batch"
     parties: Vec<AP2Party>,
-    amount: f64,
+    /// Exact amount in major units as a JSON number
+    amount: Box<RawValue>,
     currency: String,
     conditions: Option<Vec<String>>,
     expiry: Option<String>,
@@ -34,7 +36,7 @@ struct AP2TransactionRequest {
 struct AP2Party {
     party_id: String,
     role: String,  // "sender", "recipient", "intermediary"
-    amount: Option<f64>,
+    amount: Option<Box<RawValue>>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -101,12 +103,12 @@ impl PaymentProtocol for AP2Protocol {
             AP2Party {
                 party_id: request.sender.clone(),
                 role: "sender".to_string(),
-                amount: Some(request.amount),
+                amount: Some(request.amount.to_json_number()),
             },
             AP2Party {
                 party_id: request.recipient.clone(),
                 role: "recipient".to_string(),
-                amount: Some(request.amount),
+                amount: Some(request.amount.to_json_number()),
             },
         ];
 
@@ -114,8 +116,8 @@ impl PaymentProtocol for AP2Protocol {
             protocol_version: self.config.version.clone(),
             transaction_type: "direct".to_string(),
             parties,
-            amount: request.amount,
-            currency: request.currency.clone(),
+            amount: request.amount.to_json_number(),
+            currency: request.amount.currency().to_string(),
             conditions: None,
             expiry: None,
             metadata: request.metadata.clone(),
@@ -3,14 +3,15 @@ use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
+use crate::money::Money;
+
 pub mod x402;
 pub mod ap2;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PaymentRequest {
     pub id: String,
-    pub amount: f64,
-    pub currency: String,
+    pub amount: Money,
     pub sender: String,
     pub recipient: String,
     pub memo: Option<String>,
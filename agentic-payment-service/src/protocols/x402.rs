@@ -1,6 +1,7 @@
 use anyhow::{Context, Result};
 use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
+use serde_json::value::RawValue;
 use std::time::Duration;
 
 use crate::config::X402Config;
@@ -19,7 +20,8 @@ pub struct X402Protocol {
 #[derive(Debug, Serialize, Deserialize)]
 struct X402PaymentPayload {
     version: String,
-    amount: f64,
+    /// Exact amount in major units as a JSON number
+    amount: Box<RawValue>,
     currency: String,
     sender_id: String,
     recipient_id: String,
@@ -88,8 +90,8 @@ impl PaymentProtocol for X402Protocol {
 
         let payload = X402PaymentPayload {
             version: self.config.version.clone(),
-            amount: request.amount,
-            currency: request.currency.clone(),
+            amount: request.amount.to_json_number(),
+            currency: request.amount.currency().to_string(),
             sender_id: request.sender.clone(),
             recipient_id: request.recipient.clone(),
             memo: request.memo.clone(),
@@ -154,4 +154,33 @@ async fn test_authentication_required() -> Result<()> {
     assert_eq!(response.status(), 401); // Unauthorized
     
     Ok(())
-}
\ No newline at end of file
+}
+
+#[tokio::test]
+async fn test_request_id_correlation() -> Result<()> {
+    let client = reqwest::Client::new();
+
+    let prompt_response = client
+        .post("http://localhost:8080/api/v1/payment/prompt")
+        .header("Authorization", "Bearer test-token")
+        .header("X-Request-Id", "test-correlation-id")
+        .json(&json!({ "prompt": "Transfer $50 to bob@example.com" }))
+        .send()
+        .await?;
+
+    assert_eq!(prompt_response.headers()["x-request-id"], "test-correlation-id");
+    let prompt_body: serde_json::Value = prompt_response.json().await?;
+    assert_eq!(prompt_body["request_id"], "test-correlation-id");
+
+    // Generated when not provided, also on rejected requests
+    let response = client
+        .post("http://localhost:8080/api/v1/agent/query")
+        .json(&json!({ "query": "Test query" }))
+        .send()
+        .await?;
+
+    assert_eq!(response.status(), 401);
+    assert!(response.headers().get("x-request-id").is_some());
+
+    Ok(())
+}
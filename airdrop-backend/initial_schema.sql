@@ -22,6 +22,7 @@ CREATE TABLE eligibility_records (
     address BYTEA NOT NULL,
     amount NUMERIC(78, 0) NOT NULL, -- Support for U256 values
     round_id INTEGER NOT NULL,
+    leaf_data BYTEA, -- Packed trie leaf of leaf schema rounds
     created_at TIMESTAMP WITH TIME ZONE DEFAULT NOW(),
     UNIQUE(address, round_id),
     FOREIGN KEY (round_id) REFERENCES trie_states(round_id) ON DELETE CASCADE
@@ -0,0 +1,293 @@
+use alloy_primitives::{Address, Signature};
+use axum::{
+    body::Body,
+    extract::{Request, State},
+    middleware::Next,
+    response::Response,
+};
+use sha2::{Digest, Sha256};
+use std::collections::{HashMap, HashSet};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tracing::{debug, warn};
+
+use crate::config::{AuthConfig, ADMIN_API_KEY_ENV, DEFAULT_MAX_SIGNATURE_AGE_SECS};
+use crate::csv_processor::CsvProcessor;
+use crate::error::{AppError, AppResult};
+
+/// EIP-191 signature of the signed request message, hex encoded
+pub const SIGNATURE_HEADER: &str = "X-Signature";
+/// Unix timestamp (seconds) of the signed request
+pub const TIMESTAMP_HEADER: &str = "X-Timestamp";
+/// Unique value of the signed request, so each signed request is accepted once
+pub const NONCE_HEADER: &str = "X-Nonce";
+
+const MAX_NONCE_LENGTH: usize = 128;
+
+/// Admin authentication of the mutating endpoints: a shared secret bearer token,
+/// or an EIP-191 signed request from an allowlisted admin address
+pub struct AdminAuth {
+    api_key: Option<String>,
+    admin_addresses: HashSet<Address>,
+    max_signature_age: Duration,
+    max_body_size: usize,
+    /// Nonces of the accepted signed requests by signer, with the timestamp their request expires at.
+    /// Requests are accepted within the signature age window only, so expired nonces are dropped.
+    used_nonces: Mutex<HashMap<(Address, String), u64>>,
+}
+
+/// Signed request parts, as received in the request and its headers
+pub struct SignedRequest<'a> {
+    pub method: &'a str,
+    pub path: &'a str,
+    pub timestamp: &'a str,
+    pub nonce: &'a str,
+    pub body: &'a [u8],
+    pub signature: &'a str,
+}
+
+impl AdminAuth {
+    pub fn from_config(config: &AuthConfig, max_body_size: usize) -> anyhow::Result<Self> {
+        let api_key = config.resolve_api_key();
+
+        let admin_addresses = config.admin_addresses
+            .iter()
+            .map(|address| CsvProcessor::parse_address(address)
+                .map_err(|e| anyhow::anyhow!("Invalid admin address '{}': {}", address, e)))
+            .collect::<anyhow::Result<HashSet<Address>>>()?;
+
+        if api_key.is_none() && admin_addresses.is_empty() {
+            anyhow::bail!("Authentication is enabled without an API key or admin addresses");
+        }
+
+        Ok(Self {
+            api_key,
+            admin_addresses,
+            max_signature_age: Duration::from_secs(
+                config.max_signature_age_secs.unwrap_or(DEFAULT_MAX_SIGNATURE_AGE_SECS)
+            ),
+            max_body_size,
+            used_nonces: Mutex::new(HashMap::new()),
+        })
+    }
+
+    fn verify_api_key(&self, token: &str) -> AppResult<()> {
+        match &self.api_key {
+            Some(api_key) if constant_time_eq(api_key.as_bytes(), token.as_bytes()) => Ok(()),
+            _ => Err(AppError::Unauthorized("Invalid API key".to_string())),
+        }
+    }
+
+    /// Recover the signer of the request, check it's an allowlisted admin address
+    /// and that the request nonce wasn't used already, to reject replayed requests
+    fn verify_signature(&self, request: &SignedRequest, now: u64) -> AppResult<Address> {
+        let signed_at: u64 = request.timestamp.parse()
+            .map_err(|_| AppError::Unauthorized(format!("Invalid {} header", TIMESTAMP_HEADER)))?;
+        if now.abs_diff(signed_at) > self.max_signature_age.as_secs() {
+            return Err(AppError::Unauthorized("Signed request expired".to_string()));
+        }
+
+        if request.nonce.is_empty() || request.nonce.len() > MAX_NONCE_LENGTH {
+            return Err(AppError::Unauthorized(format!("Invalid {} header", NONCE_HEADER)));
+        }
+
+        let signature_bytes = hex::decode(request.signature.trim_start_matches("0x"))
+            .map_err(|_| AppError::Unauthorized(format!("Invalid {} header", SIGNATURE_HEADER)))?;
+        let signature = Signature::try_from(signature_bytes.as_slice())
+            .map_err(|_| AppError::Unauthorized(format!("Invalid {} header", SIGNATURE_HEADER)))?;
+
+        let message = signed_message(request.method, request.path, signed_at, request.nonce, request.body);
+        let signer = signature.recover_address_from_msg(message.as_bytes())
+            .map_err(|_| AppError::Unauthorized("Invalid request signature".to_string()))?;
+
+        if !self.admin_addresses.contains(&signer) {
+            return Err(AppError::Forbidden(format!("Address {} is not an admin address", signer)));
+        }
+
+        let mut used_nonces = self.used_nonces.lock().unwrap_or_else(|e| e.into_inner());
+        used_nonces.retain(|_, expires_at| *expires_at >= now);
+        let expires_at = signed_at + self.max_signature_age.as_secs();
+        if used_nonces.insert((signer, request.nonce.to_string()), expires_at).is_some() {
+            return Err(AppError::Unauthorized("Signed request already used".to_string()));
+        }
+
+        Ok(signer)
+    }
+}
+
+/// Message signed by admins with EIP-191 `personal_sign`: the request method, path with query,
+/// timestamp, nonce and SHA-256 hex digest of the body, newline separated
+pub fn signed_message(method: &str, path: &str, timestamp: u64, nonce: &str, body: &[u8]) -> String {
+    format!("{}\n{}\n{}\n{}\n{}", method, path, timestamp, nonce, hex::encode(Sha256::digest(body)))
+}
+
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0u8, |diff, (x, y)| diff | (x ^ y)) == 0
+}
+
+/// Middleware of the mutating routes: `401 Unauthorized` without valid credentials,
+/// `403 Forbidden` for requests signed by an address not in the admin allowlist.
+/// Passes all requests with authentication disabled.
+pub async fn require_admin(
+    State(auth): State<Option<Arc<AdminAuth>>>,
+    request: Request,
+    next: Next,
+) -> Result<Response, AppError> {
+    let Some(auth) = auth else {
+        return Ok(next.run(request).await);
+    };
+
+    let headers = request.headers();
+
+    if let Some(authorization) = headers.get(axum::http::header::AUTHORIZATION) {
+        let token = authorization
+            .to_str()
+            .ok()
+            .and_then(|value| value.strip_prefix("Bearer "))
+            .ok_or_else(|| AppError::Unauthorized("Expected a Bearer token".to_string()))?;
+        auth.verify_api_key(token)?;
+
+        debug!("Admin request authenticated by API key");
+        return Ok(next.run(request).await);
+    }
+
+    let header = |name: &str| headers
+        .get(name)
+        .and_then(|value| value.to_str().ok())
+        .map(str::to_string)
+        .ok_or_else(|| AppError::Unauthorized(format!("Missing Authorization or {} header", name)));
+    let signature = header(SIGNATURE_HEADER)?;
+    let timestamp = header(TIMESTAMP_HEADER)?;
+    let nonce = header(NONCE_HEADER)?;
+
+    // The body is part of the signed message, so it's buffered and handed on to the handler
+    let (parts, body) = request.into_parts();
+    let body = axum::body::to_bytes(body, auth.max_body_size)
+        .await
+        .map_err(|e| AppError::PayloadTooLarge(format!("Failed to read request body: {}", e)))?;
+
+    let path = parts.uri.path_and_query().map(|path| path.as_str()).unwrap_or("/");
+    let now = chrono::Utc::now().timestamp().max(0) as u64;
+
+    let signed_request = SignedRequest {
+        method: parts.method.as_str(),
+        path,
+        timestamp: &timestamp,
+        nonce: &nonce,
+        body: &body,
+        signature: &signature,
+    };
+    let admin = auth
+        .verify_signature(&signed_request, now)
+        .inspect_err(|e| warn!("Rejected admin request {} {}: {}", parts.method, path, e))?;
+
+    debug!("Admin request signed by {}", admin);
+    Ok(next.run(Request::from_parts(parts, Body::from(body))).await)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_signer::SignerSync;
+    use alloy_signer_local::PrivateKeySigner;
+
+    const NOW: u64 = 1_700_000_000;
+
+    fn admin_auth(admin: Address) -> AdminAuth {
+        AdminAuth::from_config(&AuthConfig {
+            enabled: true,
+            api_key: Some("secret".to_string()),
+            admin_addresses: vec![admin.to_checksum(None)],
+            max_signature_age_secs: Some(60),
+        }, 1024).unwrap()
+    }
+
+    fn sign(signer: &PrivateKeySigner, timestamp: u64, nonce: &str, body: &[u8]) -> String {
+        let message = signed_message("POST", "/api/v1/submit-trie/1", timestamp, nonce, body);
+        format!("0x{}", hex::encode(signer.sign_message_sync(message.as_bytes()).unwrap().as_bytes()))
+    }
+
+    #[test]
+    fn test_api_key() {
+        let auth = admin_auth(Address::ZERO);
+
+        assert!(auth.verify_api_key("secret").is_ok());
+        assert!(matches!(auth.verify_api_key("secrets"), Err(AppError::Unauthorized(_))));
+        assert!(matches!(auth.verify_api_key(""), Err(AppError::Unauthorized(_))));
+    }
+
+    #[test]
+    fn test_signed_request() {
+        let admin = PrivateKeySigner::random();
+        let auth = admin_auth(admin.address());
+        let verify = |timestamp: u64, nonce: &str, body: &[u8], signature: &str| auth.verify_signature(
+            &SignedRequest {
+                method: "POST",
+                path: "/api/v1/submit-trie/1",
+                timestamp: &timestamp.to_string(),
+                nonce,
+                body,
+                signature,
+            },
+            NOW,
+        );
+
+        let signature = sign(&admin, NOW, "1", b"{}");
+        assert_eq!(verify(NOW, "1", b"{}", &signature).unwrap(), admin.address());
+
+        // Tampered body or nonce recovers another address
+        assert!(matches!(verify(NOW, "1", b"{\"dry_run\":true}", &signature), Err(AppError::Forbidden(_))));
+        assert!(matches!(verify(NOW, "2", b"{}", &signature), Err(AppError::Forbidden(_))));
+
+        // Expired signature
+        let stale = sign(&admin, NOW - 120, "3", b"{}");
+        assert!(matches!(verify(NOW - 120, "3", b"{}", &stale), Err(AppError::Unauthorized(_))));
+
+        // Signer not in the allowlist
+        let other = sign(&PrivateKeySigner::random(), NOW, "4", b"{}");
+        assert!(matches!(verify(NOW, "4", b"{}", &other), Err(AppError::Forbidden(_))));
+
+        assert!(matches!(verify(NOW, "5", b"{}", "0x1234"), Err(AppError::Unauthorized(_))));
+        assert!(matches!(verify(NOW, "", b"{}", &sign(&admin, NOW, "", b"{}")), Err(AppError::Unauthorized(_))));
+    }
+
+    #[test]
+    fn test_signed_request_replay() {
+        let admin = PrivateKeySigner::random();
+        let auth = admin_auth(admin.address());
+        let verify = |timestamp: u64, nonce: &str, now: u64| auth.verify_signature(
+            &SignedRequest {
+                method: "POST",
+                path: "/api/v1/submit-trie/1",
+                timestamp: &timestamp.to_string(),
+                nonce,
+                body: b"{}",
+                signature: &sign(&admin, timestamp, nonce, b"{}"),
+            },
+            now,
+        );
+
+        assert!(verify(NOW, "1", NOW).is_ok());
+        assert!(matches!(verify(NOW, "1", NOW + 30), Err(AppError::Unauthorized(_))));
+        assert!(verify(NOW, "2", NOW + 30).is_ok());
+
+        // Nonces are kept until their requests expire
+        assert!(verify(NOW + 90, "1", NOW + 90).is_ok());
+        assert_eq!(auth.used_nonces.lock().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_config_without_credentials_rejected() {
+        let config = AuthConfig {
+            enabled: true,
+            api_key: None,
+            admin_addresses: vec![],
+            max_signature_age_secs: None,
+        };
+
+        // Unless provided by the environment variable
+        if std::env::var(ADMIN_API_KEY_ENV).is_err() {
+            assert!(AdminAuth::from_config(&config, 1024).is_err());
+        }
+    }
+}
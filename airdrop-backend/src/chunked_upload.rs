@@ -0,0 +1,145 @@
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+use serde::Serialize;
+use tokio::sync::Mutex;
+
+use crate::config::ChunkedUploadConfig;
+use crate::error::{AppError, AppResult};
+
+#[derive(Debug)]
+struct PendingUpload {
+    round_id: u32,
+    data: Vec<u8>,
+    next_chunk_index: u32,
+    last_activity: Instant,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct UploadStatus {
+    pub upload_id: String,
+    pub round_id: u32,
+    pub next_chunk_index: u32,
+    pub received_bytes: usize,
+    pub max_chunk_size: usize,
+    pub expires_in_secs: u64,
+}
+
+/// Partially uploaded CSV files, kept in memory until finalized or expired
+pub struct ChunkedUploads {
+    uploads: Mutex<HashMap<String, PendingUpload>>,
+    ttl: Duration,
+    max_chunk_size: usize,
+    max_upload_size: usize,
+}
+
+impl ChunkedUploads {
+    pub fn new(config: &ChunkedUploadConfig) -> Self {
+        Self {
+            uploads: Mutex::new(HashMap::new()),
+            ttl: Duration::from_secs(config.ttl_secs),
+            max_chunk_size: config.max_chunk_size as usize,
+            max_upload_size: config.max_upload_size as usize,
+        }
+    }
+
+    pub fn ttl(&self) -> Duration {
+        self.ttl
+    }
+
+    fn status(&self, upload_id: &str, upload: &PendingUpload) -> UploadStatus {
+        UploadStatus {
+            upload_id: upload_id.to_string(),
+            round_id: upload.round_id,
+            next_chunk_index: upload.next_chunk_index,
+            received_bytes: upload.data.len(),
+            max_chunk_size: self.max_chunk_size,
+            expires_in_secs: self.ttl.saturating_sub(upload.last_activity.elapsed()).as_secs(),
+        }
+    }
+
+    /// Get a live upload, expired uploads are removed and reported as not found
+    fn get_live<'a>(
+        &self,
+        uploads: &'a mut HashMap<String, PendingUpload>,
+        upload_id: &str,
+    ) -> AppResult<&'a mut PendingUpload> {
+        if uploads.get(upload_id).is_some_and(|upload| upload.last_activity.elapsed() > self.ttl) {
+            uploads.remove(upload_id);
+        }
+
+        uploads.get_mut(upload_id)
+            .ok_or_else(|| AppError::NotFound(format!("Upload {} not found or expired", upload_id)))
+    }
+
+    pub async fn init(&self, round_id: u32) -> UploadStatus {
+        let upload_id = uuid::Uuid::new_v4().to_string();
+        let upload = PendingUpload {
+            round_id,
+            data: Vec::new(),
+            next_chunk_index: 0,
+            last_activity: Instant::now(),
+        };
+
+        let status = self.status(&upload_id, &upload);
+        self.uploads.lock().await.insert(upload_id.clone(), upload);
+
+        tracing::info!("Started chunked CSV upload {} for round {}", upload_id, round_id);
+        status
+    }
+
+    /// Append a chunk, chunks must arrive in order. Resending an already received chunk is a no-op,
+    /// so a client can retry a chunk whose response was lost.
+    pub async fn append_chunk(&self, upload_id: &str, chunk_index: u32, chunk: &[u8]) -> AppResult<UploadStatus> {
+        if chunk.len() > self.max_chunk_size {
+            return Err(AppError::InvalidInput(format!(
+                "Chunk size {} exceeds maximum chunk size {}", chunk.len(), self.max_chunk_size
+            )));
+        }
+
+        let mut uploads = self.uploads.lock().await;
+        let upload = self.get_live(&mut uploads, upload_id)?;
+
+        if chunk_index > upload.next_chunk_index {
+            return Err(AppError::InvalidInput(format!(
+                "Unexpected chunk {} for upload {}, expected chunk {}", chunk_index, upload_id, upload.next_chunk_index
+            )));
+        }
+
+        if chunk_index == upload.next_chunk_index {
+            if upload.data.len() + chunk.len() > self.max_upload_size {
+                return Err(AppError::InvalidInput(format!(
+                    "Upload {} exceeds maximum upload size {}", upload_id, self.max_upload_size
+                )));
+            }
+
+            upload.data.extend_from_slice(chunk);
+            upload.next_chunk_index += 1;
+        }
+        upload.last_activity = Instant::now();
+
+        Ok(self.status(upload_id, upload))
+    }
+
+    pub async fn get_status(&self, upload_id: &str) -> AppResult<UploadStatus> {
+        let mut uploads = self.uploads.lock().await;
+        let upload = self.get_live(&mut uploads, upload_id)?;
+        Ok(self.status(upload_id, upload))
+    }
+
+    /// Remove the upload, returning its round ID and assembled data
+    pub async fn take(&self, upload_id: &str) -> AppResult<(u32, Vec<u8>)> {
+        let mut uploads = self.uploads.lock().await;
+        self.get_live(&mut uploads, upload_id)?;
+        let upload = uploads.remove(upload_id)
+            .ok_or_else(|| AppError::NotFound(format!("Upload {} not found or expired", upload_id)))?;
+        Ok((upload.round_id, upload.data))
+    }
+
+    /// Remove abandoned uploads, returns the number of removed uploads
+    pub async fn purge_expired(&self) -> usize {
+        let mut uploads = self.uploads.lock().await;
+        let before = uploads.len();
+        uploads.retain(|_, upload| upload.last_activity.elapsed() <= self.ttl);
+        before - uploads.len()
+    }
+}
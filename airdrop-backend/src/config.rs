@@ -11,12 +11,270 @@ pub struct Config {
     pub aws: AwsConfig,
     pub wallet: WalletConfig,
     pub nats: NatsConfig,
+    pub eligibility: Option<EligibilityConfig>,
+    pub external_fetch: Option<ExternalFetchConfig>,
+}
+
+/// External backend requests: concurrency of multi-source fetches, timeout and retries of each request
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExternalFetchConfig {
+    pub max_concurrent: usize,
+    pub request_timeout_secs: u64,
+    pub max_retries: u32,
+    /// Delay before the first retry, growing linearly with the attempts
+    pub retry_delay_ms: u64,
+}
+
+impl Default for ExternalFetchConfig {
+    fn default() -> Self {
+        Self {
+            max_concurrent: 8,
+            request_timeout_secs: 30,
+            max_retries: 3,
+            retry_delay_ms: 1000,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct EligibilityConfig {
+    /// Textual form of addresses in generated CSV and JSON eligibility data
+    #[serde(default)]
+    pub address_format: AddressFormat,
+    /// Reject eligibility data listing an address more than once, in any of its forms.
+    /// The last record of the address is kept if not set.
+    #[serde(default)]
+    pub reject_duplicate_addresses: bool,
+    /// CSV columns packed into the trie leaves, the `address,amount` layout if not set
+    pub leaf_schema: Option<LeafSchema>,
+    /// Leaf and node hashing of the trie, `keccak256` without a leaf prefix if not set
+    pub hashing: Option<HashingConfig>,
+}
+
+/// Layout of the trie leaves built from CSV columns: leaves are the `abi.encodePacked`
+/// of the fields in their declared order, as the on-chain verifier must hash them.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LeafSchema {
+    /// Column of the claimant address, an `address` field
+    #[serde(default = "default_address_column")]
+    pub address_column: String,
+    /// Column of the claimable amount, a `uint` field
+    #[serde(default = "default_amount_column")]
+    pub amount_column: String,
+    /// Leaf fields, in the packing order
+    pub fields: Vec<LeafField>,
+}
+
+fn default_address_column() -> String {
+    "address".to_string()
+}
+
+fn default_amount_column() -> String {
+    "amount".to_string()
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LeafField {
+    /// CSV column name
+    pub column: String,
+    #[serde(rename = "type")]
+    pub field_type: LeafFieldType,
+}
+
+/// Solidity type of a leaf field, packed with its `abi.encodePacked` size
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LeafFieldType {
+    Address,
+    Uint8,
+    Uint16,
+    Uint32,
+    Uint64,
+    Uint128,
+    Uint256,
+    Bytes32,
+    Bool,
+}
+
+impl LeafFieldType {
+    /// Packed size in bytes
+    pub fn packed_size(&self) -> usize {
+        match self {
+            Self::Address => 20,
+            Self::Uint8 | Self::Bool => 1,
+            Self::Uint16 => 2,
+            Self::Uint32 => 4,
+            Self::Uint64 => 8,
+            Self::Uint128 => 16,
+            Self::Uint256 | Self::Bytes32 => 32,
+        }
+    }
+
+    pub fn is_uint(&self) -> bool {
+        matches!(self, Self::Uint8 | Self::Uint16 | Self::Uint32 | Self::Uint64 | Self::Uint128 | Self::Uint256)
+    }
+}
+
+impl Default for LeafSchema {
+    /// `abi.encodePacked(address, amount)` leaves of `address,amount` CSV files
+    fn default() -> Self {
+        Self {
+            address_column: default_address_column(),
+            amount_column: default_amount_column(),
+            fields: vec![
+                LeafField { column: default_address_column(), field_type: LeafFieldType::Address },
+                LeafField { column: default_amount_column(), field_type: LeafFieldType::Uint256 },
+            ],
+        }
+    }
+}
+
+impl LeafSchema {
+    /// Check the columns are distinct, and the address and amount columns are leaf fields of their types
+    pub fn validate(&self) -> std::result::Result<(), String> {
+        let mut columns = std::collections::HashSet::new();
+        if let Some(duplicate) = self.fields.iter().find(|field| !columns.insert(field.column.as_str())) {
+            return Err(format!("Duplicate leaf schema column '{}'", duplicate.column));
+        }
+
+        match self.field(&self.address_column) {
+            Some(field) if field.field_type == LeafFieldType::Address => {}
+            Some(_) => return Err(format!("Leaf schema address column '{}' must be of address type", self.address_column)),
+            None => return Err(format!("Leaf schema address column '{}' is not a leaf field", self.address_column)),
+        }
+
+        match self.field(&self.amount_column) {
+            Some(field) if field.field_type.is_uint() => {}
+            Some(_) => return Err(format!("Leaf schema amount column '{}' must be of uint type", self.amount_column)),
+            None => return Err(format!("Leaf schema amount column '{}' is not a leaf field", self.amount_column)),
+        }
+
+        Ok(())
+    }
+
+    pub fn field(&self, column: &str) -> Option<&LeafField> {
+        self.fields.iter().find(|field| field.column == column)
+    }
+
+    /// Byte offset of the column field in the packed leaf
+    pub fn offset(&self, column: &str) -> Option<usize> {
+        let index = self.fields.iter().position(|field| field.column == column)?;
+        Some(self.fields[..index].iter().map(|field| field.field_type.packed_size()).sum())
+    }
+
+    /// Packed leaf size in bytes
+    pub fn leaf_size(&self) -> usize {
+        self.fields.iter().map(|field| field.field_type.packed_size()).sum()
+    }
+}
+
+/// Hash function of the trie leaves and nodes
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HashFunction {
+    /// Ethereum Keccak-256 (original Keccak padding), Solidity `keccak256`
+    #[default]
+    Keccak256,
+    /// FIPS 202 SHA3-256, differing from Keccak-256 only by the padding, so yielding other hashes
+    Sha3_256,
+}
+
+/// Trie hashing, which must match the on-chain verifier for its proofs to verify.
+/// Leaf hashes are `hash(leaf_prefix || leaf)` without the prefix byte if not set,
+/// node hashes are `hash(min(left, right) || max(left, right))` of the sorted child hashes.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HashingConfig {
+    #[serde(default)]
+    pub hash_function: HashFunction,
+    /// Domain separation byte prepended to the leaf preimage, distinguishing leaves from 64 bytes nodes
+    pub leaf_prefix: Option<u8>,
+    /// Proofs are verified off-chain, e.g. by a non-EVM verifier, allowing hash functions the EVM lacks
+    #[serde(default)]
+    pub off_chain_verifier: bool,
+}
+
+impl HashingConfig {
+    /// Check the on-chain verifier can compute the hashes: the EVM has a `keccak256` opcode
+    /// but neither an opcode nor a precompile for SHA3-256
+    pub fn validate(&self) -> std::result::Result<(), String> {
+        if self.hash_function != HashFunction::Keccak256 && !self.off_chain_verifier {
+            return Err(format!(
+                "The on-chain verifier hashes with keccak256, {:?} roots can't be verified on-chain \
+                 (set off_chain_verifier for verifiers outside the EVM)",
+                self.hash_function
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+/// Canonical textual address form. Leaves are hashed over the raw 20 address bytes,
+/// as the on-chain verifier does with `abi.encodePacked(address, amount)`, so both forms
+/// yield the same leaf hashes.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AddressFormat {
+    #[default]
+    Lowercase,
+    Checksummed,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ServerConfig {
     pub bind_address: String,
     pub max_upload_size: u64,
+    pub chunked_upload: Option<ChunkedUploadConfig>,
+    pub max_eligibility_batch_size: Option<usize>, // default 1000
+    pub job_timeout_secs: Option<u64>, // default 1800
+    pub auth: Option<AuthConfig>,
+}
+
+pub const DEFAULT_MAX_ELIGIBILITY_BATCH_SIZE: usize = 1000;
+pub const DEFAULT_JOB_TIMEOUT_SECS: u64 = 1800;
+
+/// Authentication of the mutating endpoints
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AuthConfig {
+    pub enabled: bool,
+    /// Shared secret accepted as `Authorization: Bearer <api_key>`
+    pub api_key: Option<String>,
+    /// Addresses allowed to sign requests (EIP-191)
+    #[serde(default)]
+    pub admin_addresses: Vec<String>,
+    pub max_signature_age_secs: Option<u64>, // default 300
+}
+
+pub const DEFAULT_MAX_SIGNATURE_AGE_SECS: u64 = 300;
+
+/// Environment variable with the admin API key, overrides the config one
+pub const ADMIN_API_KEY_ENV: &str = "AIRDROP_ADMIN_API_KEY";
+
+impl AuthConfig {
+    pub fn resolve_api_key(&self) -> Option<String> {
+        std::env::var(ADMIN_API_KEY_ENV)
+            .ok()
+            .filter(|key| !key.is_empty())
+            .or_else(|| self.api_key.clone().filter(|key| !key.is_empty()))
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChunkedUploadConfig {
+    /// Inactivity period after which a partial upload is discarded
+    pub ttl_secs: u64,
+    pub max_chunk_size: u64,
+    pub max_upload_size: u64,
+}
+
+impl Default for ChunkedUploadConfig {
+    fn default() -> Self {
+        Self {
+            ttl_secs: 3600,
+            max_chunk_size: 8 * 1024 * 1024, // 8MB
+            max_upload_size: 1024 * 1024 * 1024, // 1GB
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -68,12 +326,38 @@ pub struct NatsConfig {
 pub struct ObjectStoreConfig {
     pub bucket_name: String,
     pub max_object_size: u64,
+    pub encryption: Option<ObjectStoreEncryptionConfig>,
+}
+
+/// Environment variable with the object store encryption key, overrides the config one
+pub const OBJECT_STORE_ENCRYPTION_KEY_ENV: &str = "NATS_OBJECT_STORE_ENCRYPTION_KEY";
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ObjectStoreEncryptionConfig {
+    pub enabled: bool,
+    /// Hex encoded AES-256 key
+    pub key: Option<String>,
+    /// Rounds to encrypt data for, all rounds if not set
+    pub rounds: Option<Vec<u32>>,
+}
+
+impl ObjectStoreEncryptionConfig {
+    pub fn resolve_key(&self) -> Option<String> {
+        std::env::var(OBJECT_STORE_ENCRYPTION_KEY_ENV)
+            .ok()
+            .filter(|key| !key.is_empty())
+            .or_else(|| self.key.clone().filter(|key| !key.is_empty()))
+    }
 }
 
 impl Config {
     pub async fn load_from_file(path: &str) -> Result<Self> {
         let content = tokio::fs::read_to_string(path).await?;
-        let config: Config = serde_yaml::from_str(&content)?;
+        let config: Config = config_loader::from_str_with_env(
+            &content,
+            config_loader::Format::from_path(path),
+            std::env::vars(),
+        )?;
         Ok(config)
     }
 
@@ -25,6 +25,18 @@ pub struct RoundMetadata {
     pub metadata_uri: String,
 }
 
+/// Trie root update contract call, built without broadcasting
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TrieUpdateCall {
+    pub to: Address,
+    pub function: String,
+    pub calldata: Vec<u8>,
+    pub gas_estimate: Option<u64>,
+    pub gas_estimate_error: Option<String>,
+}
+
+const UPDATE_TRIE_ROOT_FUNCTION: &str = "updateTrieRoot";
+
 /// Wrapper around the universal contract client
 pub struct ContractClient {
     inner: TxContractClient,
@@ -43,6 +55,8 @@ impl ContractClient {
             rpc_url: rpc_url.to_string(),
             chain_id: config.blockchain.chain_id,
             timeout_seconds: 30,
+            ws_url: None,
+            headers: Default::default(),
         };
 
         // Create provider manager with signer
@@ -61,6 +75,7 @@ impl ContractClient {
         let contract_config = ContractConfig {
             address: contract_address,
             abi_path,
+            verify_deployed: false,
         };
 
         // Create contract client
@@ -100,12 +115,8 @@ impl ContractClient {
 
         let tx_hash = self.inner
             .send_transaction(
-                "updateTrieRoot",
-                &[
-                    U256::from(round_id).into(),
-                    root_hash.into(),
-                    trie_data.into(),
-                ]
+                UPDATE_TRIE_ROOT_FUNCTION,
+                &Self::trie_update_args(round_id, root_hash, trie_data),
             )
             .await
             .map_err(|e| AppError::Blockchain(format!("Transaction failed: {}", e)))?;
@@ -114,6 +125,40 @@ impl ContractClient {
         Ok(tx_hash)
     }
 
+    fn trie_update_args(round_id: u32, root_hash: B256, trie_data: Vec<u8>) -> [DynSolValue; 3] {
+        [
+            U256::from(round_id).into(),
+            root_hash.into(),
+            trie_data.into(),
+        ]
+    }
+
+    /// Build the trie update call and estimate its gas, without broadcasting a transaction.
+    /// A failed gas estimation (e.g. an expected revert) is reported in the result, not as an error.
+    pub async fn prepare_trie_update(&self, round_id: u32, root_hash: B256, trie_data: Vec<u8>) -> AppResult<TrieUpdateCall> {
+        let args = Self::trie_update_args(round_id, root_hash, trie_data);
+
+        let calldata = self.inner
+            .encode_function_data(UPDATE_TRIE_ROOT_FUNCTION, &args)
+            .map_err(|e| AppError::Blockchain(format!("Failed to encode calldata: {}", e)))?;
+
+        let (gas_estimate, gas_estimate_error) = match self.inner.estimate_gas(UPDATE_TRIE_ROOT_FUNCTION, &args).await {
+            Ok(gas) => (Some(gas), None),
+            Err(e) => {
+                tracing::warn!("Gas estimation for round {} trie update failed: {}", round_id, e);
+                (None, Some(e.to_string()))
+            }
+        };
+
+        Ok(TrieUpdateCall {
+            to: self.contract_address,
+            function: UPDATE_TRIE_ROOT_FUNCTION.to_string(),
+            calldata: calldata.to_vec(),
+            gas_estimate,
+            gas_estimate_error,
+        })
+    }
+
     pub async fn get_trie_root(&self, round_id: u32) -> AppResult<B256> {
         let result = self.inner
             .call_function("getTrieRoot", &[U256::from(round_id).into()])
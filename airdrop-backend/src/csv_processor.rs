@@ -4,6 +4,7 @@ use alloy_primitives::{Address, U256};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::io::Cursor;
+use crate::config::{AddressFormat, LeafFieldType, LeafSchema};
 use crate::error::{AppError, AppResult};
 
 #[derive(Debug, Deserialize, Serialize)]
@@ -12,10 +13,50 @@ pub struct EligibilityRow {
     pub amount: String,
 }
 
+/// Eligibility data of a leaf schema, with the packed trie leaf of each address
+#[derive(Debug, Clone, Default)]
+pub struct SchemaEligibilityData {
+    pub amounts: HashMap<Address, U256>,
+    pub leaves: HashMap<Address, Vec<u8>>,
+}
+
 pub struct CsvProcessor;
 
 impl CsvProcessor {
-    pub fn process_csv_bytes(data: &[u8]) -> AppResult<HashMap<Address, U256>> {
+    /// Parse an address with or without `0x` prefix, in lowercase, uppercase or EIP-55 checksummed form.
+    /// Mixed case addresses must carry a valid EIP-55 checksum, to catch mistyped addresses.
+    pub fn parse_address(input: &str) -> std::result::Result<Address, String> {
+        let input = input.trim();
+        let hex_part = input.strip_prefix("0x")
+            .or_else(|| input.strip_prefix("0X"))
+            .unwrap_or(input);
+
+        if hex_part.len() != 40 || !hex_part.chars().all(|c| c.is_ascii_hexdigit()) {
+            return Err("expected 20 bytes hex encoded address".to_string());
+        }
+
+        let address: Address = hex_part.parse()
+            .map_err(|e| format!("{}", e))?;
+
+        let has_lowercase = hex_part.chars().any(|c| c.is_ascii_lowercase());
+        let has_uppercase = hex_part.chars().any(|c| c.is_ascii_uppercase());
+        if has_lowercase && has_uppercase && address.to_checksum(None)[2..] != *hex_part {
+            return Err("invalid EIP-55 checksum".to_string());
+        }
+
+        Ok(address)
+    }
+
+    pub fn format_address(address: &Address, format: AddressFormat) -> String {
+        match format {
+            AddressFormat::Lowercase => format!("0x{}", hex::encode(address)),
+            AddressFormat::Checksummed => address.to_checksum(None),
+        }
+    }
+
+    /// Parse CSV eligibility data, reporting invalid rows with their data row number (header excluded).
+    /// The last row of a duplicate address is kept, unless duplicates are rejected.
+    pub fn process_csv_bytes(data: &[u8], reject_duplicates: bool) -> AppResult<HashMap<Address, U256>> {
         let cursor = Cursor::new(data);
         let mut reader = ReaderBuilder::new()
             .has_headers(true)
@@ -23,23 +64,234 @@ impl CsvProcessor {
 
         let mut eligibility_data = HashMap::new();
 
-        for result in reader.deserialize() {
+        for (index, result) in reader.deserialize().enumerate() {
             let record: EligibilityRow = result
                 .map_err(|e| AppError::CsvProcessing(e))?;
+            let row = index + 1;
 
-            let address: Address = record.address.parse()
-                .map_err(|e| AppError::InvalidInput(format!("Invalid address '{}': {}", record.address, e)))?;
+            let address = Self::parse_address(&record.address)
+                .map_err(|e| AppError::InvalidInput(format!("Invalid address '{}' at row {}: {}", record.address, row, e)))?;
 
-            let amount: U256 = record.amount.parse()
-                .map_err(|e| AppError::InvalidInput(format!("Invalid amount '{}': {}", record.amount, e)))?;
+            let amount: U256 = record.amount.trim().parse()
+                .map_err(|e| AppError::InvalidInput(format!("Invalid amount '{}' at row {}: {}", record.amount, row, e)))?;
 
-            eligibility_data.insert(address, amount);
+            if eligibility_data.insert(address, amount).is_some() && reject_duplicates {
+                return Err(AppError::InvalidInput(format!(
+                    "Duplicate address '{}' at row {}", record.address, row
+                )));
+            }
         }
 
         Ok(eligibility_data)
     }
 
-    pub fn generate_csv_bytes(eligibility_data: &HashMap<Address, U256>) -> AppResult<Vec<u8>> {
+    /// Parse CSV eligibility data into the leaves of the leaf schema, reporting invalid rows
+    /// with their data row number (header excluded). Fails upfront on missing schema columns,
+    /// other CSV columns are ignored. The last row of a duplicate address is kept, unless duplicates are rejected.
+    pub fn process_csv_bytes_with_schema(data: &[u8], schema: &LeafSchema, reject_duplicates: bool) -> AppResult<SchemaEligibilityData> {
+        let cursor = Cursor::new(data);
+        let mut reader = ReaderBuilder::new()
+            .has_headers(true)
+            .from_reader(cursor);
+
+        let headers: Vec<String> = reader.headers()
+            .map_err(|e| AppError::CsvProcessing(e))?
+            .iter()
+            .map(|header| header.trim().to_string())
+            .collect();
+
+        let missing: Vec<&str> = schema.fields
+            .iter()
+            .map(|field| field.column.as_str())
+            .filter(|column| !headers.iter().any(|header| header == column))
+            .collect();
+        if !missing.is_empty() {
+            return Err(AppError::InvalidInput(format!(
+                "Missing CSV columns required by the leaf schema: {}", missing.join(", ")
+            )));
+        }
+
+        let mut eligibility_data = SchemaEligibilityData::default();
+
+        for (index, result) in reader.records().enumerate() {
+            let record = result.map_err(|e| AppError::CsvProcessing(e))?;
+            let row = index + 1;
+
+            let value_of = |column: &str| headers
+                .iter()
+                .position(|header| header == column)
+                .and_then(|position| record.get(position));
+
+            let (address, amount, leaf) = Self::encode_leaf(schema, value_of)
+                .map_err(|e| AppError::InvalidInput(format!("{} at row {}", e, row)))?;
+
+            if eligibility_data.amounts.insert(address, amount).is_some() && reject_duplicates {
+                return Err(AppError::InvalidInput(format!(
+                    "Duplicate address '{}' at row {}", value_of(&schema.address_column).unwrap_or_default(), row
+                )));
+            }
+            eligibility_data.leaves.insert(address, leaf);
+        }
+
+        Ok(eligibility_data)
+    }
+
+    /// Pack address to amount eligibility data into the leaves of the leaf schema,
+    /// failing if the schema has other columns, which the data doesn't carry
+    pub fn encode_eligibility_leaves(data: &HashMap<Address, U256>, schema: &LeafSchema) -> AppResult<HashMap<Address, Vec<u8>>> {
+        let missing: Vec<&str> = schema.fields
+            .iter()
+            .map(|field| field.column.as_str())
+            .filter(|column| *column != schema.address_column && *column != schema.amount_column)
+            .collect();
+        if !missing.is_empty() {
+            return Err(AppError::InvalidInput(format!(
+                "Missing columns required by the leaf schema: {}, upload the eligibility data as CSV", missing.join(", ")
+            )));
+        }
+
+        data.iter()
+            .map(|(address, amount)| {
+                let address_str = Self::format_address(address, AddressFormat::Lowercase);
+                let amount_str = amount.to_string();
+                let value_of = |column: &str| {
+                    if column == schema.address_column {
+                        Some(address_str.as_str())
+                    } else {
+                        Some(amount_str.as_str())
+                    }
+                };
+
+                let (_, _, leaf) = Self::encode_leaf(schema, value_of)
+                    .map_err(|e| AppError::InvalidInput(format!("{} for address {}", e, address)))?;
+                Ok((*address, leaf))
+            })
+            .collect()
+    }
+
+    /// Pack the leaf fields in the schema order, returning the address, amount and leaf
+    fn encode_leaf<'a>(schema: &LeafSchema, value_of: impl Fn(&str) -> Option<&'a str>) -> std::result::Result<(Address, U256, Vec<u8>), String> {
+        let mut leaf = Vec::with_capacity(schema.leaf_size());
+        let mut address = None;
+        let mut amount = None;
+
+        for field in &schema.fields {
+            let value = value_of(&field.column)
+                .ok_or_else(|| format!("Missing {} value", field.column))?;
+            let packed = Self::encode_field(field.field_type, value)
+                .map_err(|e| format!("Invalid {} '{}': {}", field.column, value, e))?;
+
+            if field.column == schema.address_column {
+                address = Some(Address::from_slice(&packed));
+            }
+            if field.column == schema.amount_column {
+                amount = Some(U256::from_be_slice(&packed));
+            }
+            leaf.extend_from_slice(&packed);
+        }
+
+        match (address, amount) {
+            (Some(address), Some(amount)) => Ok((address, amount, leaf)),
+            _ => Err("Leaf schema lacks the address or amount column".to_string()),
+        }
+    }
+
+    /// Pack a field value with its `abi.encodePacked` size
+    fn encode_field(field_type: LeafFieldType, value: &str) -> std::result::Result<Vec<u8>, String> {
+        let value = value.trim();
+
+        match field_type {
+            LeafFieldType::Address => Ok(Self::parse_address(value)?.to_vec()),
+            LeafFieldType::Bool => match value {
+                "true" | "1" => Ok(vec![1]),
+                "false" | "0" => Ok(vec![0]),
+                _ => Err("expected true, false, 1 or 0".to_string()),
+            },
+            LeafFieldType::Bytes32 => {
+                let bytes = hex::decode(value.strip_prefix("0x").unwrap_or(value))
+                    .map_err(|e| format!("{}", e))?;
+                if bytes.len() != 32 {
+                    return Err("expected 32 bytes hex encoded value".to_string());
+                }
+                Ok(bytes)
+            }
+            _ => {
+                let size = field_type.packed_size();
+                let number: U256 = value.parse().map_err(|e| format!("{}", e))?;
+                if number.bit_len() > size * 8 {
+                    return Err(format!("exceeds uint{}", size * 8));
+                }
+                Ok(number.to_be_bytes::<32>()[32 - size..].to_vec())
+            }
+        }
+    }
+
+    /// Unpack the leaf fields into their CSV values, in the schema order
+    fn decode_leaf(schema: &LeafSchema, leaf: &[u8], address_format: AddressFormat) -> AppResult<Vec<String>> {
+        if leaf.len() != schema.leaf_size() {
+            return Err(AppError::InvalidInput(format!(
+                "Leaf of {} bytes doesn't match the leaf schema of {} bytes", leaf.len(), schema.leaf_size()
+            )));
+        }
+
+        let mut offset = 0;
+        Ok(schema.fields
+            .iter()
+            .map(|field| {
+                let packed = &leaf[offset..offset + field.field_type.packed_size()];
+                offset += packed.len();
+
+                match field.field_type {
+                    LeafFieldType::Address => Self::format_address(&Address::from_slice(packed), address_format),
+                    LeafFieldType::Bool => (packed[0] != 0).to_string(),
+                    LeafFieldType::Bytes32 => format!("0x{}", hex::encode(packed)),
+                    _ => U256::from_be_slice(packed).to_string(),
+                }
+            })
+            .collect())
+    }
+
+    /// Generate CSV data of the leaf schema columns from the packed leaves
+    pub fn generate_csv_bytes_from_leaves(leaves: &[Vec<u8>], schema: &LeafSchema, address_format: AddressFormat) -> AppResult<Vec<u8>> {
+        let mut writer = WriterBuilder::new()
+            .has_headers(true)
+            .from_writer(Vec::new());
+
+        writer.write_record(schema.fields.iter().map(|field| field.column.as_str()))
+            .map_err(|e| AppError::CsvProcessing(e))?;
+
+        for leaf in leaves {
+            writer.write_record(Self::decode_leaf(schema, leaf, address_format)?)
+                .map_err(|e| AppError::CsvProcessing(e))?;
+        }
+
+        let csv_data = writer.into_inner()
+            .map_err(|e| AppError::CsvProcessing(csv::Error::from(e.into_error())))?;
+
+        Ok(csv_data)
+    }
+
+    /// Parse address to amount eligibility data map, as used by JSON eligibility data.
+    /// Of the forms of a duplicate address one is kept arbitrarily, unless duplicates are rejected.
+    pub fn process_eligibility_map(data: HashMap<String, String>, reject_duplicates: bool) -> AppResult<HashMap<Address, U256>> {
+        let mut eligibility_data = HashMap::with_capacity(data.len());
+
+        for (address_str, amount_str) in data {
+            let address = Self::parse_address(&address_str)
+                .map_err(|e| AppError::InvalidInput(format!("Invalid address '{}': {}", address_str, e)))?;
+
+            let amount: U256 = amount_str.trim().parse()
+                .map_err(|e| AppError::InvalidInput(format!("Invalid amount '{}': {}", amount_str, e)))?;
+
+            if eligibility_data.insert(address, amount).is_some() && reject_duplicates {
+                return Err(AppError::InvalidInput(format!("Duplicate address '{}'", address_str)));
+            }
+        }
+
+        Ok(eligibility_data)
+    }
+
+    pub fn generate_csv_bytes(eligibility_data: &HashMap<Address, U256>, address_format: AddressFormat) -> AppResult<Vec<u8>> {
         let mut writer = WriterBuilder::new()
             .has_headers(true)
             .from_writer(Vec::new());
@@ -51,7 +303,7 @@ impl CsvProcessor {
         // Write data
         for (address, amount) in eligibility_data {
             let record = EligibilityRow {
-                address: format!("0x{}", hex::encode(address)),
+                address: Self::format_address(address, address_format),
                 amount: amount.to_string(),
             };
             writer.serialize(&record)
@@ -83,3 +335,149 @@ impl CsvProcessor {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CHECKSUMMED: &str = "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed";
+    const LOWERCASE: &str = "0x5aaeb6053f3e94c9b9a09f33669435e7ef1beaed";
+
+    #[test]
+    fn test_parse_address_forms() {
+        let expected: Address = LOWERCASE.parse().unwrap();
+
+        assert_eq!(CsvProcessor::parse_address(CHECKSUMMED).unwrap(), expected);
+        assert_eq!(CsvProcessor::parse_address(LOWERCASE).unwrap(), expected);
+        assert_eq!(CsvProcessor::parse_address(&LOWERCASE[2..]).unwrap(), expected);
+        assert_eq!(CsvProcessor::parse_address(&LOWERCASE.to_uppercase()).unwrap(), expected);
+        assert_eq!(CsvProcessor::parse_address(&format!("  {}  ", CHECKSUMMED)).unwrap(), expected);
+    }
+
+    #[test]
+    fn test_parse_address_invalid() {
+        // Wrong checksum
+        assert!(CsvProcessor::parse_address("0x5AAeb6053F3E94C9b9A09f33669435E7Ef1BeAed").is_err());
+        // Wrong length
+        assert!(CsvProcessor::parse_address("0x5aaeb6053f3e94c9b9a09f33669435e7ef1bea").is_err());
+        // Non hex characters
+        assert!(CsvProcessor::parse_address("0x5aaeb6053f3e94c9b9a09f33669435e7ef1beazz").is_err());
+        assert!(CsvProcessor::parse_address("").is_err());
+    }
+
+    #[test]
+    fn test_format_address() {
+        let address: Address = LOWERCASE.parse().unwrap();
+
+        assert_eq!(CsvProcessor::format_address(&address, AddressFormat::Lowercase), LOWERCASE);
+        assert_eq!(CsvProcessor::format_address(&address, AddressFormat::Checksummed), CHECKSUMMED);
+    }
+
+    #[test]
+    fn test_process_csv_normalizes_addresses() {
+        let csv = format!("address,amount\n{}, 100\n0x0000000000000000000000000000000000000001,200\n", CHECKSUMMED);
+        let data = CsvProcessor::process_csv_bytes(csv.as_bytes(), true).unwrap();
+
+        assert_eq!(data.len(), 2);
+        assert_eq!(data[&LOWERCASE.parse::<Address>().unwrap()], U256::from(100u64));
+    }
+
+    #[test]
+    fn test_process_csv_reports_invalid_row() {
+        let csv = format!("address,amount\n{},100\n0xnotanaddress,200\n", LOWERCASE);
+        let error = CsvProcessor::process_csv_bytes(csv.as_bytes(), false).unwrap_err().to_string();
+
+        assert!(error.contains("row 2"), "{}", error);
+    }
+
+    #[test]
+    fn test_process_csv_duplicates_across_forms() {
+        let csv = format!("address,amount\n{},100\n{},200\n", CHECKSUMMED, LOWERCASE);
+        let error = CsvProcessor::process_csv_bytes(csv.as_bytes(), true).unwrap_err().to_string();
+
+        assert!(error.contains("Duplicate address"), "{}", error);
+        assert!(error.contains("row 2"), "{}", error);
+
+        // The last row is kept if duplicates aren't rejected
+        let data = CsvProcessor::process_csv_bytes(csv.as_bytes(), false).unwrap();
+        assert_eq!(data.len(), 1);
+        assert_eq!(data[&LOWERCASE.parse::<Address>().unwrap()], U256::from(200u64));
+
+        let map = HashMap::from([(CHECKSUMMED.to_string(), "100".to_string()), (LOWERCASE.to_string(), "200".to_string())]);
+        assert!(CsvProcessor::process_eligibility_map(map.clone(), true).is_err());
+        assert_eq!(CsvProcessor::process_eligibility_map(map, false).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_generate_csv_round_trip() {
+        let mut data = HashMap::new();
+        data.insert(LOWERCASE.parse::<Address>().unwrap(), U256::from(100u64));
+
+        let csv = CsvProcessor::generate_csv_bytes(&data, AddressFormat::Checksummed).unwrap();
+        assert!(String::from_utf8(csv.clone()).unwrap().contains(CHECKSUMMED));
+        assert_eq!(CsvProcessor::process_csv_bytes(&csv, false).unwrap(), data);
+    }
+
+    fn tiered_schema() -> LeafSchema {
+        use crate::config::LeafField;
+
+        LeafSchema {
+            address_column: "wallet".to_string(),
+            amount_column: "allocation".to_string(),
+            fields: vec![
+                LeafField { column: "index".to_string(), field_type: LeafFieldType::Uint32 },
+                LeafField { column: "wallet".to_string(), field_type: LeafFieldType::Address },
+                LeafField { column: "allocation".to_string(), field_type: LeafFieldType::Uint256 },
+                LeafField { column: "tier".to_string(), field_type: LeafFieldType::Uint8 },
+                LeafField { column: "vesting_start".to_string(), field_type: LeafFieldType::Uint64 },
+            ],
+        }
+    }
+
+    #[test]
+    fn test_process_csv_with_schema() {
+        let csv = format!("vesting_start,wallet,note,allocation,tier,index\n1700000000,{},first,100,2,7\n", CHECKSUMMED);
+        let data = CsvProcessor::process_csv_bytes_with_schema(csv.as_bytes(), &tiered_schema(), false).unwrap();
+
+        let address: Address = LOWERCASE.parse().unwrap();
+        assert_eq!(data.amounts[&address], U256::from(100u64));
+
+        let mut expected = 7u32.to_be_bytes().to_vec();
+        expected.extend_from_slice(address.as_slice());
+        expected.extend_from_slice(&U256::from(100u64).to_be_bytes::<32>());
+        expected.push(2);
+        expected.extend_from_slice(&1700000000u64.to_be_bytes());
+        assert_eq!(data.leaves[&address], expected);
+
+        // Round trip through the generated CSV of the schema columns
+        let generated = CsvProcessor::generate_csv_bytes_from_leaves(&[expected], &tiered_schema(), AddressFormat::Lowercase).unwrap();
+        assert_eq!(
+            String::from_utf8(generated.clone()).unwrap(),
+            format!("index,wallet,allocation,tier,vesting_start\n7,{},100,2,1700000000\n", LOWERCASE)
+        );
+        assert_eq!(CsvProcessor::process_csv_bytes_with_schema(&generated, &tiered_schema(), false).unwrap().leaves, data.leaves);
+    }
+
+    #[test]
+    fn test_process_csv_with_schema_errors() {
+        let csv = format!("wallet,allocation\n{},100\n", LOWERCASE);
+        let error = CsvProcessor::process_csv_bytes_with_schema(csv.as_bytes(), &tiered_schema(), false).unwrap_err().to_string();
+        assert!(error.contains("Missing CSV columns required by the leaf schema: index, tier, vesting_start"), "{}", error);
+
+        let csv = format!("index,wallet,allocation,tier,vesting_start\n1,{},100,256,0\n", LOWERCASE);
+        let error = CsvProcessor::process_csv_bytes_with_schema(csv.as_bytes(), &tiered_schema(), false).unwrap_err().to_string();
+        assert!(error.contains("Invalid tier '256': exceeds uint8 at row 1"), "{}", error);
+    }
+
+    #[test]
+    fn test_default_schema_matches_default_leaves() {
+        let mut data = HashMap::new();
+        data.insert(LOWERCASE.parse::<Address>().unwrap(), U256::from(100u64));
+
+        let csv = CsvProcessor::generate_csv_bytes(&data, AddressFormat::Lowercase).unwrap();
+        let schema_data = CsvProcessor::process_csv_bytes_with_schema(&csv, &LeafSchema::default(), false).unwrap();
+        assert_eq!(schema_data.amounts, data);
+        assert_eq!(CsvProcessor::encode_eligibility_leaves(&data, &LeafSchema::default()).unwrap(), schema_data.leaves);
+        assert!(CsvProcessor::encode_eligibility_leaves(&data, &tiered_schema()).is_err());
+    }
+}
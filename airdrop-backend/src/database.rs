@@ -20,6 +20,8 @@ pub struct EligibilityRecord {
     pub address: Address,
     pub amount: U256,
     pub round_id: u32,
+    /// Packed trie leaf, for rounds built with a leaf schema
+    pub leaf_data: Option<Vec<u8>>,
     pub created_at: Option<chrono::DateTime<chrono::Utc>>,
 }
 
@@ -34,6 +36,62 @@ pub struct ProcessingLog {
     pub created_at: chrono::DateTime<chrono::Utc>,
 }
 
+/// Round summary of the trie state and the latest blockchain submission
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RoundSummary {
+    pub round_id: u32,
+    pub root_hash: B256,
+    pub entry_count: i32,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    pub updated_at: chrono::DateTime<chrono::Utc>,
+    /// Status of the latest blockchain submission, if any
+    pub submission_status: Option<String>,
+    pub last_submission_at: Option<chrono::DateTime<chrono::Utc>>,
+    /// Latest submission completed (or skipped, root already on-chain) after the last trie update
+    pub submitted: bool,
+}
+
+/// Trie state upsert, with the round id, root hash, trie data and entry count params
+const SAVE_TRIE_STATE_QUERY: &str =
+    "INSERT INTO trie_states (round_id, root_hash, trie_data, entry_count, updated_at)
+     VALUES ($1, $2, $3, $4, NOW())
+     ON CONFLICT (round_id)
+     DO UPDATE SET
+        root_hash = $2,
+        trie_data = $3,
+        entry_count = $4,
+        updated_at = NOW()";
+
+/// Rounds with their latest blockchain submission, `$1` filtering by submission state if not null
+const ROUND_SUMMARIES_QUERY: &str =
+    "WITH rounds AS (
+        SELECT t.round_id, t.root_hash, t.entry_count, t.created_at, t.updated_at,
+               s.status AS submission_status, s.created_at AS last_submission_at,
+               COALESCE(s.status IN ('completed', 'skipped') AND s.created_at >= t.updated_at, FALSE) AS submitted
+        FROM trie_states t
+        LEFT JOIN LATERAL (
+            SELECT status, created_at FROM processing_logs
+            WHERE round_id = t.round_id AND operation = 'blockchain_submission'
+            ORDER BY created_at DESC, id DESC
+            LIMIT 1
+        ) s ON TRUE
+    )
+    SELECT * FROM rounds WHERE $1::BOOLEAN IS NULL OR submitted = $1";
+
+/// Rows removed by a round deletion
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct DeletedRoundData {
+    pub trie_states: u64,
+    pub eligibility_records: u64,
+    pub processing_logs: u64,
+}
+
+impl DeletedRoundData {
+    pub fn is_empty(&self) -> bool {
+        self.trie_states == 0 && self.eligibility_records == 0 && self.processing_logs == 0
+    }
+}
+
 #[derive(Debug)]
 pub struct Database {
     client: Client,
@@ -82,6 +140,12 @@ impl Database {
             &[],
         ).await?;
 
+        // Packed trie leaves of leaf schema rounds, added to tables created without them
+        self.client.execute(
+            "ALTER TABLE eligibility_records ADD COLUMN IF NOT EXISTS leaf_data BYTEA",
+            &[],
+        ).await?;
+
         // Processing logs table - audit trail
         self.client.execute(
             "CREATE TABLE IF NOT EXISTS processing_logs (
@@ -115,26 +179,6 @@ impl Database {
         Ok(())
     }
 
-    pub async fn save_trie_state(&self, trie_state: &TrieState) -> Result<()> {
-        self.client.execute(
-            "INSERT INTO trie_states (round_id, root_hash, trie_data, entry_count, updated_at)
-             VALUES ($1, $2, $3, $4, NOW())
-             ON CONFLICT (round_id)
-             DO UPDATE SET
-                root_hash = $2,
-                trie_data = $3,
-                entry_count = $4,
-                updated_at = NOW()",
-            &[
-                &(trie_state.round_id as i32),
-                &trie_state.root_hash.as_slice(),
-                &trie_state.trie_data,
-                &trie_state.entry_count,
-            ],
-        ).await?;
-        Ok(())
-    }
-
     pub async fn get_trie_state(&self, round_id: u32) -> Result<Option<TrieState>> {
         let row = self.client.query_opt(
             "SELECT round_id, root_hash, trie_data, entry_count, created_at, updated_at
@@ -184,26 +228,41 @@ impl Database {
         Ok(trie_states)
     }
 
-    pub async fn save_eligibility_records(&self, records: &[EligibilityRecord]) -> Result<()> {
-        if records.is_empty() {
-            return Ok(());
-        }
-
-        let mut query = String::from(
-            "INSERT INTO eligibility_records (address, amount, round_id) VALUES "
-        );
+    /// Save the round trie state along with its eligibility records. A single statement with a data-modifying CTE,
+    /// so both are saved atomically without a dedicated transaction on the shared client connection.
+    pub async fn save_round_data(&self, trie_state: &TrieState, records: &[EligibilityRecord]) -> Result<()> {
+        let round_id = trie_state.round_id as i32;
+        let root_hash = trie_state.root_hash.as_slice();
 
         // Create storage vectors for the data columns
         let mut address_vecs: Vec<Vec<u8>> = Vec::with_capacity(records.len());
         let mut amount_strs: Vec<String> = Vec::with_capacity(records.len());
         let mut round_ids: Vec<i32> = Vec::with_capacity(records.len());
 
-        let mut params: Vec<&(dyn tokio_postgres::types::ToSql + Sync)> = Vec::new();
+        let mut params: Vec<&(dyn tokio_postgres::types::ToSql + Sync)> = vec![
+            &round_id,
+            &root_hash,
+            &trie_state.trie_data,
+            &trie_state.entry_count,
+        ];
+
+        if records.is_empty() {
+            self.client.execute(SAVE_TRIE_STATE_QUERY, &params).await?;
+            return Ok(());
+        }
+
+        let mut query = format!(
+            "WITH saved_trie_state AS ({} RETURNING 1)
+             INSERT INTO eligibility_records (address, amount, round_id, leaf_data) VALUES ",
+            SAVE_TRIE_STATE_QUERY
+        );
+
         let mut placeholders = Vec::new();
 
         for (i, record) in records.iter().enumerate() {
-            let base_idx = i * 3 + 1;
-            placeholders.push(format!("(${}, ${}, ${})", base_idx, base_idx + 1, base_idx + 2));
+            // Record params follow the 4 trie state params
+            let base_idx = i * 4 + 5;
+            placeholders.push(format!("(${}, ${}, ${}, ${})", base_idx, base_idx + 1, base_idx + 2, base_idx + 3));
 
             // Store the data
             address_vecs.push(record.address.to_vec());
@@ -216,10 +275,11 @@ impl Database {
             params.push(&address_vecs[i]);
             params.push(&amount_strs[i]);
             params.push(&round_ids[i]);
+            params.push(&records[i].leaf_data);
         }
 
         query.push_str(&placeholders.join(", "));
-        query.push_str(" ON CONFLICT (address, round_id) DO UPDATE SET amount = EXCLUDED.amount");
+        query.push_str(" ON CONFLICT (address, round_id) DO UPDATE SET amount = EXCLUDED.amount, leaf_data = EXCLUDED.leaf_data");
 
         self.client.execute(&query, &params).await?;
         Ok(())
@@ -227,7 +287,7 @@ impl Database {
 
     pub async fn get_eligibility_records(&self, round_id: u32) -> Result<Vec<EligibilityRecord>> {
         let rows = self.client.query(
-            "SELECT id, address, amount, round_id, created_at
+            "SELECT id, address, amount, round_id, leaf_data, created_at
              FROM eligibility_records WHERE round_id = $1 ORDER BY id",
             &[&(round_id as i32)],
         ).await?;
@@ -244,7 +304,8 @@ impl Database {
                 address,
                 amount,
                 round_id: row.get::<_, i32>(3) as u32,
-                created_at: Some(row.get(4)),
+                leaf_data: row.get(4),
+                created_at: Some(row.get(5)),
             });
         }
 
@@ -326,21 +387,75 @@ impl Database {
         Ok(())
     }
 
-    pub async fn delete_round_data(&self, round_id: u32) -> Result<()> {
-        // Delete eligibility records first (due to foreign key)
-        self.client.execute(
-            "DELETE FROM eligibility_records WHERE round_id = $1",
-            &[&(round_id as i32)],
+    /// Set the final status of operation logs left in `started` status by interrupted processing
+    pub async fn close_started_processing_logs(&self, round_id: u32, operation: &str, status: &str, message: &str) -> Result<u64> {
+        let updated = self.client.execute(
+            "UPDATE processing_logs SET status = $1, message = $2
+             WHERE round_id = $3 AND operation = $4 AND status = 'started'",
+            &[&status, &message, &(round_id as i32), &operation],
         ).await?;
+        Ok(updated)
+    }
 
-        // Delete trie state
-        self.client.execute(
-            "DELETE FROM trie_states WHERE round_id = $1",
+    pub async fn delete_round_data(&self, round_id: u32) -> Result<DeletedRoundData> {
+        // Single statement with data-modifying CTEs, so the cascade is atomic
+        // without a dedicated transaction on the shared client connection
+        let row = self.client.query_one(
+            "WITH deleted_eligibility_records AS (
+                DELETE FROM eligibility_records WHERE round_id = $1 RETURNING 1
+            ),
+            deleted_processing_logs AS (
+                DELETE FROM processing_logs WHERE round_id = $1 RETURNING 1
+            ),
+            deleted_trie_states AS (
+                DELETE FROM trie_states WHERE round_id = $1 RETURNING 1
+            )
+            SELECT
+                (SELECT COUNT(*) FROM deleted_trie_states),
+                (SELECT COUNT(*) FROM deleted_eligibility_records),
+                (SELECT COUNT(*) FROM deleted_processing_logs)",
             &[&(round_id as i32)],
         ).await?;
 
-        tracing::info!("Deleted all data for round {}", round_id);
-        Ok(())
+        let deleted = DeletedRoundData {
+            trie_states: row.get::<_, i64>(0) as u64,
+            eligibility_records: row.get::<_, i64>(1) as u64,
+            processing_logs: row.get::<_, i64>(2) as u64,
+        };
+
+        tracing::info!("Deleted data for round {}: {:?}", round_id, deleted);
+        Ok(deleted)
+    }
+
+    /// Page of round summaries ordered by round, with the total count of rounds matching the filter
+    pub async fn get_round_summaries(&self, submitted: Option<bool>, limit: i64, offset: i64) -> Result<(Vec<RoundSummary>, i64)> {
+        let total: i64 = self.client.query_one(
+            &format!("SELECT COUNT(*) FROM ({}) matching", ROUND_SUMMARIES_QUERY),
+            &[&submitted],
+        ).await?.get(0);
+
+        let rows = self.client.query(
+            &format!("{} ORDER BY round_id LIMIT $2 OFFSET $3", ROUND_SUMMARIES_QUERY),
+            &[&submitted, &limit, &offset],
+        ).await?;
+
+        let mut summaries = Vec::new();
+        for row in rows {
+            let root_hash_bytes: &[u8] = row.get(1);
+
+            summaries.push(RoundSummary {
+                round_id: row.get::<_, i32>(0) as u32,
+                root_hash: B256::from_slice(root_hash_bytes),
+                entry_count: row.get(2),
+                created_at: row.get(3),
+                updated_at: row.get(4),
+                submission_status: row.get(5),
+                last_submission_at: row.get(6),
+                submitted: row.get(7),
+            });
+        }
+
+        Ok((summaries, total))
     }
 
     pub async fn get_round_statistics(&self) -> Result<Vec<(u32, i32, chrono::DateTime<chrono::Utc>)>> {
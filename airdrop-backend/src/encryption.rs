@@ -2,8 +2,8 @@ use anyhow::Result;
 use aws_config::BehaviorVersion;
 use aws_sdk_kms::{Client, types::DataKeySpec};
 use aes_gcm::{
-    aead::{Aead, KeyInit, generic_array::GenericArray},
-    Aes256Gcm, Nonce,
+    aead::{Aead, AeadInPlace, KeyInit, generic_array::GenericArray},
+    Aes256Gcm, Nonce, Tag,
 };
 use rand::RngCore;
 use serde::{Deserialize, Serialize};
@@ -17,6 +17,87 @@ pub struct EnvelopeEncryption {
     pub nonce: Vec<u8>,
 }
 
+/// AES-256-GCM encrypted data with the detached authentication tag (MAC),
+/// serialized as hex strings instead of JSON arrays of numbers
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AuthenticatedEncryption {
+    #[serde(with = "hex_bytes")]
+    pub ciphertext: Vec<u8>,
+    #[serde(with = "hex_bytes")]
+    pub nonce: Vec<u8>,
+    #[serde(with = "hex_bytes")]
+    pub tag: Vec<u8>,
+}
+
+/// Serde helpers for hex encoded bytes fields
+mod hex_bytes {
+    use alloy_primitives::hex;
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(bytes: &[u8], serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&hex::encode(bytes))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<u8>, D::Error> {
+        let encoded = String::deserialize(deserializer)?;
+        hex::decode(&encoded).map_err(serde::de::Error::custom)
+    }
+}
+
+/// AES-256-GCM encryption with a static key supplied via config or environment
+pub struct StaticKeyEncryption {
+    cipher: Aes256Gcm,
+}
+
+impl StaticKeyEncryption {
+    /// Create from a hex encoded 32 bytes key (with or without 0x prefix)
+    pub fn from_hex_key(key_hex: &str) -> Result<Self> {
+        let key = hex::decode(key_hex.trim().trim_start_matches("0x"))
+            .map_err(|_| anyhow::anyhow!("Invalid encryption key format, expected hex"))?;
+
+        if key.len() != 32 {
+            return Err(anyhow::anyhow!("Invalid encryption key length: {} bytes, expected 32", key.len()));
+        }
+
+        Ok(Self {
+            cipher: Aes256Gcm::new(GenericArray::from_slice(&key)),
+        })
+    }
+
+    /// Encrypt data, authenticating the associated data along with it
+    pub fn encrypt(&self, plaintext: &[u8], associated_data: &[u8]) -> Result<AuthenticatedEncryption> {
+        let mut nonce_bytes = [0u8; 12];
+        rand::thread_rng().fill_bytes(&mut nonce_bytes);
+        let nonce = Nonce::from_slice(&nonce_bytes);
+
+        let mut ciphertext = plaintext.to_vec();
+        let tag = self.cipher.encrypt_in_place_detached(nonce, associated_data, &mut ciphertext)
+            .map_err(|e| anyhow::anyhow!("Encryption failed: {}", e))?;
+
+        Ok(AuthenticatedEncryption {
+            ciphertext,
+            nonce: nonce_bytes.to_vec(),
+            tag: tag.to_vec(),
+        })
+    }
+
+    /// Decrypt data, failing if the data, tag or associated data were tampered with
+    pub fn decrypt(&self, encrypted: &AuthenticatedEncryption, associated_data: &[u8]) -> Result<Vec<u8>> {
+        if encrypted.nonce.len() != 12 || encrypted.tag.len() != 16 {
+            return Err(anyhow::anyhow!("Invalid nonce or authentication tag length"));
+        }
+
+        let nonce = Nonce::from_slice(&encrypted.nonce);
+        let tag = Tag::from_slice(&encrypted.tag);
+
+        let mut plaintext = encrypted.ciphertext.clone();
+        self.cipher.decrypt_in_place_detached(nonce, associated_data, &mut plaintext, tag)
+            .map_err(|_| anyhow::anyhow!("Decryption failed: authentication tag mismatch, data was tampered with or key is wrong"))?;
+
+        Ok(plaintext)
+    }
+}
+
 pub struct KmsEnvelopeEncryption {
     kms_client: Client,
     kms_key_id: String,
@@ -147,3 +228,26 @@ impl KmsEnvelopeEncryption {
         self.decrypt_private_key(encrypted_key).await
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_authenticated_encryption_hex_round_trip() {
+        let encryption = StaticKeyEncryption::from_hex_key(&format!("0x{}", "11".repeat(32))).unwrap();
+        let encrypted = encryption.encrypt(b"trie data", b"round_1/trie").unwrap();
+
+        let json: serde_json::Value = serde_json::to_value(&encrypted).unwrap();
+        assert_eq!(json["nonce"].as_str().unwrap().len(), 24);
+        assert_eq!(json["tag"].as_str().unwrap().len(), 32);
+        assert_eq!(json["ciphertext"].as_str().unwrap(), hex::encode(&encrypted.ciphertext));
+
+        let decoded: AuthenticatedEncryption = serde_json::from_value(json).unwrap();
+        assert_eq!(encryption.decrypt(&decoded, b"round_1/trie").unwrap(), b"trie data");
+        assert!(encryption.decrypt(&decoded, b"round_2/trie").is_err());
+
+        let malformed = serde_json::json!({ "ciphertext": "zz", "nonce": "", "tag": "" });
+        assert!(serde_json::from_value::<AuthenticatedEncryption>(malformed).is_err());
+    }
+}
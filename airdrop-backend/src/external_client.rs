@@ -1,8 +1,12 @@
 use std::collections::HashMap;
+use std::time::Duration;
 use anyhow::Result;
 use alloy_primitives::{Address, B256, U256};
+use futures::StreamExt;
 use reqwest::Client;
 use serde::{Deserialize, Serialize};
+use crate::config::ExternalFetchConfig;
+use crate::csv_processor::CsvProcessor;
 use crate::error::{AppError, AppResult};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -26,33 +30,80 @@ pub struct ExternalTrieInfo {
     pub merkle_proofs: Option<HashMap<String, Vec<String>>>, // address -> proof
 }
 
+/// Source which failed to be fetched or parsed
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExternalFetchFailure {
+    pub url: String,
+    pub error: String,
+}
+
+/// Eligibility data merged from the fetched sources, with the failed sources
+#[derive(Debug, Clone, Default)]
+pub struct ExternalFetchResult {
+    pub eligibility_data: HashMap<Address, U256>,
+    pub failures: Vec<ExternalFetchFailure>,
+}
+
 pub struct ExternalBackendClient {
     client: Client,
+    max_concurrent: usize,
+    max_retries: u32,
+    retry_delay: Duration,
+    reject_duplicate_addresses: bool,
 }
 
 impl ExternalBackendClient {
-    pub fn new() -> Self {
-        Self {
-            client: Client::new(),
+    pub fn new(config: &ExternalFetchConfig, reject_duplicate_addresses: bool) -> Result<Self> {
+        let client = Client::builder()
+            .timeout(Duration::from_secs(config.request_timeout_secs))
+            .build()?;
+
+        Ok(Self {
+            client,
+            max_concurrent: config.max_concurrent.max(1),
+            max_retries: config.max_retries,
+            retry_delay: Duration::from_millis(config.retry_delay_ms),
+            reject_duplicate_addresses,
+        })
+    }
+
+    /// GET request retried on transport errors and server error statuses, with a linear backoff.
+    /// Other unsuccessful statuses (e.g. `404 Not Found`) fail at once, as retrying won't change them.
+    async fn get_with_retry(&self, url: &str) -> AppResult<reqwest::Response> {
+        let mut last_error = None;
+
+        for attempt in 0..=self.max_retries {
+            match self.client.get(url).header("Content-Type", "application/json").send().await {
+                Ok(response) if response.status().is_success() => return Ok(response),
+                Ok(response) if response.status().is_server_error() => {
+                    last_error = Some(format!("HTTP request failed with status: {}", response.status()));
+                }
+                Ok(response) => {
+                    return Err(AppError::Internal(anyhow::anyhow!(
+                        "HTTP request failed with status: {}", response.status()
+                    )));
+                }
+                Err(e) => {
+                    last_error = Some(format!("HTTP request failed: {}", e));
+                }
+            }
+
+            if attempt < self.max_retries {
+                tracing::warn!("Request to {} failed: {}, retrying... (attempt {}/{})",
+                               url, last_error.as_deref().unwrap_or_default(), attempt + 1, self.max_retries);
+                tokio::time::sleep(self.retry_delay * (attempt + 1)).await;
+            }
         }
+
+        Err(AppError::Internal(anyhow::anyhow!(
+            last_error.unwrap_or_else(|| "Max retries exceeded".to_string())
+        )))
     }
 
     pub async fn fetch_eligibility_data(&self, url: &str) -> AppResult<HashMap<Address, U256>> {
         tracing::info!("Fetching eligibility data from: {}", url);
 
-        let response = self.client
-            .get(url)
-            .header("Content-Type", "application/json")
-            .send()
-            .await
-            .map_err(|e| AppError::Internal(anyhow::anyhow!("HTTP request failed: {}", e)))?;
-
-        if !response.status().is_success() {
-            return Err(AppError::Internal(anyhow::anyhow!(
-                "HTTP request failed with status: {}",
-                response.status()
-            )));
-        }
+        let response = self.get_with_retry(url).await?;
 
         let external_data: ExternalEligibilityData = response
             .json()
@@ -60,37 +111,36 @@ impl ExternalBackendClient {
             .map_err(|e| AppError::Internal(anyhow::anyhow!("JSON parsing failed: {}", e)))?;
 
         // Convert string addresses and amounts to proper types
-        let mut eligibility_data = HashMap::new();
-        for (address_str, amount_str) in external_data.eligibility {
-            let address: Address = address_str.parse()
-                .map_err(|e| AppError::InvalidInput(format!("Invalid address '{}': {}", address_str, e)))?;
+        let eligibility_data = CsvProcessor::process_eligibility_map(external_data.eligibility, self.reject_duplicate_addresses)?;
 
-            let amount: U256 = amount_str.parse()
-                .map_err(|e| AppError::InvalidInput(format!("Invalid amount '{}': {}", amount_str, e)))?;
+        tracing::info!("Fetched {} eligibility records", eligibility_data.len());
+        Ok(eligibility_data)
+    }
 
-            eligibility_data.insert(address, amount);
+    /// Fetch the eligibility data of several sources (e.g. pages or shards of a round) concurrently,
+    /// up to the configured limit. A failed source doesn't stop the others, it's reported in the failures,
+    /// as are sources with addresses already fetched from another source.
+    pub async fn fetch_eligibility_sources(&self, urls: &[String]) -> ExternalFetchResult {
+        let mut fetches = futures::stream::iter(urls)
+            .map(|url| async move { (url, self.fetch_eligibility_data(url).await) })
+            .buffer_unordered(self.max_concurrent);
+
+        let mut result = ExternalFetchResult::default();
+        while let Some((url, fetched)) = fetches.next().await {
+            let merged = fetched.and_then(|data| merge_source(&mut result.eligibility_data, data));
+            if let Err(e) = merged {
+                tracing::warn!("Failed to fetch eligibility data from {}: {}", url, e);
+                result.failures.push(ExternalFetchFailure { url: url.clone(), error: e.to_string() });
+            }
         }
 
-        tracing::info!("Fetched {} eligibility records", eligibility_data.len());
-        Ok(eligibility_data)
+        result
     }
 
     pub async fn fetch_trie_data(&self, url: &str) -> AppResult<ExternalTrieInfo> {
         tracing::info!("Fetching trie data from: {}", url);
 
-        let response = self.client
-            .get(url)
-            .header("Content-Type", "application/json")
-            .send()
-            .await
-            .map_err(|e| AppError::Internal(anyhow::anyhow!("HTTP request failed: {}", e)))?;
-
-        if !response.status().is_success() {
-            return Err(AppError::Internal(anyhow::anyhow!(
-                "HTTP request failed with status: {}",
-                response.status()
-            )));
-        }
+        let response = self.get_with_retry(url).await?;
 
         let external_data: ExternalTrieData = response
             .json()
@@ -170,3 +220,125 @@ impl ExternalBackendClient {
         Ok(())
     }
 }
+
+/// Merge the source data, rejecting the whole source if it has addresses already merged
+fn merge_source(merged: &mut HashMap<Address, U256>, data: HashMap<Address, U256>) -> AppResult<()> {
+    if let Some(duplicate) = data.keys().find(|address| merged.contains_key(*address)) {
+        return Err(AppError::InvalidInput(format!("Duplicate address {} already fetched from another source", duplicate)));
+    }
+
+    merged.extend(data);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_merge_source_rejects_duplicates() {
+        let address1 = Address::repeat_byte(0x01);
+        let address2 = Address::repeat_byte(0x02);
+        let mut merged = HashMap::from([(address1, U256::from(100u64))]);
+
+        assert!(merge_source(&mut merged, HashMap::from([(address2, U256::from(200u64))])).is_ok());
+        assert_eq!(merged.len(), 2);
+
+        let duplicate = HashMap::from([(address1, U256::from(300u64)), (Address::repeat_byte(0x03), U256::from(1u64))]);
+        assert!(merge_source(&mut merged, duplicate).is_err());
+        // Rejected source is not partially merged
+        assert_eq!(merged.len(), 2);
+        assert_eq!(merged[&address1], U256::from(100u64));
+    }
+
+    /// Local HTTP server answering each request with the status of its path (`/200`, `/404`, ...),
+    /// and an eligibility data body on success. Returns its address and the received requests counter.
+    async fn serve_statuses() -> (std::net::SocketAddr, std::sync::Arc<std::sync::atomic::AtomicUsize>) {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+        let requests = std::sync::Arc::new(AtomicUsize::new(0));
+
+        let counter = std::sync::Arc::clone(&requests);
+        tokio::spawn(async move {
+            while let Ok((mut stream, _)) = listener.accept().await {
+                counter.fetch_add(1, Ordering::SeqCst);
+
+                let mut buffer = [0u8; 1024];
+                let read = stream.read(&mut buffer).await.unwrap_or(0);
+                let request = String::from_utf8_lossy(&buffer[..read]);
+                let status = request.split_whitespace().nth(1).unwrap_or("/500").trim_start_matches('/').to_string();
+                let body = if status == "200" {
+                    format!("{{\"eligibility\":{{\"{}\":\"100\"}}}}", Address::repeat_byte(0x01))
+                } else {
+                    String::new()
+                };
+
+                let response = format!(
+                    "HTTP/1.1 {} Status\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    status, body.len(), body
+                );
+                let _ = stream.write_all(response.as_bytes()).await;
+            }
+        });
+
+        (address, requests)
+    }
+
+    fn client(max_retries: u32) -> ExternalBackendClient {
+        ExternalBackendClient::new(&ExternalFetchConfig {
+            max_concurrent: 2,
+            request_timeout_secs: 1,
+            max_retries,
+            retry_delay_ms: 1,
+        }, false).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_only_server_errors_retried() {
+        use std::sync::atomic::Ordering;
+
+        let (address, requests) = serve_statuses().await;
+        let client = client(2);
+
+        assert!(client.fetch_eligibility_data(&format!("http://{}/404", address)).await.is_err());
+        assert_eq!(requests.swap(0, Ordering::SeqCst), 1);
+
+        assert!(client.fetch_eligibility_data(&format!("http://{}/503", address)).await.is_err());
+        assert_eq!(requests.swap(0, Ordering::SeqCst), 3);
+
+        let data = client.fetch_eligibility_data(&format!("http://{}/200", address)).await.unwrap();
+        assert_eq!(data[&Address::repeat_byte(0x01)], U256::from(100u64));
+        assert_eq!(requests.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_failed_sources_skipped() {
+        let (address, _) = serve_statuses().await;
+        let urls = vec![format!("http://{}/200", address), format!("http://{}/404", address)];
+
+        let result = client(0).fetch_eligibility_sources(&urls).await;
+
+        assert_eq!(result.eligibility_data.len(), 1);
+        assert_eq!(result.failures.len(), 1);
+        assert_eq!(result.failures[0].url, urls[1]);
+    }
+
+    #[tokio::test]
+    async fn test_failed_sources_collected() {
+        let client = ExternalBackendClient::new(&ExternalFetchConfig {
+            max_concurrent: 2,
+            request_timeout_secs: 1,
+            max_retries: 1,
+            retry_delay_ms: 1,
+        }, false).unwrap();
+
+        let urls = vec!["http://127.0.0.1:9/page/1".to_string(), "not a url".to_string()];
+        let result = client.fetch_eligibility_sources(&urls).await;
+
+        assert!(result.eligibility_data.is_empty());
+        assert_eq!(result.failures.len(), 2);
+    }
+}
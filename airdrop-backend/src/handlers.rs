@@ -2,16 +2,19 @@ use axum::{
     extract::{Path, State, Multipart, Query},
     response::Json,
     http::{StatusCode, header},
-    body::Body,
+    body::{Body, Bytes},
     response::{Response, IntoResponse},
 };
 use serde::{Deserialize, Serialize};
 use serde_json::json;
 use std::sync::Arc;
 use std::collections::HashMap;
-use alloy_primitives::{Address, U256, B256};
+use alloy_primitives::{U256, B256};
 
 use crate::service::AirdropService;
+use crate::chunked_upload::UploadStatus;
+use crate::csv_processor::CsvProcessor;
+use crate::jobs::JobInfo;
 use crate::error::{AppError, AppResult};
 use crate::database::ProcessingLog;
 use crate::contract_client::RoundMetadata;
@@ -31,6 +34,26 @@ pub struct VerifyEligibilityResponse {
     pub amount: String,
 }
 
+#[derive(Serialize, Deserialize)]
+pub struct VerifyEligibilityBatchRequest {
+    pub round_id: u32,
+    pub addresses: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct AddressEligibility {
+    pub address: String,
+    pub eligible: bool,
+    pub amount: String,
+    pub proof: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct VerifyEligibilityBatchResponse {
+    pub round_id: u32,
+    pub results: Vec<AddressEligibility>,
+}
+
 #[derive(Serialize, Deserialize)]
 pub struct EligibilityDataJson {
     pub eligibility: HashMap<String, String>, // address -> amount as strings
@@ -58,6 +81,20 @@ pub struct ExternalDataRequest {
     pub external_url: String,
 }
 
+/// External eligibility data of a single source, or of several sources (e.g. pages) fetched concurrently
+#[derive(Serialize, Deserialize)]
+pub struct ExternalEligibilityRequest {
+    pub external_url: Option<String>,
+    #[serde(default)]
+    pub external_urls: Vec<String>,
+}
+
+impl ExternalEligibilityRequest {
+    fn urls(&self) -> Vec<String> {
+        self.external_url.iter().chain(&self.external_urls).cloned().collect()
+    }
+}
+
 #[derive(Serialize, Deserialize)]
 pub struct ComparisonResult {
     pub matches: bool,
@@ -82,6 +119,26 @@ pub struct RoundStatistics {
     pub last_updated: String,
 }
 
+#[derive(Serialize)]
+pub struct RoundSummaryResponse {
+    pub round_id: u32,
+    pub root_hash: String,
+    pub entry_count: i32,
+    pub submitted: bool,
+    pub submission_status: Option<String>,
+    pub last_submission_at: Option<String>,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+#[derive(Serialize)]
+pub struct RoundsResponse {
+    pub rounds: Vec<RoundSummaryResponse>,
+    pub total: u64,
+    pub limit: u32,
+    pub offset: u32,
+}
+
 #[derive(Serialize)]
 pub struct ContractInfoResponse {
     pub contract_address: String,
@@ -102,11 +159,31 @@ pub struct RoundMetadataResponse {
     pub metadata_uri: String,
 }
 
+#[derive(Deserialize)]
+pub struct ChunkedUploadInitRequest {
+    pub round_id: u32,
+}
+
 #[derive(Deserialize)]
 pub struct LogsQuery {
     pub round_id: Option<u32>,
 }
 
+#[derive(Deserialize)]
+pub struct RoundsQuery {
+    pub submitted: Option<bool>,
+    pub limit: Option<u32>, // default 50
+    pub offset: Option<u32>,
+}
+
+pub const DEFAULT_ROUNDS_PAGE_LIMIT: u32 = 50;
+pub const MAX_ROUNDS_PAGE_LIMIT: u32 = 500;
+
+#[derive(Deserialize)]
+pub struct SubmitTrieQuery {
+    pub dry_run: Option<bool>,
+}
+
 #[derive(Deserialize)]
 pub struct FormatQuery {
     pub format: Option<String>, // "json", "hex", "base64"
@@ -123,7 +200,7 @@ pub async fn health_check() -> Json<serde_json::Value> {
 pub async fn upload_csv(
     State(service): State<Arc<AirdropService>>,
     mut multipart: Multipart,
-) -> AppResult<Json<serde_json::Value>> {
+) -> AppResult<(StatusCode, Json<serde_json::Value>)> {
     let mut round_id: Option<u32> = None;
     let mut csv_data: Option<Vec<u8>> = None;
 
@@ -153,14 +230,56 @@ pub async fn upload_csv(
     let round_id = round_id.ok_or_else(|| AppError::InvalidInput("round_id is required".to_string()))?;
     let csv_data = csv_data.ok_or_else(|| AppError::InvalidInput("csv_file is required".to_string()))?;
 
-    service.process_csv_data(&csv_data, round_id).await?;
+    let data_size = csv_data.len();
+    let job = service.start_csv_processing_job(csv_data, round_id).await;
 
-    Ok(Json(json!({
+    Ok((StatusCode::ACCEPTED, Json(json!({
         "success": true,
-        "message": format!("CSV data processed for round {}", round_id),
+        "message": format!("CSV data processing started for round {}", round_id),
         "round_id": round_id,
-        "data_size_bytes": csv_data.len()
-    })))
+        "data_size_bytes": data_size,
+        "job": job
+    }))))
+}
+
+pub async fn init_chunked_csv_upload(
+    State(service): State<Arc<AirdropService>>,
+    Json(request): Json<ChunkedUploadInitRequest>,
+) -> AppResult<Json<UploadStatus>> {
+    Ok(Json(service.init_chunked_csv_upload(request.round_id).await))
+}
+
+pub async fn upload_csv_chunk(
+    Path((upload_id, chunk_index)): Path<(String, u32)>,
+    State(service): State<Arc<AirdropService>>,
+    chunk: Bytes,
+) -> AppResult<Json<UploadStatus>> {
+    let status = service.append_csv_chunk(&upload_id, chunk_index, &chunk).await?;
+    Ok(Json(status))
+}
+
+pub async fn get_chunked_csv_upload_status(
+    Path(upload_id): Path<String>,
+    State(service): State<Arc<AirdropService>>,
+) -> AppResult<Json<UploadStatus>> {
+    let status = service.get_chunked_csv_upload_status(&upload_id).await?;
+    Ok(Json(status))
+}
+
+pub async fn finalize_chunked_csv_upload(
+    Path(upload_id): Path<String>,
+    State(service): State<Arc<AirdropService>>,
+) -> AppResult<(StatusCode, Json<serde_json::Value>)> {
+    let (job, data_size) = service.finalize_chunked_csv_upload(&upload_id).await?;
+
+    Ok((StatusCode::ACCEPTED, Json(json!({
+        "success": true,
+        "message": format!("CSV data processing started for round {}", job.round_id),
+        "upload_id": upload_id,
+        "round_id": job.round_id,
+        "data_size_bytes": data_size,
+        "job": job
+    }))))
 }
 
 pub async fn download_csv(
@@ -184,26 +303,18 @@ pub async fn upload_json_eligibility(
     Path(round_id): Path<u32>,
     State(service): State<Arc<AirdropService>>,
     Json(payload): Json<EligibilityDataJson>,
-) -> AppResult<Json<serde_json::Value>> {
+) -> AppResult<(StatusCode, Json<serde_json::Value>)> {
     // Convert string addresses and amounts to proper types
-    let mut eligibility_data = HashMap::new();
-    for (address_str, amount_str) in payload.eligibility {
-        let address: Address = address_str.parse()
-            .map_err(|e| AppError::InvalidInput(format!("Invalid address '{}': {}", address_str, e)))?;
+    let eligibility_data = CsvProcessor::process_eligibility_map(payload.eligibility, service.rejects_duplicate_addresses())?;
 
-        let amount: U256 = amount_str.parse()
-            .map_err(|e| AppError::InvalidInput(format!("Invalid amount '{}': {}", amount_str, e)))?;
+    let job = service.start_json_processing_job(eligibility_data, round_id).await;
 
-        eligibility_data.insert(address, amount);
-    }
-
-    service.process_json_eligibility_data(eligibility_data, round_id).await?;
-
-    Ok(Json(json!({
+    Ok((StatusCode::ACCEPTED, Json(json!({
         "success": true,
-        "message": format!("JSON eligibility data processed for round {}", round_id),
-        "round_id": round_id
-    })))
+        "message": format!("JSON eligibility data processing started for round {}", round_id),
+        "round_id": round_id,
+        "job": job
+    }))))
 }
 
 pub async fn download_json_eligibility(
@@ -211,11 +322,12 @@ pub async fn download_json_eligibility(
     State(service): State<Arc<AirdropService>>,
 ) -> AppResult<Json<EligibilityDataJson>> {
     let eligibility_data = service.get_round_eligibility_records(round_id).await?;
+    let address_format = service.get_address_format();
 
     // Convert to string format for JSON
     let mut string_data = HashMap::new();
     for (address, amount) in eligibility_data {
-        string_data.insert(format!("0x{}", hex::encode(address)), amount.to_string());
+        string_data.insert(CsvProcessor::format_address(&address, address_format), amount.to_string());
     }
 
     Ok(Json(EligibilityDataJson {
@@ -335,15 +447,22 @@ pub async fn upload_and_compare_trie_data(
 pub async fn fetch_external_data_and_update(
     Path(round_id): Path<u32>,
     State(service): State<Arc<AirdropService>>,
-    Json(payload): Json<ExternalDataRequest>,
+    Json(payload): Json<ExternalEligibilityRequest>,
 ) -> AppResult<Json<serde_json::Value>> {
-    service.fetch_and_update_from_external(round_id, &payload.external_url).await?;
+    let external_urls = payload.urls();
+    if external_urls.is_empty() {
+        return Err(AppError::InvalidInput("external_url or external_urls is required".to_string()));
+    }
+
+    let (record_count, failed_sources) = service.fetch_and_update_from_external(round_id, &external_urls).await?;
 
     Ok(Json(json!({
         "success": true,
         "message": format!("Successfully updated round {} with external data", round_id),
         "round_id": round_id,
-        "external_url": payload.external_url
+        "external_urls": external_urls,
+        "record_count": record_count,
+        "failed_sources": failed_sources
     })))
 }
 
@@ -386,8 +505,27 @@ pub async fn update_trie(
 
 pub async fn submit_trie(
     Path(round_id): Path<u32>,
+    Query(params): Query<SubmitTrieQuery>,
     State(service): State<Arc<AirdropService>>,
 ) -> AppResult<Json<serde_json::Value>> {
+    if params.dry_run.unwrap_or(false) {
+        let (root_hash, root_hash_exists, call) = service.prepare_trie_submission(round_id).await?;
+
+        return Ok(Json(json!({
+            "success": true,
+            "dry_run": true,
+            "message": format!("Trie update prepared for round {}, not broadcasted", round_id),
+            "round_id": round_id,
+            "root_hash": format!("0x{}", hex::encode(root_hash)),
+            "root_hash_exists": root_hash_exists,
+            "to": call.to.to_checksum(None),
+            "function": call.function,
+            "calldata": format!("0x{}", hex::encode(&call.calldata)),
+            "gas_estimate": call.gas_estimate,
+            "gas_estimate_error": call.gas_estimate_error
+        })));
+    }
+
     let tx_hash = service.submit_trie_update(round_id).await?;
 
     Ok(Json(json!({
@@ -402,7 +540,7 @@ pub async fn verify_eligibility(
     State(service): State<Arc<AirdropService>>,
     Json(payload): Json<VerifyEligibilityRequest>,
 ) -> AppResult<Json<VerifyEligibilityResponse>> {
-    let address: Address = payload.address.parse()
+    let address = CsvProcessor::parse_address(&payload.address)
         .map_err(|e| AppError::InvalidInput(format!("Invalid address: {}", e)))?;
 
     let amount: U256 = payload.amount.parse()
@@ -418,11 +556,48 @@ pub async fn verify_eligibility(
     }))
 }
 
+pub async fn verify_eligibility_batch(
+    State(service): State<Arc<AirdropService>>,
+    Json(payload): Json<VerifyEligibilityBatchRequest>,
+) -> AppResult<Json<VerifyEligibilityBatchResponse>> {
+    let addresses = payload.addresses
+        .iter()
+        .map(|address| CsvProcessor::parse_address(address)
+            .map_err(|e| AppError::InvalidInput(format!("Invalid address {}: {}", address, e))))
+        .collect::<AppResult<Vec<_>>>()?;
+
+    let eligibilities = service.get_eligibility_batch(payload.round_id, &addresses).await?;
+
+    let results = payload.addresses
+        .into_iter()
+        .zip(eligibilities)
+        .map(|(address, eligibility)| match eligibility {
+            Some((amount, proof)) => AddressEligibility {
+                address,
+                eligible: true,
+                amount: amount.to_string(),
+                proof: proof.iter().map(|p| format!("0x{}", hex::encode(p))).collect(),
+            },
+            None => AddressEligibility {
+                address,
+                eligible: false,
+                amount: "0".to_string(),
+                proof: Vec::new(),
+            },
+        })
+        .collect();
+
+    Ok(Json(VerifyEligibilityBatchResponse {
+        round_id: payload.round_id,
+        results,
+    }))
+}
+
 pub async fn get_eligibility(
     Path((round_id, address_str)): Path<(u32, String)>,
     State(service): State<Arc<AirdropService>>,
 ) -> AppResult<Json<serde_json::Value>> {
-    let address: Address = address_str.parse()
+    let address = CsvProcessor::parse_address(&address_str)
         .map_err(|e| AppError::InvalidInput(format!("Invalid address: {}", e)))?;
 
     match service.get_eligibility(round_id, address).await? {
@@ -457,6 +632,35 @@ pub async fn get_trie_info(
     }
 }
 
+pub async fn list_rounds(
+    Query(params): Query<RoundsQuery>,
+    State(service): State<Arc<AirdropService>>,
+) -> AppResult<Json<RoundsResponse>> {
+    let limit = params.limit.unwrap_or(DEFAULT_ROUNDS_PAGE_LIMIT);
+    if limit == 0 || limit > MAX_ROUNDS_PAGE_LIMIT {
+        return Err(AppError::InvalidInput(format!("limit must be between 1 and {}", MAX_ROUNDS_PAGE_LIMIT)));
+    }
+    let offset = params.offset.unwrap_or(0);
+
+    let (summaries, total) = service.list_rounds(params.submitted, limit, offset).await?;
+
+    let rounds = summaries
+        .into_iter()
+        .map(|summary| RoundSummaryResponse {
+            round_id: summary.round_id,
+            root_hash: format!("0x{}", hex::encode(summary.root_hash)),
+            entry_count: summary.entry_count,
+            submitted: summary.submitted,
+            submission_status: summary.submission_status,
+            last_submission_at: summary.last_submission_at.map(|at| at.to_rfc3339()),
+            created_at: summary.created_at.to_rfc3339(),
+            updated_at: summary.updated_at.to_rfc3339(),
+        })
+        .collect();
+
+    Ok(Json(RoundsResponse { rounds, total, limit, offset }))
+}
+
 pub async fn get_round_statistics(
     State(service): State<Arc<AirdropService>>,
 ) -> AppResult<Json<Vec<RoundStatistics>>> {
@@ -490,16 +694,40 @@ pub async fn get_round_processing_logs(
     Ok(Json(logs))
 }
 
+pub async fn list_jobs(
+    Query(params): Query<LogsQuery>,
+    State(service): State<Arc<AirdropService>>,
+) -> Json<Vec<JobInfo>> {
+    Json(service.list_jobs(params.round_id).await)
+}
+
+pub async fn get_job(
+    Path(job_id): Path<String>,
+    State(service): State<Arc<AirdropService>>,
+) -> AppResult<Json<JobInfo>> {
+    let job = service.get_job(&job_id).await?;
+    Ok(Json(job))
+}
+
+pub async fn cancel_job(
+    Path(job_id): Path<String>,
+    State(service): State<Arc<AirdropService>>,
+) -> AppResult<Json<JobInfo>> {
+    let job = service.cancel_job(&job_id).await?;
+    Ok(Json(job))
+}
+
 pub async fn delete_round(
     Path(round_id): Path<u32>,
     State(service): State<Arc<AirdropService>>,
 ) -> AppResult<Json<serde_json::Value>> {
-    service.delete_round(round_id).await?;
+    let summary = service.delete_round(round_id).await?;
 
     Ok(Json(json!({
         "success": true,
         "message": format!("Round {} deleted successfully", round_id),
-        "round_id": round_id
+        "round_id": round_id,
+        "deleted": summary
     })))
 }
 
@@ -548,6 +776,31 @@ pub async fn get_round_metadata(
     }))
 }
 
+pub async fn verify_onchain_root(
+    Path(round_id): Path<u32>,
+    State(service): State<Arc<AirdropService>>,
+) -> AppResult<Json<serde_json::Value>> {
+    let verification = service.verify_on_chain_root(round_id).await?;
+    let matches = verification.matches();
+
+    Ok(Json(json!({
+        "round_id": round_id,
+        "entry_count": verification.entry_count,
+        "computed_root": format!("0x{}", hex::encode(verification.computed_root)),
+        "stored_root": verification.stored_root.map(|root| format!("0x{}", hex::encode(root))),
+        "on_chain_root": format!("0x{}", hex::encode(verification.on_chain_root)),
+        "verifier_accepts_proof": verification.verifier_accepts_proof,
+        "matches": matches,
+        "message": if matches {
+            "Root computed from stored eligibility data matches on-chain root"
+        } else if verification.verifier_accepts_proof == Some(false) {
+            "Root matches on-chain root, but the contract verifier rejects its proofs: check the leaf hashing configuration"
+        } else {
+            "Root computed from stored eligibility data does not match on-chain root"
+        }
+    })))
+}
+
 pub async fn validate_consistency(
     Path(round_id): Path<u32>,
     State(service): State<Arc<AirdropService>>,
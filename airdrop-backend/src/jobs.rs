@@ -0,0 +1,187 @@
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::Arc;
+use std::time::Duration;
+use serde::Serialize;
+use tokio::sync::RwLock;
+use tokio::task::AbortHandle;
+use tracing::{info, warn};
+
+use crate::database::Database;
+use crate::error::{AppError, AppResult};
+
+/// Finished jobs are kept for status polling during this period
+const FINISHED_JOB_RETENTION_SECS: i64 = 24 * 60 * 60;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum JobStatus {
+    Running,
+    Completed,
+    Failed,
+    Cancelled,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct JobInfo {
+    pub job_id: String,
+    pub round_id: u32,
+    pub operation: String,
+    pub status: JobStatus,
+    pub message: Option<String>,
+    pub started_at: chrono::DateTime<chrono::Utc>,
+    pub finished_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+struct TrackedJob {
+    info: JobInfo,
+    abort_handle: Option<AbortHandle>,
+}
+
+/// Background processing jobs, decoupled from HTTP request lifetimes
+pub struct JobTracker {
+    jobs: RwLock<HashMap<String, TrackedJob>>,
+    database: Arc<Database>,
+    max_duration: Duration,
+}
+
+impl JobTracker {
+    pub fn new(database: Arc<Database>, max_duration: Duration) -> Self {
+        Self {
+            jobs: RwLock::new(HashMap::new()),
+            database,
+            max_duration,
+        }
+    }
+
+    /// Run the job in background, cancelling it when exceeding the max duration.
+    /// The job `operation` must match the processing log operation of the job work,
+    /// so its processing logs left in `started` status are closed on timeout or cancellation.
+    pub async fn spawn<F>(self: &Arc<Self>, round_id: u32, operation: &str, job: F) -> JobInfo
+    where
+        F: Future<Output = AppResult<()>> + Send + 'static,
+    {
+        self.purge_finished().await;
+
+        let info = JobInfo {
+            job_id: uuid::Uuid::new_v4().to_string(),
+            round_id,
+            operation: operation.to_string(),
+            status: JobStatus::Running,
+            message: None,
+            started_at: chrono::Utc::now(),
+            finished_at: None,
+        };
+
+        // Register before spawning, so a quickly finished job always finds its entry
+        let mut jobs = self.jobs.write().await;
+        jobs.insert(info.job_id.clone(), TrackedJob { info: info.clone(), abort_handle: None });
+
+        let tracker = Arc::clone(self);
+        let job_id = info.job_id.clone();
+        let max_duration = self.max_duration;
+        let handle = tokio::spawn(async move {
+            let (status, message) = match tokio::time::timeout(max_duration, job).await {
+                Ok(Ok(())) => (JobStatus::Completed, None),
+                Ok(Err(e)) => (JobStatus::Failed, Some(e.to_string())),
+                Err(_) => {
+                    let message = format!("Job timed out after {} seconds", max_duration.as_secs());
+                    tracker.close_started_logs(round_id, &job_id, "failed", &message).await;
+                    (JobStatus::Failed, Some(message))
+                }
+            };
+            tracker.finish(&job_id, status, message).await;
+        });
+
+        if let Some(job) = jobs.get_mut(&info.job_id) {
+            job.abort_handle = Some(handle.abort_handle());
+        }
+
+        info!("Started {} job {} for round {}", operation, info.job_id, round_id);
+        info
+    }
+
+    pub async fn get(&self, job_id: &str) -> AppResult<JobInfo> {
+        self.jobs.read().await
+            .get(job_id)
+            .map(|job| job.info.clone())
+            .ok_or_else(|| AppError::NotFound(format!("Job {} not found", job_id)))
+    }
+
+    pub async fn list(&self, round_id: Option<u32>) -> Vec<JobInfo> {
+        let mut jobs: Vec<JobInfo> = self.jobs.read().await
+            .values()
+            .filter(|job| round_id.is_none_or(|round_id| job.info.round_id == round_id))
+            .map(|job| job.info.clone())
+            .collect();
+        jobs.sort_by(|a, b| b.started_at.cmp(&a.started_at));
+        jobs
+    }
+
+    pub async fn cancel(&self, job_id: &str) -> AppResult<JobInfo> {
+        let (round_id, abort_handle) = {
+            let jobs = self.jobs.read().await;
+            let job = jobs.get(job_id)
+                .ok_or_else(|| AppError::NotFound(format!("Job {} not found", job_id)))?;
+
+            if job.info.status != JobStatus::Running {
+                return Err(AppError::InvalidInput(format!("Job {} is not running", job_id)));
+            }
+            (job.info.round_id, job.abort_handle.clone())
+        };
+
+        if let Some(abort_handle) = abort_handle {
+            abort_handle.abort();
+        }
+
+        let message = "Job cancelled".to_string();
+        self.close_started_logs(round_id, job_id, "cancelled", &message).await;
+        self.finish(job_id, JobStatus::Cancelled, Some(message)).await;
+
+        self.get(job_id).await
+    }
+
+    async fn finish(&self, job_id: &str, status: JobStatus, message: Option<String>) {
+        let mut jobs = self.jobs.write().await;
+        if let Some(job) = jobs.get_mut(job_id) {
+            // Cancellation wins over a concurrent completion
+            if job.info.status != JobStatus::Running {
+                return;
+            }
+
+            match status {
+                JobStatus::Completed => info!("Job {} completed", job_id),
+                _ => warn!("Job {} {:?}: {}", job_id, status, message.as_deref().unwrap_or_default()),
+            }
+
+            job.info.status = status;
+            job.info.message = message;
+            job.info.finished_at = Some(chrono::Utc::now());
+            job.abort_handle = None;
+        }
+    }
+
+    /// Close processing logs of the interrupted job work, which never reach a final status by themselves
+    async fn close_started_logs(&self, round_id: u32, job_id: &str, status: &str, message: &str) {
+        let operation = match self.jobs.read().await.get(job_id) {
+            Some(job) => job.info.operation.clone(),
+            None => return,
+        };
+
+        let message = format!("{} (job {})", message, job_id);
+        if let Err(e) = self.database
+            .close_started_processing_logs(round_id, &operation, status, &message)
+            .await
+        {
+            warn!("Failed to update processing logs of job {}: {}", job_id, e);
+        }
+    }
+
+    async fn purge_finished(&self) {
+        let retention = chrono::Duration::seconds(FINISHED_JOB_RETENTION_SECS);
+        let now = chrono::Utc::now();
+        self.jobs.write().await.retain(|_, job| {
+            job.info.finished_at.is_none_or(|finished_at| now - finished_at < retention)
+        });
+    }
+}
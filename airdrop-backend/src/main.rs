@@ -2,14 +2,17 @@ use anyhow::Result;
 use axum::{
     extract::DefaultBodyLimit,
     http::StatusCode,
+    middleware::from_fn_with_state,
     response::Json,
-    routing::{get, post, delete},
+    routing::{get, post, put, delete},
     Router,
 };
 use std::sync::Arc;
 use tower::ServiceBuilder;
+use tower_http::compression::CompressionLayer;
 use tower_http::cors::CorsLayer;
-use tracing::info;
+use tower_http::decompression::RequestDecompressionLayer;
+use tracing::{info, warn};
 use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
 
 mod config;
@@ -21,9 +24,13 @@ mod service;
 mod handlers;
 mod encryption;
 mod nats_storage;
+mod chunked_upload;
+mod jobs;
 mod error;
 mod external_client;
+mod auth;
 
+use crate::auth::AdminAuth;
 use crate::config::Config;
 use crate::service::AirdropService;
 
@@ -42,9 +49,20 @@ async fn main() -> Result<()> {
     let config_path = std::env::var("CONFIG_PATH").unwrap_or_else(|_| "config.yaml".to_string());
     let config = Config::load_from_file(&config_path).await?;
 
+    let admin_auth = match config.server.auth.as_ref().filter(|auth| auth.enabled) {
+        Some(auth_config) => {
+            info!("Authentication of mutating endpoints is enabled");
+            Some(Arc::new(AdminAuth::from_config(auth_config, config.server.max_upload_size as usize)?))
+        }
+        None => {
+            warn!("Authentication is disabled, mutating endpoints are open");
+            None
+        }
+    };
+
     let service = Arc::new(AirdropService::new(config.clone(), config_path).await?);
 
-    let app = create_app(service).await;
+    let app = create_app(service, admin_auth).await;
 
     let listener = tokio::net::TcpListener::bind(&config.server.bind_address).await?;
     info!("Server running on {}", config.server.bind_address);
@@ -54,36 +72,53 @@ async fn main() -> Result<()> {
     Ok(())
 }
 
-async fn create_app(service: Arc<AirdropService>) -> Router {
+async fn create_app(service: Arc<AirdropService>, admin_auth: Option<Arc<AdminAuth>>) -> Router {
+    // Mutating endpoints require admin authentication
+    let admin = from_fn_with_state(admin_auth, auth::require_admin);
+
     Router::new()
         .route("/health", get(handlers::health_check))
         // CSV endpoints
-        .route("/api/v1/upload-csv", post(handlers::upload_csv))
+        .route("/api/v1/upload-csv", post(handlers::upload_csv).route_layer(admin.clone()))
+        .route("/api/v1/upload-csv/chunked/init", post(handlers::init_chunked_csv_upload).route_layer(admin.clone()))
+        .route("/api/v1/upload-csv/chunked/:upload_id", get(handlers::get_chunked_csv_upload_status))
+        .route("/api/v1/upload-csv/chunked/:upload_id/chunks/:chunk_index", put(handlers::upload_csv_chunk).route_layer(admin.clone()))
+        .route("/api/v1/upload-csv/chunked/:upload_id/finalize", post(handlers::finalize_chunked_csv_upload).route_layer(admin.clone()))
         .route("/api/v1/download-csv/:round_id", get(handlers::download_csv))
         // JSON eligibility endpoints
-        .route("/api/v1/upload-json-eligibility/:round_id", post(handlers::upload_json_eligibility))
+        .route("/api/v1/upload-json-eligibility/:round_id", post(handlers::upload_json_eligibility).route_layer(admin.clone()))
         .route("/api/v1/download-json-eligibility/:round_id", get(handlers::download_json_eligibility))
         // Trie data endpoints
-        .route("/api/v1/download-trie-data/:round_id", get(handlers::download_trie_data))
-        .route("/api/v1/upload-compare-trie/:round_id", post(handlers::upload_and_compare_trie_data))
+        // Compressed when negotiated with Accept-Encoding / Content-Encoding (gzip, zstd)
+        .route("/api/v1/download-trie-data/:round_id", get(handlers::download_trie_data)
+            .layer(CompressionLayer::new().gzip(true).zstd(true)))
+        .route("/api/v1/upload-compare-trie/:round_id", post(handlers::upload_and_compare_trie_data)
+            .layer(RequestDecompressionLayer::new().gzip(true).zstd(true)))
         // External data endpoints
-        .route("/api/v1/fetch-external-data/:round_id", post(handlers::fetch_external_data_and_update))
+        .route("/api/v1/fetch-external-data/:round_id", post(handlers::fetch_external_data_and_update).route_layer(admin.clone()))
         .route("/api/v1/compare-external-trie/:round_id", post(handlers::fetch_and_compare_external_trie))
         // Original endpoints
-        .route("/api/v1/update-trie/:round_id", post(handlers::update_trie))
-        .route("/api/v1/submit-trie/:round_id", post(handlers::submit_trie))
+        .route("/api/v1/update-trie/:round_id", post(handlers::update_trie).route_layer(admin.clone()))
+        .route("/api/v1/submit-trie/:round_id", post(handlers::submit_trie).route_layer(admin.clone()))
         .route("/api/v1/verify-eligibility", post(handlers::verify_eligibility))
+        .route("/api/v1/verify-eligibility-batch", post(handlers::verify_eligibility_batch))
         .route("/api/v1/get-eligibility/:round_id/:address", get(handlers::get_eligibility))
         .route("/api/v1/trie-info/:round_id", get(handlers::get_trie_info))
+        .route("/api/v1/rounds", get(handlers::list_rounds))
         .route("/api/v1/rounds/statistics", get(handlers::get_round_statistics))
         .route("/api/v1/processing-logs", get(handlers::get_processing_logs))
         .route("/api/v1/processing-logs/:round_id", get(handlers::get_round_processing_logs))
-        .route("/api/v1/rounds/:round_id", delete(handlers::delete_round))
+        // Background job endpoints
+        .route("/api/v1/jobs", get(handlers::list_jobs))
+        .route("/api/v1/jobs/:job_id", get(handlers::get_job)
+            .merge(delete(handlers::cancel_job).route_layer(admin.clone())))
+        .route("/api/v1/rounds/:round_id", delete(handlers::delete_round).route_layer(admin))
         // Contract endpoints
         .route("/api/v1/contract/info", get(handlers::get_contract_info))
         .route("/api/v1/rounds/:round_id/active", get(handlers::check_round_active))
         .route("/api/v1/rounds/:round_id/metadata", get(handlers::get_round_metadata))
         .route("/api/v1/rounds/:round_id/validate-consistency", get(handlers::validate_consistency))
+        .route("/api/v1/rounds/:round_id/verify-onchain", get(handlers::verify_onchain_root))
         .with_state(service)
         .layer(
             ServiceBuilder::new()
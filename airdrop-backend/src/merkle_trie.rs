@@ -2,63 +2,59 @@ use anyhow::Result;
 use alloy_primitives::{B256, Address, U256};
 use keccak_hasher::KeccakHasher;
 use hash_db::Hasher as HashDbHasher;
+use sha3::{Digest, Sha3_256};
 use std::collections::BTreeMap;
+use serde::{Deserialize, Serialize};
 use serde_json;
 
+use crate::config::{HashFunction, HashingConfig, LeafSchema};
+
 /// Keccak256 hash using keccak-hasher
 pub fn keccak256(data: &[u8]) -> [u8; 32] {
     KeccakHasher::hash(data)
 }
 
-/// Hash a pair of nodes with sorting (lexicographic order)
-pub fn hash_pair(left: &[u8; 32], right: &[u8; 32]) -> [u8; 32] {
-    let (first, second) = if left >= right {
-        (right, left)
-    } else {
-        (left, right)
-    };
-
-    let mut packed = Vec::with_capacity(64);
-    packed.extend_from_slice(first);
-    packed.extend_from_slice(second);
-
-    keccak256(&packed)
+/// FIPS 202 SHA3-256 hash
+pub fn sha3_256(data: &[u8]) -> [u8; 32] {
+    Sha3_256::digest(data).into()
 }
 
-#[derive(Debug, Clone, PartialEq)]
-pub struct MerkleNode {
-    pub hash: [u8; 32],
-    pub left: Option<Box<MerkleNode>>,
-    pub right: Option<Box<MerkleNode>>,
-    pub data: Option<Vec<u8>>,
-    pub index: Option<usize>,
-}
-
-impl MerkleNode {
-    pub fn new_leaf(data: Vec<u8>, index: usize) -> Self {
-        let hash = keccak256(&data);
-        MerkleNode {
-            hash,
-            left: None,
-            right: None,
-            data: Some(data),
-            index: Some(index),
+impl HashFunction {
+    pub fn hash(&self, data: &[u8]) -> [u8; 32] {
+        match self {
+            HashFunction::Keccak256 => keccak256(data),
+            HashFunction::Sha3_256 => sha3_256(data),
         }
     }
+}
 
-    pub fn new_internal(left: MerkleNode, right: MerkleNode) -> Self {
-        let hash = hash_pair(&left.hash, &right.hash);
-        MerkleNode {
-            hash,
-            left: Some(Box::new(left)),
-            right: Some(Box::new(right)),
-            data: None,
-            index: None,
+impl HashingConfig {
+    /// Hash of the leaf preimage: `leaf_prefix || leaf`, or the packed leaf alone without a prefix
+    pub fn hash_leaf(&self, leaf_data: &[u8]) -> [u8; 32] {
+        match self.leaf_prefix {
+            Some(prefix) => {
+                let mut preimage = Vec::with_capacity(1 + leaf_data.len());
+                preimage.push(prefix);
+                preimage.extend_from_slice(leaf_data);
+                self.hash_function.hash(&preimage)
+            }
+            None => self.hash_function.hash(leaf_data),
         }
     }
 
-    pub fn is_leaf(&self) -> bool {
-        self.left.is_none() && self.right.is_none()
+    /// Hash of a pair of nodes sorted in lexicographic order: `min(left, right) || max(left, right)`
+    pub fn hash_pair(&self, left: &[u8; 32], right: &[u8; 32]) -> [u8; 32] {
+        let (first, second) = if left >= right {
+            (right, left)
+        } else {
+            (left, right)
+        };
+
+        let mut packed = Vec::with_capacity(64);
+        packed.extend_from_slice(first);
+        packed.extend_from_slice(second);
+
+        self.hash_function.hash(&packed)
     }
 }
 
@@ -76,24 +72,92 @@ pub struct ProofElement {
     pub is_right_sibling: bool,
 }
 
+/// Position of the address and amount fields in the packed leaves
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LeafLayout {
+    pub address_offset: usize,
+    pub amount_offset: usize,
+    pub amount_size: usize,
+}
+
+impl Default for LeafLayout {
+    /// `abi.encodePacked(address, amount)` leaves
+    fn default() -> Self {
+        Self {
+            address_offset: 0,
+            amount_offset: 20,
+            amount_size: 32,
+        }
+    }
+}
+
+impl LeafLayout {
+    /// Layout of the leaves of a validated leaf schema
+    pub fn from_schema(schema: &LeafSchema) -> Result<Self> {
+        schema.validate().map_err(|e| anyhow::anyhow!(e))?;
+
+        let amount_field = schema.field(&schema.amount_column)
+            .ok_or_else(|| anyhow::anyhow!("Leaf schema amount column '{}' is not a leaf field", schema.amount_column))?;
+
+        Ok(Self {
+            address_offset: schema.offset(&schema.address_column).unwrap_or_default(),
+            amount_offset: schema.offset(&schema.amount_column).unwrap_or_default(),
+            amount_size: amount_field.field_type.packed_size(),
+        })
+    }
+
+    /// Layout of `abi.encodePacked(address, amount)` leaves, not serialized with the trie
+    fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+
+    fn address<'a>(&self, leaf_data: &'a [u8]) -> Option<&'a [u8]> {
+        leaf_data.get(self.address_offset..self.address_offset + 20)
+    }
+
+    fn amount(&self, leaf_data: &[u8]) -> Option<U256> {
+        leaf_data
+            .get(self.amount_offset..self.amount_offset + self.amount_size)
+            .map(U256::from_be_slice)
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct MerkleTrie {
-    root: Option<MerkleNode>,
+    /// Node hashes of each level, from the leaf hashes up to the root
+    levels: Vec<Vec<[u8; 32]>>,
     ordered_leaves: Vec<Vec<u8>>,
     leaf_index_map: BTreeMap<Vec<u8>, usize>,
+    layout: LeafLayout,
+    hashing: HashingConfig,
 }
 
 impl MerkleTrie {
     pub fn new() -> Self {
+        Self::with_hashing(HashingConfig::default())
+    }
+
+    pub fn with_hashing(hashing: HashingConfig) -> Self {
         MerkleTrie {
-            root: None,
+            levels: Vec::new(),
             ordered_leaves: Vec::new(),
             leaf_index_map: BTreeMap::new(),
+            layout: LeafLayout::default(),
+            hashing,
+        }
+    }
+
+    /// Change the hashing of the trie, rebuilding it
+    pub fn set_hashing(&mut self, hashing: HashingConfig) -> Result<()> {
+        if self.hashing != hashing {
+            self.hashing = hashing;
+            self.build_tree()?;
         }
+        Ok(())
     }
 
     /// Generate leaf data from address and amount (viem-compatible encoding)
-    fn encode_leaf_data(address: &Address, amount: &U256) -> Vec<u8> {
+    pub(crate) fn encode_leaf_data(address: &Address, amount: &U256) -> Vec<u8> {
         let mut packed = Vec::with_capacity(52);
         // Address: 20 bytes
         packed.extend_from_slice(address.as_slice());
@@ -108,14 +172,27 @@ impl MerkleTrie {
         packed
     }
 
-    pub fn update_eligibility_data(&mut self, eligibility_data: std::collections::HashMap<Address, U256>) -> Result<()> {
+    /// Replace the trie leaves by the eligibility entries, in their iteration order
+    pub fn update_eligibility_data(&mut self, eligibility_data: impl IntoIterator<Item = (Address, U256)>) -> Result<()> {
+        let leaves = eligibility_data
+            .into_iter()
+            .map(|(address, amount)| Self::encode_leaf_data(&address, &amount));
+
+        self.update_leaves(leaves, LeafLayout::default())
+    }
+
+    /// Replace the trie leaves by leaves already packed, e.g. of a leaf schema, with their layout
+    pub fn update_leaves(&mut self, leaves: impl IntoIterator<Item = Vec<u8>>, layout: LeafLayout) -> Result<()> {
         // Clear existing leaves
         self.ordered_leaves.clear();
         self.leaf_index_map.clear();
+        self.layout = layout;
 
         // Add all leaves (will be automatically sorted by BTreeMap key ordering)
-        for (address, amount) in eligibility_data {
-            let leaf_data = Self::encode_leaf_data(&address, &amount);
+        for leaf_data in leaves {
+            if self.layout.address(&leaf_data).is_none() || self.layout.amount(&leaf_data).is_none() {
+                return Err(anyhow::anyhow!("Leaf of {} bytes doesn't match the leaf layout {:?}", leaf_data.len(), self.layout));
+            }
             if !self.leaf_index_map.contains_key(&leaf_data) {
                 let index = self.ordered_leaves.len();
                 self.ordered_leaves.push(leaf_data.clone());
@@ -129,65 +206,50 @@ impl MerkleTrie {
         Ok(())
     }
 
+    /// Build the levels as merkle-trie-cli does: the last node of a level with an odd number
+    /// of nodes is paired with itself. Unlike merkle-trie-cli, a single leaf is also paired
+    /// with itself into the root, keeping the roots of the rounds already submitted.
     fn build_tree(&mut self) -> Result<()> {
+        self.levels.clear();
         if self.ordered_leaves.is_empty() {
-            self.root = None;
             return Ok(());
         }
 
-        let mut current_level: Vec<MerkleNode> = self
+        let mut current_level: Vec<[u8; 32]> = self
             .ordered_leaves
             .iter()
-            .enumerate()
-            .map(|(i, data)| MerkleNode::new_leaf(data.clone(), i))
+            .map(|data| self.hashing.hash_leaf(data))
             .collect();
 
-        if current_level.len() % 2 == 1 {
-            let last_node = current_level.last().unwrap().clone();
-            current_level.push(last_node);
-        }
+        loop {
+            let next_level: Vec<[u8; 32]> = current_level
+                .chunks(2)
+                .map(|pair| self.hashing.hash_pair(&pair[0], pair.get(1).unwrap_or(&pair[0])))
+                .collect();
 
-        while current_level.len() > 1 {
-            let mut next_level = Vec::new();
-
-            for chunk in current_level.chunks(2) {
-                if chunk.len() == 2 {
-                    let internal_node = MerkleNode::new_internal(chunk[0].clone(), chunk[1].clone());
-                    next_level.push(internal_node);
-                } else {
-                    next_level.push(chunk[0].clone());
-                }
-            }
-
-            if next_level.len() % 2 == 1 && next_level.len() > 1 {
-                let last_node = next_level.last().unwrap().clone();
-                next_level.push(last_node);
+            self.levels.push(current_level);
+            if next_level.len() == 1 {
+                self.levels.push(next_level);
+                break;
             }
-
             current_level = next_level;
         }
 
-        self.root = current_level.into_iter().next();
         Ok(())
     }
 
     pub fn get_root_hash(&self) -> B256 {
-        self.root
-            .as_ref()
-            .map(|node| B256::from_slice(&node.hash))
+        self.levels
+            .last()
+            .map(|root_level| B256::from_slice(&root_level[0]))
             .unwrap_or_else(|| B256::ZERO)
     }
 
     pub fn get_value(&self, address: &Address) -> Result<Option<U256>> {
         // Search through leaves for this address
         for leaf_data in &self.ordered_leaves {
-            if leaf_data.len() >= 20 && &leaf_data[0..20] == address.as_slice() {
-                // Extract amount from leaf data (bytes 20-52)
-                if leaf_data.len() >= 52 {
-                    let amount_bytes = &leaf_data[20..52];
-                    let amount = U256::from_be_slice(amount_bytes);
-                    return Ok(Some(amount));
-                }
+            if self.layout.address(leaf_data) == Some(address.as_slice()) {
+                return Ok(self.layout.amount(leaf_data));
             }
         }
         Ok(None)
@@ -197,7 +259,7 @@ impl MerkleTrie {
         // Find the leaf index for this address
         let mut leaf_index = None;
         for (idx, leaf_data) in self.ordered_leaves.iter().enumerate() {
-            if leaf_data.len() >= 20 && &leaf_data[0..20] == address.as_slice() {
+            if self.layout.address(leaf_data) == Some(address.as_slice()) {
                 leaf_index = Some(idx);
                 break;
             }
@@ -212,23 +274,51 @@ impl MerkleTrie {
         Ok(proof.siblings.iter().map(|p| p.hash.to_vec()).collect())
     }
 
+    /// Get amounts and Merkle proofs for many addresses, indexing the leaves once.
+    /// Addresses not found in the trie yield `None`.
+    pub fn compute_merkle_proofs(&self, addresses: &[Address]) -> Result<Vec<Option<(U256, Vec<Vec<u8>>)>>> {
+        let mut address_index: std::collections::HashMap<&[u8], usize> = std::collections::HashMap::new();
+        for (idx, leaf_data) in self.ordered_leaves.iter().enumerate() {
+            if let (Some(address), Some(_)) = (self.layout.address(leaf_data), self.layout.amount(leaf_data)) {
+                address_index.entry(address).or_insert(idx);
+            }
+        }
+
+        addresses
+            .iter()
+            .map(|address| {
+                let Some(&leaf_index) = address_index.get(address.as_slice()) else {
+                    return Ok(None);
+                };
+
+                let proof = self.generate_proof_by_index(leaf_index)
+                    .ok_or_else(|| anyhow::anyhow!("Failed to generate proof"))?;
+                let amount = self.layout.amount(&proof.leaf_data).unwrap_or_default();
+
+                Ok(Some((amount, proof.siblings.iter().map(|p| p.hash.to_vec()).collect())))
+            })
+            .collect()
+    }
+
     fn generate_proof_by_index(&self, leaf_index: usize) -> Option<MerkleProof> {
         if leaf_index >= self.ordered_leaves.len() {
             return None;
         }
 
-        let root = self.root.as_ref()?;
+        // Siblings from the leaf level up, a node without a sibling being paired with itself
         let mut siblings = Vec::new();
-
-        let mut actual_tree_size = self.ordered_leaves.len();
-        if actual_tree_size % 2 == 1 {
-            actual_tree_size += 1;
+        let mut index = leaf_index;
+        for level in &self.levels[..self.levels.len() - 1] {
+            let sibling_index = index ^ 1;
+            siblings.push(ProofElement {
+                hash: *level.get(sibling_index).unwrap_or(&level[index]),
+                is_right_sibling: sibling_index > index,
+            });
+            index /= 2;
         }
 
-        self.collect_siblings(root, leaf_index, actual_tree_size, 0, &mut siblings);
-
         let leaf_data = self.ordered_leaves[leaf_index].clone();
-        let leaf_hash = keccak256(&leaf_data);
+        let leaf_hash = self.levels[0][leaf_index];
 
         Some(MerkleProof {
             leaf_index,
@@ -238,75 +328,46 @@ impl MerkleTrie {
         })
     }
 
-    fn collect_siblings(
-        &self,
-        node: &MerkleNode,
-        target_index: usize,
-        tree_width: usize,
-        _level: usize,
-        siblings: &mut Vec<ProofElement>,
-    ) {
-        if node.is_leaf() {
-            return;
-        }
-
-        let left_child = node.left.as_ref().unwrap();
-        let right_child = node.right.as_ref().unwrap();
-
-        let mid_point = tree_width / 2;
-
-        if target_index < mid_point {
-            siblings.push(ProofElement {
-                hash: right_child.hash,
-                is_right_sibling: true,
-            });
-            self.collect_siblings(left_child, target_index, mid_point, _level + 1, siblings);
-        } else {
-            siblings.push(ProofElement {
-                hash: left_child.hash,
-                is_right_sibling: false,
-            });
-            self.collect_siblings(
-                right_child,
-                target_index - mid_point,
-                tree_width - mid_point,
-                _level + 1,
-                siblings,
-            );
-        }
-    }
-
     pub fn serialize(&self) -> Result<Vec<u8>> {
-        use serde::{Serialize, Deserialize};
-
         #[derive(Serialize, Deserialize)]
         struct SerializedTrie {
             root_hash: Vec<u8>,
             ordered_leaves: Vec<Vec<u8>>,
+            // Tries of the default leaves serialize as before leaf schemas
+            #[serde(skip_serializing_if = "LeafLayout::is_default")]
+            layout: LeafLayout,
+            hashing: HashingConfig,
         }
 
         let serialized = SerializedTrie {
             root_hash: self.get_root_hash().to_vec(),
             ordered_leaves: self.ordered_leaves.clone(),
+            layout: self.layout,
+            hashing: self.hashing,
         };
 
         serde_json::to_vec(&serialized).map_err(|e| anyhow::anyhow!("Serialization failed: {}", e))
     }
 
     pub fn deserialize(data: &[u8]) -> Result<Self> {
-        use serde::{Serialize, Deserialize};
-
         #[derive(Serialize, Deserialize)]
         struct SerializedTrie {
             root_hash: Vec<u8>,
             ordered_leaves: Vec<Vec<u8>>,
+            // Tries serialized before leaf schemas have the default layout
+            #[serde(default)]
+            layout: LeafLayout,
+            // and before the hashing configuration keccak256 leaves without a prefix
+            #[serde(default)]
+            hashing: HashingConfig,
         }
 
         let serialized: SerializedTrie = serde_json::from_slice(data)
             .map_err(|e| anyhow::anyhow!("Deserialization failed: {}", e))?;
 
-        let mut trie = MerkleTrie::new();
+        let mut trie = MerkleTrie::with_hashing(serialized.hashing);
         trie.ordered_leaves = serialized.ordered_leaves;
+        trie.layout = serialized.layout;
 
         // Rebuild index map
         for (idx, leaf_data) in trie.ordered_leaves.iter().enumerate() {
@@ -322,6 +383,11 @@ impl MerkleTrie {
     pub fn get_leaf_count(&self) -> usize {
         self.ordered_leaves.len()
     }
+
+    /// Packed leaves in the trie order
+    pub fn leaves(&self) -> &[Vec<u8>] {
+        &self.ordered_leaves
+    }
 }
 
 impl Default for MerkleTrie {
@@ -363,6 +429,36 @@ mod tests {
         assert!(!proof.is_empty());
     }
 
+    #[test]
+    fn test_compute_merkle_proofs_batch() {
+        let mut trie = MerkleTrie::new();
+        let mut data = std::collections::HashMap::new();
+
+        let addr1 = Address::from_str("0x742C4d97C86bCF0176776C16e073b8c6f9Db4021").unwrap();
+        let addr2 = Address::from_str("0x0000000000000000000000000000000000000002").unwrap();
+        let addr3 = Address::from_str("0x0000000000000000000000000000000000000003").unwrap();
+        let missing = Address::from_str("0x0000000000000000000000000000000000000004").unwrap();
+
+        data.insert(addr1, U256::from(1000u64));
+        data.insert(addr2, U256::from(2000u64));
+        data.insert(addr3, U256::from(3000u64));
+
+        trie.update_eligibility_data(data).unwrap();
+
+        let results = trie.compute_merkle_proofs(&[addr2, missing, addr1]).unwrap();
+        assert_eq!(results.len(), 3);
+
+        let (amount, proof) = results[0].clone().unwrap();
+        assert_eq!(amount, U256::from(2000u64));
+        assert_eq!(proof, trie.compute_merkle_proof(&addr2).unwrap());
+
+        assert!(results[1].is_none());
+
+        let (amount, proof) = results[2].clone().unwrap();
+        assert_eq!(amount, U256::from(1000u64));
+        assert_eq!(proof, trie.compute_merkle_proof(&addr1).unwrap());
+    }
+
     #[test]
     fn test_serialization() {
         let mut trie = MerkleTrie::new();
@@ -377,5 +473,181 @@ mod tests {
         let deserialized = MerkleTrie::deserialize(&serialized).unwrap();
 
         assert_eq!(trie.get_root_hash(), deserialized.get_root_hash());
+
+        // The default layout isn't serialized
+        let json: serde_json::Value = serde_json::from_slice(&serialized).unwrap();
+        assert!(json.get("layout").is_none());
+    }
+
+    #[test]
+    fn test_schema_leaf_layout() {
+        use crate::config::{LeafField, LeafFieldType};
+
+        let schema = LeafSchema {
+            address_column: "wallet".to_string(),
+            amount_column: "allocation".to_string(),
+            fields: vec![
+                LeafField { column: "index".to_string(), field_type: LeafFieldType::Uint32 },
+                LeafField { column: "wallet".to_string(), field_type: LeafFieldType::Address },
+                LeafField { column: "allocation".to_string(), field_type: LeafFieldType::Uint128 },
+                LeafField { column: "tier".to_string(), field_type: LeafFieldType::Uint8 },
+            ],
+        };
+        let layout = LeafLayout::from_schema(&schema).unwrap();
+        assert_eq!(layout, LeafLayout { address_offset: 4, amount_offset: 24, amount_size: 16 });
+        assert_eq!(LeafLayout::from_schema(&LeafSchema::default()).unwrap(), LeafLayout::default());
+
+        let addr = Address::from_str("0x742C4d97C86bCF0176776C16e073b8c6f9Db4021").unwrap();
+        let mut leaf = vec![0, 0, 0, 7];
+        leaf.extend_from_slice(addr.as_slice());
+        leaf.extend_from_slice(&1000u128.to_be_bytes());
+        leaf.push(2);
+
+        let mut trie = MerkleTrie::new();
+        trie.update_leaves(vec![leaf.clone()], layout).unwrap();
+        assert_eq!(trie.get_value(&addr).unwrap(), Some(U256::from(1000u64)));
+        assert_eq!(trie.compute_merkle_proofs(&[addr]).unwrap()[0].as_ref().unwrap().0, U256::from(1000u64));
+
+        let deserialized = MerkleTrie::deserialize(&trie.serialize().unwrap()).unwrap();
+        assert_eq!(deserialized.get_value(&addr).unwrap(), Some(U256::from(1000u64)));
+        assert_eq!(deserialized.layout, layout);
+
+        // Leaves too short for the layout are rejected
+        assert!(trie.update_leaves(vec![leaf[..30].to_vec()], layout).is_err());
+    }
+
+    #[test]
+    fn test_hashing_config() {
+        let expected = hex::decode("a7ffc6f8bf1ed76651c14756a061d662f580ff4de43b49fa82d80a4b80f8434a").unwrap();
+        assert_eq!(sha3_256(b"").to_vec(), expected);
+        assert_ne!(sha3_256(b"hello world"), keccak256(b"hello world"));
+
+        let keccak = HashingConfig::default();
+        assert_eq!(keccak.hash_leaf(b"leaf"), keccak256(b"leaf"));
+        assert_eq!(keccak.hash_pair(&[2; 32], &[1; 32]), keccak.hash_pair(&[1; 32], &[2; 32]));
+
+        let prefixed = HashingConfig { leaf_prefix: Some(0x00), ..Default::default() };
+        assert_eq!(prefixed.hash_leaf(b"leaf"), keccak256(b"\x00leaf"));
+
+        let sha3 = HashingConfig { hash_function: HashFunction::Sha3_256, ..Default::default() };
+        assert_eq!(sha3.hash_leaf(b"leaf"), sha3_256(b"leaf"));
+        // The EVM verifier can't hash with SHA3-256
+        assert!(sha3.validate().is_err());
+        assert!(HashingConfig { off_chain_verifier: true, ..sha3 }.validate().is_ok());
+
+        let addr = Address::from_str("0x742C4d97C86bCF0176776C16e073b8c6f9Db4021").unwrap();
+        let data = std::collections::HashMap::from([(addr, U256::from(1000u64))]);
+        let mut trie = MerkleTrie::with_hashing(prefixed);
+        trie.update_eligibility_data(data).unwrap();
+
+        let deserialized = MerkleTrie::deserialize(&trie.serialize().unwrap()).unwrap();
+        assert_eq!(deserialized.hashing, prefixed);
+        assert_eq!(deserialized.get_root_hash(), trie.get_root_hash());
+
+        let root = trie.get_root_hash();
+        trie.set_hashing(keccak).unwrap();
+        assert_ne!(trie.get_root_hash(), root);
+    }
+
+    /// Cross-check against merkle-trie-cli: `proofs_reference.json` is its output
+    /// of `mock_addresses_wei.csv`, leaves in the CSV order
+    #[test]
+    fn test_merkle_trie_cli_reference() {
+        let reference: serde_json::Value = serde_json::from_str(include_str!("../proofs_reference.json")).unwrap();
+        let leaves: Vec<Vec<u8>> = include_str!("../mock_addresses_wei.csv")
+            .lines()
+            .skip(1)
+            .filter(|line| !line.trim().is_empty())
+            .map(|line| {
+                let (address, amount) = line.trim().split_once(',').unwrap();
+                MerkleTrie::encode_leaf_data(&Address::from_str(address).unwrap(), &U256::from_str(amount).unwrap())
+            })
+            .collect();
+
+        let mut trie = MerkleTrie::new();
+        trie.update_leaves(leaves, LeafLayout::default()).unwrap();
+        assert_eq!(format!("{:?}", trie.get_root_hash()), reference["root_hash"].as_str().unwrap());
+
+        let allocations = reference["allocations"].as_object().unwrap();
+        assert_eq!(allocations.len(), trie.get_leaf_count());
+        for (address, allocation) in allocations {
+            let address = Address::from_str(address).unwrap();
+            let expected: Vec<Vec<u8>> = allocation["proof"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .map(|sibling| hex::decode(sibling.as_str().unwrap().trim_start_matches("0x")).unwrap())
+                .collect();
+
+            assert_eq!(trie.get_value(&address).unwrap().unwrap().to_string(), allocation["allocation"].as_str().unwrap());
+            assert_eq!(trie.compute_merkle_proof(&address).unwrap(), expected, "proof of {}", address);
+        }
+    }
+
+    /// Proofs of every hashing and leaf layout variant verify against their root when folded
+    /// as a verifier does, hashing independently of `HashingConfig`: merkle-trie-cli has
+    /// no prefix, SHA3-256 nor leaf schema support to cross-check them against
+    #[test]
+    fn test_hashing_and_layout_variants_proofs() {
+        let addresses: Vec<Address> = (1u8..=5).map(Address::repeat_byte).collect();
+        let default_leaves: Vec<Vec<u8>> = addresses
+            .iter()
+            .enumerate()
+            .map(|(i, address)| MerkleTrie::encode_leaf_data(address, &U256::from(1000 * (i as u64 + 1))))
+            .collect();
+        // uint32 index, address, uint128 amount, uint8 tier
+        let schema_layout = LeafLayout { address_offset: 4, amount_offset: 24, amount_size: 16 };
+        let schema_leaves: Vec<Vec<u8>> = addresses
+            .iter()
+            .enumerate()
+            .map(|(i, address)| {
+                let mut leaf = (i as u32).to_be_bytes().to_vec();
+                leaf.extend_from_slice(address.as_slice());
+                leaf.extend_from_slice(&(1000 * (i as u128 + 1)).to_be_bytes());
+                leaf.push(i as u8 % 2);
+                leaf
+            })
+            .collect();
+
+        let prefixed = HashingConfig { leaf_prefix: Some(0x00), ..Default::default() };
+        let sha3 = HashingConfig { hash_function: HashFunction::Sha3_256, off_chain_verifier: true, ..Default::default() };
+        let variants = [
+            (HashingConfig::default(), &default_leaves, LeafLayout::default()),
+            (prefixed, &default_leaves, LeafLayout::default()),
+            (sha3, &default_leaves, LeafLayout::default()),
+            (HashingConfig::default(), &schema_leaves, schema_layout),
+            (prefixed, &schema_leaves, schema_layout),
+        ];
+
+        let mut roots = Vec::new();
+        for (hashing, leaves, layout) in variants {
+            let hash: fn(&[u8]) -> [u8; 32] = match hashing.hash_function {
+                HashFunction::Keccak256 => keccak256,
+                HashFunction::Sha3_256 => sha3_256,
+            };
+
+            let mut trie = MerkleTrie::with_hashing(hashing);
+            trie.update_leaves(leaves.clone(), layout).unwrap();
+            let root = trie.get_root_hash();
+
+            for (leaf, address) in leaves.iter().zip(&addresses) {
+                let mut preimage = hashing.leaf_prefix.map(|prefix| vec![prefix]).unwrap_or_default();
+                preimage.extend_from_slice(leaf);
+                let mut node = hash(&preimage);
+
+                for sibling in trie.compute_merkle_proof(address).unwrap() {
+                    let sibling: [u8; 32] = sibling.try_into().unwrap();
+                    let (first, second) = if node <= sibling { (node, sibling) } else { (sibling, node) };
+                    node = hash(&[first, second].concat());
+                }
+                assert_eq!(B256::from(node), root, "proof of {} with {:?} and {:?}", address, hashing, layout);
+            }
+            roots.push(root);
+        }
+
+        // Each variant yields its own root
+        for (i, root) in roots.iter().enumerate() {
+            assert!(!roots[i + 1..].contains(root));
+        }
     }
 }
@@ -5,6 +5,12 @@ use std::io::Cursor;
 use futures::StreamExt;
 use tokio::io::AsyncReadExt;
 
+use crate::config::ObjectStoreEncryptionConfig;
+use crate::encryption::{AuthenticatedEncryption, StaticKeyEncryption};
+
+/// Prefix marking encrypted objects, followed by the JSON serialized `AuthenticatedEncryption`
+const ENCRYPTED_OBJECT_MAGIC: &[u8] = b"AIRDROP-ENC-V1\n";
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct StoredTrieData {
     pub round_id: u32,
@@ -21,13 +27,36 @@ pub struct TrieMetadata {
     pub entry_count: usize,
 }
 
+/// Encryption at rest of the stored objects
+pub struct ObjectEncryption {
+    cipher: StaticKeyEncryption,
+    rounds: Option<Vec<u32>>,
+}
+
+impl ObjectEncryption {
+    pub fn from_config(config: &ObjectStoreEncryptionConfig) -> Result<Self> {
+        let key = config.resolve_key()
+            .ok_or_else(|| anyhow::anyhow!("Object store encryption is enabled, but no encryption key is supplied"))?;
+
+        Ok(Self {
+            cipher: StaticKeyEncryption::from_hex_key(&key)?,
+            rounds: config.rounds.clone(),
+        })
+    }
+
+    pub fn is_enabled_for_round(&self, round_id: u32) -> bool {
+        self.rounds.as_ref().is_none_or(|rounds| rounds.contains(&round_id))
+    }
+}
+
 pub struct NatsObjectStorage {
     jetstream: jetstream::Context,
     object_store: jetstream::object_store::ObjectStore,
+    encryption: Option<ObjectEncryption>,
 }
 
 impl NatsObjectStorage {
-    pub async fn new(nats_url: &str, bucket_name: String) -> Result<Self> {
+    pub async fn new(nats_url: &str, bucket_name: String, encryption: Option<ObjectEncryption>) -> Result<Self> {
         let client = async_nats::connect(nats_url).await?;
         let jetstream = jetstream::new(client);
 
@@ -47,13 +76,46 @@ impl NatsObjectStorage {
         Ok(Self {
             jetstream,
             object_store,
+            encryption,
         })
     }
 
+    /// Encrypt object data if encryption is enabled for the round.
+    /// The object name is authenticated along with the data, so objects can't be swapped.
+    fn seal(&self, round_id: u32, object_name: &str, data: Vec<u8>) -> Result<Vec<u8>> {
+        let Some(encryption) = self.encryption.as_ref().filter(|e| e.is_enabled_for_round(round_id)) else {
+            return Ok(data);
+        };
+
+        let encrypted = encryption.cipher.encrypt(&data, object_name.as_bytes())?;
+
+        let mut sealed = ENCRYPTED_OBJECT_MAGIC.to_vec();
+        sealed.extend(serde_json::to_vec(&encrypted)?);
+        Ok(sealed)
+    }
+
+    /// Decrypt and verify object data if it's encrypted
+    fn open(&self, round_id: u32, object_name: &str, data: Vec<u8>) -> Result<Vec<u8>> {
+        let Some(encrypted) = data.strip_prefix(ENCRYPTED_OBJECT_MAGIC) else {
+            // Plaintext object where encryption is expected means it was replaced
+            if self.encryption.as_ref().is_some_and(|e| e.is_enabled_for_round(round_id)) {
+                return Err(anyhow::anyhow!("Object {} is expected to be encrypted, but stored as plaintext", object_name));
+            }
+            return Ok(data);
+        };
+
+        let encryption = self.encryption.as_ref()
+            .ok_or_else(|| anyhow::anyhow!("Object {} is encrypted, but no encryption key is configured", object_name))?;
+
+        let encrypted: AuthenticatedEncryption = serde_json::from_slice(encrypted)?;
+        encryption.cipher.decrypt(&encrypted, object_name.as_bytes())
+            .map_err(|e| anyhow::anyhow!("Failed to decrypt object {}: {}", object_name, e))
+    }
+
     pub async fn store_trie_data(&self, round_id: u32, data: &StoredTrieData) -> Result<()> {
         let object_name = format!("trie_round_{}", round_id);
         let serialized = serde_json::to_vec(data)?;
-        let mut cursor = Cursor::new(serialized);
+        let mut cursor = Cursor::new(self.seal(round_id, &object_name, serialized)?);
 
         self.object_store
             .put(object_name.as_str(), &mut cursor)
@@ -71,20 +133,43 @@ impl NatsObjectStorage {
         let mut data = Vec::new();
         object.read_to_end(&mut data).await?;
 
+        let data = self.open(round_id, &object_name, data)?;
         let stored_data: StoredTrieData = serde_json::from_slice(&data)?;
         Ok(stored_data)
     }
 
-    pub async fn delete_trie_data(&self, round_id: u32) -> Result<()> {
+    /// Delete object, returns `false` if it doesn't exist
+    async fn delete_object(&self, object_name: &str) -> Result<bool> {
+        match self.object_store.delete(object_name).await {
+            Ok(()) => Ok(true),
+            Err(e) if matches!(e.kind(), jetstream::object_store::DeleteErrorKind::NotFound) => Ok(false),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// Delete trie data, returns the object name if it existed
+    pub async fn delete_trie_data(&self, round_id: u32) -> Result<Option<String>> {
         let object_name = format!("trie_round_{}", round_id);
-        self.object_store.delete(&object_name).await?;
-        tracing::info!("Deleted trie data for round {} from NATS", round_id);
-        Ok(())
+        let deleted = self.delete_object(&object_name).await?;
+        if deleted {
+            tracing::info!("Deleted trie data for round {} from NATS", round_id);
+        }
+        Ok(deleted.then_some(object_name))
+    }
+
+    /// Delete CSV data, returns the object name if it existed
+    pub async fn delete_csv_data(&self, round_id: u32) -> Result<Option<String>> {
+        let object_name = format!("csv_round_{}", round_id);
+        let deleted = self.delete_object(&object_name).await?;
+        if deleted {
+            tracing::info!("Deleted CSV data for round {} from NATS", round_id);
+        }
+        Ok(deleted.then_some(object_name))
     }
 
     pub async fn store_csv_data(&self, round_id: u32, csv_data: &[u8]) -> Result<String> {
         let object_name = format!("csv_round_{}", round_id);
-        let mut cursor = Cursor::new(csv_data.to_vec());
+        let mut cursor = Cursor::new(self.seal(round_id, &object_name, csv_data.to_vec())?);
 
         self.object_store
             .put(object_name.as_str(), &mut cursor)
@@ -102,7 +187,7 @@ impl NatsObjectStorage {
         let mut data = Vec::new();
         object.read_to_end(&mut data).await?;
 
-        Ok(data)
+        self.open(round_id, &object_name, data)
     }
 
     pub async fn list_trie_objects(&self) -> Result<Vec<String>> {
@@ -4,15 +4,46 @@ use std::sync::Arc;
 use tracing::{info, warn};
 use alloy_primitives::{Address, B256, U256};
 
-use crate::config::Config;
-use crate::database::{Database, TrieState, EligibilityRecord, ProcessingLog};
-use crate::merkle_trie::MerkleTrie;
+use crate::config::{AddressFormat, Config, HashingConfig, LeafSchema, DEFAULT_JOB_TIMEOUT_SECS, DEFAULT_MAX_ELIGIBILITY_BATCH_SIZE};
+use crate::chunked_upload::{ChunkedUploads, UploadStatus};
+use crate::jobs::{JobInfo, JobTracker};
+use crate::database::{Database, DeletedRoundData, TrieState, EligibilityRecord, ProcessingLog, RoundSummary};
+use crate::merkle_trie::{LeafLayout, MerkleTrie};
 use crate::csv_processor::CsvProcessor;
-use crate::contract_client::{ContractClient, RoundMetadata};
+use crate::contract_client::{ContractClient, RoundMetadata, TrieUpdateCall};
 use crate::encryption::KmsEnvelopeEncryption;
-use crate::nats_storage::{NatsObjectStorage, StoredTrieData, TrieMetadata};
+use crate::nats_storage::{NatsObjectStorage, ObjectEncryption, StoredTrieData, TrieMetadata};
 use crate::error::{AppError, AppResult, DatabaseError, NatsError};
-use crate::external_client::ExternalBackendClient;
+use crate::external_client::{ExternalBackendClient, ExternalFetchFailure};
+
+/// Summary of the data removed by a round deletion
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct RoundDeletionSummary {
+    pub round_id: u32,
+    pub database: DeletedRoundData,
+    pub deleted_objects: Vec<String>,
+    pub failed_objects: Vec<String>,
+}
+
+/// Root recomputed from the stored eligibility data compared against the on-chain root
+#[derive(Debug, Clone)]
+pub struct OnChainRootVerification {
+    pub round_id: u32,
+    pub entry_count: usize,
+    pub computed_root: B256,
+    pub stored_root: Option<B256>,
+    pub on_chain_root: B256,
+    /// Whether the contract verifier accepts a proof of the recomputed trie, checking its leaf
+    /// preimage and hashing match the configured ones. Not probed for leaf schema rounds,
+    /// as the verifier is given the address and amount only, nor with mismatching roots.
+    pub verifier_accepts_proof: Option<bool>,
+}
+
+impl OnChainRootVerification {
+    pub fn matches(&self) -> bool {
+        self.computed_root == self.on_chain_root && self.verifier_accepts_proof != Some(false)
+    }
+}
 
 pub struct AirdropService {
     database: Arc<Database>,
@@ -21,11 +52,90 @@ pub struct AirdropService {
     encryption: KmsEnvelopeEncryption,
     external_client: ExternalBackendClient,
     tries: tokio::sync::RwLock<HashMap<u32, MerkleTrie>>,
+    chunked_uploads: Arc<ChunkedUploads>,
+    jobs: Arc<JobTracker>,
+    max_eligibility_batch_size: usize,
+    address_format: AddressFormat,
+    reject_duplicate_addresses: bool,
+    leaf_schema: Option<LeafSchema>,
+    leaf_layout: LeafLayout,
+    hashing: HashingConfig,
     config_path: String,
 }
 
+/// Update the trie with the eligibility data, or with its leaves packed by the leaf schema if given.
+/// The leaves follow the eligibility data order, the one its records are saved to the database in,
+/// so the root recomputed from the records matches (see [`rebuild_round_trie`]).
+fn update_trie_leaves(
+    trie: &mut MerkleTrie,
+    eligibility_data: &HashMap<Address, U256>,
+    schema_leaves: Option<&HashMap<Address, Vec<u8>>>,
+    hashing: HashingConfig,
+    leaf_layout: LeafLayout,
+) -> AppResult<()> {
+    trie.set_hashing(hashing).map_err(|e| AppError::Internal(e))?;
+    match schema_leaves {
+        Some(leaves) => eligibility_data
+            .keys()
+            .map(|address| {
+                leaves.get(address).cloned()
+                    .ok_or_else(|| anyhow::anyhow!("No packed leaf of the eligible address {}", address))
+            })
+            .collect::<Result<Vec<_>>>()
+            .and_then(|leaves| trie.update_leaves(leaves, leaf_layout)),
+        None => trie.update_eligibility_data(eligibility_data.iter().map(|(address, amount)| (*address, *amount))),
+    }
+    .map_err(|e| AppError::Internal(e))
+}
+
+/// Rebuild a round trie from its eligibility records, from their packed leaves when all of them have one
+/// (leaf schema rounds). The leaves keep the order of `stored_leaves`, those of the trie the round root
+/// was built from, leaves missing from it (and all of them without it) keep the records order.
+fn rebuild_round_trie(
+    records: Vec<EligibilityRecord>,
+    stored_leaves: Option<&[Vec<u8>]>,
+    hashing: HashingConfig,
+    leaf_layout: LeafLayout,
+) -> Result<MerkleTrie> {
+    let schema_leaves = records.iter().all(|record| record.leaf_data.is_some());
+    let layout = if schema_leaves { leaf_layout } else { LeafLayout::default() };
+
+    let mut leaves: Vec<Vec<u8>> = records
+        .into_iter()
+        .map(|record| match record.leaf_data {
+            Some(leaf_data) if schema_leaves => leaf_data,
+            _ => MerkleTrie::encode_leaf_data(&record.address, &record.amount),
+        })
+        .collect();
+
+    if let Some(stored_leaves) = stored_leaves {
+        let positions: HashMap<&[u8], usize> = stored_leaves
+            .iter()
+            .enumerate()
+            .map(|(position, leaf)| (leaf.as_slice(), position))
+            .collect();
+        // Stable sort, the leaves missing from the stored trie go last in the records order
+        leaves.sort_by_key(|leaf| positions.get(leaf.as_slice()).copied().unwrap_or(usize::MAX));
+    }
+
+    let mut trie = MerkleTrie::with_hashing(hashing);
+    trie.update_leaves(leaves, layout)?;
+    Ok(trie)
+}
+
 impl AirdropService {
     pub async fn new(mut config: Config, config_path: String) -> AppResult<Self> {
+        let leaf_schema = config.eligibility.as_ref().and_then(|eligibility| eligibility.leaf_schema.clone());
+        let leaf_layout = match &leaf_schema {
+            Some(schema) => LeafLayout::from_schema(schema)
+                .map_err(|e| AppError::InvalidInput(format!("Invalid eligibility leaf schema: {}", e)))?,
+            None => LeafLayout::default(),
+        };
+
+        let hashing = config.eligibility.as_ref().and_then(|eligibility| eligibility.hashing).unwrap_or_default();
+        hashing.validate()
+            .map_err(|e| AppError::InvalidInput(format!("Invalid eligibility hashing: {}", e)))?;
+
         let encryption = KmsEnvelopeEncryption::new(&config.aws.region, config.aws.kms_key_id.clone())
             .await
             .map_err(|e| AppError::Encryption(format!("KMS initialization failed: {}", e)))?;
@@ -68,22 +178,54 @@ impl AirdropService {
             &config,
         ).await?;
 
+        let object_encryption = match config.nats.object_store.encryption.as_ref().filter(|e| e.enabled) {
+            Some(encryption_config) => {
+                info!("NATS object store encryption at rest is enabled");
+                Some(ObjectEncryption::from_config(encryption_config)
+                    .map_err(|e| AppError::Encryption(format!("Object store encryption initialization failed: {}", e)))?)
+            }
+            None => None,
+        };
+
         let nats_storage = NatsObjectStorage::new(
             &config.nats.url,
             config.nats.object_store.bucket_name.clone(),
+            object_encryption,
         )
         .await
         .map_err(|e| AppError::Nats(NatsError::App(anyhow::anyhow!("NATS storage connection or initialization error: {}", e))))?;
 
-        let external_client = ExternalBackendClient::new();
+        let reject_duplicate_addresses = config.eligibility.as_ref().is_some_and(|eligibility| eligibility.reject_duplicate_addresses);
+        let external_client = ExternalBackendClient::new(&config.external_fetch.clone().unwrap_or_default(), reject_duplicate_addresses)
+            .map_err(|e| AppError::Internal(anyhow::anyhow!("External backend client initialization failed: {}", e)))?;
+
+        let chunked_uploads = Arc::new(ChunkedUploads::new(
+            &config.server.chunked_upload.clone().unwrap_or_default()
+        ));
+        Self::spawn_chunked_uploads_cleanup(Arc::clone(&chunked_uploads));
+
+        let database = Arc::new(database);
+        let jobs = Arc::new(JobTracker::new(
+            Arc::clone(&database),
+            std::time::Duration::from_secs(config.server.job_timeout_secs.unwrap_or(DEFAULT_JOB_TIMEOUT_SECS)),
+        ));
 
         let mut service = Self {
-            database: Arc::new(database),
+            database,
             contract_client,
             nats_storage,
             encryption,
             external_client,
             tries: tokio::sync::RwLock::new(HashMap::new()),
+            chunked_uploads,
+            jobs,
+            max_eligibility_batch_size: config.server.max_eligibility_batch_size
+                .unwrap_or(DEFAULT_MAX_ELIGIBILITY_BATCH_SIZE),
+            address_format: config.eligibility.clone().unwrap_or_default().address_format,
+            reject_duplicate_addresses,
+            leaf_schema,
+            leaf_layout,
+            hashing,
             config_path,
         };
 
@@ -92,6 +234,20 @@ impl AirdropService {
         Ok(service)
     }
 
+    fn spawn_chunked_uploads_cleanup(chunked_uploads: Arc<ChunkedUploads>) {
+        let period = (chunked_uploads.ttl() / 2).max(std::time::Duration::from_secs(1));
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(period);
+            loop {
+                interval.tick().await;
+                let purged = chunked_uploads.purge_expired().await;
+                if purged > 0 {
+                    info!("Discarded {} expired chunked CSV uploads", purged);
+                }
+            }
+        });
+    }
+
     async fn load_tries_from_database(&self) -> AppResult<()> {
         let trie_states = self.database.get_all_trie_states().await?;
         let mut tries = self.tries.write().await;
@@ -107,7 +263,7 @@ impl AirdropService {
         Ok(())
     }
 
-    pub async fn process_csv_data(&self, csv_data: &[u8], round_id: u32) -> AppResult<()> {
+    pub async fn process_csv_data(&self, csv_data: Vec<u8>, round_id: u32) -> AppResult<()> {
         info!("Processing CSV data for round {}", round_id);
 
         let log_id = self.database.log_processing_operation(&ProcessingLog {
@@ -120,7 +276,7 @@ impl AirdropService {
             created_at: chrono::Utc::now(),
         }).await?;
 
-        let csv_object_name = self.nats_storage.store_csv_data(round_id, csv_data).await
+        let csv_object_name = self.nats_storage.store_csv_data(round_id, &csv_data).await
             .map_err(|e| {
                 let db = Arc::clone(&self.database);
                 let e_clone = format!("{}", e); // Create a String copy of the error message
@@ -131,15 +287,26 @@ impl AirdropService {
             })?;
         info!("Stored CSV data as object: {}", csv_object_name);
 
-        let eligibility_data = CsvProcessor::process_csv_bytes(csv_data)
-            .map_err(|e| {
-                let db = Arc::clone(&self.database);
-                let e_clone = format!("{}", e); // Create a String copy of the error message
-                tokio::spawn(async move {
-                    let _ = db.update_processing_log_status(log_id, "failed", Some(&format!("CSV processing failed: {}", e_clone))).await;
-                });
-                e
-            })?;
+        // Parsing a large file is CPU bound, keep it off the async runtime threads
+        let leaf_schema = self.leaf_schema.clone();
+        let reject_duplicates = self.reject_duplicate_addresses;
+        let (eligibility_data, leaves) = tokio::task::spawn_blocking(move || match &leaf_schema {
+            Some(schema) => CsvProcessor::process_csv_bytes_with_schema(&csv_data, schema, reject_duplicates)
+                .map(|data| (data.amounts, data.leaves)),
+            None => CsvProcessor::process_csv_bytes(&csv_data, reject_duplicates)
+                .map(|data| (data, HashMap::new())),
+        })
+        .await
+        .map_err(|e| AppError::Internal(anyhow::anyhow!("CSV processing task failed: {}", e)))
+        .and_then(|result| result)
+        .map_err(|e| {
+            let db = Arc::clone(&self.database);
+            let e_clone = format!("{}", e); // Create a String copy of the error message
+            tokio::spawn(async move {
+                let _ = db.update_processing_log_status(log_id, "failed", Some(&format!("CSV processing failed: {}", e_clone))).await;
+            });
+            e
+        })?;
 
         CsvProcessor::validate_csv_data(&eligibility_data)
             .map_err(|e| {
@@ -153,65 +320,60 @@ impl AirdropService {
 
         info!("Processed {} eligibility records", eligibility_data.len());
 
-        let mut trie = self.get_or_create_trie(round_id).await?;
-        trie.update_eligibility_data(eligibility_data.clone())
-            .map_err(|e| AppError::Internal(e))?;
+        self.update_round(round_id, log_id, eligibility_data, leaves).await
+    }
 
-        {
-            let mut tries = self.tries.write().await;
-            tries.insert(round_id, trie.clone());
-        }
+    pub async fn init_chunked_csv_upload(&self, round_id: u32) -> UploadStatus {
+        self.chunked_uploads.init(round_id).await
+    }
 
-        let trie_state = TrieState {
-            round_id,
-            root_hash: trie.get_root_hash(),
-            trie_data: trie.serialize().map_err(|e| AppError::Internal(e))?,
-            entry_count: eligibility_data.len() as i32,
-            created_at: chrono::Utc::now(),
-            updated_at: chrono::Utc::now(),
-        };
+    pub async fn append_csv_chunk(&self, upload_id: &str, chunk_index: u32, chunk: &[u8]) -> AppResult<UploadStatus> {
+        self.chunked_uploads.append_chunk(upload_id, chunk_index, chunk).await
+    }
 
-        self.database.save_trie_state(&trie_state).await?;
+    pub async fn get_chunked_csv_upload_status(&self, upload_id: &str) -> AppResult<UploadStatus> {
+        self.chunked_uploads.get_status(upload_id).await
+    }
 
-        let records: Vec<EligibilityRecord> = eligibility_data
-            .iter()
-            .map(|(address, amount)| EligibilityRecord {
-                id: None,
-                address: *address,
-                amount: *amount,
-                round_id,
-                created_at: None,
-            })
-            .collect();
+    /// Assemble the uploaded chunks and process them as a single CSV file in a background job,
+    /// the upload is consumed regardless of the processing outcome
+    pub async fn finalize_chunked_csv_upload(self: &Arc<Self>, upload_id: &str) -> AppResult<(JobInfo, usize)> {
+        let (round_id, csv_data) = self.chunked_uploads.take(upload_id).await?;
+        if csv_data.is_empty() {
+            return Err(AppError::InvalidInput(format!("Upload {} has no data", upload_id)));
+        }
 
-        self.database.save_eligibility_records(&records).await?;
+        info!("Finalizing chunked CSV upload {} for round {} ({} bytes)", upload_id, round_id, csv_data.len());
+        let data_size = csv_data.len();
+        let job = self.start_csv_processing_job(csv_data, round_id).await;
 
-        let stored_data = StoredTrieData {
-            round_id,
-            root_hash: hex::encode(trie.get_root_hash()),
-            trie_data: trie_state.trie_data.clone(),
-            metadata: TrieMetadata {
-                created_at: chrono::Utc::now(),
-                updated_at: chrono::Utc::now(),
-                version: 1,
-                entry_count: eligibility_data.len(),
-            },
-        };
+        Ok((job, data_size))
+    }
 
-        self.nats_storage.store_trie_data(round_id, &stored_data).await?;
+    pub async fn start_csv_processing_job(self: &Arc<Self>, csv_data: Vec<u8>, round_id: u32) -> JobInfo {
+        let service = Arc::clone(self);
+        self.jobs.spawn(round_id, "csv_processing", async move {
+            service.process_csv_data(csv_data, round_id).await
+        }).await
+    }
 
-        self.database.update_processing_log_status(
-            log_id,
-            "completed",
-            Some(&format!("Processed {} records with root hash: 0x{}",
-                         eligibility_data.len(),
-                         hex::encode(trie.get_root_hash())))
-        ).await?;
+    pub async fn start_json_processing_job(self: &Arc<Self>, eligibility_data: HashMap<Address, U256>, round_id: u32) -> JobInfo {
+        let service = Arc::clone(self);
+        self.jobs.spawn(round_id, "json_processing", async move {
+            service.process_json_eligibility_data(eligibility_data, round_id).await
+        }).await
+    }
 
-        info!("Updated trie for round {} with root hash: 0x{}",
-              round_id, hex::encode(trie.get_root_hash()));
+    pub async fn get_job(&self, job_id: &str) -> AppResult<JobInfo> {
+        self.jobs.get(job_id).await
+    }
 
-        Ok(())
+    pub async fn list_jobs(&self, round_id: Option<u32>) -> Vec<JobInfo> {
+        self.jobs.list(round_id).await
+    }
+
+    pub async fn cancel_job(&self, job_id: &str) -> AppResult<JobInfo> {
+        self.jobs.cancel(job_id).await
     }
 
     pub async fn process_json_eligibility_data(&self, eligibility_data: HashMap<Address, U256>, round_id: u32) -> AppResult<()> {
@@ -237,28 +399,56 @@ impl AirdropService {
                 e
             })?;
 
+        let leaves = match &self.leaf_schema {
+            Some(schema) => CsvProcessor::encode_eligibility_leaves(&eligibility_data, schema)
+                .map_err(|e| {
+                    let db = Arc::clone(&self.database);
+                    let e_clone = format!("{}", e);
+                    tokio::spawn(async move {
+                        let _ = db.update_processing_log_status(log_id, "failed", Some(&format!("Data validation failed: {}", e_clone))).await;
+                    });
+                    e
+                })?,
+            None => HashMap::new(),
+        };
+
         info!("Validated {} eligibility records", eligibility_data.len());
 
+        self.update_round(round_id, log_id, eligibility_data, leaves).await
+    }
+
+    /// Update the round trie with the eligibility data, built off the async runtime threads, then save the trie state
+    /// with the eligibility records at once, so a failed or cancelled job leaves neither of them partially updated.
+    /// The cached and object store tries are replaced after the database update.
+    async fn update_round(
+        &self,
+        round_id: u32,
+        log_id: i32,
+        eligibility_data: HashMap<Address, U256>,
+        leaves: HashMap<Address, Vec<u8>>,
+    ) -> AppResult<()> {
         let mut trie = self.get_or_create_trie(round_id).await?;
-        trie.update_eligibility_data(eligibility_data.clone())
-            .map_err(|e| AppError::Internal(e))?;
 
-        {
-            let mut tries = self.tries.write().await;
-            tries.insert(round_id, trie.clone());
-        }
+        let hashing = self.hashing;
+        let leaf_layout = self.leaf_layout;
+        let schema_leaves = self.leaf_schema.is_some();
+        let (trie, trie_data, eligibility_data, leaves) = tokio::task::spawn_blocking(move || {
+            update_trie_leaves(&mut trie, &eligibility_data, schema_leaves.then_some(&leaves), hashing, leaf_layout)?;
+            let trie_data = trie.serialize().map_err(|e| AppError::Internal(e))?;
+            Ok::<_, AppError>((trie, trie_data, eligibility_data, leaves))
+        })
+        .await
+        .map_err(|e| AppError::Internal(anyhow::anyhow!("Trie update task failed: {}", e)))??;
 
         let trie_state = TrieState {
             round_id,
             root_hash: trie.get_root_hash(),
-            trie_data: trie.serialize().map_err(|e| AppError::Internal(e))?,
+            trie_data,
             entry_count: eligibility_data.len() as i32,
             created_at: chrono::Utc::now(),
             updated_at: chrono::Utc::now(),
         };
 
-        self.database.save_trie_state(&trie_state).await?;
-
         let records: Vec<EligibilityRecord> = eligibility_data
             .iter()
             .map(|(address, amount)| EligibilityRecord {
@@ -266,11 +456,17 @@ impl AirdropService {
                 address: *address,
                 amount: *amount,
                 round_id,
+                leaf_data: leaves.get(address).cloned(),
                 created_at: None,
             })
             .collect();
 
-        self.database.save_eligibility_records(&records).await?;
+        self.database.save_round_data(&trie_state, &records).await?;
+
+        {
+            let mut tries = self.tries.write().await;
+            tries.insert(round_id, trie.clone());
+        }
 
         let stored_data = StoredTrieData {
             round_id,
@@ -314,7 +510,28 @@ impl AirdropService {
             return Ok(trie);
         }
 
-        Ok(MerkleTrie::new())
+        Ok(MerkleTrie::with_hashing(self.hashing))
+    }
+
+    /// Build the trie submission call for review (e.g. in a multisig flow) without broadcasting it
+    pub async fn prepare_trie_submission(&self, round_id: u32) -> AppResult<(B256, bool, TrieUpdateCall)> {
+        let trie = self.get_or_create_trie(round_id).await?;
+        if trie.get_leaf_count() == 0 {
+            return Err(AppError::NotFound(format!("No trie data found for round {}", round_id)));
+        }
+
+        let root_hash = trie.get_root_hash();
+        let root_hash_exists = self.contract_client.is_root_hash_exists(root_hash).await?;
+
+        let trie_data = trie.serialize().map_err(|e| AppError::Internal(e))?;
+        let call = self.contract_client
+            .prepare_trie_update(round_id, root_hash, trie_data)
+            .await?;
+
+        info!("Prepared dry-run trie update for round {} with root hash: 0x{}",
+              round_id, hex::encode(root_hash));
+
+        Ok((root_hash, root_hash_exists, call))
     }
 
     pub async fn submit_trie_update(&self, round_id: u32) -> AppResult<B256> {
@@ -400,6 +617,23 @@ impl AirdropService {
         trie.get_value(&address).map_err(|e| AppError::Internal(e))
     }
 
+    /// Get eligibility amounts and Merkle proofs for a batch of addresses from a single trie lookup
+    pub async fn get_eligibility_batch(
+        &self,
+        round_id: u32,
+        addresses: &[Address],
+    ) -> AppResult<Vec<Option<(U256, Vec<Vec<u8>>)>>> {
+        if addresses.len() > self.max_eligibility_batch_size {
+            return Err(AppError::PayloadTooLarge(format!(
+                "Batch of {} addresses exceeds maximum batch size {}",
+                addresses.len(), self.max_eligibility_batch_size
+            )));
+        }
+
+        let trie = self.get_or_create_trie(round_id).await?;
+        trie.compute_merkle_proofs(addresses).map_err(|e| AppError::Internal(e))
+    }
+
     pub async fn get_round_eligibility_records(&self, round_id: u32) -> AppResult<HashMap<Address, U256>> {
         let records = self.database.get_eligibility_records(round_id).await?;
         let mut eligibility_data = HashMap::new();
@@ -412,8 +646,25 @@ impl AirdropService {
     }
 
     pub async fn get_round_csv_data(&self, round_id: u32) -> AppResult<Vec<u8>> {
+        // Leaf schema rounds are generated with all the schema columns, from their stored leaves
+        if let Some(schema) = &self.leaf_schema {
+            let records = self.database.get_eligibility_records(round_id).await?;
+            if records.iter().all(|record| record.leaf_data.is_some()) {
+                let leaves: Vec<Vec<u8>> = records.into_iter().filter_map(|record| record.leaf_data).collect();
+                return CsvProcessor::generate_csv_bytes_from_leaves(&leaves, schema, self.address_format);
+            }
+        }
+
         let eligibility_data = self.get_round_eligibility_records(round_id).await?;
-        CsvProcessor::generate_csv_bytes(&eligibility_data)
+        CsvProcessor::generate_csv_bytes(&eligibility_data, self.address_format)
+    }
+
+    pub fn get_address_format(&self) -> AddressFormat {
+        self.address_format
+    }
+
+    pub fn rejects_duplicate_addresses(&self) -> bool {
+        self.reject_duplicate_addresses
     }
 
     pub async fn get_trie_info(&self, round_id: u32) -> AppResult<Option<TrieState>> {
@@ -425,6 +676,15 @@ impl AirdropService {
         trie.compute_merkle_proof(&address).map_err(|e| AppError::Internal(e))
     }
 
+    /// Page of round summaries, optionally filtered by submission state, with the total count of matching rounds
+    pub async fn list_rounds(&self, submitted: Option<bool>, limit: u32, offset: u32) -> AppResult<(Vec<RoundSummary>, u64)> {
+        let (summaries, total) = self.database
+            .get_round_summaries(submitted, limit as i64, offset as i64)
+            .await
+            .map_err(|e| AppError::Database(DatabaseError::App(e)))?;
+        Ok((summaries, total as u64))
+    }
+
     pub async fn get_all_round_statistics(&self) -> AppResult<Vec<(u32, i32, chrono::DateTime<chrono::Utc>)>> {
         self.database.get_round_statistics().await.map_err(|e| AppError::Database(DatabaseError::App(e)))
     }
@@ -444,20 +704,97 @@ impl AirdropService {
         }
     }
 
-    pub async fn delete_round(&self, round_id: u32) -> AppResult<()> {
-        {
-            let mut tries = self.tries.write().await;
-            tries.remove(&round_id);
+    /// Recompute the round root from the eligibility records in the database and compare it with the on-chain root,
+    /// catching reverted submissions the database still considers submitted
+    pub async fn verify_on_chain_root(&self, round_id: u32) -> AppResult<OnChainRootVerification> {
+        let records = self.database.get_eligibility_records(round_id).await?;
+        if records.is_empty() {
+            return Err(AppError::NotFound(format!("No eligibility data found for round {}", round_id)));
         }
 
-        self.database.delete_round_data(round_id).await?;
+        let entry_count = records.len();
+        let schema_leaves = records.iter().all(|record| record.leaf_data.is_some());
+        let probe = (records[0].address, records[0].amount);
+
+        let trie_state = self.database.get_trie_state(round_id).await?;
+        let stored_root = trie_state.as_ref().map(|trie_state| trie_state.root_hash);
+        // The root depends on the leaves order, the stored trie has the one the round root was built in
+        let stored_leaves = trie_state
+            .and_then(|trie_state| MerkleTrie::deserialize(&trie_state.trie_data).ok())
+            .map(|stored_trie| stored_trie.leaves().to_vec());
+
+        let trie = rebuild_round_trie(records, stored_leaves.as_deref(), self.hashing, self.leaf_layout)
+            .map_err(|e| AppError::Internal(e))?;
+        let computed_root = trie.get_root_hash();
+
+        let on_chain_root = self.contract_client.get_trie_root(round_id).await?;
+
+        let verifier_accepts_proof = if computed_root == on_chain_root && !schema_leaves {
+            let (address, amount) = probe;
+            let proof = trie.compute_merkle_proof(&address).map_err(|e| AppError::Internal(e))?;
+            Some(self.contract_client.verify_eligibility(round_id, address, amount, proof).await?)
+        } else {
+            None
+        };
 
-        if let Err(e) = self.nats_storage.delete_trie_data(round_id).await {
-            warn!("Failed to delete NATS data for round {}: {}", round_id, e);
+        let verification = OnChainRootVerification {
+            round_id,
+            entry_count,
+            computed_root,
+            stored_root,
+            on_chain_root,
+            verifier_accepts_proof,
+        };
+
+        if computed_root != on_chain_root {
+            warn!("Round {} computed root 0x{} does not match on-chain root 0x{}",
+                  round_id, hex::encode(computed_root), hex::encode(on_chain_root));
+        } else if verifier_accepts_proof == Some(false) {
+            warn!("Round {} root matches on-chain, but the contract verifier rejects its proofs: \
+                   the verifier leaf preimage or hashing differs from the configured {:?}",
+                  round_id, self.hashing);
+        }
+
+        Ok(verification)
+    }
+
+    pub async fn delete_round(&self, round_id: u32) -> AppResult<RoundDeletionSummary> {
+        let removed_trie = {
+            let mut tries = self.tries.write().await;
+            tries.remove(&round_id).is_some()
+        };
+
+        let database = self.database.delete_round_data(round_id).await
+            .map_err(|e| AppError::Database(DatabaseError::App(e)))?;
+
+        // Object store artifacts are deleted best-effort, also cleaning up orphans of earlier deletions
+        let mut deleted_objects = Vec::new();
+        let mut failed_objects = Vec::new();
+        for (object_name, result) in [
+            (format!("trie_round_{}", round_id), self.nats_storage.delete_trie_data(round_id).await),
+            (format!("csv_round_{}", round_id), self.nats_storage.delete_csv_data(round_id).await),
+        ] {
+            match result {
+                Ok(Some(object_name)) => deleted_objects.push(object_name),
+                Ok(None) => {}
+                Err(e) => {
+                    warn!("Failed to delete NATS object {} for round {}: {}", object_name, round_id, e);
+                    failed_objects.push(object_name);
+                }
+            }
+        }
+
+        if database.is_empty() && !removed_trie && deleted_objects.is_empty() && failed_objects.is_empty() {
+            return Err(AppError::NotFound(format!("Round {} not found", round_id)));
         }
 
         info!("Deleted all data for round {}", round_id);
-        Ok(())
+        Ok(RoundDeletionSummary {
+            round_id,
+            database,
+            deleted_objects,
+            failed_objects,
+        })
     }
 
     pub async fn get_contract_version(&self) -> AppResult<String> {
@@ -496,15 +833,58 @@ impl AirdropService {
         Ok(local_root_hash == external_root_hash && local_trie_data == external_trie_data)
     }
 
-    pub async fn fetch_and_update_from_external(&self, round_id: u32, external_url: &str) -> AppResult<()> {
-        info!("Fetching eligibility data from external backend for round {}", round_id);
+    /// Fetch the round eligibility data from the external sources concurrently and update the round with it.
+    /// Failed sources are skipped and recorded in the processing log, the fetch only fails if all of them failed.
+    /// Returns the number of fetched eligibility records, with the failed sources.
+    pub async fn fetch_and_update_from_external(
+        &self,
+        round_id: u32,
+        external_urls: &[String],
+    ) -> AppResult<(usize, Vec<ExternalFetchFailure>)> {
+        info!("Fetching eligibility data from {} external sources for round {}", external_urls.len(), round_id);
+
+        let log_id = self.database.log_processing_operation(&ProcessingLog {
+            id: 0,
+            round_id,
+            operation: "external_fetch".to_string(),
+            status: "started".to_string(),
+            message: Some(format!("Fetching eligibility data from {} external sources", external_urls.len())),
+            transaction_hash: None,
+            created_at: chrono::Utc::now(),
+        }).await?;
+
+        let result = self.external_client.fetch_eligibility_sources(external_urls).await;
+
+        let failures = result.failures
+            .iter()
+            .map(|failure| format!("{}: {}", failure.url, failure.error))
+            .collect::<Vec<_>>()
+            .join("; ");
+
+        if result.failures.len() == external_urls.len() {
+            let message = format!("Failed to fetch all {} external sources, round not updated: {}", external_urls.len(), failures);
+
+            self.database.update_processing_log_status(log_id, "failed", Some(&message)).await?;
+            return Err(AppError::ExternalFetch(message));
+        }
 
-        let eligibility_data = self.external_client.fetch_eligibility_data(external_url).await?;
+        let record_count = result.eligibility_data.len();
+        let fetched_sources = external_urls.len() - result.failures.len();
+        let message = if result.failures.is_empty() {
+            format!("Fetched {} records from {} external sources", record_count, fetched_sources)
+        } else {
+            warn!("Skipped {} failed external sources of round {}", result.failures.len(), round_id);
+            format!(
+                "Fetched {} records from {} of {} external sources, skipped failed sources: {}",
+                record_count, fetched_sources, external_urls.len(), failures
+            )
+        };
+        self.database.update_processing_log_status(log_id, "completed", Some(&message)).await?;
 
-        self.process_json_eligibility_data(eligibility_data, round_id).await?;
+        self.process_json_eligibility_data(result.eligibility_data, round_id).await?;
 
         info!("Successfully updated round {} with external data", round_id);
-        Ok(())
+        Ok((record_count, result.failures))
     }
 
     pub async fn fetch_and_compare_external_trie(&self,
@@ -527,3 +907,53 @@ impl AirdropService {
         Ok(matches)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(address: Address, amount: u64) -> EligibilityRecord {
+        EligibilityRecord {
+            id: None,
+            address,
+            amount: U256::from(amount),
+            round_id: 1,
+            leaf_data: None,
+            created_at: None,
+        }
+    }
+
+    #[test]
+    fn test_rebuilt_round_trie_verifies_built_root() {
+        let entries: Vec<(Address, U256)> = (1u8..=5)
+            .map(|i| (Address::repeat_byte(i), U256::from(i as u64 * 100)))
+            .collect();
+
+        // Root built by the round processing, records saved in the same order
+        let mut built = MerkleTrie::new();
+        built.update_eligibility_data(entries.iter().copied()).unwrap();
+        let records = || entries.iter().map(|(address, amount)| record(*address, amount.to::<u64>()));
+
+        let rebuilt = rebuild_round_trie(records().collect(), None, HashingConfig::default(), LeafLayout::default()).unwrap();
+        assert_eq!(rebuilt.get_root_hash(), built.get_root_hash());
+
+        // Records read in another order are rebuilt in the stored trie leaves order
+        let stored = MerkleTrie::deserialize(&built.serialize().unwrap()).unwrap();
+        let reversed: Vec<EligibilityRecord> = records().rev().collect();
+        let rebuilt = rebuild_round_trie(reversed.clone(), Some(stored.leaves()), HashingConfig::default(), LeafLayout::default()).unwrap();
+        assert_eq!(rebuilt.get_root_hash(), built.get_root_hash());
+
+        let unordered = rebuild_round_trie(reversed, None, HashingConfig::default(), LeafLayout::default()).unwrap();
+        assert_ne!(unordered.get_root_hash(), built.get_root_hash());
+    }
+
+    #[test]
+    fn test_rebuilt_round_trie_detects_changed_records() {
+        let mut built = MerkleTrie::new();
+        built.update_eligibility_data([(Address::repeat_byte(1), U256::from(100)), (Address::repeat_byte(2), U256::from(200))]).unwrap();
+
+        let changed = vec![record(Address::repeat_byte(1), 100), record(Address::repeat_byte(2), 201)];
+        let rebuilt = rebuild_round_trie(changed, Some(built.leaves()), HashingConfig::default(), LeafLayout::default()).unwrap();
+        assert_ne!(rebuilt.get_root_hash(), built.get_root_hash());
+    }
+}
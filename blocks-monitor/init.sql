@@ -36,11 +36,16 @@ CREATE TABLE IF NOT EXISTS blocks_monitor_data (
     gas_used TEXT NOT NULL,
     gas_limit TEXT NOT NULL,
     transactions JSONB,
+    reorged BOOLEAN NOT NULL DEFAULT FALSE, -- Set when the block is orphaned by a chain reorganization
     created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
     UNIQUE (chain_id, block_number, block_hash)
 );
 
+-- Add reorg flag to tables created before reorg handling
+ALTER TABLE blocks_monitor_data ADD COLUMN IF NOT EXISTS reorged BOOLEAN NOT NULL DEFAULT FALSE;
+
 CREATE INDEX IF NOT EXISTS idx_blocks_chain_id ON blocks_monitor_data(chain_id);
+CREATE INDEX IF NOT EXISTS idx_blocks_canonical ON blocks_monitor_data(chain_id, block_number) WHERE NOT reorged;
 CREATE INDEX IF NOT EXISTS idx_blocks_block_number ON blocks_monitor_data(block_number);
 CREATE INDEX IF NOT EXISTS idx_blocks_block_hash ON blocks_monitor_data(block_hash);
 CREATE INDEX IF NOT EXISTS idx_blocks_block_timestamp ON blocks_monitor_data(block_timestamp);
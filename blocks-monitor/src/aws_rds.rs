@@ -39,7 +39,7 @@ impl AwsRdsClient {
             }
         }
 
-        let (client, connection) = pg_config.connect(NoTls).await
+        let (mut client, connection) = pg_config.connect(NoTls).await
             .map_err(|e| anyhow::anyhow!("Failed to connect to AWS RDS: {:?}", e))?;
 
         // Spawn connection handler
@@ -56,6 +56,14 @@ impl AwsRdsClient {
                 .map_err(|e| anyhow::anyhow!("Failed to setup AWS RDS schema: {:?}", e))?;
         }
 
+        // Apply pending migrations, refusing a schema migrated by a newer release
+        if let Some(migrations_dir) = &config.migrations_dir {
+            let migrations = db_migrations::load_dir(migrations_dir)?;
+            let version = db_migrations::run(&mut client, &migrations).await
+                .map_err(|e| anyhow::anyhow!("Failed to migrate AWS RDS schema: {:?}", e))?;
+            info!("AWS RDS schema version {}", version);
+        }
+
         info!("Successfully connected to AWS RDS");
 
         Ok(Self {
@@ -77,14 +85,14 @@ impl AwsRdsClient {
                 gas_limit,
                 transactions
             ) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9::jsonb)
-            ON CONFLICT (chain_id, block_number) DO UPDATE SET
-                block_hash = EXCLUDED.block_hash,
+            ON CONFLICT (chain_id, block_number, block_hash) DO UPDATE SET
                 block_timestamp = EXCLUDED.block_timestamp,
                 block_time = EXCLUDED.block_time,
                 parent_hash = EXCLUDED.parent_hash,
                 gas_used = EXCLUDED.gas_used,
                 gas_limit = EXCLUDED.gas_limit,
-                transactions = EXCLUDED.transactions
+                transactions = EXCLUDED.transactions,
+                reorged = FALSE,
                 updated_at = CURRENT_TIMESTAMP
         "#;
 
@@ -120,6 +128,25 @@ impl AwsRdsClient {
         }
     }
 
+    pub async fn mark_blocks_reorged(&self, chain_id: &str, from_block: u64) -> anyhow::Result<u64> {
+        let query = r#"
+            UPDATE blocks_monitor_data
+            SET reorged = TRUE
+            WHERE chain_id = $1 AND block_number::NUMERIC >= $2::TEXT::NUMERIC AND NOT reorged
+        "#;
+
+        match self.client.execute(query, &[&chain_id, &from_block.to_string()]).await {
+            Ok(count) => {
+                debug!("Marked {} blocks as reorged in AWS RDS from block {}", count, from_block);
+                Ok(count)
+            },
+            Err(e) => {
+                error!("Failed to mark reorged blocks in AWS RDS: {:?}", e);
+                Err(anyhow::anyhow!("AWS RDS reorg marking failed: {:?}", e))
+            }
+        }
+    }
+
     pub async fn test_connection(&self) -> anyhow::Result<()> {
         match self.client.execute("SELECT 1", &[]).await {
             Ok(_) => {
@@ -0,0 +1,14 @@
+use clap::Parser;
+use monitor_common::cli::{BackfillArgs, MonitorArgs};
+
+pub use monitor_common::cli::Mode;
+
+#[derive(Debug, Parser)]
+#[command(name = "blocks-monitor", version, about = "Blocks monitor: blocks and transactions monitoring and indexing")]
+pub struct Cli {
+    #[command(flatten)]
+    pub monitor: MonitorArgs,
+
+    #[command(flatten)]
+    pub backfill: BackfillArgs,
+}
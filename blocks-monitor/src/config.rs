@@ -1,12 +1,41 @@
 use serde::Deserialize;
+use monitor_common::labels::LabelsCfg;
 
 #[derive(Debug, Deserialize, Clone)]
 pub struct ChainCfg {
     pub http_rpc_url: String,
     pub ws_rpc_url: String,
+    pub http_rpc_urls: Option<Vec<String>>, // Fallback HTTP RPC endpoints, tried in order after 'http_rpc_url' if it's unreachable on startup
+    pub ws_rpc_urls: Option<Vec<String>>, // Fallback WS RPC endpoints, tried in order after 'ws_rpc_url' on connection failure or subscription termination
+    pub ws_reconnect_delay_secs: Option<u64>, // Delay before reconnecting to the next WS RPC endpoint. Defaults to 5 if not specified.
+    pub ws_max_reconnect_attempts: Option<u64>, // Max number of consecutive WS reconnection attempts, unlimited if not specified
+    pub rpc_rate_limit: Option<u32>, // Max outbound HTTP RPC requests per second of the task, shared by the historical and subscription processing, unlimited if not specified
+    pub rpc_rate_limit_burst: Option<u32>, // Max requests sent at once before the rate limit applies, 'rpc_rate_limit' by default
     pub chain_id: u64,
 }
 
+impl ChainCfg {
+    /// Primary HTTP RPC endpoint followed by the fallback ones
+    pub fn http_rpc_endpoints(&self) -> Vec<String> {
+        rpc_endpoints(&self.http_rpc_url, self.http_rpc_urls.as_deref())
+    }
+
+    /// Primary WS RPC endpoint followed by the fallback ones
+    pub fn ws_rpc_endpoints(&self) -> Vec<String> {
+        rpc_endpoints(&self.ws_rpc_url, self.ws_rpc_urls.as_deref())
+    }
+}
+
+fn rpc_endpoints(primary_url: &str, fallback_urls: Option<&[String]>) -> Vec<String> {
+    let mut endpoints = vec![primary_url.to_string()];
+    for url in fallback_urls.unwrap_or_default() {
+        if !endpoints.contains(url) {
+            endpoints.push(url.clone());
+        }
+    }
+    endpoints
+}
+
 #[derive(Debug, Deserialize, Clone)]
 pub struct IndexingCfg {
     // Block range to process
@@ -28,6 +57,19 @@ pub struct IndexingCfg {
     pub ws_subscription_method: Option<String>, // "subscribe_full_blocks" or "subscribe_blocks" - for new blocks subscription/polling, this parameter covers underlying Alloy API, if not present in config file or "null", then "subscribe_full_blocks" used by default
     pub ws_subscription_channel_size: Option<u64>, // Size of the channel for new blocks subscription, for WebSocket RPC only (i.e. only used when 'new_blocks_subscription_protocol' is 'ws')
 
+    // Transaction receipts enrichment (full blocks only)
+    pub fetch_receipts: Option<bool>, // Fetch receipt of each transaction and add status, gas used, effective gas price and created contract address, 'false' by default
+    pub receipts_fetch_concurrency: Option<usize>, // Max number of concurrent receipt requests per block. Defaults to 16 if not specified.
+
+    // Chain reorganization handling
+    pub reorg_detection: Option<bool>, // Detect reorgs by parent hash mismatch and flag orphaned blocks, 'true' by default
+    pub max_reorg_depth: Option<u64>, // Max number of blocks to walk back looking for the fork point. Defaults to 64 if not specified.
+
+    // Missing blocks backfill
+    pub gap_filler: Option<bool>, // Periodically look up missing blocks within the indexed range and re-fetch them via HTTP RPC, 'false' by default
+    pub gap_filler_interval_secs: Option<u64>, // Interval of the gap checks in seconds. Defaults to 60 if not specified.
+    pub gap_filler_max_blocks: Option<u64>, // Max number of missing blocks to backfill per check. Defaults to 1000 if not specified.
+
     // Transaction filtering
     pub filter_senders: Option<Vec<String>>,
     pub filter_receivers: Option<Vec<String>>,
@@ -37,6 +79,7 @@ pub struct IndexingCfg {
 pub struct PgCfg {
     pub dsn: String,
     pub schema: String,
+    pub migrations_dir: Option<String>, // Numbered SQL migrations (`<version>_<name>.sql`), applied after the schema
 }
 
 #[derive(Debug, Deserialize, Clone)]
@@ -52,6 +95,7 @@ pub struct AwsRdsCfg {
     pub connection_timeout: Option<u64>,
     pub max_connections: Option<u32>,
     pub schema: Option<String>,
+    pub migrations_dir: Option<String>, // Numbered SQL migrations (`<version>_<name>.sql`), applied after the schema
 }
 
 #[derive(Debug, Deserialize, Clone)]
@@ -59,6 +103,37 @@ pub struct NatsCfg {
     pub nats_enabled: Option<u8>,
     pub url: String,
     pub object_store_bucket: String,
+    pub buffer_capacity: Option<usize>, // Max blocks buffered while NATS is degraded, the oldest are dropped when full (default 10000)
+    pub retry_interval_secs: Option<u64>, // Interval of republishing the buffered blocks while NATS is degraded (default 5)
+    pub publish_timeout_secs: Option<u64>, // Timeout of a publish, after which NATS is degraded (default 10)
+}
+
+/// Additional output sink, written after PostgreSQL and NATS Object Store
+#[derive(Debug, Deserialize, Clone)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum SinkCfg {
+    Webhook {
+        url: String,
+        timeout_secs: Option<u64>, // Request timeout (default 10)
+    },
+}
+
+#[derive(Debug, Deserialize, Clone, Default)]
+pub struct ApiCfg {
+    pub bind_address: Option<String>, // API server bind address, overridden by `--bind` CLI option
+}
+
+#[derive(Debug, Deserialize)]
+struct ApiModeCfg {
+    api: Option<ApiCfg>,
+}
+
+impl ApiCfg {
+    /// Load the `api` section only, as monitoring task sections aren't required in API mode
+    pub fn load(path: &str) -> anyhow::Result<Self> {
+        let config: ApiModeCfg = config_loader::load(path)?;
+        Ok(config.api.unwrap_or_default())
+    }
 }
 
 #[derive(Debug, Deserialize, Clone)]
@@ -69,11 +144,13 @@ pub struct AppCfg {
     pub postgres: PgCfg,
     pub aws_rds: Option<AwsRdsCfg>,
     pub nats: NatsCfg,
+    pub sinks: Option<Vec<SinkCfg>>, // Additional output sinks
+    pub labels: Option<LabelsCfg>, // Human-readable labels of the senders and receivers of the persisted transactions
 }
 
 impl AppCfg {
     pub fn load(path: &str) -> anyhow::Result<Self> {
-        let config: Self = serde_yaml::from_str(&std::fs::read_to_string(path)?)?;
+        let config: Self = config_loader::load(path)?;
         Ok(config)
     }
 
@@ -1,7 +1,7 @@
 use tokio_postgres::{Client, NoTls};
 use tracing::{info, error, warn};
 
-use crate::types::BlockPayload;
+use crate::types::{BlockPayload, ReorgedBlock};
 use crate::config::AwsRdsCfg;
 use crate::aws_rds::{AwsRdsClient, create_aws_rds_client};
 
@@ -14,10 +14,11 @@ impl DatabaseClients {
     pub async fn new(
         local_dsn: &str,
         local_schema: &str,
+        local_migrations_dir: Option<&str>,
         aws_rds_config: Option<&AwsRdsCfg>
     ) -> anyhow::Result<Self> {
         // Connect to local PostgreSQL
-        let local_pg = connect_pg(local_dsn, local_schema).await?;
+        let local_pg = connect_pg(local_dsn, local_schema, local_migrations_dir).await?;
 
         // Connect to AWS RDS if enabled
         let aws_rds = if let Some(rds_config) = aws_rds_config {
@@ -70,6 +71,32 @@ impl DatabaseClients {
         Ok(())
     }
 
+    /// Hash of the non-reorged block stored at `block_number`, if any
+    pub async fn canonical_block_hash(&self, chain_id: &str, block_number: u64) -> anyhow::Result<Option<String>> {
+        canonical_block_hash(&self.local_pg, chain_id, block_number).await
+    }
+
+    /// Flag all canonical blocks from `from_block` upwards as reorged
+    pub async fn mark_blocks_reorged(&self, chain_id: &str, from_block: u64) -> anyhow::Result<Vec<ReorgedBlock>> {
+        let reorged = mark_blocks_reorged(&self.local_pg, chain_id, from_block).await?;
+
+        // Optionally mark in AWS RDS
+        if let Some(aws_rds) = &self.aws_rds
+            && let Err(e) = aws_rds.mark_blocks_reorged(chain_id, from_block).await
+        {
+            // Log error but don't fail the entire operation
+            error!("Failed to mark reorged blocks in AWS RDS (non-critical): {:?}", e);
+        }
+
+        Ok(reorged)
+    }
+
+    /// Block numbers without a canonical block stored, from `from_block` up to the highest
+    /// stored block (capped by `to_block`), at most `limit` of them in ascending order
+    pub async fn missing_block_numbers(&self, chain_id: &str, from_block: u64, to_block: Option<u64>, limit: u64) -> anyhow::Result<Vec<u64>> {
+        missing_block_numbers(&self.local_pg, chain_id, from_block, to_block, limit).await
+    }
+
     pub async fn test_connections(&self) -> anyhow::Result<()> {
         // Test local PostgreSQL
         match self.local_pg.execute("SELECT 1", &[]).await {
@@ -95,8 +122,8 @@ impl DatabaseClients {
     }
 }
 
-pub async fn connect_pg(dsn: &str, schema: &str) -> anyhow::Result<Client> {
-    let (client, connection) = tokio_postgres::connect(dsn, NoTls).await?;
+pub async fn connect_pg(dsn: &str, schema: &str, migrations_dir: Option<&str>) -> anyhow::Result<Client> {
+    let (mut client, connection) = tokio_postgres::connect(dsn, NoTls).await?;
     tokio::spawn(async move {
         if let Err(e) = connection.await {
             error!("Postgres connection error: {:?}", e);
@@ -106,6 +133,13 @@ pub async fn connect_pg(dsn: &str, schema: &str) -> anyhow::Result<Client> {
     // Create schema if not exists
     client.batch_execute(schema).await?;
 
+    // Apply pending migrations, refusing a schema migrated by a newer release
+    if let Some(migrations_dir) = migrations_dir {
+        let migrations = db_migrations::load_dir(migrations_dir)?;
+        let version = db_migrations::run(&mut client, &migrations).await?;
+        info!("Local PostgreSQL schema version {}", version);
+    }
+
     info!("Local PostgreSQL ready");
 
     Ok(client)
@@ -127,14 +161,14 @@ pub async fn insert_block(
             gas_limit,
             transactions
         ) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9::jsonb)
-        ON CONFLICT (chain_id, block_number) DO UPDATE SET
-            block_hash = EXCLUDED.block_hash,
+        ON CONFLICT (chain_id, block_number, block_hash) DO UPDATE SET
             block_timestamp = EXCLUDED.block_timestamp,
             block_time = EXCLUDED.block_time,
             parent_hash = EXCLUDED.parent_hash,
             gas_used = EXCLUDED.gas_used,
             gas_limit = EXCLUDED.gas_limit,
-            transactions = EXCLUDED.transactions
+            transactions = EXCLUDED.transactions,
+            reorged = FALSE
     "#;
 
     let transactions_jsonb = payload.transactions.as_ref()
@@ -162,3 +196,96 @@ pub async fn insert_block(
 
     Ok(())
 }
+
+pub async fn canonical_block_hash(
+    client: &Client,
+    chain_id: &str,
+    block_number: u64,
+) -> anyhow::Result<Option<String>> {
+    let query = r#"
+        SELECT block_hash
+        FROM blocks_monitor_data
+        WHERE chain_id = $1 AND block_number = $2 AND NOT reorged
+        ORDER BY id DESC
+        LIMIT 1
+    "#;
+
+    let row = client
+        .query_opt(query, &[&chain_id, &block_number.to_string()])
+        .await?;
+
+    Ok(row.map(|row| row.get::<_, String>(0)))
+}
+
+pub async fn mark_blocks_reorged(
+    client: &Client,
+    chain_id: &str,
+    from_block: u64,
+) -> anyhow::Result<Vec<ReorgedBlock>> {
+    let query = r#"
+        UPDATE blocks_monitor_data
+        SET reorged = TRUE
+        WHERE chain_id = $1 AND block_number::NUMERIC >= $2::TEXT::NUMERIC AND NOT reorged
+        RETURNING block_number, block_hash
+    "#;
+
+    let rows = client
+        .query(query, &[&chain_id, &from_block.to_string()])
+        .await?;
+
+    let mut reorged: Vec<ReorgedBlock> = rows.iter()
+        .map(|row| ReorgedBlock {
+            block_number: row.get(0),
+            block_hash: row.get(1),
+        })
+        .collect();
+    reorged.sort_by_key(|block| block.block_number.parse::<u64>().unwrap_or_default());
+
+    info!("Marked {} blocks as reorged from block {}", reorged.len(), from_block);
+
+    Ok(reorged)
+}
+
+pub async fn missing_block_numbers(
+    client: &Client,
+    chain_id: &str,
+    from_block: u64,
+    to_block: Option<u64>,
+    limit: u64,
+) -> anyhow::Result<Vec<u64>> {
+    let query = r#"
+        SELECT gs.block_number
+        FROM (
+            SELECT MAX(block_number::BIGINT) AS max_block
+            FROM blocks_monitor_data
+            WHERE chain_id = $1 AND NOT reorged
+        ) AS indexed,
+        generate_series(
+            $2::TEXT::BIGINT,
+            LEAST(indexed.max_block, COALESCE($3::TEXT::BIGINT, indexed.max_block))
+        ) AS gs(block_number)
+        WHERE NOT EXISTS (
+            SELECT 1
+            FROM blocks_monitor_data b
+            WHERE b.chain_id = $1 AND b.block_number = gs.block_number::TEXT AND NOT b.reorged
+        )
+        ORDER BY gs.block_number
+        LIMIT $4::TEXT::BIGINT
+    "#;
+
+    let rows = client
+        .query(
+            query,
+            &[
+                &chain_id,
+                &from_block.to_string(),
+                &to_block.map(|block| block.to_string()),
+                &limit.to_string(),
+            ],
+        )
+        .await?;
+
+    Ok(rows.iter()
+        .map(|row| row.get::<_, i64>(0) as u64)
+        .collect())
+}
@@ -1,6 +1,9 @@
+mod cli;
 mod config;
+mod metrics;
 mod db;
 mod nats;
+mod sink;
 mod subscriptions;
 mod types;
 mod task_manager;
@@ -8,9 +11,11 @@ mod web_api;
 mod aws_rds;
 
 use std::sync::Arc;
+use clap::Parser;
 use tracing_subscriber::{EnvFilter, fmt};
 use tracing::{info, error};
 
+use crate::cli::{Cli, Mode};
 use crate::task_manager::TaskManager;
 use crate::web_api::start_web_server;
 
@@ -20,13 +25,17 @@ async fn main() -> anyhow::Result<()> {
     let filter = EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new("debug"));
     tracing_subscriber::fmt().with_env_filter(filter).compact().init();
 
-    // Check if we should run in API mode or single task mode
-    let args: Vec<String> = std::env::args().collect();
+    let cli = Cli::parse();
+
+    if cli.monitor.mode() == Mode::Api {
+        anyhow::ensure!(cli.backfill.backfill_range().is_none(), "Backfill-only mode runs in single task mode");
 
-    if args.len() > 1 && args[1] == "--api" {
         // Run in API mode
-        let bind_address = std::env::var("BIND_ADDRESS")
-            .unwrap_or_else(|_| "0.0.0.0:8080".to_string());
+        let api_cfg = match cli.monitor.config_path() {
+            Some(path) => config::ApiCfg::load(path)?,
+            None => config::ApiCfg::default(),
+        };
+        let bind_address = cli.monitor.bind_address(api_cfg.bind_address.as_deref());
 
         info!("Starting Block Monitor API server");
 
@@ -47,11 +56,17 @@ async fn main() -> anyhow::Result<()> {
         // Run in single task mode (original behavior)
         info!("Starting Block Monitor in single task mode");
 
-        let cfg_path = args.get(1).unwrap_or(&"./config.yaml".to_string()).clone();
-        let cfg = config::AppCfg::load(&cfg_path)?;
+        let cfg_path = cli.monitor.config_path().unwrap_or("./config.yaml");
+        let cfg = config::AppCfg::load(cfg_path)?;
+
+        let backfill_range = cli.backfill.backfill_range();
+        if let Some((from_block, to_block)) = backfill_range {
+            anyhow::ensure!(from_block <= to_block, "Backfill range start block {} is after its end block {}", from_block, to_block);
+            info!("Backfill-only mode: processing blocks {} to {}, then exiting", from_block, to_block);
+        }
 
-        let db_schema_path = if let Some(path) = args.get(2) {
-            path.clone()
+        let db_schema_path = if let Some(path) = cli.monitor.schema_path() {
+            path.to_string()
         } else {
             if cfg.postgres.schema.is_empty() {
                 "./init.sql".to_string()
@@ -72,6 +87,7 @@ async fn main() -> anyhow::Result<()> {
         let db_clients = db::DatabaseClients::new(
             &cfg.postgres.dsn,
             &db_schema,
+            cfg.postgres.migrations_dir.as_deref(),
             aws_rds_config
         ).await?;
 
@@ -86,7 +102,10 @@ async fn main() -> anyhow::Result<()> {
         };
 
         let block_processor = subscriptions::BlockProcessor::new(&cfg, db_clients, nats).await?;
-        block_processor.run().await?;
+        match backfill_range {
+            Some((from_block, to_block)) => block_processor.backfill(from_block, to_block).await?,
+            None => block_processor.run().await?,
+        }
     }
 
     Ok(())
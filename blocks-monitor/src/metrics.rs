@@ -0,0 +1,95 @@
+//! Prometheus metrics, recorded when built with the `metrics` feature and served on `/metrics` in API mode.
+//! Without the feature all recording functions are no-ops.
+
+use std::future::IntoFuture;
+use std::time::{Duration, Instant};
+
+#[cfg(feature = "metrics")]
+use axum::{routing::get, Router};
+#[cfg(feature = "metrics")]
+use metrics_exporter_prometheus::PrometheusBuilder;
+
+const BLOCKS_PROCESSED: &str = "blocks_monitor_blocks_processed_total";
+const TRANSACTIONS_PROCESSED: &str = "blocks_monitor_transactions_processed_total";
+const REORGS: &str = "blocks_monitor_reorgs_total";
+const REORG_DEPTH: &str = "blocks_monitor_reorg_depth_blocks";
+const RPC_CALL_DURATION: &str = "blocks_monitor_rpc_call_duration_seconds";
+const SINK_WRITE_DURATION: &str = "blocks_monitor_sink_write_duration_seconds";
+const TASK_ERRORS: &str = "blocks_monitor_task_errors_total";
+
+/// Latency histograms buckets, in seconds
+#[cfg(feature = "metrics")]
+const LATENCY_BUCKETS: [f64; 12] = [0.001, 0.0025, 0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 10.0];
+
+/// Reorg depth histogram buckets, in blocks
+#[cfg(feature = "metrics")]
+const REORG_DEPTH_BUCKETS: [f64; 8] = [1.0, 2.0, 3.0, 5.0, 10.0, 20.0, 50.0, 100.0];
+
+/// Install the Prometheus recorder and add the `/metrics` endpoint rendering it
+#[cfg(feature = "metrics")]
+pub fn with_metrics_route(router: Router) -> anyhow::Result<Router> {
+    let handle = PrometheusBuilder::new()
+        .set_buckets(&LATENCY_BUCKETS)?
+        .set_buckets_for_metric(
+            metrics_exporter_prometheus::Matcher::Full(REORG_DEPTH.to_string()),
+            &REORG_DEPTH_BUCKETS,
+        )?
+        .install_recorder()?;
+
+    ::metrics::describe_counter!(BLOCKS_PROCESSED, "Blocks processed and persisted");
+    ::metrics::describe_counter!(TRANSACTIONS_PROCESSED, "Transactions of the processed blocks");
+    ::metrics::describe_counter!(REORGS, "Chain reorganizations detected");
+    ::metrics::describe_histogram!(REORG_DEPTH, ::metrics::Unit::Count, "Chain reorganizations depth");
+    ::metrics::describe_histogram!(RPC_CALL_DURATION, ::metrics::Unit::Seconds, "RPC call latency");
+    ::metrics::describe_histogram!(SINK_WRITE_DURATION, ::metrics::Unit::Seconds, "Output sink write latency");
+    ::metrics::describe_counter!(TASK_ERRORS, "Processing tasks failed, by error kind");
+
+    Ok(router.route("/metrics", get(move || std::future::ready(handle.render()))))
+}
+
+pub fn block_processed(transactions: usize) {
+    #[cfg(feature = "metrics")]
+    {
+        ::metrics::counter!(BLOCKS_PROCESSED).increment(1);
+        ::metrics::counter!(TRANSACTIONS_PROCESSED).increment(transactions as u64);
+    }
+    #[cfg(not(feature = "metrics"))]
+    let _ = (BLOCKS_PROCESSED, TRANSACTIONS_PROCESSED, transactions);
+}
+
+pub fn reorg(depth: u64) {
+    #[cfg(feature = "metrics")]
+    {
+        ::metrics::counter!(REORGS).increment(1);
+        ::metrics::histogram!(REORG_DEPTH).record(depth as f64);
+    }
+    #[cfg(not(feature = "metrics"))]
+    let _ = (REORGS, REORG_DEPTH, depth);
+}
+
+pub fn sink_write(sink: &str, duration: Duration) {
+    #[cfg(feature = "metrics")]
+    ::metrics::histogram!(SINK_WRITE_DURATION, "sink" => sink.to_string()).record(duration.as_secs_f64());
+    #[cfg(not(feature = "metrics"))]
+    let _ = (SINK_WRITE_DURATION, sink, duration);
+}
+
+pub fn task_error(kind: &'static str) {
+    #[cfg(feature = "metrics")]
+    ::metrics::counter!(TASK_ERRORS, "kind" => kind).increment(1);
+    #[cfg(not(feature = "metrics"))]
+    let _ = (TASK_ERRORS, kind);
+}
+
+/// Await the RPC call, recording its latency
+pub async fn rpc_call<F: IntoFuture>(method: &'static str, call: F) -> F::Output {
+    let started = Instant::now();
+    let output = call.await;
+
+    #[cfg(feature = "metrics")]
+    ::metrics::histogram!(RPC_CALL_DURATION, "method" => method).record(started.elapsed().as_secs_f64());
+    #[cfg(not(feature = "metrics"))]
+    let _ = (RPC_CALL_DURATION, method, started);
+
+    output
+}
@@ -1,13 +1,18 @@
 use async_nats::{jetstream, jetstream::Context, Client};
 use jetstream::object_store::ObjectStore;
+use monitor_common::error::MonitorError;
 
-use crate::types::BlockPayload;
+use crate::config::NatsCfg;
+use crate::types::{BlockPayload, ReorgEvent};
 
 use std::io::Cursor;
 use std::time::Duration;
 use async_nats::jetstream::object_store::Object;
+use monitor_common::nats::{PublishTarget, PublisherCfg};
 use tracing::{error, info};
 
+pub use monitor_common::nats::NatsHealth;
+
 #[derive(Clone)]
 pub struct Nats {
     pub client: Client,
@@ -15,7 +20,7 @@ pub struct Nats {
     pub object_store: ObjectStore,
 }
 
-pub async fn connect(url: &str, bucket: &str) -> anyhow::Result<Nats> {
+pub async fn connect(url: &str, bucket: &str) -> monitor_common::error::Result<Nats> {
     // Create NATS Client with NATS connection, connect to NATS
     let client = loop {
         match async_nats::connect(url).await {
@@ -39,7 +44,8 @@ pub async fn connect(url: &str, bucket: &str) -> anyhow::Result<Nats> {
                 bucket: bucket.to_string(),
                 ..Default::default()
             })
-            .await?
+            .await
+            .map_err(|e| MonitorError::Nats(format!("Failed to create object store bucket '{}': {}", bucket, e)))?
         },
     };
     info!(bucket, "NATS Object Store ready");
@@ -63,3 +69,40 @@ pub async fn publish_block(
     let _obj = object_store.put(key.as_str(), &mut cursor).await?;
     Ok(())
 }
+
+/// Publish a chain reorganization event to the `blocks.reorg.<chain_id>` subject
+pub async fn publish_reorg(
+    client: &Client,
+    event: &ReorgEvent,
+) -> anyhow::Result<()> {
+    let subject = format!("blocks.reorg.{}", event.chain_id);
+    let bytes = serde_json::to_vec(event)?;
+    client.publish(subject, bytes.into()).await?;
+    Ok(())
+}
+
+/// Best-effort publishing of the blocks to NATS, see `monitor_common::nats::NatsPublisher`
+pub type NatsPublisher = monitor_common::nats::NatsPublisher<Nats>;
+
+impl PublishTarget for Nats {
+    type Payload = BlockPayload;
+
+    const PAYLOADS: &'static str = "blocks";
+
+    async fn publish(&self, payload: &BlockPayload) -> anyhow::Result<()> {
+        publish_block(&self.object_store, payload).await
+    }
+
+    fn client(&self) -> &Client {
+        &self.client
+    }
+}
+
+/// Buffering and timeouts of the NATS publisher
+pub fn publisher_cfg(cfg: &NatsCfg) -> PublisherCfg {
+    PublisherCfg {
+        buffer_capacity: cfg.buffer_capacity,
+        retry_interval_secs: cfg.retry_interval_secs,
+        publish_timeout_secs: cfg.publish_timeout_secs,
+    }
+}
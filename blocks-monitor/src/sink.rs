@@ -0,0 +1,136 @@
+use std::sync::Arc;
+use std::time::Duration;
+use async_trait::async_trait;
+use tracing::info;
+
+use crate::config::{AppCfg, SinkCfg};
+use crate::db::DatabaseClients;
+use crate::nats::NatsPublisher;
+use crate::types::BlockPayload;
+
+/// Default timeout of webhook sink requests
+const DEFAULT_WEBHOOK_TIMEOUT_SECS: u64 = 10;
+
+/// Destination of processed blocks. A new destination is one implementation of this trait
+/// plus its `sinks` config entry, built in `build_sinks`.
+#[async_trait]
+pub trait OutputSink: Send + Sync {
+    /// Sink name for logging
+    fn name(&self) -> &str;
+
+    async fn write(&self, payload: &BlockPayload) -> anyhow::Result<()>;
+
+    /// Flush writes buffered by the sink, no-op by default as writes are awaited
+    async fn flush(&self) -> anyhow::Result<()> {
+        Ok(())
+    }
+}
+
+/// Local PostgreSQL + AWS RDS (if enabled)
+pub struct PostgresSink {
+    db_clients: Arc<DatabaseClients>,
+}
+
+impl PostgresSink {
+    pub fn new(db_clients: Arc<DatabaseClients>) -> Self {
+        Self { db_clients }
+    }
+}
+
+#[async_trait]
+impl OutputSink for PostgresSink {
+    fn name(&self) -> &str {
+        "postgres"
+    }
+
+    async fn write(&self, payload: &BlockPayload) -> anyhow::Result<()> {
+        self.db_clients.insert_block(payload).await
+    }
+}
+
+/// NATS Object Store. Best-effort: writes don't fail while NATS is degraded, see `NatsPublisher`.
+pub struct NatsObjectStoreSink {
+    publisher: NatsPublisher,
+}
+
+impl NatsObjectStoreSink {
+    pub fn new(publisher: NatsPublisher) -> Self {
+        Self { publisher }
+    }
+}
+
+#[async_trait]
+impl OutputSink for NatsObjectStoreSink {
+    fn name(&self) -> &str {
+        "nats"
+    }
+
+    async fn write(&self, payload: &BlockPayload) -> anyhow::Result<()> {
+        self.publisher.publish(payload).await;
+        Ok(())
+    }
+
+    async fn flush(&self) -> anyhow::Result<()> {
+        self.publisher.flush().await
+    }
+}
+
+/// HTTP webhook, receiving each block payload as JSON POST request
+pub struct WebhookSink {
+    client: reqwest::Client,
+    url: String,
+}
+
+impl WebhookSink {
+    pub fn new(url: &str, timeout_secs: Option<u64>) -> anyhow::Result<Self> {
+        let client = reqwest::Client::builder()
+            .timeout(Duration::from_secs(timeout_secs.unwrap_or(DEFAULT_WEBHOOK_TIMEOUT_SECS)))
+            .build()?;
+        Ok(Self { client, url: url.to_string() })
+    }
+}
+
+#[async_trait]
+impl OutputSink for WebhookSink {
+    fn name(&self) -> &str {
+        "webhook"
+    }
+
+    async fn write(&self, payload: &BlockPayload) -> anyhow::Result<()> {
+        self.client
+            .post(&self.url)
+            .json(payload)
+            .send()
+            .await?
+            .error_for_status()?;
+        Ok(())
+    }
+}
+
+/// Build output sinks: PostgreSQL always, NATS Object Store if enabled, then the `sinks` config entries
+pub fn build_sinks(
+    config: &AppCfg,
+    db_clients: Arc<DatabaseClients>,
+    nats_publisher: Option<&NatsPublisher>,
+) -> anyhow::Result<Vec<Box<dyn OutputSink>>> {
+    let mut sinks: Vec<Box<dyn OutputSink>> = vec![Box::new(PostgresSink::new(db_clients))];
+
+    if let Some(nats_publisher) = nats_publisher {
+        sinks.push(Box::new(NatsObjectStoreSink::new(nats_publisher.clone())));
+    }
+
+    for sink_cfg in config.sinks.iter().flatten() {
+        match sink_cfg {
+            SinkCfg::Webhook { url, timeout_secs } => {
+                sinks.push(Box::new(WebhookSink::new(url, *timeout_secs)?));
+            }
+        }
+    }
+
+    info!(
+        "Output sinks: {}",
+        sinks.iter().map(|sink| sink.name()).collect::<Vec<_>>().join(", ")
+    );
+
+    Ok(sinks)
+}
@@ -1,68 +1,81 @@
 use std::collections::BTreeMap;
 use futures_util::StreamExt;
-use tracing::{info, error, debug};
+use tracing::{info, error, debug, warn};
 
 use alloy::{
     providers::{Provider, ProviderBuilder, WsConnect},
     transports::ws::WebSocketConfig,
+    rpc::client::ClientBuilder,
     rpc::types::{BlockNumberOrTag, BlockTransactionsKind, BlockId},
-    primitives::Address,
+    primitives::{Address, B256},
 };
 use alloy::providers::fillers::{BlobGasFiller, ChainIdFiller, FillProvider, GasFiller, JoinFill, NonceFiller};
 use alloy::providers::{Identity, RootProvider, WatchBlocks};
 use alloy::consensus::{BlockBody, Transaction};
-use alloy::network::TransactionResponse;
+use alloy::network::{ReceiptResponse, TransactionResponse};
 
-use crate::{db::{self, DatabaseClients}, nats::{self, Nats}};
+use monitor_common::error::{MonitorError, Result};
+use rpc_limits::{RpcRateLimitLayer, RpcRateLimiter};
+
+use crate::{db::{self, DatabaseClients}, nats::{self, Nats, NatsPublisher}};
+use crate::sink::{self, OutputSink};
+use crate::metrics;
 use crate::config::AppCfg as AppConfig;
-use crate::types::BlockPayload;
+use crate::types::{BlockPayload, GapFillerStats, ReorgEvent};
+use monitor_common::labels::AddressLabels;
 
 use std::ops::{Range, RangeFrom};
 use std::str::FromStr;
 use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
 use alloy::eips::RpcBlockHash;
 use alloy::rpc::types::TransactionTrait;
-use anyhow::anyhow;
+use tokio::sync::RwLock;
 use tokio::task::JoinHandle;
 
 type RPCProvider = FillProvider<JoinFill<Identity, JoinFill<GasFiller, JoinFill<BlobGasFiller, JoinFill<NonceFiller, ChainIdFiller>>>>, RootProvider>;
 
 pub struct BlockProcessor {
-    db_clients: DatabaseClients,
-    nats_store: Option<Nats>,
+    db_clients: Arc<DatabaseClients>,
+    nats_store: Option<Nats>, // Reorg notifications
+    nats_publisher: Option<NatsPublisher>, // Best-effort NATS publishing of the blocks, shared with the NATS sink
+    sinks: Vec<Box<dyn OutputSink>>,
     config: AppConfig,
-    ws_rpc_provider: RPCProvider,
+    ws_rpc_provider: RwLock<RPCProvider>,
+    ws_rpc_endpoints: Vec<String>,
+    ws_rpc_endpoint_index: AtomicUsize,
     http_rpc_provider: RPCProvider,
     chain_id: u64,
     filter_senders: Option<Vec<Address>>,
     filter_receivers: Option<Vec<Address>>,
+    historical_sync_done: AtomicBool,
+    gap_filler_stats: Option<Arc<RwLock<GapFillerStats>>>,
+    address_labels: AddressLabels,
+    rpc_rate_limiter: Option<Arc<RpcRateLimiter>>, // Shared by the HTTP provider requests and the WS requests
 }
 
 impl BlockProcessor {
-    pub async fn new(config: &AppConfig, db_clients: DatabaseClients, nats_store: Option<Nats>) -> anyhow::Result<Self> {
-        let ws_config = WebSocketConfig::default()
-            .read_buffer_size(256 * 1024)
-            .write_buffer_size(256 * 1024)
-            .max_message_size(Some(1024 * 1024 * 1024))
-            .max_frame_size(Some(256 * 1024 * 1024))
-            .accept_unmasked_frames(false);
-        let ws = WsConnect::new(&config.chain.ws_rpc_url).with_config(ws_config);
-        let http_rpc = reqwest::Url::from_str(&config.chain.http_rpc_url)?;
-        let (ws_rpc_provider, http_rpc_provider) = build_providers(ws, http_rpc).await?;
+    pub async fn new(config: &AppConfig, db_clients: DatabaseClients, nats_store: Option<Nats>) -> Result<Self> {
+        let ws_rpc_endpoints = config.chain.ws_rpc_endpoints();
+        let http_rpc_endpoints = config.chain.http_rpc_endpoints();
+        let rpc_rate_limiter = config.chain.rpc_rate_limit
+            .map(|requests_per_sec| RpcRateLimiter::new(requests_per_sec, config.chain.rpc_rate_limit_burst));
+        let (ws_rpc_provider, http_rpc_provider, ws_rpc_endpoint_index) =
+            build_providers(&ws_rpc_endpoints, &http_rpc_endpoints, rpc_rate_limiter.as_ref()).await?;
 
         let chain_id = http_rpc_provider.get_chain_id().await?;
         if chain_id != config.chain.chain_id {
-            anyhow::bail!("Chain ID mismatch: expected {}, got {}", config.chain.chain_id, chain_id);
+            return Err(MonitorError::Config(format!("Chain ID mismatch: expected {}, got {}", config.chain.chain_id, chain_id)));
         }
         info!("Chain ID: {}", chain_id);
 
         // Parse sender filtering addresses from configuration
         let filter_senders = if let Some(senders) = &config.indexing.filter_senders {
             if !senders.is_empty() {
-                let parsed: Result<Vec<Address>, _> = senders.iter()
+                let parsed: std::result::Result<Vec<Address>, _> = senders.iter()
                     .map(|s| Address::from_str(s))
                     .collect();
-                let parsed = parsed?;
+                let parsed = parsed.map_err(|e| MonitorError::Config(format!("Invalid filter sender address: {}", e)))?;
                 info!("Filter senders configured: {} addresses", parsed.len());
                 Some(parsed)
             } else {
@@ -75,10 +88,10 @@ impl BlockProcessor {
         // Parse receiver filtering addresses from configuration
         let filter_receivers = if let Some(receivers) = &config.indexing.filter_receivers {
             if !receivers.is_empty() {
-                let parsed: Result<Vec<Address>, _> = receivers.iter()
+                let parsed: std::result::Result<Vec<Address>, _> = receivers.iter()
                     .map(|s| Address::from_str(s))
                     .collect();
-                let parsed = parsed?;
+                let parsed = parsed.map_err(|e| MonitorError::Config(format!("Invalid filter receiver address: {}", e)))?;
                 info!("Filter receivers configured: {} addresses", parsed.len());
                 Some(parsed)
             } else {
@@ -88,25 +101,58 @@ impl BlockProcessor {
             None
         };
 
+        let gap_filler_stats = if config.indexing.gap_filler.unwrap_or(false) {
+            Some(Arc::new(RwLock::new(GapFillerStats::default())))
+        } else {
+            None
+        };
+
+        let address_labels = match &config.labels {
+            Some(labels) => AddressLabels::load(labels).map_err(|e| MonitorError::Config(format!("{:#}", e)))?,
+            None => AddressLabels::default(),
+        };
+
+        let db_clients = Arc::new(db_clients);
+        let nats_publisher = nats_store.clone().map(|nats| NatsPublisher::new(nats, nats::publisher_cfg(&config.nats)));
+        let sinks = sink::build_sinks(config, Arc::clone(&db_clients), nats_publisher.as_ref())
+            .map_err(|e| MonitorError::Config(format!("{:#}", e)))?;
+
         Ok(Self {
             db_clients,
             nats_store,
+            nats_publisher,
+            sinks,
             config: config.clone(),
-            ws_rpc_provider,
+            ws_rpc_provider: RwLock::new(ws_rpc_provider),
+            ws_rpc_endpoints,
+            ws_rpc_endpoint_index: AtomicUsize::new(ws_rpc_endpoint_index),
             http_rpc_provider,
             chain_id,
             filter_senders,
             filter_receivers,
+            historical_sync_done: AtomicBool::new(false),
+            gap_filler_stats,
+            address_labels,
+            rpc_rate_limiter,
         })
     }
 
-    pub async fn run(self) -> anyhow::Result<()> {
+    pub fn gap_filler_stats(&self) -> Option<Arc<RwLock<GapFillerStats>>> {
+        self.gap_filler_stats.clone()
+    }
+
+    /// NATS publisher if NATS is enabled, shared with the task manager for the API health
+    pub fn nats_publisher(&self) -> Option<NatsPublisher> {
+        self.nats_publisher.clone()
+    }
+
+    pub async fn run(self) -> Result<()> {
         let self_arc = Arc::new(self);
 
         let from_block = self_arc.config.indexing.from_block.unwrap_or(0u64);
         let to_block = self_arc.config.indexing.to_block;
 
-        let mut handles: Vec<JoinHandle<anyhow::Result<()>>> = Vec::new();
+        let mut handles: Vec<JoinHandle<Result<()>>> = Vec::new();
 
         // Task 1: Process historical blocks, if enabled
         let process_historical_blocks = self_arc.config.indexing.historical_blocks_processing.is_some_and(|process_blocks| process_blocks > 0);
@@ -131,7 +177,7 @@ impl BlockProcessor {
                         Ok(latest) => latest,
                         Err(e) => {
                             error!("Failed to get latest block number: {:?}", e);
-                            return Err(anyhow!("Failed to get latest block number: {:?}", e));
+                            return Err(e.into());
                         }
                     }
                 };
@@ -143,75 +189,11 @@ impl BlockProcessor {
                 );
 
                 // Process blocks in chunks
-                let mut current_block = from_block;
-                let mut total_blocks_processed = 0usize;
-
-                while current_block < end_block {
-                    let chunk_end = std::cmp::min(current_block + chunk_size, end_block);
-
-                    info!("Fetching blocks for block range {}..{}", current_block, chunk_end);
-
-                    // Process each block in the chunk
-                    for block_num in current_block..chunk_end {
-                        let block_id = BlockId::Number(BlockNumberOrTag::Number(block_num));
-
-                        // Fetch block using the configured protocol
-                        let block = match blocks_sync_protocol {
-                            Some(ref protocol) if protocol.to_lowercase() == "http" => {
-                                if full_blocks {
-                                    processor_for_history.http_rpc_provider
-                                        .get_block(block_id).full()
-                                        .await?
-                                } else {
-                                    processor_for_history.http_rpc_provider
-                                        .get_block(block_id)
-                                        .await?
-                                }
-                            },
-                            Some(ref protocol) if protocol.to_lowercase() == "ws" => {
-                                if full_blocks {
-                                    processor_for_history.ws_rpc_provider
-                                        .get_block(block_id).full()
-                                        .await?
-                                } else {
-                                    processor_for_history.ws_rpc_provider
-                                        .get_block(block_id)
-                                        .await?
-                                }
-                            },
-                            _ => {
-                                debug!("Invalid or missing block sync protocol, using 'http' as fallback");
-                                if full_blocks {
-                                    processor_for_history.http_rpc_provider
-                                        .get_block(block_id).full()
-                                        .await?
-                                } else {
-                                    processor_for_history.http_rpc_provider
-                                        .get_block(block_id)
-                                        .await?
-                                }
-                            }
-                        };
-
-                        if let Some(block) = block {
-                            debug!("Received historical block: {}", block.header.number.to_string());
-                            total_blocks_processed += 1;
-
-                            if let Err(e) = processor_for_history.handle_block(block).await {
-                                error!("Failed to handle historical block: {:?}", e);
-                                eprintln!("Historical block error: {:?}", e);
-                            }
-                        }
-                    }
-
-                    // Move to the next chunk
-                    current_block = chunk_end;
-
-                    // Optional: Add a small delay between chunks to avoid overwhelming the RPC
-                    if current_block < end_block {
-                        tokio::time::sleep(tokio::time::Duration::from_millis(100)).await;
-                    }
-                }
+                let total_blocks_processed = processor_for_history
+                    .process_block_range(from_block, end_block, chunk_size, blocks_sync_protocol.as_deref(), full_blocks)
+                    .await;
+                processor_for_history.historical_sync_done.store(true, Ordering::Relaxed);
+                let total_blocks_processed = total_blocks_processed?;
 
                 info!(
                     "Historical blocks processing completed: processed {} total blocks from {} to {}",
@@ -221,6 +203,8 @@ impl BlockProcessor {
                 Ok(())
             });
             handles.push(historical_task);
+        } else {
+            self_arc.historical_sync_done.store(true, Ordering::Relaxed);
         }
 
         // Task 2: Subscribe to new blocks, if enabled
@@ -274,7 +258,7 @@ impl BlockProcessor {
                             while let Some(new_block_hash_bytes) = block_stream.next().await {
                                 let block_hash = format!("0x{}", hex::encode(new_block_hash_bytes.0.as_slice()));
                                 debug!("Received (watch_blocks) block hash: {}", block_hash);
-                                let block = processor_for_subscription.http_rpc_provider.get_block(BlockId::Hash(RpcBlockHash::from_hash(new_block_hash_bytes, Some(false)))).await?;
+                                let block = metrics::rpc_call("eth_getBlockByHash", processor_for_subscription.http_rpc_provider.get_block(BlockId::Hash(RpcBlockHash::from_hash(new_block_hash_bytes, Some(false))))).await?;
                                 if let Some(block) = block {
                                     debug!("Received (watch_blocks + get_block) block: {}", block.header.number.to_string());
                                     if let Err(e) = processor_for_subscription.handle_block(block).await {
@@ -319,7 +303,7 @@ impl BlockProcessor {
                             while let Some(new_block_hash_bytes) = block_stream.next().await {
                                 let block_hash = format!("0x{}", hex::encode(new_block_hash_bytes.0.as_slice()));
                                 debug!("Received (watch_blocks) block hash: {}", block_hash);
-                                let block = processor_for_subscription.http_rpc_provider.get_block(BlockId::Hash(RpcBlockHash::from_hash(new_block_hash_bytes, Some(false)))).await?;
+                                let block = metrics::rpc_call("eth_getBlockByHash", processor_for_subscription.http_rpc_provider.get_block(BlockId::Hash(RpcBlockHash::from_hash(new_block_hash_bytes, Some(false))))).await?;
                                 if let Some(block) = block {
                                     debug!("Received (watch_blocks + get_block) block: {}", block.header.number.to_string());
                                     // Create and reconstruct block from the header to match the expected Block type (w/o transactions w/ txs hashes only)
@@ -359,98 +343,578 @@ impl BlockProcessor {
                         ws_subscription_channel_size
                     );
 
-                    let provider = processor_for_subscription.ws_rpc_provider.clone();
+                    let ws_subscription_method = ws_subscription_method.to_lowercase();
+                    if ws_subscription_method != "subscribe_full_blocks" && ws_subscription_method != "subscribe_blocks" {
+                        warn!("Unsupported WebSocket subscription method: {}", ws_subscription_method);
+                        return Ok(());
+                    }
 
-                    if ws_subscription_method.to_lowercase() == "subscribe_full_blocks" {
-                        let sub = if full_blocks {
-                            provider.subscribe_full_blocks().full().channel_size(ws_subscription_channel_size as usize)
-                        } else {
-                            provider.subscribe_full_blocks().hashes().channel_size(ws_subscription_channel_size as usize)
-                        };
+                    let reconnect_delay_secs = processor_for_subscription.config.chain.ws_reconnect_delay_secs.unwrap_or(5);
+                    let max_reconnect_attempts = processor_for_subscription.config.chain.ws_max_reconnect_attempts;
+                    let mut reconnect_attempts = 0u64;
 
-                        info!("Subscribed to new blocks (subscribe_full_blocks) via WebSocket");
+                    // Number of the last processed block, to resume from after reconnection
+                    let mut last_block: Option<u64> = None;
+                    let mut subscribed_from_block: Option<u64> = None;
 
-                        let mut sub_stream = sub.into_stream().await?;
+                    loop {
+                        let provider = processor_for_subscription.ws_rpc_provider().await;
 
-                        while let Some(block) = sub_stream.next().await.transpose()? {
-                            debug!("Received subscription block: {}", block.header.number.to_string());
-                            if let Err(e) = processor_for_subscription.handle_block(block).await {
-                                error!("Failed to handle subscription block: {:?}", e);
-                                eprintln!("Failed to handle subscription block: {:?}", e);
-                            }
+                        // Blocks to catch up after reconnection, the first subscription starts from the current head
+                        let resume_block = last_block.map(|block| block + 1).or(subscribed_from_block);
+                        if resume_block.is_none() {
+                            subscribed_from_block = processor_for_subscription.http_rpc_provider.get_block_number().await.ok();
                         }
-                    } else if ws_subscription_method.to_lowercase() == "subscribe_blocks" {
-                        let sub = provider.subscribe_blocks().channel_size(ws_subscription_channel_size as usize).await?;
-
-                        info!("Subscribed to new blocks (subscribe_blocks) via WebSocket");
 
-                        let mut sub_stream = sub.into_stream();
+                        if ws_subscription_method == "subscribe_full_blocks" {
+                            let sub = if full_blocks {
+                                provider.subscribe_full_blocks().full().channel_size(ws_subscription_channel_size as usize)
+                            } else {
+                                provider.subscribe_full_blocks().hashes().channel_size(ws_subscription_channel_size as usize)
+                            };
+
+                            match sub.into_stream().await {
+                                Ok(mut sub_stream) => {
+                                    info!("Subscribed to new blocks (subscribe_full_blocks) via WebSocket");
+
+                                    if let Some(resume_block) = resume_block {
+                                        match processor_for_subscription.catch_up_blocks(resume_block, full_blocks).await {
+                                            Ok(latest_block) => last_block = Some(latest_block),
+                                            Err(e) => error!("Failed to catch up blocks from block {}: {:?}", resume_block, e),
+                                        }
+                                    }
 
-                        while let Some(block_header) = sub_stream.next().await {
-                            debug!("Received subscription block header of block number: {}", block_header.number.to_string());
-                            if full_blocks {
-                                let block_hash = block_header.hash.clone();
-                                let block = provider.get_block(BlockId::Hash(RpcBlockHash::from_hash(block_hash, Some(false)))).await?;
-                                if let Some(block) = block {
-                                    debug!("Received (subscribe_blocks + get_block) block: {}", block.header.number.to_string());
-                                    if let Err(e) = processor_for_subscription.handle_block(block).await {
-                                        error!("Failed to handle subscription block: {:?}", e);
-                                        eprintln!("Failed to handle subscription block: {:?}", e);
+                                    loop {
+                                        let block = match sub_stream.next().await {
+                                            Some(Ok(block)) => block,
+                                            Some(Err(e)) => {
+                                                error!("Subscription stream error: {:?}", e);
+                                                break;
+                                            }
+                                            None => break,
+                                        };
+                                        reconnect_attempts = 0;
+
+                                        let block_number = block.header.number;
+                                        if last_block.is_some_and(|last_block| block_number <= last_block) {
+                                            debug!("Skipping already processed subscription block: {}", block_number);
+                                            continue;
+                                        }
+
+                                        debug!("Received subscription block: {}", block_number);
+                                        if let Err(e) = processor_for_subscription.handle_block(block).await {
+                                            error!("Failed to handle subscription block: {:?}", e);
+                                            eprintln!("Failed to handle subscription block: {:?}", e);
+                                        }
+                                        last_block = Some(block_number);
                                     }
-                                } else {
-                                    error!("Failed to get block for hash {}", block_hash);
-                                    eprintln!("Failed to get block for hash {}", block_hash);
                                 }
-                            } else {
-                                let block_hash = block_header.hash.clone();
-                                let block = provider.get_block(BlockId::Hash(RpcBlockHash::from_hash(block_hash, Some(false)))).await?;
-                                if let Some(block) = block {
-                                    debug!("Received (subscribe_blocks + get_block) block: {}", block.header.number.to_string());
-                                    // Create and reconstruct block from the header to match the expected Block type (w/o transactions w/ txs hashes only)
-                                    let block_txless = alloy::rpc::types::Block {
-                                        header: block.header.clone(),
-                                        uncles: block.uncles.clone(),
-                                        transactions: alloy::rpc::types::BlockTransactions::<_>::Hashes(block.transactions.as_hashes().unwrap_or(vec![].as_ref()).to_vec()),
-                                        withdrawals: block.withdrawals.clone(),
-                                    };
-                                    if let Err(e) = processor_for_subscription.handle_block(block_txless).await {
-                                        error!("Failed to handle subscription block (constructed block from the header and txs hashes taken by get_block): {:?}", e);
-                                        eprintln!("Failed to handle subscription block (constructed block from the header and txs hashes taken by get_block): {:?}", e);
+                                Err(e) => {
+                                    error!("Failed to subscribe to new blocks (subscribe_full_blocks) via WebSocket: {:?}", e);
+                                }
+                            }
+                        } else {
+                            match provider.subscribe_blocks().channel_size(ws_subscription_channel_size as usize).await {
+                                Ok(sub) => {
+                                    info!("Subscribed to new blocks (subscribe_blocks) via WebSocket");
+
+                                    if let Some(resume_block) = resume_block {
+                                        match processor_for_subscription.catch_up_blocks(resume_block, full_blocks).await {
+                                            Ok(latest_block) => last_block = Some(latest_block),
+                                            Err(e) => error!("Failed to catch up blocks from block {}: {:?}", resume_block, e),
+                                        }
+                                    }
+
+                                    let mut sub_stream = sub.into_stream();
+
+                                    while let Some(block_header) = sub_stream.next().await {
+                                        reconnect_attempts = 0;
+
+                                        let block_number = block_header.number;
+                                        if last_block.is_some_and(|last_block| block_number <= last_block) {
+                                            debug!("Skipping already processed subscription block: {}", block_number);
+                                            continue;
+                                        }
+
+                                        debug!("Received subscription block header of block number: {}", block_number);
+                                        let block_hash = block_header.hash;
+                                        // WS requests share the rate limit of the HTTP provider
+                                        if let Some(rpc_rate_limiter) = &processor_for_subscription.rpc_rate_limiter {
+                                            rpc_rate_limiter.acquire(1).await;
+                                        }
+                                        let block = match metrics::rpc_call("eth_getBlockByHash", provider.get_block(BlockId::Hash(RpcBlockHash::from_hash(block_hash, Some(false))))).await {
+                                            Ok(block) => block,
+                                            Err(e) => {
+                                                error!("Failed to get block for hash {}: {:?}", block_hash, e);
+                                                break;
+                                            }
+                                        };
+
+                                        if let Some(block) = block {
+                                            debug!("Received (subscribe_blocks + get_block) block: {}", block.header.number.to_string());
+                                            let block = if full_blocks {
+                                                block
+                                            } else {
+                                                // Create and reconstruct block from the header to match the expected Block type (w/o transactions w/ txs hashes only)
+                                                alloy::rpc::types::Block {
+                                                    header: block.header.clone(),
+                                                    uncles: block.uncles.clone(),
+                                                    transactions: alloy::rpc::types::BlockTransactions::<_>::Hashes(block.transactions.as_hashes().unwrap_or(vec![].as_ref()).to_vec()),
+                                                    withdrawals: block.withdrawals.clone(),
+                                                }
+                                            };
+                                            if let Err(e) = processor_for_subscription.handle_block(block).await {
+                                                error!("Failed to handle subscription block: {:?}", e);
+                                                eprintln!("Failed to handle subscription block: {:?}", e);
+                                            }
+                                            last_block = Some(block_number);
+                                        } else {
+                                            error!("Failed to get block for hash {}", block_hash);
+                                            eprintln!("Failed to get block for hash {}", block_hash);
+                                        }
                                     }
-                                } else {
-                                    error!("Failed to get block for hash {}", block_hash);
-                                    eprintln!("Failed to get block for hash {}", block_hash);
+                                }
+                                Err(e) => {
+                                    error!("Failed to subscribe to new blocks (subscribe_blocks) via WebSocket: {:?}", e);
                                 }
                             }
                         }
-                    }
 
-                    info!("Subscription task completed");
-                    Ok(())
+                        warn!("WebSocket blocks subscription stream terminated");
+
+                        reconnect_attempts += 1;
+                        if let Some(max_attempts) = max_reconnect_attempts && reconnect_attempts > max_attempts {
+                            return Err(MonitorError::Rpc(format!("WebSocket subscription failed after {} reconnection attempts", max_attempts)));
+                        }
+
+                        tokio::time::sleep(tokio::time::Duration::from_secs(reconnect_delay_secs)).await;
+
+                        if let Err(e) = processor_for_subscription.reconnect_ws().await {
+                            error!("Failed to reconnect to WebSocket RPC: {:?}", e);
+                        }
+                    }
                 });
                 handles.push(subscription_task);
             }
         }
 
+        // Task 3: Backfill missing blocks within the indexed range, if enabled
+        if let Some(gap_filler_stats) = self_arc.gap_filler_stats.clone() {
+            let processor_for_gaps = Arc::clone(&self_arc);
+            let interval_secs = processor_for_gaps.config.indexing.gap_filler_interval_secs.unwrap_or(60);
+
+            let gap_filler_task = tokio::spawn(async move {
+                info!("Starting missing blocks backfill task (interval: {}s)", interval_secs);
+
+                let mut interval = tokio::time::interval(tokio::time::Duration::from_secs(interval_secs));
+
+                loop {
+                    interval.tick().await;
+
+                    // Blocks ahead of the historical sync aren't gaps yet
+                    if !processor_for_gaps.historical_sync_done.load(Ordering::Relaxed) {
+                        debug!("Historical blocks processing is in progress, skipping gap check");
+                        continue;
+                    }
+
+                    if let Err(e) = processor_for_gaps.fill_gaps(from_block, to_block, &gap_filler_stats).await {
+                        error!("Failed to backfill missing blocks: {:?}", e);
+                    }
+                }
+            });
+            handles.push(gap_filler_task);
+        }
+
         // Wait for all tasks to complete
         for handle in handles {
             match handle.await {
                 Ok(Ok(())) => info!("Task completed successfully"),
                 Ok(Err(e)) => {
                     error!("Task failed with error: {:?}", e);
+                    metrics::task_error(e.kind());
                     return Err(e);
                 }
                 Err(join_err) => {
                     error!("Task panicked: {:?}", join_err);
-                    return Err(anyhow!("Task panicked: {:?}", join_err));
+                    let e = MonitorError::Internal(format!("Task panicked: {:?}", join_err));
+                    metrics::task_error(e.kind());
+                    return Err(e);
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn ws_rpc_provider(&self) -> RPCProvider {
+        self.ws_rpc_provider.read().await.clone()
+    }
+
+    /// Switch the WS provider to the next reachable endpoint after the current one
+    async fn reconnect_ws(&self) -> Result<()> {
+        let next_index = (self.ws_rpc_endpoint_index.load(Ordering::Relaxed) + 1) % self.ws_rpc_endpoints.len();
+        let (ws_rpc_provider, ws_rpc_endpoint_index) = connect_ws_provider(&self.ws_rpc_endpoints, next_index).await?;
+
+        *self.ws_rpc_provider.write().await = ws_rpc_provider;
+        self.ws_rpc_endpoint_index.store(ws_rpc_endpoint_index, Ordering::Relaxed);
+
+        Ok(())
+    }
+
+    /// Fetch and process blocks from `from_block` up to the latest block via HTTP RPC, missed
+    /// while the subscription was disconnected, returns the latest processed block number
+    async fn catch_up_blocks(&self, from_block: u64, full_blocks: bool) -> Result<u64> {
+        let chunk_size = self.config.indexing.blocks_chunk_size.unwrap_or(100);
+        let latest_block = self.http_rpc_provider.get_block_number().await?;
+
+        info!("Catching up blocks from block {} to {}", from_block, latest_block);
+        self.process_block_range(from_block, latest_block + 1, chunk_size, Some("http"), full_blocks).await?;
+
+        Ok(latest_block)
+    }
+
+    /// Look up missing blocks from `from_block` to the highest stored block and re-fetch them
+    /// via HTTP RPC, contiguous runs of missing blocks are fetched as block ranges
+    async fn fill_gaps(&self, from_block: u64, to_block: Option<u64>, stats: &RwLock<GapFillerStats>) -> Result<()> {
+        let chain_id = self.chain_id.to_string();
+        let max_blocks = self.config.indexing.gap_filler_max_blocks.unwrap_or(1000);
+        let chunk_size = self.config.indexing.blocks_chunk_size.unwrap_or(100);
+        let full_blocks = self.config.indexing.full_blocks_historical.unwrap_or(false);
+
+        let missing = self.db_clients.missing_block_numbers(&chain_id, from_block, to_block, max_blocks).await
+            .map_err(|e| MonitorError::Database(format!("{:#}", e)))?;
+
+        {
+            let mut stats = stats.write().await;
+            stats.detected_gaps = missing.len() as u64;
+            stats.last_check_at = Some(chrono::Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Millis, true));
+        }
+
+        if missing.is_empty() {
+            debug!("No missing blocks found from block {}", from_block);
+            return Ok(());
+        }
+
+        warn!("Found {} missing blocks from block {}, backfilling", missing.len(), from_block);
+
+        for range in contiguous_ranges(&missing) {
+            let backfilled = self.process_block_range(range.start, range.end, chunk_size, Some("http"), full_blocks).await?;
+            stats.write().await.backfilled_blocks += backfilled as u64;
+            info!("Backfilled {} missing blocks in block range {}..{}", backfilled, range.start, range.end);
+        }
+
+        Ok(())
+    }
+
+    /// Fetch and process blocks in `from_block..end_block` in chunks of `chunk_size` blocks,
+    /// returns the number of processed blocks
+    async fn process_block_range(
+        &self,
+        from_block: u64,
+        end_block: u64,
+        chunk_size: u64,
+        blocks_sync_protocol: Option<&str>,
+        full_blocks: bool,
+    ) -> Result<usize> {
+        let mut current_block = from_block;
+        let mut total_blocks_processed = 0usize;
+
+        while current_block < end_block {
+            let chunk_end = std::cmp::min(current_block + chunk_size, end_block);
+
+            info!("Fetching blocks for block range {}..{}", current_block, chunk_end);
+
+            // Process each block in the chunk
+            for block_num in current_block..chunk_end {
+                let block = self.fetch_block(block_num, blocks_sync_protocol, full_blocks).await?;
+
+                if let Some(block) = block {
+                    debug!("Received block: {}", block.header.number.to_string());
+                    total_blocks_processed += 1;
+
+                    if let Err(e) = self.handle_block(block).await {
+                        error!("Failed to handle historical block: {:?}", e);
+                        eprintln!("Historical block error: {:?}", e);
+                    }
+                }
+            }
+
+            // Move to the next chunk
+            current_block = chunk_end;
+
+            // Optional: Add a small delay between chunks to avoid overwhelming the RPC
+            if current_block < end_block {
+                tokio::time::sleep(tokio::time::Duration::from_millis(100)).await;
+            }
+        }
+
+        Ok(total_blocks_processed)
+    }
+
+    /// Fetch the block by number using the blocks sync protocol, `None` if it doesn't exist yet
+    async fn fetch_block(
+        &self,
+        block_num: u64,
+        blocks_sync_protocol: Option<&str>,
+        full_blocks: bool,
+    ) -> Result<Option<alloy::rpc::types::Block>> {
+        let block_id = BlockId::Number(BlockNumberOrTag::Number(block_num));
+
+        // Fetch block using the configured protocol
+        let block = metrics::rpc_call("eth_getBlockByNumber", async {
+            match blocks_sync_protocol {
+                Some(protocol) if protocol.to_lowercase() == "http" => {
+                    if full_blocks {
+                        self.http_rpc_provider
+                            .get_block(block_id).full()
+                            .await
+                    } else {
+                        self.http_rpc_provider
+                            .get_block(block_id)
+                            .await
+                    }
+                },
+                Some(protocol) if protocol.to_lowercase() == "ws" => {
+                    if full_blocks {
+                        self.ws_rpc_provider().await
+                            .get_block(block_id).full()
+                            .await
+                    } else {
+                        self.ws_rpc_provider().await
+                            .get_block(block_id)
+                            .await
+                    }
+                },
+                _ => {
+                    debug!("Invalid or missing block sync protocol, using 'http' as fallback");
+                    if full_blocks {
+                        self.http_rpc_provider
+                            .get_block(block_id).full()
+                            .await
+                    } else {
+                        self.http_rpc_provider
+                            .get_block(block_id)
+                            .await
+                    }
                 }
             }
+        }).await?;
+
+        Ok(block)
+    }
+
+    /// Process the blocks of the `from_block..=to_block` range once, for one-shot backfill jobs.
+    /// Fails if any block failed to persist, or if blocks of the range are still missing afterwards.
+    pub async fn backfill(self, from_block: u64, to_block: u64) -> Result<()> {
+        let latest_block = metrics::rpc_call("eth_blockNumber", self.http_rpc_provider.get_block_number()).await?;
+        if to_block > latest_block {
+            return Err(MonitorError::Config(format!("Backfill end block {} is ahead of the chain tip {}", to_block, latest_block)));
+        }
+
+        let full_blocks = self.config.indexing.full_blocks_historical.unwrap_or(false);
+
+        info!(
+            "Backfilling blocks from block {} to {} ({})",
+            from_block, to_block,
+            if full_blocks { "full blocks" } else { "headers only" }
+        );
+
+        let mut failed_blocks = 0u64;
+        let mut first_error = None;
+        for block_num in from_block..=to_block {
+            let result = match self.fetch_block(block_num, Some("http"), full_blocks).await {
+                Ok(Some(block)) => self.handle_block(block).await,
+                Ok(None) => Err(MonitorError::Rpc(format!("Block {} not found", block_num))),
+                Err(e) => Err(e),
+            };
+            if let Err(e) = result {
+                failed_blocks += 1;
+                error!("Failed to backfill block {}: {:?}", block_num, e);
+                first_error.get_or_insert(e);
+            }
+        }
+
+        for sink in &self.sinks {
+            sink.flush().await
+                .map_err(|e| MonitorError::Sink(format!("Failed to flush {} sink: {}", sink.name(), e)))?;
+        }
+
+        // Fails with the first block error, so the failure kind is kept
+        let total_blocks = to_block - from_block + 1;
+        if let Some(first_error) = first_error {
+            error!(
+                "Backfill of blocks {} to {} failed to persist {} of {} blocks",
+                from_block, to_block, failed_blocks, total_blocks
+            );
+            return Err(first_error);
+        }
+
+        // Verify the stored canonical chain has no gaps over the range
+        let missing = self.db_clients
+            .missing_block_numbers(&self.chain_id.to_string(), from_block, Some(to_block), total_blocks)
+            .await
+            .map_err(|e| MonitorError::Database(format!("{:#}", e)))?;
+        if !missing.is_empty() {
+            return Err(MonitorError::Database(format!(
+                "Backfill of blocks {} to {} left {} blocks missing, first missing block {}",
+                from_block, to_block, missing.len(), missing[0]
+            )));
+        }
+
+        info!("Backfill completed: processed {} blocks from block {} to {}", total_blocks, from_block, to_block);
+        Ok(())
+    }
+
+    async fn handle_block(&self, block: alloy::rpc::types::Block) -> Result<()> {
+        if self.config.indexing.reorg_detection.unwrap_or(true) {
+            let full_blocks = matches!(block.transactions, alloy::rpc::types::BlockTransactions::Full(_));
+            if let Err(e) = self.handle_reorg(&block.header, full_blocks).await {
+                error!("Failed to handle chain reorganization at block {}: {:?}", block.header.number, e);
+            }
+        }
+
+        self.persist_block(block).await
+    }
+
+    /// Detect whether `header` doesn't extend the stored canonical chain, and if so
+    /// walk back to the fork point, flag the orphaned blocks as reorged, persist the
+    /// new canonical blocks between the fork point and `header`, and emit a reorg event
+    async fn handle_reorg(&self, header: &alloy::rpc::types::Header, full_blocks: bool) -> Result<()> {
+        let chain_id = self.chain_id.to_string();
+        let block_number = header.number;
+        let block_hash = format!("0x{}", hex::encode(header.hash.0.as_slice()));
+
+        // A different canonical block at the same height is replaced, even when parents match
+        let replaces_sibling = self.db_clients.canonical_block_hash(&chain_id, block_number).await
+            .map_err(|e| MonitorError::Database(format!("{:#}", e)))?
+            .is_some_and(|stored_hash| stored_hash != block_hash);
+
+        if block_number == 0 {
+            if replaces_sibling {
+                warn!("Genesis block hash mismatch for block {}", block_hash);
+            }
+            return Ok(());
+        }
+
+        // Walk back along the new chain until its ancestor matches the stored canonical block
+        let max_depth = self.config.indexing.max_reorg_depth.unwrap_or(64);
+        let mut new_chain_hashes = Vec::new();
+        let mut height = block_number - 1;
+        let mut expected_hash = header.parent_hash;
+
+        loop {
+            let stored_hash = self.db_clients.canonical_block_hash(&chain_id, height).await
+                .map_err(|e| MonitorError::Database(format!("{:#}", e)))?;
+            let expected_hash_str = format!("0x{}", hex::encode(expected_hash.0.as_slice()));
+            match stored_hash {
+                Some(stored_hash) if stored_hash != expected_hash_str => {}
+                // Matching or not yet indexed ancestor is the fork point
+                _ => break,
+            }
+
+            if new_chain_hashes.len() as u64 >= max_depth || height == 0 {
+                warn!(
+                    "Reorg at block {} is deeper than max reorg depth {}, orphaning blocks above {}",
+                    block_number, max_depth, height
+                );
+                break;
+            }
+
+            new_chain_hashes.push(expected_hash);
+            let ancestor = self.http_rpc_provider
+                .get_block(BlockId::Hash(RpcBlockHash::from_hash(expected_hash, Some(false))))
+                .await?
+                .ok_or_else(|| MonitorError::Reorg(format!("Failed to get new chain ancestor block {}", expected_hash_str)))?;
+            expected_hash = ancestor.header.parent_hash;
+            height -= 1;
+        }
+
+        if new_chain_hashes.is_empty() && !replaces_sibling {
+            return Ok(());
+        }
+
+        let fork_block_number = block_number - 1 - new_chain_hashes.len() as u64;
+        warn!(
+            "Chain reorganization detected at block {} ({}), fork point at block {}",
+            block_number, block_hash, fork_block_number
+        );
+
+        // Flag orphaned blocks before inserting the new canonical chain
+        let orphaned_blocks = self.db_clients.mark_blocks_reorged(&chain_id, fork_block_number + 1).await
+            .map_err(|e| MonitorError::Database(format!("{:#}", e)))?;
+
+        // Persist new canonical blocks between the fork point and the incoming block, oldest first
+        for hash in new_chain_hashes.into_iter().rev() {
+            let block_id = BlockId::Hash(RpcBlockHash::from_hash(hash, Some(false)));
+            let block = if full_blocks {
+                self.http_rpc_provider.get_block(block_id).full().await?
+            } else {
+                self.http_rpc_provider.get_block(block_id).await?
+            };
+            match block {
+                Some(block) => self.persist_block(block).await?,
+                None => error!("Failed to get new canonical block {}", hash),
+            }
+        }
+
+        let event = ReorgEvent {
+            chain_id,
+            fork_block_number: fork_block_number.to_string(),
+            depth: block_number - fork_block_number,
+            orphaned_blocks,
+            new_head_number: block_number.to_string(),
+            new_head_hash: block_hash,
+            detected_at: chrono::Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Millis, true),
+        };
+        info!("Reorg handled: {:?}", event);
+        metrics::reorg(event.depth);
+
+        // Best-effort, as the blocks publishing, so a NATS outage doesn't fail the reorg handling
+        if let Some(nats_store) = &self.nats_store
+            && let Err(e) = nats::publish_reorg(&nats_store.client, &event).await
+        {
+            warn!("[NATS] Failed to publish reorg event: {}", e);
         }
 
         Ok(())
     }
 
-    async fn handle_block(&self, block: alloy::rpc::types::Block) -> anyhow::Result<()> {
+    /// Fetch receipts of `tx_hashes` concurrently and add status, gas used, effective gas price
+    /// and created contract address to the matching transaction JSON objects in `txs`
+    async fn enrich_with_receipts(&self, txs: &mut [serde_json::Value], tx_hashes: Vec<B256>) {
+        let concurrency = self.config.indexing.receipts_fetch_concurrency.unwrap_or(16).max(1);
+
+        let receipts: Vec<_> = futures::stream::iter(tx_hashes)
+            .map(|tx_hash| async move {
+                (tx_hash, metrics::rpc_call("eth_getTransactionReceipt", self.http_rpc_provider.get_transaction_receipt(tx_hash)).await)
+            })
+            .buffered(concurrency)
+            .collect()
+            .await;
+
+        for (tx, (tx_hash, receipt)) in txs.iter_mut().zip(receipts) {
+            let receipt = match receipt {
+                Ok(Some(receipt)) => receipt,
+                Ok(None) => {
+                    warn!("Receipt not found for transaction 0x{}", hex::encode(tx_hash.0.as_slice()));
+                    continue;
+                }
+                Err(e) => {
+                    error!("Failed to get receipt for transaction 0x{}: {:?}", hex::encode(tx_hash.0.as_slice()), e);
+                    continue;
+                }
+            };
+
+            if let Some(tx) = tx.as_object_mut() {
+                tx.insert("status".to_string(), serde_json::json!(ReceiptResponse::status(&receipt)));
+                tx.insert("gas_used".to_string(), serde_json::json!(ReceiptResponse::gas_used(&receipt).to_string()));
+                tx.insert("effective_gas_price".to_string(), serde_json::json!(ReceiptResponse::effective_gas_price(&receipt).to_string()));
+                tx.insert("contract_address".to_string(), serde_json::json!(ReceiptResponse::contract_address(&receipt).map(|addr| addr.to_string())));
+            }
+        }
+    }
+
+    async fn persist_block(&self, block: alloy::rpc::types::Block) -> Result<()> {
         let block_number = block.header.number;
         debug!("Received block number: {}", block_number);
 
@@ -464,6 +928,7 @@ impl BlockProcessor {
         let transactions = match block.transactions {
             alloy::rpc::types::BlockTransactions::Full(txs) => {
                 let mut filtered_txs = Vec::new();
+                let mut filtered_tx_hashes = Vec::new();
 
                 for tx in txs {
                     let tx_sender = tx.from();
@@ -501,14 +966,29 @@ impl BlockProcessor {
                     let tx_gas_price = TransactionTrait::gas_price(&tx).map(|p| p.to_string()).unwrap_or_default();
                     let tx_gas = tx.gas_limit().to_string();
 
-                    filtered_txs.push(serde_json::json!({
+                    let mut tx_json = serde_json::json!({
                         "hash": tx_hash,
                         "from": tx_sender_str,
                         "to": tx_receiver_str,
                         "value": tx_value,
                         "gas_price": tx_gas_price,
                         "gas": tx_gas,
-                    }));
+                    });
+                    // Labels of the sender and receiver, only if labelled
+                    if let Some(tx_json) = tx_json.as_object_mut() {
+                        if let Some(label) = self.address_labels.get(&tx_sender) {
+                            tx_json.insert("from_label".to_string(), serde_json::json!(label));
+                        }
+                        if let Some(label) = tx_receiver.and_then(|receiver| self.address_labels.get(&receiver)) {
+                            tx_json.insert("to_label".to_string(), serde_json::json!(label));
+                        }
+                    }
+                    filtered_txs.push(tx_json);
+                    filtered_tx_hashes.push(tx.tx_hash());
+                }
+
+                if self.config.indexing.fetch_receipts.unwrap_or(false) {
+                    self.enrich_with_receipts(&mut filtered_txs, filtered_tx_hashes).await;
                 }
 
                 Some(filtered_txs)
@@ -535,23 +1015,94 @@ impl BlockProcessor {
 
         debug!("Persisting block: {:?}", payload);
 
-        // Persist to databases (local PostgreSQL + AWS RDS if enabled)
-        self.db_clients.insert_block(&payload).await?;
+        // Persist to output sinks (local PostgreSQL + AWS RDS if enabled, NATS Object Store if enabled, configured sinks)
+        for sink in &self.sinks {
+            let started = std::time::Instant::now();
+            sink.write(&payload).await
+                .map_err(|e| MonitorError::Sink(format!("Failed to write block to {} sink: {}", sink.name(), e)))?;
+            metrics::sink_write(sink.name(), started.elapsed());
+        }
 
-        // Persist to NATS Object Store
-        if let Some(nats_store) = &self.nats_store {
-            nats::publish_block(&nats_store.object_store, &payload).await?;
-        };
+        metrics::block_processed(payload.transactions.as_ref().map_or(0, Vec::len));
 
         Ok(())
     }
 }
 
-/// Build HTTP and WS providers using Alloy
-pub async fn build_providers(ws_rpc_url: WsConnect, http_rpc_url: reqwest::Url) -> anyhow::Result<(RPCProvider, RPCProvider)> {
-    let ws_rpc_provider = ProviderBuilder::new().connect_ws(ws_rpc_url.clone()).await?;
-    let http_rpc_provider = ProviderBuilder::new().connect_http(http_rpc_url.clone());
-    info!("Connecting to RPC endpoints: ws: {:?}, http: {:?}", ws_rpc_url, http_rpc_url);
+/// Split ascending block numbers into half-open ranges of consecutive blocks
+fn contiguous_ranges(block_numbers: &[u64]) -> Vec<Range<u64>> {
+    let mut ranges: Vec<Range<u64>> = Vec::new();
+    for &block_number in block_numbers {
+        match ranges.last_mut() {
+            Some(range) if range.end == block_number => range.end += 1,
+            _ => ranges.push(block_number..block_number + 1),
+        }
+    }
+    ranges
+}
+
+fn ws_config() -> WebSocketConfig {
+    WebSocketConfig::default()
+        .read_buffer_size(256 * 1024)
+        .write_buffer_size(256 * 1024)
+        .max_message_size(Some(1024 * 1024 * 1024))
+        .max_frame_size(Some(256 * 1024 * 1024))
+        .accept_unmasked_frames(false)
+}
+
+/// Connect to the first reachable WS RPC endpoint, trying them in order (wrapping around) from `start_index`,
+/// returns the provider and the index of the connected endpoint
+pub async fn connect_ws_provider(ws_rpc_urls: &[String], start_index: usize) -> Result<(RPCProvider, usize)> {
+    for offset in 0..ws_rpc_urls.len() {
+        let index = (start_index + offset) % ws_rpc_urls.len();
+        let ws = WsConnect::new(&ws_rpc_urls[index]).with_config(ws_config());
+        match ProviderBuilder::new().connect_ws(ws).await {
+            Ok(provider) => {
+                info!("Connected to WS RPC endpoint: {}", ws_rpc_urls[index]);
+                return Ok((provider, index));
+            }
+            Err(e) => warn!("Failed to connect to WS RPC endpoint {}: {:?}", ws_rpc_urls[index], e),
+        }
+    }
+
+    Err(MonitorError::Rpc(format!("Failed to connect to any of {} WS RPC endpoints", ws_rpc_urls.len())))
+}
+
+/// Connect to the first reachable HTTP RPC endpoint, trying them in order,
+/// with the requests limited by the rate limiter, if any
+pub async fn connect_http_provider(http_rpc_urls: &[String], rpc_rate_limiter: Option<&Arc<RpcRateLimiter>>) -> Result<RPCProvider> {
+    for url in http_rpc_urls {
+        let http_rpc = reqwest::Url::from_str(url)
+            .map_err(|e| MonitorError::Config(format!("Invalid HTTP RPC endpoint {}: {}", url, e)))?;
+        let client = match rpc_rate_limiter {
+            Some(rpc_rate_limiter) => ClientBuilder::default()
+                .layer(RpcRateLimitLayer::new(Arc::clone(rpc_rate_limiter)))
+                .http(http_rpc),
+            None => ClientBuilder::default().http(http_rpc),
+        };
+        let provider = ProviderBuilder::new().connect_client(client);
+        match provider.get_block_number().await {
+            Ok(_) => {
+                info!("Connected to HTTP RPC endpoint: {}", url);
+                return Ok(provider);
+            }
+            Err(e) => warn!("Failed to connect to HTTP RPC endpoint {}: {:?}", url, e),
+        }
+    }
+
+    Err(MonitorError::Rpc(format!("Failed to connect to any of {} HTTP RPC endpoints", http_rpc_urls.len())))
+}
 
-    Ok((ws_rpc_provider, http_rpc_provider))
+/// Build WS and HTTP providers using Alloy, trying the endpoints in order,
+/// returns the providers and the index of the connected WS endpoint
+pub async fn build_providers(
+    ws_rpc_urls: &[String],
+    http_rpc_urls: &[String],
+    rpc_rate_limiter: Option<&Arc<RpcRateLimiter>>,
+) -> Result<(RPCProvider, RPCProvider, usize)> {
+    info!("Connecting to RPC endpoints: ws: {:?}, http: {:?}", ws_rpc_urls, http_rpc_urls);
+    let (ws_rpc_provider, ws_rpc_endpoint_index) = connect_ws_provider(ws_rpc_urls, 0).await?;
+    let http_rpc_provider = connect_http_provider(http_rpc_urls, rpc_rate_limiter).await?;
+
+    Ok((ws_rpc_provider, http_rpc_provider, ws_rpc_endpoint_index))
 }
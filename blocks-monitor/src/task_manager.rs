@@ -5,10 +5,13 @@ use tokio::task::JoinHandle;
 use uuid::Uuid;
 use serde::{Serialize, Deserialize};
 use tracing::{info, error, warn};
+use monitor_common::error::{MonitorError, Result};
 
 use crate::subscriptions::BlockProcessor;
 use crate::config::AppCfg;
+use crate::types::GapFillerStats;
 use crate::{db, nats};
+use crate::nats::{NatsHealth, NatsPublisher};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TaskInfo {
@@ -17,6 +20,10 @@ pub struct TaskInfo {
     pub status: TaskStatus,
     pub created_at: chrono::DateTime<chrono::Utc>,
     pub updated_at: chrono::DateTime<chrono::Utc>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub gap_filler: Option<GapFillerStats>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub nats: Option<NatsHealth>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -30,8 +37,21 @@ pub enum TaskStatus {
 
 pub struct Task {
     pub info: TaskInfo,
-    pub handle: JoinHandle<anyhow::Result<()>>,
+    pub handle: JoinHandle<Result<()>>,
     pub shutdown_sender: Option<oneshot::Sender<()>>,
+    pub gap_filler_stats: Option<Arc<RwLock<GapFillerStats>>>,
+    pub nats_publisher: Option<NatsPublisher>,
+}
+
+impl Task {
+    async fn info_with_stats(&self) -> TaskInfo {
+        let mut info = self.info.clone();
+        if let Some(stats) = &self.gap_filler_stats {
+            info.gap_filler = Some(stats.read().await.clone());
+        }
+        info.nats = self.nats_publisher.as_ref().map(NatsPublisher::health);
+        info
+    }
 }
 
 pub struct TaskManager {
@@ -61,6 +81,8 @@ impl TaskManager {
             status: TaskStatus::Starting,
             created_at: chrono::Utc::now(),
             updated_at: chrono::Utc::now(),
+            gap_filler: None,
+            nats: None,
         };
 
         // Create shutdown channel
@@ -91,6 +113,7 @@ impl TaskManager {
             let db_clients = match db::DatabaseClients::new(
                 &config.postgres.dsn,
                 &db_schema,
+                config.postgres.migrations_dir.as_deref(),
                 aws_rds_config
             ).await {
                 Ok(clients) => {
@@ -106,7 +129,7 @@ impl TaskManager {
                         task.info.status = TaskStatus::Failed(format!("Database connection failed: {}", e));
                         task.info.updated_at = chrono::Utc::now();
                     }
-                    return Err(e);
+                    return Err(MonitorError::Database(format!("{:#}", e)));
                 }
             };
 
@@ -151,6 +174,8 @@ impl TaskManager {
                 if let Some(task) = tasks.get_mut(&task_id_clone) {
                     task.info.status = TaskStatus::Running;
                     task.info.updated_at = chrono::Utc::now();
+                    task.gap_filler_stats = block_processor.gap_filler_stats();
+                    task.nats_publisher = block_processor.nats_publisher();
                 }
             }
 
@@ -188,6 +213,8 @@ impl TaskManager {
             info: task_info,
             handle,
             shutdown_sender: Some(shutdown_sender),
+            gap_filler_stats: None,
+            nats_publisher: None,
         };
 
         let mut tasks = self.tasks.write().await;
@@ -222,12 +249,19 @@ impl TaskManager {
 
     pub async fn get_task(&self, task_id: &str) -> Option<TaskInfo> {
         let tasks = self.tasks.read().await;
-        tasks.get(task_id).map(|task| task.info.clone())
+        match tasks.get(task_id) {
+            Some(task) => Some(task.info_with_stats().await),
+            None => None,
+        }
     }
 
     pub async fn list_tasks(&self) -> Vec<TaskInfo> {
         let tasks = self.tasks.read().await;
-        tasks.values().map(|task| task.info.clone()).collect()
+        let mut infos = Vec::with_capacity(tasks.len());
+        for task in tasks.values() {
+            infos.push(task.info_with_stats().await);
+        }
+        infos
     }
 
     pub async fn cleanup_finished_tasks(&self) {
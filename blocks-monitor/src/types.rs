@@ -12,3 +12,29 @@ pub struct BlockPayload {
     pub gas_limit: String,
     pub transactions: Option<Vec<serde_json::Value>>,
 }
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReorgedBlock {
+    pub block_number: String,
+    pub block_hash: String,
+}
+
+/// Chain reorganization notification, published to NATS
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReorgEvent {
+    pub chain_id: String,
+    pub fork_block_number: String, // Last block shared by the orphaned and the new canonical chain
+    pub depth: u64,
+    pub orphaned_blocks: Vec<ReorgedBlock>,
+    pub new_head_number: String,
+    pub new_head_hash: String,
+    pub detected_at: String,
+}
+
+/// Missing blocks backfill counters, reported in the task info
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct GapFillerStats {
+    pub detected_gaps: u64, // Missing blocks found by the latest check
+    pub backfilled_blocks: u64, // Total blocks re-fetched since the task start
+    pub last_check_at: Option<String>,
+}
@@ -0,0 +1,292 @@
+//! Layered service configuration loader
+//!
+//! The configuration file (YAML, or TOML by `.toml` extension) is the base layer,
+//! overlaid with environment variables named after the config key path,
+//! with path segments separated by double underscore: `SECTION__KEY`.
+//!
+//! - `POSTGRES__DSN` overrides `postgres.dsn`
+//! - `CHAIN__HTTP_RPC_URL` overrides `chain.http_rpc_url`
+//! - `CONTRACTS__0__ADDRESS` overrides `address` of the first item of `contracts`
+//!
+//! Names are matched case-insensitively against lowercase config keys. Only variables
+//! whose first segment names a top-level section present in the file are applied,
+//! so unrelated environment variables containing `__` are never picked up.
+//! The overlaid configuration is then deserialized, which validates it.
+
+use anyhow::{Context, Result};
+use serde::de::DeserializeOwned;
+use serde_json::{Map, Value};
+use std::path::Path;
+
+/// Separator of key path segments in environment variable names
+pub const ENV_SEPARATOR: &str = "__";
+
+/// Configuration file format
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Yaml,
+    Toml,
+}
+
+impl Format {
+    /// Detect format by file extension, YAML by default
+    pub fn from_path(path: impl AsRef<Path>) -> Self {
+        match path.as_ref().extension().and_then(|ext| ext.to_str()) {
+            Some(ext) if ext.eq_ignore_ascii_case("toml") => Format::Toml,
+            _ => Format::Yaml,
+        }
+    }
+}
+
+/// Load configuration from the file, overlaid with the process environment variables
+pub fn load<T: DeserializeOwned>(path: &str) -> Result<T> {
+    let content = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read config file {}", path))?;
+
+    from_str_with_env(&content, Format::from_path(path), std::env::vars())
+        .with_context(|| format!("Failed to load config {}", path))
+}
+
+/// Parse configuration content, overlaid with the given environment variables
+pub fn from_str_with_env<T, I>(content: &str, format: Format, vars: I) -> Result<T>
+where
+    T: DeserializeOwned,
+    I: IntoIterator<Item = (String, String)>,
+{
+    let mut config = parse(content, format)?;
+
+    // Never log the values, overrides commonly carry secrets
+    for name in apply_env_overrides(&mut config, vars) {
+        tracing::info!("Config value overridden by environment variable {}", name);
+    }
+
+    serde_json::from_value(config).context("Invalid config")
+}
+
+/// Parse configuration content into a generic document
+pub fn parse(content: &str, format: Format) -> Result<Value> {
+    let config = match format {
+        Format::Yaml => serde_yaml::from_str(content).context("Failed to parse YAML config")?,
+        Format::Toml => toml::from_str(content).context("Failed to parse TOML config")?,
+    };
+    Ok(config)
+}
+
+/// Overlay `SECTION__KEY` environment variables onto the config document,
+/// returns the names of the applied variables
+pub fn apply_env_overrides<I>(config: &mut Value, vars: I) -> Vec<String>
+where
+    I: IntoIterator<Item = (String, String)>,
+{
+    let mut vars: Vec<(String, String)> = vars
+        .into_iter()
+        .filter(|(name, _)| name.contains(ENV_SEPARATOR))
+        .collect();
+    // Deterministic order, so a parent section override is applied before its keys
+    vars.sort();
+
+    let mut applied = Vec::new();
+
+    for (name, raw_value) in vars {
+        let path: Vec<String> = name
+            .split(ENV_SEPARATOR)
+            .map(|segment| segment.to_lowercase())
+            .collect();
+
+        if path.iter().any(|segment| segment.is_empty()) {
+            continue;
+        }
+
+        let is_known_section = config
+            .as_object()
+            .is_some_and(|sections| sections.contains_key(&path[0]));
+        if !is_known_section {
+            continue;
+        }
+
+        if set_path(config, &path, &raw_value) {
+            applied.push(name);
+        } else {
+            tracing::warn!("Environment variable {} doesn't match the config structure, ignored", name);
+        }
+    }
+
+    applied
+}
+
+fn set_path(config: &mut Value, path: &[String], raw_value: &str) -> bool {
+    let Some((key, parents)) = path.split_last() else {
+        return false;
+    };
+
+    let mut current = config;
+    for segment in parents {
+        if current.is_null() {
+            *current = Value::Object(Map::new());
+        }
+
+        current = match current {
+            Value::Object(map) => map.entry(segment.clone()).or_insert(Value::Null),
+            Value::Array(items) => match segment.parse::<usize>().ok().and_then(|i| items.get_mut(i)) {
+                Some(item) => item,
+                None => return false,
+            },
+            _ => return false,
+        };
+    }
+
+    if current.is_null() {
+        *current = Value::Object(Map::new());
+    }
+
+    match current {
+        Value::Object(map) => {
+            let value = coerce_value(raw_value, map.get(key));
+            map.insert(key.clone(), value);
+            true
+        }
+        Value::Array(items) => match key.parse::<usize>().ok().and_then(|i| items.get_mut(i)) {
+            Some(item) => {
+                *item = coerce_value(raw_value, Some(item));
+                true
+            }
+            None => false,
+        },
+        _ => false,
+    }
+}
+
+/// Convert the raw variable value to the type of the overridden value. New values are typed
+/// as YAML scalars or flow collections (e.g. `[1, 2]`), except hex strings which stay strings.
+fn coerce_value(raw_value: &str, existing: Option<&Value>) -> Value {
+    if let Some(Value::String(_)) = existing {
+        return Value::String(raw_value.to_string());
+    }
+
+    if raw_value.starts_with("0x") || raw_value.starts_with("0X") {
+        return Value::String(raw_value.to_string());
+    }
+
+    serde_yaml::from_str::<Value>(raw_value)
+        .unwrap_or_else(|_| Value::String(raw_value.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde::Deserialize;
+
+    #[derive(Debug, Deserialize)]
+    struct TestCfg {
+        chain: ChainCfg,
+        postgres: PgCfg,
+        contracts: Vec<ContractCfg>,
+    }
+
+    #[derive(Debug, Deserialize)]
+    struct ChainCfg {
+        http_rpc_url: String,
+        chain_id: u64,
+        ws_rpc_url: Option<String>,
+    }
+
+    #[derive(Debug, Deserialize)]
+    struct PgCfg {
+        dsn: String,
+    }
+
+    #[derive(Debug, Deserialize)]
+    struct ContractCfg {
+        name: String,
+        address: String,
+    }
+
+    const YAML: &str = r#"
+chain:
+  http_rpc_url: "http://localhost:8545"
+  chain_id: 1
+postgres:
+  dsn: "postgres://localhost/db"
+contracts:
+  - name: "Token"
+    address: "0x0000000000000000000000000000000000000001"
+"#;
+
+    fn vars(vars: &[(&str, &str)]) -> Vec<(String, String)> {
+        vars.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect()
+    }
+
+    #[test]
+    fn test_env_overrides() {
+        let cfg: TestCfg = from_str_with_env(YAML, Format::Yaml, vars(&[
+            ("CHAIN__CHAIN_ID", "137"),
+            ("CHAIN__WS_RPC_URL", "ws://localhost:8546"),
+            ("POSTGRES__DSN", "postgres://db.internal/db"),
+            ("CONTRACTS__0__ADDRESS", "0x0000000000000000000000000000000000000002"),
+        ])).unwrap();
+
+        assert_eq!(cfg.chain.chain_id, 137);
+        assert_eq!(cfg.chain.http_rpc_url, "http://localhost:8545");
+        assert_eq!(cfg.chain.ws_rpc_url.as_deref(), Some("ws://localhost:8546"));
+        assert_eq!(cfg.postgres.dsn, "postgres://db.internal/db");
+        assert_eq!(cfg.contracts[0].name, "Token");
+        assert_eq!(cfg.contracts[0].address, "0x0000000000000000000000000000000000000002");
+    }
+
+    #[test]
+    fn test_unrelated_variables_ignored() {
+        let mut config = parse(YAML, Format::Yaml).unwrap();
+        let applied = apply_env_overrides(&mut config, vars(&[
+            ("UNKNOWN__KEY", "value"),
+            ("PATH", "/usr/bin"),
+            ("CHAIN____CHAIN_ID", "5"),
+            ("CONTRACTS__7__ADDRESS", "0x00"),
+        ]));
+
+        assert!(applied.is_empty());
+        assert_eq!(config, parse(YAML, Format::Yaml).unwrap());
+    }
+
+    #[test]
+    fn test_string_values_stay_strings() {
+        let cfg: TestCfg = from_str_with_env(YAML, Format::Yaml, vars(&[
+            ("POSTGRES__DSN", "12345"),
+        ])).unwrap();
+
+        assert_eq!(cfg.postgres.dsn, "12345");
+    }
+
+    #[test]
+    fn test_invalid_override_fails_validation() {
+        let result: Result<TestCfg> = from_str_with_env(YAML, Format::Yaml, vars(&[
+            ("CHAIN__CHAIN_ID", "not-a-number"),
+        ]));
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_toml_format() {
+        let toml = r#"
+[chain]
+http_rpc_url = "http://localhost:8545"
+chain_id = 1
+
+[postgres]
+dsn = "postgres://localhost/db"
+
+[[contracts]]
+name = "Token"
+address = "0x0000000000000000000000000000000000000001"
+"#;
+
+        let cfg: TestCfg = from_str_with_env(toml, Format::Toml, vars(&[
+            ("CHAIN__CHAIN_ID", "10"),
+        ])).unwrap();
+
+        assert_eq!(cfg.chain.chain_id, 10);
+        assert_eq!(cfg.contracts.len(), 1);
+        assert_eq!(Format::from_path("config.toml"), Format::Toml);
+        assert_eq!(Format::from_path("config.yaml"), Format::Yaml);
+    }
+}
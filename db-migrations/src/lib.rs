@@ -0,0 +1,200 @@
+//! Versioned SQL schema migrations
+//!
+//! Migrations are SQL files of a directory, named `<version>_<name>.sql` with a positive
+//! numeric version (e.g. `0001_add_reorged_flag.sql`), applied in the version order.
+//! Applied versions are recorded in the `schema_version` table, so each migration
+//! is applied once and upgrades apply only the new migrations.
+//!
+//! A database with an applied version newer than the latest known migration was upgraded
+//! by a newer release, so the migration run fails rather than running against a schema
+//! this release doesn't know.
+
+use anyhow::{bail, Context, Result};
+use std::collections::BTreeSet;
+use std::path::Path;
+use tokio_postgres::Client;
+
+/// Table of the applied migrations versions
+pub const SCHEMA_VERSION_TABLE: &str = "schema_version";
+
+/// Advisory lock key serializing migration runs of processes sharing the database
+const MIGRATIONS_LOCK_KEY: i64 = 0x5343_4845_4d41;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Migration {
+    pub version: i64,
+    pub name: String,
+    pub sql: String,
+}
+
+impl Migration {
+    /// Parse a `<version>_<name>.sql` file name into the version and name
+    pub fn parse_file_name(file_name: &str) -> Option<(i64, String)> {
+        let stem = file_name.strip_suffix(".sql")?;
+        let (version, name) = stem.split_once('_')?;
+        let version = version.parse::<i64>().ok().filter(|version| *version > 0)?;
+        (!name.is_empty()).then(|| (version, name.to_string()))
+    }
+}
+
+/// Load the migrations of the directory, ordered by version.
+/// Fails on `.sql` files not following the naming convention and on duplicate versions.
+pub fn load_dir(dir: impl AsRef<Path>) -> Result<Vec<Migration>> {
+    let dir = dir.as_ref();
+    let entries = std::fs::read_dir(dir)
+        .with_context(|| format!("Failed to read migrations directory {}", dir.display()))?;
+
+    let mut migrations = Vec::new();
+    for entry in entries {
+        let path = entry?.path();
+        let Some(file_name) = path.file_name().and_then(|name| name.to_str()) else {
+            continue;
+        };
+        if !path.is_file() || !file_name.ends_with(".sql") {
+            continue;
+        }
+
+        let (version, name) = Migration::parse_file_name(file_name)
+            .with_context(|| format!("Invalid migration file name {}, expected <version>_<name>.sql", path.display()))?;
+        let sql = std::fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read migration {}", path.display()))?;
+
+        migrations.push(Migration { version, name, sql });
+    }
+
+    migrations.sort_by_key(|migration| migration.version);
+    if let Some(duplicate) = migrations.windows(2).find(|pair| pair[0].version == pair[1].version) {
+        bail!(
+            "Duplicate migration version {}: {} and {}",
+            duplicate[0].version, duplicate[0].name, duplicate[1].name
+        );
+    }
+
+    Ok(migrations)
+}
+
+/// Migrations not applied yet, in the version order.
+/// Fails if an applied version is newer than the latest known migration.
+pub fn pending<'a>(applied: &BTreeSet<i64>, migrations: &'a [Migration]) -> Result<Vec<&'a Migration>> {
+    let latest_known = migrations.iter().map(|migration| migration.version).max().unwrap_or(0);
+    if let Some(latest_applied) = applied.last().filter(|version| **version > latest_known) {
+        bail!(
+            "Database schema version {} is newer than the latest known migration version {}, \
+             it was migrated by a newer release",
+            latest_applied, latest_known
+        );
+    }
+
+    Ok(migrations.iter().filter(|migration| !applied.contains(&migration.version)).collect())
+}
+
+/// Apply the pending migrations in one transaction, so a failed migration applies none of them,
+/// and return the resulting schema version (0 without any migration applied)
+pub async fn run(client: &mut Client, migrations: &[Migration]) -> Result<i64> {
+    client
+        .batch_execute(&format!(
+            "CREATE TABLE IF NOT EXISTS {SCHEMA_VERSION_TABLE} (
+                version BIGINT PRIMARY KEY,
+                name TEXT NOT NULL,
+                applied_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT CURRENT_TIMESTAMP
+            )"
+        ))
+        .await
+        .context("Failed to create schema version table")?;
+
+    let tx = client.transaction().await?;
+    tx.execute("SELECT pg_advisory_xact_lock($1)", &[&MIGRATIONS_LOCK_KEY]).await?;
+
+    let applied: BTreeSet<i64> = tx
+        .query(&format!("SELECT version FROM {SCHEMA_VERSION_TABLE}"), &[])
+        .await?
+        .iter()
+        .map(|row| row.get(0))
+        .collect();
+
+    let pending = pending(&applied, migrations)?;
+    for migration in &pending {
+        tracing::info!("Applying schema migration {} ({})", migration.version, migration.name);
+        tx.batch_execute(&migration.sql)
+            .await
+            .with_context(|| format!("Schema migration {} ({}) failed", migration.version, migration.name))?;
+        tx.execute(
+            &format!("INSERT INTO {SCHEMA_VERSION_TABLE} (version, name) VALUES ($1, $2)"),
+            &[&migration.version, &migration.name],
+        )
+        .await?;
+    }
+
+    tx.commit().await?;
+
+    let version = applied.iter().chain(pending.iter().map(|migration| &migration.version)).max().copied().unwrap_or(0);
+    if !pending.is_empty() {
+        tracing::info!("Applied {} schema migrations, schema version {}", pending.len(), version);
+    }
+
+    Ok(version)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn migration(version: i64, name: &str) -> Migration {
+        Migration { version, name: name.to_string(), sql: String::new() }
+    }
+
+    #[test]
+    fn test_parse_file_name() {
+        assert_eq!(Migration::parse_file_name("0001_add_reorged_flag.sql"), Some((1, "add_reorged_flag".to_string())));
+        assert_eq!(Migration::parse_file_name("20_index.sql"), Some((20, "index".to_string())));
+        assert_eq!(Migration::parse_file_name("0000_zero.sql"), None);
+        assert_eq!(Migration::parse_file_name("0001.sql"), None);
+        assert_eq!(Migration::parse_file_name("0001_.sql"), None);
+        assert_eq!(Migration::parse_file_name("v1_init.sql"), None);
+        assert_eq!(Migration::parse_file_name("0001_init.txt"), None);
+    }
+
+    #[test]
+    fn test_pending_migrations() {
+        let migrations = vec![migration(1, "a"), migration(2, "b"), migration(3, "c")];
+
+        let pending_versions = |applied: &[i64]| -> Result<Vec<i64>> {
+            let applied = applied.iter().copied().collect();
+            Ok(pending(&applied, &migrations)?.iter().map(|migration| migration.version).collect())
+        };
+
+        assert_eq!(pending_versions(&[]).unwrap(), vec![1, 2, 3]);
+        assert_eq!(pending_versions(&[1]).unwrap(), vec![2, 3]);
+        assert_eq!(pending_versions(&[1, 2, 3]).unwrap(), Vec::<i64>::new());
+        // Version added below the latest applied one, e.g. from a merged branch
+        assert_eq!(pending_versions(&[1, 3]).unwrap(), vec![2]);
+    }
+
+    #[test]
+    fn test_future_version_refused() {
+        let migrations = vec![migration(1, "a")];
+        let applied = BTreeSet::from([1, 2]);
+
+        let error = pending(&applied, &migrations).unwrap_err();
+        assert!(error.to_string().contains("newer than the latest known migration version 1"));
+        assert!(pending(&applied, &[]).is_err());
+    }
+
+    #[test]
+    fn test_load_dir() {
+        let dir = std::env::temp_dir().join(format!("db-migrations-test-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("0002_second.sql"), "SELECT 2;").unwrap();
+        std::fs::write(dir.join("0001_first.sql"), "SELECT 1;").unwrap();
+        std::fs::write(dir.join("README.md"), "not a migration").unwrap();
+
+        let migrations = load_dir(&dir).unwrap();
+        assert_eq!(migrations.iter().map(|migration| migration.version).collect::<Vec<_>>(), vec![1, 2]);
+        assert_eq!(migrations[0].sql, "SELECT 1;");
+
+        std::fs::write(dir.join("02_duplicate.sql"), "SELECT 2;").unwrap();
+        assert!(load_dir(&dir).unwrap_err().to_string().contains("Duplicate migration version 2"));
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -19,13 +19,26 @@ CREATE TABLE IF NOT EXISTS events_monitor_data (
     log_index TEXT NOT NULL,
     log_hash TEXT NOT NULL,
     event_name TEXT NOT NULL,
-    event_signature TEXT NOT NULL,
+    event_signature TEXT, -- NULL for anonymous events
     event_data JSONB NOT NULL,
+    contract_label TEXT, -- Address labels, NULL if not matched
+    from_label TEXT,
+    to_label TEXT,
     created_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP,
     updated_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP,
     UNIQUE (chain_id, log_hash, event_name, event_signature) -- Add UNIQUE constraint for deduplication
 );
 
+-- Anonymous events have no signature, allow NULL for tables created before anonymous events support
+ALTER TABLE events_monitor_data ALTER COLUMN event_signature DROP NOT NULL;
+-- NULL signatures are distinct in the UNIQUE constraint, deduplicate anonymous events separately
+CREATE UNIQUE INDEX IF NOT EXISTS idx_events_anonymous_unique ON events_monitor_data(chain_id, log_hash, event_name) WHERE event_signature IS NULL;
+
+-- Address labels columns, for tables created before address labels support
+ALTER TABLE events_monitor_data ADD COLUMN IF NOT EXISTS contract_label TEXT;
+ALTER TABLE events_monitor_data ADD COLUMN IF NOT EXISTS from_label TEXT;
+ALTER TABLE events_monitor_data ADD COLUMN IF NOT EXISTS to_label TEXT;
+
 -- Create indexes for efficient querying
 CREATE INDEX IF NOT EXISTS idx_events_contract_name ON events_monitor_data(contract_name);
 CREATE INDEX IF NOT EXISTS idx_events_contract_address ON events_monitor_data(contract_address);
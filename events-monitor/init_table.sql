@@ -16,12 +16,25 @@ CREATE TABLE IF NOT EXISTS events_monitor_data (
     log_index TEXT NOT NULL,
     log_hash TEXT NOT NULL,
     event_name TEXT NOT NULL,
-    event_signature TEXT NOT NULL,
+    event_signature TEXT, -- NULL for anonymous events
     event_data JSONB NOT NULL,
+    contract_label TEXT, -- Address labels, NULL if not matched
+    from_label TEXT,
+    to_label TEXT,
     created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
     UNIQUE (chain_id, log_hash, event_name, event_signature)
 );
 
+-- Anonymous events have no signature, allow NULL for tables created before anonymous events support
+ALTER TABLE events_monitor_data ALTER COLUMN event_signature DROP NOT NULL;
+-- NULL signatures are distinct in the UNIQUE constraint, deduplicate anonymous events separately
+CREATE UNIQUE INDEX IF NOT EXISTS idx_events_anonymous_unique ON events_monitor_data(chain_id, log_hash, event_name) WHERE event_signature IS NULL;
+
+-- Address labels columns, for tables created before address labels support
+ALTER TABLE events_monitor_data ADD COLUMN IF NOT EXISTS contract_label TEXT;
+ALTER TABLE events_monitor_data ADD COLUMN IF NOT EXISTS from_label TEXT;
+ALTER TABLE events_monitor_data ADD COLUMN IF NOT EXISTS to_label TEXT;
+
 CREATE INDEX IF NOT EXISTS idx_events_contract_name ON events_monitor_data(contract_name);
 CREATE INDEX IF NOT EXISTS idx_events_contract_address ON events_monitor_data(contract_address);
 CREATE INDEX IF NOT EXISTS idx_events_impl_name ON events_monitor_data(implementation_name);
@@ -49,3 +62,37 @@ CREATE INDEX IF NOT EXISTS idx_events_event_data_jsonb ON events_monitor_data US
 CREATE INDEX IF NOT EXISTS idx_events_event_name_signature ON events_monitor_data(event_name, event_signature);
 
 CREATE INDEX IF NOT EXISTS idx_events_contract_chain_block_tx_log_event ON events_monitor_data(contract_name, contract_address, implementation_name, implementation_address, chain_id, block_number, block_hash, block_timestamp, transaction_hash, transaction_sender, transaction_receiver, transaction_index, log_index, log_hash, event_name, event_signature);
+
+-- Dead-letter table for logs that failed to decode, to reprocess them after fixing the ABI
+CREATE TABLE IF NOT EXISTS failed_events (
+    id BIGSERIAL PRIMARY KEY,
+    contract_name TEXT,
+    contract_address TEXT NOT NULL,
+    chain_id TEXT NOT NULL,
+    block_number TEXT NOT NULL,
+    block_hash TEXT NOT NULL,
+    transaction_hash TEXT NOT NULL,
+    log_index TEXT NOT NULL,
+    log_hash TEXT NOT NULL,
+    topics JSONB NOT NULL, -- Array of hex-encoded topics
+    data TEXT NOT NULL, -- Hex-encoded log data
+    error TEXT NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    UNIQUE (chain_id, log_hash)
+);
+
+CREATE INDEX IF NOT EXISTS idx_failed_events_contract_address ON failed_events(contract_address);
+CREATE INDEX IF NOT EXISTS idx_failed_events_block_number ON failed_events(block_number);
+CREATE INDEX IF NOT EXISTS idx_failed_events_transaction_hash ON failed_events(transaction_hash);
+
+-- Processed logs watermark per logs stream: position of the last log, up to which all logs are durably committed.
+-- Monitors resume from it after restart, re-fetched logs are deduplicated by the events UNIQUE constraint.
+CREATE TABLE IF NOT EXISTS processed_logs (
+    chain_id TEXT NOT NULL,
+    stream_name TEXT NOT NULL,
+    block_number BIGINT NOT NULL,
+    log_index BIGINT NOT NULL,
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (chain_id, stream_name)
+);
@@ -1,17 +1,19 @@
+use std::collections::{BTreeMap, BTreeSet};
 use std::fs;
 use std::path::PathBuf;
 use std::str::FromStr;
 use alloy_json_abi::JsonAbi;
-use alloy_primitives::Address;
+use alloy_primitives::{Address, B256};
 use tracing::error;
 use crate::event_decoder::EventDecoder;
-use crate::config::ContractWithImplementation;
+use crate::config::{AppCfg, ContractWithImplementation};
 
 #[derive(Clone)]
 pub struct ContractAbi {
     pub name: String,
     pub address: Address,
     pub abi: JsonAbi,
+    pub anonymous_events: Vec<String>, // Declared anonymous events to attempt for logs not matching any regular event
     pub implementation_name: Option<String>,
     pub implementation_address: Option<Address>,
     pub parent_contract_name: Option<String>,
@@ -40,6 +42,7 @@ impl ContractAbi {
             name: name.to_string(),
             address,
             abi: json_abi,
+            anonymous_events: Vec::new(),
             implementation_name: None,
             implementation_address: None,
             parent_contract_name: None,
@@ -68,10 +71,20 @@ impl ContractAbi {
         // Safely deserialize with JsonAbi
         let json_abi: JsonAbi = serde_json::from_slice(&preprocessed_json)?;
 
+        // Declared anonymous events must exist in the ABI
+        for event_name in &contract_info.anonymous_events {
+            let declared = json_abi.events()
+                .any(|event| event.anonymous && &event.name == event_name);
+            if !declared {
+                anyhow::bail!("Anonymous event '{}' not found in ABI of contract '{}'", event_name, contract_info.name);
+            }
+        }
+
         Ok(Self {
             name: contract_info.name.clone(),
             address,
             abi: json_abi,
+            anonymous_events: contract_info.anonymous_events.clone(),
             implementation_name: Some(contract_info.name.clone()),
             implementation_address: Some(address),
             parent_contract_name: contract_info.parent_contract_name.clone(),
@@ -79,6 +92,36 @@ impl ContractAbi {
         })
     }
 
+    /// Implementation of the proxy contract detected on-chain, with the ABI fetched from a block explorer.
+    /// Declared anonymous events of the proxy are kept if present in the fetched ABI.
+    pub fn from_detected_implementation(
+        name: &str,
+        address: Address,
+        proxy: &ContractAbi,
+        abi_json: &[u8],
+    ) -> anyhow::Result<Self> {
+        // Preprocess the JSON to add missing anonymous fields
+        let preprocessed_json = EventDecoder::preprocess_abi_json_from_vec(abi_json)?;
+        // Safely deserialize with JsonAbi
+        let json_abi: JsonAbi = serde_json::from_slice(&preprocessed_json)?;
+
+        let anonymous_events = proxy.anonymous_events.iter()
+            .filter(|event_name| json_abi.events().any(|event| event.anonymous && event.name == **event_name))
+            .cloned()
+            .collect();
+
+        Ok(Self {
+            name: name.to_string(),
+            address,
+            abi: json_abi,
+            anonymous_events,
+            implementation_name: Some(name.to_string()),
+            implementation_address: Some(address),
+            parent_contract_name: Some(proxy.get_effective_contract_name().to_string()),
+            parent_contract_address: Some(proxy.get_effective_contract_address()),
+        })
+    }
+
     /// Check if this contract represents an implementation
     pub fn is_implementation(&self) -> bool {
         self.parent_contract_name.is_some()
@@ -94,3 +137,213 @@ impl ContractAbi {
         self.parent_contract_address.unwrap_or(self.address)
     }
 }
+
+/// Resolve event names (e.g. `Transfer`) or signatures (e.g. `Transfer(address,address,uint256)`)
+/// to topic0 hashes of the matching non-anonymous events declared in the ABIs
+pub fn resolve_event_topics<'a>(abis: impl IntoIterator<Item = &'a JsonAbi> + Clone, events: &[String]) -> anyhow::Result<Vec<B256>> {
+    let mut topics = BTreeSet::new();
+
+    for event in events {
+        let event: String = event.chars().filter(|c| !c.is_whitespace()).collect();
+        let is_signature = event.contains('(');
+
+        let matched: Vec<B256> = abis.clone().into_iter()
+            .flat_map(|abi| abi.events())
+            .filter(|abi_event| !abi_event.anonymous)
+            .filter(|abi_event| if is_signature { abi_event.signature() == event } else { abi_event.name == event })
+            .map(|abi_event| abi_event.selector())
+            .collect();
+
+        if matched.is_empty() {
+            anyhow::bail!("Event '{}' not found in the loaded contract ABIs (or declared anonymous)", event);
+        }
+
+        topics.extend(matched);
+    }
+
+    Ok(topics.into_iter().collect())
+}
+
+/// Different events or functions of the loaded ABIs sharing a topic0 or a 4-byte selector
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SelectorCollision {
+    pub kind: &'static str, // "event" or "function"
+    pub selector: String,
+    pub declarations: Vec<(String, String)>, // (contract name, full signature) of the colliding declarations
+}
+
+impl std::fmt::Display for SelectorCollision {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let declarations: Vec<String> = self.declarations.iter()
+            .map(|(contract, signature)| format!("'{}' of {}", signature, contract))
+            .collect();
+        write!(f, "{} selector collision {}: {}", self.kind, self.selector, declarations.join(", "))
+    }
+}
+
+/// Find events topic0 and functions selector collisions across the ABIs of the named contracts.
+/// Identical declarations, e.g. the same event of several tokens, don't collide.
+pub fn find_selector_collisions<'a>(abis: impl IntoIterator<Item = (&'a str, &'a JsonAbi)>) -> Vec<SelectorCollision> {
+    let mut events: BTreeMap<String, BTreeMap<String, String>> = BTreeMap::new();
+    let mut functions: BTreeMap<String, BTreeMap<String, String>> = BTreeMap::new();
+
+    for (contract, abi) in abis {
+        for event in abi.events().filter(|event| !event.anonymous) {
+            events.entry(event.selector().to_string()).or_default()
+                .entry(event.full_signature()).or_insert_with(|| contract.to_string());
+        }
+        for function in abi.functions() {
+            functions.entry(function.selector().to_string()).or_default()
+                .entry(function.full_signature()).or_insert_with(|| contract.to_string());
+        }
+    }
+
+    let collisions = |kind: &'static str, declarations: BTreeMap<String, BTreeMap<String, String>>| {
+        declarations.into_iter()
+            .filter(|(_, signatures)| signatures.len() > 1)
+            .map(move |(selector, signatures)| SelectorCollision {
+                kind,
+                selector,
+                declarations: signatures.into_iter().map(|(signature, contract)| (contract, signature)).collect(),
+            })
+    };
+
+    collisions("event", events).chain(collisions("function", functions)).collect()
+}
+
+/// Load the ABIs of all configured contracts (including implementations), print their events and
+/// functions with selectors, and report the ABIs failing to load and the selector collisions.
+/// Fails if any problem is found.
+pub fn check_abis(config: &AppCfg) -> anyhow::Result<()> {
+    let mut problems = Vec::new();
+    let mut contracts = Vec::new();
+
+    for contract_info in config.get_all_contracts() {
+        match ContractAbi::from_contract_with_implementation(&contract_info) {
+            Ok(contract) => contracts.push(contract),
+            Err(e) => problems.push(format!(
+                "Contract {} ({}): ABI file {}: {}",
+                contract_info.name, contract_info.address, contract_info.abi_path, e
+            )),
+        }
+    }
+
+    for contract in &contracts {
+        println!(
+            "Contract {} ({}): {} events, {} functions",
+            contract.name, contract.address, contract.abi.events().count(), contract.abi.functions().count()
+        );
+        for event in contract.abi.events() {
+            let selector = if event.anonymous { "anonymous".to_string() } else { event.selector().to_string() };
+            println!("  event    {} {}", selector, event.full_signature());
+        }
+        for function in contract.abi.functions() {
+            println!("  function {} {}", function.selector(), function.full_signature());
+        }
+    }
+
+    problems.extend(
+        find_selector_collisions(contracts.iter().map(|contract| (contract.name.as_str(), &contract.abi)))
+            .iter()
+            .map(ToString::to_string),
+    );
+
+    // Events filter must resolve against the loaded ABIs, as on startup
+    if let Some(filter_events) = config.indexing.filter_events.as_ref().filter(|events| !events.is_empty())
+        && let Err(e) = resolve_event_topics(contracts.iter().map(|contract| &contract.abi), filter_events) {
+        problems.push(format!("Events filter: {}", e));
+    }
+
+    if problems.is_empty() {
+        println!("ABIs check passed: {} contracts", contracts.len());
+        return Ok(());
+    }
+
+    for problem in &problems {
+        eprintln!("{}", problem);
+    }
+    anyhow::bail!("ABIs check failed with {} problems", problems.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ABI_JSON: &str = r#"[
+        {
+            "type": "event",
+            "name": "Transfer",
+            "inputs": [
+                {"name": "from", "type": "address", "indexed": true},
+                {"name": "to", "type": "address", "indexed": true},
+                {"name": "value", "type": "uint256", "indexed": false}
+            ],
+            "anonymous": false
+        },
+        {
+            "type": "event",
+            "name": "Approval",
+            "inputs": [
+                {"name": "owner", "type": "address", "indexed": true},
+                {"name": "spender", "type": "address", "indexed": true},
+                {"name": "value", "type": "uint256", "indexed": false}
+            ],
+            "anonymous": false
+        }
+    ]"#;
+
+    fn transfer_topic() -> B256 {
+        B256::from_str("0xddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef").unwrap()
+    }
+
+    #[test]
+    fn test_resolve_event_topics_by_name_and_signature() {
+        let abi: JsonAbi = serde_json::from_str(ABI_JSON).unwrap();
+
+        let by_name = resolve_event_topics([&abi], &["Transfer".to_string()]).unwrap();
+        assert_eq!(by_name, vec![transfer_topic()]);
+
+        let by_signature = resolve_event_topics([&abi], &["Transfer(address, address, uint256)".to_string()]).unwrap();
+        assert_eq!(by_signature, vec![transfer_topic()]);
+
+        let both = resolve_event_topics([&abi], &["Transfer".to_string(), "Approval".to_string()]).unwrap();
+        assert_eq!(both.len(), 2);
+        assert!(both.contains(&transfer_topic()));
+    }
+
+    #[test]
+    fn test_find_selector_collisions() {
+        let token: JsonAbi = serde_json::from_str(ABI_JSON).unwrap();
+        let other_token: JsonAbi = serde_json::from_str(ABI_JSON).unwrap();
+        // Same topic0 as Transfer of the token, with the indexed parameters named differently
+        let nft: JsonAbi = serde_json::from_str(r#"[
+            {
+                "type": "event",
+                "name": "Transfer",
+                "inputs": [
+                    {"name": "from", "type": "address", "indexed": true},
+                    {"name": "to", "type": "address", "indexed": true},
+                    {"name": "tokenId", "type": "uint256", "indexed": true}
+                ],
+                "anonymous": false
+            }
+        ]"#).unwrap();
+
+        assert!(find_selector_collisions([("Token", &token), ("OtherToken", &other_token)]).is_empty());
+
+        let collisions = find_selector_collisions([("Token", &token), ("NFT", &nft)]);
+        assert_eq!(collisions.len(), 1);
+        assert_eq!(collisions[0].kind, "event");
+        assert_eq!(collisions[0].selector, transfer_topic().to_string());
+        assert_eq!(collisions[0].declarations.len(), 2);
+        assert!(collisions[0].declarations.iter().any(|(contract, _)| contract == "NFT"));
+    }
+
+    #[test]
+    fn test_resolve_event_topics_unknown_event() {
+        let abi: JsonAbi = serde_json::from_str(ABI_JSON).unwrap();
+
+        assert!(resolve_event_topics([&abi], &["Burn".to_string()]).is_err());
+        assert!(resolve_event_topics([&abi], &["Transfer(address,uint256)".to_string()]).is_err());
+    }
+}
@@ -39,7 +39,7 @@ impl AwsRdsClient {
             }
         }
 
-        let (client, connection) = pg_config.connect(NoTls).await
+        let (mut client, connection) = pg_config.connect(NoTls).await
             .map_err(|e| anyhow::anyhow!("Failed to connect to AWS RDS: {:?}", e))?;
 
         // Spawn connection handler
@@ -56,6 +56,14 @@ impl AwsRdsClient {
                 .map_err(|e| anyhow::anyhow!("Failed to setup AWS RDS schema: {:?}", e))?;
         }
 
+        // Apply pending migrations, refusing a schema migrated by a newer release
+        if let Some(migrations_dir) = &config.migrations_dir {
+            let migrations = db_migrations::load_dir(migrations_dir)?;
+            let version = db_migrations::run(&mut client, &migrations).await
+                .map_err(|e| anyhow::anyhow!("Failed to migrate AWS RDS schema: {:?}", e))?;
+            info!("AWS RDS schema version {}", version);
+        }
+
         info!("Successfully connected to AWS RDS");
 
         Ok(Self {
@@ -84,8 +92,11 @@ impl AwsRdsClient {
                 log_hash,
                 event_name,
                 event_signature,
-                event_data
-            ) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16, $17, $18::jsonb)
+                event_data,
+                contract_label,
+                from_label,
+                to_label
+            ) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16, $17, $18::jsonb, $19, $20, $21)
             ON CONFLICT (log_hash) DO UPDATE SET
                 updated_at = CURRENT_TIMESTAMP
         "#;
@@ -114,6 +125,9 @@ impl AwsRdsClient {
                     &payload.event_name,
                     &payload.event_signature,
                     &event_data_jsonb,
+                    &payload.contract_label,
+                    &payload.from_label,
+                    &payload.to_label,
                 ],
             )
             .await
@@ -129,6 +143,24 @@ impl AwsRdsClient {
         }
     }
 
+    pub async fn delete_removed_event(&self, chain_id: &str, block_hash: &str, log_index: &str) -> anyhow::Result<()> {
+        let query = r#"
+            DELETE FROM events_monitor_data
+            WHERE chain_id = $1 AND block_hash = $2 AND log_index = $3
+        "#;
+
+        match self.client.execute(query, &[&chain_id, &block_hash, &log_index]).await {
+            Ok(deleted) => {
+                debug!("Deleted {} removed events from AWS RDS", deleted);
+                Ok(())
+            },
+            Err(e) => {
+                error!("Failed to delete removed event from AWS RDS: {:?}", e);
+                Err(anyhow::anyhow!("AWS RDS deletion failed: {:?}", e))
+            }
+        }
+    }
+
     pub async fn test_connection(&self) -> anyhow::Result<()> {
         match self.client.execute("SELECT 1", &[]).await {
             Ok(_) => {
@@ -0,0 +1,19 @@
+use clap::Parser;
+use monitor_common::cli::{BackfillArgs, MonitorArgs};
+
+pub use monitor_common::cli::Mode;
+
+#[derive(Debug, Parser)]
+#[command(name = "events-monitor", version, about = "Events monitor: contract events monitoring and indexing")]
+pub struct Cli {
+    #[command(flatten)]
+    pub monitor: MonitorArgs,
+
+    #[command(flatten)]
+    pub backfill: BackfillArgs,
+
+    /// Load the ABIs of the configured contracts, list their events and functions selectors and exit,
+    /// with a non-zero exit code if any ABI fails to load or selectors collide
+    #[arg(long, conflicts_with_all = ["api", "backfill_only"])]
+    pub check_abis: bool,
+}
@@ -1,12 +1,41 @@
 use serde::Deserialize;
+use monitor_common::labels::LabelsCfg;
 
 #[derive(Debug, Deserialize, Clone)]
 pub struct ChainCfg {
     pub http_rpc_url: String,
     pub ws_rpc_url: String,
+    pub http_rpc_urls: Option<Vec<String>>, // Fallback HTTP RPC endpoints, tried in order after 'http_rpc_url' if it's unreachable on startup
+    pub ws_rpc_urls: Option<Vec<String>>, // Fallback WS RPC endpoints, tried in order after 'ws_rpc_url' on connection failure or subscription termination
+    pub ws_reconnect_delay_secs: Option<u64>, // Delay before reconnecting to the next WS RPC endpoint. Defaults to 5 if not specified.
+    pub ws_max_reconnect_attempts: Option<u64>, // Max number of consecutive WS reconnection attempts, unlimited if not specified
+    pub rpc_rate_limit: Option<u32>, // Max outbound HTTP RPC requests per second of the task, shared by the historical and subscription processing, unlimited if not specified
+    pub rpc_rate_limit_burst: Option<u32>, // Max requests sent at once before the rate limit applies, 'rpc_rate_limit' by default
     pub chain_id: u64,
 }
 
+impl ChainCfg {
+    /// Primary HTTP RPC endpoint followed by the fallback ones
+    pub fn http_rpc_endpoints(&self) -> Vec<String> {
+        rpc_endpoints(&self.http_rpc_url, self.http_rpc_urls.as_deref())
+    }
+
+    /// Primary WS RPC endpoint followed by the fallback ones
+    pub fn ws_rpc_endpoints(&self) -> Vec<String> {
+        rpc_endpoints(&self.ws_rpc_url, self.ws_rpc_urls.as_deref())
+    }
+}
+
+fn rpc_endpoints(primary_url: &str, fallback_urls: Option<&[String]>) -> Vec<String> {
+    let mut endpoints = vec![primary_url.to_string()];
+    for url in fallback_urls.unwrap_or_default() {
+        if !endpoints.contains(url) {
+            endpoints.push(url.clone());
+        }
+    }
+    endpoints
+}
+
 #[derive(Debug, Deserialize, Clone)]
 pub struct IndexingCfg {
     // Block range to process
@@ -22,6 +51,26 @@ pub struct IndexingCfg {
     pub new_logs_subscription: Option<u8>, // Enabled (1) or disabled (0)
     pub new_logs_subscription_protocol: Option<String>, // "http", "http_watcher" or "ws" - for new logs subscription/polling, if not present in config file or "null", then "http" by default
     pub http_polling_interval_secs: Option<u64>, // Polling interval in seconds, for HTTP RPC only (i.e. only used when 'new_logs_subscription_protocol' is 'http')
+    pub confirmation_lag: Option<u64>, // Only process logs of blocks at least this many blocks behind the chain tip, new logs are polled via HTTP when set, 0 (no lag) by default
+
+    // Event filtering
+    pub filter_events: Option<Vec<String>>, // Event names (e.g. "Transfer") or signatures (e.g. "Transfer(address,address,uint256)") to index, resolved to topic0 hashes from the loaded ABIs. All events are indexed if empty or not specified.
+    pub indexed_preimages: Option<Vec<String>>, // Candidate values (e.g. enum-like role or status names) of indexed string and bytes parameters, decoded along the parameter hash when it matches
+    pub strict_event_collisions: Option<bool>, // Fail on startup if different events of the loaded ABIs share a topic0, otherwise only warn, 'false' by default
+
+    // Transactions lookups batching
+    pub rpc_batch_size: Option<usize>, // Max 'eth_getTransactionByHash' requests coalesced into a single JSON-RPC batch call when processing logs lists, 100 by default
+
+    // Dead-letter table for undecodable logs
+    pub record_failed_events: Option<bool>, // Persist logs that fail to decode to the 'failed_events' table, 'false' by default
+
+    // Streamed new logs persistence
+    pub log_queue_capacity: Option<usize>, // Max logs queued between 'ws'/'http_watcher' logs streams and the log workers, the stream is paused when full (default 1000)
+    pub log_workers: Option<usize>, // Number of workers persisting queued logs concurrently (default 4)
+
+    // Processed logs watermark
+    pub resume_from_watermark: Option<bool>, // Resume logs processing from the 'processed_logs' watermark after restart, 'true' by default
+    pub watermark_flush_interval_ms: Option<u64>, // Interval of committing the advanced watermark to the database (default 1000)
 
     // Transaction filtering
     pub filter_senders: Option<Vec<String>>,
@@ -32,6 +81,7 @@ pub struct IndexingCfg {
 pub struct PgCfg {
     pub dsn: String,
     pub schema: String,
+    pub migrations_dir: Option<String>, // Numbered SQL migrations (`<version>_<name>.sql`), applied after the schema
 }
 
 #[derive(Debug, Deserialize, Clone)]
@@ -47,6 +97,7 @@ pub struct AwsRdsCfg {
     pub connection_timeout: Option<u64>,
     pub max_connections: Option<u32>,
     pub schema: Option<String>,
+    pub migrations_dir: Option<String>, // Numbered SQL migrations (`<version>_<name>.sql`), applied after the schema
 }
 
 #[derive(Debug, Deserialize, Clone)]
@@ -54,6 +105,45 @@ pub struct NatsCfg {
     pub nats_enabled: Option<u8>,
     pub url: String,
     pub object_store_bucket: String,
+    pub routes: Option<Vec<NatsRouteCfg>>, // Routes of events to other subjects or buckets, first matching route wins
+    pub buffer_capacity: Option<usize>, // Max events buffered while NATS is degraded, the oldest are dropped when full (default 10000)
+    pub retry_interval_secs: Option<u64>, // Interval of republishing the buffered events while NATS is degraded (default 5)
+    pub publish_timeout_secs: Option<u64>, // Timeout of a publish, after which NATS is degraded (default 10)
+}
+
+/// Route of the matching events to a JetStream subject or another object store bucket,
+/// instead of the default bucket. Set matchers must all match, at least one is required.
+#[derive(Debug, Deserialize, Clone)]
+pub struct NatsRouteCfg {
+    pub contract_address: Option<String>,
+    pub contract_name: Option<String>,
+    pub event_name: Option<String>,
+    pub subject: Option<String>, // JetStream subject to publish the matching events to
+    pub stream: Option<String>, // Stream capturing the subject, created if missing (otherwise it must exist)
+    pub bucket: Option<String>, // Object store bucket to put the matching events to, created if missing
+}
+
+/// Additional output sink, written after PostgreSQL and NATS Object Store
+#[derive(Debug, Deserialize, Clone)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum SinkCfg {
+    Webhook {
+        url: String,
+        timeout_secs: Option<u64>, // Request timeout (default 10)
+    },
+}
+
+/// On-chain detection of proxy contracts implementations via EIP-1967 storage slots
+#[derive(Debug, Deserialize, Clone)]
+pub struct ProxyDetectionCfg {
+    pub enabled: Option<bool>, // 'false' by default
+    pub interval_secs: Option<u64>, // Implementations re-check interval, 300 by default, 0 to detect on startup only
+    pub blockscout_server: String, // Blockscout server to fetch ABIs of the detected implementations from
+    pub blockscout_api_path: Option<String>, // Blockscout v2 API path, "/api/v2" by default
+    pub request_timeout_secs: Option<u64>, // 30 by default
+    pub max_retries: Option<u32>, // 3 by default
+    pub auth_user: Option<String>,
+    pub auth_password: Option<String>,
 }
 
 #[derive(Debug, Deserialize, Clone)]
@@ -61,9 +151,28 @@ pub struct ContractCfg {
     pub name: String,
     pub address: String,
     pub abi_path: String,
+    pub anonymous_events: Option<Vec<String>>, // Names of anonymous events in the ABI to attempt, in order, for logs not matching any regular event
     pub implementations: Option<Vec<ContractCfg>>,
 }
 
+#[derive(Debug, Deserialize, Clone, Default)]
+pub struct ApiCfg {
+    pub bind_address: Option<String>, // API server bind address, overridden by `--bind` CLI option
+}
+
+#[derive(Debug, Deserialize)]
+struct ApiModeCfg {
+    api: Option<ApiCfg>,
+}
+
+impl ApiCfg {
+    /// Load the `api` section only, as monitoring task sections aren't required in API mode
+    pub fn load(path: &str) -> anyhow::Result<Self> {
+        let config: ApiModeCfg = config_loader::load(path)?;
+        Ok(config.api.unwrap_or_default())
+    }
+}
+
 #[derive(Debug, Deserialize, Clone)]
 pub struct AppCfg {
     pub name: Option<String>, // Optional name field for task identification
@@ -72,6 +181,9 @@ pub struct AppCfg {
     pub postgres: PgCfg,
     pub aws_rds: Option<AwsRdsCfg>,
     pub nats: NatsCfg,
+    pub sinks: Option<Vec<SinkCfg>>, // Additional output sinks
+    pub proxy_detection: Option<ProxyDetectionCfg>, // Proxy implementations auto-detection
+    pub labels: Option<LabelsCfg>, // Human-readable labels of the contract, sender and receiver addresses of the persisted events
     pub contracts: Vec<ContractCfg>,
     pub max_implementations_per_contract: Option<usize>,
     pub max_implementation_nesting_depth: Option<usize>,
@@ -79,7 +191,7 @@ pub struct AppCfg {
 
 impl AppCfg {
     pub fn load(path: &str) -> anyhow::Result<Self> {
-        let mut config: Self = serde_yaml::from_str(&std::fs::read_to_string(path)?)?;
+        let mut config: Self = config_loader::load(path)?;
 
         // Set default values if not specified
         if config.max_implementations_per_contract.is_none() {
@@ -185,6 +297,7 @@ impl AppCfg {
             name: contract.name.clone(),
             address: contract.address.clone(),
             abi_path: contract.abi_path.clone(),
+            anonymous_events: contract.anonymous_events.clone().unwrap_or_default(),
             parent_contract_name: parent_info.as_ref().map(|(name, _)| name.clone()),
             parent_contract_address: parent_info.as_ref().map(|(_, addr)| addr.clone()),
         });
@@ -207,6 +320,7 @@ pub struct ContractWithImplementation {
     pub name: String,
     pub address: String,
     pub abi_path: String,
+    pub anonymous_events: Vec<String>,
     pub parent_contract_name: Option<String>,
     pub parent_contract_address: Option<String>,
 }
@@ -1,7 +1,7 @@
 use tokio_postgres::{Client, NoTls};
-use tracing::{info, error, warn};
+use tracing::{info, error, warn, debug};
 
-use crate::types::EventPayload;
+use crate::types::{EventPayload, FailedEventPayload};
 use crate::config::AwsRdsCfg;
 use crate::aws_rds::{AwsRdsClient, create_aws_rds_client};
 
@@ -14,10 +14,11 @@ impl DatabaseClients {
     pub async fn new(
         local_dsn: &str,
         local_schema: &str,
+        local_migrations_dir: Option<&str>,
         aws_rds_config: Option<&AwsRdsCfg>
     ) -> anyhow::Result<Self> {
         // Connect to local PostgreSQL
-        let local_pg = connect_pg(local_dsn, local_schema).await?;
+        let local_pg = connect_pg(local_dsn, local_schema, local_migrations_dir).await?;
 
         // Connect to AWS RDS if enabled
         let aws_rds = if let Some(rds_config) = aws_rds_config {
@@ -71,6 +72,35 @@ impl DatabaseClients {
         Ok(())
     }
 
+    /// Delete the event of a log removed from the canonical chain by a reorganization,
+    /// returns the number of deleted local PostgreSQL rows
+    pub async fn delete_removed_event(&self, chain_id: &str, block_hash: &str, log_index: &str) -> anyhow::Result<u64> {
+        let deleted = delete_removed_event(&self.local_pg, chain_id, block_hash, log_index).await?;
+
+        if let Some(aws_rds) = &self.aws_rds {
+            if let Err(e) = aws_rds.delete_removed_event(chain_id, block_hash, log_index).await {
+                error!("Failed to delete removed event from AWS RDS (non-critical): {:?}", e);
+            }
+        }
+
+        Ok(deleted)
+    }
+
+    /// Load the processed logs watermark of the logs stream from the local PostgreSQL
+    pub async fn get_watermark(&self, chain_id: u64, stream_name: &str) -> anyhow::Result<Option<(u64, u64)>> {
+        get_watermark(&self.local_pg, chain_id, stream_name).await
+    }
+
+    /// Commit the processed logs watermark of the logs stream to the local PostgreSQL
+    pub async fn update_watermark(&self, chain_id: u64, stream_name: &str, position: (u64, u64)) -> anyhow::Result<()> {
+        update_watermark(&self.local_pg, chain_id, stream_name, position).await
+    }
+
+    /// Persist an undecodable log to the local dead-letter table
+    pub async fn insert_failed_event(&self, payload: &FailedEventPayload) -> anyhow::Result<()> {
+        insert_failed_event(&self.local_pg, payload).await
+    }
+
     pub async fn test_connections(&self) -> anyhow::Result<()> {
         // Test local PostgreSQL
         match self.local_pg.execute("SELECT 1", &[]).await {
@@ -96,8 +126,8 @@ impl DatabaseClients {
     }
 }
 
-pub async fn connect_pg(dsn: &str, schema: &str) -> anyhow::Result<Client> {
-    let (client, connection) = tokio_postgres::connect(dsn, NoTls).await?;
+pub async fn connect_pg(dsn: &str, schema: &str, migrations_dir: Option<&str>) -> anyhow::Result<Client> {
+    let (mut client, connection) = tokio_postgres::connect(dsn, NoTls).await?;
     tokio::spawn(async move {
         if let Err(e) = connection.await {
             error!("Postgres connection error: {:?}", e);
@@ -107,6 +137,13 @@ pub async fn connect_pg(dsn: &str, schema: &str) -> anyhow::Result<Client> {
     // Create schema if not exists
     client.batch_execute(schema).await?;
 
+    // Apply pending migrations, refusing a schema migrated by a newer release
+    if let Some(migrations_dir) = migrations_dir {
+        let migrations = db_migrations::load_dir(migrations_dir)?;
+        let version = db_migrations::run(&mut client, &migrations).await?;
+        info!("Local PostgreSQL schema version {}", version);
+    }
+
     info!("Local PostgreSQL ready");
 
     Ok(client)
@@ -135,13 +172,17 @@ pub async fn insert_event(
             log_hash,
             event_name,
             event_signature,
-            event_data
-        ) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16, $17, $18::jsonb)
+            event_data,
+            contract_label,
+            from_label,
+            to_label
+        ) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16, $17, $18::jsonb, $19, $20, $21)
+        ON CONFLICT DO NOTHING
     "#;
 
     let event_data_jsonb = serde_json::to_value(&payload.event_data)?;
 
-    client
+    let inserted = client
         .execute(
             query,
             &[
@@ -163,11 +204,129 @@ pub async fn insert_event(
                 &payload.event_name,
                 &payload.event_signature,
                 &event_data_jsonb,
+                &payload.contract_label,
+                &payload.from_label,
+                &payload.to_label,
+            ],
+        )
+        .await?;
+
+    // The log hash is the idempotency key, so logs re-fetched after a restart aren't duplicated
+    if inserted > 0 {
+        info!("Event inserted to local PostgreSQL");
+    } else {
+        debug!("Event {} already persisted to local PostgreSQL, skipped", payload.log_hash);
+    }
+
+    Ok(())
+}
+
+/// Delete the event of the log at `log_index` of the orphaned `block_hash` block
+pub async fn delete_removed_event(
+    client: &Client,
+    chain_id: &str,
+    block_hash: &str,
+    log_index: &str,
+) -> anyhow::Result<u64> {
+    let query = r#"
+        DELETE FROM events_monitor_data
+        WHERE chain_id = $1 AND block_hash = $2 AND log_index = $3
+    "#;
+
+    let deleted = client.execute(query, &[&chain_id, &block_hash, &log_index]).await?;
+    Ok(deleted)
+}
+
+pub async fn get_watermark(
+    client: &Client,
+    chain_id: u64,
+    stream_name: &str,
+) -> anyhow::Result<Option<(u64, u64)>> {
+    let row = client
+        .query_opt(
+            "SELECT block_number, log_index FROM processed_logs WHERE chain_id = $1 AND stream_name = $2",
+            &[&chain_id.to_string(), &stream_name],
+        )
+        .await?;
+
+    Ok(row.map(|row| (row.get::<_, i64>(0) as u64, row.get::<_, i64>(1) as u64)))
+}
+
+/// Upsert the watermark, it only moves forward
+pub async fn update_watermark(
+    client: &Client,
+    chain_id: u64,
+    stream_name: &str,
+    (block_number, log_index): (u64, u64),
+) -> anyhow::Result<()> {
+    let query = r#"
+        INSERT INTO processed_logs (chain_id, stream_name, block_number, log_index)
+        VALUES ($1, $2, $3, $4)
+        ON CONFLICT (chain_id, stream_name) DO UPDATE SET
+            block_number = EXCLUDED.block_number,
+            log_index = EXCLUDED.log_index,
+            updated_at = NOW()
+        WHERE (processed_logs.block_number, processed_logs.log_index) < (EXCLUDED.block_number, EXCLUDED.log_index)
+    "#;
+
+    client
+        .execute(
+            query,
+            &[&chain_id.to_string(), &stream_name, &(block_number as i64), &(log_index as i64)],
+        )
+        .await?;
+
+    debug!("Processed logs watermark of {} updated to block {} log {}", stream_name, block_number, log_index);
+
+    Ok(())
+}
+
+pub async fn insert_failed_event(
+    client: &Client,
+    payload: &FailedEventPayload,
+) -> anyhow::Result<()> {
+    let query = r#"
+        INSERT INTO failed_events (
+            contract_name,
+            contract_address,
+            chain_id,
+            block_number,
+            block_hash,
+            transaction_hash,
+            log_index,
+            log_hash,
+            topics,
+            data,
+            error
+        ) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9::jsonb, $10, $11)
+        ON CONFLICT (chain_id, log_hash) DO UPDATE SET
+            contract_name = EXCLUDED.contract_name,
+            error = EXCLUDED.error,
+            updated_at = NOW()
+    "#;
+
+    let topics_jsonb = serde_json::to_value(&payload.topics)?;
+
+    client
+        .execute(
+            query,
+            &[
+                &payload.contract_name,
+                &payload.contract_address,
+                &payload.chain_id,
+                &payload.block_number,
+                &payload.block_hash,
+                &payload.transaction_hash,
+                &payload.log_index,
+                &payload.log_hash,
+                &topics_jsonb,
+                &payload.data,
+                &payload.error,
             ],
         )
         .await?;
 
-    info!("Event inserted to local PostgreSQL");
+    info!("Failed event inserted to local PostgreSQL dead-letter table");
 
     Ok(())
 }
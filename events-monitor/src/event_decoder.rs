@@ -1,10 +1,11 @@
-use alloy::primitives::{Address, Bytes, FixedBytes, Log, LogData, B256};
+use alloy::primitives::{keccak256, Address, Bytes, FixedBytes, Log, LogData, B256};
 use alloy::json_abi::{Event, EventParam, JsonAbi, Param};
-use alloy_dyn_abi::{DynSolValue, DynSolType};
+use alloy_dyn_abi::{DynSolValue, DynSolType, Specifier};
 use anyhow::{anyhow, Result};
 use std::collections::HashMap;
 use std::path::Path;
 use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
 use serde_json::Value;
 use tracing::{info, warn};
 
@@ -14,6 +15,8 @@ pub struct ParsedEventParam {
     pub param_type: String,
     pub value: DynSolValue,
     pub indexed: bool,
+    pub preimage: Option<DynSolValue>, // Known preimage of the indexed string or bytes parameter hash in `value`
+    pub components: Vec<Param>, // Tuple components, to name the tuple fields
 }
 
 #[derive(Debug, Clone)]
@@ -27,8 +30,37 @@ pub struct ParsedEvent {
 pub struct EventDecoder {
     events: HashMap<B256, Event>,
     anonymous_events: Vec<Event>,
+    collisions: Vec<EventCollision>,
+    indexed_preimages: HashMap<B256, String>, // keccak256 hash -> candidate preimage
 }
 
+/// Different events with the same topic0 (e.g. same types with different names, order of names or
+/// indexed parameters), the later event overwrites the earlier one in the decoder
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EventCollision {
+    pub selector: B256,
+    pub event: String, // Full signature of the kept event
+    pub contract: String,
+    pub overwritten_event: String, // Full signature of the overwritten event
+    pub overwritten_contract: String,
+}
+
+impl std::fmt::Display for EventCollision {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Event topic0 collision {}: '{}' of {} overwrites '{}' of {}",
+            self.selector, self.event, self.contract, self.overwritten_event, self.overwritten_contract
+        )
+    }
+}
+
+/// Source name of events of a single ABI
+const SINGLE_ABI_SOURCE: &str = "ABI";
+
+/// Number of decoders constructed since the process start
+static DECODERS_CONSTRUCTED: AtomicUsize = AtomicUsize::new(0);
+
 impl EventDecoder {
     /// Preprocess ABI JSON to ensure all events have an anonymous field
     pub fn preprocess_abi_json(abi_json_value: &Value) -> Result<Value> {
@@ -69,22 +101,78 @@ impl EventDecoder {
 
     /// Create a new EventDecoder from a JSON ABI
     pub fn new(abi_json: Arc<JsonAbi>) -> Result<Self> {
-        let mut events = HashMap::new();
+        Ok(Self::build(abi_json.events().map(|event| (SINGLE_ABI_SOURCE, event.clone()))))
+    }
+
+    /// Create an EventDecoder aggregating the ABIs of the named contracts.
+    /// Colliding events are reported as warnings, or fail the construction in strict mode.
+    pub fn from_contract_abis<'a>(abis: impl IntoIterator<Item = (&'a str, &'a JsonAbi)>, strict: bool) -> Result<Self> {
+        let events = abis.into_iter()
+            .flat_map(|(contract, abi)| abi.events().map(move |event| (contract, event.clone())));
+        let decoder = Self::build(events);
+
+        for collision in &decoder.collisions {
+            warn!("{}", collision);
+        }
+        if strict && !decoder.collisions.is_empty() {
+            let collisions: Vec<String> = decoder.collisions.iter().map(|collision| collision.to_string()).collect();
+            return Err(anyhow!("{} event topic0 collisions: {}", collisions.len(), collisions.join("; ")));
+        }
+
+        Ok(decoder)
+    }
+
+    /// Build the decoder, recording the collisions without reporting them
+    fn build<'a>(events: impl IntoIterator<Item = (&'a str, Event)>) -> Self {
+        let mut event_map: HashMap<B256, Event> = HashMap::new();
+        let mut event_sources: HashMap<B256, &str> = HashMap::new();
         let mut anonymous_events = Vec::new();
+        let mut collisions = Vec::new();
 
-        for event in abi_json.events() {
+        for (contract, event) in events {
             if event.anonymous {
-                anonymous_events.push(event.clone());
-            } else {
-                let signature = event.selector();
-                events.insert(signature, event.clone());
+                anonymous_events.push(event);
+                continue;
             }
+
+            let selector = event.selector();
+            // Identical events, e.g. declared by both proxy and implementation ABIs, don't collide
+            if let Some(existing) = event_map.get(&selector) && existing != &event {
+                collisions.push(EventCollision {
+                    selector,
+                    event: event.full_signature(),
+                    contract: contract.to_string(),
+                    overwritten_event: existing.full_signature(),
+                    overwritten_contract: event_sources.get(&selector).copied().unwrap_or_default().to_string(),
+                });
+            }
+            event_sources.insert(selector, contract);
+            event_map.insert(selector, event);
         }
 
-        Ok(Self {
-            events,
+        DECODERS_CONSTRUCTED.fetch_add(1, Ordering::Relaxed);
+
+        Self {
+            events: event_map,
             anonymous_events,
-        })
+            collisions,
+            indexed_preimages: HashMap::new(),
+        }
+    }
+
+    /// Add candidate preimages of indexed string and bytes parameters (e.g. enum-like role or
+    /// status names). Topics matching a candidate hash are decoded with the candidate as preimage.
+    pub fn with_indexed_preimages<S: AsRef<str>>(mut self, candidates: impl IntoIterator<Item = S>) -> Self {
+        for candidate in candidates {
+            let candidate = candidate.as_ref();
+            self.indexed_preimages.insert(keccak256(candidate), candidate.to_string());
+        }
+        self
+    }
+
+    /// Get count of decoders constructed since the process start
+    pub fn constructed_count() -> usize {
+        DECODERS_CONSTRUCTED.load(Ordering::Relaxed)
     }
 
     /// Create a new EventDecoder from a JSON ABI string
@@ -120,22 +208,7 @@ impl EventDecoder {
 
     /// Create EventDecoder from individual events
     pub fn from_events(events: Vec<Event>) -> Result<Self> {
-        let mut event_map = HashMap::new();
-        let mut anonymous_events = Vec::new();
-
-        for event in events {
-            if event.anonymous {
-                anonymous_events.push(event.clone());
-            } else {
-                let signature = event.selector();
-                event_map.insert(signature, event.clone());
-            }
-        }
-
-        Ok(Self {
-            events: event_map,
-            anonymous_events,
-        })
+        Ok(Self::build(events.into_iter().map(|event| (SINGLE_ABI_SOURCE, event))))
     }
 
     /// Decode a log entry into a ParsedEvent
@@ -152,6 +225,32 @@ impl EventDecoder {
         self.try_decode_anonymous_event(log)
     }
 
+    /// Decode a log entry by its first topic as a regular event, otherwise try only the given
+    /// anonymous events by name, in order, instead of guessing among all anonymous events
+    pub fn decode_log_with_anonymous_events(&self, log: &Log, anonymous_event_names: &[String]) -> Result<ParsedEvent> {
+        if let Some(event) = log.topics().first().and_then(|topic0| self.events.get(topic0)) {
+            return self.decode_log_with_event(log, event);
+        }
+
+        let mut last_error = None;
+
+        for event_name in anonymous_event_names {
+            if !self.could_be_anonymous_event(log, event_name) {
+                continue;
+            }
+
+            match self.try_decode_anonymous_event_by_name(log, event_name) {
+                Ok(parsed) => return Ok(parsed),
+                Err(e) => last_error = Some(e),
+            }
+        }
+
+        match last_error {
+            Some(e) => Err(anyhow!("Failed to decode as any of the declared anonymous events: {}", e)),
+            None => Err(anyhow!("No regular event matches the log topic0 and no declared anonymous event matches the log layout")),
+        }
+    }
+
     /// Try to decode log as an anonymous event
     pub fn try_decode_anonymous_event(&self, log: &Log) -> Result<ParsedEvent> {
         let mut last_error = None;
@@ -189,13 +288,15 @@ impl EventDecoder {
             }
 
             let topic = log.topics()[topic_index];
-            let value = self.decode_indexed_param(param, topic)?;
+            let (value, preimage) = self.decode_indexed_param(param, topic)?;
 
             parsed_params.push(ParsedEventParam {
                 name: param.name.clone(),
                 param_type: param.ty.to_string(),
                 value,
                 indexed: true,
+                preimage,
+                components: param.components.clone(),
             });
 
             topic_index += 1;
@@ -211,6 +312,8 @@ impl EventDecoder {
                     param_type: param.ty.to_string(),
                     value: value.clone(),
                     indexed: false,
+                    preimage: None,
+                    components: param.components.clone(),
                 });
             }
         }
@@ -258,13 +361,15 @@ impl EventDecoder {
             }
 
             let topic = log.topics()[topic_index];
-            let value = self.decode_indexed_param(param, topic)?;
+            let (value, preimage) = self.decode_indexed_param(param, topic)?;
 
             parsed_params.push(ParsedEventParam {
                 name: param.name.clone(),
                 param_type: param.ty.to_string(),
                 value,
                 indexed: true,
+                preimage,
+                components: param.components.clone(),
             });
 
             topic_index += 1;
@@ -280,6 +385,8 @@ impl EventDecoder {
                     param_type: param.ty.to_string(),
                     value: value.clone(),
                     indexed: false,
+                    preimage: None,
+                    components: param.components.clone(),
                 });
             }
         }
@@ -334,26 +441,34 @@ impl EventDecoder {
         }
     }
 
-    /// Decode an indexed parameter from a topic
-    fn decode_indexed_param(&self, param: &EventParam, topic: B256) -> Result<DynSolValue> {
-        let sol_type = DynSolType::parse(&param.ty)?;
+    /// Decode an indexed parameter from a topic, with the preimage of a string or bytes hash
+    /// if it matches one of the candidate preimages
+    fn decode_indexed_param(&self, param: &EventParam, topic: B256) -> Result<(DynSolValue, Option<DynSolValue>)> {
+        let sol_type = param.resolve()
+            .map_err(|e| anyhow!("Failed to resolve type {} of indexed parameter {}: {}", param.ty, param.name, e))?;
 
-        // For dynamic types (strings, bytes, arrays), topics contain keccak256 hashes
+        // For dynamic and composite types (strings, bytes, arrays, tuples), topics contain keccak256 hashes
         match &sol_type {
             DynSolType::String | DynSolType::Bytes => {
-                // Return the hash as bytes32 since we can't recover the original value
-                Ok(DynSolValue::FixedBytes(topic.0.into(), 32))
+                // Return the hash as bytes32 since we can't recover the original value,
+                // unless it's the hash of a known candidate
+                let preimage = self.indexed_preimages.get(&topic).map(|candidate| match sol_type {
+                    DynSolType::String => DynSolValue::String(candidate.clone()),
+                    _ => DynSolValue::Bytes(candidate.as_bytes().to_vec()),
+                });
+                Ok((DynSolValue::FixedBytes(topic.0.into(), 32), preimage))
             }
 //            DynSolType::Array(_) | DynSolType::Slice(_) => {
-            DynSolType::Array(_) => {
+            DynSolType::Array(_) | DynSolType::FixedArray(..) | DynSolType::Tuple(_) => {
                 // Return the hash as bytes32 since we can't recover the original value
-                Ok(DynSolValue::FixedBytes(topic.0.into(), 32))
+                Ok((DynSolValue::FixedBytes(topic.0.into(), 32), None))
             }
             _ => {
                 // For fixed-size types, decode directly from the topic
                 let topic_bytes = topic.as_slice();
-                sol_type.abi_decode_params(topic_bytes)
-                    .map_err(|e| anyhow!("Failed to decode indexed parameter {}: {}", param.name, e))
+                let value = sol_type.abi_decode_params(topic_bytes)
+                    .map_err(|e| anyhow!("Failed to decode indexed parameter {}: {}", param.name, e))?;
+                Ok((value, None))
             }
         }
     }
@@ -366,7 +481,7 @@ impl EventDecoder {
 
         // Create tuple type from all non-indexed parameters
         let param_types: Result<Vec<DynSolType>> = params.iter()
-            .map(|p| DynSolType::parse(&p.ty).map_err(|e| anyhow!("Failed to decode non-indexed parameter {} of type {}: {}", p.name, p.ty, e)))
+            .map(|p| p.resolve().map_err(|e| anyhow!("Failed to decode non-indexed parameter {} of type {}: {}", p.name, p.ty, e)))
             .collect();
         let param_types = param_types?;
 
@@ -407,6 +522,11 @@ impl EventDecoder {
     pub fn regular_events_count(&self) -> usize {
         self.events.len()
     }
+
+    /// Get events topic0 collisions found on construction
+    pub fn collisions(&self) -> &[EventCollision] {
+        &self.collisions
+    }
 }
 
 /// Helper function to format parsed event parameters for display
@@ -427,7 +547,10 @@ impl ParsedEvent {
             param_json.insert("name".to_string(), Value::String(param.name.clone()));
             param_json.insert("type".to_string(), Value::String(param.param_type.clone()));
             param_json.insert("indexed".to_string(), Value::Bool(param.indexed));
-            param_json.insert("value".to_string(), value_to_json(&param.value)?);
+            param_json.insert("value".to_string(), value_to_json_with_components(&param.value, &param.components)?);
+            if let Some(preimage) = &param.preimage {
+                param_json.insert("preimage".to_string(), value_to_json(preimage)?);
+            }
             params_json.push(Value::Object(param_json));
         }
 
@@ -438,7 +561,10 @@ impl ParsedEvent {
     pub fn format_params(&self) -> String {
         self.params
             .iter()
-            .map(|p| format!("{}: {} = {}", p.name, p.param_type, format_value(&p.value)))
+            .map(|p| match &p.preimage {
+                Some(preimage) => format!("{}: {} = {} ({})", p.name, p.param_type, format_value(&p.value), format_value(preimage)),
+                None => format!("{}: {} = {}", p.name, p.param_type, format_value(&p.value)),
+            })
             .collect::<Vec<_>>()
             .join(", ")
     }
@@ -454,7 +580,7 @@ fn value_to_json(value: &DynSolValue) -> Result<Value> {
         DynSolValue::Bytes(bytes) => Ok(Value::String(hex::encode(bytes))),
         DynSolValue::Address(addr) => Ok(Value::String(format!("{:#x}", addr))),
         DynSolValue::String(s) => Ok(Value::String(s.clone())),
-        DynSolValue::Array(arr) => {
+        DynSolValue::Array(arr) | DynSolValue::FixedArray(arr) => {
             let json_arr: Result<Vec<Value>> = arr.iter().map(value_to_json).collect();
             Ok(Value::Array(json_arr?))
         }
@@ -466,6 +592,33 @@ fn value_to_json(value: &DynSolValue) -> Result<Value> {
     }
 }
 
+/// Convert DynSolValue to JSON Value, with tuples as objects keyed by the component names.
+/// Tuples with unnamed components stay positional arrays.
+fn value_to_json_with_components(value: &DynSolValue, components: &[Param]) -> Result<Value> {
+    match value {
+        DynSolValue::Tuple(fields) if fields.len() == components.len() => {
+            if components.iter().all(|component| !component.name.is_empty()) {
+                let mut object = serde_json::Map::new();
+                for (field, component) in fields.iter().zip(components) {
+                    object.insert(component.name.clone(), value_to_json_with_components(field, &component.components)?);
+                }
+                Ok(Value::Object(object))
+            } else {
+                let json_arr: Result<Vec<Value>> = fields.iter().zip(components)
+                    .map(|(field, component)| value_to_json_with_components(field, &component.components))
+                    .collect();
+                Ok(Value::Array(json_arr?))
+            }
+        }
+        // Arrays of tuples share the components of the element type
+        DynSolValue::Array(items) | DynSolValue::FixedArray(items) if !components.is_empty() => {
+            let json_arr: Result<Vec<Value>> = items.iter().map(|item| value_to_json_with_components(item, components)).collect();
+            Ok(Value::Array(json_arr?))
+        }
+        _ => value_to_json(value),
+    }
+}
+
 /// Format DynSolValue for human-readable display
 fn format_value(value: &DynSolValue) -> String {
     match value {
@@ -511,6 +664,49 @@ mod tests {
         assert_eq!(decoder.events.len(), 1);
     }
 
+    #[test]
+    fn test_decoders_constructed_count() {
+        let abi_json = r#"[
+            {
+                "type": "event",
+                "name": "Transfer",
+                "inputs": [
+                    {"name": "from", "type": "address", "indexed": true},
+                    {"name": "to", "type": "address", "indexed": true},
+                    {"name": "value", "type": "uint256", "indexed": false}
+                ]
+            }
+        ]"#;
+
+        // Other tests construct decoders concurrently, so only the lower bound is exact
+        let before = EventDecoder::constructed_count();
+        let decoder = EventDecoder::from_str(abi_json).unwrap();
+        assert!(EventDecoder::constructed_count() > before);
+
+        // The decoder built once decodes every log of the contract
+        let transfer_signature = decoder.events.keys().next().copied().unwrap();
+        let from = Address::repeat_byte(0x11);
+        let to = Address::repeat_byte(0x22);
+        for value in [1u64, 1_000_000] {
+            let log = Log {
+                address: Address::ZERO,
+                data: LogData::new_unchecked(
+                    vec![transfer_signature, from.into_word(), to.into_word()],
+                    Bytes::from(U256::from(value).to_be_bytes::<32>().to_vec()),
+                ),
+            };
+
+            let parsed = decoder.decode_log(&log).unwrap();
+            assert_eq!(parsed.name, "Transfer");
+            assert_eq!(parsed.signature, Some(transfer_signature));
+            assert_eq!(parsed.params.len(), 3);
+            assert_eq!(parsed.params[0].value, DynSolValue::Address(from));
+            assert_eq!(parsed.params[1].value, DynSolValue::Address(to));
+            assert_eq!(parsed.params[2].name, "value");
+            assert_eq!(parsed.params[2].value, DynSolValue::Uint(U256::from(value), 256));
+        }
+    }
+
     #[test]
     fn test_log_decoding() {
         let abi_json = r#"[
@@ -613,6 +809,71 @@ mod tests {
         assert_eq!(parsed.params.len(), 3);
     }
 
+    #[test]
+    fn test_decode_log_with_anonymous_events() {
+        let abi_json = r#"[
+            {
+                "type": "event",
+                "name": "Transfer",
+                "inputs": [
+                    {"name": "from", "type": "address", "indexed": true},
+                    {"name": "to", "type": "address", "indexed": true},
+                    {"name": "value", "type": "uint256", "indexed": false}
+                ]
+            },
+            {
+                "type": "event",
+                "name": "AnonymousTransfer",
+                "anonymous": true,
+                "inputs": [
+                    {"name": "from", "type": "address", "indexed": true},
+                    {"name": "to", "type": "address", "indexed": true},
+                    {"name": "amount", "type": "uint256", "indexed": false}
+                ]
+            },
+            {
+                "type": "event",
+                "name": "AnonymousDeposit",
+                "anonymous": true,
+                "inputs": [
+                    {"name": "account", "type": "address", "indexed": true},
+                    {"name": "amount", "type": "uint256", "indexed": false}
+                ]
+            }
+        ]"#;
+
+        let decoder = EventDecoder::from_str(abi_json).unwrap();
+        let declared = vec!["AnonymousDeposit".to_string(), "AnonymousTransfer".to_string()];
+
+        let from_addr = B256::from_slice(&hex::decode("000000000000000000000000742d35Cc6634C0532925a3b8BC342A5b6437AFCD").unwrap());
+        let to_addr = B256::from_slice(&hex::decode("000000000000000000000000742d35Cc6634C0532925a3b8BC342A5b6437AFCE").unwrap());
+        let data = Bytes::from(hex::decode("0000000000000000000000000000000000000000000000000de0b6b3a7640000").unwrap());
+
+        // Regular event is decoded by its topic0
+        let transfer_signature = B256::from_slice(&hex::decode("ddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef").unwrap());
+        let log = Log {
+            address: Address::ZERO,
+            data: LogData::new_unchecked(vec![transfer_signature, from_addr, to_addr], data.clone()),
+        };
+        let parsed = decoder.decode_log_with_anonymous_events(&log, &declared).unwrap();
+        assert_eq!(parsed.name, "Transfer");
+        assert!(!parsed.anonymous);
+
+        // Anonymous event is resolved by the topics layout among the declared ones
+        let log = Log {
+            address: Address::ZERO,
+            data: LogData::new_unchecked(vec![from_addr, to_addr], data.clone()),
+        };
+        let parsed = decoder.decode_log_with_anonymous_events(&log, &declared).unwrap();
+        assert_eq!(parsed.name, "AnonymousTransfer");
+        assert!(parsed.anonymous);
+        assert!(parsed.signature.is_none());
+
+        // Undeclared anonymous events aren't attempted
+        assert!(decoder.decode_log_with_anonymous_events(&log, &["AnonymousDeposit".to_string()]).is_err());
+        assert!(decoder.decode_log_with_anonymous_events(&log, &[]).is_err());
+    }
+
     #[test]
     fn test_could_be_anonymous_event() {
         let abi_json = r#"[
@@ -650,6 +911,156 @@ mod tests {
 
         assert!(!decoder.could_be_anonymous_event(&log, "AnonymousEvent"));
     }
+
+    const TOKEN_ABI: &str = r#"[
+        {
+            "type": "event",
+            "name": "Transfer",
+            "inputs": [
+                {"name": "from", "type": "address", "indexed": true},
+                {"name": "to", "type": "address", "indexed": true},
+                {"name": "value", "type": "uint256", "indexed": false}
+            ]
+        }
+    ]"#;
+
+    // Same topic0 as Transfer(address,address,uint256), different indexed parameters
+    const NFT_ABI: &str = r#"[
+        {
+            "type": "event",
+            "name": "Transfer",
+            "inputs": [
+                {"name": "from", "type": "address", "indexed": true},
+                {"name": "to", "type": "address", "indexed": true},
+                {"name": "tokenId", "type": "uint256", "indexed": true}
+            ]
+        }
+    ]"#;
+
+    #[test]
+    fn test_event_collisions_detected() {
+        let token: JsonAbi = serde_json::from_str(TOKEN_ABI).unwrap();
+        let nft: JsonAbi = serde_json::from_str(NFT_ABI).unwrap();
+
+        let decoder = EventDecoder::from_contract_abis([("Token", &token), ("NFT", &nft)], false).unwrap();
+        assert_eq!(decoder.regular_events_count(), 1);
+        assert_eq!(decoder.collisions().len(), 1);
+
+        let collision = &decoder.collisions()[0];
+        assert_eq!(collision.contract, "NFT");
+        assert_eq!(collision.overwritten_contract, "Token");
+        assert_eq!(collision.selector, token.events().next().unwrap().selector());
+        assert!(collision.event.contains("uint256 indexed tokenId"));
+
+        // Later event overwrites the earlier one
+        let event = decoder.get_event(collision.selector).unwrap();
+        assert!(event.inputs[2].indexed);
+    }
+
+    #[test]
+    fn test_event_collisions_strict_mode() {
+        let token: JsonAbi = serde_json::from_str(TOKEN_ABI).unwrap();
+        let nft: JsonAbi = serde_json::from_str(NFT_ABI).unwrap();
+
+        let error = EventDecoder::from_contract_abis([("Token", &token), ("NFT", &nft)], true).err().unwrap();
+        assert!(error.to_string().contains("of NFT overwrites"));
+    }
+
+    #[test]
+    fn test_identical_events_dont_collide() {
+        let token: JsonAbi = serde_json::from_str(TOKEN_ABI).unwrap();
+        let proxy: JsonAbi = serde_json::from_str(TOKEN_ABI).unwrap();
+
+        let decoder = EventDecoder::from_contract_abis([("Token", &token), ("Proxy", &proxy)], true).unwrap();
+        assert_eq!(decoder.regular_events_count(), 1);
+        assert!(decoder.collisions().is_empty());
+    }
+
+    #[test]
+    fn test_indexed_preimages() {
+        let abi_json = r#"[
+            {
+                "type": "event",
+                "name": "StatusChanged",
+                "inputs": [
+                    {"name": "status", "type": "string", "indexed": true},
+                    {"name": "tag", "type": "bytes", "indexed": true}
+                ]
+            }
+        ]"#;
+
+        let decoder = EventDecoder::from_str(abi_json).unwrap()
+            .with_indexed_preimages(["Active", "Paused"]);
+        let selector = decoder.get_event_signatures()[0];
+
+        let log = Log {
+            address: Address::ZERO,
+            data: LogData::new_unchecked(vec![selector, keccak256("Paused"), keccak256("unknown")], Bytes::new()),
+        };
+
+        let parsed = decoder.decode_log(&log).unwrap();
+        assert_eq!(parsed.params[0].value, DynSolValue::FixedBytes(keccak256("Paused"), 32));
+        assert_eq!(parsed.params[0].preimage, Some(DynSolValue::String("Paused".to_string())));
+        assert_eq!(parsed.params[1].preimage, None);
+
+        let json = parsed.to_json().unwrap();
+        assert_eq!(json["parameters"][0]["preimage"], "Paused");
+        assert!(json["parameters"][1].get("preimage").is_none());
+    }
+
+    #[test]
+    fn test_tuple_fields_named_in_json() {
+        let abi_json = r#"[
+            {
+                "type": "event",
+                "name": "OrderFilled",
+                "inputs": [
+                    {"name": "maker", "type": "address", "indexed": true},
+                    {"name": "order", "type": "tuple", "indexed": false, "components": [
+                        {"name": "id", "type": "uint256"},
+                        {"name": "asset", "type": "tuple", "components": [
+                            {"name": "token", "type": "address"},
+                            {"name": "amount", "type": "uint256"}
+                        ]}
+                    ]},
+                    {"name": "fees", "type": "tuple[]", "indexed": false, "components": [
+                        {"name": "recipient", "type": "address"},
+                        {"name": "amount", "type": "uint256"}
+                    ]}
+                ]
+            }
+        ]"#;
+
+        let decoder = EventDecoder::from_str(abi_json).unwrap();
+        let selector = decoder.get_event_signatures()[0];
+
+        let token = Address::repeat_byte(0x11);
+        let recipient = Address::repeat_byte(0x22);
+        let data = DynSolValue::Tuple(vec![
+            DynSolValue::Tuple(vec![
+                DynSolValue::Uint(U256::from(7), 256),
+                DynSolValue::Tuple(vec![DynSolValue::Address(token), DynSolValue::Uint(U256::from(100), 256)]),
+            ]),
+            DynSolValue::Array(vec![
+                DynSolValue::Tuple(vec![DynSolValue::Address(recipient), DynSolValue::Uint(U256::from(3), 256)]),
+            ]),
+        ]).abi_encode_params();
+
+        let log = Log {
+            address: Address::ZERO,
+            data: LogData::new_unchecked(vec![selector, Address::ZERO.into_word()], data.into()),
+        };
+
+        let json = decoder.decode_log(&log).unwrap().to_json().unwrap();
+        let order = &json["parameters"][1]["value"];
+        assert_eq!(order["id"], "7");
+        assert_eq!(order["asset"]["token"], format!("{:#x}", token));
+        assert_eq!(order["asset"]["amount"], "100");
+
+        let fees = &json["parameters"][2]["value"];
+        assert_eq!(fees[0]["recipient"], format!("{:#x}", recipient));
+        assert_eq!(fees[0]["amount"], "3");
+    }
 }
 
 /*
@@ -0,0 +1,86 @@
+//! Prometheus metrics, recorded when built with the `metrics` feature and served on `/metrics` in API mode.
+//! Without the feature all recording functions are no-ops.
+
+use std::future::IntoFuture;
+use std::time::{Duration, Instant};
+
+#[cfg(feature = "metrics")]
+use axum::{routing::get, Router};
+#[cfg(feature = "metrics")]
+use metrics_exporter_prometheus::PrometheusBuilder;
+
+const LOGS_RECEIVED: &str = "events_monitor_logs_received_total";
+const EVENTS_DECODED: &str = "events_monitor_events_decoded_total";
+const DECODE_ERRORS: &str = "events_monitor_decode_errors_total";
+const RPC_CALL_DURATION: &str = "events_monitor_rpc_call_duration_seconds";
+const SINK_WRITE_DURATION: &str = "events_monitor_sink_write_duration_seconds";
+const TASK_ERRORS: &str = "events_monitor_task_errors_total";
+
+/// Latency histograms buckets, in seconds
+#[cfg(feature = "metrics")]
+const LATENCY_BUCKETS: [f64; 12] = [0.001, 0.0025, 0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 10.0];
+
+/// Install the Prometheus recorder and add the `/metrics` endpoint rendering it
+#[cfg(feature = "metrics")]
+pub fn with_metrics_route(router: Router) -> anyhow::Result<Router> {
+    let handle = PrometheusBuilder::new()
+        .set_buckets(&LATENCY_BUCKETS)?
+        .install_recorder()?;
+
+    ::metrics::describe_counter!(LOGS_RECEIVED, "Logs received from RPC, before filtering and decoding");
+    ::metrics::describe_counter!(EVENTS_DECODED, "Events decoded and persisted");
+    ::metrics::describe_counter!(DECODE_ERRORS, "Logs failed to decode");
+    ::metrics::describe_histogram!(RPC_CALL_DURATION, ::metrics::Unit::Seconds, "RPC call latency");
+    ::metrics::describe_histogram!(SINK_WRITE_DURATION, ::metrics::Unit::Seconds, "Output sink write latency");
+    ::metrics::describe_counter!(TASK_ERRORS, "Processing tasks failed, by error kind");
+
+    Ok(router.route("/metrics", get(move || std::future::ready(handle.render()))))
+}
+
+pub fn logs_received(count: usize) {
+    #[cfg(feature = "metrics")]
+    ::metrics::counter!(LOGS_RECEIVED).increment(count as u64);
+    #[cfg(not(feature = "metrics"))]
+    let _ = (LOGS_RECEIVED, count);
+}
+
+pub fn event_decoded(contract: &str, event: &str) {
+    #[cfg(feature = "metrics")]
+    ::metrics::counter!(EVENTS_DECODED, "contract" => contract.to_string(), "event" => event.to_string()).increment(1);
+    #[cfg(not(feature = "metrics"))]
+    let _ = (EVENTS_DECODED, contract, event);
+}
+
+pub fn decode_error(contract: &str) {
+    #[cfg(feature = "metrics")]
+    ::metrics::counter!(DECODE_ERRORS, "contract" => contract.to_string()).increment(1);
+    #[cfg(not(feature = "metrics"))]
+    let _ = (DECODE_ERRORS, contract);
+}
+
+pub fn sink_write(sink: &str, duration: Duration) {
+    #[cfg(feature = "metrics")]
+    ::metrics::histogram!(SINK_WRITE_DURATION, "sink" => sink.to_string()).record(duration.as_secs_f64());
+    #[cfg(not(feature = "metrics"))]
+    let _ = (SINK_WRITE_DURATION, sink, duration);
+}
+
+pub fn task_error(kind: &'static str) {
+    #[cfg(feature = "metrics")]
+    ::metrics::counter!(TASK_ERRORS, "kind" => kind).increment(1);
+    #[cfg(not(feature = "metrics"))]
+    let _ = (TASK_ERRORS, kind);
+}
+
+/// Await the RPC call, recording its latency
+pub async fn rpc_call<F: IntoFuture>(method: &'static str, call: F) -> F::Output {
+    let started = Instant::now();
+    let output = call.await;
+
+    #[cfg(feature = "metrics")]
+    ::metrics::histogram!(RPC_CALL_DURATION, "method" => method).record(started.elapsed().as_secs_f64());
+    #[cfg(not(feature = "metrics"))]
+    let _ = (RPC_CALL_DURATION, method, started);
+
+    output
+}
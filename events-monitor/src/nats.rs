@@ -1,21 +1,50 @@
 use async_nats::{jetstream, jetstream::Context, Client};
 use jetstream::object_store::ObjectStore;
+use monitor_common::error::MonitorError;
 
+use crate::config::{NatsCfg, NatsRouteCfg};
 use crate::types::EventPayload;
 
 use std::io::Cursor;
 use std::time::Duration;
 use async_nats::jetstream::object_store::Object;
+use monitor_common::nats::{PublishTarget, PublisherCfg};
 use tracing::{error, info};
 
+pub use monitor_common::nats::NatsHealth;
+
 #[derive(Clone)]
 pub struct Nats {
     pub client: Client,
     pub js: Context,
     pub object_store: ObjectStore,
+    pub routes: Vec<NatsRoute>,
+}
+
+/// Events route, resolved from its config entry
+#[derive(Clone)]
+pub struct NatsRoute {
+    pub cfg: NatsRouteCfg,
+    pub target: RouteTarget,
+}
+
+#[derive(Clone)]
+pub enum RouteTarget {
+    Subject(String),
+    Bucket(ObjectStore),
 }
 
-pub async fn connect(url: &str, bucket: &str) -> anyhow::Result<Nats> {
+impl NatsRoute {
+    /// Whether the event matches all the route matchers (addresses compared case-insensitively)
+    pub fn matches(&self, contract_address: &str, contract_name: &str, event_name: &str) -> bool {
+        self.cfg.contract_address.as_deref().is_none_or(|address| address.eq_ignore_ascii_case(contract_address))
+            && self.cfg.contract_name.as_deref().is_none_or(|name| name == contract_name)
+            && self.cfg.event_name.as_deref().is_none_or(|name| name == event_name)
+    }
+}
+
+pub async fn connect(cfg: &NatsCfg) -> monitor_common::error::Result<Nats> {
+    let url = cfg.url.as_str();
     // Create NATS Client with NATS connection, connect to NATS
     let client = loop {
         match async_nats::connect(url).await {
@@ -30,6 +59,18 @@ pub async fn connect(url: &str, bucket: &str) -> anyhow::Result<Nats> {
 
     let js = jetstream::new(client.clone());
 
+    let object_store = get_or_create_object_store(&js, &cfg.object_store_bucket).await
+        .map_err(|e| MonitorError::Nats(format!("{:#}", e)))?;
+
+    let mut routes = Vec::new();
+    for route_cfg in cfg.routes.iter().flatten() {
+        routes.push(resolve_route(&js, route_cfg).await.map_err(|e| MonitorError::Nats(format!("{:#}", e)))?);
+    }
+
+    Ok(Nats { client, js, object_store, routes })
+}
+
+async fn get_or_create_object_store(js: &Context, bucket: &str) -> anyhow::Result<ObjectStore> {
     // ensure bucket exists; if already exists, get; otherwise, create
     let object_store = match js.get_object_store(bucket).await {
         Ok(store) => store,
@@ -44,11 +85,37 @@ pub async fn connect(url: &str, bucket: &str) -> anyhow::Result<Nats> {
     };
     info!(bucket, "NATS Object Store ready");
 
-    Ok(Nats { client, js, object_store })
+    Ok(object_store)
+}
+
+async fn resolve_route(js: &Context, cfg: &NatsRouteCfg) -> anyhow::Result<NatsRoute> {
+    if cfg.contract_address.is_none() && cfg.contract_name.is_none() && cfg.event_name.is_none() {
+        anyhow::bail!("NATS route requires a contract_address, contract_name or event_name matcher");
+    }
+
+    let target = match (&cfg.subject, &cfg.bucket) {
+        (Some(subject), None) => {
+            if let Some(stream) = &cfg.stream {
+                js.get_or_create_stream(jetstream::stream::Config {
+                    name: stream.clone(),
+                    subjects: vec![subject.clone()],
+                    ..Default::default()
+                })
+                .await?;
+                info!("[NATS] Stream '{}' ready for subject '{}'", stream, subject);
+            }
+            RouteTarget::Subject(subject.clone())
+        }
+        (None, Some(bucket)) => RouteTarget::Bucket(get_or_create_object_store(js, bucket).await?),
+        _ => anyhow::bail!("NATS route requires either a subject or a bucket"),
+    };
+
+    Ok(NatsRoute { cfg: cfg.clone(), target })
 }
 
+/// Publish the event to the target of the first matching route, otherwise to the default bucket
 pub async fn publish_event(
-    object_store: &ObjectStore,
+    nats: &Nats,
     payload: &EventPayload,
 ) -> anyhow::Result<()> {
     let key = format!(
@@ -62,11 +129,88 @@ pub async fn publish_event(
         payload.transaction_sender,
         payload.transaction_receiver,
         payload.event_name,
-        payload.event_signature,
+        payload.event_signature.as_deref().unwrap_or("anonymous"),
     );
 
     let bytes = serde_json::to_vec(&serde_json::to_value(payload)?)?;
+
+    let route = nats.routes.iter()
+        .find(|route| route.matches(&payload.contract_address, &payload.contract_name, &payload.event_name));
+    let object_store = match route.map(|route| &route.target) {
+        Some(RouteTarget::Subject(subject)) => {
+            // Wait for the stream acknowledgement, so unpersisted events fail the processing
+            nats.js.publish(subject.clone(), bytes.into()).await?.await?;
+            return Ok(());
+        }
+        Some(RouteTarget::Bucket(object_store)) => object_store,
+        None => &nats.object_store,
+    };
+
     let mut cursor = Cursor::new(bytes);
     let _obj = object_store.put(key.as_str(), &mut cursor).await?;
     Ok(())
 }
+
+/// Best-effort publishing of the events to NATS, see `monitor_common::nats::NatsPublisher`
+pub type NatsPublisher = monitor_common::nats::NatsPublisher<Nats>;
+
+impl PublishTarget for Nats {
+    type Payload = EventPayload;
+
+    const PAYLOADS: &'static str = "events";
+
+    async fn publish(&self, payload: &EventPayload) -> anyhow::Result<()> {
+        publish_event(self, payload).await
+    }
+
+    fn client(&self) -> &Client {
+        &self.client
+    }
+}
+
+/// Buffering and timeouts of the NATS publisher
+pub fn publisher_cfg(cfg: &NatsCfg) -> PublisherCfg {
+    PublisherCfg {
+        buffer_capacity: cfg.buffer_capacity,
+        retry_interval_secs: cfg.retry_interval_secs,
+        publish_timeout_secs: cfg.publish_timeout_secs,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn route(contract_address: Option<&str>, contract_name: Option<&str>, event_name: Option<&str>) -> NatsRoute {
+        NatsRoute {
+            cfg: NatsRouteCfg {
+                contract_address: contract_address.map(str::to_string),
+                contract_name: contract_name.map(str::to_string),
+                event_name: event_name.map(str::to_string),
+                subject: Some("events.test".to_string()),
+                stream: None,
+                bucket: None,
+            },
+            target: RouteTarget::Subject("events.test".to_string()),
+        }
+    }
+
+    #[test]
+    fn test_route_matches() {
+        let address = "0xA0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48";
+
+        let by_address = route(Some("0xa0b86991c6218b36c1d19d4a2e9eb0ce3606eb48"), None, None);
+        assert!(by_address.matches(address, "USDC", "Transfer"));
+        assert!(!by_address.matches("0x0000000000000000000000000000000000000001", "USDC", "Transfer"));
+
+        let by_event = route(None, None, Some("Transfer"));
+        assert!(by_event.matches(address, "USDC", "Transfer"));
+        assert!(!by_event.matches(address, "USDC", "Approval"));
+
+        // All set matchers must match
+        let by_contract_event = route(None, Some("USDC"), Some("Approval"));
+        assert!(by_contract_event.matches(address, "USDC", "Approval"));
+        assert!(!by_contract_event.matches(address, "USDC", "Transfer"));
+        assert!(!by_contract_event.matches(address, "WETH", "Approval"));
+    }
+}
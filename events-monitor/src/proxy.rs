@@ -0,0 +1,221 @@
+//! Proxy contracts implementations detection via EIP-1967 storage slots
+
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+use abi_fetcher::blockscout::BlockscoutClient;
+use alloy::primitives::{b256, Address, Bytes, B256, U256};
+use alloy::providers::Provider;
+use alloy::rpc::types::TransactionRequest;
+use anyhow::anyhow;
+use tokio::sync::RwLock;
+use tracing::{info, debug, warn};
+
+use crate::abi::ContractAbi;
+use crate::config::{ContractWithImplementation, ProxyDetectionCfg};
+use crate::event_decoder::EventDecoder;
+use crate::metrics;
+
+/// `bytes32(uint256(keccak256("eip1967.proxy.implementation")) - 1)`
+pub const IMPLEMENTATION_SLOT: B256 = b256!("360894a13ba1a3210667c828492db98dca3e2076cc3735a920a3ca505d382bbc");
+/// `bytes32(uint256(keccak256("eip1967.proxy.beacon")) - 1)`
+pub const BEACON_SLOT: B256 = b256!("a3f0ad74e5423aebfd80d3ef4346578335a9a72aeaee59ff6cb3582b35133d50");
+/// `bytes32(uint256(keccak256("eip1967.proxy.admin")) - 1)`
+pub const ADMIN_SLOT: B256 = b256!("b53127684a568b3173ae13b9f8a6016e243e63b6e8ee1178d6a717850b5d6103");
+
+/// `implementation()` selector of the beacon contract
+const BEACON_IMPLEMENTATION_SELECTOR: [u8; 4] = [0x5c, 0x60, 0xda, 0x1b];
+
+/// EIP-1967 proxy storage slots values, `None` for empty slots
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct ProxySlots {
+    pub implementation: Option<Address>,
+    pub beacon: Option<Address>,
+    pub admin: Option<Address>,
+}
+
+/// Implementation of the proxy resolved on-chain, with its ABI and event decoder
+pub struct ResolvedImplementation {
+    pub contract: ContractAbi,
+    pub decoder: EventDecoder,
+}
+
+/// Resolves current implementations of the configured proxy contracts, overriding the configured
+/// implementation ABIs. ABIs of implementations missing in the config are fetched from Blockscout.
+pub struct ProxyResolver {
+    proxies: BTreeMap<Address, ContractAbi>,
+    configured_implementations: BTreeMap<Address, ContractWithImplementation>,
+    blockscout: BlockscoutClient,
+    indexed_preimages: Vec<String>,
+    implementations: RwLock<BTreeMap<Address, Arc<ResolvedImplementation>>>,
+}
+
+impl ProxyResolver {
+    /// Resolver for the contracts by address (proxy ABI, or the configured implementation ABI),
+    /// with the configured implementations ABIs preferred over the fetched ones
+    pub fn new(
+        config: &ProxyDetectionCfg,
+        proxies: BTreeMap<Address, ContractAbi>,
+        configured_implementations: &[ContractWithImplementation],
+        indexed_preimages: Vec<String>,
+    ) -> anyhow::Result<Self> {
+        let mut implementations_by_address = BTreeMap::new();
+        for implementation in configured_implementations.iter().filter(|c| c.parent_contract_address.is_some()) {
+            implementations_by_address.insert(implementation.address.parse::<Address>()?, implementation.clone());
+        }
+
+        let blockscout = BlockscoutClient::new(
+            &config.blockscout_server,
+            config.blockscout_api_path.as_deref().unwrap_or("/api/v2"),
+            config.request_timeout_secs.unwrap_or(30),
+            config.max_retries.unwrap_or(3),
+            config.auth_user.clone(),
+            config.auth_password.clone(),
+        );
+
+        Ok(Self {
+            proxies,
+            configured_implementations: implementations_by_address,
+            blockscout,
+            indexed_preimages,
+            implementations: RwLock::new(BTreeMap::new()),
+        })
+    }
+
+    /// Resolved implementation of the proxy, if detected
+    pub async fn implementation(&self, proxy: &Address) -> Option<Arc<ResolvedImplementation>> {
+        self.implementations.read().await.get(proxy).cloned()
+    }
+
+    /// Detect implementations of all proxies, re-resolving ABIs of the changed ones.
+    /// Failed proxies keep the current implementation and are retried on the next refresh.
+    pub async fn refresh<P: Provider>(&self, provider: &P) {
+        for (proxy, contract) in &self.proxies {
+            if let Err(e) = self.refresh_proxy(provider, *proxy, contract).await {
+                warn!(
+                    "Failed to resolve implementation of proxy {} ({}): {:?}",
+                    contract.get_effective_contract_name(), proxy, e
+                );
+            }
+        }
+    }
+
+    async fn refresh_proxy<P: Provider>(&self, provider: &P, proxy: Address, contract: &ContractAbi) -> anyhow::Result<()> {
+        let slots = read_proxy_slots(provider, proxy).await?;
+        let Some(implementation) = resolve_implementation(provider, &slots).await? else {
+            debug!("No EIP-1967 implementation of contract {} ({})", contract.get_effective_contract_name(), proxy);
+            return Ok(());
+        };
+
+        let current = self.implementation(&proxy).await.map(|resolved| resolved.contract.address);
+        if current == Some(implementation) {
+            return Ok(());
+        }
+
+        let contract_name = contract.get_effective_contract_name();
+        match current {
+            Some(previous) => info!(
+                "Proxy {} ({}) upgraded: implementation {} -> {} (beacon: {:?}, admin: {:?})",
+                contract_name, proxy, previous, implementation, slots.beacon, slots.admin
+            ),
+            None => info!(
+                "Proxy {} ({}) implementation detected: {} (beacon: {:?}, admin: {:?})",
+                contract_name, proxy, implementation, slots.beacon, slots.admin
+            ),
+        }
+
+        let implementation_contract = self.load_implementation(implementation, contract).await?;
+        let decoder = EventDecoder::new(Arc::new(implementation_contract.abi.clone()))?
+            .with_indexed_preimages(&self.indexed_preimages);
+
+        info!("Decoding events of proxy {} with ABI of implementation {} ({})", contract_name, implementation_contract.name, implementation);
+        self.implementations.write().await.insert(proxy, Arc::new(ResolvedImplementation {
+            contract: implementation_contract,
+            decoder,
+        }));
+
+        Ok(())
+    }
+
+    async fn load_implementation(&self, implementation: Address, proxy: &ContractAbi) -> anyhow::Result<ContractAbi> {
+        if let Some(configured) = self.configured_implementations.get(&implementation) {
+            debug!("Using configured ABI file {} of implementation {}", configured.abi_path, implementation);
+            return ContractAbi::from_contract_with_implementation(configured);
+        }
+
+        let details = self.blockscout.fetch_contract_details(&implementation.to_string()).await?;
+        let is_verified = details.is_verified.unwrap_or(false);
+        let abi = details.abi
+            .filter(|abi| is_verified && abi.is_array())
+            .ok_or_else(|| anyhow!("Implementation {} isn't verified on Blockscout, its ABI is unavailable", implementation))?;
+        let name = details.name
+            .unwrap_or_else(|| format!("{}Implementation", proxy.get_effective_contract_name()));
+
+        ContractAbi::from_detected_implementation(&name, implementation, proxy, &serde_json::to_vec(&abi)?)
+    }
+}
+
+/// Read the EIP-1967 implementation, beacon and admin slots of the contract
+pub async fn read_proxy_slots<P: Provider>(provider: &P, proxy: Address) -> anyhow::Result<ProxySlots> {
+    Ok(ProxySlots {
+        implementation: read_address_slot(provider, proxy, IMPLEMENTATION_SLOT).await?,
+        beacon: read_address_slot(provider, proxy, BEACON_SLOT).await?,
+        admin: read_address_slot(provider, proxy, ADMIN_SLOT).await?,
+    })
+}
+
+/// Current implementation: the implementation slot, otherwise the implementation of the beacon
+pub async fn resolve_implementation<P: Provider>(provider: &P, slots: &ProxySlots) -> anyhow::Result<Option<Address>> {
+    if slots.implementation.is_some() {
+        return Ok(slots.implementation);
+    }
+    let Some(beacon) = slots.beacon else { return Ok(None); };
+
+    let call = TransactionRequest::default()
+        .to(beacon)
+        .input(Bytes::from_static(&BEACON_IMPLEMENTATION_SELECTOR).into());
+    let output = metrics::rpc_call("eth_call", provider.call(call)).await?;
+
+    Ok(address_from_word(&output))
+}
+
+async fn read_address_slot<P: Provider>(provider: &P, address: Address, slot: B256) -> anyhow::Result<Option<Address>> {
+    let value = metrics::rpc_call("eth_getStorageAt", provider.get_storage_at(address, U256::from_be_bytes(slot.0))).await?;
+    Ok(address_from_word(&value.to_be_bytes::<32>()))
+}
+
+/// Address stored in the low 20 bytes of a 32 bytes word, `None` if zero or not a word
+fn address_from_word(word: &[u8]) -> Option<Address> {
+    let word: [u8; 32] = word.try_into().ok()?;
+    let address = Address::from_word(B256::from(word));
+    (!address.is_zero()).then_some(address)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy::primitives::{address, keccak256};
+
+    fn eip1967_slot(name: &str) -> B256 {
+        B256::from((U256::from_be_bytes(keccak256(name).0) - U256::from(1)).to_be_bytes::<32>())
+    }
+
+    #[test]
+    fn test_eip1967_slots() {
+        assert_eq!(IMPLEMENTATION_SLOT, eip1967_slot("eip1967.proxy.implementation"));
+        assert_eq!(BEACON_SLOT, eip1967_slot("eip1967.proxy.beacon"));
+        assert_eq!(ADMIN_SLOT, eip1967_slot("eip1967.proxy.admin"));
+        assert_eq!(BEACON_IMPLEMENTATION_SELECTOR, keccak256("implementation()")[..4]);
+    }
+
+    #[test]
+    fn test_address_from_word() {
+        let implementation = address!("cA11bde05977b3631167028862bE2a173976CA11");
+        let mut word = [0u8; 32];
+        word[12..].copy_from_slice(implementation.as_slice());
+
+        assert_eq!(address_from_word(&word), Some(implementation));
+        assert_eq!(address_from_word(&[0u8; 32]), None);
+        assert_eq!(address_from_word(&word[1..]), None);
+    }
+}
@@ -0,0 +1,36 @@
+use std::collections::HashMap;
+
+use alloy::primitives::B256;
+use alloy::providers::Provider;
+use alloy::rpc::client::BatchRequest;
+use alloy::rpc::types::Transaction;
+
+/// Fetch transactions by their hashes, coalescing up to `batch_size` 'eth_getTransactionByHash' requests
+/// into a single JSON-RPC batch call. Transactions not found by the node are absent in the returned map.
+pub async fn get_transactions_by_hash<P: Provider>(
+    provider: &P,
+    tx_hashes: &[B256],
+    batch_size: usize,
+) -> anyhow::Result<HashMap<B256, Transaction>> {
+    let mut transactions = HashMap::with_capacity(tx_hashes.len());
+
+    for chunk in tx_hashes.chunks(batch_size.max(1)) {
+        let mut batch = BatchRequest::new(provider.client());
+
+        let mut waiters = Vec::with_capacity(chunk.len());
+        for tx_hash in chunk {
+            let waiter = batch.add_call::<_, Option<Transaction>>("eth_getTransactionByHash", &(*tx_hash,))?;
+            waiters.push((*tx_hash, waiter));
+        }
+
+        batch.send().await?;
+
+        for (tx_hash, waiter) in waiters {
+            if let Some(tx) = waiter.await? {
+                transactions.insert(tx_hash, tx);
+            }
+        }
+    }
+
+    Ok(transactions)
+}
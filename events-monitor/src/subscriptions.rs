@@ -1,12 +1,13 @@
 use std::collections::BTreeMap;
 use futures_util::StreamExt;
-use tracing::{info, error, debug};
+use tracing::{info, error, debug, warn};
 
 use alloy::{
     providers::{Provider, ProviderBuilder, WsConnect},
-    transports::ws::WebSocketConfig,
-    rpc::types::{Filter, FilterBlockOption, BlockNumberOrTag, Log as RpcLog},
-    primitives::Address,
+    transports::{ws::WebSocketConfig, TransportError},
+    rpc::client::ClientBuilder,
+    rpc::types::{Filter, FilterBlockOption, BlockNumberOrTag, Log as RpcLog, Transaction as RpcTransaction},
+    primitives::{Address, B256},
     json_abi::JsonAbi,
 };
 use alloy::providers::fillers::{BlobGasFiller, ChainIdFiller, FillProvider, GasFiller, JoinFill, NonceFiller};
@@ -15,45 +16,84 @@ use alloy::consensus::Transaction;
 use alloy::network::TransactionResponse;
 
 use async_nats::jetstream::object_store::ObjectStore;
+use monitor_common::error::{MonitorError, Result};
+use rpc_limits::{LogsChunkSize, RpcRateLimitLayer, RpcRateLimiter, is_logs_range_limit_error};
 
-use crate::{abi::ContractAbi, db::{self, DatabaseClients}, nats, nats::Nats};
+use crate::{abi::{self, ContractAbi}, db::{self, DatabaseClients}, nats::{Nats, NatsPublisher}, rpc_batch};
+use crate::sink::{self, OutputSink};
+use crate::metrics;
 use crate::config::AppCfg as AppConfig;
 use crate::event_decoder::EventDecoder;
-use crate::types::EventPayload;
+use crate::types::{EventPayload, FailedEventPayload};
+use crate::watermark::{LogPosition, LogWatermark};
+use crate::proxy::ProxyResolver;
+use monitor_common::labels::AddressLabels;
 
 use std::ops::{Range, RangeFrom};
 use std::str::FromStr;
-use std::sync::Arc;
+use std::sync::{Arc, Weak};
+use std::sync::atomic::{AtomicUsize, Ordering};
 use anyhow::anyhow;
+use tokio::sync::{mpsc, Mutex, RwLock};
 use tokio::task::JoinHandle;
 
+/// Watermark stream name of tasks without a configured name
+const DEFAULT_STREAM_NAME: &str = "events-monitor";
+
 type RPCProvider = FillProvider<JoinFill<Identity, JoinFill<GasFiller, JoinFill<BlobGasFiller, JoinFill<NonceFiller, ChainIdFiller>>>>, RootProvider>;
 
 pub struct EventProcessor {
     addr_abi_map: BTreeMap<Address, ContractAbi>,
-    db_clients: DatabaseClients,
-    nats_store: Option<Nats>,
+    addr_decoder_map: BTreeMap<Address, EventDecoder>,
+    db_clients: Arc<DatabaseClients>,
+    sinks: Vec<Box<dyn OutputSink>>,
     config: AppConfig,
-    ws_rpc_provider: RPCProvider,
+    ws_rpc_provider: RwLock<RPCProvider>,
+    ws_rpc_endpoints: Vec<String>,
+    ws_rpc_endpoint_index: AtomicUsize,
     http_rpc_provider: RPCProvider,
     chain_id: u64,
     filter_senders: Option<Vec<Address>>,
     filter_receivers: Option<Vec<Address>>,
+    event_topics: Option<Vec<B256>>,
+    watermark: Arc<LogWatermark>,
+    stream_name: String,
+    proxy_resolver: Option<ProxyResolver>,
+    nats_publisher: Option<NatsPublisher>, // Best-effort NATS publishing, shared with the NATS sink
+    address_labels: AddressLabels,
+    rpc_rate_limiter: Option<Arc<RpcRateLimiter>>, // Shared by the HTTP provider requests and the WS requests
 }
 
 impl EventProcessor {
-    pub async fn new(config: &AppConfig, db_clients: DatabaseClients, nats_store: Option<Nats>) -> anyhow::Result<Self> {
+    pub async fn new(config: &AppConfig, db_clients: DatabaseClients, nats_store: Option<Nats>) -> Result<Self> {
         // Get all contracts including implementations
         let all_contracts = config.get_all_contracts();
 
         let mut contracts = Vec::with_capacity(all_contracts.len());
         for c in all_contracts.iter() {
-            let abi = ContractAbi::from_contract_with_implementation(c)?;
+            let abi = ContractAbi::from_contract_with_implementation(c)
+                .map_err(|e| MonitorError::Config(format!("{:#}", e)))?;
             contracts.push(abi);
         }
 
         info!("Loaded contracts: {} (including implementations)", contracts.len());
 
+        // Check events topic0 collisions across all loaded ABIs, not only within a contract ABI,
+        // as a topic0 decoded differently by contracts makes the stored events inconsistent
+        let strict_event_collisions = config.indexing.strict_event_collisions.unwrap_or(false);
+        let event_collisions: Vec<abi::SelectorCollision> =
+            abi::find_selector_collisions(contracts.iter().map(|contract| (contract.name.as_str(), &contract.abi)))
+                .into_iter()
+                .filter(|collision| collision.kind == "event")
+                .collect();
+        for collision in &event_collisions {
+            warn!("{}", collision);
+        }
+        if strict_event_collisions && !event_collisions.is_empty() {
+            let collisions: Vec<String> = event_collisions.iter().map(ToString::to_string).collect();
+            return Err(MonitorError::Config(format!("{} event topic0 collisions: {}", collisions.len(), collisions.join("; "))));
+        }
+
         // Index contracts by address for a quick lookup
         // For proxy contracts, we need to map the proxy address to implementation ABI
         let mut addr_abi_map: BTreeMap<Address, ContractAbi> = BTreeMap::new();
@@ -78,29 +118,39 @@ impl EventProcessor {
             }
         }
 
-        let ws_config = WebSocketConfig::default()
-            .read_buffer_size(256 * 1024)
-            .write_buffer_size(256 * 1024)
-            .max_message_size(Some(1024 * 1024 * 1024))
-            .max_frame_size(Some(256 * 1024 * 1024))
-            .accept_unmasked_frames(false);
-        let ws = WsConnect::new(&config.chain.ws_rpc_url).with_config(ws_config);
-        let http_rpc = reqwest::Url::from_str(&config.chain.http_rpc_url)?;
-        let (ws_rpc_provider, http_rpc_provider) = build_providers(ws, http_rpc).await?;
+        // Build event decoders once per contract, as decoders are immutable and shared by all logs
+        let indexed_preimages = config.indexing.indexed_preimages.clone().unwrap_or_default();
+        let mut addr_decoder_map: BTreeMap<Address, EventDecoder> = BTreeMap::new();
+        for (address, contract) in addr_abi_map.iter() {
+            let decoder = EventDecoder::new(Arc::new(contract.abi.clone()))
+                .map_err(|e| MonitorError::Config(format!("Failed to build event decoder of contract {} ({}): {}", contract.get_effective_contract_name(), address, e)))?;
+            addr_decoder_map.insert(*address, decoder.with_indexed_preimages(&indexed_preimages));
+        }
+        info!(
+            "Event decoders built: {} for {} contract addresses (constructed in total: {})",
+            addr_decoder_map.len(), addr_abi_map.len(), EventDecoder::constructed_count()
+        );
+
+        let ws_rpc_endpoints = config.chain.ws_rpc_endpoints();
+        let http_rpc_endpoints = config.chain.http_rpc_endpoints();
+        let rpc_rate_limiter = config.chain.rpc_rate_limit
+            .map(|requests_per_sec| RpcRateLimiter::new(requests_per_sec, config.chain.rpc_rate_limit_burst));
+        let (ws_rpc_provider, http_rpc_provider, ws_rpc_endpoint_index) =
+            build_providers(&ws_rpc_endpoints, &http_rpc_endpoints, rpc_rate_limiter.as_ref()).await?;
 
         let chain_id = http_rpc_provider.get_chain_id().await?;
         if chain_id != config.chain.chain_id {
-            anyhow::bail!("Chain ID mismatch: expected {}, got {}", config.chain.chain_id, chain_id);
+            return Err(MonitorError::Config(format!("Chain ID mismatch: expected {}, got {}", config.chain.chain_id, chain_id)));
         }
         info!("Chain ID: {}", chain_id);
 
         // Parse sender filtering addresses from configuration
         let filter_senders = if let Some(senders) = &config.indexing.filter_senders {
             if !senders.is_empty() {
-                let parsed: Result<Vec<Address>, _> = senders.iter()
+                let parsed: std::result::Result<Vec<Address>, _> = senders.iter()
                     .map(|s| Address::from_str(s))
                     .collect();
-                let parsed = parsed?;
+                let parsed = parsed.map_err(|e| MonitorError::Config(format!("Invalid filter sender address: {}", e)))?;
                 info!("Filter senders configured: {} addresses", parsed.len());
                 Some(parsed)
             } else {
@@ -113,10 +163,10 @@ impl EventProcessor {
         // Parse receiver filtering addresses from configuration
         let filter_receivers = if let Some(receivers) = &config.indexing.filter_receivers {
             if !receivers.is_empty() {
-                let parsed: Result<Vec<Address>, _> = receivers.iter()
+                let parsed: std::result::Result<Vec<Address>, _> = receivers.iter()
                     .map(|s| Address::from_str(s))
                     .collect();
-                let parsed = parsed?;
+                let parsed = parsed.map_err(|e| MonitorError::Config(format!("Invalid filter receiver address: {}", e)))?;
                 info!("Filter receivers configured: {} addresses", parsed.len());
                 Some(parsed)
             } else {
@@ -126,29 +176,133 @@ impl EventProcessor {
             None
         };
 
+        // Resolve allowlisted events to topic0 hashes from the loaded ABIs
+        let event_topics = match &config.indexing.filter_events {
+            Some(events) if !events.is_empty() => {
+                let topics = abi::resolve_event_topics(addr_abi_map.values().map(|c| &c.abi), events)
+                    .map_err(|e| MonitorError::Config(format!("{:#}", e)))?;
+                info!("Filter events configured: {} events resolved to {} topics", events.len(), topics.len());
+                Some(topics)
+            }
+            _ => None,
+        };
+
+        // Detect current implementations of the proxy contracts on-chain, overriding the configured ones
+        let proxy_resolver = match &config.proxy_detection {
+            Some(detection) if detection.enabled.unwrap_or(false) => {
+                let mut proxies = BTreeMap::new();
+                for contract in &config.contracts {
+                    let address = Address::from_str(&contract.address)
+                        .map_err(|e| MonitorError::Config(format!("Invalid contract address {}: {}", contract.address, e)))?;
+                    if let Some(contract_abi) = addr_abi_map.get(&address) {
+                        proxies.insert(address, contract_abi.clone());
+                    }
+                }
+                info!("Proxy implementations detection enabled for {} contracts", proxies.len());
+
+                let resolver = ProxyResolver::new(detection, proxies, &all_contracts, indexed_preimages.clone())
+                    .map_err(|e| MonitorError::Config(format!("{:#}", e)))?;
+                resolver.refresh(&http_rpc_provider).await;
+                Some(resolver)
+            }
+            _ => None,
+        };
+
+        // Resume from the processed logs watermark of the task logs stream, if persisted
+        let stream_name = config.name.clone().unwrap_or_else(|| DEFAULT_STREAM_NAME.to_string());
+        let persisted_watermark = if config.indexing.resume_from_watermark.unwrap_or(true) {
+            db_clients.get_watermark(chain_id, &stream_name).await
+                .map_err(|e| MonitorError::Database(format!("{:#}", e)))?
+        } else {
+            None
+        };
+        match persisted_watermark {
+            Some((block_number, log_index)) => info!(
+                "Resuming logs stream '{}' from processed logs watermark: block {}, log index {}",
+                stream_name, block_number, log_index
+            ),
+            None => info!("No processed logs watermark to resume logs stream '{}' from", stream_name),
+        }
+        let watermark = Arc::new(LogWatermark::new(persisted_watermark));
+
+        let address_labels = match &config.labels {
+            Some(labels) => AddressLabels::load(labels).map_err(|e| MonitorError::Config(format!("{:#}", e)))?,
+            None => AddressLabels::default(),
+        };
+
+        let db_clients = Arc::new(db_clients);
+        let nats_publisher = nats_store.map(|nats| NatsPublisher::new(nats, crate::nats::publisher_cfg(&config.nats)));
+        let sinks = sink::build_sinks(config, Arc::clone(&db_clients), nats_publisher.as_ref())
+            .map_err(|e| MonitorError::Config(format!("{:#}", e)))?;
+
         Ok(Self {
             addr_abi_map,
+            addr_decoder_map,
             db_clients,
-            nats_store,
+            sinks,
             config: config.clone(),
-            ws_rpc_provider,
+            ws_rpc_provider: RwLock::new(ws_rpc_provider),
+            ws_rpc_endpoints,
+            ws_rpc_endpoint_index: AtomicUsize::new(ws_rpc_endpoint_index),
             http_rpc_provider,
             chain_id,
             filter_senders,
             filter_receivers,
+            event_topics,
+            watermark,
+            stream_name,
+            proxy_resolver,
+            nats_publisher,
+            address_labels,
+            rpc_rate_limiter,
         })
     }
 
-    pub async fn run(self) -> anyhow::Result<()> {
+    /// Processed logs watermark, shared with the task manager for the API
+    pub fn watermark(&self) -> Arc<LogWatermark> {
+        Arc::clone(&self.watermark)
+    }
+
+    /// NATS publisher if NATS is enabled, shared with the task manager for the API health
+    pub fn nats_publisher(&self) -> Option<NatsPublisher> {
+        self.nats_publisher.clone()
+    }
+
+    pub async fn run(self) -> Result<()> {
         let self_arc = Arc::new(self);
 
-        let from_block = self_arc.config.indexing.from_block.unwrap_or(0u64);
+        let mut from_block = self_arc.config.indexing.from_block.unwrap_or(0u64);
         let to_block = self_arc.config.indexing.to_block;
 
+        // Logs before the watermark are already committed, the watermark block is re-fetched
+        // as it may be committed partially, and its committed logs are skipped
+        if let Some((watermark_block, _)) = self_arc.watermark.position() && watermark_block > from_block {
+            info!("Starting logs processing from watermark block {} instead of block {}", watermark_block, from_block);
+            from_block = watermark_block;
+        }
+
+        // Commit the advanced watermark periodically, instead of after each log
+        let flush_interval_ms = self_arc.config.indexing.watermark_flush_interval_ms.unwrap_or(1000).max(1);
+        let watermark_flusher = tokio::spawn(EventProcessor::flush_watermark_periodically(
+            Arc::downgrade(&self_arc),
+            tokio::time::Duration::from_millis(flush_interval_ms),
+        ));
+
+        // Re-detect proxy implementations periodically, to follow upgrades mid-run
+        let proxy_detection_interval_secs = self_arc.config.proxy_detection.as_ref()
+            .and_then(|detection| detection.interval_secs)
+            .unwrap_or(300);
+        let proxy_refresher = (self_arc.proxy_resolver.is_some() && proxy_detection_interval_secs > 0).then(|| {
+            tokio::spawn(EventProcessor::refresh_proxies_periodically(
+                Arc::downgrade(&self_arc),
+                tokio::time::Duration::from_secs(proxy_detection_interval_secs),
+            ))
+        });
+
         // build a single filter for all addresses
         let addresses: Vec<Address> = self_arc.addr_abi_map.iter().map(|(addr, _c)| *addr).collect();
 
-        let mut handles: Vec<JoinHandle<anyhow::Result<()>>> = Vec::new();
+        let mut handles: Vec<JoinHandle<Result<()>>> = Vec::new();
 
         // Task 1: Process historical logs, if enabled
         let process_historical_logs = self_arc.config.indexing.historical_logs_processing.is_some_and(|process_logs| process_logs > 0);
@@ -156,6 +310,9 @@ impl EventProcessor {
             let processor_for_history = Arc::clone(&self_arc);
             let addresses_for_history = addresses.clone();
 
+            // The new logs may be processed concurrently, hold the watermark until historical logs are accepted
+            self_arc.watermark.hold_from_block(Some(from_block));
+
             let historical_task = tokio::spawn(async move {
                 info!("Starting historical logs processing task");
 
@@ -171,12 +328,12 @@ impl EventProcessor {
                     let end_block = if let Some(to) = to_block {
                         to
                     } else {
-                        // Fetch the latest block number if to_block is not specified
-                        match processor_for_history.http_rpc_provider.get_block_number().await {
+                        // Fetch the latest confirmed block number if to_block is not specified
+                        match processor_for_history.confirmed_block_number().await {
                             Ok(latest) => latest,
                             Err(e) => {
                                 error!("Failed to get latest block number: {:?}", e);
-                                return Err(anyhow!("Failed to get latest block number: {:?}", e));
+                                return Err(e);
                             }
                         }
                     };
@@ -192,12 +349,12 @@ impl EventProcessor {
 
                     while current_block < end_block {
                         let chunk_end = std::cmp::min(current_block + chunk_size, end_block);
+                        processor_for_history.watermark.hold_from_block(Some(current_block));
 
                         info!("Starting watch_logs for block range {}..{}", current_block, chunk_end);
 
                         // Create filter for this chunk
-                        let chunk_filter = Filter::new()
-                            .address(addresses_for_history.clone())
+                        let chunk_filter = processor_for_history.logs_filter(addresses_for_history.clone())
                             .select(BlockRange(current_block..chunk_end));
 
                         // Start watching logs using HTTP polling for this chunk
@@ -221,6 +378,11 @@ impl EventProcessor {
                                 }
                             }
 
+                            if is_processed(&log, processor_for_history.watermark.position()) {
+                                debug!("Skipping already processed historical watch_logs log from contract: {}", log.address());
+                                continue;
+                            }
+
                             debug!("Received historical watch_logs log from contract: {}", log.address());
                             chunk_logs_count += 1;
 
@@ -251,12 +413,12 @@ impl EventProcessor {
                     let end_block = if let Some(to) = to_block {
                         to
                     } else {
-                        // Fetch the latest block number if to_block is not specified
-                        match processor_for_history.http_rpc_provider.get_block_number().await {
+                        // Fetch the latest confirmed block number if to_block is not specified
+                        match processor_for_history.confirmed_block_number().await {
                             Ok(latest) => latest,
                             Err(e) => {
                                 error!("Failed to get latest block number: {:?}", e);
-                                return Err(anyhow!("Failed to get latest block number: {:?}", e));
+                                return Err(e);
                             }
                         }
                     };
@@ -266,48 +428,42 @@ impl EventProcessor {
                         from_block, end_block, chunk_size
                     );
 
-                    // Process logs in chunks
+                    // Fetch logs using the configured protocol
+                    let over_ws = match logs_sync_protocol {
+                        Some(ref protocol) if protocol.to_lowercase() == "http" => false,
+                        Some(ref protocol) if protocol.to_lowercase() == "ws" => true,
+                        _ => {
+                            debug!("Invalid or missing log sync protocol, using 'http' as fallback");
+                            false
+                        }
+                    };
+
+                    // Process logs in chunks, narrowed on the endpoint range limit rejections
+                    let mut logs_chunk_size = LogsChunkSize::new(chunk_size);
                     let mut current_block = from_block;
                     let mut total_logs_processed = 0usize;
 
                     while current_block < end_block {
-                        let chunk_end = std::cmp::min(current_block + chunk_size, end_block);
-
-                        info!("Fetching logs for block range {}..{}", current_block, chunk_end);
-
-                        // Create filter for this chunk
-                        let chunk_filter = Filter::new()
-                            .address(addresses_for_history.clone())
-                            .select(BlockRange(current_block..chunk_end));
+                        processor_for_history.watermark.hold_from_block(Some(current_block));
 
-                        // Fetch logs using the configured protocol
-                        let logs = match logs_sync_protocol {
-                            Some(ref protocol) if protocol.to_lowercase() == "http" => {
-                                processor_for_history.http_rpc_provider.get_logs(&chunk_filter).await?
-                            },
-                            Some(ref protocol) if protocol.to_lowercase() == "ws" => {
-                                processor_for_history.ws_rpc_provider.get_logs(&chunk_filter).await?
-                            },
-                            _ => {
-                                debug!("Invalid or missing log sync protocol, using 'http' as fallback");
-                                processor_for_history.http_rpc_provider.get_logs(&chunk_filter).await?
-                            }
-                        };
+                        // The end block is exclusive
+                        let (logs, chunk_end) = processor_for_history
+                            .get_logs_chunk(&addresses_for_history, current_block, end_block - 1, &mut logs_chunk_size, over_ws)
+                            .await?;
 
-                        debug!("Received {} logs from block range {}..{}", logs.len(), current_block, chunk_end);
+                        debug!("Received {} logs from block range {}..={}", logs.len(), current_block, chunk_end);
                         total_logs_processed += logs.len();
 
                         // Process each log in the chunk
-                        for log in logs.iter() {
-                            debug!("Received historical log from contract: {}", log.address());
-                            if let Err(e) = processor_for_history.handle_log(log.clone()).await {
+                        for result in processor_for_history.handle_logs(logs).await {
+                            if let Err(e) = result {
                                 error!("Failed to handle historical log: {:?}", e);
                                 eprintln!("Historical log error: {:?}", e);
                             }
                         }
 
                         // Move to the next chunk
-                        current_block = chunk_end;
+                        current_block = chunk_end + 1;
 
                         // Optional: Add a small delay between chunks to avoid overwhelming the RPC
                         if current_block < end_block {
@@ -315,12 +471,18 @@ impl EventProcessor {
                         }
                     }
 
+                    if logs_chunk_size.min_size() < chunk_size {
+                        info!("Historical logs chunk was narrowed down to {} blocks by the endpoint limits", logs_chunk_size.min_size());
+                    }
+
                     info!(
                         "Historical logs processing completed: processed {} total logs from {} to {}",
                         total_logs_processed, from_block, end_block
                     );
                 }
 
+                processor_for_history.watermark.hold_from_block(None);
+
                 Ok(())
             });
             handles.push(historical_task);
@@ -333,10 +495,19 @@ impl EventProcessor {
             let addresses_for_subscription = addresses.clone();
 
             // Determine subscription protocol (default to WS for backward compatibility)
-            let subscription_protocol = processor_for_subscription.config.indexing.new_logs_subscription_protocol
+            let mut subscription_protocol = processor_for_subscription.config.indexing.new_logs_subscription_protocol
                 .clone()
                 .unwrap_or_else(|| "http".to_string()); // fetch new logs via HTTP RPC by default
 
+            // Streams deliver logs at the chain tip, so the confirmed logs are polled instead
+            if self_arc.confirmation_lag() > 0 && subscription_protocol.to_lowercase() != "http" {
+                warn!(
+                    "Confirmation lag of {} blocks is set, polling new logs via HTTP instead of '{}' subscription",
+                    self_arc.confirmation_lag(), subscription_protocol
+                );
+                subscription_protocol = "http".to_string();
+            }
+
             if subscription_protocol.to_lowercase() == "http" {
                 // HTTP polling mode
                 let polling_interval_secs = processor_for_subscription.config.indexing.http_polling_interval_secs.unwrap_or(5);
@@ -344,14 +515,19 @@ impl EventProcessor {
                 let subscription_task = tokio::spawn(async move {
                     info!("Starting HTTP polling task for new logs (interval: {}s)", polling_interval_secs);
 
-                    // Start watching from the current block or configured block
-                    let start_block = match processor_for_subscription.http_rpc_provider.get_block_number().await {
+                    // Start watching from the current confirmed block or configured block
+                    let start_block = match processor_for_subscription.confirmed_block_number().await {
                         Ok(block) => block,
                         Err(e) => {
                             error!("Failed to get latest block number (as starting block): {:?}", e);
                             BlockNumberOrTag::Latest.as_number().unwrap_or(0)
                         }
                     };
+                    // Poll the blocks since the watermark block (inclusive), missed while stopped
+                    let start_block = match processor_for_subscription.watermark.position() {
+                        Some((watermark_block, _)) if watermark_block <= start_block => watermark_block.saturating_sub(1),
+                        _ => start_block,
+                    };
                     let mut current_block = start_block;
                     info!("Starting HTTP polling from block {}", current_block);
 
@@ -360,8 +536,8 @@ impl EventProcessor {
                     loop {
                         interval.tick().await;
 
-                        // Get the latest block number
-                        let latest_block = match processor_for_subscription.http_rpc_provider.get_block_number().await {
+                        // Get the latest confirmed block number
+                        let latest_block = match processor_for_subscription.confirmed_block_number().await {
                             Ok(block) => block,
                             Err(e) => {
                                 error!("Failed to get latest block number: {:?}", e);
@@ -374,17 +550,15 @@ impl EventProcessor {
                             debug!("Polling for logs from block {} to {}", current_block + 1, latest_block);
 
                             // Create a filter for the new blocks
-                            let poll_filter = Filter::new()
-                                .address(addresses_for_subscription.clone())
+                            let poll_filter = processor_for_subscription.logs_filter(addresses_for_subscription.clone())
                                 .select(BlockRange((current_block + 1)..latest_block + 1));
 
-                            match processor_for_subscription.http_rpc_provider.get_logs(&poll_filter).await {
+                            match metrics::rpc_call("eth_getLogs", processor_for_subscription.http_rpc_provider.get_logs(&poll_filter)).await {
                                 Ok(logs) => {
                                     debug!("Received {} new logs via HTTP polling", logs.len());
 
-                                    for log in logs {
-                                        debug!("Received polling log from contract: {}", log.address());
-                                        if let Err(e) = processor_for_subscription.handle_log(log).await {
+                                    for result in processor_for_subscription.handle_logs(logs).await {
+                                        if let Err(e) = result {
                                             error!("Failed to handle polling log: {:?}", e);
                                             eprintln!("Polling log error: {:?}", e);
                                         }
@@ -409,8 +583,7 @@ impl EventProcessor {
                     info!("Starting HTTP watch_logs task for new logs");
 
                     // Create filter for new logs (from latest block)
-                    let watch_filter = Filter::new()
-                        .address(addresses_for_subscription.clone())
+                    let watch_filter = processor_for_subscription.logs_filter(addresses_for_subscription.clone())
                         .from_block(BlockNumberOrTag::Latest);
 
                     // Start watching logs using HTTP polling
@@ -423,13 +596,12 @@ impl EventProcessor {
 
                     info!("Started watching logs via HTTP polling");
 
-                    // Process logs as they arrive
+                    // Enqueue logs as they arrive, persisted by the log workers
+                    let log_queue = EventProcessor::spawn_log_workers(&processor_for_subscription);
                     while let Some(log) = log_stream.next().await {
                         debug!("Received watch_logs log from contract: {}", log.address());
-                        if let Err(e) = processor_for_subscription.handle_log(log).await {
-                            error!("Failed to handle watch_logs log: {:?}", e);
-                            eprintln!("Watch logs error: {:?}", e);
-                        }
+                        metrics::logs_received(1);
+                        enqueue_log(&log_queue, &processor_for_subscription.watermark, log).await?;
                     }
 
                     info!("Watch logs task completed");
@@ -440,71 +612,541 @@ impl EventProcessor {
                 // WebSocket subscription mode (original initial implementation using WebSocket 'subscribe_logs' method)
 
                 // Create filter for new logs (from latest block)
-                let filter = Filter::new()
-                    .address(addresses_for_subscription.clone())
+                let filter = processor_for_subscription.logs_filter(addresses_for_subscription.clone())
                     .from_block(BlockNumberOrTag::Latest);
 
                 let subscription_task = tokio::spawn(async move {
                     info!("Starting WebSocket subscription task");
 
-                    let provider = processor_for_subscription.ws_rpc_provider.clone();
-                    let sub = provider.subscribe_logs(&filter).await?;
-                    info!("Subscribed to logs for {} contracts", addresses_for_subscription.len());
+                    // Logs are enqueued by the subscription and persisted by the log workers,
+                    // so a slow database doesn't stall the subscription stream consumption
+                    let log_queue = EventProcessor::spawn_log_workers(&processor_for_subscription);
+
+                    let reconnect_delay_secs = processor_for_subscription.config.chain.ws_reconnect_delay_secs.unwrap_or(5);
+                    let max_reconnect_attempts = processor_for_subscription.config.chain.ws_max_reconnect_attempts;
+                    let mut reconnect_attempts = 0u64;
+
+                    // Position (block number, log index) of the last received log, to resume from after reconnection,
+                    // initially the watermark, so logs emitted while stopped are caught up on the first subscription.
+                    // Received logs are processed by the log workers, whether a log is processed is tracked by the watermark.
+                    let mut last_received: Option<LogPosition> = processor_for_subscription.watermark.position();
+                    let mut subscribed_from_block: Option<u64> = None;
+
+                    loop {
+                        let provider = processor_for_subscription.ws_rpc_provider().await;
+
+                        match provider.subscribe_logs(&filter).await {
+                            Ok(sub) => {
+                                info!("Subscribed to logs for {} contracts", addresses_for_subscription.len());
+
+                                // Catch up logs emitted while disconnected, and the failed logs, before consuming the new subscription
+                                let resume_block = last_received.map(|(block, _)| block).or(subscribed_from_block)
+                                    .map(|block| processor_for_subscription.watermark.first_failed_block().map_or(block, |failed| failed.min(block)));
+                                if let Some(resume_block) = resume_block {
+                                    match processor_for_subscription.catch_up_logs(&addresses_for_subscription, resume_block).await {
+                                        Ok(position) => last_received = position.max(last_received),
+                                        Err(e) => error!("Failed to catch up logs from block {}: {:?}", resume_block, e),
+                                    }
+                                } else {
+                                    subscribed_from_block = processor_for_subscription.http_rpc_provider.get_block_number().await.ok();
+                                }
+
+                                let mut sub_stream = sub.into_stream();
+                                while let Some(log) = sub_stream.next().await {
+                                    reconnect_attempts = 0;
+
+                                    if log_position(&log).is_some_and(|position| processor_for_subscription.watermark.is_handled(position)) {
+                                        debug!("Skipping already processed subscription log from contract: {}", log.address());
+                                        continue;
+                                    }
+
+                                    debug!("Received subscription log from contract: {}", log.address());
+                                    metrics::logs_received(1);
+                                    let position = log_position(&log);
+                                    enqueue_log(&log_queue, &processor_for_subscription.watermark, log).await?;
+                                    if position.is_some() {
+                                        last_received = position;
+                                    }
+                                }
+
+                                warn!("WebSocket logs subscription stream terminated");
+                            }
+                            Err(e) => {
+                                error!("Failed to subscribe to logs via WebSocket: {:?}", e);
+                            }
+                        }
 
-                    let mut sub_stream = sub.into_stream();
-                    while let Some(log) = sub_stream.next().await {
-                        debug!("Received subscription log from contract: {}", log.address());
-                        if let Err(e) = processor_for_subscription.handle_log(log).await {
-                            error!("Failed to handle subscription log: {:?}", e);
-                            eprintln!("Subscription log error: {:?}", e);
+                        reconnect_attempts += 1;
+                        if let Some(max_attempts) = max_reconnect_attempts && reconnect_attempts > max_attempts {
+                            return Err(MonitorError::Rpc(format!("WebSocket subscription failed after {} reconnection attempts", max_attempts)));
                         }
-                    }
 
-                    info!("Subscription task completed");
-                    Ok(())
+                        tokio::time::sleep(tokio::time::Duration::from_secs(reconnect_delay_secs)).await;
+
+                        if let Err(e) = processor_for_subscription.reconnect_ws().await {
+                            error!("Failed to reconnect to WebSocket RPC: {:?}", e);
+                        }
+                    }
                 });
                 handles.push(subscription_task);
             }
         }
 
         // Wait for all tasks to complete
+        let mut result = Ok(());
         for handle in handles {
             match handle.await {
                 Ok(Ok(())) => info!("Task completed successfully"),
                 Ok(Err(e)) => {
                     error!("Task failed with error: {:?}", e);
-                    return Err(e);
+                    metrics::task_error(e.kind());
+                    result = Err(e);
+                    break;
                 }
                 Err(join_err) => {
                     error!("Task panicked: {:?}", join_err);
-                    return Err(anyhow!("Task panicked: {:?}", join_err));
+                    let e = MonitorError::Internal(format!("Task panicked: {:?}", join_err));
+                    metrics::task_error(e.kind());
+                    result = Err(e);
+                    break;
+                }
+            }
+        }
+
+        watermark_flusher.abort();
+        if let Some(proxy_refresher) = proxy_refresher {
+            proxy_refresher.abort();
+        }
+        self_arc.flush_watermark().await;
+
+        result
+    }
+
+    /// Process the logs of the `from_block..=to_block` range once, for one-shot backfill jobs.
+    /// Fails if the range isn't covered in full, or if any log failed to persist.
+    pub async fn backfill(self, from_block: u64, to_block: u64) -> Result<()> {
+        let latest_block = metrics::rpc_call("eth_blockNumber", self.http_rpc_provider.get_block_number()).await?;
+        if to_block > latest_block {
+            return Err(MonitorError::Config(format!("Backfill end block {} is ahead of the chain tip {}", to_block, latest_block)));
+        }
+
+        let max_chunk_size = self.config.indexing.logs_chunk_size.unwrap_or(1000).max(1);
+        let mut chunk_size = LogsChunkSize::new(max_chunk_size);
+        let addresses: Vec<Address> = self.addr_abi_map.keys().copied().collect();
+
+        info!("Backfilling logs from block {} to {} with chunk size of {} blocks", from_block, to_block, max_chunk_size);
+
+        let mut total_logs = 0usize;
+        let mut failed_logs = 0usize;
+        let mut first_error = None;
+        let mut current_block = from_block;
+
+        // Contiguous chunks, each one starting right after the previous one
+        loop {
+            let (logs, chunk_end) = self.get_logs_chunk(&addresses, current_block, to_block, &mut chunk_size, false).await
+                .map_err(|e| MonitorError::Rpc(format!("Failed to fetch logs of blocks from {}: {:?}", current_block, e)))?;
+
+            // Pending logs or logs out of the requested range mean the range isn't covered as requested
+            if let Some(log) = logs.iter().find(|log| !log.block_number.is_some_and(|block| (current_block..=chunk_end).contains(&block))) {
+                return Err(MonitorError::Rpc(format!(
+                    "Logs of block range {}..={} include a log of block {:?}",
+                    current_block, chunk_end, log.block_number
+                )));
+            }
+
+            debug!("Received {} logs from block range {}..={}", logs.len(), current_block, chunk_end);
+            total_logs += logs.len();
+
+            for result in self.handle_logs(logs).await {
+                if let Err(e) = result {
+                    failed_logs += 1;
+                    error!("Failed to handle backfilled log: {:?}", e);
+                    first_error.get_or_insert(e);
                 }
             }
+
+            if chunk_end == to_block {
+                break;
+            }
+            current_block = chunk_end + 1;
+        }
+
+        for sink in &self.sinks {
+            sink.flush().await
+                .map_err(|e| MonitorError::Sink(format!("Failed to flush {} sink: {}", sink.name(), e)))?;
+        }
+
+        // Fails with the first log error, so the failure kind is kept
+        if let Some(first_error) = first_error {
+            error!(
+                "Backfill of blocks {} to {} failed to persist {} of {} logs",
+                from_block, to_block, failed_logs, total_logs
+            );
+            return Err(first_error);
         }
 
+        info!("Backfill completed: processed {} logs from block {} to {}", total_logs, from_block, to_block);
         Ok(())
     }
 
-    async fn handle_log(&self, log: RpcLog) -> anyhow::Result<()> {
-        let addr = log.address();
-        debug!("Received log from contract: {}", addr);
+    /// Commit the watermark at the interval, until the processor is dropped
+    async fn flush_watermark_periodically(processor: Weak<Self>, interval: tokio::time::Duration) {
+        let mut interval = tokio::time::interval(interval);
+        loop {
+            interval.tick().await;
+            let Some(processor) = processor.upgrade() else { break; };
+            processor.flush_watermark().await;
+        }
+    }
 
-        // Retrieve tx sender using transaction hash
-        let tx_sender = if let Some(h) = log.transaction_hash {
-            match self.http_rpc_provider.get_transaction_by_hash(h).await? {
-                Some(tx) => Some(tx.from()),
-                None => None,
+    /// Re-detect proxy implementations at the interval, until the processor is dropped
+    async fn refresh_proxies_periodically(processor: Weak<Self>, interval: tokio::time::Duration) {
+        let mut interval = tokio::time::interval(interval);
+        // Implementations are detected on startup, skip the immediate first tick
+        interval.tick().await;
+        loop {
+            interval.tick().await;
+            let Some(processor) = processor.upgrade() else { break; };
+            if let Some(proxy_resolver) = &processor.proxy_resolver {
+                proxy_resolver.refresh(&processor.http_rpc_provider).await;
             }
-        } else { None };
+        }
+    }
+
+    /// Commit the advanced watermark to the database
+    async fn flush_watermark(&self) {
+        let Some(position) = self.watermark.unpersisted() else { return; };
+
+        match self.db_clients.update_watermark(self.chain_id, &self.stream_name, position).await {
+            Ok(()) => self.watermark.mark_persisted(position),
+            Err(e) => warn!("Failed to commit processed logs watermark of '{}': {:?}", self.stream_name, e),
+        }
+    }
+
+    async fn ws_rpc_provider(&self) -> RPCProvider {
+        self.ws_rpc_provider.read().await.clone()
+    }
+
+    /// Switch the WS provider to the next reachable endpoint after the current one
+    async fn reconnect_ws(&self) -> Result<()> {
+        let next_index = (self.ws_rpc_endpoint_index.load(Ordering::Relaxed) + 1) % self.ws_rpc_endpoints.len();
+        let (ws_rpc_provider, ws_rpc_endpoint_index) = connect_ws_provider(&self.ws_rpc_endpoints, next_index).await?;
+
+        *self.ws_rpc_provider.write().await = ws_rpc_provider;
+        self.ws_rpc_endpoint_index.store(ws_rpc_endpoint_index, Ordering::Relaxed);
+
+        Ok(())
+    }
+
+    /// Spawn the workers persisting logs from a bounded queue, returns the queue sender.
+    /// Workers exit when the sender is dropped and the queue is drained.
+    fn spawn_log_workers(processor: &Arc<Self>) -> mpsc::Sender<RpcLog> {
+        let queue_capacity = processor.config.indexing.log_queue_capacity.unwrap_or(1000).max(1);
+        let workers = processor.config.indexing.log_workers.unwrap_or(4).max(1);
+
+        let (log_sender, log_receiver) = mpsc::channel::<RpcLog>(queue_capacity);
+        let log_receiver = Arc::new(Mutex::new(log_receiver));
+
+        for worker_id in 0..workers {
+            let processor = Arc::clone(processor);
+            let log_receiver = Arc::clone(&log_receiver);
+
+            tokio::spawn(async move {
+                loop {
+                    let log = log_receiver.lock().await.recv().await;
+                    let Some(log) = log else { break; };
+
+                    if let Err(e) = processor.handle_log(log).await {
+                        error!("Log worker {} failed to handle log: {:?}", worker_id, e);
+                        eprintln!("Log worker error: {:?}", e);
+                    }
+                }
+                debug!("Log worker {} stopped", worker_id);
+            });
+        }
+
+        info!("Started {} log workers with queue capacity {}", workers, queue_capacity);
+        log_sender
+    }
+
+    /// Fetch logs from `from_block` up to the latest confirmed block via HTTP RPC and process the ones
+    /// not handled yet: neither processed, nor queued for the log workers (failed logs are processed again),
+    /// returns the position of the last fetched log
+    async fn catch_up_logs(
+        &self,
+        addresses: &[Address],
+        from_block: u64,
+    ) -> Result<Option<LogPosition>> {
+        let mut chunk_size = LogsChunkSize::new(self.config.indexing.logs_chunk_size.unwrap_or(1000));
+        let latest_block = self.confirmed_block_number().await?;
+
+        info!("Catching up logs from block {} to {}", from_block, latest_block);
+
+        let mut position = None;
+        let mut current_block = from_block;
+
+        while current_block <= latest_block {
+            let (logs, chunk_end) = self.get_logs_chunk(addresses, current_block, latest_block, &mut chunk_size, false).await?;
+            let logs: Vec<RpcLog> = logs
+                .into_iter()
+                .filter(|log| !log_position(log).is_some_and(|position| self.watermark.is_handled(position)))
+                .collect();
+            let log_positions: Vec<_> = logs.iter()
+                .map(log_position)
+                .collect();
+
+            for (result, log_position) in self.handle_logs(logs).await.into_iter().zip(log_positions) {
+                if let Err(e) = result {
+                    error!("Failed to handle catch-up log: {:?}", e);
+                }
+                if log_position.is_some() {
+                    position = log_position;
+                }
+            }
+
+            current_block = chunk_end + 1;
+        }
+
+        Ok(position)
+    }
+
+    /// Fetch the logs of a chunk of blocks from `from_block`, up to `to_block`, via HTTP RPC or WS RPC
+    /// if `over_ws`, returns the logs with the chunk end block. A chunk rejected by the endpoint as a
+    /// too wide range or over its results limit is narrowed and fetched again, see `LogsChunkSize`.
+    async fn get_logs_chunk(
+        &self,
+        addresses: &[Address],
+        from_block: u64,
+        to_block: u64,
+        chunk_size: &mut LogsChunkSize,
+        over_ws: bool,
+    ) -> std::result::Result<(Vec<RpcLog>, u64), TransportError> {
+        loop {
+            let chunk_end = chunk_size.chunk_end(from_block, to_block);
+            let chunk_filter = self.logs_filter(addresses.to_vec())
+                .from_block(from_block)
+                .to_block(chunk_end);
+
+            let result = if over_ws {
+                // WS requests share the rate limit of the HTTP provider
+                if let Some(rpc_rate_limiter) = &self.rpc_rate_limiter {
+                    rpc_rate_limiter.acquire(1).await;
+                }
+                let ws_rpc_provider = self.ws_rpc_provider().await;
+                metrics::rpc_call("eth_getLogs", ws_rpc_provider.get_logs(&chunk_filter)).await
+            } else {
+                metrics::rpc_call("eth_getLogs", self.http_rpc_provider.get_logs(&chunk_filter)).await
+            };
+
+            match result {
+                Ok(logs) => {
+                    chunk_size.accepted();
+                    return Ok((logs, chunk_end));
+                }
+                Err(e) if is_logs_range_limit_error(&e.to_string()) && chunk_size.rejected() => {
+                    warn!(
+                        "Logs of block range {}..={} rejected ({}), retrying with chunk size of {} blocks",
+                        from_block, chunk_end, e, chunk_size.size()
+                    );
+                }
+                Err(e) => return Err(e),
+            }
+        }
+    }
+
+    /// Number of blocks a log's block must be behind the chain tip to be processed
+    fn confirmation_lag(&self) -> u64 {
+        self.config.indexing.confirmation_lag.unwrap_or(0)
+    }
+
+    /// Latest block at least `confirmation_lag` blocks deep, the chain tip when no lag is set
+    async fn confirmed_block_number(&self) -> Result<u64> {
+        let latest_block = metrics::rpc_call("eth_blockNumber", self.http_rpc_provider.get_block_number()).await?;
+        Ok(latest_block.saturating_sub(self.confirmation_lag()))
+    }
+
+    /// Logs filter for the contract addresses, narrowed to the allowlisted events topic0 if configured
+    fn logs_filter(&self, addresses: Vec<Address>) -> Filter {
+        let filter = Filter::new().address(addresses);
+        match &self.event_topics {
+            Some(topics) => filter.event_signature(topics.clone()),
+            None => filter,
+        }
+    }
+
+    /// Persist an undecodable log with the decode error to the dead-letter table
+    async fn record_failed_event(&self, log: &RpcLog, contract: &ContractAbi, decode_error: &anyhow::Error) -> Result<()> {
+        let payload = FailedEventPayload {
+            contract_name: Some(contract.get_effective_contract_name().to_string()),
+            contract_address: log.address().to_string(),
+            chain_id: self.chain_id.to_string(),
+            block_number: log.block_number.unwrap_or_default().to_string(),
+            block_hash: log.block_hash
+                .map(|bh| format!("0x{}", hex::encode(bh.0.as_slice())))
+                .unwrap_or("0x".to_string()),
+            transaction_hash: log.transaction_hash
+                .map(|th| format!("0x{}", hex::encode(th.0.as_slice())))
+                .unwrap_or("0x".to_string()),
+            log_index: log.log_index.unwrap_or_default().to_string(),
+            log_hash: compute_log_hash(log),
+            topics: log.inner.data.topics().iter()
+                .map(|topic| format!("0x{}", hex::encode(topic.0.as_slice())))
+                .collect(),
+            data: format!("0x{}", hex::encode(log.inner.data.data.as_ref())),
+            error: decode_error.to_string(),
+        };
+
+        warn!("Recording undecodable log {} of contract {} to dead-letter table: {}", payload.log_hash, payload.contract_address, payload.error);
+
+        self.db_clients.insert_failed_event(&payload).await
+            .map_err(|e| MonitorError::Database(format!("Failed to record undecodable log to dead-letter table: {:#}", e)))
+    }
+
+    /// Delete the stored event of a log removed from the canonical chain by a reorganization,
+    /// so the log can be stored again if it's included in the new chain.
+    /// Events already published to NATS or the other sinks aren't retracted.
+    async fn delete_removed_log(&self, log: &RpcLog) -> Result<()> {
+        let (Some(block_hash), Some(log_index)) = (log.block_hash, log.log_index) else {
+            debug!("Skipping removed log without block hash or log index");
+            return Ok(());
+        };
+        let block_hash = format!("0x{}", hex::encode(block_hash.0.as_slice()));
 
-        // Retrieve tx receiver using transaction hash
-        let tx_receiver = if let Some(h) = log.transaction_hash {
-            match self.http_rpc_provider.get_transaction_by_hash(h).await? {
-                Some(tx) => if let Some(addr) = tx.to() { Some(addr) } else { None },
-                None => None,
+        let deleted = self.db_clients
+            .delete_removed_event(&self.chain_id.to_string(), &block_hash, &log_index.to_string())
+            .await
+            .map_err(|e| MonitorError::Database(format!("Failed to delete event of removed log: {:#}", e)))?;
+
+        info!(
+            "Log {} of block {} removed from the canonical chain, deleted {} stored events",
+            log_index, block_hash, deleted
+        );
+        Ok(())
+    }
+
+    /// Whether the log event passes the topic0 allowlist, if configured
+    fn is_allowlisted(&self, log: &RpcLog) -> bool {
+        let Some(topics) = &self.event_topics else { return true; };
+
+        let topic0 = log.inner.data.topics().first();
+        if !topic0.is_some_and(|topic0| topics.contains(topic0)) {
+            debug!("Filtering out log: event topic0 {:?} not in filter list", topic0);
+            return false;
+        }
+        true
+    }
+
+    /// Handle logs with their transactions prefetched in batched RPC calls,
+    /// returns the handling result of each log in the logs order
+    async fn handle_logs(&self, logs: Vec<RpcLog>) -> Vec<Result<()>> {
+        metrics::logs_received(logs.len());
+
+        // Logs at or before the watermark are already committed and skipped
+        let watermark_position = self.watermark.position();
+        let is_new = |log: &RpcLog| !is_processed(log, watermark_position);
+        for position in logs.iter().filter(|log| is_new(log)).filter_map(log_position) {
+            self.watermark.begin(position);
+        }
+
+        let tx_hashes: Vec<B256> = logs.iter()
+            .filter(|log| is_new(log) && self.is_allowlisted(log))
+            .filter_map(|log| log.transaction_hash)
+            .collect::<std::collections::BTreeSet<_>>()
+            .into_iter()
+            .collect();
+
+        let batch_size = self.config.indexing.rpc_batch_size.unwrap_or(100);
+        let transactions = if tx_hashes.is_empty() {
+            std::collections::HashMap::new()
+        } else {
+            let batch_call = rpc_batch::get_transactions_by_hash(&self.http_rpc_provider, &tx_hashes, batch_size);
+            match metrics::rpc_call("eth_getTransactionByHash_batch", batch_call).await {
+                Ok(transactions) => transactions,
+                Err(e) => {
+                    warn!("Batched transactions fetching failed, falling back to per-log requests: {:?}", e);
+                    let mut results = Vec::with_capacity(logs.len());
+                    for log in logs {
+                        if !is_new(&log) {
+                            results.push(Ok(()));
+                            continue;
+                        }
+                        results.push(self.handle_log(log).await);
+                    }
+                    return results;
+                }
             }
+        };
+
+        let mut results = Vec::with_capacity(logs.len());
+        for log in logs {
+            if !is_new(&log) {
+                results.push(Ok(()));
+                continue;
+            }
+            let position = log_position(&log);
+            let result = if self.is_allowlisted(&log) {
+                let tx = log.transaction_hash.and_then(|h| transactions.get(&h));
+                self.process_log(log, tx).await
+            } else {
+                Ok(())
+            };
+            if let Some(position) = position {
+                self.settle_log(position, &result);
+            }
+            results.push(result);
+        }
+        results
+    }
+
+    /// Handle the log, tracked by the watermark, see `settle_log`
+    async fn handle_log(&self, log: RpcLog) -> Result<()> {
+        let position = log_position(&log);
+        if let Some(position) = position {
+            self.watermark.begin(position);
+        }
+
+        let result = self.fetch_tx_and_process_log(log).await;
+
+        if let Some(position) = position {
+            self.settle_log(position, &result);
+        }
+        result
+    }
+
+    /// Pass the handled log by the watermark if it's settled, see `settle_log`
+    fn settle_log(&self, position: LogPosition, result: &Result<()>) {
+        settle_log(&self.watermark, position, result, self.config.indexing.record_failed_events.unwrap_or(false));
+    }
+
+    async fn fetch_tx_and_process_log(&self, log: RpcLog) -> Result<()> {
+        if !self.is_allowlisted(&log) {
+            return Ok(());
+        }
+
+        // Retrieve transaction using transaction hash
+        let tx = if let Some(h) = log.transaction_hash {
+            // Undecodable responses are RPC failures, not undecodable logs
+            metrics::rpc_call("eth_getTransactionByHash", self.http_rpc_provider.get_transaction_by_hash(h)).await
+                .map_err(|e| MonitorError::Rpc(format!("Failed to get transaction {}: {}", h, e)))?
         } else { None };
 
+        self.process_log(log, tx.as_ref()).await
+    }
+
+    async fn process_log(&self, log: RpcLog, tx: Option<&RpcTransaction>) -> Result<()> {
+        let addr = log.address();
+        debug!("Received log from contract: {}", addr);
+
+        // Logs of blocks orphaned by a reorganization are redelivered by subscriptions as removed,
+        // ahead of the logs of the new chain
+        if log.removed {
+            return self.delete_removed_log(&log).await;
+        }
+
+        // Retrieve tx sender and receiver from the transaction
+        let tx_sender = tx.map(|tx| tx.from());
+        let tx_receiver = tx.and_then(|tx| tx.to());
+
         // Apply sender filtering if configured
         if let Some(filter_senders) = &self.filter_senders {
             if !filter_senders.is_empty() {
@@ -551,12 +1193,35 @@ impl EventProcessor {
             })
             .unwrap_or("".to_string());
 
-        let Some(contract) = self.addr_abi_map.get(&addr) else { return Ok(()); };
+        // Detected proxy implementation takes precedence over the configured one
+        let resolved_implementation = match &self.proxy_resolver {
+            Some(proxy_resolver) => proxy_resolver.implementation(&addr).await,
+            None => None,
+        };
+        let (contract, decoder) = match &resolved_implementation {
+            Some(resolved) => (&resolved.contract, &resolved.decoder),
+            None => {
+                let Some(contract) = self.addr_abi_map.get(&addr) else { return Ok(()); };
+                let Some(decoder) = self.addr_decoder_map.get(&addr) else { return Ok(()); };
+                (contract, decoder)
+            }
+        };
 
-        let abi = Arc::new(contract.abi.clone());
-        let decoder = EventDecoder::new(abi)?;
-        let parsed_event = decoder.decode_log(&log.inner)?;
-        let parsed_event_value = parsed_event.to_json()?;
+        let decoded = decoder.decode_log_with_anonymous_events(&log.inner, &contract.anonymous_events)
+            .and_then(|parsed_event| {
+                let parsed_event_value = parsed_event.to_json()?;
+                Ok((parsed_event, parsed_event_value))
+            });
+        let (parsed_event, parsed_event_value) = match decoded {
+            Ok(decoded) => decoded,
+            Err(e) => {
+                metrics::decode_error(contract.get_effective_contract_name());
+                if self.config.indexing.record_failed_events.unwrap_or(false) {
+                    self.record_failed_event(&log, contract, &e).await?;
+                }
+                return Err(MonitorError::Decode(format!("{:#}", e)));
+            }
+        };
 
         // Determine contract and implementation details
         let (contract_name, contract_address, implementation_name, implementation_address) =
@@ -600,19 +1265,11 @@ impl EventProcessor {
         let tx_index = log.transaction_index.unwrap_or_default().to_string();
         let log_index = log.log_index.unwrap_or_default().to_string();
         let event_name = parsed_event.name.as_str();
+        // Anonymous events have no signature/hash and are persisted with a null signature
         let event_signature = parsed_event.signature
-            .map(|h| format!("0x{}", hex::encode(h.0.as_slice())))
-            .ok_or_else(|| {
-                error!("Missing event signature/hash in parsed event data: anonymous event");
-                anyhow!("Missing event signature/hash in parsed event data: anonymous event")
-            })
-            .unwrap_or("0x".to_string());
+            .map(|h| format!("0x{}", hex::encode(h.0.as_slice())));
 
-        // Compute unique log hash using the Log's `hash()` with SHA3-256 hasher
-        let mut hasher = Sha3_256StdHasher::default();
-        log.inner.hash(&mut hasher);
-        let log_hash_bytes = hasher.finalize_bytes();
-        let log_hash = format!("0x{}", hex::encode(log_hash_bytes));
+        let log_hash = compute_log_hash(&log);
 
         let payload = EventPayload {
             contract_name,
@@ -633,22 +1290,45 @@ impl EventProcessor {
             event_name: event_name.to_string(),
             event_signature,
             event_data: parsed_event_value,
+            contract_label: self.address_labels.get(&addr),
+            from_label: tx_sender.and_then(|sender| self.address_labels.get(&sender)),
+            to_label: tx_receiver.and_then(|receiver| self.address_labels.get(&receiver)),
         };
 
         debug!("Persisting event: {:?}", payload);
 
-        // Persist to databases (local PostgreSQL + AWS RDS if enabled)
-        self.db_clients.insert_event(&payload).await?;
+        // Persist to output sinks (local PostgreSQL + AWS RDS if enabled, NATS Object Store if enabled, configured sinks)
+        for sink in &self.sinks {
+            let started = std::time::Instant::now();
+            sink.write(&payload).await
+                .map_err(|e| MonitorError::Sink(format!("Failed to write event to {} sink: {}", sink.name(), e)))?;
+            metrics::sink_write(sink.name(), started.elapsed());
+        }
 
-        // Persist to NATS Object Store
-        if let Some(nats_store) = &self.nats_store {
-            nats::publish_event(&nats_store.object_store, &payload).await?;
-        };
+        metrics::event_decoded(&payload.contract_name, &payload.event_name);
 
         Ok(())
     }
 }
 
+/// Enqueue the log for the log workers, waiting for a free queue slot when the queue is full,
+/// so the subscription is slowed down instead of logs being dropped.
+/// The log is registered in the watermark on enqueueing, as workers complete logs out of order.
+async fn enqueue_log(log_queue: &mpsc::Sender<RpcLog>, watermark: &LogWatermark, log: RpcLog) -> Result<()> {
+    if let Some(position) = log_position(&log) {
+        watermark.begin(position);
+    }
+
+    if log_queue.capacity() == 0 {
+        warn!("Log queue is full ({} logs), applying backpressure to the logs subscription", log_queue.max_capacity());
+    }
+
+    log_queue.send(log).await.map_err(|_| MonitorError::Internal("Log workers stopped, log queue is closed".to_string()))?;
+    debug!("Log queue depth: {}", log_queue.max_capacity() - log_queue.capacity());
+
+    Ok(())
+}
+
 /// Range (from..to) block type conversion helpers
 pub struct BlockRange(pub Range<u64>);
 impl From<BlockRange> for FilterBlockOption {
@@ -675,6 +1355,14 @@ impl From<BlockRangeFrom> for FilterBlockOption {
 use sha3::{Digest, Sha3_256};
 use std::hash::{Hash, Hasher};
 
+/// Compute unique log hash using the Log's `hash()` with SHA3-256 hasher
+fn compute_log_hash(log: &RpcLog) -> String {
+    let mut hasher = Sha3_256StdHasher::default();
+    log.inner.hash(&mut hasher);
+    let log_hash_bytes = hasher.finalize_bytes();
+    format!("0x{}", hex::encode(log_hash_bytes))
+}
+
 /// Custom hasher adapter so we can use `Log::hash(&mut hasher)` and also get full 32-byte SHA3-256
 #[derive(Default)]
 struct Sha3_256StdHasher {
@@ -702,11 +1390,129 @@ impl Sha3_256StdHasher {
     }
 }
 
-/// Build HTTP and WS providers using Alloy
-pub async fn build_providers(ws_rpc_url: WsConnect, http_rpc_url: reqwest::Url) -> anyhow::Result<(RPCProvider, RPCProvider)> {
-    let ws_rpc_provider = ProviderBuilder::new().connect_ws(ws_rpc_url.clone()).await?;
-    let http_rpc_provider = ProviderBuilder::new().connect_http(http_rpc_url.clone());
-    info!("Connecting to RPC endpoints: ws: {:?}, http: {:?}", ws_rpc_url, http_rpc_url);
+/// Complete the handled log in the watermark if it's settled: processed (stored or filtered out),
+/// or undecodable and recorded to the dead-letter table (if `dead_letter` is enabled), as it fails
+/// the same way when processed again. Other failures, e.g. a failed sink write, keep the watermark
+/// before the log, so it's processed again after a restart.
+fn settle_log(watermark: &LogWatermark, position: LogPosition, result: &Result<()>, dead_letter: bool) {
+    let settled = match result {
+        Ok(()) => true,
+        Err(MonitorError::Decode(_)) => dead_letter,
+        Err(_) => false,
+    };
+
+    if settled {
+        watermark.complete(position);
+    } else {
+        watermark.fail(position);
+    }
+}
+
+/// Position (block number, log index) of the mined log
+fn log_position(log: &RpcLog) -> Option<LogPosition> {
+    log.block_number.zip(log.log_index)
+}
+
+/// Whether the log is at or before the `last_processed` (block number, log index) position
+fn is_processed(log: &RpcLog, last_processed: Option<LogPosition>) -> bool {
+    match (last_processed, log_position(log)) {
+        (Some(last_processed), Some(position)) => position <= last_processed,
+        _ => false,
+    }
+}
+
+fn ws_config() -> WebSocketConfig {
+    WebSocketConfig::default()
+        .read_buffer_size(256 * 1024)
+        .write_buffer_size(256 * 1024)
+        .max_message_size(Some(1024 * 1024 * 1024))
+        .max_frame_size(Some(256 * 1024 * 1024))
+        .accept_unmasked_frames(false)
+}
+
+/// Connect to the first reachable WS RPC endpoint, trying them in order (wrapping around) from `start_index`,
+/// returns the provider and the index of the connected endpoint
+pub async fn connect_ws_provider(ws_rpc_urls: &[String], start_index: usize) -> Result<(RPCProvider, usize)> {
+    for offset in 0..ws_rpc_urls.len() {
+        let index = (start_index + offset) % ws_rpc_urls.len();
+        let ws = WsConnect::new(&ws_rpc_urls[index]).with_config(ws_config());
+        match ProviderBuilder::new().connect_ws(ws).await {
+            Ok(provider) => {
+                info!("Connected to WS RPC endpoint: {}", ws_rpc_urls[index]);
+                return Ok((provider, index));
+            }
+            Err(e) => warn!("Failed to connect to WS RPC endpoint {}: {:?}", ws_rpc_urls[index], e),
+        }
+    }
+
+    Err(MonitorError::Rpc(format!("Failed to connect to any of {} WS RPC endpoints", ws_rpc_urls.len())))
+}
+
+/// Connect to the first reachable HTTP RPC endpoint, trying them in order,
+/// with the requests limited by the rate limiter, if any
+pub async fn connect_http_provider(http_rpc_urls: &[String], rpc_rate_limiter: Option<&Arc<RpcRateLimiter>>) -> Result<RPCProvider> {
+    for url in http_rpc_urls {
+        let http_rpc = reqwest::Url::from_str(url)
+            .map_err(|e| MonitorError::Config(format!("Invalid HTTP RPC endpoint {}: {}", url, e)))?;
+        let client = match rpc_rate_limiter {
+            Some(rpc_rate_limiter) => ClientBuilder::default()
+                .layer(RpcRateLimitLayer::new(Arc::clone(rpc_rate_limiter)))
+                .http(http_rpc),
+            None => ClientBuilder::default().http(http_rpc),
+        };
+        let provider = ProviderBuilder::new().connect_client(client);
+        match provider.get_block_number().await {
+            Ok(_) => {
+                info!("Connected to HTTP RPC endpoint: {}", url);
+                return Ok(provider);
+            }
+            Err(e) => warn!("Failed to connect to HTTP RPC endpoint {}: {:?}", url, e),
+        }
+    }
+
+    Err(MonitorError::Rpc(format!("Failed to connect to any of {} HTTP RPC endpoints", http_rpc_urls.len())))
+}
+
+/// Build WS and HTTP providers using Alloy, trying the endpoints in order,
+/// returns the providers and the index of the connected WS endpoint
+pub async fn build_providers(
+    ws_rpc_urls: &[String],
+    http_rpc_urls: &[String],
+    rpc_rate_limiter: Option<&Arc<RpcRateLimiter>>,
+) -> Result<(RPCProvider, RPCProvider, usize)> {
+    info!("Connecting to RPC endpoints: ws: {:?}, http: {:?}", ws_rpc_urls, http_rpc_urls);
+    let (ws_rpc_provider, ws_rpc_endpoint_index) = connect_ws_provider(ws_rpc_urls, 0).await?;
+    let http_rpc_provider = connect_http_provider(http_rpc_urls, rpc_rate_limiter).await?;
+
+    Ok((ws_rpc_provider, http_rpc_provider, ws_rpc_endpoint_index))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_failed_sink_write_holds_watermark() {
+        let watermark = LogWatermark::new(Some((1, 0)));
+        for position in [(2, 0), (2, 1), (3, 0)] {
+            watermark.begin(position);
+        }
 
-    Ok((ws_rpc_provider, http_rpc_provider))
+        settle_log(&watermark, (2, 0), &Ok(()), true);
+        settle_log(&watermark, (2, 1), &Err(MonitorError::Sink("Failed to write event to webhook sink".to_string())), true);
+        settle_log(&watermark, (3, 0), &Ok(()), true);
+        assert_eq!(watermark.position(), Some((2, 0)));
+    }
+
+    #[test]
+    fn test_decode_failure_passed_only_when_dead_lettered() {
+        let watermark = LogWatermark::new(None);
+        watermark.begin((1, 0));
+        settle_log(&watermark, (1, 0), &Err(MonitorError::Decode("unknown event".to_string())), false);
+        assert_eq!(watermark.position(), None);
+
+        watermark.begin((1, 0));
+        settle_log(&watermark, (1, 0), &Err(MonitorError::Decode("unknown event".to_string())), true);
+        assert_eq!(watermark.position(), Some((1, 0)));
+    }
 }
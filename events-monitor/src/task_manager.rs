@@ -5,10 +5,13 @@ use tokio::task::JoinHandle;
 use uuid::Uuid;
 use serde::{Serialize, Deserialize};
 use tracing::{info, error, warn};
+use monitor_common::error::{MonitorError, Result};
 
 use crate::subscriptions::EventProcessor;
 use crate::config::AppCfg;
+use crate::watermark::{LogWatermark, WatermarkInfo};
 use crate::{db, nats};
+use crate::nats::{NatsHealth, NatsPublisher};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TaskInfo {
@@ -17,6 +20,10 @@ pub struct TaskInfo {
     pub status: TaskStatus,
     pub created_at: chrono::DateTime<chrono::Utc>,
     pub updated_at: chrono::DateTime<chrono::Utc>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub watermark: Option<WatermarkInfo>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub nats: Option<NatsHealth>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -30,8 +37,22 @@ pub enum TaskStatus {
 
 pub struct Task {
     pub info: TaskInfo,
-    pub handle: JoinHandle<anyhow::Result<()>>,
+    pub handle: JoinHandle<Result<()>>,
     pub shutdown_sender: Option<oneshot::Sender<()>>,
+    pub watermark: Option<Arc<LogWatermark>>,
+    pub nats_publisher: Option<NatsPublisher>,
+}
+
+impl Task {
+    /// Task info with the current watermark and NATS health
+    fn live_info(&self) -> TaskInfo {
+        let mut info = self.info.clone();
+        if let Some(watermark) = &self.watermark {
+            info.watermark = watermark.info();
+        }
+        info.nats = self.nats_publisher.as_ref().map(NatsPublisher::health);
+        info
+    }
 }
 
 pub struct TaskManager {
@@ -61,6 +82,8 @@ impl TaskManager {
             status: TaskStatus::Starting,
             created_at: chrono::Utc::now(),
             updated_at: chrono::Utc::now(),
+            watermark: None,
+            nats: None,
         };
 
         // Create shutdown channel
@@ -91,6 +114,7 @@ impl TaskManager {
             let db_clients = match db::DatabaseClients::new(
                 &config.postgres.dsn,
                 &db_schema,
+                config.postgres.migrations_dir.as_deref(),
                 aws_rds_config
             ).await {
                 Ok(clients) => {
@@ -106,7 +130,7 @@ impl TaskManager {
                         task.info.status = TaskStatus::Failed(format!("Database connection failed: {}", e));
                         task.info.updated_at = chrono::Utc::now();
                     }
-                    return Err(e);
+                    return Err(MonitorError::Database(format!("{:#}", e)));
                 }
             };
 
@@ -118,7 +142,7 @@ impl TaskManager {
 
             // Initialize NATS if enabled
             let nats = if config.nats.nats_enabled.is_some_and(|enabled| enabled > 0) {
-                match nats::connect(&config.nats.url, &config.nats.object_store_bucket).await {
+                match nats::connect(&config.nats).await {
                     Ok(nats_client) => Some(nats_client),
                     Err(e) => {
                         warn!("Failed to connect to NATS for task {}: {:?}", task_id_clone, e);
@@ -151,6 +175,8 @@ impl TaskManager {
                 if let Some(task) = tasks.get_mut(&task_id_clone) {
                     task.info.status = TaskStatus::Running;
                     task.info.updated_at = chrono::Utc::now();
+                    task.watermark = Some(event_processor.watermark());
+                    task.nats_publisher = event_processor.nats_publisher();
                 }
             }
 
@@ -188,6 +214,8 @@ impl TaskManager {
             info: task_info,
             handle,
             shutdown_sender: Some(shutdown_sender),
+            watermark: None,
+            nats_publisher: None,
         };
 
         let mut tasks = self.tasks.write().await;
@@ -222,12 +250,12 @@ impl TaskManager {
 
     pub async fn get_task(&self, task_id: &str) -> Option<TaskInfo> {
         let tasks = self.tasks.read().await;
-        tasks.get(task_id).map(|task| task.info.clone())
+        tasks.get(task_id).map(|task| task.live_info())
     }
 
     pub async fn list_tasks(&self) -> Vec<TaskInfo> {
         let tasks = self.tasks.read().await;
-        tasks.values().map(|task| task.info.clone()).collect()
+        tasks.values().map(|task| task.live_info()).collect()
     }
 
     pub async fn cleanup_finished_tasks(&self) {
@@ -19,6 +19,29 @@ pub struct EventPayload {
     pub log_index: String,
     pub log_hash: String,
     pub event_name: String,
-    pub event_signature: String,
+    pub event_signature: Option<String>, // None for anonymous events
     pub event_data: Value,
+    // Address labels, if configured and matched
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub contract_label: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub from_label: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub to_label: Option<String>,
+}
+
+/// Log that failed to decode, persisted to the dead-letter table
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FailedEventPayload {
+    pub contract_name: Option<String>,
+    pub contract_address: String,
+    pub chain_id: String,
+    pub block_number: String,
+    pub block_hash: String,
+    pub transaction_hash: String,
+    pub log_index: String,
+    pub log_hash: String,
+    pub topics: Vec<String>,
+    pub data: String,
+    pub error: String,
 }
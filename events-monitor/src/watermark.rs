@@ -0,0 +1,254 @@
+use std::collections::BTreeSet;
+use std::sync::Mutex;
+
+use serde::{Deserialize, Serialize};
+
+/// Log position in the chain: (block number, log index)
+pub type LogPosition = (u64, u64);
+
+/// Processed logs watermark: the position up to which all accepted logs are processed.
+/// Logs are processed concurrently by the log workers and may complete out of order,
+/// so the watermark only advances over a contiguous run of completed logs,
+/// and never passes a log which is still in flight or failed.
+pub struct LogWatermark {
+    state: Mutex<WatermarkState>,
+}
+
+#[derive(Default)]
+struct WatermarkState {
+    in_flight: BTreeSet<LogPosition>,
+    failed: BTreeSet<LogPosition>,
+    completed: BTreeSet<LogPosition>,
+    hold_from_block: Option<u64>,
+    position: Option<LogPosition>,
+    persisted: Option<LogPosition>,
+}
+
+impl WatermarkState {
+    /// Move the watermark to the last completed log before the first log in flight or failed, and the held block
+    fn advance(&mut self) {
+        let held = self.hold_from_block.map(|block| (block, 0));
+        let limit = [self.in_flight.first().copied(), self.failed.first().copied(), held].into_iter().flatten().min();
+
+        let advanced = match limit {
+            Some(limit) => self.completed.range(..limit).next_back().copied(),
+            None => self.completed.last().copied(),
+        };
+
+        if let Some(advanced) = advanced {
+            self.position = Some(advanced);
+            self.completed = self.completed.split_off(&(advanced.0, advanced.1 + 1));
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WatermarkInfo {
+    pub block_number: u64,
+    pub log_index: u64,
+    pub persisted: bool, // Whether the watermark is already committed to the database
+}
+
+impl LogWatermark {
+    /// Watermark resumed from the persisted position
+    pub fn new(persisted: Option<LogPosition>) -> Self {
+        Self {
+            state: Mutex::new(WatermarkState {
+                position: persisted,
+                persisted,
+                ..Default::default()
+            }),
+        }
+    }
+
+    /// Register the accepted log, the watermark doesn't advance past it until it's completed
+    pub fn begin(&self, position: LogPosition) {
+        let mut state = self.state.lock().unwrap();
+        if state.position.is_none_or(|current| position > current) {
+            state.in_flight.insert(position);
+        }
+    }
+
+    /// Mark the log as processed, advancing the watermark if no earlier log is in flight
+    pub fn complete(&self, position: LogPosition) {
+        let mut state = self.state.lock().unwrap();
+        state.in_flight.remove(&position);
+        state.failed.remove(&position);
+        if state.position.is_some_and(|current| position <= current) {
+            return;
+        }
+        state.completed.insert(position);
+        state.advance();
+    }
+
+    /// Mark the log as failed, the watermark doesn't advance past it, so it's processed again
+    /// after a restart. Released if a later attempt completes it.
+    pub fn fail(&self, position: LogPosition) {
+        let mut state = self.state.lock().unwrap();
+        state.in_flight.remove(&position);
+        if state.position.is_some_and(|current| position <= current) {
+            return;
+        }
+        state.failed.insert(position);
+    }
+
+    /// Keep the watermark before the block, as logs from it are yet to be fetched and accepted
+    /// (e.g. by the historical logs processing, while the new logs are processed concurrently).
+    /// Released by `None`.
+    pub fn hold_from_block(&self, block: Option<u64>) {
+        let mut state = self.state.lock().unwrap();
+        state.hold_from_block = block;
+        state.advance();
+    }
+
+    /// Whether the log needs no processing: at or before the watermark, in flight or completed.
+    /// Failed logs aren't handled, so they're processed again when fetched again.
+    pub fn is_handled(&self, position: LogPosition) -> bool {
+        let state = self.state.lock().unwrap();
+        state.position.is_some_and(|current| position <= current)
+            || state.in_flight.contains(&position)
+            || state.completed.contains(&position)
+    }
+
+    /// Block of the first failed log, from which it's to be fetched again
+    pub fn first_failed_block(&self) -> Option<u64> {
+        self.state.lock().unwrap().failed.first().map(|(block, _)| *block)
+    }
+
+    pub fn position(&self) -> Option<LogPosition> {
+        self.state.lock().unwrap().position
+    }
+
+    /// Watermark position not yet committed to the database
+    pub fn unpersisted(&self) -> Option<LogPosition> {
+        let state = self.state.lock().unwrap();
+        state.position.filter(|position| state.persisted != Some(*position))
+    }
+
+    pub fn mark_persisted(&self, position: LogPosition) {
+        let mut state = self.state.lock().unwrap();
+        if state.persisted.is_none_or(|persisted| position > persisted) {
+            state.persisted = Some(position);
+        }
+    }
+
+    pub fn info(&self) -> Option<WatermarkInfo> {
+        let state = self.state.lock().unwrap();
+        state.position.map(|(block_number, log_index)| WatermarkInfo {
+            block_number,
+            log_index,
+            persisted: state.persisted == state.position,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_advances_over_contiguous_completed_logs() {
+        let watermark = LogWatermark::new(None);
+        for position in [(1, 0), (1, 1), (2, 0)] {
+            watermark.begin(position);
+        }
+
+        watermark.complete((1, 1));
+        assert_eq!(watermark.position(), None);
+
+        watermark.complete((1, 0));
+        assert_eq!(watermark.position(), Some((1, 1)));
+
+        watermark.complete((2, 0));
+        assert_eq!(watermark.position(), Some((2, 0)));
+    }
+
+    #[test]
+    fn test_never_passes_in_flight_log() {
+        let watermark = LogWatermark::new(Some((5, 3)));
+        for position in [(6, 0), (7, 0), (8, 0)] {
+            watermark.begin(position);
+        }
+
+        watermark.complete((8, 0));
+        watermark.complete((6, 0));
+        assert_eq!(watermark.position(), Some((6, 0)));
+
+        watermark.complete((7, 0));
+        assert_eq!(watermark.position(), Some((8, 0)));
+    }
+
+    #[test]
+    fn test_never_passes_failed_log() {
+        let watermark = LogWatermark::new(Some((5, 3)));
+        for position in [(6, 0), (7, 0), (8, 0)] {
+            watermark.begin(position);
+        }
+
+        watermark.complete((6, 0));
+        watermark.fail((7, 0));
+        watermark.complete((8, 0));
+        assert_eq!(watermark.position(), Some((6, 0)));
+
+        // Released when processed again
+        watermark.begin((7, 0));
+        watermark.complete((7, 0));
+        assert_eq!(watermark.position(), Some((8, 0)));
+    }
+
+    #[test]
+    fn test_handled_logs() {
+        let watermark = LogWatermark::new(Some((5, 0)));
+        for position in [(6, 0), (7, 0), (8, 0)] {
+            watermark.begin(position);
+        }
+        watermark.fail((6, 0));
+        watermark.complete((8, 0));
+
+        assert!(watermark.is_handled((4, 2)));
+        assert!(!watermark.is_handled((6, 0)));
+        assert!(watermark.is_handled((7, 0)));
+        assert!(watermark.is_handled((8, 0)));
+        assert!(!watermark.is_handled((9, 0)));
+        assert_eq!(watermark.first_failed_block(), Some(6));
+    }
+
+    #[test]
+    fn test_held_before_unfetched_block() {
+        let watermark = LogWatermark::new(None);
+        watermark.hold_from_block(Some(3));
+        watermark.begin((2, 0));
+        watermark.begin((9, 0));
+
+        watermark.complete((9, 0));
+        watermark.complete((2, 0));
+        assert_eq!(watermark.position(), Some((2, 0)));
+
+        watermark.hold_from_block(None);
+        assert_eq!(watermark.position(), Some((9, 0)));
+    }
+
+    #[test]
+    fn test_ignores_logs_before_watermark() {
+        let watermark = LogWatermark::new(Some((10, 0)));
+        watermark.begin((9, 5));
+        watermark.complete((9, 5));
+
+        assert_eq!(watermark.position(), Some((10, 0)));
+        assert_eq!(watermark.unpersisted(), None);
+    }
+
+    #[test]
+    fn test_persistence_tracking() {
+        let watermark = LogWatermark::new(None);
+        watermark.begin((1, 0));
+        watermark.complete((1, 0));
+
+        assert_eq!(watermark.unpersisted(), Some((1, 0)));
+        assert!(!watermark.info().unwrap().persisted);
+
+        watermark.mark_persisted((1, 0));
+        assert_eq!(watermark.unpersisted(), None);
+        assert!(watermark.info().unwrap().persisted);
+    }
+}
@@ -49,6 +49,8 @@ pub async fn create_web_api(task_manager: Arc<TaskManager>) -> Router {
         .route("/api/tasks", get(list_tasks_handler))
         .route("/api/tasks/:task_id", get(get_task_handler))
         .route("/api/tasks/:task_id/stop", post(stop_task_handler))
+        .route("/api/tasks/:task_id/watermark", get(get_task_watermark_handler))
+        .route("/api/tasks/:task_id/nats", get(get_task_nats_handler))
         .route("/api/tasks/:task_id", delete(delete_task_handler))
         .route("/api/health", get(health_check_handler))
         .with_state(app_state)
@@ -156,6 +158,36 @@ async fn get_task_handler(
     }
 }
 
+async fn get_task_watermark_handler(
+    Path(task_id): Path<String>,
+    State(state): State<AppState>,
+) -> Result<Json<serde_json::Value>, (StatusCode, Json<ApiError>)> {
+    match state.task_manager.get_task(&task_id).await {
+        Some(task) => Ok(Json(json!({
+            "task_id": task.id,
+            "watermark": task.watermark
+        }))),
+        None => Err((StatusCode::NOT_FOUND, Json(ApiError {
+            error: format!("Task not found: {}", task_id)
+        })))
+    }
+}
+
+async fn get_task_nats_handler(
+    Path(task_id): Path<String>,
+    State(state): State<AppState>,
+) -> Result<Json<serde_json::Value>, (StatusCode, Json<ApiError>)> {
+    match state.task_manager.get_task(&task_id).await {
+        Some(task) => Ok(Json(json!({
+            "task_id": task.id,
+            "nats": task.nats
+        }))),
+        None => Err((StatusCode::NOT_FOUND, Json(ApiError {
+            error: format!("Task not found: {}", task_id)
+        })))
+    }
+}
+
 async fn stop_task_handler(
     Path(task_id): Path<String>,
     State(state): State<AppState>,
@@ -204,6 +236,9 @@ pub async fn start_web_server(
 ) -> anyhow::Result<()> {
     let app = create_web_api(task_manager).await;
 
+    #[cfg(feature = "metrics")]
+    let app = crate::metrics::with_metrics_route(app)?;
+
     info!("Starting web server on {}", bind_address);
 
     let listener = tokio::net::TcpListener::bind(bind_address)
@@ -2,7 +2,7 @@ use alloy::primitives::{hex, Address, B256, Bytes};
 use alloy::rpc::types::eth::Log;
 use alloy::dyn_abi::{DynSolType, DynSolValue};
 use alloy::json_abi as json_abi;
-use json_abi::{Event, JsonAbi};
+use json_abi::{Event, Function, JsonAbi};
 use serde_json::{json, Value};
 use std::{collections::HashMap, fs};
 
@@ -19,6 +19,10 @@ impl ContractAbi {
     pub fn load(name: String, address: Address, abi_path: &str) -> anyhow::Result<Self> {
         let raw = fs::read_to_string(abi_path)?;
         let abi: JsonAbi = serde_json::from_str(&raw)?;
+        Ok(Self::from_abi(name, address, abi))
+    }
+
+    pub fn from_abi(name: String, address: Address, abi: JsonAbi) -> Self {
         let mut events_by_topic = HashMap::new();
         for (_name, events) in &abi.events {
             for ev in events {
@@ -27,7 +31,7 @@ impl ContractAbi {
                 }
             }
         }
-        Ok(Self { name, address, abi, events_by_topic })
+        Self { name, address, abi, events_by_topic }
     }
 
     /// Returns the event hash (keccak256 of the event signature) for the first topic.
@@ -118,14 +122,114 @@ fn dyn_value_to_json(v: DynSolValue) -> Value {
 pub struct AbiIndex {
     // address -> ContractAbi
     pub by_address: HashMap<Address, ContractAbi>,
+    // Contracts addresses in the loading order, for deterministic lookups across contracts
+    order: Vec<Address>,
 }
 
 impl AbiIndex {
     pub fn new(contracts: impl IntoIterator<Item = ContractAbi>) -> Self {
         let mut by_address = HashMap::new();
-        for c in contracts { by_address.insert(c.address, c); }
-        Self { by_address }
+        let mut order = Vec::new();
+        for c in contracts {
+            if !by_address.contains_key(&c.address) { order.push(c.address); }
+            by_address.insert(c.address, c);
+        }
+        Self { by_address, order }
     }
 
     pub fn get(&self, addr: &Address) -> Option<&ContractAbi> { self.by_address.get(addr) }
+
+    /// Loaded contracts in the loading order.
+    fn contracts(&self) -> impl Iterator<Item = &ContractAbi> {
+        self.order.iter().filter_map(|addr| self.by_address.get(addr))
+    }
+
+    /// Finds a non-anonymous event by its first topic (event signature hash) across all loaded contracts.
+    /// Returns the owning contract name and the event of the first contract (in the loading order) declaring it.
+    pub fn find_event_by_topic(&self, topic: B256) -> Option<(&str, &Event)> {
+        self.contracts()
+            .find_map(|c| c.events_by_topic.get(&topic).map(|ev| (c.name.as_str(), ev)))
+    }
+
+    /// Finds a function by its 4-byte selector across all loaded contracts.
+    /// Returns the owning contract name and the function of the first contract (in the loading order) declaring it.
+    pub fn find_function_by_selector(&self, sel: [u8; 4]) -> Option<(&str, &Function)> {
+        self.contracts().find_map(|c| {
+            c.abi.functions()
+                .find(|f| f.selector().0 == sel)
+                .map(|f| (c.name.as_str(), f))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn contract(name: &str, address: Address, abi_json: &str) -> ContractAbi {
+        ContractAbi::from_abi(name.to_string(), address, serde_json::from_str(abi_json).unwrap())
+    }
+
+    fn index() -> AbiIndex {
+        let token = contract("Token", Address::repeat_byte(1), r#"[
+            {"type": "event", "name": "Transfer", "anonymous": false, "inputs": [
+                {"name": "from", "type": "address", "indexed": true},
+                {"name": "to", "type": "address", "indexed": true},
+                {"name": "value", "type": "uint256", "indexed": false}
+            ]},
+            {"type": "function", "name": "transfer", "stateMutability": "nonpayable",
+             "inputs": [{"name": "to", "type": "address"}, {"name": "value", "type": "uint256"}],
+             "outputs": [{"name": "", "type": "bool"}]}
+        ]"#);
+        let vault = contract("Vault", Address::repeat_byte(2), r#"[
+            {"type": "event", "name": "Transfer", "anonymous": false, "inputs": [
+                {"name": "from", "type": "address", "indexed": true},
+                {"name": "to", "type": "address", "indexed": true},
+                {"name": "value", "type": "uint256", "indexed": false}
+            ]},
+            {"type": "event", "name": "Deposit", "anonymous": false, "inputs": [
+                {"name": "owner", "type": "address", "indexed": true},
+                {"name": "assets", "type": "uint256", "indexed": false}
+            ]},
+            {"type": "function", "name": "deposit", "stateMutability": "nonpayable",
+             "inputs": [{"name": "assets", "type": "uint256"}],
+             "outputs": []}
+        ]"#);
+        AbiIndex::new([token, vault])
+    }
+
+    #[test]
+    fn test_find_event_by_topic() {
+        let index = index();
+
+        let deposit = alloy::primitives::keccak256("Deposit(address,uint256)");
+        let (contract, event) = index.find_event_by_topic(deposit).unwrap();
+        assert_eq!(contract, "Vault");
+        assert_eq!(event.name, "Deposit");
+
+        // Event declared by several contracts resolves to the first loaded one
+        let transfer = alloy::primitives::keccak256("Transfer(address,address,uint256)");
+        let (contract, event) = index.find_event_by_topic(transfer).unwrap();
+        assert_eq!(contract, "Token");
+        assert_eq!(event.name, "Transfer");
+
+        assert!(index.find_event_by_topic(B256::ZERO).is_none());
+    }
+
+    #[test]
+    fn test_find_function_by_selector() {
+        let index = index();
+
+        // transfer(address,uint256)
+        let (contract, function) = index.find_function_by_selector([0xa9, 0x05, 0x9c, 0xbb]).unwrap();
+        assert_eq!(contract, "Token");
+        assert_eq!(function.name, "transfer");
+
+        // deposit(uint256)
+        let (contract, function) = index.find_function_by_selector([0xb6, 0xb5, 0x5f, 0x25]).unwrap();
+        assert_eq!(contract, "Vault");
+        assert_eq!(function.name, "deposit");
+
+        assert!(index.find_function_by_selector([0; 4]).is_none());
+    }
 }
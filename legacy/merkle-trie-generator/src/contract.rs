@@ -7,6 +7,7 @@ use alloy_rpc_types::transaction::eip2718::TypedTransaction;
 use alloy_providers::Middleware;
 
 use std::sync::Arc;
+use std::time::Duration;
 
 sol! {
     contract AirdropStorage {
@@ -15,17 +16,87 @@ sol! {
     }
 }
 
+/// Maximum broadcast attempts of a trie update transaction
+const MAX_SEND_ATTEMPTS: u32 = 5;
+/// Gas price bump per retry, in percent (replacements require at least 10%)
+const GAS_PRICE_BUMP_PERCENT: u64 = 20;
+/// Time to wait for the transaction receipt before retrying
+const RECEIPT_TIMEOUT: Duration = Duration::from_secs(120);
+
+/// Confirmed trie update transaction
+#[derive(Debug, Clone)]
+pub struct TrieUpdateReceipt {
+    pub tx_hash: B256,
+    pub block_number: Option<u64>,
+}
+
+/// Trie update failure, after all retries
+#[derive(Debug)]
+pub enum TrieUpdateError {
+    /// Transaction was mined but reverted
+    Reverted { tx_hash: B256, reason: Option<String> },
+    /// Transaction was not confirmed after all attempts
+    NotConfirmed { attempts: u32, last_error: String },
+    /// Failure not recoverable by retrying
+    Other(anyhow::Error),
+}
+
+impl std::fmt::Display for TrieUpdateError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Reverted { tx_hash, reason } => write!(
+                f,
+                "Trie update transaction {:?} reverted: {}",
+                tx_hash,
+                reason.as_deref().unwrap_or("no revert reason")
+            ),
+            Self::NotConfirmed { attempts, last_error } => write!(
+                f,
+                "Trie update transaction not confirmed after {} attempts, last error: {}",
+                attempts, last_error
+            ),
+            Self::Other(e) => write!(f, "Trie update failed: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for TrieUpdateError {}
+
+impl From<anyhow::Error> for TrieUpdateError {
+    fn from(e: anyhow::Error) -> Self {
+        Self::Other(e)
+    }
+}
+
+/// Errors worth retrying with the same nonce and bumped gas price. A too low nonce means
+/// a previously sent transaction was mined, its receipt is looked up before any retry.
+fn is_retryable(error: &str) -> bool {
+    let error = error.to_lowercase();
+    ["replacement transaction underpriced", "replacement underpriced", "transaction underpriced",
+     "fee too low", "timeout", "timed out", "already known", "nonce too low"]
+        .iter()
+        .any(|pattern| error.contains(pattern))
+}
+
+fn bump_gas_price(gas_price: U256) -> U256 {
+    gas_price * U256::from(100 + GAS_PRICE_BUMP_PERCENT) / U256::from(100)
+}
+
+/// Send the trie root update and wait for its confirmation. On underpriced replacement
+/// or timeout failures the transaction is resent with the same nonce and a bumped gas price,
+/// unless one of the previously sent transactions got mined in the meantime.
+/// Returns `Ok(None)` if the root is already on-chain.
 pub async fn send_trie_update(
     rpc_url: &str,
     private_key_hex: &str,
     contract_address: Address,
     root: B256,
     trie_data: Vec<u8>,
-) -> anyhow::Result<()> {
-    let wallet: LocalWallet = private_key_hex.parse()?;
+) -> Result<Option<TrieUpdateReceipt>, TrieUpdateError> {
+    let wallet: LocalWallet = private_key_hex.parse().map_err(anyhow::Error::from)?;
     let address = wallet.address();
 
-    let provider = ProviderBuilder::new().on_ws(rpc_url).await?;
+    let provider = ProviderBuilder::new().on_ws(rpc_url).await.map_err(anyhow::Error::from)?;
     let client = Arc::new(provider.with_signer(wallet));
 
     // Optional: validate current root from chain
@@ -37,12 +108,13 @@ pub async fn send_trie_update(
             data: Some(Bytes::from(encoded)),
             ..Default::default()
         }, None)
-        .await?;
+        .await
+        .map_err(anyhow::Error::from)?;
 
     let onchain_root = B256::from_slice(&result[..32]);
     if onchain_root == root {
         println!("Root already on-chain, skipping update.");
-        return Ok(());
+        return Ok(None);
     }
 
     // Prepare contract calldata
@@ -50,15 +122,16 @@ pub async fn send_trie_update(
         root,
         trieData: Bytes::from(trie_data),
     };
-    let calldata = update_call.abi_encode();
+    let calldata = Bytes::from(update_call.abi_encode());
 
-    let gas_price = client.get_gas_price().await?;
-    let nonce = client.get_transaction_count(address, None).await?;
+    let mut gas_price = client.get_gas_price().await.map_err(anyhow::Error::from)?;
+    // Same nonce for all attempts, so a retry replaces the previous transaction instead of adding one
+    let nonce = client.get_transaction_count(address, None).await.map_err(anyhow::Error::from)?;
     let gas_limit = U256::from(1_000_000); // We can estimate gas limit instead
 
-    let tx = TypedTransaction::Legacy {
+    let build_tx = |gas_price: U256| TypedTransaction::Legacy {
         to: Some(contract_address),
-        data: Some(Bytes::from(calldata)),
+        data: Some(calldata.clone()),
         nonce: Some(nonce),
         gas_price: Some(gas_price),
         gas: Some(gas_limit),
@@ -66,9 +139,88 @@ pub async fn send_trie_update(
         ..Default::default()
     };
 
-    let pending = client.send_transaction(tx, None).await?;
-    let receipt = pending.await?;
-    println!("Transaction sent: {:?}", receipt.transaction_hash);
+    let mut sent_tx_hashes: Vec<B256> = Vec::new();
+    let mut attempt = 0;
+    let mut last_error = String::new();
+
+    let receipt = loop {
+        // A previous transaction may still get mined after its receipt wait timed out, in which case
+        // a replacement would fail with a too low nonce, so previous transactions are checked first
+        let mut mined = None;
+        for sent_tx_hash in &sent_tx_hashes {
+            if let Some(receipt) = client.get_transaction_receipt(*sent_tx_hash).await.map_err(anyhow::Error::from)? {
+                println!("Previously sent transaction {:?} was mined", sent_tx_hash);
+                mined = Some(receipt);
+                break;
+            }
+        }
+        if let Some(receipt) = mined {
+            break receipt;
+        }
+
+        if attempt == MAX_SEND_ATTEMPTS {
+            return Err(TrieUpdateError::NotConfirmed {
+                attempts: MAX_SEND_ATTEMPTS,
+                last_error,
+            });
+        }
+        attempt += 1;
+
+        let pending = match client.send_transaction(build_tx(gas_price), None).await {
+            Ok(pending) => pending,
+            Err(e) => {
+                last_error = e.to_string();
+                if !is_retryable(&last_error) {
+                    return Err(TrieUpdateError::Other(anyhow::anyhow!(last_error)));
+                }
+                println!("Attempt {}/{} to send trie update failed: {}", attempt, MAX_SEND_ATTEMPTS, last_error);
+                gas_price = bump_gas_price(gas_price);
+                continue;
+            }
+        };
+
+        let tx_hash = pending.tx_hash();
+        sent_tx_hashes.push(tx_hash);
+        println!("Transaction sent: {:?} (attempt {}/{}, gas price {})", tx_hash, attempt, MAX_SEND_ATTEMPTS, gas_price);
+
+        match tokio::time::timeout(RECEIPT_TIMEOUT, pending).await {
+            Ok(Ok(Some(receipt))) => break receipt,
+            Ok(Ok(None)) => {
+                last_error = format!("Transaction {:?} dropped from mempool", tx_hash);
+            }
+            Ok(Err(e)) => {
+                last_error = e.to_string();
+                if !is_retryable(&last_error) {
+                    return Err(TrieUpdateError::Other(anyhow::anyhow!(last_error)));
+                }
+            }
+            Err(_) => {
+                last_error = format!("Timeout waiting for receipt of {:?}", tx_hash);
+            }
+        }
+
+        println!("Attempt {}/{} to confirm trie update failed: {}", attempt, MAX_SEND_ATTEMPTS, last_error);
+        gas_price = bump_gas_price(gas_price);
+    };
+
+    if receipt.status == Some(1u64.into()) {
+        let block_number = receipt.block_number.map(|n| n.as_u64());
+        println!("Transaction confirmed: {:?} in block {:?}", receipt.transaction_hash, block_number);
+        return Ok(Some(TrieUpdateReceipt {
+            tx_hash: receipt.transaction_hash,
+            block_number,
+        }));
+    }
+
+    // Replay the call at the mined block to get the revert reason
+    let reason = client
+        .call(&build_tx(gas_price), receipt.block_number.map(Into::into))
+        .await
+        .err()
+        .map(|e| e.to_string());
 
-    Ok(())
+    Err(TrieUpdateError::Reverted {
+        tx_hash: receipt.transaction_hash,
+        reason,
+    })
 }
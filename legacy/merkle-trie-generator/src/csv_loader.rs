@@ -1,7 +1,10 @@
+use crate::trie_builder::DuplicatePolicy;
 use crate::types::AirdropEntry;
+use alloy_primitives::Address;
+use std::collections::HashMap;
 use std::fs::File;
 use std::io::BufReader;
-use csv::Reader;
+use csv::{ErrorKind, Reader};
 
 pub fn load_airdrop_csv(path: &str) -> anyhow::Result<Vec<AirdropEntry>> {
     let file = File::open(path)?;
@@ -13,3 +16,177 @@ pub fn load_airdrop_csv(path: &str) -> anyhow::Result<Vec<AirdropEntry>> {
     }
     Ok(entries)
 }
+
+/// Problem of an airdrop CSV row, `line` is the 1-based line number in the file
+#[derive(Debug)]
+pub struct CsvIssue {
+    pub line: u64,
+    pub message: String,
+}
+
+/// Result of the airdrop CSV validation, see [`validate_airdrop_csv`]
+#[derive(Debug, Default)]
+pub struct CsvReport {
+    pub rows: usize,
+    pub valid_rows: usize,
+    /// Sum of the valid rows amounts
+    pub total_amount: u128,
+    pub rounds: Vec<u32>,
+    pub issues: Vec<CsvIssue>,
+}
+
+impl CsvReport {
+    pub fn has_issues(&self) -> bool {
+        !self.issues.is_empty()
+    }
+}
+
+/// Parse the whole airdrop CSV, collecting the malformed rows, zero amounts
+/// and duplicate addresses instead of stopping at the first error.
+/// Duplicate addresses are issues with the `Reject` policy, with `Merge` only
+/// the merged amounts overflowing as by the trie build are.
+pub fn validate_airdrop_csv(path: &str, duplicate_policy: DuplicatePolicy) -> anyhow::Result<CsvReport> {
+    let file = File::open(path)?;
+    let mut rdr = Reader::from_reader(BufReader::new(file));
+    let headers = rdr.headers()?.clone();
+
+    let mut report = CsvReport::default();
+    // First line and merged amount of each address
+    let mut addresses: HashMap<Address, (u64, u64)> = HashMap::new();
+
+    for result in rdr.records() {
+        report.rows += 1;
+
+        let record = match result {
+            Ok(record) => record,
+            Err(err) => {
+                if let ErrorKind::Io(_) = err.kind() {
+                    return Err(err.into());
+                }
+                let line = err.position().map_or(0, |position| position.line());
+                report.issues.push(CsvIssue { line, message: format!("Malformed row: {}", err) });
+                continue;
+            }
+        };
+        let line = record.position().map_or(0, |position| position.line());
+
+        let entry: AirdropEntry = match record.deserialize(Some(&headers)) {
+            Ok(entry) => entry,
+            Err(err) => {
+                report.issues.push(CsvIssue { line, message: format!("Invalid row: {}", err) });
+                continue;
+            }
+        };
+
+        if entry.amount == 0 {
+            report.issues.push(CsvIssue { line, message: format!("Zero amount for {}", entry.address) });
+            continue;
+        }
+        match addresses.get_mut(&entry.address) {
+            None => {
+                addresses.insert(entry.address, (line, entry.amount));
+            }
+            Some((first_line, _)) if duplicate_policy == DuplicatePolicy::Reject => {
+                report.issues.push(CsvIssue {
+                    line,
+                    message: format!("Duplicate address {}, first seen at line {}", entry.address, first_line),
+                });
+                continue;
+            }
+            Some((first_line, merged_amount)) => match merged_amount.checked_add(entry.amount) {
+                Some(amount) => *merged_amount = amount,
+                None => {
+                    report.issues.push(CsvIssue {
+                        line,
+                        message: format!(
+                            "Merged amount of {} overflows, first seen at line {}", entry.address, first_line
+                        ),
+                    });
+                    continue;
+                }
+            },
+        }
+
+        report.valid_rows += 1;
+        report.total_amount += entry.amount as u128;
+        if !report.rounds.contains(&entry.round) {
+            report.rounds.push(entry.round);
+        }
+    }
+
+    report.rounds.sort_unstable();
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+
+    const ADDRESS_1: &str = "0x0000000000000000000000000000000000000001";
+    const ADDRESS_2: &str = "0x0000000000000000000000000000000000000002";
+
+    /// Validate the CSV content written to a temporary file
+    fn validate(name: &str, content: &str, policy: DuplicatePolicy) -> CsvReport {
+        let path = std::env::temp_dir().join(format!("merkle-trie-generator-{}-{}.csv", name, std::process::id()));
+        File::create(&path).unwrap().write_all(content.as_bytes()).unwrap();
+        let report = validate_airdrop_csv(path.to_str().unwrap(), policy);
+        std::fs::remove_file(&path).unwrap();
+        report.unwrap()
+    }
+
+    #[test]
+    fn test_valid_csv() {
+        let content = format!("address,amount,round\n{},100,1\n{},250,2\n", ADDRESS_1, ADDRESS_2);
+        let report = validate("valid", &content, DuplicatePolicy::Reject);
+
+        assert!(!report.has_issues());
+        assert_eq!(report.rows, 2);
+        assert_eq!(report.valid_rows, 2);
+        assert_eq!(report.total_amount, 350);
+        assert_eq!(report.rounds, vec![1, 2]);
+    }
+
+    #[test]
+    fn test_invalid_rows_reported_with_lines() {
+        let content = format!(
+            "address,amount,round\n{},100,1\n0xnot-an-address,5,1\n{},-3,1\n{},0,1\n{},7\n",
+            ADDRESS_1, ADDRESS_2, ADDRESS_2, ADDRESS_2
+        );
+        let report = validate("invalid", &content, DuplicatePolicy::Reject);
+
+        assert_eq!(report.rows, 5);
+        assert_eq!(report.valid_rows, 1);
+        assert_eq!(report.total_amount, 100);
+        let lines: Vec<u64> = report.issues.iter().map(|issue| issue.line).collect();
+        assert_eq!(lines, vec![3, 4, 5, 6]);
+        assert!(report.issues[2].message.starts_with("Zero amount"));
+    }
+
+    #[test]
+    fn test_duplicates_by_policy() {
+        let content = format!("address,amount,round\n{},100,1\n{},50,1\n{},20,1\n", ADDRESS_1, ADDRESS_2, ADDRESS_1);
+
+        let rejected = validate("duplicates-reject", &content, DuplicatePolicy::Reject);
+        assert_eq!(rejected.valid_rows, 2);
+        assert_eq!(rejected.total_amount, 150);
+        assert_eq!(rejected.issues.len(), 1);
+        assert_eq!(rejected.issues[0].line, 4);
+        assert!(rejected.issues[0].message.contains("first seen at line 2"));
+
+        let merged = validate("duplicates-merge", &content, DuplicatePolicy::Merge);
+        assert!(!merged.has_issues());
+        assert_eq!(merged.valid_rows, 3);
+        assert_eq!(merged.total_amount, 170);
+    }
+
+    #[test]
+    fn test_merged_amount_overflow() {
+        let content = format!("address,amount,round\n{},{},1\n{},1,1\n", ADDRESS_1, u64::MAX, ADDRESS_1);
+        let report = validate("merge-overflow", &content, DuplicatePolicy::Merge);
+
+        assert_eq!(report.issues.len(), 1);
+        assert_eq!(report.issues[0].line, 3);
+        assert!(report.issues[0].message.contains("overflows"));
+    }
+}
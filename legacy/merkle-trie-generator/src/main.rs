@@ -3,29 +3,160 @@ mod trie_builder;
 mod contract;
 mod types;
 
-use alloy_primitives::{Address, hex};
+use alloy_primitives::{Address, U256, hex};
 use std::env;
 use std::str::FromStr;
 
 #[tokio::main]
 async fn main() -> anyhow::Result<()> {
+    let args = parse_args(env::args().skip(1))?;
+    let max_total = args.max_total;
+
     let csv_path = env::var("CSV_PATH")?;
+
+    // Entries with the same address: reject (default) or merge, summing the amounts
+    let duplicate_policy = match env::var("DUPLICATE_POLICY") {
+        Ok(policy) => policy.parse()?,
+        Err(_) => trie_builder::DuplicatePolicy::default(),
+    };
+
+    if args.validate {
+        return validate(&csv_path, duplicate_policy, max_total);
+    }
+
     let private_key = env::var("PRIVATE_KEY")?;
     let rpc_url = env::var("RPC_URL")?;
     let contract_address = Address::from_str(&env::var("CONTRACT_ADDRESS")?)?;
 
+    // Leaf encoding of the target verifier: packed (default), abi_encoded or double_keccak
+    let leaf_encoding = match env::var("LEAF_ENCODING") {
+        Ok(encoding) => encoding.parse()?,
+        Err(_) => trie_builder::LeafEncoding::default(),
+    };
+
     let entries = csv_loader::load_airdrop_csv(&csv_path)?;
-    let trie_result = trie_builder::build_trie(&entries);
+    let trie_result = trie_builder::build_trie(&entries, leaf_encoding, duplicate_policy)?;
 
     println!("Root Hash: 0x{}", hex::encode(trie_result.root_hash));
+    println!("Total amount: {}", trie_result.total_amount);
+    if let Some(max_total) = max_total {
+        trie_builder::check_total_cap(trie_result.total_amount, max_total)?;
+    }
 
-    contract::send_trie_update(
+    if let Some(receipt) = contract::send_trie_update(
         &rpc_url,
         &private_key,
         contract_address,
         trie_result.root_hash,
         trie_result.trie_nodes,
-    ).await?;
+    ).await? {
+        println!("Root update confirmed: tx {:?}, block {:?}", receipt.tx_hash, receipt.block_number);
+    }
 
     Ok(())
 }
+
+/// Command line arguments
+#[derive(Debug, Default, PartialEq)]
+struct Args {
+    /// `validate` command: only check the CSV, without building the trie or sending the root update
+    validate: bool,
+    /// `--max-total <amount>`: cap of the allocations sum (total supply or airdrop budget)
+    max_total: Option<U256>,
+}
+
+/// Parse the command line arguments, in any order. Unknown options and commands
+/// are errors, so a mistyped `validate` never falls through to the root update.
+fn parse_args(args: impl IntoIterator<Item = String>) -> anyhow::Result<Args> {
+    let mut parsed = Args::default();
+    let mut commands = Vec::new();
+
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
+        if !arg.starts_with('-') {
+            commands.push(arg);
+            continue;
+        }
+
+        let (name, value) = match arg.split_once('=') {
+            Some((name, value)) => (name.to_string(), Some(value.to_string())),
+            None => (arg, None),
+        };
+        match name.as_str() {
+            "--max-total" => {
+                let value = match value {
+                    Some(value) => value,
+                    None => args.next().ok_or_else(|| anyhow::anyhow!("Missing --max-total value"))?,
+                };
+                let max_total = U256::from_str(&value)
+                    .map_err(|err| anyhow::anyhow!("Invalid --max-total value {}: {}", value, err))?;
+                parsed.max_total = Some(max_total);
+            }
+            _ => anyhow::bail!("Unknown option {}, expected --max-total", name),
+        }
+    }
+
+    match commands.as_slice() {
+        [] => {}
+        [command] if command == "validate" => parsed.validate = true,
+        [command] => anyhow::bail!("Unknown command {}, expected validate or no command", command),
+        _ => anyhow::bail!("Unexpected arguments {:?}, expected a single validate command", commands),
+    }
+
+    Ok(parsed)
+}
+
+fn validate(
+    csv_path: &str,
+    duplicate_policy: trie_builder::DuplicatePolicy,
+    max_total: Option<U256>,
+) -> anyhow::Result<()> {
+    let report = csv_loader::validate_airdrop_csv(csv_path, duplicate_policy)?;
+
+    for issue in &report.issues {
+        println!("Line {}: {}", issue.line, issue.message);
+    }
+    println!("Rows: {}, valid: {}, with issues: {}", report.rows, report.valid_rows, report.issues.len());
+    println!("Rounds: {:?}", report.rounds);
+    println!("Total amount: {}", report.total_amount);
+
+    if report.has_issues() {
+        anyhow::bail!("{} has {} invalid rows", csv_path, report.issues.len());
+    }
+    if let Some(max_total) = max_total {
+        trie_builder::check_total_cap(U256::from(report.total_amount), max_total)?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(args: &[&str]) -> anyhow::Result<Args> {
+        parse_args(args.iter().map(|arg| arg.to_string()))
+    }
+
+    #[test]
+    fn test_parse_args() {
+        assert_eq!(parse(&[]).unwrap(), Args::default());
+        assert_eq!(parse(&["validate"]).unwrap(), Args { validate: true, max_total: None });
+
+        let expected = Args { validate: true, max_total: Some(U256::from(1000)) };
+        assert_eq!(parse(&["validate", "--max-total", "1000"]).unwrap(), expected);
+        assert_eq!(parse(&["--max-total", "1000", "validate"]).unwrap(), expected);
+        assert_eq!(parse(&["--max-total=1000", "validate"]).unwrap(), expected);
+    }
+
+    #[test]
+    fn test_parse_args_rejects_unknown_arguments() {
+        // Mistyped or misplaced commands must never fall through to the root update
+        assert!(parse(&["validat"]).is_err());
+        assert!(parse(&["validate", "validate"]).is_err());
+        assert!(parse(&["--max-total", "1000", "send"]).is_err());
+        assert!(parse(&["--validate"]).is_err());
+        assert!(parse(&["--max-total"]).is_err());
+        assert!(parse(&["--max-total", "lots"]).is_err());
+    }
+}
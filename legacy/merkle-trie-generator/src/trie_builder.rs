@@ -1,35 +1,313 @@
 use crate::types::AirdropEntry;
-use trie_db::{Trie, TrieMut, TrieDBMut, DBValue};
-use hash_db::Hasher;
-use keccak_hasher::KeccakHasher;
-use memory_db::{MemoryDB, HashKey};
-use rlp::RlpStream;
-use alloy_primitives::{Address, B256};
+use alloy_primitives::{keccak256, Address, B256, U256};
+use std::collections::BTreeMap;
+use std::str::FromStr;
+
+/// Leaf hashing scheme, must match the one of the target on-chain verifier.
+/// The airdrop-backend uses `Packed` encoding.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum LeafEncoding {
+    /// `keccak256(abi.encodePacked(address, uint256 amount))`
+    #[default]
+    Packed,
+    /// `keccak256(abi.encode(address, uint256 amount))`
+    AbiEncoded,
+    /// `keccak256(bytes.concat(keccak256(abi.encode(address, uint256 amount))))`,
+    /// as OpenZeppelin `StandardMerkleTree` leaves
+    DoubleKeccak,
+}
+
+impl FromStr for LeafEncoding {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> anyhow::Result<Self> {
+        match s {
+            "packed" => Ok(Self::Packed),
+            "abi_encoded" => Ok(Self::AbiEncoded),
+            "double_keccak" => Ok(Self::DoubleKeccak),
+            _ => Err(anyhow::anyhow!(
+                "Unknown leaf encoding '{}', expected 'packed', 'abi_encoded' or 'double_keccak'", s
+            )),
+        }
+    }
+}
+
+impl LeafEncoding {
+    pub fn leaf_hash(&self, entry: &AirdropEntry) -> B256 {
+        let amount = U256::from(entry.amount).to_be_bytes::<32>();
+
+        match self {
+            Self::Packed => {
+                let mut packed = Vec::with_capacity(52);
+                packed.extend_from_slice(entry.address.as_slice());
+                packed.extend_from_slice(&amount);
+                keccak256(&packed)
+            }
+            Self::AbiEncoded => keccak256(abi_encode(entry, &amount)),
+            Self::DoubleKeccak => keccak256(keccak256(abi_encode(entry, &amount))),
+        }
+    }
+}
+
+/// Handling of entries with the same address, which would otherwise produce several leaves
+/// for one claimer
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum DuplicatePolicy {
+    /// Fail the trie build
+    #[default]
+    Reject,
+    /// Sum the amounts into a single leaf
+    Merge,
+}
+
+impl FromStr for DuplicatePolicy {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> anyhow::Result<Self> {
+        match s {
+            "reject" => Ok(Self::Reject),
+            "merge" => Ok(Self::Merge),
+            _ => Err(anyhow::anyhow!("Unknown duplicate policy '{}', expected 'reject' or 'merge'", s)),
+        }
+    }
+}
+
+/// Entries with unique addresses, sorted by address, duplicates handled per the policy
+fn dedup_entries(entries: &[AirdropEntry], policy: DuplicatePolicy) -> anyhow::Result<Vec<AirdropEntry>> {
+    let mut unique: BTreeMap<Address, AirdropEntry> = BTreeMap::new();
+
+    for entry in entries {
+        match unique.get_mut(&entry.address) {
+            None => {
+                unique.insert(entry.address, entry.clone());
+            }
+            Some(_) if policy == DuplicatePolicy::Reject => {
+                anyhow::bail!("Duplicate address {} in the airdrop entries", entry.address);
+            }
+            Some(existing) => {
+                existing.amount = existing.amount.checked_add(entry.amount).ok_or_else(|| {
+                    anyhow::anyhow!("Merged amount of {} overflows", entry.address)
+                })?;
+            }
+        }
+    }
+
+    Ok(unique.into_values().collect())
+}
+
+fn abi_encode(entry: &AirdropEntry, amount: &[u8; 32]) -> Vec<u8> {
+    let mut encoded = Vec::with_capacity(64);
+    encoded.extend_from_slice(&[0u8; 12]);
+    encoded.extend_from_slice(entry.address.as_slice());
+    encoded.extend_from_slice(amount);
+    encoded
+}
+
+/// Hash a pair of nodes in sorted order, as verified by OpenZeppelin `MerkleProof`
+fn hash_pair(left: &B256, right: &B256) -> B256 {
+    let (first, second) = if left <= right { (left, right) } else { (right, left) };
+
+    let mut packed = [0u8; 64];
+    packed[..32].copy_from_slice(first.as_slice());
+    packed[32..].copy_from_slice(second.as_slice());
+    keccak256(packed)
+}
 
 pub struct TrieResult {
     pub root_hash: B256,
-    pub trie_nodes: Vec<u8>, // RLP-encoded trie root
+    pub trie_nodes: Vec<u8>, // Sorted leaf hashes, 32 bytes each
+    /// Sum of the leaves amounts, after the duplicates merge
+    pub total_amount: U256,
 }
 
-pub fn build_trie(entries: &[AirdropEntry]) -> TrieResult {
-    let mut db = MemoryDB::<KeccakHasher, HashKey<_>, DBValue>::default();
-    let mut root = Default::default();
-    let mut trie = TrieDBMut::<KeccakHasher>::new(&mut db, &mut root);
+/// Fail when the total amount exceeds the cap (total supply or airdrop budget), reporting the overage
+pub fn check_total_cap(total_amount: U256, max_total: U256) -> anyhow::Result<()> {
+    if total_amount > max_total {
+        anyhow::bail!(
+            "Total amount {} exceeds the cap {} by {}",
+            total_amount,
+            max_total,
+            total_amount - max_total
+        );
+    }
+    Ok(())
+}
 
-    for entry in entries {
-        let key = entry.address.as_slice();
-        let mut value_stream = RlpStream::new_list(3);
-        value_stream.append(&entry.amount);
-        value_stream.append(&entry.round);
-        value_stream.append(&entry.address); // optional redundancy
-        trie.insert(key, &value_stream.out()).unwrap();
+/// Build a Merkle tree over the entries leaf hashes. Entries with the same address are
+/// rejected or merged per the duplicate policy, and leaves are sorted by hash, so the root
+/// doesn't depend on the entries order. The last node of an odd sized level is paired with
+/// itself, as in the airdrop-backend Merkle trie.
+pub fn build_trie(
+    entries: &[AirdropEntry],
+    encoding: LeafEncoding,
+    duplicates: DuplicatePolicy,
+) -> anyhow::Result<TrieResult> {
+    let entries = dedup_entries(entries, duplicates)?;
+
+    let mut leaves: Vec<B256> = entries.iter().map(|entry| encoding.leaf_hash(entry)).collect();
+    leaves.sort();
+
+    let trie_nodes = leaves.iter().flat_map(|leaf| leaf.0).collect();
+    let total_amount = entries.iter().map(|entry| U256::from(entry.amount)).fold(U256::ZERO, |sum, amount| sum + amount);
+
+    Ok(TrieResult {
+        root_hash: compute_root(leaves),
+        trie_nodes,
+        total_amount,
+    })
+}
+
+fn compute_root(mut level: Vec<B256>) -> B256 {
+    if level.is_empty() {
+        return B256::ZERO;
     }
 
-    let root_hash = B256::from_slice(trie.root().as_bytes());
-    let trie_encoded = rlp::encode(trie.root());
+    if level.len() % 2 == 1 {
+        level.push(*level.last().unwrap());
+    }
+
+    while level.len() > 1 {
+        level = level.chunks(2).map(|pair| hash_pair(&pair[0], &pair[1])).collect();
+
+        if level.len() % 2 == 1 && level.len() > 1 {
+            level.push(*level.last().unwrap());
+        }
+    }
+
+    level[0]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::{b256, Address};
+
+    fn root(entries: &[AirdropEntry], encoding: LeafEncoding) -> B256 {
+        build_trie(entries, encoding, DuplicatePolicy::Reject).unwrap().root_hash
+    }
+
+    fn entries() -> Vec<AirdropEntry> {
+        (1u64..=3)
+            .map(|i| AirdropEntry {
+                address: Address::with_last_byte(i as u8),
+                amount: i * 100,
+                round: 1,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_leaf_hash_vectors() {
+        let entry = &entries()[0];
+
+        assert_eq!(
+            LeafEncoding::Packed.leaf_hash(entry),
+            b256!("eaf4f17819af3a9b14bda1f6c91bd1ccc63dc24933ec6966756a9a01d04c5170")
+        );
+        assert_eq!(
+            LeafEncoding::AbiEncoded.leaf_hash(entry),
+            b256!("84d38288bfc2d0dbffb953282e4834a72a7fd5e9f8000d46139e8490d63b1338")
+        );
+        assert_eq!(
+            LeafEncoding::DoubleKeccak.leaf_hash(entry),
+            b256!("46f78df7c8fc404ca4c68f617c2987869c8e047595a4f6c61dd0b2c30bc87e81")
+        );
+    }
+
+    #[test]
+    fn test_root_vectors() {
+        let entries = entries();
+
+        assert_eq!(
+            root(&entries, LeafEncoding::Packed),
+            b256!("379cc80f6d317444c9f718ec86063d10357227e5773ec26c1ae1743979519274")
+        );
+        assert_eq!(
+            root(&entries, LeafEncoding::AbiEncoded),
+            b256!("0d40eaa23f89d6e4f011b3aeb030d88775171134e2968579d4a1cf27771cacb7")
+        );
+        assert_eq!(
+            root(&entries, LeafEncoding::DoubleKeccak),
+            b256!("2b0431461a32a539ff45791752175cea6c0a9091327c43d15f9bc73a138c6aa4")
+        );
+    }
+
+    #[test]
+    fn test_single_entry_root() {
+        let entries = &entries()[..1];
+
+        assert_eq!(
+            root(entries, LeafEncoding::Packed),
+            b256!("ec8ef0b5f8d031b2a220cd15814f0b86971e641b65d5586aacd9863f544bcf45")
+        );
+    }
+
+    #[test]
+    fn test_root_independent_of_entries_order() {
+        let entries = entries();
+        let reversed: Vec<AirdropEntry> = entries.iter().rev().cloned().collect();
+
+        for encoding in [LeafEncoding::Packed, LeafEncoding::AbiEncoded, LeafEncoding::DoubleKeccak] {
+            assert_eq!(root(&entries, encoding), root(&reversed, encoding));
+        }
+    }
+
+    #[test]
+    fn test_root_independent_of_shuffled_rows() {
+        let entries: Vec<AirdropEntry> = (1u64..=20)
+            .map(|i| AirdropEntry { address: Address::with_last_byte(i as u8), amount: i * 10, round: 1 })
+            .collect();
+        let expected = root(&entries, LeafEncoding::Packed);
+
+        // Fisher-Yates shuffles driven by a fixed seed LCG
+        let mut seed = 0x2545f4914f6cdd1du64;
+        for _ in 0..10 {
+            let mut shuffled = entries.clone();
+            for i in (1..shuffled.len()).rev() {
+                seed = seed.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+                shuffled.swap(i, (seed >> 33) as usize % (i + 1));
+            }
+            assert_eq!(root(&shuffled, LeafEncoding::Packed), expected);
+        }
+    }
+
+    #[test]
+    fn test_duplicate_addresses() {
+        let mut with_duplicate = entries();
+        with_duplicate.push(AirdropEntry { address: Address::with_last_byte(1), amount: 50, round: 1 });
+
+        assert!(build_trie(&with_duplicate, LeafEncoding::Packed, DuplicatePolicy::Reject).is_err());
+
+        let merged = build_trie(&with_duplicate, LeafEncoding::Packed, DuplicatePolicy::Merge).unwrap();
+        let mut expected = entries();
+        expected[0].amount += 50;
+        assert_eq!(merged.root_hash, root(&expected, LeafEncoding::Packed));
+        assert_eq!(merged.trie_nodes.len(), 3 * 32);
+        assert_eq!(merged.total_amount, U256::from(650));
+    }
+
+    #[test]
+    fn test_total_cap() {
+        let total_amount = build_trie(&entries(), LeafEncoding::Packed, DuplicatePolicy::Reject).unwrap().total_amount;
+        assert_eq!(total_amount, U256::from(600));
+
+        assert!(check_total_cap(total_amount, U256::from(600)).is_ok());
+        let err = check_total_cap(total_amount, U256::from(550)).unwrap_err();
+        assert_eq!(err.to_string(), "Total amount 600 exceeds the cap 550 by 50");
+    }
+
+    #[test]
+    fn test_duplicate_policy_from_str() {
+        assert_eq!("reject".parse::<DuplicatePolicy>().unwrap(), DuplicatePolicy::Reject);
+        assert_eq!("merge".parse::<DuplicatePolicy>().unwrap(), DuplicatePolicy::Merge);
+        assert!("keep_first".parse::<DuplicatePolicy>().is_err());
+    }
 
-    TrieResult {
-        root_hash,
-        trie_nodes: trie_encoded.to_vec(),
+    #[test]
+    fn test_leaf_encoding_from_str() {
+        assert_eq!("packed".parse::<LeafEncoding>().unwrap(), LeafEncoding::Packed);
+        assert_eq!("abi_encoded".parse::<LeafEncoding>().unwrap(), LeafEncoding::AbiEncoded);
+        assert_eq!("double_keccak".parse::<LeafEncoding>().unwrap(), LeafEncoding::DoubleKeccak);
+        assert!("rlp".parse::<LeafEncoding>().is_err());
     }
 }
@@ -1,7 +1,7 @@
 use alloy_primitives::Address;
 use serde::Deserialize;
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, Deserialize)]
 pub struct AirdropEntry {
     pub address: Address,
     pub amount: u64,
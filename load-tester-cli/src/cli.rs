@@ -3,6 +3,8 @@
 use clap::{Parser, Subcommand, ValueEnum};
 use std::path::PathBuf;
 
+use crate::config::ThinkTime;
+
 #[derive(Parser, Debug)]
 #[command(name = "load-tester")]
 #[command(about = "Blockchain load testing and benchmarking tool", long_about = None)]
@@ -28,6 +30,31 @@ pub struct Cli {
     #[arg(short, long, default_value = "abi/LoadTester.json")]
     pub abi: PathBuf,
 
+    /// Header of the RPC endpoint requests as `Name: value`, e.g. the provider API key (repeatable)
+    #[arg(long = "rpc-header", value_name = "NAME: VALUE")]
+    pub rpc_headers: Vec<String>,
+
+    /// RPC endpoint URL to compare against, running the scenario on both endpoints concurrently
+    #[arg(long, env = "COMPARE_RPC_URL")]
+    pub compare_rpc_url: Option<String>,
+
+    /// Chain ID of the compared endpoint (defaults to --chain-id)
+    #[arg(long, env = "COMPARE_CHAIN_ID")]
+    pub compare_chain_id: Option<u64>,
+
+    /// Contract address on the compared endpoint (defaults to --contract)
+    #[arg(long, env = "COMPARE_CONTRACT_ADDRESS")]
+    pub compare_contract: Option<String>,
+
+    /// Private key for the compared endpoint (defaults to --private-key)
+    #[arg(long, env = "COMPARE_PRIVATE_KEY")]
+    pub compare_private_key: Option<String>,
+
+    /// Header of the compared endpoint requests as `Name: value` (repeatable), the --rpc-header
+    /// headers are only sent to the compared endpoint with the same URL
+    #[arg(long = "compare-rpc-header", value_name = "NAME: VALUE")]
+    pub compare_rpc_headers: Vec<String>,
+
     /// Test scenario to run
     #[command(subcommand)]
     pub scenario: TestScenario,
@@ -44,6 +71,18 @@ pub struct Cli {
     #[arg(short = 'r', long, default_value = "0")]
     pub rate_limit: u64,
 
+    /// Warmup operations before statistics collection (timings discarded)
+    #[arg(long, default_value = "0")]
+    pub warmup_operations: u64,
+
+    /// Pause of each worker between its operations: none, fixed:<MS>, uniform:<MIN_MS>-<MAX_MS> or exponential:<MEAN_MS>
+    #[arg(long, default_value = "none")]
+    pub think_time: ThinkTime,
+
+    /// Pre-allocate the signer nonces range before the test (default: for storage and batch mint scenarios)
+    #[arg(long)]
+    pub preallocate_nonces: Option<bool>,
+
     /// Output format
     #[arg(short = 'o', long, value_enum, default_value = "text")]
     pub output: OutputFormat,
@@ -52,6 +91,14 @@ pub struct Cli {
     #[arg(long)]
     pub save_results: Option<PathBuf>,
 
+    /// Save final results report as JSON (for CI regression tracking)
+    #[arg(long)]
+    pub report: Option<PathBuf>,
+
+    /// Save final results report as single row CSV
+    #[arg(long)]
+    pub report_csv: Option<PathBuf>,
+
     /// Verbose output
     #[arg(short, long)]
     pub verbose: bool,
@@ -189,6 +236,28 @@ pub enum TestScenario {
         tps: u64,
     },
 
+    /// Replay test - recorded contract calls from file
+    Replay {
+        /// Path to recorded calls file (JSON Lines: {"function": "...", "args": [...]})
+        #[arg(short = 'f', long)]
+        file: PathBuf,
+    },
+
+    /// External source test - contract calls pulled at runtime, until the source is exhausted or interrupted
+    External {
+        /// HTTP endpoint polled for calls, or '-' to read JSON Lines from stdin
+        #[arg(short = 's', long)]
+        source: String,
+
+        /// Delay before polling the HTTP endpoint again when it has no calls
+        #[arg(long, default_value = "500")]
+        poll_interval_ms: u64,
+
+        /// Stop after this many calls (unbounded by default)
+        #[arg(short = 'n', long)]
+        max_operations: Option<usize>,
+    },
+
     /// Custom scenario from config file
     Custom {
         /// Path to scenario config file
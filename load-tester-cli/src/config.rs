@@ -1,14 +1,22 @@
 //! Configuration management
 
 use anyhow::{Context, Result};
+use rand::Rng;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::path::Path;
+use std::str::FromStr;
+use std::time::Duration;
 
 use crate::cli::{Cli, TestScenario};
+use crate::scenarios::CallTemplate;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct LoadTestConfig {
     pub rpc_url: String,
+    /// Headers of the RPC endpoint requests, e.g. the provider API key
+    #[serde(default)]
+    pub rpc_headers: HashMap<String, String>,
     pub contract_address: String,
     pub private_key: String,
     pub chain_id: u64,
@@ -16,108 +24,396 @@ pub struct LoadTestConfig {
     pub workers: usize,
     pub duration: u64,
     pub rate_limit: u64,
+    #[serde(default)]
+    pub warmup_operations: u64,
+    #[serde(default)]
+    pub load_profile: LoadProfile,
+    #[serde(default = "default_error_rate_threshold")]
+    pub error_rate_threshold: f64,
+    #[serde(default)]
+    pub think_time: ThinkTime,
+    /// Pre-allocate the signer nonces, by default for the write-heavy scenarios
+    #[serde(default)]
+    pub preallocate_nonces: Option<bool>,
+    /// Second endpoint to run the scenario against concurrently, compared with this one as the baseline
+    #[serde(default)]
+    pub compare: Option<CompareEndpoint>,
     pub scenario: ScenarioConfig,
 }
 
+/// Compared endpoint, with the baseline chain ID, contract, signer key and RPC headers unless overridden
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CompareEndpoint {
+    pub rpc_url: String,
+    #[serde(default)]
+    pub rpc_headers: Option<HashMap<String, String>>,
+    #[serde(default)]
+    pub chain_id: Option<u64>,
+    #[serde(default)]
+    pub contract_address: Option<String>,
+    #[serde(default)]
+    pub private_key: Option<String>,
+}
+
+fn default_error_rate_threshold() -> f64 {
+    0.05
+}
+
+/// Parse `Name: value` RPC headers
+fn parse_rpc_headers(headers: &[String]) -> Result<HashMap<String, String>> {
+    headers
+        .iter()
+        .map(|header| {
+            // The header isn't part of the error, not to leak the credentials
+            let (name, value) = header
+                .split_once(':')
+                .context("Invalid RPC header, expected 'Name: value'")?;
+            Ok((name.trim().to_string(), value.trim().to_string()))
+        })
+        .collect()
+}
+
+/// Load profile, the target transactions rate over the test time
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(tag = "type")]
+pub enum LoadProfile {
+    /// Fixed rate, paced by the scenario with `rate_limit`
+    #[default]
+    Constant,
+    /// Linear ramp up from `start_tps` to `peak_tps`, hold, then linear ramp down back to `start_tps`
+    LinearRamp {
+        start_tps: u64,
+        peak_tps: u64,
+        ramp_up_secs: u64,
+        hold_secs: u64,
+        ramp_down_secs: u64,
+    },
+    /// Rate increased by `step_tps` every `step_secs`, for `steps` steps
+    Step {
+        start_tps: u64,
+        step_tps: u64,
+        step_secs: u64,
+        steps: u64,
+    },
+    /// Base rate with a single spike to `spike_tps` for `spike_secs` after `spike_at_secs`
+    Spike {
+        base_tps: u64,
+        spike_tps: u64,
+        spike_at_secs: u64,
+        spike_secs: u64,
+    },
+}
+
+impl LoadProfile {
+    /// Target rate in transactions per second at the elapsed test time,
+    /// `None` for the constant profile paced by the scenario itself.
+    /// After the profile ends, the final rate is held.
+    pub fn rate_at(&self, elapsed_secs: f64) -> Option<f64> {
+        match *self {
+            LoadProfile::Constant => None,
+            LoadProfile::LinearRamp { start_tps, peak_tps, ramp_up_secs, hold_secs, ramp_down_secs } => {
+                let (start, peak) = (start_tps as f64, peak_tps as f64);
+                let ramp_up = ramp_up_secs as f64;
+                let hold = hold_secs as f64;
+                let ramp_down = ramp_down_secs as f64;
+
+                let rate = if elapsed_secs < ramp_up {
+                    start + (peak - start) * elapsed_secs / ramp_up
+                } else if elapsed_secs < ramp_up + hold {
+                    peak
+                } else if elapsed_secs < ramp_up + hold + ramp_down {
+                    peak - (peak - start) * (elapsed_secs - ramp_up - hold) / ramp_down
+                } else {
+                    start
+                };
+                Some(rate)
+            }
+            LoadProfile::Step { start_tps, step_tps, step_secs, steps } => {
+                let step = (elapsed_secs / step_secs.max(1) as f64) as u64;
+                let step = step.min(steps.saturating_sub(1));
+                Some((start_tps + step_tps * step) as f64)
+            }
+            LoadProfile::Spike { base_tps, spike_tps, spike_at_secs, spike_secs } => {
+                let spike_at = spike_at_secs as f64;
+                if elapsed_secs >= spike_at && elapsed_secs < spike_at + spike_secs as f64 {
+                    Some(spike_tps as f64)
+                } else {
+                    Some(base_tps as f64)
+                }
+            }
+        }
+    }
+}
+
+/// Think time, the pause of each worker between its operations, on top of the rate limit
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type")]
+pub enum ThinkTime {
+    /// Next operation right away
+    #[default]
+    None,
+    /// Same pause every time
+    Fixed { ms: u64 },
+    /// Pause drawn uniformly between `min_ms` and `max_ms`
+    Uniform { min_ms: u64, max_ms: u64 },
+    /// Pause drawn from the exponential distribution with `mean_ms` mean,
+    /// mostly short pauses with occasional long ones, like users between actions
+    Exponential { mean_ms: u64 },
+}
+
+impl ThinkTime {
+    /// Draw a pause from the distribution
+    pub fn sample(&self) -> Duration {
+        let ms = match *self {
+            ThinkTime::None => 0.0,
+            ThinkTime::Fixed { ms } => ms as f64,
+            ThinkTime::Uniform { min_ms, max_ms } => {
+                rand::thread_rng().gen_range(min_ms.min(max_ms)..=max_ms.max(min_ms)) as f64
+            }
+            ThinkTime::Exponential { mean_ms } => {
+                // Inverse transform sampling, `1 - u` is in (0, 1] so the logarithm is finite
+                let u: f64 = rand::thread_rng().gen();
+                -(mean_ms as f64) * (1.0 - u).ln()
+            }
+        };
+        Duration::from_secs_f64(ms / 1000.0)
+    }
+
+    /// Mean pause of the distribution
+    pub fn mean(&self) -> Duration {
+        let ms = match *self {
+            ThinkTime::None => 0.0,
+            ThinkTime::Fixed { ms } => ms as f64,
+            ThinkTime::Uniform { min_ms, max_ms } => (min_ms + max_ms) as f64 / 2.0,
+            ThinkTime::Exponential { mean_ms } => mean_ms as f64,
+        };
+        Duration::from_secs_f64(ms / 1000.0)
+    }
+
+    /// Pause the calling worker for a drawn think time
+    pub async fn pause(&self) {
+        if *self != ThinkTime::None {
+            tokio::time::sleep(self.sample()).await;
+        }
+    }
+}
+
+impl std::fmt::Display for ThinkTime {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ThinkTime::None => write!(f, "none"),
+            ThinkTime::Fixed { ms } => write!(f, "fixed {}ms", ms),
+            ThinkTime::Uniform { min_ms, max_ms } => write!(f, "uniform {}-{}ms", min_ms, max_ms),
+            ThinkTime::Exponential { mean_ms } => write!(f, "exponential, mean {}ms", mean_ms),
+        }
+    }
+}
+
+/// Parse `none`, `fixed:<MS>`, `uniform:<MIN_MS>-<MAX_MS>` or `exponential:<MEAN_MS>`
+impl FromStr for ThinkTime {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let (kind, params) = s.split_once(':').unwrap_or((s, ""));
+        let ms = |value: &str| -> Result<u64> {
+            value
+                .trim()
+                .parse()
+                .with_context(|| format!("Invalid think time milliseconds '{}'", value))
+        };
+
+        match kind.trim().to_lowercase().as_str() {
+            "none" => Ok(ThinkTime::None),
+            "fixed" => Ok(ThinkTime::Fixed { ms: ms(params)? }),
+            "uniform" => {
+                let (min, max) = params
+                    .split_once('-')
+                    .context("Uniform think time is 'uniform:<MIN_MS>-<MAX_MS>'")?;
+                let (min_ms, max_ms) = (ms(min)?, ms(max)?);
+                anyhow::ensure!(min_ms <= max_ms, "Uniform think time minimum exceeds the maximum");
+                Ok(ThinkTime::Uniform { min_ms, max_ms })
+            }
+            "exponential" | "exp" => Ok(ThinkTime::Exponential { mean_ms: ms(params)? }),
+            _ => anyhow::bail!(
+                "Unknown think time '{}', expected none, fixed:<MS>, uniform:<MIN_MS>-<MAX_MS> or exponential:<MEAN_MS>",
+                s
+            ),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(tag = "type")]
 pub enum ScenarioConfig {
     Basic {
         count: usize,
         iterations: u64,
+        /// Contract call in place of the built-in one
+        #[serde(default)]
+        call: Option<CallTemplate>,
     },
     Storage {
         writes: u64,
         reads: u64,
         count: usize,
+        /// Contract call in place of the built-in one
+        #[serde(default)]
+        call: Option<CallTemplate>,
     },
     Calldata {
         min_size: usize,
         max_size: usize,
         increment: usize,
+        /// Contract call in place of the built-in one
+        #[serde(default)]
+        call: Option<CallTemplate>,
     },
     BatchMint {
         token_type: String,
         batch_size: usize,
         batches: usize,
+        /// Not supported, the scenario calls several built-in functions, rejected when set
+        #[serde(default)]
+        call: Option<CallTemplate>,
     },
     ExternalCall {
         call_type: String,
         gas_limit: u64,
         count: usize,
+        /// Contract call in place of the built-in one
+        #[serde(default)]
+        call: Option<CallTemplate>,
     },
     Crypto {
         test_type: String,
         count: usize,
+        /// Not supported, the scenario calls several built-in functions, rejected when set
+        #[serde(default)]
+        call: Option<CallTemplate>,
     },
     Mixed {
         duration: u64,
         profile: String,
+        /// Not supported, the scenario calls several built-in functions, rejected when set
+        #[serde(default)]
+        call: Option<CallTemplate>,
     },
     Stress {
         ramp_up: u64,
         peak: u64,
         ramp_down: u64,
         target_tps: u64,
+        /// Contract call in place of the built-in one
+        #[serde(default)]
+        call: Option<CallTemplate>,
     },
     Endurance {
         hours: u64,
         tps: u64,
+        /// Contract call in place of the built-in one
+        #[serde(default)]
+        call: Option<CallTemplate>,
+    },
+    Replay {
+        file: String,
+    },
+    External {
+        source: String,
+        #[serde(default = "default_poll_interval_ms")]
+        poll_interval_ms: u64,
+        #[serde(default)]
+        max_operations: Option<usize>,
     },
 }
 
+fn default_poll_interval_ms() -> u64 {
+    500
+}
+
+impl ScenarioConfig {
+    /// Scenarios sending transactions from a single signer at high concurrency,
+    /// which would contend on nonces
+    pub fn is_write_heavy(&self) -> bool {
+        matches!(self, ScenarioConfig::Storage { .. } | ScenarioConfig::BatchMint { .. })
+    }
+}
+
 impl LoadTestConfig {
     pub fn from_cli(cli: &Cli) -> Result<Self> {
         let scenario = match &cli.scenario {
             TestScenario::Basic { count, iterations } => ScenarioConfig::Basic {
                 count: *count,
                 iterations: *iterations,
+                call: None,
             },
             TestScenario::Storage { writes, reads, count } => ScenarioConfig::Storage {
                 writes: *writes,
                 reads: *reads,
                 count: *count,
+                call: None,
             },
             TestScenario::Calldata { min_size, max_size, increment } => ScenarioConfig::Calldata {
                 min_size: *min_size,
                 max_size: *max_size,
                 increment: *increment,
+                call: None,
             },
             TestScenario::BatchMint { token_type, batch_size, batches } => ScenarioConfig::BatchMint {
                 token_type: format!("{:?}", token_type),
                 batch_size: *batch_size,
                 batches: *batches,
+                call: None,
             },
             TestScenario::ExternalCall { call_type, gas_limit, count } => ScenarioConfig::ExternalCall {
                 call_type: format!("{:?}", call_type),
                 gas_limit: *gas_limit,
                 count: *count,
+                call: None,
             },
             TestScenario::Crypto { test_type, count } => ScenarioConfig::Crypto {
                 test_type: format!("{:?}", test_type),
                 count: *count,
+                call: None,
             },
             TestScenario::Mixed { duration, profile } => ScenarioConfig::Mixed {
                 duration: *duration,
                 profile: format!("{:?}", profile),
+                call: None,
             },
             TestScenario::Stress { ramp_up, peak, ramp_down, target_tps } => ScenarioConfig::Stress {
                 ramp_up: *ramp_up,
                 peak: *peak,
                 ramp_down: *ramp_down,
                 target_tps: *target_tps,
+                call: None,
             },
             TestScenario::Endurance { hours, tps } => ScenarioConfig::Endurance {
                 hours: *hours,
                 tps: *tps,
+                call: None,
+            },
+            TestScenario::Replay { file } => ScenarioConfig::Replay {
+                file: file.to_string_lossy().to_string(),
+            },
+            TestScenario::External { source, poll_interval_ms, max_operations } => ScenarioConfig::External {
+                source: source.clone(),
+                poll_interval_ms: *poll_interval_ms,
+                max_operations: *max_operations,
             },
             TestScenario::Custom { config } => {
                 return Self::from_file(config);
             }
         };
 
+        let compare_rpc_headers = if cli.compare_rpc_headers.is_empty() {
+            None
+        } else {
+            Some(parse_rpc_headers(&cli.compare_rpc_headers)?)
+        };
+
         Ok(Self {
             rpc_url: cli.rpc_url.clone(),
+            rpc_headers: parse_rpc_headers(&cli.rpc_headers)?,
             contract_address: cli.contract.clone(),
             private_key: cli.private_key.clone(),
             chain_id: cli.chain_id,
@@ -125,10 +421,57 @@ impl LoadTestConfig {
             workers: cli.workers,
             duration: cli.duration,
             rate_limit: cli.rate_limit,
+            warmup_operations: cli.warmup_operations,
+            load_profile: LoadProfile::Constant,
+            error_rate_threshold: default_error_rate_threshold(),
+            think_time: cli.think_time,
+            preallocate_nonces: cli.preallocate_nonces,
+            compare: cli.compare_rpc_url.as_ref().map(|rpc_url| CompareEndpoint {
+                rpc_url: rpc_url.clone(),
+                rpc_headers: compare_rpc_headers,
+                chain_id: cli.compare_chain_id,
+                contract_address: cli.compare_contract.clone(),
+                private_key: cli.compare_private_key.clone(),
+            }),
             scenario,
         })
     }
 
+    /// Config of the compared endpoint: this config with the endpoint overrides, without comparison.
+    /// The RPC headers, e.g. the provider API key, are inherited only by the endpoint with the same URL,
+    /// another endpoint fails without its own headers.
+    pub fn compare_config(&self) -> Result<Option<Self>> {
+        let Some(compare) = self.compare.as_ref() else {
+            return Ok(None);
+        };
+
+        let rpc_headers = match &compare.rpc_headers {
+            Some(headers) => headers.clone(),
+            None if compare.rpc_url == self.rpc_url || self.rpc_headers.is_empty() => self.rpc_headers.clone(),
+            None => anyhow::bail!(
+                "The baseline RPC headers aren't sent to the compared endpoint {}, set its headers with \
+                 --compare-rpc-header (or 'compare.rpc_headers' in the config file)",
+                compare.rpc_url
+            ),
+        };
+
+        Ok(Some(Self {
+            rpc_url: compare.rpc_url.clone(),
+            rpc_headers,
+            chain_id: compare.chain_id.unwrap_or(self.chain_id),
+            contract_address: compare
+                .contract_address
+                .clone()
+                .unwrap_or_else(|| self.contract_address.clone()),
+            private_key: compare
+                .private_key
+                .clone()
+                .unwrap_or_else(|| self.private_key.clone()),
+            compare: None,
+            ..self.clone()
+        }))
+    }
+
     pub fn from_file(path: &Path) -> Result<Self> {
         let content = std::fs::read_to_string(path)
             .context("Failed to read config file")?;
@@ -2,6 +2,8 @@
 
 mod cli;
 mod config;
+mod nonce;
+mod report;
 mod runner;
 mod scenarios;
 mod stats;
@@ -13,7 +15,7 @@ use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
 
 use cli::Cli;
 use config::LoadTestConfig;
-use runner::TestRunner;
+use runner::{ComparisonRunner, TestRunner};
 
 #[tokio::main]
 async fn main() -> Result<()> {
@@ -29,9 +31,36 @@ async fn main() -> Result<()> {
     // Load configuration
     let config = LoadTestConfig::from_cli(&cli)?;
 
+    // Run the scenario on both endpoints when comparing
+    if config.compare.is_some() {
+        let mut runner = ComparisonRunner::new(config).await?;
+        let report = runner.run().await?;
+
+        if let Some(path) = &cli.report {
+            report.save_json(path)?;
+            println!("Comparison report saved to {}", path.display());
+        }
+        if let Some(path) = &cli.report_csv {
+            report.save_csv(path)?;
+            println!("Comparison CSV report saved to {}", path.display());
+        }
+
+        return Ok(());
+    }
+
     // Create and run test runner
     let mut runner = TestRunner::new(config).await?;
-    runner.run().await?;
+    let report = runner.run().await?;
+
+    // Export results report
+    if let Some(path) = &cli.report {
+        report.save_json(path)?;
+        println!("Report saved to {}", path.display());
+    }
+    if let Some(path) = &cli.report_csv {
+        report.save_csv(path)?;
+        println!("CSV report saved to {}", path.display());
+    }
 
     Ok(())
 }
@@ -0,0 +1,75 @@
+//! Pre-allocated nonce range of the signer, for write-heavy scenarios
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::sync::Mutex;
+
+use tx_producer::{Address, NonceManager};
+
+/// Contiguous nonce range starting at the signer pending nonce, fetched once before the test.
+/// Workers take the next nonce of the range with an atomic increment, so they never
+/// contend on nonce fetching from the node. Nonces of transactions which weren't broadcast
+/// are released and handed out again first, to fill the gaps they'd leave.
+/// Only safe when the test is the single writer of the signing key.
+pub struct PreallocatedNonces {
+    start: u64,
+    next: AtomicU64,
+    released: Mutex<BTreeSet<u64>>,
+    released_count: AtomicUsize,
+}
+
+/// Nonces used by the test
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NonceReport {
+    /// First nonce of the range
+    pub start: u64,
+    /// Next nonce after the range
+    pub end: u64,
+    /// Released nonces not handed out again, transactions at the later nonces
+    /// stay queued by the node until the gaps are filled
+    pub gaps: Vec<u64>,
+}
+
+impl PreallocatedNonces {
+    pub fn new(start: u64) -> Self {
+        Self {
+            start,
+            next: AtomicU64::new(start),
+            released: Mutex::new(BTreeSet::new()),
+            released_count: AtomicUsize::new(0),
+        }
+    }
+
+    pub fn report(&self) -> NonceReport {
+        NonceReport {
+            start: self.start,
+            end: self.next.load(Ordering::SeqCst),
+            gaps: self.released.lock().unwrap().iter().copied().collect(),
+        }
+    }
+}
+
+#[async_trait]
+impl NonceManager for PreallocatedNonces {
+    async fn next_nonce(&self, _address: Address) -> tx_producer::Result<u64> {
+        // Lock only when there are released nonces to hand out again
+        if self.released_count.load(Ordering::SeqCst) > 0 {
+            let mut released = self.released.lock().unwrap();
+            if let Some(nonce) = released.pop_first() {
+                self.released_count.store(released.len(), Ordering::SeqCst);
+                return Ok(nonce);
+            }
+        }
+
+        Ok(self.next.fetch_add(1, Ordering::SeqCst))
+    }
+
+    async fn release_nonce(&self, _address: Address, nonce: u64) -> tx_producer::Result<()> {
+        let mut released = self.released.lock().unwrap();
+        released.insert(nonce);
+        self.released_count.store(released.len(), Ordering::SeqCst);
+        Ok(())
+    }
+}
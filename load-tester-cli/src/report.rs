@@ -0,0 +1,207 @@
+//! Test results report export
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::Path;
+use std::time::Duration;
+
+use crate::config::LoadTestConfig;
+use crate::nonce::NonceReport;
+use crate::stats::{ErrorCategory, ErrorRateCrossing, LatencySummary, TestStatistics};
+
+/// CSV header of the report rows
+const CSV_HEADER: &str = "scenario,build_label,started_at,duration_secs,total_transactions,\
+                          successful_transactions,failed_transactions,success_rate,tps,avg_latency_ms,\
+                          p50_latency_ms,p95_latency_ms,p99_latency_ms,max_latency_ms,total_gas_used,avg_gas_per_tx";
+
+/// Environment variable with the build label (e.g. git commit hash),
+/// read at run time and falling back to its value at build time
+const BUILD_LABEL_ENV: &str = "BUILD_LABEL";
+
+/// Final test results, for regression tracking across runs
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TestReport {
+    pub scenario: String,
+    pub build_label: Option<String>,
+    pub started_at: String,
+    pub duration_secs: f64,
+    pub total_transactions: u64,
+    pub successful_transactions: u64,
+    pub failed_transactions: u64,
+    pub success_rate: f64,
+    pub tps: f64,
+    pub avg_latency_ms: f64,
+    pub latency: LatencySummary,
+    pub total_gas_used: u64,
+    pub avg_gas_per_tx: f64,
+    pub error_categories: BTreeMap<ErrorCategory, u64>,
+    pub error_rate_crossing: Option<ErrorRateCrossing>,
+    /// Stopped with Ctrl-C before the scenario completed
+    #[serde(default)]
+    pub interrupted: bool,
+    /// Pre-allocated nonces range and the gaps left by failed sends
+    #[serde(default)]
+    pub nonces: Option<NonceReport>,
+    pub config: LoadTestConfig,
+}
+
+impl TestReport {
+    pub fn new(
+        scenario: &str,
+        config: &LoadTestConfig,
+        stats: &TestStatistics,
+        started_at: chrono::DateTime<chrono::Utc>,
+        duration: Duration,
+        interrupted: bool,
+    ) -> Self {
+        // Never leak the signer key or the RPC credentials into reports
+        let mut config = config.clone();
+        config.private_key = "<redacted>".to_string();
+        redact_headers(&mut config.rpc_headers);
+        if let Some(compare) = config.compare.as_mut() {
+            compare.private_key = compare.private_key.as_ref().map(|_| "<redacted>".to_string());
+            if let Some(headers) = compare.rpc_headers.as_mut() {
+                redact_headers(headers);
+            }
+        }
+
+        let build_label = std::env::var(BUILD_LABEL_ENV)
+            .ok()
+            .or_else(|| option_env!("BUILD_LABEL").map(String::from))
+            .filter(|label| !label.is_empty());
+
+        Self {
+            scenario: scenario.to_string(),
+            build_label,
+            started_at: started_at.to_rfc3339(),
+            duration_secs: duration.as_secs_f64(),
+            total_transactions: stats.total_transactions,
+            successful_transactions: stats.successful_transactions,
+            failed_transactions: stats.failed_transactions,
+            success_rate: stats.success_rate(),
+            tps: stats.tps(duration),
+            avg_latency_ms: stats.avg_latency_ms(),
+            latency: stats.latency_summary(),
+            total_gas_used: stats.total_gas_used,
+            avg_gas_per_tx: stats.avg_gas_per_tx(),
+            error_categories: stats.error_categories.clone(),
+            error_rate_crossing: stats.error_rate_crossing,
+            interrupted,
+            nonces: None,
+            config,
+        }
+    }
+
+    pub fn save_json(&self, path: &Path) -> Result<()> {
+        let content = serde_json::to_string_pretty(self)?;
+        std::fs::write(path, content)
+            .with_context(|| format!("Failed to write JSON report to {}", path.display()))?;
+        Ok(())
+    }
+
+    /// Save as a single row CSV with header, error categories are not included
+    pub fn save_csv(&self, path: &Path) -> Result<()> {
+        std::fs::write(path, format!("{}\n{}\n", CSV_HEADER, self.csv_row()))
+            .with_context(|| format!("Failed to write CSV report to {}", path.display()))?;
+        Ok(())
+    }
+
+    fn csv_row(&self) -> String {
+        format!(
+            "\"{}\",\"{}\",{},{:.3},{},{},{},{:.4},{:.2},{:.3},{:.3},{:.3},{:.3},{:.3},{},{:.2}",
+            self.scenario.replace('"', "\"\""),
+            self.build_label.as_deref().unwrap_or_default().replace('"', "\"\""),
+            self.started_at,
+            self.duration_secs,
+            self.total_transactions,
+            self.successful_transactions,
+            self.failed_transactions,
+            self.success_rate,
+            self.tps,
+            self.avg_latency_ms,
+            self.latency.p50_ms,
+            self.latency.p95_ms,
+            self.latency.p99_ms,
+            self.latency.max_ms,
+            self.total_gas_used,
+            self.avg_gas_per_tx,
+        )
+    }
+
+    /// Failed transactions share of the total
+    pub fn error_rate(&self) -> f64 {
+        if self.total_transactions == 0 {
+            0.0
+        } else {
+            self.failed_transactions as f64 / self.total_transactions as f64
+        }
+    }
+}
+
+/// Results of the same scenario run on two endpoints concurrently
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ComparisonReport {
+    pub baseline: TestReport,
+    pub candidate: TestReport,
+    pub delta: ComparisonDelta,
+}
+
+/// Candidate results minus the baseline results
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct ComparisonDelta {
+    pub tps: f64,
+    /// TPS change relative to the baseline, `None` when the baseline TPS is zero
+    pub tps_change_pct: Option<f64>,
+    pub avg_latency_ms: f64,
+    pub p50_latency_ms: f64,
+    pub p95_latency_ms: f64,
+    pub p99_latency_ms: f64,
+    pub max_latency_ms: f64,
+    pub error_rate: f64,
+}
+
+impl ComparisonReport {
+    pub fn new(baseline: TestReport, candidate: TestReport) -> Self {
+        let delta = ComparisonDelta {
+            tps: candidate.tps - baseline.tps,
+            tps_change_pct: (baseline.tps > 0.0)
+                .then(|| (candidate.tps - baseline.tps) / baseline.tps * 100.0),
+            avg_latency_ms: candidate.avg_latency_ms - baseline.avg_latency_ms,
+            p50_latency_ms: candidate.latency.p50_ms - baseline.latency.p50_ms,
+            p95_latency_ms: candidate.latency.p95_ms - baseline.latency.p95_ms,
+            p99_latency_ms: candidate.latency.p99_ms - baseline.latency.p99_ms,
+            max_latency_ms: candidate.latency.max_ms - baseline.latency.max_ms,
+            error_rate: candidate.error_rate() - baseline.error_rate(),
+        };
+
+        Self { baseline, candidate, delta }
+    }
+
+    pub fn save_json(&self, path: &Path) -> Result<()> {
+        let content = serde_json::to_string_pretty(self)?;
+        std::fs::write(path, content)
+            .with_context(|| format!("Failed to write JSON report to {}", path.display()))?;
+        Ok(())
+    }
+
+    /// Save as CSV with a row per endpoint, the delta is not included
+    pub fn save_csv(&self, path: &Path) -> Result<()> {
+        let content = format!(
+            "endpoint,rpc_url,{}\nbaseline,\"{}\",{}\ncandidate,\"{}\",{}\n",
+            CSV_HEADER,
+            self.baseline.config.rpc_url.replace('"', "\"\""),
+            self.baseline.csv_row(),
+            self.candidate.config.rpc_url.replace('"', "\"\""),
+            self.candidate.csv_row(),
+        );
+
+        std::fs::write(path, content)
+            .with_context(|| format!("Failed to write CSV report to {}", path.display()))?;
+        Ok(())
+    }
+}
+
+fn redact_headers(headers: &mut std::collections::HashMap<String, String>) {
+    headers.values_mut().for_each(|value| *value = "<redacted>".to_string());
+}
@@ -2,26 +2,81 @@
 
 use anyhow::{Context, Result};
 use colored::Colorize;
-use indicatif::{ProgressBar, ProgressStyle};
+use indicatif::{MultiProgress, ProgressBar, ProgressStyle};
 use std::sync::Arc;
 use std::time::{Duration, Instant};
-use tokio::time::sleep;
+use tokio::sync::Semaphore;
+use tokio::time::{interval, sleep, MissedTickBehavior};
+use tokio_util::task::TaskTracker;
 use tracing::{info, warn, error};
 
 use tx_producer::prelude::*;
 
-use crate::config::{LoadTestConfig, ScenarioConfig};
-use crate::scenarios::{self, TestScenario as ScenarioTrait};
-use crate::stats::TestStatistics;
+use crate::config::{LoadProfile, LoadTestConfig, ScenarioConfig, ThinkTime};
+use crate::nonce::PreallocatedNonces;
+use crate::report::{ComparisonReport, TestReport};
+use crate::scenarios::{self, CallTemplate, TestScenario as ScenarioTrait};
+use crate::stats::{ErrorRateCrossing, TestStatistics};
+
+/// Interval of the periodic progress report
+const PROGRESS_REPORT_INTERVAL_SECS: u64 = 10;
+
+/// Tick of the load profile pacing and error rate monitoring
+const PACING_TICK_MS: u64 = 100;
+
+/// Lowest paced rate, so a zero rate profile can't stall the scenario forever
+const MIN_PACED_TPS: f64 = 1.0;
+
+/// Minimal completed transactions count in the error rate window
+const MIN_ERROR_RATE_WINDOW_SAMPLES: u64 = 10;
+
+/// Longest wait for the in-flight operations after Ctrl-C
+const SHUTDOWN_GRACE_SECS: u64 = 5;
+
+/// Progress bar of the scenario operations, a spinner for unbounded scenarios.
+/// The bar prefix labels the endpoint in the comparison mode.
+fn new_progress_bar(total_operations: Option<usize>) -> ProgressBar {
+    match total_operations {
+        Some(total) => {
+            let progress = ProgressBar::new(total as u64);
+            progress.set_style(
+                ProgressStyle::default_bar()
+                    .template("{prefix}{spinner:.green} [{bar:40.cyan/blue}] {pos}/{len} ({percent}%) {msg}")
+                    .unwrap()
+                    .progress_chars("█▓▒░"),
+            );
+            progress
+        }
+        // Unbounded scenario, runs until stopped
+        None => {
+            let progress = ProgressBar::new_spinner();
+            progress.set_style(
+                ProgressStyle::default_spinner()
+                    .template("{prefix}{spinner:.green} {pos} ops [{elapsed_precise}] {msg}")
+                    .unwrap(),
+            );
+            progress.enable_steady_tick(Duration::from_millis(100));
+            progress
+        }
+    }
+}
 
 pub struct TestRunner {
     config: LoadTestConfig,
     contract: Arc<ContractClient>,
     stats: Arc<tokio::sync::RwLock<TestStatistics>>,
+    rate_gate: Option<Arc<Semaphore>>,
+    nonces: Option<Arc<PreallocatedNonces>>,
 }
 
 impl TestRunner {
     pub async fn new(config: LoadTestConfig) -> Result<Self> {
+        Self::with_nonces(config, None).await
+    }
+
+    /// Runner taking the signer nonces from the given range, shared with another runner
+    /// signing with the same key, instead of allocating its own
+    async fn with_nonces(config: LoadTestConfig, shared_nonces: Option<Arc<PreallocatedNonces>>) -> Result<Self> {
         info!("Initializing test runner...");
 
         // Configure provider
@@ -29,10 +84,12 @@ impl TestRunner {
             rpc_url: config.rpc_url.clone(),
             chain_id: config.chain_id,
             timeout_seconds: 60,
+            ws_url: None,
+            headers: config.rpc_headers.clone(),
         };
 
         // Create provider with signer
-        let provider_manager = ProviderManager::new(provider_config)
+        let mut provider_manager = ProviderManager::new(provider_config)
             .context("Failed to create provider")?
             .with_signer(&config.private_key)
             .context("Failed to add signer")?;
@@ -41,6 +98,24 @@ impl TestRunner {
         let block_number = provider_manager.check_connection().await?;
         info!("Connected to network at block {}", block_number);
 
+        // Workers take nonces from a range starting at the signer pending nonce, instead of the node
+        let nonces = if let Some(nonces) = shared_nonces {
+            provider_manager = provider_manager.with_nonce_manager(nonces.clone());
+            info!("Nonces shared with the other endpoint runner");
+            Some(nonces)
+        } else if config.preallocate_nonces.unwrap_or_else(|| config.scenario.is_write_heavy()) {
+            let start = provider_manager
+                .next_nonce()
+                .await
+                .context("Failed to get the signer nonce")?;
+            let nonces = Arc::new(PreallocatedNonces::new(start));
+            provider_manager = provider_manager.with_nonce_manager(nonces.clone());
+            info!("Nonces pre-allocated from {}", start);
+            Some(nonces)
+        } else {
+            None
+        };
+
         // Configure contract
         let contract_address: alloy_primitives::Address = config.contract_address
             .parse()
@@ -49,6 +124,8 @@ impl TestRunner {
         let contract_config = ContractConfig {
             address: contract_address,
             abi_path: config.abi_path.clone(),
+            // Fail fast on a wrong network rather than with every operation reverting
+            verify_deployed: true,
         };
 
         // Create contract client
@@ -59,6 +136,13 @@ impl TestRunner {
         .await
         .context("Failed to create contract client")?;
 
+        // Load profile paces the scenario operations through the rate gate
+        let rate_gate = if matches!(config.load_profile, LoadProfile::Constant) {
+            None
+        } else {
+            Some(Arc::new(Semaphore::new(0)))
+        };
+
         info!("Contract client initialized: {}", contract_address);
 
         let stats = Arc::new(tokio::sync::RwLock::new(TestStatistics::new()));
@@ -67,10 +151,12 @@ impl TestRunner {
             config,
             contract: Arc::new(contract),
             stats,
+            rate_gate,
+            nonces,
         })
     }
 
-    pub async fn run(&mut self) -> Result<()> {
+    pub async fn run(&mut self) -> Result<TestReport> {
         println!("{}", "Starting load test...".bright_green().bold());
         println!();
 
@@ -79,83 +165,280 @@ impl TestRunner {
         // Create scenario
         let scenario = self.create_scenario()?;
 
+        // Warm up before statistics collection
+        if self.config.warmup_operations > 0 {
+            self.warmup(scenario.as_ref()).await;
+        }
+
         // Run test
-        let start_time = Instant::now();
-        self.execute_scenario(scenario).await?;
-        let total_duration = start_time.elapsed();
+        let progress = new_progress_bar(scenario.total_operations());
+        let report = self.measure(scenario, progress).await?;
 
         // Print results
         println!();
-        self.print_results(total_duration).await?;
+        self.print_results(Duration::from_secs_f64(report.duration_secs), report.interrupted).await?;
 
-        Ok(())
+        Ok(report)
     }
 
+    /// Execute the scenario collecting statistics, into the results report
+    async fn measure(&self, scenario: Box<dyn ScenarioTrait>, progress: ProgressBar) -> Result<TestReport> {
+        let scenario_name = scenario.name().to_string();
+
+        let started_at = chrono::Utc::now();
+        let start_time = Instant::now();
+        let interrupted = self.execute_scenario(scenario, progress).await?;
+        let total_duration = start_time.elapsed();
+
+        let stats = self.stats.read().await;
+        let mut report = TestReport::new(&scenario_name, &self.config, &stats, started_at, total_duration, interrupted);
+        report.nonces = self.nonces.as_ref().map(|nonces| nonces.report());
+        Ok(report)
+    }
+
+    /// Create the configured scenario, checking its call template against the contract ABI
     fn create_scenario(&self) -> Result<Box<dyn ScenarioTrait>> {
+        let scenario = self.build_scenario()?;
+
+        if let Some(call) = scenario.call_template() {
+            call.validate(&self.contract, &scenario.template_values(0))
+                .context("Invalid scenario call template")?;
+            info!("Scenario calls {} in place of its built-in call", call.function);
+        }
+
+        Ok(scenario)
+    }
+
+    fn build_scenario(&self) -> Result<Box<dyn ScenarioTrait>> {
         match &self.config.scenario {
-            ScenarioConfig::Basic { count, iterations } => {
-                Ok(Box::new(scenarios::BasicScenario::new(*count, *iterations)))
+            ScenarioConfig::Basic { count, iterations, call } => {
+                Ok(Box::new(scenarios::BasicScenario::new(*count, *iterations).with_call(call.clone())))
             }
-            ScenarioConfig::Storage { writes, reads, count } => {
-                Ok(Box::new(scenarios::StorageScenario::new(*writes, *reads, *count)))
+            ScenarioConfig::Storage { writes, reads, count, call } => {
+                Ok(Box::new(scenarios::StorageScenario::new(*writes, *reads, *count).with_call(call.clone())))
             }
-            ScenarioConfig::Calldata { min_size, max_size, increment } => {
-                Ok(Box::new(scenarios::CalldataScenario::new(*min_size, *max_size, *increment)))
+            ScenarioConfig::Calldata { min_size, max_size, increment, call } => {
+                Ok(Box::new(
+                    scenarios::CalldataScenario::new(*min_size, *max_size, *increment).with_call(call.clone()),
+                ))
             }
-            ScenarioConfig::BatchMint { token_type, batch_size, batches } => {
+            ScenarioConfig::BatchMint { token_type, batch_size, batches, call } => {
+                reject_call_template("batch mint", call)?;
                 Ok(Box::new(scenarios::BatchMintScenario::new(
                     token_type.clone(),
                     *batch_size,
                     *batches,
                 )))
             }
-            ScenarioConfig::ExternalCall { call_type, gas_limit, count } => {
+            ScenarioConfig::ExternalCall { call_type, gas_limit, count, call } => {
                 Ok(Box::new(scenarios::ExternalCallScenario::new(
                     call_type.clone(),
                     *gas_limit,
                     *count,
-                )))
+                ).with_call(call.clone())))
             }
-            ScenarioConfig::Crypto { test_type, count } => {
+            ScenarioConfig::Crypto { test_type, count, call } => {
+                reject_call_template("crypto", call)?;
                 Ok(Box::new(scenarios::CryptoScenario::new(test_type.clone(), *count)))
             }
-            ScenarioConfig::Mixed { duration, profile } => {
+            ScenarioConfig::Mixed { duration, profile, call } => {
+                reject_call_template("mixed", call)?;
                 Ok(Box::new(scenarios::MixedScenario::new(*duration, profile.clone())))
             }
-            ScenarioConfig::Stress { ramp_up, peak, ramp_down, target_tps } => {
+            ScenarioConfig::Stress { ramp_up, peak, ramp_down, target_tps, call } => {
                 Ok(Box::new(scenarios::StressScenario::new(
                     *ramp_up,
                     *peak,
                     *ramp_down,
                     *target_tps,
-                )))
+                ).with_call(call.clone())))
             }
-            ScenarioConfig::Endurance { hours, tps } => {
-                Ok(Box::new(scenarios::EnduranceScenario::new(*hours, *tps)))
+            ScenarioConfig::Endurance { hours, tps, call } => {
+                Ok(Box::new(scenarios::EnduranceScenario::new(*hours, *tps).with_call(call.clone())))
+            }
+            ScenarioConfig::Replay { file } => {
+                let scenario = scenarios::ReplayScenario::from_file(std::path::Path::new(file))?;
+                Ok(Box::new(scenario))
+            }
+            ScenarioConfig::External { source, poll_interval_ms, max_operations } => {
+                let source = scenarios::WorkSource::parse(source)?;
+                Ok(Box::new(scenarios::ExternalSourceScenario::new(source, *poll_interval_ms, *max_operations)))
             }
         }
     }
 
-    async fn execute_scenario(&self, scenario: Box<dyn ScenarioTrait>) -> Result<()> {
-        let progress = ProgressBar::new(scenario.total_operations() as u64);
-        progress.set_style(
-            ProgressStyle::default_bar()
-                .template("{spinner:.green} [{bar:40.cyan/blue}] {pos}/{len} ({percent}%) {msg}")
-                .unwrap()
-                .progress_chars("█▓▒░"),
-        );
+    /// Execute warmup operations, discarding their timings and results
+    async fn warmup(&self, scenario: &dyn ScenarioTrait) {
+        let operations = self.config.warmup_operations;
+        info!("Warming up with {} operations...", operations);
 
-        // Execute scenario
-        scenario.execute(
-            self.contract.clone(),
-            self.stats.clone(),
-            progress.clone(),
-            self.config.workers,
-            self.config.rate_limit,
-        ).await?;
+        let start_time = Instant::now();
+        let workers = self.config.workers.max(1) as u64;
+        let mut failed = 0;
+
+        // Run in rounds of workers count concurrent operations
+        let mut remaining = operations;
+        while remaining > 0 {
+            let round = remaining.min(workers);
+            let results = futures::future::join_all(
+                (0..round).map(|_| scenario.warmup_operation(&self.contract))
+            ).await;
+            for result in results {
+                if let Err(e) = result {
+                    failed += 1;
+                    warn!("Warmup operation failed: {}", e);
+                }
+            }
+            remaining -= round;
+        }
 
-        progress.finish_with_message("Complete");
-        Ok(())
+        info!("Warmup completed in {:.2}s ({} of {} operations failed), measurement begins",
+              start_time.elapsed().as_secs_f64(), failed, operations);
+        println!("{}", "Warmup completed, measurement begins".bright_green());
+        println!();
+    }
+
+    /// Execute the scenario until it completes or Ctrl-C is pressed, returns whether it was interrupted
+    async fn execute_scenario(&self, scenario: Box<dyn ScenarioTrait>, progress: ProgressBar) -> Result<bool> {
+        // Operations spawned by the scenario, tracked to wait for the in-flight ones on Ctrl-C
+        let operations = TaskTracker::new();
+
+        // Periodic progress report with latency percentiles
+        let reporter = {
+            let stats = self.stats.clone();
+            let latency_histograms = stats.read().await.latency_histograms();
+            let progress = progress.clone();
+            let start_time = Instant::now();
+
+            tokio::spawn(async move {
+                loop {
+                    sleep(Duration::from_secs(PROGRESS_REPORT_INTERVAL_SECS)).await;
+
+                    let (total, tps) = {
+                        let stats = stats.read().await;
+                        (stats.total_transactions, stats.tps(start_time.elapsed()))
+                    };
+                    progress.println(format!(
+                        "{}[{:.0}s] {} txs, TPS: {:.2}, latency {}",
+                        progress.prefix(),
+                        start_time.elapsed().as_secs_f64(),
+                        total,
+                        tps,
+                        latency_histograms.summary(),
+                    ));
+                }
+            })
+        };
+
+        let controller = self.spawn_load_controller();
+
+        // Scenario rate limit is disabled when the load profile paces transactions
+        let pacing = scenarios::Pacing::new(self.config.rate_limit, self.rate_gate.clone());
+
+        // Execute scenario, on Ctrl-C the scenario is dropped and no new operations are issued
+        let (result, interrupted) = tokio::select! {
+            result = scenario.execute(
+                self.contract.clone(),
+                self.stats.clone(),
+                progress.clone(),
+                self.config.workers,
+                &pacing,
+                self.config.think_time,
+                &operations,
+            ) => (result, false),
+            _ = tokio::signal::ctrl_c() => (Ok(()), true),
+        };
+
+        controller.abort();
+        reporter.abort();
+        let _ = tokio::join!(controller, reporter);
+        result?;
+
+        if interrupted {
+            wait_in_flight(&operations, &progress).await;
+            progress.abandon_with_message("Interrupted");
+        } else {
+            progress.finish_with_message("Complete");
+        }
+
+        Ok(interrupted)
+    }
+
+    /// Spawn the task adding rate gate permits by the load profile target rate,
+    /// and recording when the error rate first crosses the configured threshold
+    fn spawn_load_controller(&self) -> tokio::task::JoinHandle<()> {
+        let stats = self.stats.clone();
+        let rate_gate = self.rate_gate.clone();
+        let load_profile = self.config.load_profile.clone();
+        let rate_limit = self.config.rate_limit;
+        let error_rate_threshold = self.config.error_rate_threshold;
+        let start_time = Instant::now();
+
+        tokio::spawn(async move {
+            let mut ticker = interval(Duration::from_millis(PACING_TICK_MS));
+            ticker.set_missed_tick_behavior(MissedTickBehavior::Delay);
+            let tick_secs = PACING_TICK_MS as f64 / 1000.0;
+
+            let mut permits_carry = 0.0;
+            let mut window_start = Instant::now();
+            let (mut window_total, mut window_failed) = (0u64, 0u64);
+            let mut crossed = false;
+
+            loop {
+                ticker.tick().await;
+                let elapsed = start_time.elapsed().as_secs_f64();
+
+                let target_tps = load_profile
+                    .rate_at(elapsed)
+                    .map(|tps| tps.max(MIN_PACED_TPS));
+
+                if let (Some(rate_gate), Some(tps)) = (&rate_gate, target_tps) {
+                    permits_carry += tps * tick_secs;
+                    let permits = permits_carry.floor();
+                    permits_carry -= permits;
+
+                    // Don't let unused permits pile up into a burst beyond a single tick
+                    let burst = (tps * tick_secs).ceil().max(1.0) as usize;
+                    let available = rate_gate.available_permits();
+                    if available < burst {
+                        rate_gate.add_permits((permits as usize).min(burst - available));
+                    }
+                }
+
+                if crossed {
+                    continue;
+                }
+
+                let (total, failed) = {
+                    let stats = stats.read().await;
+                    (stats.total_transactions, stats.failed_transactions)
+                };
+
+                let window_count = total - window_total;
+                let window_secs = window_start.elapsed().as_secs_f64();
+                if window_count < MIN_ERROR_RATE_WINDOW_SAMPLES || window_secs < 1.0 {
+                    continue;
+                }
+
+                let error_rate = (failed - window_failed) as f64 / window_count as f64;
+                if error_rate > error_rate_threshold {
+                    let crossing = ErrorRateCrossing {
+                        elapsed_secs: elapsed,
+                        target_tps: target_tps.or((rate_limit > 0).then_some(rate_limit as f64)),
+                        observed_tps: window_count as f64 / window_secs,
+                        error_rate,
+                    };
+                    warn!("Error rate {:.1}% crossed the {:.1}% threshold at {:.2} TPS after {:.0}s",
+                          error_rate * 100.0, error_rate_threshold * 100.0, crossing.observed_tps, elapsed);
+                    stats.write().await.error_rate_crossing = Some(crossing);
+                    crossed = true;
+                }
+
+                window_start = Instant::now();
+                window_total = total;
+                window_failed = failed;
+            }
+        })
     }
 
     fn print_config(&self) {
@@ -164,13 +447,58 @@ impl TestRunner {
         println!("  Contract: {}", self.config.contract_address);
         println!("  Workers: {}", self.config.workers);
         println!("  Duration: {}s", self.config.duration);
-        if self.config.rate_limit > 0 {
+        if self.config.warmup_operations > 0 {
+            println!("  Warmup Operations: {}", self.config.warmup_operations);
+        }
+        if self.config.rate_limit > 0 && self.rate_gate.is_none() {
             println!("  Rate Limit: {} TPS", self.config.rate_limit);
         }
+        if self.rate_gate.is_some() {
+            println!("  Load Profile: {:?}", self.config.load_profile);
+        }
+        if self.config.think_time != ThinkTime::None {
+            println!("  Think Time: {}", self.config.think_time);
+        }
+        if self.nonces.is_some() {
+            println!("  Nonces: pre-allocated");
+        }
+        println!();
+    }
+
+    /// Achieved rate against the configured one, and the rate the workers can reach with the think time
+    fn print_rate(&self, stats: &TestStatistics, duration: Duration) {
+        let achieved_tps = stats.tps(duration);
+
+        println!("{}", "Rate:".bright_yellow());
+        if self.rate_gate.is_some() {
+            println!("  Configured: load profile {:?}", self.config.load_profile);
+        } else if self.config.rate_limit > 0 {
+            println!("  Configured: {} TPS", self.config.rate_limit);
+        } else {
+            println!("  Configured: unlimited");
+        }
+
+        if self.config.think_time != ThinkTime::None {
+            println!("  Think Time: {}", self.config.think_time);
+
+            // Each worker completes an operation per its latency and think time on average
+            let cycle_secs = stats.avg_latency_ms() / 1000.0 + self.config.think_time.mean().as_secs_f64();
+            if cycle_secs > 0.0 {
+                println!("  Think Time Bound: {:.2} TPS ({} workers)",
+                    self.config.workers as f64 / cycle_secs, self.config.workers);
+            }
+        }
+
+        if self.rate_gate.is_none() && self.config.rate_limit > 0 {
+            println!("  Achieved: {:.2} TPS ({:.1}% of configured)",
+                achieved_tps, achieved_tps / self.config.rate_limit as f64 * 100.0);
+        } else {
+            println!("  Achieved: {:.2} TPS", achieved_tps);
+        }
         println!();
     }
 
-    async fn print_results(&self, duration: Duration) -> Result<()> {
+    async fn print_results(&self, duration: Duration, interrupted: bool) -> Result<()> {
         let stats = self.stats.read().await;
 
         println!("{}", "═══════════════════════════════════════".bright_cyan());
@@ -178,6 +506,11 @@ impl TestRunner {
         println!("{}", "═══════════════════════════════════════".bright_cyan());
         println!();
 
+        if interrupted {
+            println!("{}", "Interrupted with Ctrl-C, partial results".bright_red());
+            println!();
+        }
+
         println!("{}", "Overall Statistics:".bright_yellow());
         println!("  Total Duration: {:.2}s", duration.as_secs_f64());
         println!("  Total Transactions: {}", stats.total_transactions);
@@ -190,19 +523,71 @@ impl TestRunner {
 
         println!("{}", "Performance:".bright_yellow());
         println!("  Average TPS: {:.2}", stats.tps(duration));
+        let latency = stats.latency_summary();
         println!("  Average Latency: {:.2}ms", stats.avg_latency_ms());
-        println!("  P50 Latency: {:.2}ms", stats.p50_latency_ms());
-        println!("  P95 Latency: {:.2}ms", stats.p95_latency_ms());
-        println!("  P99 Latency: {:.2}ms", stats.p99_latency_ms());
-        println!("  Max Latency: {:.2}ms", stats.max_latency_ms());
+        println!("  P50 Latency: {:.2}ms", latency.p50_ms);
+        println!("  P95 Latency: {:.2}ms", latency.p95_ms);
+        println!("  P99 Latency: {:.2}ms", latency.p99_ms);
+        println!("  Max Latency: {:.2}ms", latency.max_ms);
+        println!();
+
+        self.print_rate(&stats, duration);
+
+        println!("{}", "Error Rate Threshold:".bright_yellow());
+        match &stats.error_rate_crossing {
+            Some(crossing) => {
+                println!("  Crossed {:.1}% after {:.0}s: {:.1}% errors",
+                    self.config.error_rate_threshold * 100.0,
+                    crossing.elapsed_secs,
+                    crossing.error_rate * 100.0,
+                );
+                if let Some(target_tps) = crossing.target_tps {
+                    println!("  Target Rate: {:.2} TPS", target_tps);
+                }
+                println!("  Observed Rate: {:.2} TPS", crossing.observed_tps);
+            }
+            None => {
+                println!("  Not crossed ({:.1}%)", self.config.error_rate_threshold * 100.0);
+            }
+        }
         println!();
 
+        if let Some(nonces) = &self.nonces {
+            let nonces = nonces.report();
+            println!("{}", "Nonces:".bright_yellow());
+            println!("  Range: {}..{} ({} allocated)", nonces.start, nonces.end, nonces.end - nonces.start);
+            if nonces.gaps.is_empty() {
+                println!("  Gaps: none");
+            } else {
+                let shown: Vec<_> = nonces.gaps.iter().take(10).map(|nonce| nonce.to_string()).collect();
+                println!("  {}", format!("Gaps: {} ({}{})",
+                    nonces.gaps.len(),
+                    shown.join(", "),
+                    if nonces.gaps.len() > 10 { ", ..." } else { "" },
+                ).bright_red());
+                println!("  Transactions after the first gap stay queued by the node until it's filled");
+            }
+            println!();
+        }
+
         println!("{}", "Gas Usage:".bright_yellow());
         println!("  Total Gas: {}", stats.total_gas_used);
         println!("  Average Gas per TX: {:.2}", stats.avg_gas_per_tx());
         println!();
 
         if stats.failed_transactions > 0 {
+            println!("{}", "Failures by Category:".bright_red());
+            let mut categories: Vec<_> = stats.error_categories.iter().collect();
+            categories.sort_by(|a, b| b.1.cmp(a.1));
+            for (category, count) in categories {
+                println!("  {}: {} ({:.1}%)",
+                    category,
+                    count,
+                    *count as f64 / stats.failed_transactions as f64 * 100.0,
+                );
+            }
+            println!();
+
             println!("{}", "Failed Transactions:".bright_red());
             for (i, error) in stats.errors.iter().take(10).enumerate() {
                 println!("  {}. {}", i + 1, error);
@@ -218,3 +603,183 @@ impl TestRunner {
         Ok(())
     }
 }
+
+/// Fail on a call template of a scenario without a single built-in call to replace
+fn reject_call_template(scenario: &str, call: &Option<CallTemplate>) -> Result<()> {
+    if let Some(call) = call {
+        anyhow::bail!(
+            "The {} scenario doesn't support call templates, remove its '{}' call",
+            scenario, call.function
+        );
+    }
+    Ok(())
+}
+
+/// Wait for the operations issued before the interruption, up to the grace period or another Ctrl-C
+async fn wait_in_flight(operations: &TaskTracker, progress: &ProgressBar) {
+    // No operations are spawned once the scenario is dropped
+    operations.close();
+
+    progress.println(format!(
+        "{}Interrupted, waiting up to {}s for {} in-flight operations (Ctrl-C again to skip)",
+        progress.prefix(),
+        SHUTDOWN_GRACE_SECS,
+        operations.len(),
+    ));
+
+    tokio::select! {
+        _ = tokio::time::timeout(Duration::from_secs(SHUTDOWN_GRACE_SECS), operations.wait()) => {}
+        _ = tokio::signal::ctrl_c() => {}
+    }
+
+    let abandoned = operations.len();
+    if abandoned > 0 {
+        warn!("{} in-flight operations abandoned, not included in the statistics", abandoned);
+    }
+}
+
+/// Runs the same scenario against two endpoints concurrently, the primary endpoint
+/// as the baseline and the compared endpoint as the candidate
+pub struct ComparisonRunner {
+    baseline: TestRunner,
+    candidate: TestRunner,
+}
+
+impl ComparisonRunner {
+    pub async fn new(config: LoadTestConfig) -> Result<Self> {
+        let candidate_config = config
+            .compare_config()?
+            .context("No endpoint to compare against")?;
+
+        // Calls pulled from an external source would be split between the endpoints
+        if matches!(config.scenario, ScenarioConfig::External { .. }) {
+            anyhow::bail!("External source scenario can't run in the comparison mode");
+        }
+
+        // Runners signing with the same key on the same chain would take the same nonces from the node,
+        // so both take them from a single pre-allocated range
+        if candidate_config.private_key == config.private_key
+            && candidate_config.chain_id == config.chain_id
+        {
+            if config.preallocate_nonces == Some(false) {
+                anyhow::bail!(
+                    "Both endpoints sign with the same key on chain {}, their nonces would collide without \
+                     pre-allocation: enable --preallocate-nonces or sign with --compare-private-key",
+                    config.chain_id
+                );
+            }
+
+            let mut config = config;
+            config.preallocate_nonces = Some(true);
+            let mut candidate_config = candidate_config;
+            candidate_config.preallocate_nonces = Some(true);
+
+            let baseline = TestRunner::new(config).await?;
+            let candidate = TestRunner::with_nonces(candidate_config, baseline.nonces.clone()).await?;
+            info!("Both endpoints sign with the same key on chain {}, sharing the pre-allocated nonces",
+                  baseline.config.chain_id);
+
+            return Ok(Self { baseline, candidate });
+        }
+
+        let (baseline, candidate) = tokio::try_join!(
+            TestRunner::new(config),
+            TestRunner::new(candidate_config),
+        )?;
+
+        Ok(Self { baseline, candidate })
+    }
+
+    pub async fn run(&mut self) -> Result<ComparisonReport> {
+        println!("{}", "Starting comparison load test...".bright_green().bold());
+        println!();
+
+        println!("{}", "Baseline".bright_cyan().bold());
+        self.baseline.print_config();
+        println!("{}", "Candidate".bright_cyan().bold());
+        self.candidate.print_config();
+
+        let baseline_scenario = self.baseline.create_scenario()?;
+        let candidate_scenario = self.candidate.create_scenario()?;
+
+        // Warm up both endpoints before both measurements start
+        if self.baseline.config.warmup_operations > 0 {
+            tokio::join!(
+                self.baseline.warmup(baseline_scenario.as_ref()),
+                self.candidate.warmup(candidate_scenario.as_ref()),
+            );
+        }
+
+        let progress = MultiProgress::new();
+        let baseline_progress = progress.add(new_progress_bar(baseline_scenario.total_operations()));
+        baseline_progress.set_prefix("baseline  ");
+        let candidate_progress = progress.add(new_progress_bar(candidate_scenario.total_operations()));
+        candidate_progress.set_prefix("candidate ");
+
+        let (baseline, candidate) = tokio::try_join!(
+            self.baseline.measure(baseline_scenario, baseline_progress),
+            self.candidate.measure(candidate_scenario, candidate_progress),
+        )?;
+
+        let report = ComparisonReport::new(baseline, candidate);
+
+        println!();
+        print_comparison(&report);
+
+        Ok(report)
+    }
+}
+
+fn print_comparison(report: &ComparisonReport) {
+    let (baseline, candidate, delta) = (&report.baseline, &report.candidate, &report.delta);
+
+    println!("{}", "═══════════════════════════════════════════════════════════════".bright_cyan());
+    println!("{}", "Comparison Results".bright_green().bold());
+    println!("{}", "═══════════════════════════════════════════════════════════════".bright_cyan());
+    println!();
+
+    if baseline.interrupted || candidate.interrupted {
+        println!("{}", "Interrupted with Ctrl-C, partial results".bright_red());
+        println!();
+    }
+
+    println!("  Baseline:  {}", baseline.config.rpc_url);
+    println!("  Candidate: {}", candidate.config.rpc_url);
+    println!();
+
+    println!("  {:<20} {:>12} {:>12} {:>18}", "", "Baseline", "Candidate", "Delta");
+    println!("  {:<20} {:>12} {:>12} {:>18}",
+        "Transactions", baseline.total_transactions, candidate.total_transactions,
+        format!("{:+}", candidate.total_transactions as i64 - baseline.total_transactions as i64),
+    );
+    println!("  {:<20} {:>12.2} {:>12.2} {:>18}",
+        "Average TPS", baseline.tps, candidate.tps,
+        match delta.tps_change_pct {
+            Some(pct) => format!("{:+.2} ({:+.1}%)", delta.tps, pct),
+            None => format!("{:+.2}", delta.tps),
+        },
+    );
+
+    let latencies = [
+        ("Average Latency", baseline.avg_latency_ms, candidate.avg_latency_ms, delta.avg_latency_ms),
+        ("P50 Latency", baseline.latency.p50_ms, candidate.latency.p50_ms, delta.p50_latency_ms),
+        ("P95 Latency", baseline.latency.p95_ms, candidate.latency.p95_ms, delta.p95_latency_ms),
+        ("P99 Latency", baseline.latency.p99_ms, candidate.latency.p99_ms, delta.p99_latency_ms),
+        ("Max Latency", baseline.latency.max_ms, candidate.latency.max_ms, delta.max_latency_ms),
+    ];
+    for (name, baseline_ms, candidate_ms, delta_ms) in latencies {
+        println!("  {:<20} {:>10.2}ms {:>10.2}ms {:>+16.2}ms",
+            name, baseline_ms, candidate_ms, delta_ms,
+        );
+    }
+
+    println!("  {:<20} {:>11.1}% {:>11.1}% {:>+15.1} pp",
+        "Error Rate",
+        baseline.error_rate() * 100.0,
+        candidate.error_rate() * 100.0,
+        delta.error_rate * 100.0,
+    );
+    println!();
+
+    println!("{}", "═══════════════════════════════════════════════════════════════".bright_cyan());
+}
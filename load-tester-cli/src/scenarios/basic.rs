@@ -7,20 +7,29 @@ use std::sync::Arc;
 use std::time::Instant;
 use tokio::sync::{RwLock, Semaphore};
 use tokio::time::{sleep, Duration};
+use tokio_util::task::TaskTracker;
 use tracing::{info, warn};
 
 use tx_producer::prelude::*;
-use crate::scenarios::TestScenario;
-use crate::stats::TestStatistics;
+use crate::scenarios::{CallTemplate, TestScenario, Pacing};
+use crate::config::ThinkTime;
+use crate::stats::{ErrorCategory, TestStatistics};
 
 pub struct BasicScenario {
     count: usize,
     iterations: u64,
+    call: Option<Arc<CallTemplate>>,
 }
 
 impl BasicScenario {
     pub fn new(count: usize, iterations: u64) -> Self {
-        Self { count, iterations }
+        Self { count, iterations, call: None }
+    }
+
+    /// Call the configured contract function instead of `consumeGas`
+    pub fn with_call(mut self, call: Option<CallTemplate>) -> Self {
+        self.call = call.map(Arc::new);
+        self
     }
 }
 
@@ -30,8 +39,16 @@ impl TestScenario for BasicScenario {
         "Basic Load Test"
     }
 
-    fn total_operations(&self) -> usize {
-        self.count
+    fn total_operations(&self) -> Option<usize> {
+        Some(self.count)
+    }
+
+    fn call_template(&self) -> Option<&CallTemplate> {
+        self.call.as_deref()
+    }
+
+    fn template_values(&self, _index: usize) -> Vec<(&'static str, String)> {
+        vec![("iterations", self.iterations.to_string())]
     }
 
     async fn execute(
@@ -40,23 +57,31 @@ impl TestScenario for BasicScenario {
         stats: Arc<RwLock<TestStatistics>>,
         progress: ProgressBar,
         workers: usize,
-        rate_limit: u64,
+        pacing: &Pacing,
+        think_time: ThinkTime,
+        operations: &TaskTracker,
     ) -> Result<()> {
         info!("Starting basic load test: {} transactions", self.count);
 
+        let rate_limit = pacing.rate_limit();
+
         let semaphore = Arc::new(Semaphore::new(workers));
         let start_time = Instant::now();
+        let latency_histograms = stats.read().await.latency_histograms();
 
         let mut tasks = Vec::new();
 
         for i in 0..self.count {
             let contract = contract.clone();
+            let pacing = pacing.clone();
+            let latency_histograms = latency_histograms.clone();
             let stats = stats.clone();
             let progress = progress.clone();
             let permit = semaphore.clone().acquire_owned().await.unwrap();
             let iterations = self.iterations;
+            let call = self.call.clone().map(|call| (call, self.template_values(i)));
 
-            let task = tokio::spawn(async move {
+            let task = operations.spawn(async move {
                 let _permit = permit;
 
                 // Rate limiting
@@ -65,27 +90,36 @@ impl TestScenario for BasicScenario {
                     sleep(delay).await;
                 }
 
+                let turn = pacing.turn().await;
                 let tx_start = Instant::now();
                 let timestamp = start_time.elapsed().as_secs_f64();
 
-                match contract.send_transaction(
-                    "consumeGas",
-                    &[DynSolValue::Uint(alloy_primitives::U256::from(iterations).into(), 256)],
-                ).await {
+                let result = match &call {
+                    Some((call, values)) => call.send(&contract, i, values).await,
+                    None => contract.send_transaction(
+                        "consumeGas",
+                        &[DynSolValue::Uint(alloy_primitives::U256::from(iterations).into(), 256)],
+                    ).await,
+                };
+
+                turn.complete();
+
+                match result {
                     Ok(tx_hash) => {
-                        let latency = tx_start.elapsed();
+                        latency_histograms.record(tx_start.elapsed());
                         let mut stats = stats.write().await;
-                        stats.record_success(latency, 100000, timestamp); // Approximate gas
+                        stats.record_success(100000, timestamp); // Approximate gas
                         progress.set_message(format!("TPS: {:.2}", stats.tps(start_time.elapsed())));
                     }
                     Err(e) => {
                         let mut stats = stats.write().await;
-                        stats.record_failure(e.to_string(), timestamp);
+                        stats.record_failure(ErrorCategory::from(&e), e.to_string(), timestamp);
                         warn!("Transaction {} failed: {}", i, e);
                     }
                 }
 
                 progress.inc(1);
+                think_time.pause().await;
             });
 
             tasks.push(task);
@@ -7,11 +7,13 @@ use rand::Rng;
 use std::sync::Arc;
 use std::time::Instant;
 use tokio::sync::{RwLock, Semaphore};
+use tokio_util::task::TaskTracker;
 use tracing::{info, warn};
 
 use tx_producer::prelude::*;
-use crate::scenarios::TestScenario;
-use crate::stats::TestStatistics;
+use crate::scenarios::{TestScenario, Pacing};
+use crate::config::ThinkTime;
+use crate::stats::{ErrorCategory, TestStatistics};
 
 pub struct BatchMintScenario {
     token_type: String,
@@ -41,8 +43,8 @@ impl TestScenario for BatchMintScenario {
         "Batch Token Minting"
     }
 
-    fn total_operations(&self) -> usize {
-        self.batches
+    fn total_operations(&self) -> Option<usize> {
+        Some(self.batches)
     }
 
     async fn execute(
@@ -51,27 +53,33 @@ impl TestScenario for BatchMintScenario {
         stats: Arc<RwLock<TestStatistics>>,
         progress: ProgressBar,
         workers: usize,
-        _rate_limit: u64,
+        pacing: &Pacing,
+        think_time: ThinkTime,
+        operations: &TaskTracker,
     ) -> Result<()> {
         info!("Starting batch mint test: {} type, {} per batch, {} batches",
               self.token_type, self.batch_size, self.batches);
 
         let semaphore = Arc::new(Semaphore::new(workers));
         let start_time = Instant::now();
+        let latency_histograms = stats.read().await.latency_histograms();
 
         let mut tasks = Vec::new();
 
         for i in 0..self.batches {
             let contract = contract.clone();
+            let pacing = pacing.clone();
+            let latency_histograms = latency_histograms.clone();
             let stats = stats.clone();
             let progress = progress.clone();
             let permit = semaphore.clone().acquire_owned().await.unwrap();
             let token_type = self.token_type.clone();
             let batch_size = self.batch_size;
 
-            let task = tokio::spawn(async move {
+            let task = operations.spawn(async move {
                 let _permit = permit;
 
+                let turn = pacing.turn().await;
                 let tx_start = Instant::now();
                 let timestamp = start_time.elapsed().as_secs_f64();
 
@@ -148,22 +156,25 @@ impl TestScenario for BatchMintScenario {
                     }
                 };
 
+                turn.complete();
+
                 match result {
                     Ok(_tx_hash) => {
-                        let latency = tx_start.elapsed();
+                        latency_histograms.record(tx_start.elapsed());
                         let gas_estimate = 50000 + (batch_size as u64 * 50000);
                         let mut stats = stats.write().await;
-                        stats.record_success(latency, gas_estimate, timestamp);
+                        stats.record_success(gas_estimate, timestamp);
                         progress.set_message(format!("TPS: {:.2}", stats.tps(start_time.elapsed())));
                     }
                     Err(e) => {
                         let mut stats = stats.write().await;
-                        stats.record_failure(e.to_string(), timestamp);
+                        stats.record_failure(ErrorCategory::from(&e), e.to_string(), timestamp);
                         warn!("Batch {} failed: {}", i, e);
                     }
                 }
 
                 progress.inc(1);
+                think_time.pause().await;
             });
 
             tasks.push(task);
@@ -7,21 +7,30 @@ use rand::Rng;
 use std::sync::Arc;
 use std::time::Instant;
 use tokio::sync::RwLock;
+use tokio_util::task::TaskTracker;
 use tracing::info;
 
 use tx_producer::prelude::*;
-use crate::scenarios::TestScenario;
-use crate::stats::TestStatistics;
+use crate::scenarios::{CallTemplate, TestScenario, Pacing};
+use crate::config::ThinkTime;
+use crate::stats::{ErrorCategory, TestStatistics};
 
 pub struct CalldataScenario {
     min_size: usize,
     max_size: usize,
     increment: usize,
+    call: Option<CallTemplate>,
 }
 
 impl CalldataScenario {
     pub fn new(min_size: usize, max_size: usize, increment: usize) -> Self {
-        Self { min_size, max_size, increment }
+        Self { min_size, max_size, increment, call: None }
+    }
+
+    /// Call the configured contract function instead of `bigCalldataEcho`
+    pub fn with_call(mut self, call: Option<CallTemplate>) -> Self {
+        self.call = call;
+        self
     }
 }
 
@@ -31,8 +40,22 @@ impl TestScenario for CalldataScenario {
         "Calldata Size Test"
     }
 
-    fn total_operations(&self) -> usize {
-        ((self.max_size - self.min_size) / self.increment) + 1
+    fn total_operations(&self) -> Option<usize> {
+        Some(((self.max_size - self.min_size) / self.increment) + 1)
+    }
+
+    fn call_template(&self) -> Option<&CallTemplate> {
+        self.call.as_ref()
+    }
+
+    fn template_values(&self, index: usize) -> Vec<(&'static str, String)> {
+        let size = self.min_size + index * self.increment;
+        let data: Vec<u8> = (0..size).map(|_| rand::thread_rng().gen()).collect();
+
+        vec![
+            ("size", size.to_string()),
+            ("data", format!("0x{}", hex::encode(data))),
+        ]
     }
 
     async fn execute(
@@ -41,41 +64,57 @@ impl TestScenario for CalldataScenario {
         stats: Arc<RwLock<TestStatistics>>,
         progress: ProgressBar,
         _workers: usize,
-        _rate_limit: u64,
+        pacing: &Pacing,
+        think_time: ThinkTime,
+        _operations: &TaskTracker,
     ) -> Result<()> {
         info!("Starting calldata test: {} to {} bytes", self.min_size, self.max_size);
 
         let start_time = Instant::now();
+        let latency_histograms = stats.read().await.latency_histograms();
         let mut rng = rand::thread_rng();
 
         let mut size = self.min_size;
+        let mut index = 0;
         while size <= self.max_size {
-            // Generate random data of specified size
-            let data: Vec<u8> = (0..size).map(|_| rng.gen()).collect();
-
+            let turn = pacing.turn().await;
             let tx_start = Instant::now();
             let timestamp = start_time.elapsed().as_secs_f64();
 
             progress.set_message(format!("Testing {} bytes", size));
 
-            match contract.send_transaction(
-                "bigCalldataEcho",
-                &[DynSolValue::Bytes(data.clone())],
-            ).await {
+            let result = match &self.call {
+                Some(call) => call.send(&contract, index, &self.template_values(index)).await,
+                None => {
+                    // Generate random data of specified size
+                    let data: Vec<u8> = (0..size).map(|_| rng.gen()).collect();
+
+                    contract.send_transaction(
+                        "bigCalldataEcho",
+                        &[DynSolValue::Bytes(data)],
+                    ).await
+                }
+            };
+
+            turn.complete();
+
+            match result {
                 Ok(_tx_hash) => {
-                    let latency = tx_start.elapsed();
+                    latency_histograms.record(tx_start.elapsed());
                     let gas_estimate = 21000 + (size as u64 * 16); // Approximate calldata gas
                     let mut stats = stats.write().await;
-                    stats.record_success(latency, gas_estimate, timestamp);
+                    stats.record_success(gas_estimate, timestamp);
                 }
                 Err(e) => {
                     let mut stats = stats.write().await;
-                    stats.record_failure(format!("Size {}: {}", size, e), timestamp);
+                    stats.record_failure(ErrorCategory::from(&e), format!("Size {}: {}", size, e), timestamp);
                 }
             }
 
             progress.inc(1);
+            think_time.pause().await;
             size += self.increment;
+            index += 1;
         }
 
         info!("Calldata test completed");
@@ -6,11 +6,13 @@ use indicatif::ProgressBar;
 use std::sync::Arc;
 use std::time::Instant;
 use tokio::sync::{RwLock, Semaphore};
+use tokio_util::task::TaskTracker;
 use tracing::{info, warn};
 
 use tx_producer::prelude::*;
-use crate::scenarios::TestScenario;
-use crate::stats::TestStatistics;
+use crate::scenarios::{TestScenario, Pacing};
+use crate::config::ThinkTime;
+use crate::stats::{ErrorCategory, TestStatistics};
 
 pub struct CryptoScenario {
     test_type: String,
@@ -34,8 +36,8 @@ impl TestScenario for CryptoScenario {
         "Cryptography Test"
     }
 
-    fn total_operations(&self) -> usize {
-        self.count
+    fn total_operations(&self) -> Option<usize> {
+        Some(self.count)
     }
 
     async fn execute(
@@ -44,26 +46,32 @@ impl TestScenario for CryptoScenario {
         stats: Arc<RwLock<TestStatistics>>,
         progress: ProgressBar,
         workers: usize,
-        _rate_limit: u64,
+        pacing: &Pacing,
+        think_time: ThinkTime,
+        operations: &TaskTracker,
     ) -> Result<()> {
         info!("Starting crypto test: {} type, {} verifications",
               self.test_type, self.count);
 
         let semaphore = Arc::new(Semaphore::new(workers));
         let start_time = Instant::now();
+        let latency_histograms = stats.read().await.latency_histograms();
 
         let mut tasks = Vec::new();
 
         for i in 0..self.count {
             let contract = contract.clone();
+            let pacing = pacing.clone();
+            let latency_histograms = latency_histograms.clone();
             let stats = stats.clone();
             let progress = progress.clone();
             let permit = semaphore.clone().acquire_owned().await.unwrap();
             let test_type = self.test_type.clone();
 
-            let task = tokio::spawn(async move {
+            let task = operations.spawn(async move {
                 let _permit = permit;
 
+                let turn = pacing.turn().await;
                 let tx_start = Instant::now();
                 let timestamp = start_time.elapsed().as_secs_f64();
 
@@ -105,21 +113,24 @@ impl TestScenario for CryptoScenario {
                     }
                 };
 
+                turn.complete();
+
                 match result {
                     Ok(_) => {
-                        let latency = tx_start.elapsed();
+                        latency_histograms.record(tx_start.elapsed());
                         let mut stats = stats.write().await;
-                        stats.record_success(latency, 50000, timestamp);
+                        stats.record_success(50000, timestamp);
                         progress.set_message(format!("Ops/s: {:.2}", stats.tps(start_time.elapsed())));
                     }
                     Err(e) => {
                         let mut stats = stats.write().await;
-                        stats.record_failure(e.to_string(), timestamp);
+                        stats.record_failure(ErrorCategory::from(&e), e.to_string(), timestamp);
                         warn!("Verification {} failed: {}", i, e);
                     }
                 }
 
                 progress.inc(1);
+                think_time.pause().await;
             });
 
             tasks.push(task);
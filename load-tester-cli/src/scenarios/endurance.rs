@@ -7,20 +7,29 @@ use std::sync::Arc;
 use std::time::Instant;
 use tokio::sync::{RwLock, Semaphore};
 use tokio::time::{sleep, Duration};
+use tokio_util::task::TaskTracker;
 use tracing::{info, warn};
 
 use tx_producer::prelude::*;
-use crate::scenarios::TestScenario;
-use crate::stats::TestStatistics;
+use crate::scenarios::{CallTemplate, TestScenario, Pacing};
+use crate::config::ThinkTime;
+use crate::stats::{ErrorCategory, TestStatistics};
 
 pub struct EnduranceScenario {
     hours: u64,
     tps: u64,
+    call: Option<Arc<CallTemplate>>,
 }
 
 impl EnduranceScenario {
     pub fn new(hours: u64, tps: u64) -> Self {
-        Self { hours, tps }
+        Self { hours, tps, call: None }
+    }
+
+    /// Call the configured contract function instead of `consumeGas`
+    pub fn with_call(mut self, call: Option<CallTemplate>) -> Self {
+        self.call = call.map(Arc::new);
+        self
     }
 }
 
@@ -30,8 +39,12 @@ impl TestScenario for EnduranceScenario {
         "Endurance Test"
     }
 
-    fn total_operations(&self) -> usize {
-        (self.hours * 3600 * self.tps) as usize
+    fn total_operations(&self) -> Option<usize> {
+        Some((self.hours * 3600 * self.tps) as usize)
+    }
+
+    fn call_template(&self) -> Option<&CallTemplate> {
+        self.call.as_deref()
     }
 
     async fn execute(
@@ -40,49 +53,65 @@ impl TestScenario for EnduranceScenario {
         stats: Arc<RwLock<TestStatistics>>,
         progress: ProgressBar,
         workers: usize,
-        _rate_limit: u64,
+        pacing: &Pacing,
+        think_time: ThinkTime,
+        operations: &TaskTracker,
     ) -> Result<()> {
         info!("Starting endurance test: {} hours at {} TPS", self.hours, self.tps);
 
         let semaphore = Arc::new(Semaphore::new(workers));
         let start_time = Instant::now();
+        let latency_histograms = stats.read().await.latency_histograms();
         let duration = Duration::from_secs(self.hours * 3600);
         let end_time = start_time + duration;
 
         let delay_per_tx = Duration::from_secs_f64(1.0 / self.tps as f64);
 
         let mut last_report = Instant::now();
+        let mut index = 0;
         let report_interval = Duration::from_secs(300); // Report every 5 minutes
 
         while Instant::now() < end_time {
             let contract = contract.clone();
+            let pacing = pacing.clone();
+            let latency_histograms = latency_histograms.clone();
             let stats = stats.clone();
             let progress = progress.clone();
             let permit = semaphore.clone().acquire_owned().await.unwrap();
+            let call = self.call.clone().map(|call| (call, self.template_values(index)));
 
-            tokio::spawn(async move {
+            operations.spawn(async move {
                 let _permit = permit;
 
+                let turn = pacing.turn().await;
                 let tx_start = Instant::now();
                 let timestamp = start_time.elapsed().as_secs_f64();
 
-                match contract.send_transaction(
-                    "consumeGas",
-                    &[DynSolValue::Uint(alloy_primitives::U256::from(100).into(), 256)],
-                ).await {
+                let result = match &call {
+                    Some((call, values)) => call.send(&contract, index, values).await,
+                    None => contract.send_transaction(
+                        "consumeGas",
+                        &[DynSolValue::Uint(alloy_primitives::U256::from(100).into(), 256)],
+                    ).await,
+                };
+
+                turn.complete();
+
+                match result {
                     Ok(_tx_hash) => {
-                        let latency = tx_start.elapsed();
+                        latency_histograms.record(tx_start.elapsed());
                         let mut stats = stats.write().await;
-                        stats.record_success(latency, 100000, timestamp);
+                        stats.record_success(100000, timestamp);
                     }
                     Err(e) => {
                         let mut stats = stats.write().await;
-                        stats.record_failure(e.to_string(), timestamp);
+                        stats.record_failure(ErrorCategory::from(&e), e.to_string(), timestamp);
                         warn!("Transaction failed: {}", e);
                     }
                 }
 
                 progress.inc(1);
+                think_time.pause().await;
             });
 
             // Periodic reporting
@@ -110,6 +139,7 @@ impl TestScenario for EnduranceScenario {
                 last_report = Instant::now();
             }
 
+            index += 1;
             sleep(delay_per_tx).await;
         }
 
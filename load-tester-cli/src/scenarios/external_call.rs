@@ -6,21 +6,30 @@ use indicatif::ProgressBar;
 use std::sync::Arc;
 use std::time::Instant;
 use tokio::sync::{RwLock, Semaphore};
+use tokio_util::task::TaskTracker;
 use tracing::{info, warn};
 
 use tx_producer::prelude::*;
-use crate::scenarios::TestScenario;
-use crate::stats::TestStatistics;
+use crate::scenarios::{CallTemplate, TestScenario, Pacing};
+use crate::config::ThinkTime;
+use crate::stats::{ErrorCategory, TestStatistics};
 
 pub struct ExternalCallScenario {
     call_type: String,
     gas_limit: u64,
     count: usize,
+    call: Option<Arc<CallTemplate>>,
 }
 
 impl ExternalCallScenario {
     pub fn new(call_type: String, gas_limit: u64, count: usize) -> Self {
-        Self { call_type, gas_limit, count }
+        Self { call_type, gas_limit, count, call: None }
+    }
+
+    /// Call the configured contract function instead of the call type one
+    pub fn with_call(mut self, call: Option<CallTemplate>) -> Self {
+        self.call = call.map(Arc::new);
+        self
     }
 }
 
@@ -30,8 +39,16 @@ impl TestScenario for ExternalCallScenario {
         "External Call Test"
     }
 
-    fn total_operations(&self) -> usize {
-        self.count
+    fn total_operations(&self) -> Option<usize> {
+        Some(self.count)
+    }
+
+    fn call_template(&self) -> Option<&CallTemplate> {
+        self.call.as_deref()
+    }
+
+    fn template_values(&self, _index: usize) -> Vec<(&'static str, String)> {
+        vec![("gas_limit", self.gas_limit.to_string())]
     }
 
     async fn execute(
@@ -40,27 +57,34 @@ impl TestScenario for ExternalCallScenario {
         stats: Arc<RwLock<TestStatistics>>,
         progress: ProgressBar,
         workers: usize,
-        _rate_limit: u64,
+        pacing: &Pacing,
+        think_time: ThinkTime,
+        operations: &TaskTracker,
     ) -> Result<()> {
         info!("Starting external call test: {} type, {} gas limit, {} calls",
               self.call_type, self.gas_limit, self.count);
 
         let semaphore = Arc::new(Semaphore::new(workers));
         let start_time = Instant::now();
+        let latency_histograms = stats.read().await.latency_histograms();
 
         let mut tasks = Vec::new();
 
         for i in 0..self.count {
             let contract = contract.clone();
+            let pacing = pacing.clone();
+            let latency_histograms = latency_histograms.clone();
             let stats = stats.clone();
             let progress = progress.clone();
             let permit = semaphore.clone().acquire_owned().await.unwrap();
             let call_type = self.call_type.clone();
             let gas_limit = self.gas_limit;
+            let call = self.call.clone().map(|call| (call, self.template_values(i)));
 
-            let task = tokio::spawn(async move {
+            let task = operations.spawn(async move {
                 let _permit = permit;
 
+                let turn = pacing.turn().await;
                 let tx_start = Instant::now();
                 let timestamp = start_time.elapsed().as_secs_f64();
 
@@ -72,27 +96,35 @@ impl TestScenario for ExternalCallScenario {
                     _ => "callDummy",
                 };
 
-                match contract.send_transaction(
-                    function_name,
-                    &[
-                        DynSolValue::Bytes(data),
-                        DynSolValue::Uint(alloy_primitives::U256::from(gas_limit).into(), 256),
-                    ],
-                ).await {
+                let result = match &call {
+                    Some((call, values)) => call.send(&contract, i, values).await,
+                    None => contract.send_transaction(
+                        function_name,
+                        &[
+                            DynSolValue::Bytes(data),
+                            DynSolValue::Uint(alloy_primitives::U256::from(gas_limit).into(), 256),
+                        ],
+                    ).await,
+                };
+
+                turn.complete();
+
+                match result {
                     Ok(_tx_hash) => {
-                        let latency = tx_start.elapsed();
+                        latency_histograms.record(tx_start.elapsed());
                         let mut stats = stats.write().await;
-                        stats.record_success(latency, gas_limit, timestamp);
+                        stats.record_success(gas_limit, timestamp);
                         progress.set_message(format!("TPS: {:.2}", stats.tps(start_time.elapsed())));
                     }
                     Err(e) => {
                         let mut stats = stats.write().await;
-                        stats.record_failure(e.to_string(), timestamp);
+                        stats.record_failure(ErrorCategory::from(&e), e.to_string(), timestamp);
                         warn!("Call {} failed: {}", i, e);
                     }
                 }
 
                 progress.inc(1);
+                think_time.pause().await;
             });
 
             tasks.push(task);
@@ -0,0 +1,265 @@
+//! Contract calls pulled from an external source at runtime scenario
+
+use anyhow::{bail, Result};
+use async_trait::async_trait;
+use indicatif::ProgressBar;
+use serde::Deserialize;
+use std::sync::Arc;
+use std::time::Instant;
+use tokio::io::{AsyncBufReadExt, BufReader};
+use tokio::sync::{mpsc, RwLock, Semaphore};
+use tokio::task::JoinSet;
+use tokio::time::{interval, sleep, Duration, MissedTickBehavior};
+use tokio_util::task::TaskTracker;
+use tracing::{debug, info, warn};
+
+use tx_producer::prelude::*;
+use crate::scenarios::replay::RecordedCall;
+use crate::scenarios::{TestScenario, Pacing};
+use crate::config::ThinkTime;
+use crate::stats::{ErrorCategory, TestStatistics};
+
+/// Source of the work items
+#[derive(Debug, Clone)]
+pub enum WorkSource {
+    /// HTTP endpoint polled for calls
+    Http(String),
+    /// JSON Lines from the standard input, e.g. piped from a queue subscriber
+    Stdin,
+}
+
+impl WorkSource {
+    /// Parse source spec: `http(s)://...` URL or `-` for the standard input
+    pub fn parse(source: &str) -> Result<Self> {
+        if source == "-" {
+            Ok(Self::Stdin)
+        } else if source.starts_with("http://") || source.starts_with("https://") {
+            Ok(Self::Http(source.to_string()))
+        } else {
+            bail!("Unsupported work source '{}', expected http(s):// URL or '-' for stdin", source)
+        }
+    }
+}
+
+/// HTTP endpoint response, a batch of calls or a single call
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum WorkBatch {
+    Many(Vec<RecordedCall>),
+    One(RecordedCall),
+}
+
+pub struct ExternalSourceScenario {
+    source: WorkSource,
+    poll_interval: Duration,
+    max_operations: Option<usize>,
+}
+
+impl ExternalSourceScenario {
+    pub fn new(source: WorkSource, poll_interval_ms: u64, max_operations: Option<usize>) -> Self {
+        Self {
+            source,
+            poll_interval: Duration::from_millis(poll_interval_ms),
+            max_operations,
+        }
+    }
+}
+
+/// Poll the HTTP endpoint until it answers `410 Gone` or the receiver is dropped.
+/// `204 No Content` or an empty batch means no work yet, polled again after the interval.
+async fn poll_http(url: String, poll_interval: Duration, calls: mpsc::Sender<RecordedCall>) {
+    let client = reqwest::Client::new();
+
+    loop {
+        let batch = match client.get(&url).send().await {
+            Ok(response) if response.status() == reqwest::StatusCode::GONE => {
+                info!("Work source {} is exhausted", url);
+                return;
+            }
+            Ok(response) if response.status() == reqwest::StatusCode::NO_CONTENT => Vec::new(),
+            Ok(response) if response.status().is_success() => {
+                match response.json::<WorkBatch>().await {
+                    Ok(WorkBatch::Many(batch)) => batch,
+                    Ok(WorkBatch::One(call)) => vec![call],
+                    Err(e) => {
+                        warn!("Invalid work batch from {}: {}", url, e);
+                        Vec::new()
+                    }
+                }
+            }
+            Ok(response) => {
+                warn!("Work source {} responded with {}", url, response.status());
+                Vec::new()
+            }
+            Err(e) => {
+                warn!("Failed to poll work source {}: {}", url, e);
+                Vec::new()
+            }
+        };
+
+        if batch.is_empty() {
+            sleep(poll_interval).await;
+            continue;
+        }
+
+        debug!("Pulled {} calls from {}", batch.len(), url);
+        for call in batch {
+            // Blocks while the workers are busy, so the source isn't drained ahead of the load
+            if calls.send(call).await.is_err() {
+                return;
+            }
+        }
+    }
+}
+
+/// Read JSON Lines calls from the standard input until EOF, skipping invalid lines
+async fn read_stdin(calls: mpsc::Sender<RecordedCall>) {
+    let mut lines = BufReader::new(tokio::io::stdin()).lines();
+
+    loop {
+        let line = match lines.next_line().await {
+            Ok(Some(line)) => line,
+            Ok(None) => {
+                info!("Work source stdin is exhausted");
+                return;
+            }
+            Err(e) => {
+                warn!("Failed to read work source stdin: {}", e);
+                return;
+            }
+        };
+
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        match serde_json::from_str::<RecordedCall>(&line) {
+            Ok(call) => {
+                if calls.send(call).await.is_err() {
+                    return;
+                }
+            }
+            Err(e) => warn!("Skipping invalid call from stdin: {}", e),
+        }
+    }
+}
+
+#[async_trait]
+impl TestScenario for ExternalSourceScenario {
+    fn name(&self) -> &str {
+        "External Source Test"
+    }
+
+    fn total_operations(&self) -> Option<usize> {
+        self.max_operations
+    }
+
+    async fn execute(
+        &self,
+        contract: Arc<ContractClient>,
+        stats: Arc<RwLock<TestStatistics>>,
+        progress: ProgressBar,
+        workers: usize,
+        pacing: &Pacing,
+        think_time: ThinkTime,
+        operations: &TaskTracker,
+    ) -> Result<()> {
+        info!("Starting external source test: {:?}, max operations: {:?}",
+              self.source, self.max_operations);
+
+        let workers = workers.max(1);
+        let semaphore = Arc::new(Semaphore::new(workers));
+        let start_time = Instant::now();
+        let latency_histograms = stats.read().await.latency_histograms();
+
+        // Small buffer, the source is pulled at the pace the calls are submitted
+        let (sender, mut receiver) = mpsc::channel(workers * 2);
+        // Producer is aborted when the set is dropped, also when the scenario is interrupted
+        let mut producer = JoinSet::new();
+        match self.source.clone() {
+            WorkSource::Http(url) => producer.spawn(poll_http(url, self.poll_interval, sender)),
+            WorkSource::Stdin => producer.spawn(read_stdin(sender)),
+        };
+
+        // Pace the calls submission when rate limit is set
+        let mut ticker = if pacing.rate_limit() > 0 {
+            let mut ticker = interval(Duration::from_secs_f64(1.0 / pacing.rate_limit() as f64));
+            ticker.set_missed_tick_behavior(MissedTickBehavior::Delay);
+            Some(ticker)
+        } else {
+            None
+        };
+
+        let shutdown = tokio::signal::ctrl_c();
+        tokio::pin!(shutdown);
+
+        let mut submitted = 0usize;
+
+        while self.max_operations.map_or(true, |max| submitted < max) {
+            let call = tokio::select! {
+                call = receiver.recv() => match call {
+                    Some(call) => call,
+                    None => break,
+                },
+                _ = &mut shutdown => {
+                    info!("Interrupted, waiting for in-flight calls");
+                    break;
+                }
+            };
+
+            if let Some(ticker) = ticker.as_mut() {
+                ticker.tick().await;
+            }
+
+            let contract = contract.clone();
+            let pacing = pacing.clone();
+            let latency_histograms = latency_histograms.clone();
+            let stats = stats.clone();
+            let progress = progress.clone();
+            let permit = semaphore.clone().acquire_owned().await.unwrap();
+            let i = submitted;
+
+            operations.spawn(async move {
+                let _permit = permit;
+
+                let turn = pacing.turn().await;
+                let tx_start = Instant::now();
+                let timestamp = start_time.elapsed().as_secs_f64();
+
+                let result = TransactionBuilder::new(&contract, call.function.clone())
+                    .args(call.args)
+                    .send()
+                    .await;
+
+                turn.complete();
+
+                match result {
+                    Ok(_tx_hash) => {
+                        latency_histograms.record(tx_start.elapsed());
+                        let mut stats = stats.write().await;
+                        stats.record_success(100000, timestamp); // Approximate gas
+                        progress.set_message(format!("TPS: {:.2}", stats.tps(start_time.elapsed())));
+                    }
+                    Err(e) => {
+                        let mut stats = stats.write().await;
+                        stats.record_failure(ErrorCategory::from(&e), e.to_string(), timestamp);
+                        warn!("External call {} ({}) failed: {}", i, call.function, e);
+                    }
+                }
+
+                progress.inc(1);
+                think_time.pause().await;
+            });
+
+            submitted += 1;
+        }
+
+        producer.abort_all();
+
+        // All permits are back once the in-flight calls complete
+        let _ = semaphore.acquire_many(workers as u32).await;
+
+        info!("External source test completed: {} calls submitted", submitted);
+        Ok(())
+    }
+}
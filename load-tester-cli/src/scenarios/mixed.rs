@@ -8,11 +8,13 @@ use std::sync::Arc;
 use std::time::Instant;
 use tokio::sync::{RwLock, Semaphore};
 use tokio::time::{sleep, Duration};
+use tokio_util::task::TaskTracker;
 use tracing::{info, warn};
 
 use tx_producer::prelude::*;
-use crate::scenarios::TestScenario;
-use crate::stats::TestStatistics;
+use crate::scenarios::{TestScenario, Pacing};
+use crate::config::ThinkTime;
+use crate::stats::{ErrorCategory, TestStatistics};
 
 pub struct MixedScenario {
     duration: u64,
@@ -28,7 +30,7 @@ impl MixedScenario {
         &self,
         contract: &ContractClient,
         op_type: &str,
-    ) -> Result<alloy_primitives::B256> {
+    ) -> tx_producer::Result<alloy_primitives::B256> {
         match op_type {
             "storage" => {
                 contract.send_transaction(
@@ -94,8 +96,8 @@ impl TestScenario for MixedScenario {
         "Mixed Workload"
     }
 
-    fn total_operations(&self) -> usize {
-        (self.duration * 10) as usize // Estimate
+    fn total_operations(&self) -> Option<usize> {
+        Some((self.duration * 10) as usize) // Estimate
     }
 
     async fn execute(
@@ -104,13 +106,16 @@ impl TestScenario for MixedScenario {
         stats: Arc<RwLock<TestStatistics>>,
         progress: ProgressBar,
         workers: usize,
-        _rate_limit: u64,
+        pacing: &Pacing,
+        think_time: ThinkTime,
+        operations: &TaskTracker,
     ) -> Result<()> {
         info!("Starting mixed workload test: {} seconds, {} profile",
               self.duration, self.profile);
 
         let semaphore = Arc::new(Semaphore::new(workers));
         let start_time = Instant::now();
+        let latency_histograms = stats.read().await.latency_histograms();
         let end_time = start_time + Duration::from_secs(self.duration);
 
         let op_mix = self.get_operation_mix();
@@ -119,6 +124,8 @@ impl TestScenario for MixedScenario {
         let mut task_count = 0;
         while Instant::now() < end_time {
             let contract = contract.clone();
+            let pacing = pacing.clone();
+            let latency_histograms = latency_histograms.clone();
             let stats = stats.clone();
             let progress = progress.clone();
             let permit = semaphore.clone().acquire_owned().await.unwrap();
@@ -139,28 +146,33 @@ impl TestScenario for MixedScenario {
             let op_type = selected_op.to_string();
             let profile = self.profile.clone();
 
-            tokio::spawn(async move {
+            operations.spawn(async move {
                 let _permit = permit;
 
+                let turn = pacing.turn().await;
                 let tx_start = Instant::now();
                 let timestamp = start_time.elapsed().as_secs_f64();
 
                 let scenario = MixedScenario::new(0, profile);
-                match scenario.execute_operation(&contract, &op_type).await {
+                let result = scenario.execute_operation(&contract, &op_type).await;
+                turn.complete();
+
+                match result {
                     Ok(_tx_hash) => {
-                        let latency = tx_start.elapsed();
+                        latency_histograms.record(tx_start.elapsed());
                         let mut stats = stats.write().await;
-                        stats.record_success(latency, 100000, timestamp);
+                        stats.record_success(100000, timestamp);
                         progress.set_message(format!("TPS: {:.2}", stats.tps(start_time.elapsed())));
                     }
                     Err(e) => {
                         let mut stats = stats.write().await;
-                        stats.record_failure(e.to_string(), timestamp);
+                        stats.record_failure(ErrorCategory::from(&e), e.to_string(), timestamp);
                         warn!("Operation {} failed: {}", op_type, e);
                     }
                 }
 
                 progress.inc(1);
+                think_time.pause().await;
             });
 
             task_count += 1;
@@ -9,6 +9,9 @@ mod crypto;
 mod mixed;
 mod stress;
 mod endurance;
+mod replay;
+mod external_source;
+mod template;
 
 pub use basic::BasicScenario;
 pub use storage::StorageScenario;
@@ -19,14 +22,19 @@ pub use crypto::CryptoScenario;
 pub use mixed::MixedScenario;
 pub use stress::StressScenario;
 pub use endurance::EnduranceScenario;
+pub use replay::ReplayScenario;
+pub use external_source::{ExternalSourceScenario, WorkSource};
+pub use template::CallTemplate;
 
 use anyhow::Result;
 use async_trait::async_trait;
 use indicatif::ProgressBar;
 use std::sync::Arc;
-use tokio::sync::RwLock;
+use tokio::sync::{OwnedSemaphorePermit, RwLock, Semaphore};
+use tokio_util::task::TaskTracker;
 
 use tx_producer::prelude::*;
+use crate::config::ThinkTime;
 use crate::stats::TestStatistics;
 
 #[async_trait]
@@ -34,16 +42,86 @@ pub trait TestScenario: Send + Sync {
     /// Get scenario name
     fn name(&self) -> &str;
 
-    /// Get total number of operations
-    fn total_operations(&self) -> usize;
+    /// Get total number of operations, `None` when unbounded (runs until stopped)
+    fn total_operations(&self) -> Option<usize>;
 
-    /// Execute the scenario
+    /// Contract call replacing the scenario built-in call, from the scenario `call` config
+    fn call_template(&self) -> Option<&CallTemplate> {
+        None
+    }
+
+    /// Scenario values of the operation for the call template placeholders, besides the common ones
+    fn template_values(&self, _index: usize) -> Vec<(&'static str, String)> {
+        Vec::new()
+    }
+
+    /// Execute a single warmup operation before measurement
+    async fn warmup_operation(&self, contract: &ContractClient) -> tx_producer::Result<B256> {
+        if let Some(call) = self.call_template() {
+            return call.send(contract, 0, &self.template_values(0)).await;
+        }
+
+        contract.send_transaction(
+            "consumeGas",
+            &[DynSolValue::Uint(alloy_primitives::U256::from(1).into(), 256)],
+        ).await
+    }
+
+    /// Execute the scenario. Each operation waits for its turn by the `pacing` before
+    /// the latency timer starts, and each worker pauses for the think time after its operation,
+    /// before taking the next one. Operations are spawned on the `operations` tracker,
+    /// so the in-flight ones are awaited when the scenario is interrupted.
     async fn execute(
         &self,
         contract: Arc<ContractClient>,
         stats: Arc<RwLock<TestStatistics>>,
         progress: ProgressBar,
         workers: usize,
-        rate_limit: u64,
+        pacing: &Pacing,
+        think_time: ThinkTime,
+        operations: &TaskTracker,
     ) -> Result<()>;
 }
+
+/// Pacing of the scenario operations, either by the scenario own rate limit
+/// or by the load profile rate gate the runner adds permits to over time
+#[derive(Clone, Default)]
+pub struct Pacing {
+    rate_limit: u64,
+    rate_gate: Option<Arc<Semaphore>>,
+}
+
+impl Pacing {
+    /// The scenario rate limit applies only without the load profile rate gate
+    pub fn new(rate_limit: u64, rate_gate: Option<Arc<Semaphore>>) -> Self {
+        let rate_limit = if rate_gate.is_some() { 0 } else { rate_limit };
+        Self { rate_limit, rate_gate }
+    }
+
+    /// Scenario rate limit in operations per second, 0 when unlimited or paced by the rate gate
+    pub fn rate_limit(&self) -> u64 {
+        self.rate_limit
+    }
+
+    /// Wait for the operation turn through the rate gate
+    pub async fn turn(&self) -> Turn {
+        let permit = match &self.rate_gate {
+            Some(rate_gate) => rate_gate.clone().acquire_owned().await.ok(),
+            None => None,
+        };
+        Turn(permit)
+    }
+}
+
+/// Operation turn through the rate gate, held for the operation call.
+/// Completing the call consumes the permit, a dropped unfinished operation returns it to the gate.
+pub struct Turn(Option<OwnedSemaphorePermit>);
+
+impl Turn {
+    /// Consume the permit of the completed operation
+    pub fn complete(self) {
+        if let Some(permit) = self.0 {
+            permit.forget();
+        }
+    }
+}
@@ -0,0 +1,163 @@
+//! Replay of recorded transactions scenario
+
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use indicatif::ProgressBar;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use std::sync::Arc;
+use std::time::Instant;
+use tokio::sync::{RwLock, Semaphore};
+use tokio::time::{interval, Duration, MissedTickBehavior};
+use tokio_util::task::TaskTracker;
+use tracing::{info, warn};
+
+use tx_producer::prelude::*;
+use crate::scenarios::{TestScenario, Pacing};
+use crate::config::ThinkTime;
+use crate::stats::{ErrorCategory, TestStatistics};
+
+/// Recorded contract call, one JSON object per line of the replay file
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RecordedCall {
+    /// Function name
+    pub function: String,
+    /// Function arguments
+    #[serde(default)]
+    pub args: Vec<serde_json::Value>,
+}
+
+pub struct ReplayScenario {
+    calls: Vec<RecordedCall>,
+}
+
+impl ReplayScenario {
+    pub fn new(calls: Vec<RecordedCall>) -> Self {
+        Self { calls }
+    }
+
+    /// Load recorded calls from a JSON Lines file, skipping empty lines
+    pub fn from_file(path: &Path) -> Result<Self> {
+        let content = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read replay file {}", path.display()))?;
+
+        let calls = content
+            .lines()
+            .enumerate()
+            .filter(|(_, line)| !line.trim().is_empty())
+            .map(|(i, line)| {
+                serde_json::from_str::<RecordedCall>(line)
+                    .with_context(|| format!("Failed to parse recorded call at line {}", i + 1))
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(Self::new(calls))
+    }
+}
+
+#[async_trait]
+impl TestScenario for ReplayScenario {
+    fn name(&self) -> &str {
+        "Replay Test"
+    }
+
+    fn total_operations(&self) -> Option<usize> {
+        Some(self.calls.len())
+    }
+
+    async fn warmup_operation(&self, contract: &ContractClient) -> tx_producer::Result<B256> {
+        // Warm up with the first recorded call
+        let call = self.calls.first().ok_or_else(|| {
+            TxProducerError::InvalidInput("No recorded calls to warm up with".to_string())
+        })?;
+
+        TransactionBuilder::new(contract, call.function.clone())
+            .args(call.args.clone())
+            .send()
+            .await
+    }
+
+    async fn execute(
+        &self,
+        contract: Arc<ContractClient>,
+        stats: Arc<RwLock<TestStatistics>>,
+        progress: ProgressBar,
+        workers: usize,
+        pacing: &Pacing,
+        think_time: ThinkTime,
+        operations: &TaskTracker,
+    ) -> Result<()> {
+        info!("Starting replay test: {} recorded calls", self.calls.len());
+
+        let semaphore = Arc::new(Semaphore::new(workers));
+        let start_time = Instant::now();
+        let latency_histograms = stats.read().await.latency_histograms();
+
+        // Pace the calls submission when rate limit is set
+        let mut ticker = if pacing.rate_limit() > 0 {
+            let mut ticker = interval(Duration::from_secs_f64(1.0 / pacing.rate_limit() as f64));
+            ticker.set_missed_tick_behavior(MissedTickBehavior::Delay);
+            Some(ticker)
+        } else {
+            None
+        };
+
+        let mut tasks = Vec::new();
+
+        for (i, call) in self.calls.iter().enumerate() {
+            if let Some(ticker) = ticker.as_mut() {
+                ticker.tick().await;
+            }
+
+            let contract = contract.clone();
+            let pacing = pacing.clone();
+            let latency_histograms = latency_histograms.clone();
+            let stats = stats.clone();
+            let progress = progress.clone();
+            let permit = semaphore.clone().acquire_owned().await.unwrap();
+            let call = call.clone();
+
+            let task = operations.spawn(async move {
+                let _permit = permit;
+
+                let turn = pacing.turn().await;
+                let tx_start = Instant::now();
+                let timestamp = start_time.elapsed().as_secs_f64();
+
+                let result = TransactionBuilder::new(&contract, call.function.clone())
+                    .args(call.args)
+                    .send()
+                    .await;
+
+                turn.complete();
+
+                match result {
+                    Ok(_tx_hash) => {
+                        latency_histograms.record(tx_start.elapsed());
+                        let mut stats = stats.write().await;
+                        stats.record_success(100000, timestamp); // Approximate gas
+                        progress.set_message(format!("TPS: {:.2}", stats.tps(start_time.elapsed())));
+                    }
+                    Err(e) => {
+                        let mut stats = stats.write().await;
+                        stats.record_failure(ErrorCategory::from(&e), e.to_string(), timestamp);
+                        warn!("Recorded call {} ({}) failed: {}", i, call.function, e);
+                    }
+                }
+
+                progress.inc(1);
+                think_time.pause().await;
+            });
+
+            tasks.push(task);
+        }
+
+        // Wait for all tasks
+        for task in tasks {
+            let _ = task.await;
+        }
+
+        info!("Replay test completed");
+        Ok(())
+    }
+}
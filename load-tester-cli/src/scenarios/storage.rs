@@ -7,21 +7,30 @@ use std::sync::Arc;
 use std::time::Instant;
 use tokio::sync::{RwLock, Semaphore};
 use tokio::time::{sleep, Duration};
+use tokio_util::task::TaskTracker;
 use tracing::{info, warn};
 
 use tx_producer::prelude::*;
-use crate::scenarios::TestScenario;
-use crate::stats::TestStatistics;
+use crate::scenarios::{CallTemplate, TestScenario, Pacing};
+use crate::config::ThinkTime;
+use crate::stats::{ErrorCategory, TestStatistics};
 
 pub struct StorageScenario {
     writes: u64,
     reads: u64,
     count: usize,
+    call: Option<Arc<CallTemplate>>,
 }
 
 impl StorageScenario {
     pub fn new(writes: u64, reads: u64, count: usize) -> Self {
-        Self { writes, reads, count }
+        Self { writes, reads, count, call: None }
+    }
+
+    /// Call the configured contract function instead of `touchStorage`
+    pub fn with_call(mut self, call: Option<CallTemplate>) -> Self {
+        self.call = call.map(Arc::new);
+        self
     }
 }
 
@@ -31,8 +40,19 @@ impl TestScenario for StorageScenario {
         "Storage Stress Test"
     }
 
-    fn total_operations(&self) -> usize {
-        self.count
+    fn total_operations(&self) -> Option<usize> {
+        Some(self.count)
+    }
+
+    fn call_template(&self) -> Option<&CallTemplate> {
+        self.call.as_deref()
+    }
+
+    fn template_values(&self, _index: usize) -> Vec<(&'static str, String)> {
+        vec![
+            ("writes", self.writes.to_string()),
+            ("reads", self.reads.to_string()),
+        ]
     }
 
     async fn execute(
@@ -41,25 +61,33 @@ impl TestScenario for StorageScenario {
         stats: Arc<RwLock<TestStatistics>>,
         progress: ProgressBar,
         workers: usize,
-        rate_limit: u64,
+        pacing: &Pacing,
+        think_time: ThinkTime,
+        operations: &TaskTracker,
     ) -> Result<()> {
         info!("Starting storage stress test: {} writes, {} reads, {} transactions",
               self.writes, self.reads, self.count);
 
+        let rate_limit = pacing.rate_limit();
+
         let semaphore = Arc::new(Semaphore::new(workers));
         let start_time = Instant::now();
+        let latency_histograms = stats.read().await.latency_histograms();
 
         let mut tasks = Vec::new();
 
         for i in 0..self.count {
             let contract = contract.clone();
+            let pacing = pacing.clone();
+            let latency_histograms = latency_histograms.clone();
             let stats = stats.clone();
             let progress = progress.clone();
             let permit = semaphore.clone().acquire_owned().await.unwrap();
             let writes = self.writes;
             let reads = self.reads;
+            let call = self.call.clone().map(|call| (call, self.template_values(i)));
 
-            let task = tokio::spawn(async move {
+            let task = operations.spawn(async move {
                 let _permit = permit;
 
                 if rate_limit > 0 {
@@ -67,35 +95,44 @@ impl TestScenario for StorageScenario {
                     sleep(delay).await;
                 }
 
+                let turn = pacing.turn().await;
                 let tx_start = Instant::now();
                 let timestamp = start_time.elapsed().as_secs_f64();
 
                 // Generate random tag for this transaction
                 let tag = alloy_primitives::B256::random();
 
-                match contract.send_transaction(
-                    "touchStorage",
-                    &[
-                        DynSolValue::Uint(alloy_primitives::U256::from(writes).into(), 256),
-                        DynSolValue::Uint(alloy_primitives::U256::from(reads).into(), 256),
-                        DynSolValue::FixedBytes(tag, 32),
-                    ],
-                ).await {
+                let result = match &call {
+                    Some((call, values)) => call.send(&contract, i, values).await,
+                    None => contract.send_transaction(
+                        "touchStorage",
+                        &[
+                            DynSolValue::Uint(alloy_primitives::U256::from(writes).into(), 256),
+                            DynSolValue::Uint(alloy_primitives::U256::from(reads).into(), 256),
+                            DynSolValue::FixedBytes(tag, 32),
+                        ],
+                    ).await,
+                };
+
+                turn.complete();
+
+                match result {
                     Ok(_tx_hash) => {
-                        let latency = tx_start.elapsed();
+                        latency_histograms.record(tx_start.elapsed());
                         let gas_estimate = 20000 + (writes * 20000) + (reads * 2100);
                         let mut stats = stats.write().await;
-                        stats.record_success(latency, gas_estimate, timestamp);
+                        stats.record_success(gas_estimate, timestamp);
                         progress.set_message(format!("TPS: {:.2}", stats.tps(start_time.elapsed())));
                     }
                     Err(e) => {
                         let mut stats = stats.write().await;
-                        stats.record_failure(e.to_string(), timestamp);
+                        stats.record_failure(ErrorCategory::from(&e), e.to_string(), timestamp);
                         warn!("Transaction {} failed: {}", i, e);
                     }
                 }
 
                 progress.inc(1);
+                think_time.pause().await;
             });
 
             tasks.push(task);
@@ -7,22 +7,31 @@ use std::sync::Arc;
 use std::time::Instant;
 use tokio::sync::{RwLock, Semaphore};
 use tokio::time::{sleep, Duration};
+use tokio_util::task::TaskTracker;
 use tracing::{info, warn};
 
 use tx_producer::prelude::*;
-use crate::scenarios::TestScenario;
-use crate::stats::TestStatistics;
+use crate::scenarios::{CallTemplate, TestScenario, Pacing};
+use crate::config::ThinkTime;
+use crate::stats::{ErrorCategory, TestStatistics};
 
 pub struct StressScenario {
     ramp_up: u64,
     peak: u64,
     ramp_down: u64,
     target_tps: u64,
+    call: Option<Arc<CallTemplate>>,
 }
 
 impl StressScenario {
     pub fn new(ramp_up: u64, peak: u64, ramp_down: u64, target_tps: u64) -> Self {
-        Self { ramp_up, peak, ramp_down, target_tps }
+        Self { ramp_up, peak, ramp_down, target_tps, call: None }
+    }
+
+    /// Call the configured contract function instead of `consumeGas`
+    pub fn with_call(mut self, call: Option<CallTemplate>) -> Self {
+        self.call = call.map(Arc::new);
+        self
     }
 
     fn calculate_current_tps(&self, elapsed: u64) -> u64 {
@@ -48,9 +57,13 @@ impl TestScenario for StressScenario {
         "Stress Test"
     }
 
-    fn total_operations(&self) -> usize {
+    fn total_operations(&self) -> Option<usize> {
         let total_time = self.ramp_up + self.peak + self.ramp_down;
-        (self.target_tps * total_time / 2) as usize
+        Some((self.target_tps * total_time / 2) as usize)
+    }
+
+    fn call_template(&self) -> Option<&CallTemplate> {
+        self.call.as_deref()
     }
 
     async fn execute(
@@ -59,17 +72,21 @@ impl TestScenario for StressScenario {
         stats: Arc<RwLock<TestStatistics>>,
         progress: ProgressBar,
         workers: usize,
-        _rate_limit: u64,
+        pacing: &Pacing,
+        think_time: ThinkTime,
+        operations: &TaskTracker,
     ) -> Result<()> {
         info!("Starting stress test: ramp_up={}s, peak={}s, ramp_down={}s, target={}tps",
               self.ramp_up, self.peak, self.ramp_down, self.target_tps);
 
         let semaphore = Arc::new(Semaphore::new(workers));
         let start_time = Instant::now();
+        let latency_histograms = stats.read().await.latency_histograms();
         let total_duration = self.ramp_up + self.peak + self.ramp_down;
 
         let mut last_second = 0;
         let mut current_second_count = 0;
+        let mut index = 0;
 
         while start_time.elapsed().as_secs() < total_duration {
             let elapsed = start_time.elapsed().as_secs();
@@ -87,38 +104,51 @@ impl TestScenario for StressScenario {
             }
 
             let contract = contract.clone();
+            let pacing = pacing.clone();
+            let latency_histograms = latency_histograms.clone();
             let stats = stats.clone();
             let progress = progress.clone();
             let permit = semaphore.clone().acquire_owned().await.unwrap();
+            let call = self.call.clone().map(|call| (call, self.template_values(index)));
 
-            tokio::spawn(async move {
+            operations.spawn(async move {
                 let _permit = permit;
 
+                let turn = pacing.turn().await;
                 let tx_start = Instant::now();
                 let timestamp = start_time.elapsed().as_secs_f64();
 
-                match contract.send_transaction(
-                    "consumeGas",
-                    &[DynSolValue::Uint(alloy_primitives::U256::from(50).into(), 256)],
-                ).await {
+                let result = match &call {
+                    Some((call, values)) => call.send(&contract, index, values).await,
+                    None => contract.send_transaction(
+                        "consumeGas",
+                        &[DynSolValue::Uint(alloy_primitives::U256::from(50).into(), 256)],
+                    ).await,
+                };
+
+                turn.complete();
+
+                match result {
                     Ok(_tx_hash) => {
-                        let latency = tx_start.elapsed();
+                        latency_histograms.record(tx_start.elapsed());
                         let mut stats = stats.write().await;
-                        stats.record_success(latency, 50000, timestamp);
+                        stats.record_success(50000, timestamp);
                         progress.set_message(format!("Target: {} TPS, Current: {:.2} TPS",
                                                     target_tps, stats.tps(start_time.elapsed())));
                     }
                     Err(e) => {
                         let mut stats = stats.write().await;
-                        stats.record_failure(e.to_string(), timestamp);
+                        stats.record_failure(ErrorCategory::from(&e), e.to_string(), timestamp);
                         warn!("Transaction failed: {}", e);
                     }
                 }
 
                 progress.inc(1);
+                think_time.pause().await;
             });
 
             current_second_count += 1;
+            index += 1;
             sleep(Duration::from_millis(1000 / target_tps.max(1))).await;
         }
 
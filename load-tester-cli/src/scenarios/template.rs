@@ -0,0 +1,143 @@
+//! Configurable contract call of the scenarios, in place of their built-in call
+
+use anyhow::{bail, Result};
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use tx_producer::prelude::*;
+
+/// Placeholders available to every scenario
+const COMMON_PLACEHOLDERS: &[&str] = &["index", "random", "random_bytes32", "random_address", "timestamp"];
+
+/// Contract function a scenario calls on each operation, with its arguments template.
+/// String arguments (also inside arrays and structs) may contain placeholders,
+/// e.g. `"{index}"`, replaced per operation:
+/// - `{index}` - operation index, starting from 0
+/// - `{random}` - random 64-bit number
+/// - `{random_bytes32}` - random 32 bytes hex
+/// - `{random_address}` - random address
+/// - `{timestamp}` - current Unix time in seconds
+///
+/// and the scenario values (see [`TestScenario::template_values`](super::TestScenario::template_values)).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CallTemplate {
+    pub function: String,
+    #[serde(default)]
+    pub args: Vec<Value>,
+}
+
+impl CallTemplate {
+    /// Check the function exists in the contract ABI, takes as many arguments as the template has,
+    /// the template placeholders are either common or the scenario ones, and the arguments of the
+    /// first operation, rendered with its scenario values, convert to the function input types
+    pub fn validate(&self, contract: &ContractClient, scenario_values: &[(&str, String)]) -> Result<()> {
+        let function = contract.get_function(&self.function)?;
+        if function.inputs.len() != self.args.len() {
+            bail!("Function '{}' takes {} arguments, the call template has {}",
+                  self.function, function.inputs.len(), self.args.len());
+        }
+
+        let scenario_placeholders: Vec<&str> = scenario_values.iter().map(|(name, _)| *name).collect();
+
+        let mut placeholders = Vec::new();
+        for arg in &self.args {
+            collect_placeholders(arg, &mut placeholders);
+        }
+
+        for placeholder in placeholders {
+            if !COMMON_PLACEHOLDERS.contains(&placeholder.as_str())
+                && !scenario_placeholders.contains(&placeholder.as_str())
+            {
+                bail!("Unknown placeholder '{{{}}}' in the '{}' call template, available: {}",
+                      placeholder,
+                      self.function,
+                      COMMON_PLACEHOLDERS
+                          .iter()
+                          .chain(&scenario_placeholders)
+                          .map(|name| format!("{{{}}}", name))
+                          .collect::<Vec<_>>()
+                          .join(", "));
+            }
+        }
+
+        value_helpers::args_from_json(function, &self.render(0, scenario_values))
+            .map_err(|e| anyhow::anyhow!("Arguments of the '{}' call template: {}", self.function, e))?;
+
+        Ok(())
+    }
+
+    /// Arguments of the operation, with the placeholders replaced
+    pub fn render(&self, index: usize, values: &[(&str, String)]) -> Vec<Value> {
+        let mut rng = rand::thread_rng();
+        let timestamp = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|elapsed| elapsed.as_secs())
+            .unwrap_or_default();
+
+        let mut replace = |text: &str| {
+            let mut text = text.replace("{index}", &index.to_string());
+            // Random values are drawn per occurrence
+            while text.contains("{random}") {
+                text = text.replacen("{random}", &rng.gen::<u64>().to_string(), 1);
+            }
+            while text.contains("{random_bytes32}") {
+                text = text.replacen("{random_bytes32}", &B256::random().to_string(), 1);
+            }
+            while text.contains("{random_address}") {
+                text = text.replacen("{random_address}", &Address::random().to_string(), 1);
+            }
+            text = text.replace("{timestamp}", &timestamp.to_string());
+            for (name, value) in values {
+                text = text.replace(&format!("{{{}}}", name), value);
+            }
+            text
+        };
+
+        self.args.iter().map(|arg| render_value(arg, &mut replace)).collect()
+    }
+
+    /// Send the call of the operation
+    pub async fn send(
+        &self,
+        contract: &ContractClient,
+        index: usize,
+        values: &[(&str, String)],
+    ) -> tx_producer::Result<B256> {
+        let function = contract.get_function(&self.function)?;
+        let args = value_helpers::args_from_json(function, &self.render(index, values))?;
+        contract.send_transaction(&self.function, &args).await
+    }
+}
+
+fn render_value(value: &Value, replace: &mut impl FnMut(&str) -> String) -> Value {
+    match value {
+        Value::String(text) => Value::String(replace(text)),
+        Value::Array(items) => Value::Array(items.iter().map(|item| render_value(item, replace)).collect()),
+        Value::Object(fields) => Value::Object(
+            fields
+                .iter()
+                .map(|(name, field)| (name.clone(), render_value(field, replace)))
+                .collect(),
+        ),
+        other => other.clone(),
+    }
+}
+
+fn collect_placeholders(value: &Value, placeholders: &mut Vec<String>) {
+    match value {
+        Value::String(text) => {
+            let mut rest = text.as_str();
+            while let Some(start) = rest.find('{') {
+                let Some(end) = rest[start..].find('}') else {
+                    break;
+                };
+                placeholders.push(rest[start + 1..start + end].to_string());
+                rest = &rest[start + end + 1..];
+            }
+        }
+        Value::Array(items) => items.iter().for_each(|item| collect_placeholders(item, placeholders)),
+        Value::Object(fields) => fields.values().for_each(|field| collect_placeholders(field, placeholders)),
+        _ => {}
+    }
+}
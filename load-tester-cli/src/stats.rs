@@ -1,8 +1,195 @@
 //! Statistics collection and reporting
 
+use std::cell::Cell;
+use std::collections::BTreeMap;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
 use std::time::Duration;
 use hdrhistogram::Histogram;
 use serde::{Deserialize, Serialize};
+use tx_producer::TxProducerError;
+
+/// Highest trackable latency in microseconds (1 hour)
+const MAX_TRACKABLE_LATENCY_US: u64 = 3_600_000_000;
+
+/// Counter for assigning histogram shards to worker threads
+static NEXT_SHARD: AtomicUsize = AtomicUsize::new(0);
+
+thread_local! {
+    /// Histogram shard index of the current worker thread
+    static SHARD_INDEX: Cell<Option<usize>> = const { Cell::new(None) };
+}
+
+fn new_latency_histogram() -> Histogram<u64> {
+    Histogram::<u64>::new_with_bounds(1, MAX_TRACKABLE_LATENCY_US, 3).unwrap()
+}
+
+/// Latency histograms sharded per worker thread, merged on read.
+/// Each runtime worker thread records into its own shard, so concurrent
+/// recordings don't contend on a single histogram lock.
+#[derive(Debug)]
+pub struct LatencyHistograms {
+    shards: Vec<Mutex<Histogram<u64>>>,
+}
+
+impl LatencyHistograms {
+    pub fn new(shards: usize) -> Self {
+        Self {
+            shards: (0..shards.max(1))
+                .map(|_| Mutex::new(new_latency_histogram()))
+                .collect(),
+        }
+    }
+
+    /// Record latency with microsecond resolution
+    pub fn record(&self, latency: Duration) {
+        let index = SHARD_INDEX.with(|shard| {
+            shard.get().unwrap_or_else(|| {
+                let index = NEXT_SHARD.fetch_add(1, Ordering::Relaxed);
+                shard.set(Some(index));
+                index
+            })
+        }) % self.shards.len();
+
+        let latency_us = (latency.as_micros() as u64).clamp(1, MAX_TRACKABLE_LATENCY_US);
+        let mut histogram = self.shards[index].lock().unwrap();
+        histogram.saturating_record(latency_us);
+    }
+
+    /// Merge all shards into a single histogram
+    pub fn merged(&self) -> Histogram<u64> {
+        let mut merged = new_latency_histogram();
+        for shard in &self.shards {
+            let _ = merged.add(&*shard.lock().unwrap());
+        }
+        merged
+    }
+
+    /// Add the recorded latencies of other histograms
+    pub fn add(&self, other: &LatencyHistograms) {
+        let merged = other.merged();
+        let _ = self.shards[0].lock().unwrap().add(&merged);
+    }
+
+    /// Mean latency in milliseconds
+    pub fn mean_ms(&self) -> f64 {
+        let merged = self.merged();
+        if merged.is_empty() {
+            return 0.0;
+        }
+        merged.mean() / 1000.0
+    }
+
+    pub fn summary(&self) -> LatencySummary {
+        LatencySummary::from_histogram(&self.merged())
+    }
+}
+
+impl Default for LatencyHistograms {
+    fn default() -> Self {
+        let shards = std::thread::available_parallelism()
+            .map(|n| n.get())
+            .unwrap_or(1);
+        Self::new(shards)
+    }
+}
+
+/// Latency percentiles in milliseconds
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+pub struct LatencySummary {
+    pub count: u64,
+    pub p50_ms: f64,
+    pub p95_ms: f64,
+    pub p99_ms: f64,
+    pub max_ms: f64,
+}
+
+impl LatencySummary {
+    pub fn from_histogram(histogram: &Histogram<u64>) -> Self {
+        if histogram.is_empty() {
+            return Self::default();
+        }
+        Self {
+            count: histogram.len(),
+            p50_ms: histogram.value_at_quantile(0.50) as f64 / 1000.0,
+            p95_ms: histogram.value_at_quantile(0.95) as f64 / 1000.0,
+            p99_ms: histogram.value_at_quantile(0.99) as f64 / 1000.0,
+            max_ms: histogram.max() as f64 / 1000.0,
+        }
+    }
+}
+
+impl std::fmt::Display for LatencySummary {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "p50: {:.2}ms, p95: {:.2}ms, p99: {:.2}ms, max: {:.2}ms",
+               self.p50_ms, self.p95_ms, self.p99_ms, self.max_ms)
+    }
+}
+
+/// Failed transaction category, for failures diagnostics
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ErrorCategory {
+    Revert,
+    Nonce,
+    Timeout,
+    RateLimited,
+    Other,
+}
+
+impl ErrorCategory {
+    /// Classify RPC node and transport errors by the error message
+    fn from_message(message: &str) -> Self {
+        let message = message.to_lowercase();
+
+        if message.contains("revert") {
+            ErrorCategory::Revert
+        } else if message.contains("nonce") || message.contains("already known") {
+            ErrorCategory::Nonce
+        } else if message.contains("timeout") || message.contains("timed out") {
+            ErrorCategory::Timeout
+        } else if message.contains("429") || message.contains("rate limit") || message.contains("too many requests") {
+            ErrorCategory::RateLimited
+        } else {
+            ErrorCategory::Other
+        }
+    }
+}
+
+impl From<&TxProducerError> for ErrorCategory {
+    fn from(error: &TxProducerError) -> Self {
+        match error {
+            TxProducerError::ConfirmationTimeout { .. } => ErrorCategory::Timeout,
+            TxProducerError::Provider(message)
+            | TxProducerError::ContractCall(message)
+            | TxProducerError::Transaction(message)
+            | TxProducerError::Internal(message) => ErrorCategory::from_message(message),
+            _ => ErrorCategory::Other,
+        }
+    }
+}
+
+impl std::fmt::Display for ErrorCategory {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            ErrorCategory::Revert => "Revert",
+            ErrorCategory::Nonce => "Nonce",
+            ErrorCategory::Timeout => "Timeout",
+            ErrorCategory::RateLimited => "Rate limited",
+            ErrorCategory::Other => "Other",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// Point of the test where the error rate first crossed the configured threshold
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct ErrorRateCrossing {
+    pub elapsed_secs: f64,
+    pub target_tps: Option<f64>,
+    pub observed_tps: f64,
+    pub error_rate: f64,
+}
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TestStatistics {
@@ -11,11 +198,12 @@ pub struct TestStatistics {
     pub failed_transactions: u64,
     pub total_gas_used: u64,
     pub errors: Vec<String>,
+    pub error_categories: BTreeMap<ErrorCategory, u64>,
+    pub error_rate_crossing: Option<ErrorRateCrossing>,
 
     #[serde(skip)]
-    latency_histogram: Histogram<u64>,
+    latency_histograms: Arc<LatencyHistograms>,
 
-    latencies_ms: Vec<f64>,
     start_times: Vec<f64>,
 }
 
@@ -27,28 +215,29 @@ impl TestStatistics {
             failed_transactions: 0,
             total_gas_used: 0,
             errors: Vec::new(),
-            latency_histogram: Histogram::<u64>::new(3).unwrap(),
-            latencies_ms: Vec::new(),
+            error_categories: BTreeMap::new(),
+            error_rate_crossing: None,
+            latency_histograms: Arc::new(LatencyHistograms::default()),
             start_times: Vec::new(),
         }
     }
 
-    pub fn record_success(&mut self, latency: Duration, gas_used: u64, timestamp: f64) {
+    /// Record a successful transaction, its latency is recorded by the worker into
+    /// the [`latency_histograms`](Self::latency_histograms), without the statistics lock
+    pub fn record_success(&mut self, gas_used: u64, timestamp: f64) {
         self.total_transactions += 1;
         self.successful_transactions += 1;
         self.total_gas_used += gas_used;
 
-        let latency_ms = latency.as_millis() as u64;
-        let _ = self.latency_histogram.record(latency_ms);
-
-        self.latencies_ms.push(latency.as_secs_f64() * 1000.0);
         self.start_times.push(timestamp);
     }
 
-    pub fn record_failure(&mut self, error: String, timestamp: f64) {
+    pub fn record_failure(&mut self, category: ErrorCategory, error: String, timestamp: f64) {
         self.total_transactions += 1;
         self.failed_transactions += 1;
 
+        *self.error_categories.entry(category).or_insert(0) += 1;
+
         if self.errors.len() < 100 {
             self.errors.push(error);
         }
@@ -71,26 +260,17 @@ impl TestStatistics {
     }
 
     pub fn avg_latency_ms(&self) -> f64 {
-        if self.latencies_ms.is_empty() {
-            return 0.0;
-        }
-        self.latencies_ms.iter().sum::<f64>() / self.latencies_ms.len() as f64
-    }
-
-    pub fn p50_latency_ms(&self) -> f64 {
-        self.latency_histogram.value_at_percentile(50.0) as f64
-    }
-
-    pub fn p95_latency_ms(&self) -> f64 {
-        self.latency_histogram.value_at_percentile(95.0) as f64
+        self.latency_histograms.mean_ms()
     }
 
-    pub fn p99_latency_ms(&self) -> f64 {
-        self.latency_histogram.value_at_percentile(99.0) as f64
+    /// Latency percentiles merged from all worker histograms
+    pub fn latency_summary(&self) -> LatencySummary {
+        self.latency_histograms.summary()
     }
 
-    pub fn max_latency_ms(&self) -> f64 {
-        self.latency_histogram.max() as f64
+    /// Shared handle of the per-worker latency histograms
+    pub fn latency_histograms(&self) -> Arc<LatencyHistograms> {
+        self.latency_histograms.clone()
     }
 
     pub fn avg_gas_per_tx(&self) -> f64 {
@@ -106,12 +286,12 @@ impl TestStatistics {
         self.failed_transactions += other.failed_transactions;
         self.total_gas_used += other.total_gas_used;
         self.errors.extend(other.errors.clone());
-        self.latencies_ms.extend(other.latencies_ms.clone());
-        self.start_times.extend(other.start_times.clone());
-
-        for &latency in &other.latencies_ms {
-            let _ = self.latency_histogram.record(latency as u64);
+        for (category, count) in &other.error_categories {
+            *self.error_categories.entry(*category).or_insert(0) += count;
         }
+        self.error_rate_crossing = self.error_rate_crossing.or(other.error_rate_crossing);
+        self.start_times.extend(other.start_times.clone());
+        self.latency_histograms.add(&other.latency_histograms);
     }
 
     pub fn export_csv(&self, path: &str) -> std::io::Result<()> {
@@ -119,11 +299,10 @@ impl TestStatistics {
         use std::io::Write;
 
         let mut file = File::create(path)?;
-        writeln!(file, "timestamp,latency_ms,success")?;
+        writeln!(file, "timestamp")?;
 
-        for (i, (&time, &latency)) in self.start_times.iter().zip(&self.latencies_ms).enumerate() {
-            let success = i < self.successful_transactions as usize;
-            writeln!(file, "{},{},{}", time, latency, success)?;
+        for time in &self.start_times {
+            writeln!(file, "{}", time)?;
         }
 
         Ok(())
@@ -32,6 +32,7 @@ CREATE TABLE IF NOT EXISTS mempool_monitor_data (
     transaction_hash TEXT NOT NULL UNIQUE,
     transaction_sender TEXT NOT NULL,
     transaction_receiver TEXT,
+    created_contract_address TEXT,
     nonce TEXT NOT NULL,
     value TEXT NOT NULL,
     gas_limit TEXT NOT NULL,
@@ -45,11 +46,15 @@ CREATE TABLE IF NOT EXISTS mempool_monitor_data (
     UNIQUE (chain_id, transaction_hash, nonce)
 );
 
+-- Created contract address of contract creation transactions, for tables created before the column was added
+ALTER TABLE mempool_monitor_data ADD COLUMN IF NOT EXISTS created_contract_address TEXT;
+
 -- Indexes for efficient querying
 CREATE INDEX IF NOT EXISTS idx_mempool_tx_chain_id ON mempool_monitor_data(chain_id);
 CREATE INDEX IF NOT EXISTS idx_mempool_tx_hash ON mempool_monitor_data(transaction_hash);
 CREATE INDEX IF NOT EXISTS idx_mempool_tx_sender ON mempool_monitor_data(transaction_sender);
 CREATE INDEX IF NOT EXISTS idx_mempool_tx_receiver ON mempool_monitor_data(transaction_receiver);
+CREATE INDEX IF NOT EXISTS idx_mempool_tx_created_contract_address ON mempool_monitor_data(created_contract_address);
 CREATE INDEX IF NOT EXISTS idx_mempool_tx_nonce ON mempool_monitor_data(nonce);
 CREATE INDEX IF NOT EXISTS idx_mempool_tx_value ON mempool_monitor_data(value);
 CREATE INDEX IF NOT EXISTS idx_mempool_tx_gas_limit ON mempool_monitor_data(gas_limit);
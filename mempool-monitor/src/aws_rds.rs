@@ -1,5 +1,12 @@
 use tokio_postgres::{Client, NoTls, Config as PgConfig};
+use tokio::sync::{Mutex, Notify, RwLock};
+use tokio::task::JoinHandle;
+use tokio_util::sync::{CancellationToken, DropGuard};
 use tracing::{info, error, warn, debug};
+use serde::{Serialize, Deserialize};
+use std::collections::VecDeque;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::time::Duration;
 
 use crate::types::TransactionPayload;
@@ -74,6 +81,7 @@ impl AwsRdsClient {
                 transaction_hash,
                 transaction_sender,
                 transaction_receiver,
+                created_contract_address,
                 nonce,
                 value,
                 gas_limit,
@@ -83,10 +91,11 @@ impl AwsRdsClient {
                 input_data,
                 transaction_type,
                 timestamp
-            ) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13)
+            ) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14)
             ON CONFLICT (chain_id, transaction_hash) DO UPDATE SET
                 transaction_sender = EXCLUDED.transaction_sender,
                 transaction_receiver = EXCLUDED.transaction_receiver,
+                created_contract_address = COALESCE(EXCLUDED.created_contract_address, mempool_monitor_data.created_contract_address),
                 nonce = EXCLUDED.nonce,
                 value = EXCLUDED.value,
                 gas_limit = EXCLUDED.gas_limit,
@@ -106,6 +115,7 @@ impl AwsRdsClient {
                     &payload.transaction_hash,
                     &payload.transaction_sender,
                     &payload.transaction_receiver,
+                    &payload.created_contract_address,
                     &payload.nonce,
                     &payload.value,
                     &payload.gas_limit,
@@ -125,11 +135,16 @@ impl AwsRdsClient {
             },
             Err(e) => {
                 error!("Failed to insert transaction to AWS RDS: {:?}", e);
-                Err(anyhow::anyhow!("AWS RDS insertion failed: {:?}", e))
+                // Keeps the database error as the source, to tell permanent failures apart
+                Err(anyhow::Error::new(e).context("AWS RDS insertion failed"))
             }
         }
     }
 
+    pub fn is_closed(&self) -> bool {
+        self.client.is_closed()
+    }
+
     pub async fn test_connection(&self) -> anyhow::Result<()> {
         match self.client.execute("SELECT 1", &[]).await {
             Ok(_) => {
@@ -163,3 +178,297 @@ impl AwsRdsClient {
 pub async fn create_aws_rds_client(config: &AwsRdsCfg) -> anyhow::Result<AwsRdsClient> {
     AwsRdsClient::new(config).await
 }
+
+const DEFAULT_WRITE_BUFFER_SIZE: usize = 10_000;
+const DEFAULT_WRITE_RETRY_INTERVAL_MS: u64 = 500;
+const MAX_WRITE_RETRY_INTERVAL: Duration = Duration::from_secs(30);
+/// Max time to wait for the worker to finish an in-flight write once stopped
+const WORKER_STOP_TIMEOUT: Duration = Duration::from_secs(5);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BufferFullPolicy {
+    DropOldest,
+    Backpressure,
+}
+
+impl BufferFullPolicy {
+    fn from_config(policy: Option<&str>) -> Self {
+        match policy.unwrap_or("drop_oldest") {
+            "drop_oldest" => Self::DropOldest,
+            "backpressure" => Self::Backpressure,
+            other => {
+                warn!("Unknown AWS RDS write buffer policy: {:?}, using drop_oldest", other);
+                Self::DropOldest
+            }
+        }
+    }
+}
+
+/// Snapshot of the AWS RDS write buffer state for monitoring
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RdsBufferStats {
+    pub depth: usize,
+    pub capacity: usize,
+    pub replicated: u64,
+    pub dropped: u64,
+    /// Transactions AWS RDS rejected, dropped without retrying
+    pub rejected: u64,
+    pub failed_attempts: u64,
+}
+
+struct RdsBufferState {
+    queue: Mutex<VecDeque<TransactionPayload>>,
+    capacity: usize,
+    policy: BufferFullPolicy,
+    item_available: Notify,
+    space_available: Notify,
+    replicated: AtomicU64,
+    dropped: AtomicU64,
+    rejected: AtomicU64,
+    failed_attempts: AtomicU64,
+}
+
+/// Bounded in-memory buffer in front of AWS RDS.
+///
+/// Transactions are enqueued after the local PostgreSQL write and replicated
+/// by a background worker, which retries with backoff and reconnects when the
+/// AWS RDS connection is lost, so an AWS RDS outage never stalls local indexing.
+#[derive(Clone)]
+pub struct RdsWriteBuffer {
+    state: Arc<RdsBufferState>,
+    client: Arc<RwLock<AwsRdsClient>>,
+}
+
+/// Background replication worker of an `RdsWriteBuffer`, stopped when dropped.
+/// Call `shutdown` to flush the buffer and wait for the worker to exit.
+pub struct RdsWorkerHandle {
+    buffer: RdsWriteBuffer,
+    stop: DropGuard,
+    handle: JoinHandle<()>,
+}
+
+impl RdsWorkerHandle {
+    /// Wait until the buffer is drained, up to `timeout`, then stop the worker and join it.
+    /// Transactions still buffered after the timeout are not replicated.
+    pub async fn shutdown(self, timeout: Duration) {
+        let Self { buffer, stop, mut handle } = self;
+        buffer.flush(timeout).await;
+        drop(stop);
+
+        match tokio::time::timeout(WORKER_STOP_TIMEOUT, &mut handle).await {
+            Ok(Ok(())) => info!("AWS RDS write buffer worker stopped"),
+            Ok(Err(join_err)) => error!("AWS RDS write buffer worker panicked: {:?}", join_err),
+            Err(_) => {
+                warn!("AWS RDS write buffer worker didn't stop within {:?}, aborting", WORKER_STOP_TIMEOUT);
+                handle.abort();
+            }
+        }
+    }
+}
+
+impl RdsWriteBuffer {
+    /// Buffer with its replication worker spawned, the worker runs until its handle is shut down or dropped
+    pub fn new(client: AwsRdsClient) -> (Self, RdsWorkerHandle) {
+        let capacity = client.config.write_buffer_size.unwrap_or(DEFAULT_WRITE_BUFFER_SIZE).max(1);
+        let policy = BufferFullPolicy::from_config(client.config.write_buffer_policy.as_deref());
+        let retry_interval = Duration::from_millis(
+            client.config.write_retry_interval_ms.unwrap_or(DEFAULT_WRITE_RETRY_INTERVAL_MS).max(1)
+        );
+
+        info!("AWS RDS write buffer: capacity {}, policy {:?}", capacity, policy);
+
+        let buffer = Self {
+            state: Arc::new(RdsBufferState {
+                queue: Mutex::new(VecDeque::with_capacity(capacity.min(1024))),
+                capacity,
+                policy,
+                item_available: Notify::new(),
+                space_available: Notify::new(),
+                replicated: AtomicU64::new(0),
+                dropped: AtomicU64::new(0),
+                rejected: AtomicU64::new(0),
+                failed_attempts: AtomicU64::new(0),
+            }),
+            client: Arc::new(RwLock::new(client)),
+        };
+
+        let stop = CancellationToken::new();
+        let worker = buffer.clone();
+        let worker_stop = stop.clone();
+        let handle = tokio::spawn(async move { worker.run_worker(retry_interval, worker_stop).await });
+
+        let worker_handle = RdsWorkerHandle {
+            buffer: buffer.clone(),
+            stop: stop.drop_guard(),
+            handle,
+        };
+
+        (buffer, worker_handle)
+    }
+
+    /// Queue a transaction for replication to AWS RDS.
+    /// With the `backpressure` policy waits for free space when the buffer is full,
+    /// with `drop_oldest` evicts the oldest queued transaction instead.
+    pub async fn push(&self, payload: TransactionPayload) {
+        loop {
+            let mut queue = self.state.queue.lock().await;
+            if queue.len() < self.state.capacity {
+                queue.push_back(payload);
+                drop(queue);
+                self.state.item_available.notify_one();
+                return;
+            }
+
+            match self.state.policy {
+                BufferFullPolicy::DropOldest => {
+                    if let Some(dropped) = queue.pop_front() {
+                        self.state.dropped.fetch_add(1, Ordering::Relaxed);
+                        warn!("AWS RDS write buffer full, dropping oldest transaction: {}", dropped.transaction_hash);
+                    }
+                    queue.push_back(payload);
+                    drop(queue);
+                    self.state.item_available.notify_one();
+                    return;
+                }
+                BufferFullPolicy::Backpressure => {
+                    // Register interest before releasing the lock so a wakeup isn't missed
+                    let space_available = self.state.space_available.notified();
+                    drop(queue);
+                    debug!("AWS RDS write buffer full, waiting for free space");
+                    space_available.await;
+                }
+            }
+        }
+    }
+
+    pub async fn depth(&self) -> usize {
+        self.state.queue.lock().await.len()
+    }
+
+    pub async fn stats(&self) -> RdsBufferStats {
+        RdsBufferStats {
+            depth: self.depth().await,
+            capacity: self.state.capacity,
+            replicated: self.state.replicated.load(Ordering::Relaxed),
+            dropped: self.state.dropped.load(Ordering::Relaxed),
+            rejected: self.state.rejected.load(Ordering::Relaxed),
+            failed_attempts: self.state.failed_attempts.load(Ordering::Relaxed),
+        }
+    }
+
+    pub async fn test_connection(&self) -> anyhow::Result<()> {
+        self.client.read().await.test_connection().await
+    }
+
+    /// Wait until the buffer is drained, up to `timeout`
+    pub async fn flush(&self, timeout: Duration) {
+        let deadline = tokio::time::Instant::now() + timeout;
+        loop {
+            let depth = self.depth().await;
+            if depth == 0 {
+                info!("AWS RDS write buffer flushed");
+                return;
+            }
+            if tokio::time::Instant::now() >= deadline {
+                warn!("AWS RDS write buffer flush timed out, {} transactions not replicated", depth);
+                return;
+            }
+            tokio::time::sleep(Duration::from_millis(100)).await;
+        }
+    }
+
+    async fn run_worker(&self, retry_interval: Duration, stop: CancellationToken) {
+        let mut backoff = retry_interval;
+
+        while !stop.is_cancelled() {
+            // Peek the oldest transaction, it's removed only after a successful write
+            let next = {
+                let item_available = self.state.item_available.notified();
+                let queue = self.state.queue.lock().await;
+                match queue.front() {
+                    Some(payload) => Some(payload.clone()),
+                    None => {
+                        drop(queue);
+                        tokio::select! {
+                            _ = item_available => {}
+                            _ = stop.cancelled() => {}
+                        }
+                        None
+                    }
+                }
+            };
+            let Some(payload) = next else { continue };
+
+            let result = self.client.read().await.insert_transaction(&payload).await;
+            match result {
+                Ok(()) => {
+                    self.pop_written(&payload).await;
+                    self.state.replicated.fetch_add(1, Ordering::Relaxed);
+                    backoff = retry_interval;
+                    debug!("Transaction replicated to AWS RDS: {}", payload.transaction_hash);
+                }
+                // Retrying a transaction AWS RDS rejects would stall every write queued behind it
+                Err(e) if is_permanent_write_error(&e) => {
+                    self.pop_written(&payload).await;
+                    self.state.rejected.fetch_add(1, Ordering::Relaxed);
+                    backoff = retry_interval;
+                    error!(
+                        "AWS RDS rejected transaction {}, dropping it from the write buffer: {:?}",
+                        payload.transaction_hash, e
+                    );
+                }
+                Err(e) => {
+                    self.state.failed_attempts.fetch_add(1, Ordering::Relaxed);
+                    warn!(
+                        "AWS RDS replication failed, retrying in {:?} ({} transactions buffered): {:?}",
+                        backoff, self.depth().await, e
+                    );
+                    tokio::select! {
+                        _ = tokio::time::sleep(backoff) => {}
+                        _ = stop.cancelled() => break,
+                    }
+                    backoff = (backoff * 2).min(MAX_WRITE_RETRY_INTERVAL);
+                    self.reconnect_if_closed().await;
+                }
+            }
+        }
+    }
+
+    /// Remove the written (or rejected) head of the queue and wake a producer waiting for space
+    async fn pop_written(&self, payload: &TransactionPayload) {
+        {
+            let mut queue = self.state.queue.lock().await;
+            // The head may have been evicted by drop_oldest while writing
+            if queue.front().is_some_and(|front| front.transaction_hash == payload.transaction_hash) {
+                queue.pop_front();
+            }
+        }
+        self.state.space_available.notify_one();
+    }
+
+    async fn reconnect_if_closed(&self) {
+        if !self.client.read().await.is_closed() {
+            return;
+        }
+
+        let config = self.client.read().await.config.clone();
+        info!("AWS RDS connection lost, reconnecting...");
+        match AwsRdsClient::new(&config).await {
+            Ok(client) => {
+                *self.client.write().await = client;
+                info!("Reconnected to AWS RDS");
+            }
+            Err(e) => warn!("Failed to reconnect to AWS RDS: {:?}", e),
+        }
+    }
+}
+
+/// Whether a failed AWS RDS write fails again however often it's retried: the server rejected
+/// the transaction data itself (SQLSTATE class 22 data exception or 23 integrity constraint
+/// violation). Lost connections, overloaded servers and schema errors are retried.
+fn is_permanent_write_error(error: &anyhow::Error) -> bool {
+    error
+        .downcast_ref::<tokio_postgres::Error>()
+        .and_then(|e| e.code())
+        .is_some_and(|code| code.code().starts_with("22") || code.code().starts_with("23"))
+}
@@ -0,0 +1,11 @@
+use clap::Parser;
+use monitor_common::cli::MonitorArgs;
+
+pub use monitor_common::cli::Mode;
+
+#[derive(Debug, Parser)]
+#[command(name = "mempool-monitor", version, about = "Mempool monitor: pending transactions monitoring and indexing")]
+pub struct Cli {
+    #[command(flatten)]
+    pub monitor: MonitorArgs,
+}
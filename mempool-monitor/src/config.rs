@@ -13,7 +13,17 @@ pub struct IndexingCfg {
     pub http_polling_interval_secs: Option<u64>, // Polling interval in seconds for HTTP RPC
     pub filter_senders: Option<Vec<String>>,
     pub filter_receivers: Option<Vec<String>>,
+    pub include_contract_creations: Option<bool>, // If true, contract creation transactions (without receiver) of the filter_senders pass the receiver filter, and created contract addresses are recorded on confirmation (requires filter_senders)
+    pub max_tracked_creations: Option<usize>, // Max contract creation transactions awaiting confirmation, the oldest are untracked when full (default 1000)
+    pub min_value: Option<String>, // Minimal transaction value in wei (decimal string), inclusive
+    pub max_value: Option<String>, // Maximal transaction value in wei (decimal string), inclusive
+    pub min_gas_price: Option<String>, // Minimal gas price (max fee per gas for EIP-1559) in wei (decimal string), inclusive
+    pub max_gas_price: Option<String>, // Maximal gas price (max fee per gas for EIP-1559) in wei (decimal string), inclusive
     pub mempool_full_transactions: Option<bool>, // If true, subscribe to full transaction bodies; if false, subscribe to hashes only
+    pub track_pending_age: Option<bool>, // If true, track first-seen time of pending transactions to report stuck ones
+    pub stuck_tx_threshold_secs: Option<u64>, // Pending time after which a transaction is reported as stuck (default 300)
+    pub pending_tx_max_age_secs: Option<u64>, // Pending time after which a transaction is considered dropped and untracked (default 3600)
+    pub pending_tx_check_interval_secs: Option<u64>, // Interval of checking new blocks for mined transactions (default 5)
 }
 
 #[derive(Debug, Deserialize, Clone)]
@@ -35,6 +45,9 @@ pub struct AwsRdsCfg {
     pub connection_timeout: Option<u64>,
     pub max_connections: Option<u32>,
     pub schema: Option<String>,
+    pub write_buffer_size: Option<usize>, // Max transactions queued for AWS RDS replication (default 10000)
+    pub write_buffer_policy: Option<String>, // "drop_oldest" (default) or "backpressure" - what to do when the write buffer is full
+    pub write_retry_interval_ms: Option<u64>, // Initial delay between AWS RDS write retries, doubled up to 30s (default 500)
 }
 
 #[derive(Debug, Deserialize, Clone)]
@@ -42,6 +55,27 @@ pub struct NatsCfg {
     pub nats_enabled: Option<u8>,
     pub url: String,
     pub object_store_bucket: String,
+    pub buffer_capacity: Option<usize>, // Max transactions buffered while NATS is degraded, the oldest are dropped when full (default 10000)
+    pub retry_interval_secs: Option<u64>, // Interval of republishing the buffered transactions while NATS is degraded (default 5)
+    pub publish_timeout_secs: Option<u64>, // Timeout of a publish, after which NATS is degraded (default 10)
+}
+
+#[derive(Debug, Deserialize, Clone, Default)]
+pub struct ApiCfg {
+    pub bind_address: Option<String>, // API server bind address, overridden by `--bind` CLI option
+}
+
+#[derive(Debug, Deserialize)]
+struct ApiModeCfg {
+    api: Option<ApiCfg>,
+}
+
+impl ApiCfg {
+    /// Load the `api` section only, as monitoring task sections aren't required in API mode
+    pub fn load(path: &str) -> anyhow::Result<Self> {
+        let config: ApiModeCfg = config_loader::load(path)?;
+        Ok(config.api.unwrap_or_default())
+    }
 }
 
 #[derive(Debug, Deserialize, Clone)]
@@ -56,7 +90,7 @@ pub struct AppCfg {
 
 impl AppCfg {
     pub fn load(path: &str) -> anyhow::Result<Self> {
-        let config: Self = serde_yaml::from_str(&std::fs::read_to_string(path)?)?;
+        let config: Self = config_loader::load(path)?;
         Ok(config)
     }
 
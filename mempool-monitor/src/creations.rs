@@ -0,0 +1,93 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+use tokio::sync::RwLock;
+use tracing::{debug, warn};
+
+use alloy::primitives::{Address, TxHash};
+
+use crate::types::TransactionPayload;
+
+/// Contract creation transaction observed in the mempool, awaiting confirmation
+#[derive(Debug, Clone)]
+pub struct PendingCreation {
+    pub first_seen: chrono::DateTime<chrono::Utc>,
+    pub sender: Address,
+    pub nonce: u64,
+    pub payload: TransactionPayload,
+}
+
+impl PendingCreation {
+    /// Address of the created contract, as derived from the sender and nonce (`CREATE`)
+    pub fn derived_contract_address(&self) -> Address {
+        self.sender.create(self.nonce)
+    }
+}
+
+/// Tracks contract creation transactions until they are confirmed,
+/// to record the created contract addresses
+#[derive(Clone)]
+pub struct ContractCreationTracker {
+    entries: Arc<RwLock<HashMap<TxHash, PendingCreation>>>,
+    max_age_secs: u64,
+    capacity: usize,
+}
+
+impl ContractCreationTracker {
+    pub fn new(max_age_secs: u64, capacity: usize) -> Self {
+        Self {
+            entries: Arc::new(RwLock::new(HashMap::new())),
+            max_age_secs,
+            capacity: capacity.max(1),
+        }
+    }
+
+    /// Record a contract creation transaction, keeping already tracked ones.
+    /// When the tracker is full the oldest transaction is untracked, as the receipt of every
+    /// tracked transaction is requested on each check.
+    pub async fn observe(&self, tx_hash: TxHash, sender: Address, nonce: u64, payload: TransactionPayload) {
+        let mut entries = self.entries.write().await;
+
+        if !entries.contains_key(&tx_hash) && entries.len() >= self.capacity {
+            let oldest = entries.iter()
+                .min_by_key(|(_, creation)| creation.first_seen)
+                .map(|(oldest, _)| *oldest);
+            if let Some(oldest) = oldest {
+                entries.remove(&oldest);
+                warn!("Contract creation tracker full ({} transactions), untracked the oldest: {}", self.capacity, oldest);
+            }
+        }
+
+        entries.entry(tx_hash).or_insert(PendingCreation {
+            first_seen: chrono::Utc::now(),
+            sender,
+            nonce,
+            payload,
+        });
+    }
+
+    /// Tracked contract creation transactions
+    pub async fn pending(&self) -> Vec<(TxHash, PendingCreation)> {
+        self.entries.read().await.iter().map(|(tx_hash, creation)| (*tx_hash, creation.clone())).collect()
+    }
+
+    pub async fn remove(&self, tx_hash: &TxHash) {
+        self.entries.write().await.remove(tx_hash);
+    }
+
+    /// Evict transactions pending longer than the max age (considered dropped)
+    pub async fn evict_expired(&self) {
+        let now = chrono::Utc::now();
+        let mut entries = self.entries.write().await;
+
+        let before = entries.len();
+        entries.retain(|_, creation| ((now - creation.first_seen).num_seconds().max(0) as u64) < self.max_age_secs);
+        let evicted = before - entries.len();
+        if evicted > 0 {
+            debug!("Evicted {} contract creation transactions older than {}s (considered dropped)", evicted, self.max_age_secs);
+        }
+    }
+
+    pub async fn len(&self) -> usize {
+        self.entries.read().await.len()
+    }
+}
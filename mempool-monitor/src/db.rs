@@ -1,13 +1,16 @@
 use tokio_postgres::{Client, NoTls};
+use tokio::sync::Mutex;
 use tracing::{info, error, warn};
 
 use crate::types::TransactionPayload;
 use crate::config::AwsRdsCfg;
-use crate::aws_rds::{AwsRdsClient, create_aws_rds_client};
+use crate::aws_rds::{RdsWorkerHandle, RdsWriteBuffer, create_aws_rds_client};
 
 pub struct DatabaseClients {
     pub local_pg: Client,
-    pub aws_rds: Option<AwsRdsClient>,
+    pub aws_rds: Option<RdsWriteBuffer>,
+    /// Replication worker of the AWS RDS write buffer, taken on shutdown
+    aws_rds_worker: Mutex<Option<RdsWorkerHandle>>,
 }
 
 impl DatabaseClients {
@@ -20,32 +23,34 @@ impl DatabaseClients {
         let local_pg = connect_pg(local_dsn, local_schema).await?;
 
         // Connect to AWS RDS if enabled
-        let aws_rds = if let Some(rds_config) = aws_rds_config {
+        let (aws_rds, aws_rds_worker) = if let Some(rds_config) = aws_rds_config {
             if rds_config.enabled.unwrap_or(0) > 0 {
                 info!("AWS RDS is enabled, connecting...");
                 match create_aws_rds_client(rds_config).await {
                     Ok(client) => {
                         info!("Successfully connected to AWS RDS");
-                        Some(client)
+                        let (buffer, worker) = RdsWriteBuffer::new(client);
+                        (Some(buffer), Some(worker))
                     },
                     Err(e) => {
                         error!("Failed to connect to AWS RDS: {:?}", e);
                         warn!("Continuing without AWS RDS support");
-                        None
+                        (None, None)
                     }
                 }
             } else {
                 info!("AWS RDS is disabled in configuration");
-                None
+                (None, None)
             }
         } else {
             info!("AWS RDS configuration not found");
-            None
+            (None, None)
         };
 
         Ok(Self {
             local_pg,
             aws_rds,
+            aws_rds_worker: Mutex::new(aws_rds_worker),
         })
     }
 
@@ -56,21 +61,24 @@ impl DatabaseClients {
             return Err(e);
         }
 
-        // Optionally insert to AWS RDS
+        // Optionally replicate to AWS RDS through the write buffer
+        // AWS RDS is an additional data availability layer, its failures are retried in background
         if let Some(aws_rds) = &self.aws_rds {
-            if let Err(e) = aws_rds.insert_transaction(payload).await {
-                // Log error but don't fail the entire operation
-                // AWS RDS is an additional data availability layer
-                error!("Failed to insert transaction to AWS RDS (non-critical): {:?}", e);
-                warn!("Transaction was saved to local PostgreSQL but failed to replicate to AWS RDS");
-            } else {
-                info!("Transaction successfully replicated to AWS RDS: {}", payload.transaction_hash);
-            }
+            aws_rds.push(payload.clone()).await;
         }
 
         Ok(())
     }
 
+    /// Wait until buffered AWS RDS writes are replicated, up to `timeout`,
+    /// then stop the AWS RDS replication worker and join it
+    pub async fn shutdown(&self, timeout: std::time::Duration) {
+        let worker = self.aws_rds_worker.lock().await.take();
+        if let Some(worker) = worker {
+            worker.shutdown(timeout).await;
+        }
+    }
+
     pub async fn test_connections(&self) -> anyhow::Result<()> {
         // Test local PostgreSQL
         match self.local_pg.execute("SELECT 1", &[]).await {
@@ -122,6 +130,7 @@ pub async fn insert_transaction(
             transaction_hash,
             transaction_sender,
             transaction_receiver,
+            created_contract_address,
             nonce,
             value,
             gas_limit,
@@ -131,10 +140,11 @@ pub async fn insert_transaction(
             input_data,
             transaction_type,
             timestamp
-        ) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13)
+        ) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14)
         ON CONFLICT (chain_id, transaction_hash) DO UPDATE SET
             transaction_sender = EXCLUDED.transaction_sender,
             transaction_receiver = EXCLUDED.transaction_receiver,
+            created_contract_address = COALESCE(EXCLUDED.created_contract_address, mempool_monitor_data.created_contract_address),
             nonce = EXCLUDED.nonce,
             value = EXCLUDED.value,
             gas_limit = EXCLUDED.gas_limit,
@@ -154,6 +164,7 @@ pub async fn insert_transaction(
                 &payload.transaction_hash,
                 &payload.transaction_sender,
                 &payload.transaction_receiver,
+                &payload.created_contract_address,
                 &payload.nonce,
                 &payload.value,
                 &payload.gas_limit,
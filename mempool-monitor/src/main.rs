@@ -1,4 +1,6 @@
+mod cli;
 mod config;
+mod metrics;
 mod db;
 mod nats;
 mod subscriptions;
@@ -6,11 +8,16 @@ mod types;
 mod task_manager;
 mod web_api;
 mod aws_rds;
+mod pending;
+mod creations;
 
 use std::sync::Arc;
+use clap::Parser;
+use tokio_util::sync::CancellationToken;
 use tracing_subscriber::{EnvFilter, fmt};
 use tracing::{info, error};
 
+use crate::cli::{Cli, Mode};
 use crate::task_manager::TaskManager;
 use crate::web_api::start_web_server;
 
@@ -20,13 +27,15 @@ async fn main() -> anyhow::Result<()> {
     let filter = EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new("debug"));
     tracing_subscriber::fmt().with_env_filter(filter).compact().init();
 
-    // Check if we should run in API mode or single task mode
-    let args: Vec<String> = std::env::args().collect();
+    let cli = Cli::parse();
 
-    if args.len() > 1 && args[1] == "--api" {
+    if cli.monitor.mode() == Mode::Api {
         // Run in API mode
-        let bind_address = std::env::var("BIND_ADDRESS")
-            .unwrap_or_else(|_| "0.0.0.0:8080".to_string());
+        let api_cfg = match cli.monitor.config_path() {
+            Some(path) => config::ApiCfg::load(path)?,
+            None => config::ApiCfg::default(),
+        };
+        let bind_address = cli.monitor.bind_address(api_cfg.bind_address.as_deref());
 
         info!("Starting Mempool Monitor API server");
 
@@ -42,16 +51,19 @@ async fn main() -> anyhow::Result<()> {
             }
         });
 
-        start_web_server(task_manager, &bind_address).await?;
+        start_web_server(Arc::clone(&task_manager), &bind_address, shutdown_signal()).await?;
+
+        // Drain running tasks before exiting
+        task_manager.stop_all().await;
     } else {
         // Run in single task mode (original behavior)
         info!("Starting Mempool Monitor in single task mode");
 
-        let cfg_path = args.get(1).unwrap_or(&"./config.yaml".to_string()).clone();
-        let cfg = config::AppCfg::load(&cfg_path)?;
+        let cfg_path = cli.monitor.config_path().unwrap_or("./config.yaml");
+        let cfg = config::AppCfg::load(cfg_path)?;
 
-        let db_schema_path = if let Some(path) = args.get(2) {
-            path.clone()
+        let db_schema_path = if let Some(path) = cli.monitor.schema_path() {
+            path.to_string()
         } else {
             if cfg.postgres.schema.is_empty() {
                 "./init.sql".to_string()
@@ -86,8 +98,43 @@ async fn main() -> anyhow::Result<()> {
         };
 
         let tx_processor = subscriptions::TxProcessor::new(&cfg, db_clients, nats).await?;
-        tx_processor.run().await?;
+
+        let shutdown = CancellationToken::new();
+        let shutdown_trigger = shutdown.clone();
+        tokio::spawn(async move {
+            shutdown_signal().await;
+            shutdown_trigger.cancel();
+        });
+
+        tx_processor.run(shutdown).await?;
     }
 
     Ok(())
 }
+
+/// Resolves on Ctrl-C or SIGTERM
+async fn shutdown_signal() {
+    let ctrl_c = async {
+        if let Err(e) = tokio::signal::ctrl_c().await {
+            error!("Failed to listen for Ctrl-C: {:?}", e);
+            std::future::pending::<()>().await;
+        }
+    };
+
+    let terminate = async {
+        match tokio::signal::unix::signal(tokio::signal::unix::SignalKind::terminate()) {
+            Ok(mut sigterm) => { sigterm.recv().await; },
+            Err(e) => {
+                error!("Failed to listen for SIGTERM: {:?}", e);
+                std::future::pending::<()>().await;
+            }
+        }
+    };
+
+    tokio::select! {
+        _ = ctrl_c => {},
+        _ = terminate => {},
+    }
+
+    info!("Shutdown signal received, draining tasks");
+}
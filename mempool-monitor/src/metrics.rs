@@ -0,0 +1,60 @@
+//! Prometheus metrics, recorded when built with the `metrics` feature and served on `/metrics` in API mode.
+//! Without the feature all recording functions are no-ops.
+
+use std::future::IntoFuture;
+use std::time::{Duration, Instant};
+
+#[cfg(feature = "metrics")]
+use axum::{routing::get, Router};
+#[cfg(feature = "metrics")]
+use metrics_exporter_prometheus::PrometheusBuilder;
+
+const TRANSACTIONS_PROCESSED: &str = "mempool_monitor_transactions_processed_total";
+const RPC_CALL_DURATION: &str = "mempool_monitor_rpc_call_duration_seconds";
+const WRITE_DURATION: &str = "mempool_monitor_write_duration_seconds";
+
+/// Latency histograms buckets, in seconds
+#[cfg(feature = "metrics")]
+const LATENCY_BUCKETS: [f64; 12] = [0.001, 0.0025, 0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 10.0];
+
+/// Install the Prometheus recorder and add the `/metrics` endpoint rendering it
+#[cfg(feature = "metrics")]
+pub fn with_metrics_route(router: Router) -> anyhow::Result<Router> {
+    let handle = PrometheusBuilder::new()
+        .set_buckets(&LATENCY_BUCKETS)?
+        .install_recorder()?;
+
+    ::metrics::describe_counter!(TRANSACTIONS_PROCESSED, "Pending transactions processed and persisted");
+    ::metrics::describe_histogram!(RPC_CALL_DURATION, ::metrics::Unit::Seconds, "RPC call latency");
+    ::metrics::describe_histogram!(WRITE_DURATION, ::metrics::Unit::Seconds, "PostgreSQL and NATS Object Store write latency");
+
+    Ok(router.route("/metrics", get(move || std::future::ready(handle.render()))))
+}
+
+pub fn transaction_processed() {
+    #[cfg(feature = "metrics")]
+    ::metrics::counter!(TRANSACTIONS_PROCESSED).increment(1);
+    #[cfg(not(feature = "metrics"))]
+    let _ = TRANSACTIONS_PROCESSED;
+}
+
+/// Record write latency, `target` is "postgres" or "nats"
+pub fn write(target: &'static str, duration: Duration) {
+    #[cfg(feature = "metrics")]
+    ::metrics::histogram!(WRITE_DURATION, "target" => target).record(duration.as_secs_f64());
+    #[cfg(not(feature = "metrics"))]
+    let _ = (WRITE_DURATION, target, duration);
+}
+
+/// Await the RPC call, recording its latency
+pub async fn rpc_call<F: IntoFuture>(method: &'static str, call: F) -> F::Output {
+    let started = Instant::now();
+    let output = call.await;
+
+    #[cfg(feature = "metrics")]
+    ::metrics::histogram!(RPC_CALL_DURATION, "method" => method).record(started.elapsed().as_secs_f64());
+    #[cfg(not(feature = "metrics"))]
+    let _ = (RPC_CALL_DURATION, method, started);
+
+    output
+}
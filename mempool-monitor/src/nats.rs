@@ -1,13 +1,17 @@
 use async_nats::{jetstream, jetstream::Context, Client};
 use jetstream::object_store::ObjectStore;
 
+use crate::config::NatsCfg;
 use crate::types::TransactionPayload;
 
 use std::io::Cursor;
 use std::time::Duration;
 use async_nats::jetstream::object_store::Object;
+use monitor_common::nats::{PublishTarget, PublisherCfg};
 use tracing::{error, info};
 
+pub use monitor_common::nats::NatsHealth;
+
 #[derive(Clone)]
 pub struct Nats {
     pub client: Client,
@@ -66,3 +70,29 @@ pub async fn publish_transaction(
     let _obj = object_store.put(key.as_str(), &mut cursor).await?;
     Ok(())
 }
+
+/// Best-effort publishing of the transactions to NATS, see `monitor_common::nats::NatsPublisher`
+pub type NatsPublisher = monitor_common::nats::NatsPublisher<Nats>;
+
+impl PublishTarget for Nats {
+    type Payload = TransactionPayload;
+
+    const PAYLOADS: &'static str = "transactions";
+
+    async fn publish(&self, payload: &TransactionPayload) -> anyhow::Result<()> {
+        publish_transaction(&self.object_store, payload).await
+    }
+
+    fn client(&self) -> &Client {
+        &self.client
+    }
+}
+
+/// Buffering and timeouts of the NATS publisher
+pub fn publisher_cfg(cfg: &NatsCfg) -> PublisherCfg {
+    PublisherCfg {
+        buffer_capacity: cfg.buffer_capacity,
+        retry_interval_secs: cfg.retry_interval_secs,
+        publish_timeout_secs: cfg.publish_timeout_secs,
+    }
+}
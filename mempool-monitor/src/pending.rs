@@ -0,0 +1,136 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+use tokio::sync::RwLock;
+use tracing::{debug, warn};
+use serde::{Serialize, Deserialize};
+
+use alloy::primitives::{Address, TxHash};
+
+/// Pending transaction observed in the mempool, tracked until mined or dropped
+#[derive(Debug, Clone)]
+pub struct PendingTxEntry {
+    pub first_seen: chrono::DateTime<chrono::Utc>,
+    pub sender: Address,
+    pub receiver: Option<Address>,
+    pub nonce: u64,
+    pub gas_price: u128, // Gas price for legacy transactions, max fee per gas for EIP-1559 ones
+    pub max_priority_fee_per_gas: Option<u128>,
+    alerted: bool,
+}
+
+impl PendingTxEntry {
+    pub fn new(sender: Address, receiver: Option<Address>, nonce: u64, gas_price: u128, max_priority_fee_per_gas: Option<u128>) -> Self {
+        Self {
+            first_seen: chrono::Utc::now(),
+            sender,
+            receiver,
+            nonce,
+            gas_price,
+            max_priority_fee_per_gas,
+            alerted: false,
+        }
+    }
+
+    fn pending_secs(&self, now: chrono::DateTime<chrono::Utc>) -> u64 {
+        (now - self.first_seen).num_seconds().max(0) as u64
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StuckTransaction {
+    pub transaction_hash: String,
+    pub transaction_sender: String,
+    pub transaction_receiver: Option<String>,
+    pub nonce: u64,
+    pub gas_price: String,
+    pub max_priority_fee_per_gas: Option<String>,
+    pub first_seen: chrono::DateTime<chrono::Utc>,
+    pub pending_secs: u64,
+}
+
+/// Tracks first-seen timestamps of pending transactions to report the ones
+/// stuck in the mempool longer than the configured threshold
+#[derive(Clone)]
+pub struct PendingTxTracker {
+    entries: Arc<RwLock<HashMap<TxHash, PendingTxEntry>>>,
+    stuck_threshold_secs: u64,
+    max_age_secs: u64,
+}
+
+impl PendingTxTracker {
+    pub fn new(stuck_threshold_secs: u64, max_age_secs: u64) -> Self {
+        Self {
+            entries: Arc::new(RwLock::new(HashMap::new())),
+            stuck_threshold_secs,
+            max_age_secs: max_age_secs.max(stuck_threshold_secs),
+        }
+    }
+
+    /// Record a pending transaction, keeping the first-seen timestamp of already tracked ones
+    pub async fn observe(&self, tx_hash: TxHash, entry: PendingTxEntry) {
+        self.entries.write().await.entry(tx_hash).or_insert(entry);
+    }
+
+    /// Stop tracking transactions included in a block
+    pub async fn remove_mined<I>(&self, tx_hashes: I) -> usize
+    where
+        I: IntoIterator<Item = TxHash>,
+    {
+        let mut entries = self.entries.write().await;
+        tx_hashes.into_iter().filter(|tx_hash| entries.remove(tx_hash).is_some()).count()
+    }
+
+    /// Evict transactions pending longer than the max age (considered dropped)
+    /// and warn once about every transaction crossing the stuck threshold
+    pub async fn sweep(&self) {
+        let now = chrono::Utc::now();
+        let mut entries = self.entries.write().await;
+
+        let before = entries.len();
+        entries.retain(|_, entry| entry.pending_secs(now) < self.max_age_secs);
+        let evicted = before - entries.len();
+        if evicted > 0 {
+            debug!("Evicted {} pending transactions older than {}s (considered dropped)", evicted, self.max_age_secs);
+        }
+
+        for (tx_hash, entry) in entries.iter_mut() {
+            let pending_secs = entry.pending_secs(now);
+            if !entry.alerted && pending_secs >= self.stuck_threshold_secs {
+                entry.alerted = true;
+                warn!(
+                    "Stuck transaction: {} from {} (nonce {}) pending for {}s with gas price {}",
+                    tx_hash, entry.sender, entry.nonce, pending_secs, entry.gas_price
+                );
+            }
+        }
+    }
+
+    pub async fn len(&self) -> usize {
+        self.entries.read().await.len()
+    }
+
+    /// Transactions pending at least `min_age_secs` (the configured threshold by default), oldest first
+    pub async fn stuck(&self, min_age_secs: Option<u64>) -> Vec<StuckTransaction> {
+        let min_age_secs = min_age_secs.unwrap_or(self.stuck_threshold_secs);
+        let now = chrono::Utc::now();
+        let entries = self.entries.read().await;
+
+        let mut stuck: Vec<StuckTransaction> = entries
+            .iter()
+            .filter(|(_, entry)| entry.pending_secs(now) >= min_age_secs)
+            .map(|(tx_hash, entry)| StuckTransaction {
+                transaction_hash: tx_hash.to_string(),
+                transaction_sender: entry.sender.to_string(),
+                transaction_receiver: entry.receiver.map(|addr| addr.to_string()),
+                nonce: entry.nonce,
+                gas_price: entry.gas_price.to_string(),
+                max_priority_fee_per_gas: entry.max_priority_fee_per_gas.map(|f| f.to_string()),
+                first_seen: entry.first_seen,
+                pending_secs: entry.pending_secs(now),
+            })
+            .collect();
+
+        stuck.sort_by_key(|tx| std::cmp::Reverse(tx.pending_secs));
+        stuck
+    }
+}
@@ -6,7 +6,7 @@ use alloy::{
     providers::{Provider, ProviderBuilder, WsConnect},
     transports::ws::WebSocketConfig,
     rpc::types::{Filter, FilterBlockOption, BlockNumberOrTag, Log as RpcLog},
-    primitives::Address,
+    primitives::{Address, U256},
     json_abi::JsonAbi,
     network::TransactionResponse,
     consensus::Transaction as ConsensusTx,
@@ -16,27 +16,40 @@ use alloy::providers::{Identity, RootProvider};
 
 use async_nats::jetstream::object_store::ObjectStore;
 
-use crate::{db::{self, DatabaseClients}, nats, nats::Nats};
+use crate::{db::{self, DatabaseClients}, nats::{Nats, NatsPublisher}};
 use crate::config::AppCfg as AppConfig;
 use crate::types::TransactionPayload;
+use crate::pending::{PendingTxEntry, PendingTxTracker};
+use crate::creations::ContractCreationTracker;
+use crate::metrics;
 
 use std::ops::{Range, RangeFrom};
 use std::str::FromStr;
 use std::sync::Arc;
+use std::future::Future;
+use std::time::Duration;
 use anyhow::anyhow;
 use tokio::task::JoinHandle;
+use tokio_util::sync::CancellationToken;
+
+/// Max time to wait for buffered AWS RDS writes on shutdown
+const RDS_FLUSH_TIMEOUT: Duration = Duration::from_secs(30);
 
 type RPCProvider = FillProvider<JoinFill<Identity, JoinFill<GasFiller, JoinFill<BlobGasFiller, JoinFill<NonceFiller, ChainIdFiller>>>>, RootProvider>;
 
 pub struct TxProcessor {
     db_clients: DatabaseClients,
-    nats_store: Option<Nats>,
+    nats_publisher: Option<NatsPublisher>, // Best-effort NATS publishing
     config: AppConfig,
     ws_rpc_provider: RPCProvider,
     http_rpc_provider: RPCProvider,
     chain_id: u64,
     filter_senders: Option<Vec<Address>>,
     filter_receivers: Option<Vec<Address>>,
+    value_range: (Option<U256>, Option<U256>),
+    gas_price_range: (Option<U256>, Option<U256>),
+    pending_tracker: Option<PendingTxTracker>,
+    creation_tracker: Option<ContractCreationTracker>,
 }
 
 impl TxProcessor {
@@ -89,19 +102,71 @@ impl TxProcessor {
             None
         };
 
+        // Parse value and gas price range filters from configuration
+        let value_range = parse_u256_range(
+            "value",
+            config.indexing.min_value.as_deref(),
+            config.indexing.max_value.as_deref(),
+        )?;
+        let gas_price_range = parse_u256_range(
+            "gas price",
+            config.indexing.min_gas_price.as_deref(),
+            config.indexing.max_gas_price.as_deref(),
+        )?;
+
+        // Track pending transactions age to report stuck ones
+        let pending_tracker = if config.indexing.track_pending_age.unwrap_or(false) {
+            let stuck_threshold_secs = config.indexing.stuck_tx_threshold_secs.unwrap_or(300);
+            let max_age_secs = config.indexing.pending_tx_max_age_secs.unwrap_or(3600);
+            info!("Pending transactions age tracking enabled: stuck threshold {}s, max age {}s", stuck_threshold_secs, max_age_secs);
+            Some(PendingTxTracker::new(stuck_threshold_secs, max_age_secs))
+        } else {
+            None
+        };
+
+        // Track contract creation transactions to record the created contract addresses
+        // Only of watched senders, as a receipt is requested for every tracked creation on each check
+        let creation_tracker = if config.indexing.include_contract_creations.unwrap_or(false) {
+            if filter_senders.is_none() {
+                anyhow::bail!("include_contract_creations requires filter_senders, contract creations are tracked for the watched senders only");
+            }
+            let max_age_secs = config.indexing.pending_tx_max_age_secs.unwrap_or(3600);
+            let max_tracked = config.indexing.max_tracked_creations.unwrap_or(1000);
+            info!("Contract creation transactions included, created contract addresses are recorded on confirmation (up to {} tracked)", max_tracked);
+            Some(ContractCreationTracker::new(max_age_secs, max_tracked))
+        } else {
+            None
+        };
+
         Ok(Self {
             db_clients,
-            nats_store,
+            nats_publisher: nats_store.map(|nats| NatsPublisher::new(nats, crate::nats::publisher_cfg(&config.nats))),
             config: config.clone(),
             ws_rpc_provider,
             http_rpc_provider,
             chain_id,
             filter_senders,
             filter_receivers,
+            value_range,
+            gas_price_range,
+            pending_tracker,
+            creation_tracker,
         })
     }
 
-    pub async fn run(self) -> anyhow::Result<()> {
+    pub fn pending_tracker(&self) -> Option<PendingTxTracker> {
+        self.pending_tracker.clone()
+    }
+
+    /// NATS publisher if NATS is enabled, shared with the task manager for the API health
+    pub fn nats_publisher(&self) -> Option<NatsPublisher> {
+        self.nats_publisher.clone()
+    }
+
+    /// Run subscription tasks until they complete or `shutdown` is cancelled.
+    /// On shutdown the transaction being processed is persisted before the loops exit,
+    /// then buffered AWS RDS writes are flushed and their worker is joined.
+    pub async fn run(self, shutdown: CancellationToken) -> anyhow::Result<()> {
         let self_arc = Arc::new(self);
 
         let mut handles: Vec<JoinHandle<anyhow::Result<()>>> = Vec::new();
@@ -117,6 +182,7 @@ impl TxProcessor {
         if subscription_protocol.to_lowercase() == "ws" {
             // WebSocket subscription mode
             let processor_for_subscription = Arc::clone(&self_arc);
+            let shutdown = shutdown.clone();
 
             let subscription_task = tokio::spawn(async move {
                 info!("Starting WebSocket subscription task for pending transactions (full: {})", use_full_transactions);
@@ -127,7 +193,7 @@ impl TxProcessor {
                     info!("Subscribed to full pending transactions");
 
                     let mut sub_stream = sub.into_stream();
-                    while let Some(tx) = sub_stream.next().await {
+                    while let Some(tx) = unless_cancelled(&shutdown, sub_stream.next()).await.flatten() {
                         debug!("Received full pending transaction: {}", tx.tx_hash());
                         if let Err(e) = processor_for_subscription.handle_transaction(tx).await {
                             error!("Failed to handle full pending transaction: {:?}", e);
@@ -139,11 +205,11 @@ impl TxProcessor {
                     info!("Subscribed to pending transaction hashes");
 
                     let mut sub_stream = sub.into_stream();
-                    while let Some(tx_hash) = sub_stream.next().await {
+                    while let Some(tx_hash) = unless_cancelled(&shutdown, sub_stream.next()).await.flatten() {
                         debug!("Received pending transaction hash: {}", tx_hash);
 
                         // Fetch full transaction details
-                        match processor_for_subscription.http_rpc_provider.get_transaction_by_hash(tx_hash).await? {
+                        match metrics::rpc_call("eth_getTransactionByHash", processor_for_subscription.http_rpc_provider.get_transaction_by_hash(tx_hash)).await? {
                             Some(tx) => {
                                 if let Err(e) = processor_for_subscription.handle_transaction(tx).await {
                                     error!("Failed to handle pending transaction: {:?}", e);
@@ -164,6 +230,7 @@ impl TxProcessor {
             // HTTP polling mode for mempool
             let processor_for_subscription = Arc::clone(&self_arc);
             let polling_interval_secs = processor_for_subscription.config.indexing.http_polling_interval_secs.unwrap_or(5);
+            let shutdown = shutdown.clone();
 
             let subscription_task = tokio::spawn(async move {
                 info!("Starting HTTP polling task for pending transactions (interval: {}s, full: {})",
@@ -171,16 +238,14 @@ impl TxProcessor {
 
                 let mut interval = tokio::time::interval(tokio::time::Duration::from_secs(polling_interval_secs));
 
-                loop {
-                    interval.tick().await;
-
+                while unless_cancelled(&shutdown, interval.tick()).await.is_some() {
                     if use_full_transactions {
                         // Watch full pending transactions
                         match processor_for_subscription.http_rpc_provider.watch_full_pending_transactions().await {
                             Ok(watcher) => {
                                 let mut tx_stream = watcher.into_stream().flat_map(futures::stream::iter);
 
-                                while let Some(tx) = tx_stream.next().await {
+                                while let Some(tx) = unless_cancelled(&shutdown, tx_stream.next()).await.flatten() {
                                     debug!("Received full pending transaction via HTTP: {}", tx.tx_hash());
                                     if let Err(e) = processor_for_subscription.handle_transaction(tx).await {
                                         error!("Failed to handle full pending transaction: {:?}", e);
@@ -197,11 +262,11 @@ impl TxProcessor {
                             Ok(watcher) => {
                                 let mut hash_stream = watcher.into_stream().flat_map(futures::stream::iter);
 
-                                while let Some(tx_hash) = hash_stream.next().await {
+                                while let Some(tx_hash) = unless_cancelled(&shutdown, hash_stream.next()).await.flatten() {
                                     debug!("Received pending transaction hash via HTTP: {}", tx_hash);
 
                                     // Fetch full transaction details
-                                    match processor_for_subscription.http_rpc_provider.get_transaction_by_hash(tx_hash).await {
+                                    match metrics::rpc_call("eth_getTransactionByHash", processor_for_subscription.http_rpc_provider.get_transaction_by_hash(tx_hash)).await {
                                         Ok(Some(tx)) => {
                                             if let Err(e) = processor_for_subscription.handle_transaction(tx).await {
                                                 error!("Failed to handle pending transaction: {:?}", e);
@@ -222,12 +287,112 @@ impl TxProcessor {
                         }
                     }
                 }
+
+                info!("Polling task completed");
+                Ok(())
             });
             handles.push(subscription_task);
         } else {
             anyhow::bail!("Unsupported subscription protocol: {}", subscription_protocol);
         }
 
+        // Pending transactions cleanup task: untrack mined transactions and report stuck ones
+        if let Some(pending_tracker) = self_arc.pending_tracker.clone() {
+            let processor_for_cleanup = Arc::clone(&self_arc);
+            let check_interval_secs = processor_for_cleanup.config.indexing.pending_tx_check_interval_secs.unwrap_or(5);
+            let shutdown = shutdown.clone();
+
+            let cleanup_task = tokio::spawn(async move {
+                info!("Starting pending transactions tracking task (interval: {}s)", check_interval_secs);
+
+                let mut interval = tokio::time::interval(tokio::time::Duration::from_secs(check_interval_secs));
+                let mut last_block: Option<u64> = None;
+
+                while unless_cancelled(&shutdown, interval.tick()).await.is_some() {
+                    match processor_for_cleanup.http_rpc_provider.get_block_number().await {
+                        Ok(latest_block) => {
+                            let from_block = last_block.map(|b| b + 1).unwrap_or(latest_block);
+                            for block_number in from_block..=latest_block {
+                                match processor_for_cleanup.http_rpc_provider.get_block_by_number(BlockNumberOrTag::Number(block_number)).await {
+                                    Ok(Some(block)) => {
+                                        let mined = pending_tracker.remove_mined(block.transactions.hashes()).await;
+                                        debug!("Block {}: {} tracked pending transactions mined", block_number, mined);
+                                        last_block = Some(block_number);
+                                    }
+                                    Ok(None) => {
+                                        debug!("Block {} not found yet", block_number);
+                                        break;
+                                    }
+                                    Err(e) => {
+                                        error!("Failed to get block {}: {:?}", block_number, e);
+                                        break;
+                                    }
+                                }
+                            }
+                        }
+                        Err(e) => {
+                            error!("Failed to get latest block number: {:?}", e);
+                        }
+                    }
+
+                    pending_tracker.sweep().await;
+                    debug!("Tracking {} pending transactions", pending_tracker.len().await);
+                }
+
+                info!("Pending transactions tracking task completed");
+                Ok(())
+            });
+            handles.push(cleanup_task);
+        }
+
+        // Contract creations confirmation task: record addresses of the created contracts
+        if let Some(creation_tracker) = self_arc.creation_tracker.clone() {
+            let processor_for_creations = Arc::clone(&self_arc);
+            let check_interval_secs = processor_for_creations.config.indexing.pending_tx_check_interval_secs.unwrap_or(5);
+            let shutdown = shutdown.clone();
+
+            let creations_task = tokio::spawn(async move {
+                info!("Starting contract creations confirmation task (interval: {}s)", check_interval_secs);
+
+                let mut interval = tokio::time::interval(tokio::time::Duration::from_secs(check_interval_secs));
+
+                while unless_cancelled(&shutdown, interval.tick()).await.is_some() {
+                    for (tx_hash, creation) in creation_tracker.pending().await {
+                        let receipt = match metrics::rpc_call("eth_getTransactionReceipt", processor_for_creations.http_rpc_provider.get_transaction_receipt(tx_hash)).await {
+                            Ok(Some(receipt)) => receipt,
+                            Ok(None) => continue,
+                            Err(e) => {
+                                error!("Failed to get receipt of contract creation transaction {}: {:?}", tx_hash, e);
+                                continue;
+                            }
+                        };
+                        creation_tracker.remove(&tx_hash).await;
+
+                        if !receipt.status() {
+                            info!("Contract creation transaction {} from {} reverted, no contract created", tx_hash, creation.sender);
+                            continue;
+                        }
+
+                        let contract_address = receipt.contract_address.unwrap_or_else(|| creation.derived_contract_address());
+                        info!("Contract {} created by {} in transaction {}", contract_address, creation.sender, tx_hash);
+
+                        let mut payload = creation.payload;
+                        payload.created_contract_address = Some(contract_address.to_string());
+                        if let Err(e) = processor_for_creations.persist(&payload).await {
+                            error!("Failed to persist created contract address of transaction {}: {:?}", tx_hash, e);
+                        }
+                    }
+
+                    creation_tracker.evict_expired().await;
+                    debug!("Tracking {} contract creation transactions", creation_tracker.len().await);
+                }
+
+                info!("Contract creations confirmation task completed");
+                Ok(())
+            });
+            handles.push(creations_task);
+        }
+
         // Wait for all tasks to complete
         for handle in handles {
             match handle.await {
@@ -243,6 +408,9 @@ impl TxProcessor {
             }
         }
 
+        // Flush buffered AWS RDS writes and stop their worker before exiting
+        self_arc.db_clients.shutdown(RDS_FLUSH_TIMEOUT).await;
+
         Ok(())
     }
 
@@ -274,19 +442,46 @@ impl TxProcessor {
                         debug!("Filtering out transaction: receiver {} not in filter list", to_addr);
                         return Ok(());
                     }
-                } else {
-                    // Contract creation (no receiver)
+                } else if self.creation_tracker.is_none() {
+                    // Contract creation (no receiver), included only if configured
                     debug!("Filtering out transaction: no receiver (contract creation)");
                     return Ok(());
                 }
             }
         }
 
+        // Apply value range filtering if configured
+        let value = tx.value();
+        if !in_range(value, &self.value_range) {
+            debug!("Filtering out transaction: value {} out of configured range", value);
+            return Ok(());
+        }
+
+        // Apply gas price range filtering if configured
+        // Max fee per gas is used for EIP-1559 transactions, gas price for legacy ones
+        let effective_gas_price = U256::from(ConsensusTx::max_fee_per_gas(&tx));
+        if !in_range(effective_gas_price, &self.gas_price_range) {
+            debug!("Filtering out transaction: gas price {} out of configured range", effective_gas_price);
+            return Ok(());
+        }
+
+        let nonce = tx.nonce();
+
+        // Record first-seen time for pending age tracking
+        if let Some(pending_tracker) = &self.pending_tracker {
+            let entry = PendingTxEntry::new(
+                sender,
+                receiver,
+                nonce,
+                ConsensusTx::max_fee_per_gas(&tx),
+                tx.max_priority_fee_per_gas(),
+            );
+            pending_tracker.observe(tx_hash, entry).await;
+        }
+
         let tx_sender = sender.to_string();
         let tx_receiver = receiver.map(|addr| addr.to_string());
         let tx_hash_str = format!("0x{}", hex::encode(tx_hash.0.as_slice()));
-        let nonce = tx.nonce();
-        let value = tx.value();
         let gas_limit = tx.gas_limit();
         let gas_price = TransactionResponse::gas_price(&tx);
         let input_data = tx.input();
@@ -308,6 +503,7 @@ impl TxProcessor {
             transaction_hash: tx_hash_str,
             transaction_sender: tx_sender,
             transaction_receiver: tx_receiver,
+            created_contract_address: None,
             nonce: nonce.to_string(),
             value: value.to_string(),
             gas_limit: gas_limit.to_string(),
@@ -319,20 +515,72 @@ impl TxProcessor {
             timestamp,
         };
 
+        // Track contract creation until confirmed, to record the created contract address
+        if receiver.is_none() && let Some(creation_tracker) = &self.creation_tracker {
+            creation_tracker.observe(tx_hash, sender, nonce, payload.clone()).await;
+        }
+
+        self.persist(&payload).await?;
+
+        metrics::transaction_processed();
+
+        Ok(())
+    }
+
+    async fn persist(&self, payload: &TransactionPayload) -> anyhow::Result<()> {
         debug!("Persisting transaction: {:?}", payload);
 
         // Persist to databases (local PostgreSQL + AWS RDS if enabled)
-        self.db_clients.insert_transaction(&payload).await?;
-
-        // Persist to NATS Object Store
-        if let Some(nats_store) = &self.nats_store {
-            nats::publish_transaction(&nats_store.object_store, &payload).await?;
+        let started = std::time::Instant::now();
+        self.db_clients.insert_transaction(payload).await?;
+        metrics::write("postgres", started.elapsed());
+
+        // Persist to NATS Object Store, best-effort: buffered while NATS is degraded
+        if let Some(nats_publisher) = &self.nats_publisher {
+            let started = std::time::Instant::now();
+            nats_publisher.publish(payload).await;
+            metrics::write("nats", started.elapsed());
         };
 
         Ok(())
     }
 }
 
+/// Await `future` unless `shutdown` is cancelled first, in which case `None` is returned
+async fn unless_cancelled<F: Future>(shutdown: &CancellationToken, future: F) -> Option<F::Output> {
+    tokio::select! {
+        biased;
+        _ = shutdown.cancelled() => None,
+        output = future => Some(output),
+    }
+}
+
+/// Parse an optional inclusive `[min, max]` range of decimal wei amounts
+fn parse_u256_range(name: &str, min: Option<&str>, max: Option<&str>) -> anyhow::Result<(Option<U256>, Option<U256>)> {
+    let parse = |bound: Option<&str>| -> anyhow::Result<Option<U256>> {
+        match bound.map(str::trim).filter(|s| !s.is_empty()) {
+            Some(s) => U256::from_str_radix(s, 10)
+                .map(Some)
+                .map_err(|e| anyhow!("Invalid {} filter bound {:?}: {}", name, s, e)),
+            None => Ok(None),
+        }
+    };
+
+    let (min, max) = (parse(min)?, parse(max)?);
+    if let (Some(min), Some(max)) = (min, max) && min > max {
+        anyhow::bail!("Invalid {} filter range: min {} is greater than max {}", name, min, max);
+    }
+    if min.is_some() || max.is_some() {
+        info!("Filter {} range configured: min {:?}, max {:?}", name, min, max);
+    }
+
+    Ok((min, max))
+}
+
+fn in_range(amount: U256, (min, max): &(Option<U256>, Option<U256>)) -> bool {
+    min.is_none_or(|min| amount >= min) && max.is_none_or(|max| amount <= max)
+}
+
 /// Build HTTP and WS providers using Alloy
 pub async fn build_providers(ws_rpc_url: WsConnect, http_rpc_url: reqwest::Url) -> anyhow::Result<(RPCProvider, RPCProvider)> {
     let ws_rpc_provider = ProviderBuilder::new().connect_ws(ws_rpc_url.clone()).await?;
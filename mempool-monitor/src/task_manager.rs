@@ -1,14 +1,19 @@
 use std::collections::HashMap;
 use std::sync::Arc;
-use tokio::sync::{RwLock, oneshot};
+use std::time::Duration;
+use tokio::sync::RwLock;
 use tokio::task::JoinHandle;
+use tokio_util::sync::CancellationToken;
 use uuid::Uuid;
 use serde::{Serialize, Deserialize};
 use tracing::{info, error, warn};
 
 use crate::subscriptions::TxProcessor;
 use crate::config::AppCfg;
+use crate::aws_rds::{RdsBufferStats, RdsWriteBuffer};
+use crate::pending::{PendingTxTracker, StuckTransaction};
 use crate::{db, nats};
+use crate::nats::{NatsHealth, NatsPublisher};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TaskInfo {
@@ -17,6 +22,10 @@ pub struct TaskInfo {
     pub status: TaskStatus,
     pub created_at: chrono::DateTime<chrono::Utc>,
     pub updated_at: chrono::DateTime<chrono::Utc>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub aws_rds_buffer: Option<RdsBufferStats>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub nats: Option<NatsHealth>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -28,10 +37,27 @@ pub enum TaskStatus {
     Failed(String),
 }
 
+/// Max time to wait for a stopping task to flush in-flight writes before aborting it
+const TASK_STOP_TIMEOUT: Duration = Duration::from_secs(60);
+
 pub struct Task {
     pub info: TaskInfo,
-    pub handle: JoinHandle<anyhow::Result<()>>,
-    pub shutdown_sender: Option<oneshot::Sender<()>>,
+    pub handle: Option<JoinHandle<anyhow::Result<()>>>,
+    pub shutdown: CancellationToken,
+    pub aws_rds_buffer: Option<RdsWriteBuffer>,
+    pub pending_tracker: Option<PendingTxTracker>,
+    pub nats_publisher: Option<NatsPublisher>,
+}
+
+impl Task {
+    async fn info_with_stats(&self) -> TaskInfo {
+        let mut info = self.info.clone();
+        if let Some(buffer) = &self.aws_rds_buffer {
+            info.aws_rds_buffer = Some(buffer.stats().await);
+        }
+        info.nats = self.nats_publisher.as_ref().map(NatsPublisher::health);
+        info
+    }
 }
 
 pub struct TaskManager {
@@ -61,10 +87,13 @@ impl TaskManager {
             status: TaskStatus::Starting,
             created_at: chrono::Utc::now(),
             updated_at: chrono::Utc::now(),
+            aws_rds_buffer: None,
+            nats: None,
         };
 
-        // Create shutdown channel
-        let (shutdown_sender, shutdown_receiver) = oneshot::channel::<()>();
+        // Create shutdown token
+        let shutdown = CancellationToken::new();
+        let shutdown_clone = shutdown.clone();
 
         // Clone necessary data for the task
         let tasks_clone = Arc::clone(&self.tasks);
@@ -110,6 +139,14 @@ impl TaskManager {
                 }
             };
 
+            // Expose AWS RDS write buffer for monitoring
+            if let Some(buffer) = &db_clients.aws_rds {
+                let mut tasks = tasks_clone.write().await;
+                if let Some(task) = tasks.get_mut(&task_id_clone) {
+                    task.aws_rds_buffer = Some(buffer.clone());
+                }
+            }
+
             // Test database connections
             if let Err(e) = db_clients.test_connections().await {
                 warn!("Database connection test issues for task {}: {:?}", task_id_clone, e);
@@ -151,22 +188,17 @@ impl TaskManager {
                 if let Some(task) = tasks.get_mut(&task_id_clone) {
                     task.info.status = TaskStatus::Running;
                     task.info.updated_at = chrono::Utc::now();
+                    task.pending_tracker = tx_processor.pending_tracker();
+                    task.nats_publisher = tx_processor.nats_publisher();
                 }
             }
 
             info!("Task {} ({}) is now running", name, task_id_clone);
 
-            // Run the event processor with shutdown handling
-            let processor_result = tokio::select! {
-                result = tx_processor.run() => {
-                    info!("Task {} completed: {:?}", task_id_clone, result);
-                    result
-                }
-                _ = shutdown_receiver => {
-                    info!("Task {} received shutdown signal", task_id_clone);
-                    Ok(())
-                }
-            };
+            // Run the transactions processor until completion or shutdown,
+            // in-flight writes are persisted before it returns
+            let processor_result = tx_processor.run(shutdown_clone).await;
+            info!("Task {} completed: {:?}", task_id_clone, processor_result);
 
             // Update final status
             {
@@ -186,8 +218,11 @@ impl TaskManager {
         // Store the task
         let task = Task {
             info: task_info,
-            handle,
-            shutdown_sender: Some(shutdown_sender),
+            handle: Some(handle),
+            shutdown,
+            aws_rds_buffer: None,
+            pending_tracker: None,
+            nats_publisher: None,
         };
 
         let mut tasks = self.tasks.write().await;
@@ -197,37 +232,88 @@ impl TaskManager {
         Ok(task_id)
     }
 
+    /// Signal the task to shut down and wait for it to flush in-flight writes
     pub async fn stop_task(&self, task_id: &str) -> anyhow::Result<()> {
-        let mut tasks = self.tasks.write().await;
+        let handle = {
+            let mut tasks = self.tasks.write().await;
+            let task = tasks.get_mut(task_id)
+                .ok_or_else(|| anyhow::anyhow!("Task not found: {}", task_id))?;
 
-        if let Some(task) = tasks.get_mut(task_id) {
             info!("Stopping task: {} ({})", task.info.name, task_id);
 
             // Update status to stopping
             task.info.status = TaskStatus::Stopping;
             task.info.updated_at = chrono::Utc::now();
 
-            // Send shutdown signal if sender is available
-            if let Some(sender) = task.shutdown_sender.take() {
-                if let Err(_) = sender.send(()) {
-                    warn!("Failed to send shutdown signal to task {}", task_id);
+            task.shutdown.cancel();
+            task.handle.take()
+        };
+
+        // Wait for the task to drain outside of the lock, as the task updates its own status
+        if let Some(mut handle) = handle {
+            match tokio::time::timeout(TASK_STOP_TIMEOUT, &mut handle).await {
+                Ok(Ok(Ok(()))) => info!("Task {} drained", task_id),
+                Ok(Ok(Err(e))) => warn!("Task {} finished with error while stopping: {:?}", task_id, e),
+                Ok(Err(join_err)) => error!("Task {} panicked while stopping: {:?}", task_id, join_err),
+                Err(_) => {
+                    warn!("Task {} didn't stop within {:?}, aborting", task_id, TASK_STOP_TIMEOUT);
+                    handle.abort();
                 }
             }
+        }
 
-            Ok(())
-        } else {
-            Err(anyhow::anyhow!("Task not found: {}", task_id))
+        let mut tasks = self.tasks.write().await;
+        if let Some(task) = tasks.get_mut(task_id) {
+            if !matches!(task.info.status, TaskStatus::Failed(_)) {
+                task.info.status = TaskStatus::Stopped;
+            }
+            task.info.updated_at = chrono::Utc::now();
+        }
+
+        Ok(())
+    }
+
+    /// Stop all tasks concurrently, waiting for each of them to drain
+    pub async fn stop_all(&self) {
+        let task_ids: Vec<String> = self.tasks.read().await.keys().cloned().collect();
+        info!("Stopping all tasks: {}", task_ids.len());
+
+        let results = futures::future::join_all(
+            task_ids.iter().map(|task_id| self.stop_task(task_id))
+        ).await;
+
+        for (task_id, result) in task_ids.iter().zip(results) {
+            if let Err(e) = result {
+                warn!("Failed to stop task {}: {:?}", task_id, e);
+            }
         }
     }
 
     pub async fn get_task(&self, task_id: &str) -> Option<TaskInfo> {
         let tasks = self.tasks.read().await;
-        tasks.get(task_id).map(|task| task.info.clone())
+        match tasks.get(task_id) {
+            Some(task) => Some(task.info_with_stats().await),
+            None => None,
+        }
     }
 
     pub async fn list_tasks(&self) -> Vec<TaskInfo> {
         let tasks = self.tasks.read().await;
-        tasks.values().map(|task| task.info.clone()).collect()
+        let mut infos = Vec::with_capacity(tasks.len());
+        for task in tasks.values() {
+            infos.push(task.info_with_stats().await);
+        }
+        infos
+    }
+
+    pub async fn stuck_transactions(&self, task_id: &str, min_age_secs: Option<u64>) -> anyhow::Result<Vec<StuckTransaction>> {
+        let tasks = self.tasks.read().await;
+        let task = tasks.get(task_id)
+            .ok_or_else(|| anyhow::anyhow!("Task not found: {}", task_id))?;
+        let pending_tracker = task.pending_tracker.as_ref()
+            .ok_or_else(|| anyhow::anyhow!("Pending transactions tracking is not enabled for task: {}", task_id))?;
+
+        Ok(pending_tracker.stuck(min_age_secs).await)
     }
 
     pub async fn cleanup_finished_tasks(&self) {
@@ -235,7 +321,7 @@ impl TaskManager {
         let mut to_remove = Vec::new();
 
         for (id, task) in tasks.iter() {
-            if task.handle.is_finished() {
+            if task.handle.as_ref().is_none_or(|handle| handle.is_finished()) {
                 match &task.info.status {
                     TaskStatus::Stopped | TaskStatus::Failed(_) => {
                         to_remove.push(id.clone());
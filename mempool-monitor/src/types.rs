@@ -6,6 +6,7 @@ pub struct TransactionPayload {
     pub transaction_hash: String,
     pub transaction_sender: String,
     pub transaction_receiver: Option<String>,
+    pub created_contract_address: Option<String>, // Contract created by the transaction, recorded once confirmed
     pub nonce: String,
     pub value: String,
     pub gas_limit: String,
@@ -1,6 +1,6 @@
 use std::sync::Arc;
 use axum::{
-    extract::{Path, State, Multipart},
+    extract::{Path, Query, State, Multipart},
     http::StatusCode,
     response::Json,
     routing::{get, post, delete},
@@ -17,6 +17,7 @@ use tracing::{info, error};
 
 use crate::config::AppCfg;
 use crate::task_manager::{TaskManager, TaskInfo};
+use crate::pending::StuckTransaction;
 
 #[derive(Clone)]
 pub struct AppState {
@@ -36,6 +37,11 @@ pub struct CreateTaskResponse {
     pub message: String,
 }
 
+#[derive(Deserialize)]
+pub struct StuckTransactionsQuery {
+    pub min_age_secs: Option<u64>,
+}
+
 #[derive(Serialize)]
 pub struct ApiError {
     pub error: String,
@@ -49,6 +55,8 @@ pub async fn create_web_api(task_manager: Arc<TaskManager>) -> Router {
         .route("/api/tasks", get(list_tasks_handler))
         .route("/api/tasks/:task_id", get(get_task_handler))
         .route("/api/tasks/:task_id/stop", post(stop_task_handler))
+        .route("/api/tasks/:task_id/stuck_transactions", get(stuck_transactions_handler))
+        .route("/api/tasks/:task_id/nats", get(get_task_nats_handler))
         .route("/api/tasks/:task_id", delete(delete_task_handler))
         .route("/api/health", get(health_check_handler))
         .with_state(app_state)
@@ -156,15 +164,43 @@ async fn get_task_handler(
     }
 }
 
+async fn get_task_nats_handler(
+    Path(task_id): Path<String>,
+    State(state): State<AppState>,
+) -> Result<Json<serde_json::Value>, (StatusCode, Json<ApiError>)> {
+    match state.task_manager.get_task(&task_id).await {
+        Some(task) => Ok(Json(json!({
+            "task_id": task.id,
+            "nats": task.nats
+        }))),
+        None => Err((StatusCode::NOT_FOUND, Json(ApiError {
+            error: format!("Task not found: {}", task_id)
+        })))
+    }
+}
+
+async fn stuck_transactions_handler(
+    Path(task_id): Path<String>,
+    Query(query): Query<StuckTransactionsQuery>,
+    State(state): State<AppState>,
+) -> Result<Json<Vec<StuckTransaction>>, (StatusCode, Json<ApiError>)> {
+    match state.task_manager.stuck_transactions(&task_id, query.min_age_secs).await {
+        Ok(stuck) => Ok(Json(stuck)),
+        Err(e) => Err((StatusCode::NOT_FOUND, Json(ApiError {
+            error: e.to_string()
+        })))
+    }
+}
+
 async fn stop_task_handler(
     Path(task_id): Path<String>,
     State(state): State<AppState>,
 ) -> Result<Json<serde_json::Value>, (StatusCode, Json<ApiError>)> {
     match state.task_manager.stop_task(&task_id).await {
         Ok(_) => {
-            info!("Stopping task: {}", task_id);
+            info!("Stopped task: {}", task_id);
             Ok(Json(json!({
-                "message": format!("Task {} stop signal sent", task_id)
+                "message": format!("Task {} stopped", task_id)
             })))
         }
         Err(e) => {
@@ -201,9 +237,13 @@ async fn health_check_handler() -> Json<serde_json::Value> {
 pub async fn start_web_server(
     task_manager: Arc<TaskManager>,
     bind_address: &str,
+    shutdown_signal: impl std::future::Future<Output = ()> + Send + 'static,
 ) -> anyhow::Result<()> {
     let app = create_web_api(task_manager).await;
 
+    #[cfg(feature = "metrics")]
+    let app = crate::metrics::with_metrics_route(app)?;
+
     info!("Starting web server on {}", bind_address);
 
     let listener = tokio::net::TcpListener::bind(bind_address)
@@ -211,6 +251,7 @@ pub async fn start_web_server(
         .map_err(|e| anyhow::anyhow!("Failed to bind to {}: {}", bind_address, e))?;
 
     axum::serve(listener, app)
+        .with_graceful_shutdown(shutdown_signal)
         .await
         .map_err(|e| anyhow::anyhow!("Web server error: {}", e))?;
 
@@ -3,25 +3,29 @@ use std::fs::File;
 use std::io::{BufReader, Write};
 use std::path::PathBuf;
 use std::process;
-use clap::Parser;
+use clap::{Parser, Subcommand};
 use anyhow::{Result, Context};
 use csv::ReaderBuilder;
 use serde::{Serialize, Deserialize};
 
 // Import the merkle trie implementation
-use merkle_trie_cli::merkle_trie::{MerkleTrie, keccak256};
+use merkle_trie_cli::merkle_trie::{MerkleTrie, MerkleProof, keccak256, verify_sorted_pairs_proof};
 
 #[derive(Parser, Debug)]
 #[command(name = "merkle-cli")]
 #[command(about = "Generate Merkle Trie from CSV file with address and amount columns", long_about = None)]
+#[command(subcommand_negates_reqs = true)]
 struct Args {
+    #[command(subcommand)]
+    command: Option<Command>,
+
     /// Input CSV file path
-    #[arg(short, long)]
-    input: PathBuf,
+    #[arg(short, long, required = true)]
+    input: Option<PathBuf>,
 
     /// Output JSON file path
-    #[arg(short, long)]
-    output: PathBuf,
+    #[arg(short, long, required = true)]
+    output: Option<PathBuf>,
 
     /// Print root hash to stdout
     #[arg(short, long, default_value_t = false)]
@@ -44,6 +48,40 @@ struct Args {
     compare_json: Option<PathBuf>,
 }
 
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Verify a Merkle proof of an allocation against a root hash
+    Verify(VerifyArgs),
+}
+
+#[derive(clap::Args, Debug)]
+struct VerifyArgs {
+    /// Expected root hash (with 0x prefix)
+    #[arg(long)]
+    root: String,
+
+    /// Leaf as `<address>,<allocation>`
+    #[arg(long)]
+    leaf: String,
+
+    /// Proof sibling hashes ordered from the leaf to the root, comma separated
+    #[arg(long, value_delimiter = ',')]
+    proof: Vec<String>,
+
+    /// Leaf index in the trie, to derive the sibling sides from, as node pairs
+    /// of the generated tries are hashed in position order
+    #[arg(long, required_unless_present = "sorted_pairs", conflicts_with = "sorted_pairs")]
+    leaf_index: Option<usize>,
+
+    /// Hash node pairs in sorted order instead (OpenZeppelin/viem compatible proofs), without a leaf index
+    #[arg(long, default_value_t = false)]
+    sorted_pairs: bool,
+
+    /// Keep 0x prefix in leaf data for hashing (don't strip it)
+    #[arg(long, default_value_t = false)]
+    keep_prefix: bool,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 struct AllocationProof {
     allocation: String,
@@ -390,19 +428,88 @@ fn compare_output_data(actual: &OutputData, reference: &OutputData) -> Compariso
     result
 }
 
+/// Parse a 32 bytes hash from hex string (with or without 0x prefix)
+fn parse_hash(hash: &str) -> Result<[u8; 32]> {
+    let hash = hash.trim();
+    let bytes = hex::decode(hash.strip_prefix("0x").unwrap_or(hash))
+        .with_context(|| format!("Failed to decode hash: {}", hash))?;
+
+    bytes.try_into()
+        .map_err(|bytes: Vec<u8>| anyhow::anyhow!("Invalid hash length: {} (expected 32 bytes)", bytes.len()))
+}
+
+/// Verify the leaf proof against the root, returns whether the proof reconstructs the root
+fn verify_proof(args: &VerifyArgs) -> Result<bool> {
+    let (address, amount) = args.leaf.split_once(',')
+        .ok_or_else(|| anyhow::anyhow!("Invalid leaf format: {} (expected <address>,<allocation>)", args.leaf))?;
+
+    let normalized_address = normalize_address(address, args.keep_prefix);
+    let leaf_data = encode_leaf_data(&normalized_address, amount, args.keep_prefix)?;
+
+    let root_hash = parse_hash(&args.root)?;
+    let sibling_hashes = args.proof.iter()
+        .filter(|hash| !hash.trim().is_empty())
+        .map(|hash| parse_hash(hash))
+        .collect::<Result<Vec<_>>>()?;
+
+    let valid = match (args.sorted_pairs, args.leaf_index) {
+        (true, _) => {
+            let leaf_hash = keccak256(&leaf_data);
+            println!("Leaf hash: 0x{}", hex::encode(leaf_hash));
+            println!("Sibling hashes ({} levels), hashed in sorted pairs", sibling_hashes.len());
+            verify_sorted_pairs_proof(&leaf_hash, &sibling_hashes, &root_hash)
+        }
+        (false, Some(leaf_index)) => {
+            let proof = MerkleProof::from_sibling_hashes(leaf_data, leaf_index, &sibling_hashes);
+            println!("{}", proof);
+            MerkleTrie::verify_proof_against_root(&proof, &root_hash)
+        }
+        (false, None) => anyhow::bail!("The leaf index is required to verify a proof of position ordered node pairs"),
+    };
+
+    Ok(valid)
+}
+
+fn run_verify(args: VerifyArgs) -> Result<()> {
+    println!("Merkle Proof Verification");
+    println!("=========================");
+    println!("Root hash: {}", args.root);
+    println!("Leaf: {}", args.leaf);
+    println!("Keep 0x prefix in leaf data: {}", args.keep_prefix);
+    println!();
+
+    if verify_proof(&args)? {
+        println!("\n✓ Proof reconstructs the root hash");
+        Ok(())
+    } else {
+        eprintln!("\n✗ ERROR: Proof DOES NOT reconstruct the root hash!");
+        process::exit(1);
+    }
+}
+
 fn main() -> Result<()> {
     let args = Args::parse();
 
+    if let Some(Command::Verify(verify_args)) = args.command {
+        return run_verify(verify_args);
+    }
+
+    // Required unless a subcommand is given
+    let (input, output) = match (args.input, args.output) {
+        (Some(input), Some(output)) => (input, output),
+        _ => anyhow::bail!("--input and --output are required"),
+    };
+
     println!("Merkle Trie CLI Tool");
     println!("===================");
-    println!("Input file: {:?}", args.input);
-    println!("Output file: {:?}", args.output);
+    println!("Input file: {:?}", input);
+    println!("Output file: {:?}", output);
     println!("Keep 0x prefix in leaf data: {}", args.keep_prefix);
     println!();
 
     // Process CSV file
     println!("Processing CSV file...");
-    let (trie, address_amount_map) = process_csv_file(&args.input, args.keep_prefix)?;
+    let (trie, address_amount_map) = process_csv_file(&input, args.keep_prefix)?;
 
     // Get root hash
     let root_hash = trie.get_root_hash_hex()
@@ -445,8 +552,8 @@ fn main() -> Result<()> {
     }
 
     // Write to output file
-    println!("\nWriting output to {:?}...", args.output);
-    write_output(&args.output, &output_data, args.pretty)?;
+    println!("\nWriting output to {:?}...", output);
+    write_output(&output, &output_data, args.pretty)?;
 
     println!("\n✓ Successfully generated Merkle Trie data!");
     println!("  Root Hash: {}", output_data.root_hash);
@@ -488,6 +595,7 @@ fn main() -> Result<()> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use merkle_trie_cli::merkle_trie::keccak256_combine;
 
     #[test]
     fn test_normalize_address_strip_prefix() {
@@ -559,6 +667,85 @@ mod tests {
         assert!(result.is_err());
     }
 
+    fn verify_args(leaf: &str, proof: Vec<String>, root: &str, leaf_index: Option<usize>) -> VerifyArgs {
+        VerifyArgs {
+            root: root.to_string(),
+            leaf: leaf.to_string(),
+            proof,
+            leaf_index,
+            sorted_pairs: false,
+            keep_prefix: false,
+        }
+    }
+
+    #[test]
+    fn test_verify_proof() {
+        let leaves = [
+            ("0x742C4d97C86bCF0176776C16e073b8c6f9Db4021", "1000"),
+            ("0x06a37c563d88894a98438e3b2fe17f365f1d3530", "2000"),
+        ];
+        let mut trie = MerkleTrie::new();
+        for (address, amount) in leaves {
+            trie.add_leaf(encode_leaf_data(&normalize_address(address, false), amount, false).unwrap());
+        }
+        trie.build_tree();
+        let root = trie.get_root_hash_hex().unwrap();
+
+        for (i, (address, amount)) in leaves.iter().enumerate() {
+            let proof = trie.proof_to_hex_array(&trie.generate_proof_by_index(i).unwrap());
+            let leaf = format!("{},{}", address, amount);
+
+            assert!(verify_proof(&verify_args(&leaf, proof.clone(), &root, Some(i))).unwrap());
+            assert!(!verify_proof(&verify_args(&leaf, proof, &root, Some(1 - i))).unwrap());
+        }
+
+        let proof = trie.proof_to_hex_array(&trie.generate_proof_by_index(0).unwrap());
+        let wrong_allocation = format!("{},{}", leaves[0].0, 1001);
+        assert!(!verify_proof(&verify_args(&wrong_allocation, proof, &root, Some(0))).unwrap());
+    }
+
+    #[test]
+    fn test_verify_proof_modes() {
+        let leaves = [
+            ("0x742C4d97C86bCF0176776C16e073b8c6f9Db4021", "1000"),
+            ("0x06a37c563d88894a98438e3b2fe17f365f1d3530", "2000"),
+        ];
+        let hashes: Vec<[u8; 32]> = leaves
+            .iter()
+            .map(|(address, amount)| keccak256(&encode_leaf_data(&normalize_address(address, false), amount, false).unwrap()))
+            .collect();
+        let sorted_root = if hashes[0] <= hashes[1] {
+            keccak256_combine(&hashes[0], &hashes[1])
+        } else {
+            keccak256_combine(&hashes[1], &hashes[0])
+        };
+        let root = format!("0x{}", hex::encode(sorted_root));
+        let leaf = format!("{},{}", leaves[0].0, leaves[0].1);
+        let proof = vec![format!("0x{}", hex::encode(hashes[1]))];
+
+        // Positional by default, the leaf index is required
+        assert!(verify_proof(&verify_args(&leaf, proof.clone(), &root, None)).is_err());
+
+        let sorted_args = VerifyArgs { sorted_pairs: true, ..verify_args(&leaf, proof, &root, None) };
+        assert!(verify_proof(&sorted_args).unwrap());
+
+        // Clap enforces the leaf index unless sorted pairs are verified
+        let parse = |args: &[&str]| Args::try_parse_from(["merkle-cli", "verify", "--root", root.as_str(), "--leaf", leaf.as_str()].iter().chain(args));
+        assert!(parse(&[]).is_err());
+        assert!(parse(&["--leaf-index", "0"]).is_ok());
+        assert!(parse(&["--sorted-pairs"]).is_ok());
+        assert!(parse(&["--sorted-pairs", "--leaf-index", "0"]).is_err());
+    }
+
+    #[test]
+    fn test_verify_proof_invalid_input() {
+        let root = format!("0x{}", "00".repeat(32));
+        assert!(verify_proof(&verify_args("0x742C4d97C86bCF0176776C16e073b8c6f9Db4021", Vec::new(), &root, None)).is_err());
+        assert!(verify_proof(&verify_args("0x742C4d97C86bCF0176776C16e073b8c6f9Db4021,1000", vec!["0x1234".to_string()], &root, None)).is_err());
+        assert!(parse_hash("0x1234").is_err());
+        assert_eq!(parse_hash(&root).unwrap(), [0u8; 32]);
+    }
+
     #[test]
     fn test_comparison_result_success() {
         let result = ComparisonResult {
@@ -6,6 +6,7 @@ use std::process;
 use clap::Parser;
 use anyhow::{Result, Context};
 use csv::ReaderBuilder;
+use rayon::prelude::*;
 use serde::{Serialize, Deserialize};
 use keccak_hasher::KeccakHasher;
 use hash_db::Hasher as HashDbHasher;
@@ -19,6 +20,11 @@ const EXIT_PROOFS_MISMATCH_JSON: i32 = 3;
 #[derive(Parser, Debug)]
 #[command(name = "merkle-viem-compat")]
 #[command(about = "Generate Merkle tree compatible with viem/TypeScript implementation", long_about = None)]
+#[command(after_help = "Exit codes:
+  0  Success, all comparisons passed or no comparisons requested
+  1  Root hash provided via --compare-root does not match (checked only without --compare-json), or failed to run
+  2  Root hash in --compare-json reference does not match
+  3  Proofs in --compare-json reference do not match")]
 struct Args {
     /// Input CSV file path
     #[arg(short, long)]
@@ -59,6 +65,14 @@ struct Args {
     /// Reference JSON file to compare output against
     #[arg(long)]
     compare_json: Option<PathBuf>,
+
+    /// Number of threads for proofs generation (all cores by default)
+    #[arg(long)]
+    threads: Option<usize>,
+
+    /// Write the comparison result as JSON to the file (root hashes match, diverged allocations, exit code)
+    #[arg(long)]
+    report_json: Option<PathBuf>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -88,7 +102,7 @@ struct LeafData {
     packed_data: Vec<u8>,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Serialize)]
 struct ComparisonResult {
     root_hash_match: bool,
     proofs_match: bool,
@@ -98,6 +112,30 @@ struct ComparisonResult {
     mismatched_proofs: Vec<String>,
 }
 
+/// Machine-readable comparison report, written by `--report-json`
+#[derive(Debug, Serialize)]
+struct ComparisonReport<'a> {
+    exit_code: i32,
+    success: bool,
+    root_hash: String,
+    expected_root: Option<&'a str>, // Root hash provided via `--compare-root`
+    root_hash_cli_match: Option<bool>,
+    json_comparison: Option<&'a ComparisonResult>, // Comparison with `--compare-json` reference
+}
+
+/// Exit code by the comparisons results: the reference JSON root hash is checked first,
+/// then the reference JSON proofs. The root hash provided via CLI is only checked
+/// without reference JSON, as the reference JSON root hash takes precedence.
+fn determine_exit_code(root_hash_cli_match: Option<bool>, json_comparison: Option<&ComparisonResult>) -> i32 {
+    match json_comparison {
+        Some(comparison) if !comparison.root_hash_match => EXIT_ROOT_MISMATCH_JSON,
+        Some(comparison) if !comparison.is_success() => EXIT_PROOFS_MISMATCH_JSON,
+        Some(_) => EXIT_SUCCESS,
+        None if root_hash_cli_match == Some(false) => EXIT_ROOT_MISMATCH_CLI,
+        None => EXIT_SUCCESS,
+    }
+}
+
 impl ComparisonResult {
     fn is_success(&self) -> bool {
         self.root_hash_match
@@ -469,28 +507,38 @@ fn compare_output_data(actual: &OutputData, reference: &OutputData) -> Compariso
 }
 
 /// Generate output JSON
+/// Proofs are generated in parallel, as the tree levels are immutable, then collected
+/// in the rows order, so the output is the same as of the sequential generation
 fn generate_output(
     data: &[CsvRow],
     leaves: &[[u8; 32]],
     levels: &[Vec<[u8; 32]>],
     root: &[u8; 32],
 ) -> Result<OutputData> {
-    let mut allocations = BTreeMap::new();
-
-    for (i, row) in data.iter().enumerate() {
-        let proof = get_merkle_proof(i, levels);
-        let proof_hex: Vec<String> = proof.iter().map(|p| bytes_to_hex(p)).collect();
-
-        let checksum_addr = to_checksum_address(&row.address)
-            .unwrap_or_else(|_| row.address.clone());
+    let allocation_proofs: Vec<(String, AllocationProof)> = data
+        .par_iter()
+        .enumerate()
+        .map(|(i, row)| {
+            let proof = get_merkle_proof(i, levels);
+            let proof_hex: Vec<String> = proof.iter().map(|p| bytes_to_hex(p)).collect();
+
+            let checksum_addr = to_checksum_address(&row.address)
+                .unwrap_or_else(|_| row.address.clone());
+
+            (
+                checksum_addr,
+                AllocationProof {
+                    allocation: row.allocation.clone(),
+                    proof: proof_hex,
+                },
+            )
+        })
+        .collect();
 
-        allocations.insert(
-            checksum_addr,
-            AllocationProof {
-                allocation: row.allocation.clone(),
-                proof: proof_hex,
-            },
-        );
+    // Inserted in the rows order, so duplicate addresses resolve the same as sequentially
+    let mut allocations = BTreeMap::new();
+    for (checksum_addr, allocation_proof) in allocation_proofs {
+        allocations.insert(checksum_addr, allocation_proof);
     }
 
     Ok(OutputData {
@@ -519,6 +567,22 @@ fn write_output(output_path: Option<&PathBuf>, data: &OutputData, pretty: bool)
     Ok(())
 }
 
+/// Write comparison report to JSON file
+fn write_report(report_path: &PathBuf, report: &ComparisonReport, pretty: bool) -> Result<()> {
+    let json_string = if pretty {
+        serde_json::to_string_pretty(report)?
+    } else {
+        serde_json::to_string(report)?
+    };
+
+    let mut file = File::create(report_path)
+        .with_context(|| format!("Failed to create report file: {:?}", report_path))?;
+    file.write_all(json_string.as_bytes())
+        .with_context(|| format!("Failed to write to report file: {:?}", report_path))?;
+
+    Ok(())
+}
+
 /// Display detailed leaf content
 fn display_leaf_content(leaf_data: &[LeafData], keep_prefix: bool) {
     println!("\nLeaf Content Details:");
@@ -683,7 +747,14 @@ fn main() -> Result<()> {
     }
 
     // Generate JSON output
-    let output_data = generate_output(&data, &leaves, &levels, &root)?;
+    let output_data = match args.threads {
+        Some(threads) => rayon::ThreadPoolBuilder::new()
+            .num_threads(threads)
+            .build()
+            .context("Failed to build proofs generation thread pool")?
+            .install(|| generate_output(&data, &leaves, &levels, &root))?,
+        None => generate_output(&data, &leaves, &levels, &root)?,
+    };
 
     // Compare with reference JSON if provided
     let mut json_comparison: Option<ComparisonResult> = None;
@@ -714,28 +785,35 @@ fn main() -> Result<()> {
     }
 
     // Determine exit code
-    let exit_code = if let Some(comparison) = json_comparison {
-        if !comparison.root_hash_match {
-            eprintln!("\n✗ ERROR: Root hash in reference JSON does not match!");
-            eprintln!("  Exit code: {}", EXIT_ROOT_MISMATCH_JSON);
-            EXIT_ROOT_MISMATCH_JSON
-        } else if !comparison.proofs_match || !comparison.missing_addresses.is_empty()
-            || !comparison.extra_addresses.is_empty()
-            || !comparison.mismatched_allocations.is_empty()
-            || !comparison.mismatched_proofs.is_empty() {
-            eprintln!("\n✗ ERROR: Proofs in reference JSON do not match!");
-            eprintln!("  Exit code: {}", EXIT_PROOFS_MISMATCH_JSON);
-            EXIT_PROOFS_MISMATCH_JSON
-        } else {
-            EXIT_SUCCESS
+    let root_hash_cli_match = args.compare_root.as_ref().map(|_| root_hash_cli_matches);
+    let exit_code = determine_exit_code(root_hash_cli_match, json_comparison.as_ref());
+
+    match exit_code {
+        EXIT_ROOT_MISMATCH_JSON => eprintln!("\n✗ ERROR: Root hash in reference JSON does not match!"),
+        EXIT_PROOFS_MISMATCH_JSON => eprintln!("\n✗ ERROR: Proofs in reference JSON do not match!"),
+        EXIT_ROOT_MISMATCH_CLI => eprintln!("\n✗ ERROR: Root hash provided via CLI does not match!"),
+        _ => {}
+    }
+    if exit_code != EXIT_SUCCESS {
+        eprintln!("  Exit code: {}", exit_code);
+    }
+
+    // Write machine-readable comparison report
+    if let Some(report_path) = &args.report_json {
+        let report = ComparisonReport {
+            exit_code,
+            success: exit_code == EXIT_SUCCESS,
+            root_hash: bytes_to_hex(&root),
+            expected_root: args.compare_root.as_deref(),
+            root_hash_cli_match,
+            json_comparison: json_comparison.as_ref(),
+        };
+        write_report(report_path, &report, args.pretty)?;
+
+        if args.verbose {
+            println!("\n✓ Comparison report written to {:?}", report_path);
         }
-    } else if args.compare_root.is_some() && !root_hash_cli_matches {
-        eprintln!("\n✗ ERROR: Root hash provided via CLI does not match!");
-        eprintln!("  Exit code: {}", EXIT_ROOT_MISMATCH_CLI);
-        EXIT_ROOT_MISMATCH_CLI
-    } else {
-        EXIT_SUCCESS
-    };
+    }
 
     if exit_code != EXIT_SUCCESS {
         process::exit(exit_code);
@@ -938,4 +1016,80 @@ mod tests {
         };
         assert!(!result.is_success());
     }
+
+    fn comparison_result(root_hash_match: bool, mismatched_proofs: Vec<String>) -> ComparisonResult {
+        ComparisonResult {
+            root_hash_match,
+            proofs_match: mismatched_proofs.is_empty(),
+            missing_addresses: Vec::new(),
+            extra_addresses: Vec::new(),
+            mismatched_allocations: Vec::new(),
+            mismatched_proofs,
+        }
+    }
+
+    #[test]
+    fn test_parallel_output_identical_to_sequential() {
+        // Includes a duplicate address, resolved by the last row
+        let mut data: Vec<CsvRow> = (1..=1000u32)
+            .map(|i| CsvRow {
+                address: format!("0x{:040x}", i * 7919),
+                allocation: (u128::from(i) * 1_000_000_000).to_string(),
+            })
+            .collect();
+        data.push(CsvRow { address: data[10].address.clone(), allocation: "1".to_string() });
+
+        let leaves: Vec<[u8; 32]> = data.iter()
+            .map(|row| leaf_hash(&row.address, row.allocation.parse().unwrap(), false).unwrap())
+            .collect();
+        let (levels, root) = build_merkle_tree(leaves.clone()).unwrap();
+
+        let mut allocations = BTreeMap::new();
+        for (i, row) in data.iter().enumerate() {
+            let proof = get_merkle_proof(i, &levels).iter().map(|p| bytes_to_hex(p)).collect();
+            let checksum_addr = to_checksum_address(&row.address).unwrap();
+            allocations.insert(checksum_addr, AllocationProof { allocation: row.allocation.clone(), proof });
+        }
+        let sequential = serde_json::to_string(&OutputData { root_hash: bytes_to_hex(&root), allocations }).unwrap();
+
+        for threads in [1, 4] {
+            let pool = rayon::ThreadPoolBuilder::new().num_threads(threads).build().unwrap();
+            let output = pool.install(|| generate_output(&data, &leaves, &levels, &root)).unwrap();
+            assert_eq!(serde_json::to_string(&output).unwrap(), sequential, "Output with {} threads differs", threads);
+        }
+    }
+
+    #[test]
+    fn test_determine_exit_code() {
+        let passed = comparison_result(true, Vec::new());
+        let root_mismatch = comparison_result(false, vec!["0x01".to_string()]);
+        let proofs_mismatch = comparison_result(true, vec!["0x01".to_string()]);
+
+        assert_eq!(determine_exit_code(None, None), EXIT_SUCCESS);
+        assert_eq!(determine_exit_code(Some(true), Some(&passed)), EXIT_SUCCESS);
+        assert_eq!(determine_exit_code(Some(false), None), EXIT_ROOT_MISMATCH_CLI);
+        // Reference JSON root hash takes precedence over the CLI one
+        assert_eq!(determine_exit_code(Some(false), Some(&passed)), EXIT_SUCCESS);
+        assert_eq!(determine_exit_code(Some(false), Some(&root_mismatch)), EXIT_ROOT_MISMATCH_JSON);
+        assert_eq!(determine_exit_code(Some(false), Some(&proofs_mismatch)), EXIT_PROOFS_MISMATCH_JSON);
+    }
+
+    #[test]
+    fn test_comparison_report_json() {
+        let comparison = comparison_result(true, vec!["0x01".to_string()]);
+        let report = ComparisonReport {
+            exit_code: EXIT_PROOFS_MISMATCH_JSON,
+            success: false,
+            root_hash: "0xabcd".to_string(),
+            expected_root: None,
+            root_hash_cli_match: None,
+            json_comparison: Some(&comparison),
+        };
+
+        let json = serde_json::to_value(&report).unwrap();
+        assert_eq!(json["exit_code"], 3);
+        assert_eq!(json["json_comparison"]["root_hash_match"], true);
+        assert_eq!(json["json_comparison"]["mismatched_proofs"][0], "0x01");
+        assert!(json["root_hash_cli_match"].is_null());
+    }
 }
@@ -5,6 +5,8 @@ pub use merkle_trie::{
     MerkleNode,
     MerkleProof,
     ProofElement,
+    verify_sorted_pairs_proof,
+    abi_encode_arrays,
     keccak256,
     keccak256_combine
 };
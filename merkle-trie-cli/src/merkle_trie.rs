@@ -66,6 +66,92 @@ pub struct ProofElement {
     pub is_right_sibling: bool,
 }
 
+impl MerkleProof {
+    /// Build a proof from sibling hashes ordered from the leaf to the root,
+    /// with the sibling sides derived from the leaf index bits
+    pub fn from_sibling_hashes(leaf_data: Vec<u8>, leaf_index: usize, sibling_hashes: &[[u8; 32]]) -> Self {
+        let siblings = sibling_hashes
+            .iter()
+            .enumerate()
+            .map(|(level, hash)| ProofElement {
+                hash: *hash,
+                // Even index at the level: current node is left, sibling is right
+                is_right_sibling: (leaf_index >> level) & 1 == 0,
+            })
+            .collect();
+
+        MerkleProof {
+            leaf_index,
+            leaf_hash: keccak256(&leaf_data),
+            leaf_data,
+            siblings,
+        }
+    }
+
+    /// Sibling hashes ordered from the leaf to the root and their sides, the `bytes32[] proof`
+    /// and `bool[] isRightSibling` arguments of Solidity verifiers hashing node pairs in position order
+    pub fn to_solidity_calldata(&self) -> (Vec<[u8; 32]>, Vec<bool>) {
+        self.siblings
+            .iter()
+            .map(|element| (element.hash, element.is_right_sibling))
+            .unzip()
+    }
+
+    /// ABI encoding of the proof and sibling sides as `(bytes32[], bool[])` arguments,
+    /// i.e. `abi.encode(proof, isRightSibling)`
+    pub fn abi_encode_calldata(&self) -> Vec<u8> {
+        let (proof, sides) = self.to_solidity_calldata();
+        let sides = sides.into_iter().map(bool_word).collect();
+        abi_encode_arrays(&[proof, sides])
+    }
+}
+
+/// ABI word of a `bool` value
+fn bool_word(value: bool) -> [u8; 32] {
+    let mut word = [0u8; 32];
+    word[31] = u8::from(value);
+    word
+}
+
+/// ABI encoding of dynamic arrays of 32-byte words as consecutive arguments, i.e. `abi.encode(a, b, ...)`:
+/// the offsets of the arrays, then each array length and words
+pub fn abi_encode_arrays(arrays: &[Vec<[u8; 32]>]) -> Vec<u8> {
+    fn word(value: usize) -> [u8; 32] {
+        let mut word = [0u8; 32];
+        word[24..].copy_from_slice(&(value as u64).to_be_bytes());
+        word
+    }
+
+    let mut head = Vec::with_capacity(arrays.len() * 32);
+    let mut tail = Vec::new();
+    for array in arrays {
+        head.extend_from_slice(&word(arrays.len() * 32 + tail.len()));
+        tail.extend_from_slice(&word(array.len()));
+        for element in array {
+            tail.extend_from_slice(element);
+        }
+    }
+
+    head.extend_from_slice(&tail);
+    head
+}
+
+/// Verify a proof with node pairs hashed in sorted order (OpenZeppelin `MerkleProof` and viem compatible),
+/// sibling hashes ordered from the leaf to the root
+pub fn verify_sorted_pairs_proof(leaf_hash: &[u8; 32], sibling_hashes: &[[u8; 32]], root_hash: &[u8; 32]) -> bool {
+    let mut current_hash = *leaf_hash;
+
+    for sibling in sibling_hashes {
+        current_hash = if &current_hash <= sibling {
+            keccak256_combine(&current_hash, sibling)
+        } else {
+            keccak256_combine(sibling, &current_hash)
+        };
+    }
+
+    &current_hash == root_hash
+}
+
 impl fmt::Display for MerkleProof {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         writeln!(f, "Merkle Proof for leaf index {}:", self.leaf_index)?;
@@ -208,6 +294,8 @@ impl MerkleTrie {
         })
     }
 
+    /// Collect the sibling hashes of the path to the target leaf, ordered from the leaf to the root:
+    /// each level pushes its sibling after the deeper levels did
     fn collect_siblings(
         &self,
         node: &MerkleNode,
@@ -227,17 +315,13 @@ impl MerkleTrie {
 
         if target_index < mid_point {
             // Target is in left subtree, right child is sibling
+            self.collect_siblings(left_child, target_index, mid_point, level + 1, siblings);
             siblings.push(ProofElement {
                 hash: right_child.hash,
                 is_right_sibling: true,
             });
-            self.collect_siblings(left_child, target_index, mid_point, level + 1, siblings);
         } else {
             // Target is in right subtree, left child is sibling
-            siblings.push(ProofElement {
-                hash: left_child.hash,
-                is_right_sibling: false,
-            });
             self.collect_siblings(
                 right_child,
                 target_index - mid_point,
@@ -245,6 +329,10 @@ impl MerkleTrie {
                 level + 1,
                 siblings,
             );
+            siblings.push(ProofElement {
+                hash: left_child.hash,
+                is_right_sibling: false,
+            });
         }
     }
 
@@ -496,6 +584,60 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_solidity_calldata() {
+        let data = vec![b"a".to_vec(), b"b".to_vec(), b"c".to_vec(), b"d".to_vec()];
+        let trie = MerkleTrie::from_data(data);
+        let proof = trie.generate_proof(b"c").unwrap();
+
+        // Leaf to root: the sibling leaf on the right, then the sibling subtree on the left
+        let (hashes, sides) = proof.to_solidity_calldata();
+        assert_eq!(hashes, vec![keccak256(b"d"), keccak256_combine(&keccak256(b"a"), &keccak256(b"b"))]);
+        assert_eq!(sides, vec![true, false]);
+
+        let encoded = proof.abi_encode_calldata();
+        // Offsets, proof length and hashes, sides length and sides
+        assert_eq!(encoded.len(), 32 * 8);
+        assert_eq!(encoded[31], 0x40);
+        assert_eq!(encoded[63], 0xa0);
+        assert_eq!(encoded[95], 2);
+        assert_eq!(&encoded[96..128], &hashes[0]);
+        assert_eq!(&encoded[128..160], &hashes[1]);
+        assert_eq!(encoded[191], 2);
+        assert_eq!(encoded[223], 1);
+        assert_eq!(encoded[255], 0);
+    }
+
+    #[test]
+    fn test_solidity_calldata_reconstructs_root() {
+        let data: Vec<Vec<u8>> = (0..8).map(|i| format!("leaf{}", i).into_bytes()).collect();
+        let trie = MerkleTrie::from_data(data.clone());
+        let root_hash = trie.get_root_hash().unwrap();
+
+        for (i, leaf) in data.iter().enumerate() {
+            let encoded = trie.generate_proof_by_index(i).unwrap().abi_encode_calldata();
+
+            // Decode the arguments the way a Solidity verifier receives them
+            let word = |offset: usize| -> [u8; 32] { encoded[offset..offset + 32].try_into().unwrap() };
+            let number = |offset: usize| u64::from_be_bytes(encoded[offset + 24..offset + 32].try_into().unwrap()) as usize;
+            let (proof_offset, sides_offset) = (number(0), number(32));
+            let levels = number(proof_offset);
+            assert_eq!(number(sides_offset), levels);
+
+            // Hash the node pairs in position order, as the verifier does
+            let mut current_hash = keccak256(leaf);
+            for level in 0..levels {
+                let sibling = word(proof_offset + 32 + level * 32);
+                current_hash = if number(sides_offset + 32 + level * 32) == 1 {
+                    keccak256_combine(&current_hash, &sibling)
+                } else {
+                    keccak256_combine(&sibling, &current_hash)
+                };
+            }
+            assert_eq!(current_hash, root_hash, "Calldata of leaf {} should reconstruct the root", i);
+        }
+    }
+
     #[test]
     fn test_hex_root_verification() {
         let data = vec![b"test".to_vec()];
@@ -648,6 +790,53 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_eight_leaves_proofs_ordered_from_leaf_to_root() {
+        let data: Vec<Vec<u8>> = (0..8).map(|i| format!("leaf{}", i).into_bytes()).collect();
+        let trie = MerkleTrie::from_data(data.clone());
+
+        for i in 0..8 {
+            let proof = trie.generate_proof_by_index(i).unwrap();
+            assert_eq!(proof.siblings.len(), 3);
+            // The first sibling is the neighbour leaf
+            assert_eq!(proof.siblings[0].hash, keccak256(&data[i ^ 1]));
+            assert_eq!(proof.siblings[0].is_right_sibling, i % 2 == 0);
+            assert!(trie.verify_proof(&proof), "Proof for leaf {} should verify", i);
+        }
+    }
+
+    #[test]
+    fn test_proof_from_sibling_hashes() {
+        let data = vec![b"leaf0".to_vec(), b"leaf1".to_vec()];
+        let trie = MerkleTrie::from_data(data.clone());
+        let root = trie.get_root_hash().unwrap();
+
+        for (i, leaf) in data.iter().enumerate() {
+            let sibling = keccak256(&data[1 - i]);
+            let proof = MerkleProof::from_sibling_hashes(leaf.clone(), i, &[sibling]);
+            assert_eq!(proof.siblings, trie.generate_proof_by_index(i).unwrap().siblings);
+            assert!(MerkleTrie::verify_proof_against_root(&proof, &root));
+        }
+
+        // Wrong leaf index swaps the sibling sides
+        let proof = MerkleProof::from_sibling_hashes(data[0].clone(), 1, &[keccak256(&data[1])]);
+        assert!(!MerkleTrie::verify_proof_against_root(&proof, &root));
+    }
+
+    #[test]
+    fn test_sorted_pairs_proof_verification() {
+        let leaves: Vec<[u8; 32]> = [b"a", b"b", b"c"].iter().map(|data| keccak256(*data)).collect();
+        let sorted_pair = |x: &[u8; 32], y: &[u8; 32]| if x <= y { keccak256_combine(x, y) } else { keccak256_combine(y, x) };
+
+        let ab = sorted_pair(&leaves[0], &leaves[1]);
+        let cc = sorted_pair(&leaves[2], &leaves[2]);
+        let root = sorted_pair(&ab, &cc);
+
+        assert!(verify_sorted_pairs_proof(&leaves[0], &[leaves[1], cc], &root));
+        assert!(verify_sorted_pairs_proof(&leaves[2], &[leaves[2], ab], &root));
+        assert!(!verify_sorted_pairs_proof(&leaves[0], &[cc, leaves[1]], &root));
+    }
+
     #[test]
     fn test_equals() {
         let data1 = vec![b"a".to_vec(), b"b".to_vec()];
@@ -0,0 +1,132 @@
+//! Command line arguments shared by the monitors binaries, flattened into each binary `Cli`
+
+use clap::{Args, ValueEnum};
+
+/// Default API server bind address
+pub const DEFAULT_BIND_ADDRESS: &str = "0.0.0.0:8080";
+
+/// Environment variable with API server bind address, the lowest priority source
+pub const BIND_ADDRESS_ENV: &str = "BIND_ADDRESS";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum Mode {
+    /// HTTP API server, managing monitoring tasks
+    Api,
+    /// Single monitoring task, defined by the config file
+    Single,
+}
+
+/// Run mode, config and API server arguments of a monitor
+#[derive(Debug, Args)]
+pub struct MonitorArgs {
+    /// Run mode
+    #[arg(long, value_enum, default_value_t = Mode::Single)]
+    pub mode: Mode,
+
+    /// Shorthand for `--mode api`
+    #[arg(long, conflicts_with = "mode")]
+    pub api: bool,
+
+    /// Config file path [default: ./config.yaml in single task mode]
+    #[arg(short, long, conflicts_with = "config_path")]
+    pub config: Option<String>,
+
+    /// DB schema SQL file path [default: `postgres.schema` config value, then ./init.sql]
+    #[arg(short, long, conflicts_with = "schema_path")]
+    pub schema: Option<String>,
+
+    /// API server bind address [default: `api.bind_address` config value, then BIND_ADDRESS env, then 0.0.0.0:8080]
+    #[arg(short, long)]
+    pub bind: Option<String>,
+
+    /// Config file path, positional form kept for backward compatibility
+    #[arg(hide = true)]
+    pub config_path: Option<String>,
+
+    /// DB schema SQL file path, positional form kept for backward compatibility
+    #[arg(hide = true)]
+    pub schema_path: Option<String>,
+}
+
+impl MonitorArgs {
+    pub fn mode(&self) -> Mode {
+        if self.api { Mode::Api } else { self.mode }
+    }
+
+    pub fn config_path(&self) -> Option<&str> {
+        self.config.as_deref().or(self.config_path.as_deref())
+    }
+
+    pub fn schema_path(&self) -> Option<&str> {
+        self.schema.as_deref().or(self.schema_path.as_deref())
+    }
+
+    /// Resolve API server bind address: `--bind` option, then config value, then `BIND_ADDRESS` env
+    pub fn bind_address(&self, config_bind_address: Option<&str>) -> String {
+        self.bind
+            .clone()
+            .or_else(|| config_bind_address.map(str::to_string))
+            .or_else(|| std::env::var(BIND_ADDRESS_ENV).ok().filter(|address| !address.is_empty()))
+            .unwrap_or_else(|| DEFAULT_BIND_ADDRESS.to_string())
+    }
+}
+
+/// Backfill-only mode arguments of the monitors indexing blocks ranges
+#[derive(Debug, Args)]
+pub struct BackfillArgs {
+    /// Process the `--from`..`--to` blocks range (inclusive) and exit instead of monitoring,
+    /// with a non-zero exit code if any of it failed to persist
+    #[arg(long, requires_all = ["from", "to"], conflicts_with = "api")]
+    pub backfill_only: bool,
+
+    /// First block of the backfill range
+    #[arg(long, requires = "backfill_only")]
+    pub from: Option<u64>,
+
+    /// Last block of the backfill range
+    #[arg(long, requires = "backfill_only")]
+    pub to: Option<u64>,
+}
+
+impl BackfillArgs {
+    /// Backfill blocks range of the backfill-only mode
+    pub fn backfill_range(&self) -> Option<(u64, u64)> {
+        if self.backfill_only { self.from.zip(self.to) } else { None }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::Parser;
+
+    #[derive(Debug, Parser)]
+    struct Cli {
+        #[command(flatten)]
+        monitor: MonitorArgs,
+
+        #[command(flatten)]
+        backfill: BackfillArgs,
+    }
+
+    #[test]
+    fn test_flattened_args() {
+        let cli = Cli::try_parse_from(["monitor", "config.yaml", "init.sql"]).unwrap();
+        assert_eq!(cli.monitor.mode(), Mode::Single);
+        assert_eq!(cli.monitor.config_path(), Some("config.yaml"));
+        assert_eq!(cli.monitor.schema_path(), Some("init.sql"));
+        assert_eq!(cli.backfill.backfill_range(), None);
+
+        let cli = Cli::try_parse_from(["monitor", "--api", "--bind", "127.0.0.1:9000"]).unwrap();
+        assert_eq!(cli.monitor.mode(), Mode::Api);
+        assert_eq!(cli.monitor.bind_address(Some("0.0.0.0:8081")), "127.0.0.1:9000");
+
+        let cli = Cli::try_parse_from(["monitor", "--backfill-only", "--from", "10", "--to", "20"]).unwrap();
+        assert_eq!(cli.backfill.backfill_range(), Some((10, 20)));
+
+        // Constraints across the flattened groups still apply
+        assert!(Cli::try_parse_from(["monitor", "--api", "--backfill-only", "--from", "10", "--to", "20"]).is_err());
+        assert!(Cli::try_parse_from(["monitor", "--backfill-only", "--from", "10"]).is_err());
+        assert!(Cli::try_parse_from(["monitor", "--config", "a.yaml", "b.yaml"]).is_err());
+    }
+}
@@ -0,0 +1,70 @@
+//! Error types of the monitors processors
+
+use alloy::transports::TransportError;
+use thiserror::Error;
+
+/// Result type alias
+pub type Result<T> = std::result::Result<T, MonitorError>;
+
+/// Failure kinds of the monitors processors, so callers can match on them
+#[derive(Debug, Error)]
+pub enum MonitorError {
+    /// RPC request or endpoint connection error
+    #[error("RPC error: {0}")]
+    Rpc(String),
+
+    /// Database connection or query error
+    #[error("Database error: {0}")]
+    Database(String),
+
+    /// Log, block or RPC response decoding error
+    #[error("Decode error: {0}")]
+    Decode(String),
+
+    /// NATS connection or publishing error
+    #[error("NATS error: {0}")]
+    Nats(String),
+
+    /// Invalid configuration, ABIs or chain mismatch
+    #[error("Configuration error: {0}")]
+    Config(String),
+
+    /// Chain reorganization handling error, e.g. a missing ancestor of the new chain
+    #[error("Chain reorganization: {0}")]
+    Reorg(String),
+
+    /// Output sink write or flush error
+    #[error("Sink error: {0}")]
+    Sink(String),
+
+    /// Internal error, e.g. a panicked task or stopped workers
+    #[error("Internal error: {0}")]
+    Internal(String),
+}
+
+impl MonitorError {
+    /// Short error kind name, for metrics labels
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Self::Rpc(_) => "rpc",
+            Self::Database(_) => "database",
+            Self::Decode(_) => "decode",
+            Self::Nats(_) => "nats",
+            Self::Config(_) => "config",
+            Self::Reorg(_) => "reorg",
+            Self::Sink(_) => "sink",
+            Self::Internal(_) => "internal",
+        }
+    }
+}
+
+impl From<TransportError> for MonitorError {
+    fn from(err: TransportError) -> Self {
+        // Responses not deserializing into the expected type are decoding failures
+        if err.is_deser_error() {
+            Self::Decode(err.to_string())
+        } else {
+            Self::Rpc(err.to_string())
+        }
+    }
+}
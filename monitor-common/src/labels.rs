@@ -0,0 +1,92 @@
+//! Human-readable labels of addresses, from the contracts file names and the config
+
+use std::collections::HashMap;
+use std::str::FromStr;
+use alloy::primitives::Address;
+use anyhow::anyhow;
+use serde::Deserialize;
+use tracing::info;
+use abi_fetcher::contracts_file::ContractsFile;
+
+/// Address labels config of a monitor, the labels are attached to its persisted payloads
+#[derive(Debug, Deserialize, Clone, Default)]
+pub struct LabelsCfg {
+    pub addresses: Option<HashMap<String, String>>, // Address -> label, overrides the contracts file names
+    pub contracts_file: Option<String>, // Contracts YAML file written by 'abi-fetcher' or 'contracts-fetcher', the contracts names are used as labels
+}
+
+/// Human-readable labels of addresses, attached to the persisted payloads
+#[derive(Debug, Clone, Default)]
+pub struct AddressLabels {
+    labels: HashMap<Address, String>,
+}
+
+impl AddressLabels {
+    /// Load the contracts names of the contracts file as labels, overridden by the labels listed in the config
+    pub fn load(config: &LabelsCfg) -> anyhow::Result<Self> {
+        let mut labels = HashMap::new();
+
+        if let Some(path) = &config.contracts_file {
+            let contracts = ContractsFile::load(path)?;
+            for (address, name) in contracts.contract_names() {
+                let parsed = Address::from_str(&address)
+                    .map_err(|e| anyhow!("Invalid address {} in contracts file {}: {}", address, path, e))?;
+                labels.insert(parsed, name);
+            }
+        }
+
+        for (address, label) in config.addresses.iter().flatten() {
+            let parsed = Address::from_str(address)
+                .map_err(|e| anyhow!("Invalid labelled address {}: {}", address, e))?;
+            labels.insert(parsed, label.clone());
+        }
+
+        info!("Address labels loaded: {}", labels.len());
+
+        Ok(Self { labels })
+    }
+
+    /// Label of the address, if any
+    pub fn get(&self, address: &Address) -> Option<String> {
+        self.labels.get(address).cloned()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_address_labels_override_contracts_file() {
+        let contracts_file = std::env::temp_dir().join(format!("monitor-common-labels-{}.yaml", std::process::id()));
+        std::fs::write(&contracts_file, r#"
+metadata:
+  generated_at: "2025-01-01T00:00:00Z"
+verified_contracts:
+  - name: "Proxy"
+    address: "0x1111111111111111111111111111111111111111"
+    implementations:
+      - name: "TokenV2"
+        address: "0x2222222222222222222222222222222222222222"
+unverified_contracts:
+  - name: null
+    address: "0x3333333333333333333333333333333333333333"
+"#).unwrap();
+
+        let config = LabelsCfg {
+            addresses: Some(HashMap::from([
+                ("0x1111111111111111111111111111111111111111".to_string(), "Token".to_string()),
+                ("0xabcdefabcdefabcdefabcdefabcdefabcdefabcd".to_string(), "Treasury".to_string()),
+            ])),
+            contracts_file: Some(contracts_file.to_string_lossy().to_string()),
+        };
+        let labels = AddressLabels::load(&config).unwrap();
+        std::fs::remove_file(&contracts_file).unwrap();
+
+        let address = |hex: &str| Address::from_str(hex).unwrap();
+        assert_eq!(labels.get(&address("0x1111111111111111111111111111111111111111")).as_deref(), Some("Token"));
+        assert_eq!(labels.get(&address("0x2222222222222222222222222222222222222222")).as_deref(), Some("TokenV2"));
+        assert_eq!(labels.get(&address("0xABCDEFABCDEFABCDEFABCDEFABCDEFABCDEFABCD")).as_deref(), Some("Treasury"));
+        assert_eq!(labels.get(&address("0x3333333333333333333333333333333333333333")), None);
+    }
+}
@@ -0,0 +1,13 @@
+//! Shared building blocks of the monitors
+//!
+//! - `cli`: run mode, config, API server and backfill-only command line arguments of the binaries
+//! - `error`: `MonitorError` failure kinds of the processors and the `Result` alias
+//! - `labels`: address labels of the persisted payloads, from the contracts file and the config
+//! - `nats`: best-effort NATS publisher, buffering the payloads while NATS is degraded
+
+pub mod cli;
+pub mod error;
+pub mod labels;
+pub mod nats;
+
+pub use error::{MonitorError, Result};
@@ -0,0 +1,290 @@
+//! Best-effort NATS publishing of the monitors payloads, buffered while NATS is degraded
+
+use std::collections::VecDeque;
+use std::future::Future;
+use std::sync::{Arc, Mutex, Weak};
+use std::time::Duration;
+use async_nats::Client;
+use async_nats::connection::State;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use tracing::{info, warn};
+
+/// Default bound of the payloads buffered while NATS is degraded
+const DEFAULT_BUFFER_CAPACITY: usize = 10_000;
+/// Default interval of republishing the buffered payloads while NATS is degraded
+const DEFAULT_RETRY_INTERVAL_SECS: u64 = 5;
+/// Default timeout of a publish, after which NATS is degraded
+const DEFAULT_PUBLISH_TIMEOUT_SECS: u64 = 10;
+
+/// NATS destination of a monitor payloads, e.g. the object store of the events or the blocks
+pub trait PublishTarget: Send + Sync + 'static {
+    type Payload: Clone + Send + Sync + 'static;
+
+    /// Payloads name for logging, e.g. "events"
+    const PAYLOADS: &'static str;
+
+    /// Publish the payload, awaiting its persistence
+    fn publish(&self, payload: &Self::Payload) -> impl Future<Output = anyhow::Result<()>> + Send;
+
+    /// Client of the NATS connection, for its state and flushing
+    fn client(&self) -> &Client;
+}
+
+/// Buffering and timeouts of the publisher, defaults apply to the unset values
+#[derive(Debug, Clone, Copy, Default)]
+pub struct PublisherCfg {
+    pub buffer_capacity: Option<usize>,
+    pub retry_interval_secs: Option<u64>,
+    pub publish_timeout_secs: Option<u64>,
+}
+
+/// Bounded FIFO of the payloads to republish, dropping the oldest ones when full.
+/// Entries are numbered, so an entry republished while newer ones were pushed is popped only
+/// if it wasn't dropped meanwhile.
+struct PublishBuffer<T> {
+    entries: VecDeque<(u64, T)>,
+    capacity: usize,
+    next_seq: u64,
+    dropped: u64,
+}
+
+impl<T: Clone> PublishBuffer<T> {
+    fn new(capacity: usize) -> Self {
+        Self { entries: VecDeque::new(), capacity: capacity.max(1), next_seq: 0, dropped: 0 }
+    }
+
+    /// Push the payload, returning whether the oldest payload was dropped to make room
+    fn push(&mut self, payload: T) -> bool {
+        let dropped = self.entries.len() >= self.capacity;
+        if dropped {
+            self.entries.pop_front();
+            self.dropped += 1;
+        }
+        self.entries.push_back((self.next_seq, payload));
+        self.next_seq += 1;
+        dropped
+    }
+
+    fn front(&self) -> Option<(u64, T)> {
+        self.entries.front().cloned()
+    }
+
+    /// Pop the front entry if it's still the given one
+    fn pop_published(&mut self, seq: u64) {
+        if self.entries.front().is_some_and(|(front_seq, _)| *front_seq == seq) {
+            self.entries.pop_front();
+        }
+    }
+
+    fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
+
+/// NATS publishing health, as exposed by the task API
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NatsHealth {
+    pub degraded: bool,
+    pub connection: String, // NATS client connection state: "connected", "disconnected" or "pending"
+    pub buffered: usize, // Payloads waiting to be republished
+    pub dropped: u64, // Payloads dropped from the full buffer since start
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub degraded_since: Option<DateTime<Utc>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_error: Option<String>,
+}
+
+/// Best-effort publishing of the payloads to NATS. A failed or timed out publish marks NATS degraded
+/// instead of failing the payload processing, so the database persistence continues during a NATS outage.
+/// While degraded, the payloads are buffered in order (up to the buffer capacity, dropping the oldest),
+/// and republished by a background task once the client, reconnecting in the background, is connected again.
+pub struct NatsPublisher<T: PublishTarget> {
+    inner: Arc<PublisherInner<T>>,
+}
+
+impl<T: PublishTarget> Clone for NatsPublisher<T> {
+    fn clone(&self) -> Self {
+        Self { inner: Arc::clone(&self.inner) }
+    }
+}
+
+struct PublisherInner<T: PublishTarget> {
+    target: T,
+    publish_timeout: Duration,
+    state: Mutex<PublisherState<T::Payload>>,
+}
+
+struct PublisherState<P> {
+    buffer: PublishBuffer<P>,
+    degraded_since: Option<DateTime<Utc>>,
+    last_error: Option<String>,
+}
+
+impl<T: PublishTarget> NatsPublisher<T> {
+    /// Publisher to the connected NATS target, spawning the republishing task, which ends with the last publisher clone
+    pub fn new(target: T, cfg: PublisherCfg) -> Self {
+        let inner = Arc::new(PublisherInner {
+            target,
+            publish_timeout: Duration::from_secs(cfg.publish_timeout_secs.unwrap_or(DEFAULT_PUBLISH_TIMEOUT_SECS)),
+            state: Mutex::new(PublisherState {
+                buffer: PublishBuffer::new(cfg.buffer_capacity.unwrap_or(DEFAULT_BUFFER_CAPACITY)),
+                degraded_since: None,
+                last_error: None,
+            }),
+        });
+
+        let retry_interval = Duration::from_secs(cfg.retry_interval_secs.unwrap_or(DEFAULT_RETRY_INTERVAL_SECS).max(1));
+        tokio::spawn(republish_buffered(Arc::downgrade(&inner), retry_interval));
+
+        Self { inner }
+    }
+
+    /// Publish the payload, or buffer it if NATS is degraded. Never fails.
+    pub async fn publish(&self, payload: &T::Payload) {
+        {
+            let mut state = self.inner.state.lock().unwrap();
+            if state.degraded_since.is_some() {
+                self.inner.buffer(&mut state, payload.clone());
+                return;
+            }
+        }
+
+        if let Err(e) = self.inner.try_publish(payload).await {
+            let mut state = self.inner.state.lock().unwrap();
+            if state.degraded_since.is_none() {
+                warn!("[NATS] Publishing failed, NATS degraded, buffering {}: {:#}", T::PAYLOADS, e);
+                state.degraded_since = Some(Utc::now());
+            }
+            state.last_error = Some(format!("{:#}", e));
+            self.inner.buffer(&mut state, payload.clone());
+        }
+    }
+
+    /// Republish the buffered payloads if the client is connected, then flush the client.
+    /// Fails if payloads are still buffered or were dropped from the full buffer, as they aren't
+    /// published, so one-shot jobs don't exit successfully after losing them.
+    pub async fn flush(&self) -> anyhow::Result<()> {
+        if self.inner.target.client().connection_state() == State::Connected {
+            self.inner.republish().await;
+        }
+
+        {
+            let state = self.inner.state.lock().unwrap();
+            if !state.buffer.is_empty() || state.buffer.dropped > 0 {
+                anyhow::bail!(
+                    "NATS degraded, {} {} not published ({} buffered, {} dropped from the full buffer), last error: {}",
+                    state.buffer.len() as u64 + state.buffer.dropped, T::PAYLOADS, state.buffer.len(), state.buffer.dropped,
+                    state.last_error.as_deref().unwrap_or("none")
+                );
+            }
+        }
+
+        match tokio::time::timeout(self.inner.publish_timeout, self.inner.target.client().flush()).await {
+            Ok(Ok(())) => {}
+            Ok(Err(e)) => warn!("[NATS] Flush failed: {}", e),
+            Err(_) => warn!("[NATS] Flush timed out after {:?}", self.inner.publish_timeout),
+        }
+        Ok(())
+    }
+
+    pub fn health(&self) -> NatsHealth {
+        let state = self.inner.state.lock().unwrap();
+        NatsHealth {
+            degraded: state.degraded_since.is_some(),
+            connection: self.inner.target.client().connection_state().to_string(),
+            buffered: state.buffer.len(),
+            dropped: state.buffer.dropped,
+            degraded_since: state.degraded_since,
+            last_error: state.last_error.clone(),
+        }
+    }
+}
+
+impl<T: PublishTarget> PublisherInner<T> {
+    async fn try_publish(&self, payload: &T::Payload) -> anyhow::Result<()> {
+        tokio::time::timeout(self.publish_timeout, self.target.publish(payload))
+            .await
+            .map_err(|_| anyhow::anyhow!("publish timed out after {:?}", self.publish_timeout))?
+    }
+
+    fn buffer(&self, state: &mut PublisherState<T::Payload>, payload: T::Payload) {
+        let dropped_before = state.buffer.dropped;
+        if state.buffer.push(payload) && dropped_before == 0 {
+            warn!("[NATS] Buffer of {} {} is full, dropping the oldest {}", state.buffer.capacity, T::PAYLOADS, T::PAYLOADS);
+        }
+    }
+
+    /// Republish the buffered payloads in order, until the buffer is empty (NATS recovered) or a publish fails
+    async fn republish(&self) {
+        let mut republished = 0;
+        loop {
+            let Some((seq, payload)) = self.state.lock().unwrap().buffer.front() else {
+                return;
+            };
+
+            if let Err(e) = self.try_publish(&payload).await {
+                warn!("[NATS] Republishing buffered {} failed: {:#}", T::PAYLOADS, e);
+                self.state.lock().unwrap().last_error = Some(format!("{:#}", e));
+                return;
+            }
+            republished += 1;
+
+            let mut state = self.state.lock().unwrap();
+            state.buffer.pop_published(seq);
+            if state.buffer.is_empty() {
+                state.degraded_since = None;
+                state.last_error = None;
+                info!("[NATS] Recovered, republished {} buffered {}", republished, T::PAYLOADS);
+                return;
+            }
+        }
+    }
+}
+
+/// Republish the buffered payloads once per interval while the client is connected,
+/// until the publisher is dropped
+async fn republish_buffered<T: PublishTarget>(inner: Weak<PublisherInner<T>>, interval: Duration) {
+    let mut ticker = tokio::time::interval(interval);
+    ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+    loop {
+        ticker.tick().await;
+        let Some(inner) = inner.upgrade() else {
+            return;
+        };
+        if inner.target.client().connection_state() == State::Connected {
+            inner.republish().await;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_publish_buffer_drops_oldest() {
+        let mut buffer = PublishBuffer::new(2);
+        assert!(!buffer.push("a"));
+        assert!(!buffer.push("b"));
+        assert!(buffer.push("c"));
+        assert_eq!(buffer.len(), 2);
+        assert_eq!(buffer.dropped, 1);
+
+        // "b" is republished while "d" drops it, so the front "c" isn't popped for it
+        let (seq, payload) = buffer.front().unwrap();
+        assert_eq!(payload, "b");
+        assert!(buffer.push("d"));
+        buffer.pop_published(seq);
+        assert_eq!(buffer.front().map(|(_, payload)| payload), Some("c"));
+
+        let (seq, _) = buffer.front().unwrap();
+        buffer.pop_published(seq);
+        assert_eq!(buffer.front().map(|(_, payload)| payload), Some("d"));
+        assert!(!buffer.is_empty());
+    }
+}
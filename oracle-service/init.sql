@@ -9,6 +9,8 @@ CREATE TABLE IF NOT EXISTS revenue_reports (
     revenue TEXT NOT NULL,
     timestamp BIGINT NOT NULL,
     submitted_to_chain BOOLEAN DEFAULT FALSE,
+    -- Reason the report is excluded from the batches, e.g. an invalid value
+    quarantine_reason TEXT,
     created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
     UNIQUE(artifact_address, timestamp)
 );
@@ -21,10 +23,16 @@ CREATE TABLE IF NOT EXISTS usage_reports (
     usage TEXT NOT NULL,
     timestamp BIGINT NOT NULL,
     submitted_to_chain BOOLEAN DEFAULT FALSE,
+    -- Reason the report is excluded from the batches, e.g. an invalid value
+    quarantine_reason TEXT,
     created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
     UNIQUE(artifact_address, timestamp)
 );
 
+-- Quarantine of the reports tables created before it
+ALTER TABLE revenue_reports ADD COLUMN IF NOT EXISTS quarantine_reason TEXT;
+ALTER TABLE usage_reports ADD COLUMN IF NOT EXISTS quarantine_reason TEXT;
+
 -- Indexes for efficient queries
 CREATE INDEX IF NOT EXISTS idx_revenue_artifact_timestamp ON revenue_reports(artifact_address, timestamp);
 CREATE INDEX IF NOT EXISTS idx_usage_artifact_timestamp ON usage_reports(artifact_address, timestamp);
@@ -0,0 +1,131 @@
+use alloy::primitives::U256;
+use serde::Deserialize;
+
+/// Rounding of the value digits beyond the configured decimals
+#[derive(Debug, Deserialize, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum RoundingPolicy {
+    /// Values with more significant fractional digits than the decimals are invalid
+    #[default]
+    Reject,
+    /// Round towards zero
+    Floor,
+    /// Round away from zero
+    Ceil,
+    /// Round to the nearest, halves away from zero
+    HalfUp,
+}
+
+/// Parse a non-negative decimal string, e.g. `"12.345"` or `"1.5e3"`, scaled by `10^decimals`
+/// into an integer amount, rounding the digits beyond the decimals per the policy
+pub fn scale_decimal(value: &str, decimals: u8, rounding: RoundingPolicy) -> Result<U256, String> {
+    let value = value.trim();
+    if value.is_empty() {
+        return Err("Value is empty".to_string());
+    }
+    if value.starts_with('-') {
+        return Err(format!("Value '{}' is negative", value));
+    }
+
+    let (mantissa, exponent) = match value.split_once(['e', 'E']) {
+        Some((mantissa, exponent)) => {
+            let exponent = exponent.parse::<i64>()
+                .map_err(|_| format!("Value '{}' has invalid exponent", value))?;
+            (mantissa, exponent)
+        }
+        None => (value, 0),
+    };
+
+    let (integer, fraction) = mantissa.split_once('.').unwrap_or((mantissa, ""));
+    if (integer.is_empty() && fraction.is_empty())
+        || !integer.bytes().chain(fraction.bytes()).all(|digit| digit.is_ascii_digit())
+    {
+        return Err(format!("Value '{}' is not a decimal number", value));
+    }
+
+    // value = digits * 10^shift
+    let digits = format!("{}{}", integer, fraction);
+    let shift = (decimals as i64).saturating_add(exponent).saturating_sub(fraction.len() as i64);
+
+    let amount = if shift >= 0 {
+        let digits = parse_digits(&digits, value)?;
+        if digits.is_zero() {
+            return Ok(U256::ZERO);
+        }
+        U256::from(10)
+            .checked_pow(U256::from(shift))
+            .and_then(|scale| digits.checked_mul(scale))
+            .ok_or_else(|| format!("Value '{}' overflows uint256", value))?
+    } else {
+        let rounded_digits = shift.unsigned_abs();
+        let dropped_count = (rounded_digits.min(digits.len() as u64)) as usize;
+        let (kept, dropped) = digits.split_at(digits.len() - dropped_count);
+        let kept = parse_digits(kept, value)?;
+        let has_remainder = dropped.bytes().any(|digit| digit != b'0');
+
+        let round_up = match rounding {
+            RoundingPolicy::Reject if has_remainder => {
+                return Err(format!("Value '{}' has more than {} decimals", value, decimals));
+            }
+            RoundingPolicy::Reject | RoundingPolicy::Floor => false,
+            RoundingPolicy::Ceil => has_remainder,
+            // Dropped digits are all of the rounded part only if the value was shifted by their count exactly
+            RoundingPolicy::HalfUp => rounded_digits == dropped_count as u64 && dropped.as_bytes()[0] >= b'5',
+        };
+
+        if round_up {
+            kept.checked_add(U256::from(1))
+                .ok_or_else(|| format!("Value '{}' overflows uint256", value))?
+        } else {
+            kept
+        }
+    };
+
+    Ok(amount)
+}
+
+fn parse_digits(digits: &str, value: &str) -> Result<U256, String> {
+    if digits.is_empty() {
+        return Ok(U256::ZERO);
+    }
+    U256::from_str_radix(digits, 10).map_err(|_| format!("Value '{}' overflows uint256", value))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scale_integer_and_decimal_values() {
+        assert_eq!(scale_decimal("42", 0, RoundingPolicy::Reject), Ok(U256::from(42)));
+        assert_eq!(scale_decimal("42", 6, RoundingPolicy::Reject), Ok(U256::from(42_000_000)));
+        assert_eq!(scale_decimal("12.345", 6, RoundingPolicy::Reject), Ok(U256::from(12_345_000)));
+        assert_eq!(scale_decimal(".5", 1, RoundingPolicy::Reject), Ok(U256::from(5)));
+        assert_eq!(scale_decimal("1.5e3", 0, RoundingPolicy::Reject), Ok(U256::from(1500)));
+        assert_eq!(scale_decimal("0.000", 0, RoundingPolicy::Reject), Ok(U256::ZERO));
+        assert_eq!(scale_decimal("1.0", 0, RoundingPolicy::Reject), Ok(U256::from(1)));
+    }
+
+    #[test]
+    fn test_rounding_policies() {
+        assert!(scale_decimal("1.25", 1, RoundingPolicy::Reject).is_err());
+        assert_eq!(scale_decimal("1.25", 1, RoundingPolicy::Floor), Ok(U256::from(12)));
+        assert_eq!(scale_decimal("1.21", 1, RoundingPolicy::Ceil), Ok(U256::from(13)));
+        assert_eq!(scale_decimal("1.25", 1, RoundingPolicy::HalfUp), Ok(U256::from(13)));
+        assert_eq!(scale_decimal("1.249", 1, RoundingPolicy::HalfUp), Ok(U256::from(12)));
+        // All digits rounded off
+        assert_eq!(scale_decimal("0.004", 2, RoundingPolicy::HalfUp), Ok(U256::ZERO));
+        assert_eq!(scale_decimal("0.004", 2, RoundingPolicy::Ceil), Ok(U256::from(1)));
+        assert_eq!(scale_decimal("4e-4", 2, RoundingPolicy::HalfUp), Ok(U256::ZERO));
+    }
+
+    #[test]
+    fn test_invalid_values() {
+        assert!(scale_decimal("", 0, RoundingPolicy::Floor).is_err());
+        assert!(scale_decimal("-1", 0, RoundingPolicy::Floor).is_err());
+        assert!(scale_decimal("1.2.3", 0, RoundingPolicy::Floor).is_err());
+        assert!(scale_decimal("abc", 0, RoundingPolicy::Floor).is_err());
+        assert!(scale_decimal(".", 0, RoundingPolicy::Floor).is_err());
+        assert!(scale_decimal("1e80", 0, RoundingPolicy::Floor).is_err());
+    }
+}
@@ -8,6 +8,7 @@ use axum::{
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
 use crate::db::Database;
+use crate::queue::{QueueStatus, TxQueue};
 
 // Response models for the API endpoints
 #[derive(Debug, Serialize, Deserialize)]
@@ -34,16 +35,19 @@ pub struct ErrorResponse {
 #[derive(Clone)]
 pub struct AppState {
     pub _db: Arc<Database>,
+    pub queue: Option<Arc<TxQueue>>,
 }
 
 // Create the main router with all endpoints
-pub fn create_router(db: Arc<Database>) -> Router {
+pub fn create_router(db: Arc<Database>, queue: Option<Arc<TxQueue>>) -> Router {
     let state = AppState {
         _db: db,
+        queue,
     };
 
     Router::new()
         .route("/health", get(health_check))
+        .route("/api/v1/queue", get(get_queue_status))
         .route("/api/v1/artifacts/{address}/six-month-revenue", get(get_six_months_revenue))
         .route("/api/v1/artifacts/{address}/total-usage", get(get_total_usage))
         .with_state(state)
@@ -54,6 +58,16 @@ async fn health_check() -> StatusCode {
     StatusCode::OK
 }
 
+// Submission queue status endpoint, not found when batching is disabled
+async fn get_queue_status(
+    State(state): State<AppState>,
+) -> Result<Json<QueueStatus>, StatusCode> {
+    state.queue
+        .as_ref()
+        .map(|queue| Json(queue.status()))
+        .ok_or(StatusCode::NOT_FOUND)
+}
+
 // Get six months revenue endpoint
 async fn get_six_months_revenue(
     Path(address): Path<String>,
@@ -1,12 +1,18 @@
 use anyhow::Result;
 use serde::Deserialize;
 
+use crate::amount::RoundingPolicy;
+
 #[derive(Debug, Deserialize, Clone)]
 pub struct Config {
     pub database: DatabaseConfig,
     pub service: ServiceConfig,
+    #[serde(default)]
+    pub blockchain: BlockchainConfig,
     pub mining: MiningConfig,
     pub contract: ContractConfig,
+    #[serde(default)]
+    pub validation: ValidationConfig,
 }
 
 #[derive(Debug, Deserialize, Clone)]
@@ -25,6 +31,14 @@ pub struct ServiceConfig {
     pub log_level: String,
 }
 
+/// Blockchain settings, each falling back to its legacy environment variable if not set
+#[derive(Debug, Deserialize, Clone, Default)]
+pub struct BlockchainConfig {
+    pub rpc_url: Option<String>,
+    pub private_key: Option<String>,
+    pub contract_address: Option<String>,
+    pub chain_id: Option<u64>,
+}
 
 #[derive(Debug, Deserialize, Clone)]
 pub struct MiningConfig {
@@ -37,12 +51,62 @@ pub struct MiningConfig {
     pub page_size: u32,
     /// Maximum number of pages to fetch to prevent infinite loops
     pub max_pages: u32,
+    /// Mining API URL, falls back to `API_URL` environment variable if not set
+    pub api_url: Option<String>,
+    /// Mining API key, falls back to `API_KEY` environment variable if not set
+    pub api_key: Option<String>,
+}
+
+/// Data-quality rules of the mined data, each unset rule is not applied
+#[derive(Debug, Deserialize, Clone, Default)]
+pub struct ValidationConfig {
+    pub min_usage: Option<f64>,
+    pub max_usage: Option<f64>,
+    pub min_revenue: Option<f64>,
+    pub max_revenue: Option<f64>,
+    /// Artifact addresses accepted, any if empty
+    #[serde(default)]
+    pub allowed_artifacts: Vec<String>,
+    /// Maximum age of the data timestamp, relative to the end of the mined window
+    pub max_age_seconds: Option<u64>,
+    /// Maximum skew of the data timestamp ahead of the end of the mined window
+    pub max_future_seconds: Option<u64>,
 }
 
 #[derive(Debug, Deserialize, Clone)]
 pub struct ContractConfig {
     pub batch_size: i32,
     pub batch_interval_seconds: u64,
+    /// Minimal interval between transactions sent by the submission queue worker
+    #[serde(default = "default_submission_interval_ms")]
+    pub submission_interval_ms: u64,
+    /// Number of batches the submission queue holds before the batching task waits
+    #[serde(default = "default_queue_capacity")]
+    pub queue_capacity: usize,
+    /// Interval of re-syncing the locally tracked nonce with the node's pending nonce
+    #[serde(default = "default_nonce_resync_interval_seconds")]
+    pub nonce_resync_interval_seconds: u64,
+    /// Decimals the usage values are scaled by before submission, `usage * 10^usage_decimals`
+    #[serde(default)]
+    pub usage_decimals: u8,
+    /// Decimals the revenue values are scaled by before submission, e.g. of the revenue token
+    #[serde(default)]
+    pub revenue_decimals: u8,
+    /// Rounding of the value digits beyond the decimals, values with such digits are quarantined by default
+    #[serde(default)]
+    pub rounding: RoundingPolicy,
+}
+
+fn default_submission_interval_ms() -> u64 {
+    1000
+}
+
+fn default_queue_capacity() -> usize {
+    16
+}
+
+fn default_nonce_resync_interval_seconds() -> u64 {
+    60
 }
 
 impl Config {
@@ -56,6 +120,8 @@ impl Config {
             .add_source(config::File::with_name("config").required(false))
             // Add environment variables with prefix "ORACLE_"
             .add_source(config::Environment::with_prefix("ORACLE").separator("_"))
+            // Add `SECTION__KEY` environment variables, e.g. `BLOCKCHAIN__RPC_URL`
+            .add_source(config::Environment::default().separator("__"))
             .build()?;
 
         let config: Config = config_builder.try_deserialize()?;
@@ -73,41 +139,38 @@ impl Config {
         )
     }
 
-    /// Load blockchain RPC URL from environment variable
+    /// Blockchain RPC URL from `blockchain.rpc_url` or `RPC_URL` environment variable
     pub fn blockchain_rpc_url(&self) -> Result<String> {
-        std::env::var("RPC_URL")
-            .map_err(|_| anyhow::anyhow!("RPC_URL environment variable not set"))
+        setting(&self.blockchain.rpc_url, "blockchain.rpc_url", "RPC_URL")
     }
 
-    /// Load blockchain private key from environment variable
+    /// Blockchain private key from `blockchain.private_key` or `PRIVATE_KEY` environment variable
     pub fn blockchain_private_key(&self) -> Result<String> {
-        std::env::var("PRIVATE_KEY")
-            .map_err(|_| anyhow::anyhow!("PRIVATE_KEY environment variable not set"))
+        setting(&self.blockchain.private_key, "blockchain.private_key", "PRIVATE_KEY")
     }
 
-    /// Load blockchain contract address from environment variable
+    /// Blockchain contract address from `blockchain.contract_address` or `CONTRACT_ADDRESS` environment variable
     pub fn blockchain_contract_address(&self) -> Result<String> {
-        std::env::var("CONTRACT_ADDRESS")
-            .map_err(|_| anyhow::anyhow!("CONTRACT_ADDRESS environment variable not set"))
+        setting(&self.blockchain.contract_address, "blockchain.contract_address", "CONTRACT_ADDRESS")
     }
 
-    /// Load mining API URL from environment variable
+    /// Mining API URL from `mining.api_url` or `API_URL` environment variable
     pub fn mining_api_url(&self) -> Result<String> {
-        std::env::var("API_URL")
-            .map_err(|_| anyhow::anyhow!("API_URL environment variable not set"))
+        setting(&self.mining.api_url, "mining.api_url", "API_URL")
     }
 
-    /// Load mining API key from environment variable
+    /// Mining API key from `mining.api_key` or `API_KEY` environment variable
     pub fn mining_api_key(&self) -> Result<String> {
-        std::env::var("API_KEY")
-            .map_err(|_| anyhow::anyhow!("API_KEY environment variable not set"))
+        setting(&self.mining.api_key, "mining.api_key", "API_KEY")
     }
 
-    /// Load blockchain chain ID from environment variable
+    /// Blockchain chain ID from `blockchain.chain_id` or `CHAIN_ID` environment variable
     pub fn blockchain_chain_id(&self) -> Result<u64> {
-        std::env::var("CHAIN_ID")
-            .map_err(|_| anyhow::anyhow!("CHAIN_ID environment variable not set"))
-            .and_then(|id| id.parse::<u64>().map_err(|e| anyhow::anyhow!("Invalid CHAIN_ID: {}", e)))
+        match self.blockchain.chain_id {
+            Some(chain_id) => Ok(chain_id),
+            None => setting(&None, "blockchain.chain_id", "CHAIN_ID")
+                .and_then(|id| id.parse::<u64>().map_err(|e| anyhow::anyhow!("Invalid CHAIN_ID: {}", e))),
+        }
     }
 
     /// Validate mining configuration to prevent invalid time ranges
@@ -125,3 +188,14 @@ impl Config {
         Ok(())
     }
 }
+
+/// Config value if set, otherwise the legacy environment variable
+fn setting(value: &Option<String>, key: &str, legacy_env: &str) -> Result<String> {
+    value
+        .clone()
+        .filter(|value| !value.is_empty())
+        .or_else(|| std::env::var(legacy_env).ok().filter(|value| !value.is_empty()))
+        .ok_or_else(|| anyhow::anyhow!(
+            "{} is not configured, set it in config or via {} environment variable", key, legacy_env
+        ))
+}
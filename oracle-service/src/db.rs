@@ -1,8 +1,60 @@
 use anyhow::Result;
+use std::sync::Arc;
+use std::time::Duration;
 use tokio_postgres::{Client, NoTls};
-use tracing::{info, error};
+use tracing::{info, error, warn};
 use crate::types::BackendData;
 
+/// Attempts of releasing a cycle lock
+const UNLOCK_ATTEMPTS: u32 = 3;
+
+/// Periodic cycle of the service, run by one service instance at a time
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Cycle {
+    Mining,
+    Batching,
+}
+
+impl Cycle {
+    /// Postgres advisory lock key of the cycle
+    fn lock_key(self) -> i64 {
+        match self {
+            Cycle::Mining => 0x6f7261636c6501,
+            Cycle::Batching => 0x6f7261636c6502,
+        }
+    }
+}
+
+/// Advisory lock of the cycle, held on the database session until released.
+/// Dropping it without the release, e.g. on panic, unlocks in the background.
+pub struct CycleLock {
+    db: Arc<Database>,
+    cycle: Cycle,
+    released: bool,
+}
+
+impl CycleLock {
+    pub async fn release(mut self) -> Result<()> {
+        self.released = true;
+        self.db.advisory_unlock(self.cycle).await
+    }
+}
+
+impl Drop for CycleLock {
+    fn drop(&mut self) {
+        if self.released {
+            return;
+        }
+        let db = Arc::clone(&self.db);
+        let cycle = self.cycle;
+        tokio::spawn(async move {
+            if let Err(e) = db.advisory_unlock(cycle).await {
+                error!("Failed to release {:?} cycle lock: {}", cycle, e);
+            }
+        });
+    }
+}
+
 pub struct Database {
     client: Client,
 }
@@ -68,7 +120,7 @@ impl Database {
         let query = r#"
             SELECT id, artifact_address, revenue, timestamp
             FROM revenue_reports
-            WHERE submitted_to_chain = FALSE
+            WHERE submitted_to_chain = FALSE AND quarantine_reason IS NULL
             ORDER BY timestamp ASC
             LIMIT $1
         "#;
@@ -82,7 +134,7 @@ impl Database {
         let query = r#"
             SELECT id, artifact_address, usage, timestamp
             FROM usage_reports
-            WHERE submitted_to_chain = FALSE
+            WHERE submitted_to_chain = FALSE AND quarantine_reason IS NULL
             ORDER BY timestamp ASC
             LIMIT $1
         "#;
@@ -121,6 +173,83 @@ impl Database {
         self.update_revenue_report_submitted_to_chain(ids).await
     }
 
+    /// Exclude the usage report from the batches, with the reason it can't be submitted
+    pub async fn quarantine_usage_report(&self, id: i32, reason: &str) -> Result<()> {
+        let query = r#"
+            UPDATE usage_reports
+            SET quarantine_reason = $2
+            WHERE id = $1
+        "#;
+        self.client.execute(query, &[&id, &reason]).await?;
+        Ok(())
+    }
+
+    /// Exclude the revenue report from the batches, with the reason it can't be submitted
+    pub async fn quarantine_revenue_report(&self, id: i32, reason: &str) -> Result<()> {
+        let query = r#"
+            UPDATE revenue_reports
+            SET quarantine_reason = $2
+            WHERE id = $1
+        "#;
+        self.client.execute(query, &[&id, &reason]).await?;
+        Ok(())
+    }
+
+    /// Take the cycle advisory lock, `None` when it's held by another session, i.e. another service instance.
+    /// The lock is re-entrant within the session, the cycles of the service are run sequentially by their tasks,
+    /// and the release drops all the session holds.
+    pub async fn try_lock_cycle(self: &Arc<Self>, cycle: Cycle) -> Result<Option<CycleLock>> {
+        let row = self.client
+            .query_one("SELECT pg_try_advisory_lock($1)", &[&cycle.lock_key()])
+            .await?;
+        let locked: bool = row.get(0);
+
+        Ok(locked.then(|| CycleLock {
+            db: Arc::clone(self),
+            cycle,
+            released: false,
+        }))
+    }
+
+    /// Release the cycle lock, retried as a held lock would keep the other instances out of the cycle
+    async fn advisory_unlock(&self, cycle: Cycle) -> Result<()> {
+        let mut attempt = 1;
+        loop {
+            match self.release_cycle_holds(cycle).await {
+                Ok(()) => return Ok(()),
+                Err(e) if attempt < UNLOCK_ATTEMPTS => {
+                    warn!("Failed to release {:?} cycle lock (attempt {}/{}): {}", cycle, attempt, UNLOCK_ATTEMPTS, e);
+                    tokio::time::sleep(Duration::from_millis(500 * attempt as u64)).await;
+                    attempt += 1;
+                }
+                Err(e) => return Err(e),
+            }
+        }
+    }
+
+    /// Release every hold of the session on the cycle lock. A cycle runs once at a time in the service,
+    /// so extra holds are left by a failed release, they're released along to not leak the lock.
+    async fn release_cycle_holds(&self, cycle: Cycle) -> Result<()> {
+        let mut holds = 0;
+        loop {
+            let row = self.client
+                .query_one("SELECT pg_advisory_unlock($1)", &[&cycle.lock_key()])
+                .await?;
+            let unlocked: bool = row.get(0);
+            if !unlocked {
+                break;
+            }
+            holds += 1;
+        }
+
+        match holds {
+            0 => warn!("{:?} cycle lock was not held on release", cycle),
+            1 => {}
+            _ => warn!("Released {} leaked holds of {:?} cycle lock", holds - 1, cycle),
+        }
+        Ok(())
+    }
+
     /// Record that a time period has been successfully mined
     pub async fn record_mining_completed(&self, start_timestamp: i64, end_timestamp: i64, records_found: i32) -> Result<()> {
         let query = r#"
@@ -6,21 +6,26 @@ mod validators;
 mod miner;
 mod transaction;
 mod batch;
+mod queue;
+mod amount;
 
 use anyhow::Result;
 use std::sync::Arc;
 use std::time::Duration;
-use tokio::time::interval;
+use tokio::time::{interval, MissedTickBehavior};
 use tracing::{info, error, warn};
 use alloy::primitives::{Address, U256};
 use std::str::FromStr;
 
 use crate::config::Config;
-use crate::db::Database;
+use crate::db::{Cycle, CycleLock, Database};
 use crate::miner::APIMiner;
 use crate::transaction::ContractClient;
 use crate::batch::{get_batch_usage_report, get_batch_revenue_report};
 use crate::api::create_router;
+use crate::queue::{ReportKind, Submission, TxQueue};
+use crate::amount::scale_decimal;
+use crate::validators::Validator;
 
 async fn initialize_blockchain_client(config: &Config) -> Result<ContractClient> {
     let rpc_url = config.blockchain_rpc_url()?;
@@ -42,6 +47,7 @@ async fn main() -> Result<()> {
     
     // Validate configuration
     config.validate_mining_config()?;
+    let validator = Validator::new(&config.validation)?;
     
     // Initialize logging with configured level
     let log_level = config.service.log_level.parse::<tracing::Level>().unwrap_or(tracing::Level::INFO);
@@ -78,11 +84,11 @@ async fn main() -> Result<()> {
         }
     };
     
-    // Initialize blockchain client if configured
+    // Initialize blockchain client if configured, once for the service lifetime
     let contract_client = match initialize_blockchain_client(&config).await {
         Ok(client) => {
             info!("Blockchain client initialized with wallet: {:?}", client.wallet_address());
-            Some(client)
+            Some(Arc::new(client))
         }
         Err(e) => {
             warn!("Blockchain client not configured - batching will be disabled: {}", e);
@@ -90,8 +96,18 @@ async fn main() -> Result<()> {
         }
     };
     
+    // Start the submission queue, sending the transactions of both report kinds
+    let (tx_queue, submission_handle) = match &contract_client {
+        Some(client) => {
+            let (queue, worker) = TxQueue::start(Arc::clone(client), db.clone(), &config.contract);
+            info!("Submission queue started with capacity {}", config.contract.queue_capacity);
+            (Some(queue), Some(worker))
+        }
+        None => (None, None),
+    };
+    
     // Create API router
-    let router = create_router(db.clone());
+    let router = create_router(db.clone(), tx_queue.clone());
     
     info!("Oracle Service initialized successfully");
     
@@ -100,17 +116,17 @@ async fn main() -> Result<()> {
         let db = db.clone();
         let config = config.clone();
         Some(tokio::spawn(async move {
-            start_mining_task(db, config).await;
+            start_mining_task(db, config, validator).await;
         }))
     } else {
         None
     };
     
-    let batching_handle = if contract_client.is_some() {
+    let batching_handle = if let Some(queue) = tx_queue.clone() {
         let db = db.clone();
         let config = config.clone();
         Some(tokio::spawn(async move {
-            start_batching_task(db, config).await;
+            start_batching_task(db, config, queue).await;
         }))
     } else {
         None
@@ -138,79 +154,134 @@ async fn main() -> Result<()> {
         result = batching_handle.unwrap_or_else(|| tokio::spawn(async {})) => {
             error!("Batching task ended: {:?}", result);
         }
+        result = submission_handle.unwrap_or_else(|| tokio::spawn(async {})) => {
+            error!("Submission worker ended: {:?}", result);
+        }
     }
     
     Ok(())
 }
 
 /// Start the mining task that periodically fetches data from external APIs
-async fn start_mining_task(db: Arc<Database>, config: Config) {
+async fn start_mining_task(db: Arc<Database>, config: Config, validator: Validator) {
     let mining_interval = Duration::from_secs(config.mining.mining_interval_seconds);
     let mut interval = interval(mining_interval);
+    // Ticks missed by a cycle longer than the interval are skipped, not run back to back
+    interval.set_missed_tick_behavior(MissedTickBehavior::Skip);
     
     info!("Starting mining task with interval: {:?}", mining_interval);
     
     loop {
         interval.tick().await;
         
-        info!("Starting mining cycle...");
-        
-        // Determine next time range to mine
-        let (start_time, end_time) = match determine_next_mining_range(db.clone(), &config).await {
-            Ok(Some(range)) => range,
-            Ok(None) => {
-                info!("Mining is caught up with real-time, skipping this cycle");
-                continue;
-            }
-            Err(e) => {
-                error!("Failed to determine next mining range: {}", e);
-                continue;
-            }
+        // Overlapping cycles would mine the same range, the lock is held by one service instance at a time
+        let Some(lock) = acquire_cycle_lock(&db, Cycle::Mining).await else {
+            continue;
         };
         
-        info!("Mining time range: {} to {} (with {}s delay applied)", start_time, end_time, config.mining.mining_delay_seconds);
+        run_mining_cycle(db.clone(), &config, &validator).await;
         
-        match mine_data_with_tracking(db.clone(), &config, start_time, end_time).await {
-            Ok(records_found) => {
-                info!("Mining cycle completed successfully, found {} records", records_found);
-            }
-            Err(e) => {
-                error!("Mining cycle failed: {}", e);
-            }
+        if let Err(e) = lock.release().await {
+            error!("Failed to release mining cycle lock: {}", e);
+        }
+    }
+}
+
+async fn run_mining_cycle(db: Arc<Database>, config: &Config, validator: &Validator) {
+    info!("Starting mining cycle...");
+    
+    // Determine next time range to mine
+    let (start_time, end_time) = match determine_next_mining_range(db.clone(), config).await {
+        Ok(Some(range)) => range,
+        Ok(None) => {
+            info!("Mining is caught up with real-time, skipping this cycle");
+            return;
+        }
+        Err(e) => {
+            error!("Failed to determine next mining range: {}", e);
+            return;
+        }
+    };
+    
+    info!("Mining time range: {} to {} (with {}s delay applied)", start_time, end_time, config.mining.mining_delay_seconds);
+    
+    match mine_data_with_tracking(db, config, validator, start_time, end_time).await {
+        Ok(records_found) => {
+            info!("Mining cycle completed successfully, found {} records", records_found);
+        }
+        Err(e) => {
+            error!("Mining cycle failed: {}", e);
         }
     }
 }
 
-/// Start the batching task that periodically batches and submits data to blockchain
-async fn start_batching_task(db: Arc<Database>, config: Config) {
+/// Start the batching task that periodically batches data and enqueues it for submission to blockchain
+async fn start_batching_task(db: Arc<Database>, config: Config, queue: Arc<TxQueue>) {
     let batch_interval = Duration::from_secs(config.contract.batch_interval_seconds);
     let mut interval = interval(batch_interval);
+    interval.set_missed_tick_behavior(MissedTickBehavior::Skip);
     
     info!("Starting batching task with interval: {:?}", batch_interval);
     
     loop {
         interval.tick().await;
         
-        info!("Starting batching cycle...");
+        // Overlapping cycles would batch the same unsubmitted reports
+        let Some(lock) = acquire_cycle_lock(&db, Cycle::Batching).await else {
+            continue;
+        };
         
-        // Process usage reports
-        match process_usage_reports(db.clone(), &config).await {
-            Ok(()) => {
-                info!("Usage reports processing completed");
-            }
-            Err(e) => {
-                error!("Usage reports processing failed: {}", e);
-            }
-        }
+        run_batching_cycle(db.clone(), &config, &queue).await;
         
-        // Process revenue reports
-        match process_revenue_reports(db.clone(), &config).await {
-            Ok(()) => {
-                info!("Revenue reports processing completed");
-            }
-            Err(e) => {
-                error!("Revenue reports processing failed: {}", e);
-            }
+        // The enqueued reports stay unsubmitted in the database until the worker sends them,
+        // so the lock is held meanwhile, not to let another instance batch them again
+        queue.wait_idle().await;
+        
+        if let Err(e) = lock.release().await {
+            error!("Failed to release batching cycle lock: {}", e);
+        }
+    }
+}
+
+async fn run_batching_cycle(db: Arc<Database>, config: &Config, queue: &TxQueue) {
+    info!("Starting batching cycle...");
+    
+    // Process usage reports
+    match process_usage_reports(db.clone(), config, queue).await {
+        Ok(()) => {
+            info!("Usage reports processing completed");
+        }
+        Err(e) => {
+            error!("Usage reports processing failed: {}", e);
+        }
+    }
+    
+    // Process revenue reports
+    match process_revenue_reports(db, config, queue).await {
+        Ok(()) => {
+            info!("Revenue reports processing completed");
+        }
+        Err(e) => {
+            error!("Revenue reports processing failed: {}", e);
+        }
+    }
+    
+    let status = queue.status();
+    info!("Submission queue depth: {}/{}, submitted: {}, failed: {}",
+        status.depth, status.capacity, status.submitted, status.failed);
+}
+
+/// Take the cycle lock, `None` to skip the tick when the cycle is running or the lock can't be taken
+async fn acquire_cycle_lock(db: &Arc<Database>, cycle: Cycle) -> Option<CycleLock> {
+    match db.try_lock_cycle(cycle).await {
+        Ok(Some(lock)) => Some(lock),
+        Ok(None) => {
+            info!("{:?} cycle is still running in another instance, skipping this tick", cycle);
+            None
+        }
+        Err(e) => {
+            error!("Failed to take {:?} cycle lock: {}", cycle, e);
+            None
         }
     }
 }
@@ -231,7 +302,13 @@ async fn start_api_server(router: axum::Router, addr: std::net::SocketAddr) {
 }
 
 /// Mine data from external API with state tracking
-async fn mine_data_with_tracking(db: Arc<Database>, config: &Config, start_at: i64, end_at: i64) -> Result<i32> {
+async fn mine_data_with_tracking(
+    db: Arc<Database>,
+    config: &Config,
+    validator: &Validator,
+    start_at: i64,
+    end_at: i64,
+) -> Result<i32> {
     let (api_url, api_key) = match (config.mining_api_url(), config.mining_api_key()) {
         (Ok(url), Ok(key)) => (url, key),
         (Err(e), _) | (_, Err(e)) => return Err(e),
@@ -244,19 +321,16 @@ async fn mine_data_with_tracking(db: Arc<Database>, config: &Config, start_at: i
     let backend_data = api_miner.fetch_data(start_at, end_at).await?;
     info!("Fetched {} data items from API", backend_data.len());
     
+    // Timestamps are bounded relative to the mined window, which is historical on bootstrap and catch-up
     let mut records_inserted = 0;
     for data in backend_data {
-        match crate::validators::validate_backend_data(&data) {
-            Ok(valid) => {
-                if valid {
-                    db.insert_backend_data(&data).await?;
-                    records_inserted += 1;
-                } else {
-                    warn!("Invalid data rejected: {:?}", data);
-                }
+        match validator.validate(&data, end_at) {
+            Ok(()) => {
+                db.insert_backend_data(&data).await?;
+                records_inserted += 1;
             }
-            Err(e) => {
-                error!("Failed to validate backend data: {}", e);
+            Err(reason) => {
+                warn!("Data of artifact {} at {} rejected: {}", data.artifact_address, data.timestamp, reason);
             }
         }
     }
@@ -316,25 +390,62 @@ async fn determine_next_mining_range(db: Arc<Database>, config: &Config) -> Resu
 }
 
 
-/// Process usage reports and submit to blockchain
-async fn process_usage_reports(db: Arc<Database>, config: &Config) -> Result<()> {
-    let (rpc_url, private_key, contract_address, chain_id) = match (
-        config.blockchain_rpc_url(),
-        config.blockchain_private_key(),
-        config.blockchain_contract_address(),
-        config.blockchain_chain_id(),
-    ) {
-        (Ok(url), Ok(key), Ok(addr), Ok(id)) => (url, key, addr, id),
-        (Err(e), _, _, _) | (_, Err(e), _, _) | (_, _, Err(e), _) | (_, _, _, Err(e)) => return Err(e),
+/// Convert the reports batch for submission, scaling the values by the configured decimals.
+/// Reports with an invalid address or value are quarantined with the reason, not to abort the batch.
+async fn build_submission(
+    db: &Database,
+    kind: ReportKind,
+    ids: Vec<i32>,
+    addresses: Vec<String>,
+    values: Vec<String>,
+    timestamps: Vec<i64>,
+    config: &Config,
+) -> Result<Submission> {
+    let decimals = match kind {
+        ReportKind::Usage => config.contract.usage_decimals,
+        ReportKind::Revenue => config.contract.revenue_decimals,
     };
     
-    let contract_address = Address::from_str(&contract_address)?;
-    let contract_client = ContractClient::new(
-        rpc_url,
-        private_key,
-        contract_address,
-        chain_id,
-    ).await?;
+    let mut submission = Submission {
+        kind,
+        artifacts: Vec::with_capacity(ids.len()),
+        values: Vec::with_capacity(ids.len()),
+        timestamps: Vec::with_capacity(ids.len()),
+        ids: Vec::with_capacity(ids.len()),
+    };
+    
+    for (((id, address), value), timestamp) in ids.into_iter().zip(addresses).zip(values).zip(timestamps) {
+        let converted = Address::from_str(&address)
+            .map_err(|e| format!("Invalid artifact address '{}': {}", address, e))
+            .and_then(|artifact| Ok((artifact, scale_decimal(&value, decimals, config.contract.rounding)?)));
+        
+        match converted {
+            Ok((artifact, value)) => {
+                submission.artifacts.push(artifact);
+                submission.values.push(value);
+                submission.timestamps.push(U256::from(timestamp));
+                submission.ids.push(id);
+            }
+            Err(reason) => {
+                warn!("Quarantining {:?} report {}: {}", kind, id, reason);
+                match kind {
+                    ReportKind::Usage => db.quarantine_usage_report(id, &reason).await?,
+                    ReportKind::Revenue => db.quarantine_revenue_report(id, &reason).await?,
+                }
+            }
+        }
+    }
+    
+    Ok(submission)
+}
+
+/// Process usage reports and enqueue them for submission to blockchain
+async fn process_usage_reports(db: Arc<Database>, config: &Config, queue: &TxQueue) -> Result<()> {
+    // The queued batch holds the first unsubmitted reports, they'd be batched again
+    if queue.is_pending(ReportKind::Usage) {
+        info!("Usage reports batch is still queued, skipping this cycle");
+        return Ok(());
+    }
     
     let (batch, ids) = get_batch_usage_report(&*db, config.contract.batch_size).await?;
     
@@ -345,51 +456,35 @@ async fn process_usage_reports(db: Arc<Database>, config: &Config) -> Result<()>
     
     info!("Processing {} usage reports", batch.artifact_address.len());
     
-    // Convert addresses
-    let artifacts: Result<Vec<Address>, _> = batch.artifact_address
-        .iter()
-        .map(|addr| Address::from_str(addr))
-        .collect();
-    let artifacts = artifacts?;
-    
-    // Convert to U256
-    let usages: Result<Vec<U256>, _> = batch.usage.into_iter()
-        .map(|s| U256::from_str(&s))
-        .collect();
-    let usages = usages?;
-    let timestamps: Vec<U256> = batch.timestamp.into_iter().map(U256::from).collect();
-    
-    // Submit to blockchain
-    let tx_hash = contract_client.batch_report_artifact_usage(artifacts, usages, timestamps).await?;
-    info!("Usage reports submitted to blockchain with tx hash: {:?}", tx_hash);
-    
-    // Mark reports as submitted in database
-    let id_count = ids.len();
-    db.mark_usage_reports_submitted(ids).await?;
-    info!("Marked {} usage reports as submitted", id_count);
+    let submission = build_submission(
+        &db,
+        ReportKind::Usage,
+        ids,
+        batch.artifact_address,
+        batch.usage,
+        batch.timestamp,
+        config,
+    ).await?;
+    
+    if submission.ids.is_empty() {
+        info!("All usage reports of the batch are quarantined");
+        return Ok(());
+    }
+    
+    // Submitted and marked as submitted by the queue worker
+    queue.enqueue(submission).await?;
+    info!("Usage reports enqueued for submission");
     
     Ok(())
 }
 
-/// Process revenue reports and submit to blockchain
-async fn process_revenue_reports(db: Arc<Database>, config: &Config) -> Result<()> {
-    let (rpc_url, private_key, contract_address, chain_id) = match (
-        config.blockchain_rpc_url(),
-        config.blockchain_private_key(),
-        config.blockchain_contract_address(),
-        config.blockchain_chain_id(),
-    ) {
-        (Ok(url), Ok(key), Ok(addr), Ok(id)) => (url, key, addr, id),
-        (Err(e), _, _, _) | (_, Err(e), _, _) | (_, _, Err(e), _) | (_, _, _, Err(e)) => return Err(e),
-    };
-    
-    let contract_address = Address::from_str(&contract_address)?;
-    let contract_client = ContractClient::new(
-        rpc_url,
-        private_key,
-        contract_address,
-        chain_id,
-    ).await?;
+/// Process revenue reports and enqueue them for submission to blockchain
+async fn process_revenue_reports(db: Arc<Database>, config: &Config, queue: &TxQueue) -> Result<()> {
+    // The queued batch holds the first unsubmitted reports, they'd be batched again
+    if queue.is_pending(ReportKind::Revenue) {
+        info!("Revenue reports batch is still queued, skipping this cycle");
+        return Ok(());
+    }
     
     let (batch, ids) = get_batch_revenue_report(&*db, config.contract.batch_size).await?;
     
@@ -400,28 +495,24 @@ async fn process_revenue_reports(db: Arc<Database>, config: &Config) -> Result<(
     
     info!("Processing {} revenue reports", batch.artifact_address.len());
     
-    // Convert addresses
-    let artifacts: Result<Vec<Address>, _> = batch.artifact_address
-        .iter()
-        .map(|addr| Address::from_str(addr))
-        .collect();
-    let artifacts = artifacts?;
-    
-    // Convert to U256
-    let revenues: Result<Vec<U256>, _> = batch.revenue.into_iter()
-        .map(|s| U256::from_str(&s))
-        .collect();
-    let revenues = revenues?;
-    let timestamps: Vec<U256> = batch.timestamp.into_iter().map(U256::from).collect();
-    
-    // Submit to blockchain
-    let tx_hash = contract_client.batch_report_artifact_revenue(artifacts, revenues, timestamps).await?;
-    info!("Revenue reports submitted to blockchain with tx hash: {:?}", tx_hash);
-    
-    // Mark reports as submitted in database
-    let id_count = ids.len();
-    db.mark_revenue_reports_submitted(ids).await?;
-    info!("Marked {} revenue reports as submitted", id_count);
+    let submission = build_submission(
+        &db,
+        ReportKind::Revenue,
+        ids,
+        batch.artifact_address,
+        batch.revenue,
+        batch.timestamp,
+        config,
+    ).await?;
+    
+    if submission.ids.is_empty() {
+        info!("All revenue reports of the batch are quarantined");
+        return Ok(());
+    }
+    
+    // Submitted and marked as submitted by the queue worker
+    queue.enqueue(submission).await?;
+    info!("Revenue reports enqueued for submission");
     
     Ok(())
 }
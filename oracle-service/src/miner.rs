@@ -93,7 +93,8 @@ impl APIMiner {
                         info!("Empty array received from backend API");
                         break;
                     }
-                    serde_json::from_value(Value::Array(arr))?
+                    // Parsed from the text, the value literals would be rounded through the `Value` numbers
+                    serde_json::from_str(&response_text)?
                 },
                 Value::Null => {
                     info!("Null response received from backend API");
@@ -0,0 +1,307 @@
+use anyhow::Result;
+use alloy::primitives::{Address, U256};
+use serde::Serialize;
+use std::collections::HashSet;
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tokio::sync::{mpsc, Notify};
+use tokio::task::JoinHandle;
+use tokio::time::{interval, MissedTickBehavior};
+use tracing::{info, error, warn};
+
+use crate::config::ContractConfig;
+use crate::db::Database;
+use crate::transaction::ContractClient;
+
+/// Kind of the reports batch
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ReportKind {
+    Usage,
+    Revenue,
+}
+
+/// Reports batch waiting for submission to the contract
+#[derive(Debug)]
+pub struct Submission {
+    pub kind: ReportKind,
+    pub artifacts: Vec<Address>,
+    /// Usages or revenues, by the kind
+    pub values: Vec<U256>,
+    pub timestamps: Vec<U256>,
+    /// Database IDs of the reports, marked as submitted once the transaction is sent
+    pub ids: Vec<i32>,
+}
+
+/// Queue state, served by the API
+#[derive(Debug, Clone, Serialize)]
+pub struct QueueStatus {
+    /// Batches waiting for submission, the one being submitted included
+    pub depth: usize,
+    pub capacity: usize,
+    pub submitted: u64,
+    pub failed: u64,
+}
+
+/// Submission worker timings
+struct Pacing {
+    submission_interval: Duration,
+    nonce_resync_interval: Duration,
+}
+
+/// Single transaction queue of the oracle wallet, drained by one worker at the configured pace.
+/// Both the usage and revenue batches go through it, so the transactions of the single key
+/// are sent one at a time with nonces tracked by the worker, independently of the batching timer.
+pub struct TxQueue {
+    sender: mpsc::Sender<Submission>,
+    capacity: usize,
+    depth: AtomicUsize,
+    /// Kinds with a batch queued or being submitted, their reports are still unsubmitted in the database
+    pending_kinds: Mutex<HashSet<ReportKind>>,
+    submitted: AtomicU64,
+    failed: AtomicU64,
+    /// Notified when the last queued batch is complete
+    idle: Notify,
+}
+
+impl TxQueue {
+    /// Create the queue and spawn its submission worker, sending with the shared client
+    pub fn start(
+        client: Arc<ContractClient>,
+        db: Arc<Database>,
+        config: &ContractConfig,
+    ) -> (Arc<Self>, JoinHandle<()>) {
+        let (queue, receiver) = Self::new(config.queue_capacity);
+        let queue = Arc::new(queue);
+
+        let pacing = Pacing {
+            submission_interval: Duration::from_millis(config.submission_interval_ms),
+            nonce_resync_interval: Duration::from_secs(config.nonce_resync_interval_seconds),
+        };
+        let worker = tokio::spawn(Arc::clone(&queue).run(receiver, client, db, pacing));
+
+        (queue, worker)
+    }
+
+    fn new(capacity: usize) -> (Self, mpsc::Receiver<Submission>) {
+        let capacity = capacity.max(1);
+        let (sender, receiver) = mpsc::channel(capacity);
+
+        let queue = Self {
+            sender,
+            capacity,
+            depth: AtomicUsize::new(0),
+            pending_kinds: Mutex::new(HashSet::new()),
+            submitted: AtomicU64::new(0),
+            failed: AtomicU64::new(0),
+            idle: Notify::new(),
+        };
+
+        (queue, receiver)
+    }
+
+    /// Whether a batch of the kind is queued or being submitted.
+    /// A new batch of the kind would hold the same unsubmitted reports, so it's not built meanwhile.
+    pub fn is_pending(&self, kind: ReportKind) -> bool {
+        self.pending_kinds.lock().unwrap().contains(&kind)
+    }
+
+    /// Enqueue the batch, waits while the queue is full
+    pub async fn enqueue(&self, submission: Submission) -> Result<()> {
+        let kind = submission.kind;
+        if !self.pending_kinds.lock().unwrap().insert(kind) {
+            return Err(anyhow::anyhow!("A {:?} reports batch is already queued", kind));
+        }
+        self.depth.fetch_add(1, Ordering::SeqCst);
+
+        if self.sender.send(submission).await.is_err() {
+            self.complete(kind);
+            return Err(anyhow::anyhow!("Submission worker is stopped"));
+        }
+
+        Ok(())
+    }
+
+    /// Wait until all the queued batches are submitted or failed
+    pub async fn wait_idle(&self) {
+        loop {
+            // Registered before the depth check, so the notification in between isn't missed
+            let idle = self.idle.notified();
+            if self.depth.load(Ordering::SeqCst) == 0 {
+                return;
+            }
+            idle.await;
+        }
+    }
+
+    pub fn status(&self) -> QueueStatus {
+        QueueStatus {
+            depth: self.depth.load(Ordering::SeqCst),
+            capacity: self.capacity,
+            submitted: self.submitted.load(Ordering::SeqCst),
+            failed: self.failed.load(Ordering::SeqCst),
+        }
+    }
+
+    /// Submit the queued batches one at a time, at most one per submission interval
+    async fn run(
+        self: Arc<Self>,
+        mut receiver: mpsc::Receiver<Submission>,
+        client: Arc<ContractClient>,
+        db: Arc<Database>,
+        pacing: Pacing,
+    ) {
+        info!("Starting submission worker with interval: {:?}", pacing.submission_interval);
+
+        let mut pace = interval(pacing.submission_interval.max(Duration::from_millis(1)));
+        pace.set_missed_tick_behavior(MissedTickBehavior::Delay);
+        // Nonce of the next transaction, re-synced from the node after a failed submission
+        let mut next_nonce: Option<u64> = None;
+        let mut nonce_synced_at = Instant::now();
+
+        while let Some(submission) = receiver.recv().await {
+            pace.tick().await;
+
+            // A sent transaction which never mines would stall all the later ones at the local nonce,
+            // the node's pending nonce steps back to it once the transaction is dropped
+            if nonce_synced_at.elapsed() >= pacing.nonce_resync_interval {
+                next_nonce = None;
+            }
+            if next_nonce.is_none() {
+                nonce_synced_at = Instant::now();
+            }
+
+            let kind = submission.kind;
+            match self.submit(&client, &db, submission, &mut next_nonce).await {
+                Ok(()) => {
+                    self.submitted.fetch_add(1, Ordering::SeqCst);
+                }
+                Err(e) => {
+                    // The reports stay unsubmitted, so they're batched again on the next cycle
+                    self.failed.fetch_add(1, Ordering::SeqCst);
+                    next_nonce = None;
+                    error!("{:?} reports submission failed: {}", kind, e);
+                }
+            }
+
+            self.complete(kind);
+        }
+
+        warn!("Submission queue closed, worker stopped");
+    }
+
+    /// Batch of the kind left the queue, submitted, failed or not enqueued
+    fn complete(&self, kind: ReportKind) {
+        self.pending_kinds.lock().unwrap().remove(&kind);
+        if self.depth.fetch_sub(1, Ordering::SeqCst) == 1 {
+            self.idle.notify_waiters();
+        }
+    }
+
+    async fn submit(
+        &self,
+        client: &ContractClient,
+        db: &Database,
+        submission: Submission,
+        next_nonce: &mut Option<u64>,
+    ) -> Result<()> {
+        let nonce = match *next_nonce {
+            Some(nonce) => nonce,
+            None => client.pending_nonce().await?,
+        };
+
+        let Submission { kind, artifacts, values, timestamps, ids } = submission;
+        let tx_hash = match kind {
+            ReportKind::Usage => client.batch_report_artifact_usage(artifacts, values, timestamps, Some(nonce)).await?,
+            ReportKind::Revenue => client.batch_report_artifact_revenue(artifacts, values, timestamps, Some(nonce)).await?,
+        };
+        *next_nonce = Some(nonce + 1);
+        info!("{:?} reports submitted to blockchain with tx hash: {:?}, nonce: {}", kind, tx_hash, nonce);
+
+        // Mark reports as submitted in database
+        let id_count = ids.len();
+        match kind {
+            ReportKind::Usage => db.mark_usage_reports_submitted(ids).await?,
+            ReportKind::Revenue => db.mark_revenue_reports_submitted(ids).await?,
+        }
+        info!("Marked {} {:?} reports as submitted", id_count, kind);
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn submission(kind: ReportKind) -> Submission {
+        Submission {
+            kind,
+            artifacts: vec![Address::ZERO],
+            values: vec![U256::from(1)],
+            timestamps: vec![U256::from(1)],
+            ids: vec![1],
+        }
+    }
+
+    #[tokio::test]
+    async fn test_enqueue_tracks_pending_kinds_and_depth() {
+        let (queue, mut receiver) = TxQueue::new(4);
+
+        queue.enqueue(submission(ReportKind::Usage)).await.unwrap();
+        assert!(queue.is_pending(ReportKind::Usage));
+        assert!(!queue.is_pending(ReportKind::Revenue));
+        assert_eq!(queue.status().depth, 1);
+
+        // One batch of a kind at a time, its reports would be batched again
+        assert!(queue.enqueue(submission(ReportKind::Usage)).await.is_err());
+        assert_eq!(queue.status().depth, 1);
+
+        queue.enqueue(submission(ReportKind::Revenue)).await.unwrap();
+        assert_eq!(queue.status().depth, 2);
+
+        let received = receiver.recv().await.unwrap();
+        // Depth includes the batch being submitted until it's complete
+        assert_eq!(queue.status().depth, 2);
+        queue.complete(received.kind);
+        assert!(!queue.is_pending(ReportKind::Usage));
+        assert_eq!(queue.status().depth, 1);
+
+        queue.enqueue(submission(ReportKind::Usage)).await.unwrap();
+        assert_eq!(queue.status().depth, 2);
+    }
+
+    #[tokio::test]
+    async fn test_wait_idle() {
+        let (queue, mut receiver) = TxQueue::new(4);
+        let queue = Arc::new(queue);
+        queue.wait_idle().await;
+
+        queue.enqueue(submission(ReportKind::Usage)).await.unwrap();
+        queue.enqueue(submission(ReportKind::Revenue)).await.unwrap();
+
+        let waiter = tokio::spawn({
+            let queue = Arc::clone(&queue);
+            async move { queue.wait_idle().await }
+        });
+        tokio::task::yield_now().await;
+
+        queue.complete(receiver.recv().await.unwrap().kind);
+        tokio::task::yield_now().await;
+        assert!(!waiter.is_finished());
+
+        queue.complete(receiver.recv().await.unwrap().kind);
+        tokio::time::timeout(Duration::from_secs(1), waiter).await.unwrap().unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_enqueue_without_worker() {
+        let (queue, receiver) = TxQueue::new(0);
+        assert_eq!(queue.status().capacity, 1);
+        drop(receiver);
+
+        assert!(queue.enqueue(submission(ReportKind::Revenue)).await.is_err());
+        assert!(!queue.is_pending(ReportKind::Revenue));
+        assert_eq!(queue.status().depth, 0);
+    }
+}
@@ -1,4 +1,4 @@
-use alloy_provider::WalletProvider;
+use alloy_provider::{Provider, WalletProvider};
 use anyhow::Result;
 use tracing::info;
 use alloy::{
@@ -92,11 +92,13 @@ impl ContractClient {
 
     /// Submit batch revenue reports to the blockchain
     /// This calls the batchReportArtifactRevenue function on the ArtifactManager contract
+    /// The nonce is filled by the provider if not given
     pub async fn batch_report_artifact_revenue(
         &self,
         artifacts: Vec<Address>,
         revenues: Vec<U256>,
         timestamps: Vec<U256>,
+        nonce: Option<u64>,
     ) -> Result<alloy::primitives::TxHash> {
         info!(
             "ContractClient: Submitting batch revenue report for {} artifacts",
@@ -110,7 +112,10 @@ impl ContractClient {
 
         // Create contract instance and call the function
         let contract = ArtifactManager::new(self.contract_address, &self.provider);
-        let call = contract.batchReportArtifactRevenue(artifacts, revenues, timestamps);
+        let mut call = contract.batchReportArtifactRevenue(artifacts, revenues, timestamps);
+        if let Some(nonce) = nonce {
+            call = call.nonce(nonce);
+        }
         let pending_tx = call.send().await?;
         let tx_hash = *pending_tx.tx_hash();
         
@@ -119,11 +124,13 @@ impl ContractClient {
     }
 
     /// Submit batch usage reports to the blockchain
+    /// The nonce is filled by the provider if not given
     pub async fn batch_report_artifact_usage(
         &self,
         artifacts: Vec<Address>,
         usages: Vec<U256>,
         timestamps: Vec<U256>,
+        nonce: Option<u64>,
     ) -> Result<alloy::primitives::TxHash> {
         info!(
             "ContractClient: Submitting batch usage report for {} artifacts",
@@ -137,7 +144,10 @@ impl ContractClient {
 
         // Create contract instance and call the function
         let contract = ArtifactManager::new(self.contract_address, &self.provider);
-        let call = contract.batchReportArtifactUsage(artifacts, usages, timestamps);
+        let mut call = contract.batchReportArtifactUsage(artifacts, usages, timestamps);
+        if let Some(nonce) = nonce {
+            call = call.nonce(nonce);
+        }
         let pending_tx = call.send().await?;
         let tx_hash = *pending_tx.tx_hash();
         
@@ -145,6 +155,15 @@ impl ContractClient {
         Ok(tx_hash)
     }
 
+    /// Pending nonce of the wallet, the nonce of its next transaction
+    pub async fn pending_nonce(&self) -> Result<u64> {
+        let nonce = self.provider
+            .get_transaction_count(self.wallet_address())
+            .pending()
+            .await?;
+        Ok(nonce)
+    }
+
     /// Get the contract address
     pub fn _contract_address(&self) -> Address {
         self.contract_address
@@ -248,7 +267,7 @@ mod tests {
         let revenues = vec![U256::from(100)];
         let timestamp = Utc::now().timestamp() - 60;
         let timestamps = vec![U256::from(timestamp)];
-        match client.batch_report_artifact_revenue(artifacts, revenues, timestamps).await {
+        match client.batch_report_artifact_revenue(artifacts, revenues, timestamps, None).await {
             Ok(tx_hash) => {
                 info!("ContractClient: Batch revenue report submitted with tx hash: {:?}", tx_hash);
                 assert!(!tx_hash.is_zero());
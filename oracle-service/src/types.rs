@@ -1,4 +1,5 @@
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Deserializer, Serialize};
+use serde_json::value::RawValue;
 
 // These structs would be used to store received data from the backend
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -17,18 +18,51 @@ pub struct BackendApiResponse {
     pub revenue: ValueData,
 }
 
+// Integer or fractional amount, kept as the decimal string it was received as
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ValueData {
-    pub value: i64,
+    #[serde(deserialize_with = "decimal_literal")]
+    pub value: String,
+}
+
+/// JSON number literal or numeric string as is, parsing the number as `f64` would round
+/// the amounts with more than ~15 significant digits before they're scaled
+fn decimal_literal<'de, D: Deserializer<'de>>(deserializer: D) -> Result<String, D::Error> {
+    let raw = Box::<RawValue>::deserialize(deserializer)?;
+    let literal = raw.get();
+
+    if literal.starts_with('"') {
+        serde_json::from_str::<String>(literal).map_err(serde::de::Error::custom)
+    } else if serde_json::from_str::<serde_json::Number>(literal).is_ok() {
+        Ok(literal.to_string())
+    } else {
+        Err(serde::de::Error::custom(format!("expected a decimal number, got {}", literal)))
+    }
 }
 
 impl BackendApiResponse {
     pub fn to_backend_data(&self, timestamp: i64) -> BackendData {
         BackendData {
             artifact_address: self.art_address.clone(),
-            revenue: self.revenue.value.to_string(),
-            usage: self.usage.value.to_string(),
+            revenue: self.revenue.value.clone(),
+            usage: self.usage.value.clone(),
             timestamp,
         }
     }
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_value_literal_precision() {
+        let json = r#"[{"art_address": "0x1111111111111111111111111111111111111111",
+            "usage": {"value": 12345678901234567.891234}, "revenue": {"value": "0.000000000000000001"}}]"#;
+        let responses: Vec<BackendApiResponse> = serde_json::from_str(json).unwrap();
+        let data = responses[0].to_backend_data(100);
+
+        assert_eq!(data.usage, "12345678901234567.891234");
+        assert_eq!(data.revenue, "0.000000000000000001");
+        assert!(serde_json::from_str::<ValueData>(r#"{"value": true}"#).is_err());
+    }
+}
@@ -1,36 +1,182 @@
+use crate::amount::{scale_decimal, RoundingPolicy};
+use crate::config::ValidationConfig;
 use crate::types::{BackendData};
 use alloy::primitives::Address;
+use anyhow::Result;
+use std::collections::HashSet;
 use std::str::FromStr;
+use thiserror::Error;
 
+/// Reason the mined data is dropped
+#[derive(Debug, Clone, PartialEq, Error)]
+pub enum Rejection {
+    #[error("{0} is empty")]
+    Empty(&'static str),
+    #[error("Artifact address '{0}' is not valid")]
+    InvalidAddress(String),
+    #[error("Artifact address {0} is not in the allowlist")]
+    AddressNotAllowed(Address),
+    #[error("Timestamp {0} is less than 0")]
+    NegativeTimestamp(i64),
+    #[error("Timestamp {timestamp} is older than the max age of {max_age_seconds}s before the mined window end")]
+    TooOld { timestamp: i64, max_age_seconds: u64 },
+    #[error("Timestamp {timestamp} is more than {max_future_seconds}s after the mined window end")]
+    InFuture { timestamp: i64, max_future_seconds: u64 },
+    #[error("{field} is not valid: {reason}")]
+    InvalidValue { field: &'static str, reason: String },
+    #[error("{field} {value} is out of the range {}", range(.min, .max))]
+    OutOfRange { field: &'static str, value: String, min: Option<f64>, max: Option<f64> },
+}
 
-pub fn validate_backend_data(revenue_data: &BackendData) -> Result<bool, String> {
+/// Validation rules of the mined data, built from the configured rule set.
+/// The basic checks (non-empty fields, valid address, non-negative timestamp and values) always apply.
+#[derive(Debug, Clone, Default)]
+pub struct Validator {
+    config: ValidationConfig,
+    /// Empty allows any artifact address
+    allowed_artifacts: HashSet<Address>,
+}
 
-    // Non-empty checks
+impl Validator {
+    pub fn new(config: &ValidationConfig) -> Result<Self> {
+        let allowed_artifacts = config.allowed_artifacts
+            .iter()
+            .map(|address| Address::from_str(address)
+                .map_err(|e| anyhow::anyhow!("Invalid allowed artifact address '{}': {}", address, e)))
+            .collect::<Result<HashSet<_>>>()?;
 
-    if revenue_data.revenue.is_empty() {
-        return Err("Revenue is empty".to_string());
+        Ok(Self {
+            config: config.clone(),
+            allowed_artifacts,
+        })
     }
-    if revenue_data.usage.is_empty() {
-        return Err("Usage is empty".to_string());
+
+    /// Check the data against the rules. The timestamp age and skew are measured from `reference`,
+    /// the end of the mined window as Unix timestamp in seconds, not the wall clock.
+    pub fn validate(&self, data: &BackendData, reference: i64) -> Result<(), Rejection> {
+        // Non-empty checks
+        if data.revenue.is_empty() {
+            return Err(Rejection::Empty("Revenue"));
+        }
+        if data.usage.is_empty() {
+            return Err(Rejection::Empty("Usage"));
+        }
+        if data.artifact_address.is_empty() {
+            return Err(Rejection::Empty("Artifact address"));
+        }
+
+        // Validity checks
+        let address = Address::from_str(&data.artifact_address)
+            .map_err(|_| Rejection::InvalidAddress(data.artifact_address.clone()))?;
+        if !self.allowed_artifacts.is_empty() && !self.allowed_artifacts.contains(&address) {
+            return Err(Rejection::AddressNotAllowed(address));
+        }
+
+        if data.timestamp < 0 {
+            return Err(Rejection::NegativeTimestamp(data.timestamp));
+        }
+        if let Some(max_age_seconds) = self.config.max_age_seconds {
+            if reference.saturating_sub(data.timestamp) > max_age_seconds as i64 {
+                return Err(Rejection::TooOld { timestamp: data.timestamp, max_age_seconds });
+            }
+        }
+        if let Some(max_future_seconds) = self.config.max_future_seconds {
+            if data.timestamp.saturating_sub(reference) > max_future_seconds as i64 {
+                return Err(Rejection::InFuture { timestamp: data.timestamp, max_future_seconds });
+            }
+        }
+
+        // Non-negative decimal numbers, scaled and rounded on submission
+        check_value("Revenue", &data.revenue, self.config.min_revenue, self.config.max_revenue)?;
+        check_value("Usage", &data.usage, self.config.min_usage, self.config.max_usage)?;
+
+        Ok(())
     }
-    if revenue_data.artifact_address.is_empty() {
-        return Err("Artifact address is empty".to_string());
+}
+
+fn range(min: &Option<f64>, max: &Option<f64>) -> String {
+    let bound = |bound: &Option<f64>, unbounded: &str| bound.map_or(unbounded.to_string(), |bound| bound.to_string());
+    format!("[{}, {}]", bound(min, "-inf"), bound(max, "inf"))
+}
+
+fn check_value(field: &'static str, value: &str, min: Option<f64>, max: Option<f64>) -> Result<(), Rejection> {
+    scale_decimal(value, 0, RoundingPolicy::Floor)
+        .map_err(|reason| Rejection::InvalidValue { field, reason })?;
+
+    if min.is_none() && max.is_none() {
+        return Ok(());
     }
+    // Bounds are data-quality thresholds, the float precision is enough for them
+    let number = value.trim().parse::<f64>()
+        .map_err(|e| Rejection::InvalidValue { field, reason: e.to_string() })?;
+    if min.is_some_and(|min| number < min) || max.is_some_and(|max| number > max) {
+        return Err(Rejection::OutOfRange { field, value: value.to_string(), min, max });
+    }
+
+    Ok(())
+}
 
-    // Validity checks
-    // check address is valid
-    if Address::from_str(&revenue_data.artifact_address).is_err() {
-        return Err("Artifact address is not valid".to_string());
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ARTIFACT: &str = "0x1111111111111111111111111111111111111111";
+
+    fn data(usage: &str, timestamp: i64) -> BackendData {
+        BackendData {
+            artifact_address: ARTIFACT.to_string(),
+            revenue: "10.5".to_string(),
+            usage: usage.to_string(),
+            timestamp,
+        }
     }
-    if revenue_data.timestamp < 0 {
-        return Err("Timestamp is less than 0".to_string());
+
+    #[test]
+    fn test_default_rules() {
+        let validator = Validator::default();
+
+        assert_eq!(validator.validate(&data("3", 100), 1000), Ok(()));
+        assert_eq!(validator.validate(&data("", 100), 1000), Err(Rejection::Empty("Usage")));
+        assert_eq!(validator.validate(&data("3", -1), 1000), Err(Rejection::NegativeTimestamp(-1)));
+        assert!(matches!(
+            validator.validate(&data("-3", 100), 1000),
+            Err(Rejection::InvalidValue { field: "Usage", .. })
+        ));
     }
-    if revenue_data.revenue.parse::<i64>().unwrap() < 0 {
-        return Err("Revenue is less than 0".to_string());
+
+    #[test]
+    fn test_configured_rules() {
+        let validator = Validator::new(&ValidationConfig {
+            min_usage: Some(1.0),
+            max_usage: Some(100.0),
+            max_age_seconds: Some(3600),
+            max_future_seconds: Some(60),
+            allowed_artifacts: vec![ARTIFACT.to_string()],
+            ..Default::default()
+        }).unwrap();
+        // End of the mined window
+        let now = 10_000;
+
+        assert_eq!(validator.validate(&data("50", now), now), Ok(()));
+        assert_eq!(
+            validator.validate(&data("0.5", now), now).unwrap_err().to_string(),
+            "Usage 0.5 is out of the range [1, 100]"
+        );
+        assert!(matches!(validator.validate(&data("101", now), now), Err(Rejection::OutOfRange { .. })));
+        assert!(matches!(validator.validate(&data("50", now - 3601), now), Err(Rejection::TooOld { .. })));
+        assert!(matches!(validator.validate(&data("50", now + 61), now), Err(Rejection::InFuture { .. })));
+
+        let mut other = data("50", now);
+        other.artifact_address = "0x2222222222222222222222222222222222222222".to_string();
+        assert!(matches!(validator.validate(&other, now), Err(Rejection::AddressNotAllowed(_))));
     }
-    if revenue_data.usage.parse::<i64>().unwrap() < 0 {
-        return Err("Usage is less than 0".to_string());
+
+    #[test]
+    fn test_invalid_allowlist() {
+        let config = ValidationConfig {
+            allowed_artifacts: vec!["0x123".to_string()],
+            ..Default::default()
+        };
+        assert!(Validator::new(&config).is_err());
     }
-    
-    Ok(true)
 }
@@ -0,0 +1,10 @@
+//! Outbound RPC limits, shared by the services of the stack
+//!
+//! - `rate_limit`: token bucket of the outbound requests, applied as a transport layer of the HTTP RPC provider
+//! - `logs_range`: block ranges of `eth_getLogs` requests, narrowed on the endpoint range limit rejections
+
+pub mod logs_range;
+pub mod rate_limit;
+
+pub use logs_range::{LogsChunkSize, is_logs_range_limit_error};
+pub use rate_limit::{RpcRateLimitLayer, RpcRateLimitService, RpcRateLimiter};
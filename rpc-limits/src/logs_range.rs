@@ -0,0 +1,150 @@
+//! Block ranges of `eth_getLogs` requests, adapted to the range and results limits of the endpoint:
+//! a request rejected as a too wide range or over the results limit is narrowed and sent again
+
+/// Accepted requests in a row after which the chunk grows back, so a chunk narrowed
+/// right at the endpoint limit isn't widened over it again by the next request
+pub const GROW_AFTER_ACCEPTED: u32 = 4;
+
+/// Provider rejections of an `eth_getLogs` range too wide or with too many results,
+/// the messages vary by provider, in lowercase
+pub const LOGS_RANGE_LIMIT_MARKERS: &[&str] = &[
+    // Infura, Alchemy, geth based nodes: "query returned more than 10000 results"
+    "query returned more than",
+    // Alchemy: "Log response size exceeded. You can make eth_getLogs requests with up to a 2K block range"
+    "log response size exceeded",
+    // QuickNode: "eth_getLogs is limited to a 10,000 range"
+    "eth_getlogs is limited to",
+    // Chainstack, Erigon: "exceed maximum block range: 50000", "query exceeds max block range 1000"
+    "exceed maximum block range",
+    "exceeds max block range",
+    // Ankr, BlastAPI, public endpoints
+    "block range is too wide",
+    "block range is too large",
+    "block range too large",
+    "query exceeds max results",
+];
+
+/// Whether the error message is an endpoint rejection of a logs request range or results count
+pub fn is_logs_range_limit_error(message: &str) -> bool {
+    let message = message.to_lowercase();
+    LOGS_RANGE_LIMIT_MARKERS.iter().any(|marker| message.contains(marker))
+}
+
+/// Chunk size of logs requests, in blocks. Halved on each range limit rejection, grows back
+/// (doubled, up to the max size) after `GROW_AFTER_ACCEPTED` accepted requests in a row.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LogsChunkSize {
+    size: u64,
+    max_size: u64,
+    min_size: u64,
+    accepted: u32,
+}
+
+impl LogsChunkSize {
+    /// Start with the max chunk size, at least 1 block
+    pub fn new(max_size: u64) -> Self {
+        let max_size = max_size.max(1);
+        Self {
+            size: max_size,
+            max_size,
+            min_size: max_size,
+            accepted: 0,
+        }
+    }
+
+    /// Current chunk size
+    pub fn size(&self) -> u64 {
+        self.size
+    }
+
+    /// Smallest chunk size the requests were narrowed to
+    pub fn min_size(&self) -> u64 {
+        self.min_size
+    }
+
+    /// Last block of the chunk starting at `from_block`, up to `to_block` inclusive
+    pub fn chunk_end(&self, from_block: u64, to_block: u64) -> u64 {
+        from_block.saturating_add(self.size - 1).min(to_block)
+    }
+
+    /// Record an accepted request
+    pub fn accepted(&mut self) {
+        self.accepted += 1;
+        if self.accepted >= GROW_AFTER_ACCEPTED && self.size < self.max_size {
+            self.size = self.size.saturating_mul(2).min(self.max_size);
+            self.accepted = 0;
+        }
+    }
+
+    /// Record a range limit rejection, returns false if the chunk is a single block already
+    /// and can't be narrowed
+    pub fn rejected(&mut self) -> bool {
+        self.accepted = 0;
+        if self.size == 1 {
+            return false;
+        }
+        self.size /= 2;
+        self.min_size = self.min_size.min(self.size);
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_logs_range_limit_errors() {
+        assert!(is_logs_range_limit_error("server returned an error response: error code -32005: query returned more than 10000 results"));
+        assert!(is_logs_range_limit_error("Log response size exceeded. You can make eth_getLogs requests with up to a 2K block range"));
+        assert!(is_logs_range_limit_error("eth_getLogs is limited to a 10,000 range"));
+        assert!(is_logs_range_limit_error("exceed maximum block range: 50000"));
+        assert!(is_logs_range_limit_error("block range is too wide"));
+
+        assert!(!is_logs_range_limit_error("daily request count exceeded, request rate limited"));
+        assert!(!is_logs_range_limit_error("connection refused"));
+        // Not a range limit, narrowing the range doesn't help
+        assert!(!is_logs_range_limit_error("invalid block range params: from block is after to block"));
+        assert!(!is_logs_range_limit_error("block range extends beyond current head block"));
+    }
+
+    #[test]
+    fn test_chunk_size_narrowing() {
+        let mut chunk_size = LogsChunkSize::new(1000);
+        assert_eq!(chunk_size.chunk_end(0, 5000), 999);
+        assert_eq!(chunk_size.chunk_end(4500, 5000), 5000);
+
+        assert!(chunk_size.rejected());
+        assert!(chunk_size.rejected());
+        assert_eq!(chunk_size.size(), 250);
+        assert_eq!(chunk_size.min_size(), 250);
+
+        // Stays narrowed until enough requests are accepted in a row
+        for _ in 0..GROW_AFTER_ACCEPTED - 1 {
+            chunk_size.accepted();
+        }
+        assert_eq!(chunk_size.size(), 250);
+        chunk_size.rejected();
+        for _ in 0..GROW_AFTER_ACCEPTED - 1 {
+            chunk_size.accepted();
+        }
+        assert_eq!(chunk_size.size(), 125);
+        chunk_size.accepted();
+        assert_eq!(chunk_size.size(), 250);
+
+        // Grows back up to the max size only
+        for _ in 0..GROW_AFTER_ACCEPTED * 4 {
+            chunk_size.accepted();
+        }
+        assert_eq!(chunk_size.size(), 1000);
+        assert_eq!(chunk_size.min_size(), 125);
+    }
+
+    #[test]
+    fn test_chunk_size_single_block() {
+        let mut chunk_size = LogsChunkSize::new(0);
+        assert_eq!(chunk_size.size(), 1);
+        assert_eq!(chunk_size.chunk_end(7, 100), 7);
+        assert!(!chunk_size.rejected());
+    }
+}
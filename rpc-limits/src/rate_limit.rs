@@ -0,0 +1,164 @@
+//! Outbound RPC rate limiting: a token bucket shared by all requests of a task HTTP RPC provider,
+//! applied as a transport layer, so requests sent by the provider internally (e.g. polling) are limited too
+
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll};
+use std::time::{Duration, Instant};
+use alloy::rpc::json_rpc::{RequestPacket, ResponsePacket};
+use alloy::transports::{TransportError, TransportFut};
+use tower::{Layer, Service};
+use tracing::warn;
+
+/// Min interval between the throttling warnings
+const THROTTLING_REPORT_INTERVAL: Duration = Duration::from_secs(10);
+
+pub struct RpcRateLimiter {
+    requests_per_sec: f64,
+    burst: f64,
+    state: Mutex<LimiterState>,
+}
+
+struct LimiterState {
+    tokens: f64,
+    refilled_at: Instant,
+    throttled: u64, // Requests delayed since the last report
+    reported_at: Option<Instant>,
+}
+
+impl RpcRateLimiter {
+    /// Limiter allowing `requests_per_sec` requests per second on average, and bursts of up to `burst`
+    /// requests (`requests_per_sec` if not set)
+    pub fn new(requests_per_sec: u32, burst: Option<u32>) -> Arc<Self> {
+        let requests_per_sec = f64::from(requests_per_sec.max(1));
+        let burst = burst.map(|burst| f64::from(burst.max(1))).unwrap_or(requests_per_sec);
+
+        Arc::new(Self {
+            requests_per_sec,
+            burst,
+            state: Mutex::new(LimiterState {
+                tokens: burst,
+                refilled_at: Instant::now(),
+                throttled: 0,
+                reported_at: None,
+            }),
+        })
+    }
+
+    /// Wait until `requests` tokens are available and take them. Batches larger than the burst
+    /// take the whole bucket, so they aren't delayed forever.
+    pub async fn acquire(&self, requests: usize) {
+        let requests = (requests.max(1) as f64).min(self.burst);
+
+        loop {
+            let Some(wait) = self.try_acquire(requests) else { return; };
+            tokio::time::sleep(wait).await;
+        }
+    }
+
+    /// Take the tokens if available, otherwise return the time to wait for them
+    fn try_acquire(&self, requests: f64) -> Option<Duration> {
+        let mut state = self.state.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+
+        let now = Instant::now();
+        let elapsed = now.duration_since(state.refilled_at).as_secs_f64();
+        state.tokens = (state.tokens + elapsed * self.requests_per_sec).min(self.burst);
+        state.refilled_at = now;
+
+        if state.tokens >= requests {
+            state.tokens -= requests;
+            return None;
+        }
+
+        state.throttled += 1;
+        if state.reported_at.is_none_or(|reported_at| now.duration_since(reported_at) >= THROTTLING_REPORT_INTERVAL) {
+            warn!(
+                "RPC rate limit of {} requests/s reached, {} requests throttled since the last report",
+                self.requests_per_sec, state.throttled
+            );
+            state.throttled = 0;
+            state.reported_at = Some(now);
+        }
+
+        Some(Duration::from_secs_f64((requests - state.tokens) / self.requests_per_sec))
+    }
+}
+
+/// Transport layer waiting for the rate limiter before sending each request packet,
+/// batches count as their number of requests
+#[derive(Clone)]
+pub struct RpcRateLimitLayer {
+    limiter: Arc<RpcRateLimiter>,
+}
+
+impl RpcRateLimitLayer {
+    pub fn new(limiter: Arc<RpcRateLimiter>) -> Self {
+        Self { limiter }
+    }
+}
+
+impl<S> Layer<S> for RpcRateLimitLayer {
+    type Service = RpcRateLimitService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        RpcRateLimitService {
+            inner,
+            limiter: Arc::clone(&self.limiter),
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct RpcRateLimitService<S> {
+    inner: S,
+    limiter: Arc<RpcRateLimiter>,
+}
+
+impl<S> Service<RequestPacket> for RpcRateLimitService<S>
+where
+    S: Service<RequestPacket, Response = ResponsePacket, Error = TransportError> + Clone + Send + Sync + 'static,
+    S::Future: Send + 'static,
+{
+    type Response = ResponsePacket;
+    type Error = TransportError;
+    type Future = TransportFut<'static>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, request: RequestPacket) -> Self::Future {
+        let mut inner = self.inner.clone();
+        let limiter = Arc::clone(&self.limiter);
+
+        Box::pin(async move {
+            limiter.acquire(request.len()).await;
+            inner.call(request).await
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rate_limiter_burst_then_throttle() {
+        let limiter = RpcRateLimiter::new(10, Some(3));
+
+        for _ in 0..3 {
+            assert!(limiter.try_acquire(1.0).is_none());
+        }
+
+        let wait = limiter.try_acquire(1.0).expect("bucket must be empty after the burst");
+        assert!(wait > Duration::ZERO && wait <= Duration::from_millis(100));
+    }
+
+    #[tokio::test]
+    async fn test_rate_limiter_caps_batches_at_burst() {
+        let limiter = RpcRateLimiter::new(1000, Some(5));
+
+        // Larger than the burst, takes the whole bucket instead of waiting forever
+        tokio::time::timeout(Duration::from_secs(1), limiter.acquire(50)).await
+            .expect("batch larger than the burst must not wait forever");
+    }
+}
@@ -13,6 +13,8 @@ async fn main() -> Result<()> {
         rpc_url: "http://localhost:8545".to_string(),
         chain_id: 1,
         timeout_seconds: 60,
+        ws_url: None,
+        headers: Default::default(),
     };
 
     let provider_manager = ProviderManager::new(provider_config)?
@@ -21,6 +23,7 @@ async fn main() -> Result<()> {
     let contract_config = ContractConfig {
         address: "0x1234567890123456789012345678901234567890".parse().unwrap(),
         abi_path: "abi/AirdropContract.json".to_string(),
+        verify_deployed: false,
     };
 
     let contract = ContractClient::new(
@@ -79,6 +82,7 @@ async fn main() -> Result<()> {
             gas_limit: Some(500000),
             gas_price: None,
             value: None,
+            max_gas: None,
         },
         BatchTransaction {
             id: "update_round_2".to_string(),
@@ -92,6 +96,7 @@ async fn main() -> Result<()> {
             gas_limit: Some(500000),
             gas_price: None,
             value: None,
+            max_gas: None,
         },
     ];
 
@@ -143,6 +148,7 @@ async fn main() -> Result<()> {
             gas_limit: Some(200000),
             gas_price: None,
             value: None,
+            max_gas: None,
         });
     }
 
@@ -13,6 +13,8 @@ async fn main() -> Result<()> {
         rpc_url: "http://localhost:8545".to_string(),
         chain_id: 1,
         timeout_seconds: 30,
+        ws_url: None,
+        headers: Default::default(),
     };
 
     // Step 2: Create provider manager with private key
@@ -27,6 +29,7 @@ async fn main() -> Result<()> {
     let contract_config = ContractConfig {
         address: "0x1234567890123456789012345678901234567890".parse().unwrap(),
         abi_path: "abi/MyContract.json".to_string(),
+        verify_deployed: true,
     };
 
     // Step 5: Create contract client
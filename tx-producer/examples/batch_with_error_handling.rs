@@ -14,6 +14,8 @@ async fn main() -> Result<()> {
         rpc_url: "http://localhost:8545".to_string(),
         chain_id: 1,
         timeout_seconds: 30,
+        ws_url: None,
+        headers: Default::default(),
     };
 
     let provider_manager = ProviderManager::new(provider_config)?
@@ -22,6 +24,7 @@ async fn main() -> Result<()> {
     let contract_config = ContractConfig {
         address: "0x1234567890123456789012345678901234567890".parse().unwrap(),
         abi_path: "abi/AirdropContract.json".to_string(),
+        verify_deployed: false,
     };
 
     let contract = ContractClient::new(
@@ -42,6 +45,7 @@ async fn main() -> Result<()> {
             gas_limit: Some(100000),
             gas_price: None,
             value: None,
+            max_gas: None,
         });
     }
 
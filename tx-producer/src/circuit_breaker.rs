@@ -0,0 +1,248 @@
+//! Circuit breaker of RPC calls, fast-failing calls to a failing endpoint
+
+use alloy::transports::{RpcError, TransportErrorKind};
+use serde::{Deserialize, Serialize};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use crate::error::{TxProducerError, Result};
+
+/// Circuit breaker configuration
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct CircuitBreakerConfig {
+    /// Consecutive RPC failures opening the circuit
+    pub failure_threshold: u32,
+    /// Time the open circuit fast-fails calls before allowing a trial call
+    pub cooldown: Duration,
+}
+
+impl Default for CircuitBreakerConfig {
+    fn default() -> Self {
+        Self {
+            failure_threshold: 5,
+            cooldown: Duration::from_secs(30),
+        }
+    }
+}
+
+/// Circuit breaker state
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum CircuitState {
+    /// Calls pass
+    Closed,
+    /// Calls fast-fail with `CircuitOpen` until the cooldown elapses
+    Open,
+    /// Cooldown elapsed, the next call is a trial closing the circuit on success
+    HalfOpen,
+}
+
+/// RPC errors telling the endpoint itself failed (connection, timeout, malformed response),
+/// as opposed to the node JSON-RPC error responses (e.g. reverts) of a healthy endpoint
+pub trait EndpointFailure {
+    fn is_endpoint_failure(&self) -> bool;
+}
+
+impl EndpointFailure for RpcError<TransportErrorKind> {
+    fn is_endpoint_failure(&self) -> bool {
+        !self.is_error_resp()
+    }
+}
+
+impl EndpointFailure for alloy_contract::Error {
+    fn is_endpoint_failure(&self) -> bool {
+        match self {
+            alloy_contract::Error::TransportError(e) => e.is_endpoint_failure(),
+            _ => false,
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+struct BreakerState {
+    consecutive_failures: u32,
+    opened_at: Option<Instant>,
+    trial_started_at: Option<Instant>,
+}
+
+/// Opens after `failure_threshold` consecutive endpoint failures, fast-failing calls
+/// for the cooldown, then lets a single trial call through: its success closes the circuit,
+/// its failure opens it again for another cooldown.
+#[derive(Debug)]
+pub struct CircuitBreaker {
+    config: CircuitBreakerConfig,
+    state: Mutex<BreakerState>,
+}
+
+impl CircuitBreaker {
+    pub fn new(config: CircuitBreakerConfig) -> Self {
+        Self {
+            config,
+            state: Mutex::new(BreakerState::default()),
+        }
+    }
+
+    pub fn config(&self) -> &CircuitBreakerConfig {
+        &self.config
+    }
+
+    pub fn state(&self) -> CircuitState {
+        let state = self.state.lock().unwrap();
+        match state.opened_at {
+            None => CircuitState::Closed,
+            Some(opened_at) if opened_at.elapsed() < self.config.cooldown => CircuitState::Open,
+            Some(_) => CircuitState::HalfOpen,
+        }
+    }
+
+    /// Consecutive endpoint failures since the last success
+    pub fn consecutive_failures(&self) -> u32 {
+        self.state.lock().unwrap().consecutive_failures
+    }
+
+    /// Run the RPC call through the breaker: fast-fail with `CircuitOpen` while open,
+    /// otherwise return the call result, recording whether the endpoint failed
+    pub async fn call<T, E, F>(&self, rpc_call: F) -> Result<std::result::Result<T, E>>
+    where
+        E: EndpointFailure,
+        F: std::future::IntoFuture<Output = std::result::Result<T, E>>,
+    {
+        self.try_acquire()?;
+
+        let result = rpc_call.await;
+        match &result {
+            Err(e) if e.is_endpoint_failure() => self.record_failure(),
+            _ => self.record_success(),
+        }
+
+        Ok(result)
+    }
+
+    fn try_acquire(&self) -> Result<()> {
+        let mut state = self.state.lock().unwrap();
+        let Some(opened_at) = state.opened_at else {
+            return Ok(());
+        };
+
+        let open_error = |retry_in: Duration| TxProducerError::CircuitOpen {
+            failures: state.consecutive_failures,
+            retry_in,
+        };
+
+        let elapsed = opened_at.elapsed();
+        if elapsed < self.config.cooldown {
+            return Err(open_error(self.config.cooldown - elapsed));
+        }
+
+        // Single trial call, unless the previous one was abandoned without an outcome
+        if let Some(trial_started_at) = state.trial_started_at {
+            if trial_started_at.elapsed() < self.config.cooldown {
+                return Err(open_error(self.config.cooldown - trial_started_at.elapsed()));
+            }
+        }
+
+        state.trial_started_at = Some(Instant::now());
+        Ok(())
+    }
+
+    fn record_success(&self) {
+        let mut state = self.state.lock().unwrap();
+        if state.opened_at.is_some() {
+            tracing::info!("RPC circuit breaker closed after a successful trial call");
+        }
+        *state = BreakerState::default();
+    }
+
+    fn record_failure(&self) {
+        let mut state = self.state.lock().unwrap();
+        state.consecutive_failures += 1;
+
+        let is_trial = state.trial_started_at.take().is_some();
+        if is_trial || (state.opened_at.is_none() && state.consecutive_failures >= self.config.failure_threshold) {
+            tracing::warn!(
+                "RPC circuit breaker opened after {} consecutive failures, fast-failing calls for {:?}",
+                state.consecutive_failures, self.config.cooldown
+            );
+            state.opened_at = Some(Instant::now());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn endpoint_failure() -> RpcError<TransportErrorKind> {
+        TransportErrorKind::custom_str("connection refused")
+    }
+
+    async fn fail(breaker: &CircuitBreaker) -> Result<std::result::Result<(), RpcError<TransportErrorKind>>> {
+        breaker.call(async { Err(endpoint_failure()) }).await
+    }
+
+    async fn succeed(breaker: &CircuitBreaker) -> Result<std::result::Result<(), RpcError<TransportErrorKind>>> {
+        breaker.call(async { Ok(()) }).await
+    }
+
+    #[tokio::test]
+    async fn test_opens_after_consecutive_failures() {
+        let breaker = CircuitBreaker::new(CircuitBreakerConfig {
+            failure_threshold: 3,
+            cooldown: Duration::from_secs(60),
+        });
+
+        fail(&breaker).await.unwrap().unwrap_err();
+        fail(&breaker).await.unwrap().unwrap_err();
+        succeed(&breaker).await.unwrap().unwrap();
+        assert_eq!(breaker.consecutive_failures(), 0);
+
+        for _ in 0..3 {
+            fail(&breaker).await.unwrap().unwrap_err();
+        }
+        assert_eq!(breaker.state(), CircuitState::Open);
+        assert!(matches!(succeed(&breaker).await, Err(TxProducerError::CircuitOpen { failures: 3, .. })));
+    }
+
+    #[tokio::test]
+    async fn test_trial_call_after_cooldown() {
+        let breaker = CircuitBreaker::new(CircuitBreakerConfig {
+            failure_threshold: 1,
+            cooldown: Duration::from_millis(20),
+        });
+
+        fail(&breaker).await.unwrap().unwrap_err();
+        assert_eq!(breaker.state(), CircuitState::Open);
+
+        // Failed trial opens the circuit again
+        tokio::time::sleep(Duration::from_millis(30)).await;
+        assert_eq!(breaker.state(), CircuitState::HalfOpen);
+        fail(&breaker).await.unwrap().unwrap_err();
+        assert_eq!(breaker.state(), CircuitState::Open);
+
+        // Successful trial closes it
+        tokio::time::sleep(Duration::from_millis(30)).await;
+        succeed(&breaker).await.unwrap().unwrap();
+        assert_eq!(breaker.state(), CircuitState::Closed);
+    }
+
+    #[tokio::test]
+    async fn test_error_responses_are_not_failures() {
+        let breaker = CircuitBreaker::new(CircuitBreakerConfig {
+            failure_threshold: 1,
+            cooldown: Duration::from_secs(60),
+        });
+
+        let reverted = breaker
+            .call(async {
+                Err::<(), _>(RpcError::<TransportErrorKind>::ErrorResp(alloy::rpc::json_rpc::ErrorPayload {
+                    code: 3,
+                    message: "execution reverted".into(),
+                    data: None,
+                }))
+            })
+            .await
+            .unwrap();
+
+        assert!(reverted.is_err());
+        assert_eq!(breaker.state(), CircuitState::Closed);
+    }
+}
@@ -1,17 +1,30 @@
 //! Universal contract interaction using JSON ABI
 
 use alloy_contract::{ContractInstance, Interface};
-use alloy_dyn_abi::DynSolValue;
-use alloy_json_abi::{JsonAbi, Function, Event};
-use alloy_primitives::{Address, B256, Bytes, U256};
+use alloy_dyn_abi::{DynSolType, DynSolValue};
+use alloy_json_abi::{JsonAbi, Function, Event, Param};
+use alloy_primitives::{b256, Address, B256, Bytes, U256};
+use alloy::consensus::Transaction as _;
+use alloy::eips::BlockId;
+use alloy::eips::eip2930::AccessList;
+use alloy::network::TransactionResponse as _;
+use alloy::rpc::types::{Filter, TransactionInput, TransactionRequest};
 use alloy_provider::Provider;
+use alloy_sol_types::SolCall;
+use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::path::Path;
 use std::sync::Arc;
+use std::time::Duration;
 
+use crate::erc20::{Erc20Transfer, IERC20};
 use crate::error::{TxProducerError, Result};
-use crate::provider::{ProviderManager, TxProvider};
+use crate::multicall::{IMulticall3, MULTICALL3_ADDRESS};
+use crate::provider::{ChunkedLogs, FeeSuggestion, ProviderManager, TxProvider};
+
+/// EIP-1967 proxy implementation slot: `bytes32(uint256(keccak256("eip1967.proxy.implementation")) - 1)`
+pub const EIP1967_IMPLEMENTATION_SLOT: B256 = b256!("360894a13ba1a3210667c828492db98dca3e2076cc3735a920a3ca505d382bbc");
 
 /// Contract configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -20,6 +33,95 @@ pub struct ContractConfig {
     pub address: Address,
     /// Path to ABI file (JSON)
     pub abi_path: String,
+    /// Check the contract is deployed on construction of the client
+    #[serde(default)]
+    pub verify_deployed: bool,
+}
+
+impl ContractConfig {
+    /// Request the contract code (`eth_getCode`) on construction of the client,
+    /// failing with `NoContractCode` if there's none, instead of on the first call
+    pub fn verify_deployed(mut self, verify: bool) -> Self {
+        self.verify_deployed = verify;
+        self
+    }
+}
+
+/// EIP-2930 access list generated by the node, with the gas of the call with and without it
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AccessListEstimate {
+    /// Accessed addresses and storage slots
+    pub access_list: AccessList,
+    /// Gas used with the access list included
+    pub gas_with_access_list: u64,
+    /// Gas estimate without the access list
+    pub gas_without_access_list: u64,
+}
+
+impl AccessListEstimate {
+    /// Gas saved by the access list, negative if it costs more than it saves
+    pub fn gas_delta(&self) -> i64 {
+        self.gas_without_access_list as i64 - self.gas_with_access_list as i64
+    }
+}
+
+/// Polling of the sent transaction receipt until it's mined
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ConfirmationConfig {
+    /// Interval between receipt requests
+    pub poll_interval: Duration,
+    /// Time to wait for the receipt, failing with `ConfirmationTimeout` after it
+    pub timeout: Duration,
+}
+
+impl ConfirmationConfig {
+    /// Defaults by the chain block time: tight polling on L2s and dev chains,
+    /// long timeouts on Ethereum L1s
+    pub fn for_chain(chain_id: u64) -> Self {
+        let (poll_interval_ms, timeout_secs) = match chain_id {
+            // Ethereum mainnet, Sepolia, Holesky
+            1 | 11155111 | 17000 => (3_000, 600),
+            // Optimism, Base, Arbitrum One, zkSync Era, Polygon zkEVM, Linea, Scroll
+            10 | 8453 | 42161 | 324 | 1101 | 59144 | 534352 => (500, 120),
+            // Anvil/Hardhat, Ganache
+            31337 | 1337 => (100, 30),
+            _ => (1_000, 180),
+        };
+
+        Self {
+            poll_interval: Duration::from_millis(poll_interval_ms),
+            timeout: Duration::from_secs(timeout_secs),
+        }
+    }
+}
+
+/// Options of a sent transaction, the provider fillers and chain defaults for the unset ones
+#[derive(Debug, Clone, Default)]
+pub(crate) struct SendOptions {
+    /// EIP-2930 access list
+    pub(crate) access_list: Option<AccessList>,
+    /// EIP-1559 fees, e.g. by `ProviderManager::suggest_fees`
+    pub(crate) fees: Option<FeeSuggestion>,
+    /// Receipt polling
+    pub(crate) confirmation: Option<ConfirmationConfig>,
+}
+
+/// Minimal fee bump (%) of a replacement transaction accepted by nodes
+pub const MIN_REPLACEMENT_FEE_BUMP_PERCENT: u64 = 10;
+
+/// Gas limit of a plain ETH transfer
+const TRANSFER_GAS_LIMIT: u64 = 21_000;
+
+/// Transaction replacing a pending transaction at the same nonce
+#[derive(Debug, Clone)]
+pub enum Replacement {
+    /// 0-value transfer to the signer itself, cancelling the pending transaction
+    Cancel,
+    /// Function call of the contract, resent with the higher fee
+    Call {
+        function_name: String,
+        args: Vec<DynSolValue>,
+    },
 }
 
 /// Universal contract client
@@ -32,6 +134,12 @@ pub struct ContractClient {
     instance: ContractInstance<TxProvider>,
     /// Provider manager
     provider_manager: Arc<ProviderManager>,
+    /// Multicall3 contract address for batched reads
+    multicall_address: Address,
+    /// Functions allowed to be called, all of them if empty
+    allowed_functions: HashSet<String>,
+    /// Functions never allowed to be called, overriding the allowlist
+    denied_functions: HashSet<String>,
 }
 
 impl ContractClient {
@@ -43,6 +151,10 @@ impl ContractClient {
         // Load ABI from file
         let abi = Self::load_abi(&config.abi_path).await?;
 
+        if config.verify_deployed {
+            Self::verify_code(config.address, &provider_manager).await?;
+        }
+
         // Create contract interface
         let interface = Interface::new(abi.clone());
 
@@ -58,9 +170,66 @@ impl ContractClient {
             abi,
             instance,
             provider_manager,
+            multicall_address: MULTICALL3_ADDRESS,
+            allowed_functions: HashSet::new(),
+            denied_functions: HashSet::new(),
         })
     }
 
+    /// Fail with `NoContractCode` if there's no code at the address,
+    /// e.g. the RPC endpoint is of another network or the contract isn't deployed yet
+    async fn verify_code(address: Address, provider_manager: &ProviderManager) -> Result<()> {
+        let request = provider_manager.provider().get_code_at(address);
+
+        let code = provider_manager
+            .rpc(request)
+            .await?
+            .map_err(|e| TxProducerError::Provider(format!("Failed to get code of {}: {}", address, e)))?;
+
+        if code.is_empty() {
+            return Err(TxProducerError::NoContractCode { address });
+        }
+
+        Ok(())
+    }
+
+    /// Use a Multicall3 contract at a custom address, for chains without the canonical deployment
+    pub fn with_multicall_address(mut self, multicall_address: Address) -> Self {
+        self.multicall_address = multicall_address;
+        self
+    }
+
+    /// Restrict the callable functions to the named ones, for services calling
+    /// functions named by user input. An empty allowlist allows all functions.
+    pub fn with_allowed_functions(mut self, functions: Vec<String>) -> Self {
+        self.allowed_functions = functions.into_iter().collect();
+        self
+    }
+
+    /// Forbid calling the named functions, even if they are allowlisted
+    pub fn with_denied_functions(mut self, functions: Vec<String>) -> Self {
+        self.denied_functions = functions.into_iter().collect();
+        self
+    }
+
+    /// Whether the function name passes the allowlist and denylist
+    pub fn is_function_permitted(&self, function_name: &str) -> bool {
+        !self.denied_functions.contains(function_name)
+            && (self.allowed_functions.is_empty() || self.allowed_functions.contains(function_name))
+    }
+
+    /// Fail with `FunctionNotPermitted` if the function is excluded by the allowlist or denylist
+    pub fn ensure_function_permitted(&self, function_name: &str) -> Result<()> {
+        if self.is_function_permitted(function_name) {
+            Ok(())
+        } else {
+            Err(TxProducerError::FunctionNotPermitted {
+                contract: self.address,
+                function: function_name.to_string(),
+            })
+        }
+    }
+
     /// Load ABI from JSON file
     async fn load_abi(path: &str) -> Result<JsonAbi> {
         let abi_content = tokio::fs::read_to_string(path)
@@ -89,39 +258,481 @@ impl ContractClient {
         function_name: &str,
         args: &[DynSolValue],
     ) -> Result<Vec<DynSolValue>> {
+        self.call_function_at(function_name, args, None).await
+    }
+
+    /// Call a read-only function against the state of the block (number, hash or tag),
+    /// at the latest block if no block is given. Past blocks state requires an archive node
+    /// unless they are recent.
+    pub async fn call_function_at(
+        &self,
+        function_name: &str,
+        args: &[DynSolValue],
+        block: Option<BlockId>,
+    ) -> Result<Vec<DynSolValue>> {
+        self.ensure_function_permitted(function_name)?;
+
         let call = self.instance
             .function(function_name, args)
-            .map_err(|e| TxProducerError::ContractCall(format!("Failed to create function call: {}", e)))?;
+            .map_err(|e| TxProducerError::ContractCall(format!("Failed to create function call: {}", e)))?
+            .block(block.unwrap_or_default());
 
-        let result = call
-            .call()
-            .await
+        let result = self.provider_manager
+            .rpc(call.call())
+            .await?
             .map_err(|e| TxProducerError::ContractCall(format!("Function call failed: {}", e)))?;
 
         Ok(result)
     }
 
+    /// Call a read-only function, with the outputs as JSON named by the ABI outputs:
+    /// an object keyed by the output names (index for unnamed ones), or the value of a single
+    /// unnamed output. Structs are objects keyed by their field names, see `value_helpers::to_json`.
+    pub async fn call_function_json(
+        &self,
+        function_name: &str,
+        args: &[DynSolValue],
+    ) -> Result<serde_json::Value> {
+        let function = self.get_function(function_name)?;
+        let outputs = self.call_function(function_name, args).await?;
+
+        Ok(value_helpers::outputs_to_json(&function.outputs, &outputs))
+    }
+
+    /// Call a read-only function, with the outputs deserialized from their JSON
+    /// by `call_function_json`, e.g. into a struct with the ABI output field names
+    pub async fn call_function_typed<T: DeserializeOwned>(
+        &self,
+        function_name: &str,
+        args: &[DynSolValue],
+    ) -> Result<T> {
+        let outputs = self.call_function_json(function_name, args).await?;
+
+        serde_json::from_value(outputs)
+            .map_err(|e| TxProducerError::Decoding(format!("Failed to deserialize '{}' result: {}", function_name, e)))
+    }
+
+    /// Read a raw storage slot of the contract, at the latest block if no block is given
+    pub async fn get_storage_at(&self, slot: B256, block: Option<BlockId>) -> Result<B256> {
+        let request = self.provider_manager
+            .provider()
+            .get_storage_at(self.address, U256::from_be_bytes(slot.0))
+            .block_id(block.unwrap_or_default());
+
+        let value = self.provider_manager
+            .rpc(request)
+            .await?
+            .map_err(|e| TxProducerError::Provider(format!("Failed to read storage slot {} of {}: {}", slot, self.address, e)))?;
+
+        Ok(B256::from(value.to_be_bytes::<32>()))
+    }
+
+    /// Current implementation of the EIP-1967 proxy contract, read from its implementation slot.
+    /// Fails if the slot is empty, i.e. the contract isn't an EIP-1967 proxy (or a beacon proxy).
+    pub async fn get_eip1967_implementation(&self) -> Result<Address> {
+        let value = self.get_storage_at(EIP1967_IMPLEMENTATION_SLOT, None).await?;
+
+        address_from_slot(value).ok_or_else(|| TxProducerError::ContractCall(format!(
+            "Contract {} has no EIP-1967 implementation set", self.address
+        )))
+    }
+
+    /// Call many read-only functions in a single Multicall3 `aggregate3` call.
+    /// Each entry yields the decoded outputs of its function, or its own error
+    /// if the sub-call reverted or its output failed to decode.
+    pub async fn multicall_read(
+        &self,
+        calls: &[(String, Vec<DynSolValue>)],
+    ) -> Result<Vec<Result<Vec<DynSolValue>>>> {
+        if calls.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let sub_calls = calls
+            .iter()
+            .map(|(function_name, args)| {
+                Ok(IMulticall3::Call3 {
+                    target: self.address,
+                    allowFailure: true,
+                    callData: self.encode_function_data(function_name, args)?,
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        let calldata = IMulticall3::aggregate3Call { calls: sub_calls }.abi_encode();
+        let request = TransactionRequest::default()
+            .to(self.multicall_address)
+            .input(TransactionInput::new(calldata.into()));
+
+        let output = self.provider_manager
+            .rpc(self.provider_manager.provider().call(request))
+            .await?
+            .map_err(|e| TxProducerError::ContractCall(format!("Multicall failed: {}", e)))?;
+
+        let results = IMulticall3::aggregate3Call::abi_decode_returns(&output)
+            .map_err(|e| TxProducerError::Decoding(format!("Failed to decode multicall result: {}", e)))?;
+
+        if results.len() != calls.len() {
+            return Err(TxProducerError::Decoding(format!(
+                "Multicall returned {} results for {} calls", results.len(), calls.len()
+            )));
+        }
+
+        Ok(calls
+            .iter()
+            .zip(results)
+            .map(|((function_name, _), result)| {
+                if result.success {
+                    self.decode_function_result(function_name, &result.returnData)
+                } else {
+                    Err(TxProducerError::ContractCall(format!(
+                        "Function call '{}' reverted: 0x{}", function_name, hex::encode(&result.returnData)
+                    )))
+                }
+            })
+            .collect())
+    }
+
+    /// Confirmation polling defaults of the provider chain
+    pub fn default_confirmation(&self) -> ConfirmationConfig {
+        ConfirmationConfig::for_chain(self.provider_manager.chain_id())
+    }
+
     /// Send a transaction (state-changing function)
     pub async fn send_transaction(
         &self,
         function_name: &str,
         args: &[DynSolValue],
     ) -> Result<B256> {
-        let call = self.instance
+        self.send(function_name, args, SendOptions::default()).await
+    }
+
+    /// Send a transaction with an EIP-2930 access list
+    pub async fn send_transaction_with_access_list(
+        &self,
+        function_name: &str,
+        args: &[DynSolValue],
+        access_list: AccessList,
+    ) -> Result<B256> {
+        self.send(function_name, args, SendOptions {
+            access_list: Some(access_list),
+            ..Default::default()
+        }).await
+    }
+
+    /// Send a transaction with the options, polling its receipt by the confirmation config.
+    /// Fails with `ConfirmationTimeout` carrying the hash of the still pending transaction.
+    pub(crate) async fn send(
+        &self,
+        function_name: &str,
+        args: &[DynSolValue],
+        options: SendOptions,
+    ) -> Result<B256> {
+        self.ensure_function_permitted(function_name)?;
+
+        let mut call = self.instance
             .function(function_name, args)
             .map_err(|e| TxProducerError::ContractCall(format!("Failed to create transaction: {}", e)))?;
 
-        let pending_tx = call
-            .send()
+        if let Some(access_list) = options.access_list {
+            call = call.access_list(access_list);
+        }
+
+        if let Some(fees) = options.fees {
+            call = call
+                .max_fee_per_gas(fees.max_fee_per_gas)
+                .max_priority_fee_per_gas(fees.max_priority_fee_per_gas);
+        }
+
+        // Nonce from the nonce manager, so writers sharing the signing key don't collide
+        let allocated_nonce = match self.provider_manager.signer_address() {
+            Some(from) => {
+                let nonce = self.provider_manager.nonce_manager().next_nonce(from).await?;
+                call = call.from(from).nonce(nonce);
+                Some((from, nonce))
+            }
+            None => None,
+        };
+
+        let send_result = self.provider_manager
+            .rpc(call.send())
             .await
-            .map_err(|e| TxProducerError::Transaction(format!("Transaction failed: {}", e)))?;
+            .and_then(|result| result.map_err(|e| TxProducerError::Transaction(format!("Transaction failed: {}", e))));
+
+        let pending_tx = match send_result {
+            Ok(pending_tx) => pending_tx,
+            Err(e) => {
+                // Not broadcast, the nonce is free to be allocated again
+                if let Some((from, nonce)) = allocated_nonce {
+                    if let Err(release_error) = self.provider_manager.nonce_manager().release_nonce(from, nonce).await {
+                        tracing::warn!("Failed to release nonce {} of {}: {}", nonce, from, release_error);
+                    }
+                }
+                return Err(e);
+            }
+        };
+
+        let confirmation = options.confirmation.unwrap_or_else(|| self.default_confirmation());
+        self.wait_for_confirmation(*pending_tx.tx_hash(), confirmation).await
+    }
+
+    /// Poll the transaction receipt until it's mined or the timeout elapses.
+    /// Failed receipt requests are retried on the next poll.
+    async fn wait_for_confirmation(&self, tx_hash: B256, confirmation: ConfirmationConfig) -> Result<B256> {
+        let provider = self.provider_manager.provider();
 
-        let receipt = pending_tx
-            .get_receipt()
+        let poll_receipt = async {
+            loop {
+                let receipt = self.provider_manager
+                    .rpc(provider.get_transaction_receipt(tx_hash))
+                    .await
+                    .and_then(|result| result.map_err(|e| TxProducerError::Provider(e.to_string())));
+
+                match receipt {
+                    Ok(Some(receipt)) => return receipt.transaction_hash,
+                    Ok(None) => {}
+                    Err(e) => tracing::warn!("Failed to get receipt of transaction {}: {}", tx_hash, e),
+                }
+                tokio::time::sleep(confirmation.poll_interval).await;
+            }
+        };
+
+        tokio::time::timeout(confirmation.timeout, poll_receipt)
             .await
-            .map_err(|e| TxProducerError::Transaction(format!("Failed to get receipt: {}", e)))?;
+            .map_err(|_| TxProducerError::ConfirmationTimeout { tx_hash, timeout: confirmation.timeout })
+    }
+
+    /// Replace a stuck transaction of the signer at the nonce, by a cancelling self-transfer or
+    /// the resent function call, with the legacy gas price (also covering EIP-1559 fees).
+    /// The gas price must exceed the replaced transaction fees by the node bump threshold
+    /// (10% by default). Returns the hash of the replacement transaction, without waiting for it.
+    pub async fn replace_transaction(
+        &self,
+        nonce: u64,
+        new_gas_price: u128,
+        replacement: Replacement,
+    ) -> Result<B256> {
+        let from = self.provider_manager.signer_address().ok_or_else(|| {
+            TxProducerError::Configuration("Transaction replacement requires a signer".to_string())
+        })?;
+
+        let request = match replacement {
+            Replacement::Cancel => TransactionRequest::default()
+                .to(from)
+                .value(U256::ZERO)
+                .gas_limit(TRANSFER_GAS_LIMIT),
+            Replacement::Call { function_name, args } => TransactionRequest::default()
+                .to(self.address)
+                .input(TransactionInput::new(self.encode_function_data(&function_name, &args)?)),
+        };
+
+        self.broadcast(request.from(from).nonce(nonce).gas_price(new_gas_price)).await
+    }
+
+    /// Rebroadcast the pending transaction with its fees bumped by the percentage
+    /// (at least `MIN_REPLACEMENT_FEE_BUMP_PERCENT`), keeping its nonce, call and fee type.
+    /// Returns the hash of the replacement transaction, without waiting for it.
+    pub async fn speed_up_pending(&self, tx_hash: B256, bump_percent: u64) -> Result<B256> {
+        if bump_percent < MIN_REPLACEMENT_FEE_BUMP_PERCENT {
+            return Err(TxProducerError::InvalidInput(format!(
+                "Fee bump {}% is below the replacement minimum {}%", bump_percent, MIN_REPLACEMENT_FEE_BUMP_PERCENT
+            )));
+        }
+
+        let tx = self.provider_manager
+            .rpc(self.provider_manager.provider().get_transaction_by_hash(tx_hash))
+            .await?
+            .map_err(|e| TxProducerError::Provider(format!("Failed to get transaction {}: {}", tx_hash, e)))?
+            .ok_or_else(|| TxProducerError::Transaction(format!("Transaction {} not found", tx_hash)))?;
+
+        if let Some(block_number) = tx.block_number {
+            return Err(TxProducerError::Transaction(format!(
+                "Transaction {} is already included in block {}", tx_hash, block_number
+            )));
+        }
+
+        let mut request = TransactionRequest::default()
+            .from(tx.from())
+            .nonce(tx.nonce())
+            .value(tx.value())
+            .gas_limit(tx.gas_limit())
+            .input(TransactionInput::new(tx.input().clone()));
+
+        if let Some(to) = tx.to() {
+            request = request.to(to);
+        }
+        if let Some(access_list) = tx.access_list() {
+            request = request.access_list(access_list.clone());
+        }
+
+        request = if tx.is_dynamic_fee() {
+            request
+                .max_fee_per_gas(bump_fee(tx.max_fee_per_gas(), bump_percent))
+                .max_priority_fee_per_gas(bump_fee(tx.max_priority_fee_per_gas().unwrap_or_default(), bump_percent))
+        } else {
+            request.gas_price(bump_fee(tx.max_fee_per_gas(), bump_percent))
+        };
+
+        self.broadcast(request).await
+    }
+
+    /// Send the transaction request, returning its hash once accepted by the node
+    async fn broadcast(&self, request: TransactionRequest) -> Result<B256> {
+        let pending_tx = self.provider_manager
+            .rpc(self.provider_manager.provider().send_transaction(request))
+            .await?
+            .map_err(|e| TxProducerError::Transaction(format!("Replacement transaction failed: {}", e)))?;
 
-        Ok(receipt.transaction_hash)
+        Ok(*pending_tx.tx_hash())
+    }
+
+    /// Fail fast if the ERC20 `transfer`/`transferFrom` calldata exceeds the holder balance
+    /// or the signer allowance, read via `eth_call`. Other calldata passes unchecked.
+    pub async fn erc20_precheck(&self, calldata: &[u8]) -> Result<()> {
+        let Some(transfer) = Erc20Transfer::from_calldata(calldata) else {
+            return Ok(());
+        };
+
+        let caller = self.provider_manager.signer_address().ok_or_else(|| {
+            TxProducerError::Configuration("ERC20 precheck requires a signer".to_string())
+        })?;
+        let holder = transfer.holder(caller);
+        let required = transfer.amount();
+
+        let balance = self.erc20_read(IERC20::balanceOfCall { account: holder }).await?;
+        if balance < required {
+            return Err(TxProducerError::InsufficientBalance {
+                token: self.address,
+                holder,
+                balance,
+                required,
+            });
+        }
+
+        if let Erc20Transfer::TransferFrom { from, .. } = transfer {
+            let allowance = self.erc20_read(IERC20::allowanceCall { owner: from, spender: caller }).await?;
+            if allowance < required {
+                return Err(TxProducerError::InsufficientAllowance {
+                    token: self.address,
+                    owner: from,
+                    spender: caller,
+                    allowance,
+                    required,
+                });
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Read an ERC20 amount of the contract via `eth_call`
+    async fn erc20_read<C: SolCall<Return = U256>>(&self, call: C) -> Result<U256> {
+        let request = TransactionRequest::default()
+            .to(self.address)
+            .input(TransactionInput::new(call.abi_encode().into()));
+
+        let output = self.provider_manager
+            .rpc(self.provider_manager.provider().call(request))
+            .await?
+            .map_err(|e| TxProducerError::ContractCall(format!("ERC20 precheck call failed: {}", e)))?;
+
+        C::abi_decode_returns(&output)
+            .map_err(|e| TxProducerError::Decoding(format!("Failed to decode ERC20 precheck result: {}", e)))
+    }
+
+    /// Estimate gas of a transaction without sending it, from the signer address if configured
+    pub async fn estimate_gas(
+        &self,
+        function_name: &str,
+        args: &[DynSolValue],
+    ) -> Result<u64> {
+        self.estimate_gas_at(function_name, args, None).await
+    }
+
+    /// Estimate gas of a transaction against the state of the block (number, hash or tag),
+    /// at the latest block if no block is given
+    pub async fn estimate_gas_at(
+        &self,
+        function_name: &str,
+        args: &[DynSolValue],
+        block: Option<BlockId>,
+    ) -> Result<u64> {
+        self.ensure_function_permitted(function_name)?;
+
+        let mut call = self.instance
+            .function(function_name, args)
+            .map_err(|e| TxProducerError::ContractCall(format!("Failed to create transaction: {}", e)))?
+            .block(block.unwrap_or_default());
+
+        if let Some(from) = self.provider_manager.signer_address() {
+            call = call.from(from);
+        }
+
+        self.provider_manager
+            .rpc(call.estimate_gas())
+            .await?
+            .map_err(|e| TxProducerError::Transaction(format!("Gas estimation failed: {}", e)))
+    }
+
+    /// Simulate a transaction via `eth_call` without sending it, from the signer address
+    /// if configured, against the state of the block (number, hash or tag), at the latest
+    /// block if no block is given. Returns the function outputs, or fails with the revert
+    /// of the transaction as it would have executed, e.g. to reproduce a historical failure
+    /// on the state of its block.
+    pub async fn simulate_transaction(
+        &self,
+        function_name: &str,
+        args: &[DynSolValue],
+        block: Option<BlockId>,
+    ) -> Result<Vec<DynSolValue>> {
+        self.ensure_function_permitted(function_name)?;
+
+        let mut call = self.instance
+            .function(function_name, args)
+            .map_err(|e| TxProducerError::ContractCall(format!("Failed to create transaction: {}", e)))?
+            .block(block.unwrap_or_default());
+
+        if let Some(from) = self.provider_manager.signer_address() {
+            call = call.from(from);
+        }
+
+        self.provider_manager
+            .rpc(call.call())
+            .await?
+            .map_err(|e| TxProducerError::Transaction(format!("Transaction simulation failed: {}", e)))
+    }
+
+    /// Generate an EIP-2930 access list of a transaction via `eth_createAccessList`,
+    /// from the signer address if configured. Requires the node to support the RPC method.
+    pub async fn generate_access_list(
+        &self,
+        function_name: &str,
+        args: &[DynSolValue],
+    ) -> Result<AccessListEstimate> {
+        let mut request = TransactionRequest::default()
+            .to(self.address)
+            .input(TransactionInput::new(self.encode_function_data(function_name, args)?));
+
+        if let Some(from) = self.provider_manager.signer_address() {
+            request = request.from(from);
+        }
+
+        let result = self.provider_manager
+            .rpc(self.provider_manager.provider().create_access_list(&request))
+            .await?
+            .map_err(|e| TxProducerError::Transaction(format!("Access list generation failed: {}", e)))?;
+
+        if let Some(error) = result.error {
+            return Err(TxProducerError::Transaction(format!("Access list generation reverted: {}", error)));
+        }
+
+        let gas_without_access_list = self.estimate_gas(function_name, args).await?;
+
+        Ok(AccessListEstimate {
+            access_list: result.access_list,
+            gas_with_access_list: result.gas_used.saturating_to::<u64>(),
+            gas_without_access_list,
+        })
     }
 
     /// Get function by name
@@ -138,6 +749,25 @@ impl ContractClient {
             .ok_or_else(|| TxProducerError::ContractCall(format!("Event '{}' not found in ABI", name)))
     }
 
+    /// Query the logs of the event emitted by the contract in the `from_block..=to_block` range,
+    /// chunked by `ProviderManager::get_logs_chunked`, so wide ranges pass the endpoint limits
+    pub async fn query_events(
+        &self,
+        event_name: &str,
+        from_block: u64,
+        to_block: u64,
+        chunk_size: u64,
+    ) -> Result<ChunkedLogs> {
+        let event = self.get_event(event_name)?;
+        let filter = Filter::new()
+            .address(self.address)
+            .event_signature(event.selector());
+
+        self.provider_manager
+            .get_logs_chunked(&filter, from_block, to_block, chunk_size)
+            .await
+    }
+
     /// List all available functions
     pub fn list_functions(&self) -> Vec<String> {
         self.abi.functions().map(|f| f.name.clone()).collect()
@@ -148,12 +778,15 @@ impl ContractClient {
         self.abi.events().map(|e| e.name.clone()).collect()
     }
 
-    /// Encode function call data
+    /// Encode function call data. Checked against the allowlist and denylist,
+    /// as the calldata is sent by multicalls, access list generation and replacements.
     pub fn encode_function_data(
         &self,
         function_name: &str,
         args: &[DynSolValue],
     ) -> Result<Bytes> {
+        self.ensure_function_permitted(function_name)?;
+
         let function = self.get_function(function_name)?;
         let encoded = function
             .abi_encode_input(args)
@@ -230,11 +863,363 @@ pub mod value_helpers {
             .as_tuple()
             .ok_or_else(|| TxProducerError::Decoding("Expected tuple value".to_string()))
     }
+
+    /// Convert JSON function arguments to DynSolValues by the function ABI inputs
+    pub fn args_from_json(function: &Function, args: &[serde_json::Value]) -> Result<Vec<DynSolValue>> {
+        if args.len() != function.inputs.len() {
+            return Err(TxProducerError::Encoding(format!(
+                "Function '{}' expects {} arguments, got {}", function.name, function.inputs.len(), args.len()
+            )));
+        }
+
+        function.inputs
+            .iter()
+            .zip(args)
+            .enumerate()
+            .map(|(i, (param, arg))| {
+                let path = if param.name.is_empty() { format!("arg{}", i) } else { param.name.clone() };
+                json_to_value(arg, &param.ty, &param.components, &path)
+            })
+            .collect()
+    }
+
+    /// Convert JSON value to DynSolValue of the ABI parameter type.
+    /// Structs (tuples) are JSON objects keyed by the component names, recursively,
+    /// scalars are JSON strings, numbers or booleans.
+    /// Errors name the path of the offending value, e.g. `order.maker`.
+    pub fn from_json(value: &serde_json::Value, param: &Param) -> Result<DynSolValue> {
+        json_to_value(value, &param.ty, &param.components, &param.name)
+    }
+
+    fn json_to_value(value: &serde_json::Value, ty: &str, components: &[Param], path: &str) -> Result<DynSolValue> {
+        if let Some(inner_ty) = ty.strip_suffix(']') {
+            let (element_ty, size) = inner_ty
+                .rsplit_once('[')
+                .ok_or_else(|| TxProducerError::Encoding(format!("{}: invalid type '{}'", path, ty)))?;
+            let items = value
+                .as_array()
+                .ok_or_else(|| TxProducerError::Encoding(format!("{}: expected array of {}", path, element_ty)))?;
+
+            let values = items
+                .iter()
+                .enumerate()
+                .map(|(i, item)| json_to_value(item, element_ty, components, &format!("{}[{}]", path, i)))
+                .collect::<Result<Vec<_>>>()?;
+
+            if size.is_empty() {
+                return Ok(DynSolValue::Array(values));
+            }
+            let size: usize = size
+                .parse()
+                .map_err(|_| TxProducerError::Encoding(format!("{}: invalid type '{}'", path, ty)))?;
+            if values.len() != size {
+                return Err(TxProducerError::Encoding(format!("{}: expected {} items, got {}", path, size, values.len())));
+            }
+            return Ok(DynSolValue::FixedArray(values));
+        }
+
+        if ty == "tuple" {
+            let fields = value
+                .as_object()
+                .ok_or_else(|| TxProducerError::Encoding(format!("{}: expected struct object", path)))?;
+
+            if let Some(unknown) = fields.keys().find(|key| !components.iter().any(|c| &c.name == *key)) {
+                return Err(TxProducerError::Encoding(format!("{}.{}: unknown struct field", path, unknown)));
+            }
+
+            return components
+                .iter()
+                .map(|component| {
+                    let field_path = format!("{}.{}", path, component.name);
+                    let field = fields
+                        .get(&component.name)
+                        .ok_or_else(|| TxProducerError::Encoding(format!("{}: missing struct field", field_path)))?;
+                    json_to_value(field, &component.ty, &component.components, &field_path)
+                })
+                .collect::<Result<Vec<_>>>()
+                .map(DynSolValue::Tuple);
+        }
+
+        let raw = match value {
+            serde_json::Value::String(s) => s.clone(),
+            serde_json::Value::Number(n) => n.to_string(),
+            serde_json::Value::Bool(b) => b.to_string(),
+            _ => return Err(TxProducerError::Encoding(format!("{}: expected {} value", path, ty))),
+        };
+
+        DynSolType::parse(ty)
+            .map_err(|e| TxProducerError::Encoding(format!("{}: invalid type '{}': {}", path, ty, e)))?
+            .coerce_str(&raw)
+            .map_err(|e| TxProducerError::Encoding(format!("{}: invalid {} value '{}': {}", path, ty, raw, e)))
+    }
+
+    /// Convert function outputs to JSON: an object keyed by the output names
+    /// (index for unnamed ones), or the value of a single unnamed output
+    pub fn outputs_to_json(outputs: &[Param], values: &[DynSolValue]) -> serde_json::Value {
+        if let ([output], [value]) = (outputs, values) {
+            if output.name.is_empty() {
+                return to_json(value, output);
+            }
+        }
+
+        named_values_to_json(outputs, values)
+    }
+
+    /// Convert DynSolValue of the ABI parameter type to JSON, inverse of `from_json`.
+    /// Structs (tuples) are objects keyed by the component names, integers are decimal strings
+    /// (lossless for 256 bits), addresses are checksummed, bytes are `0x` prefixed hex.
+    pub fn to_json(value: &DynSolValue, param: &Param) -> serde_json::Value {
+        value_to_json(value, &param.components)
+    }
+
+    fn value_to_json(value: &DynSolValue, components: &[Param]) -> serde_json::Value {
+        use serde_json::Value;
+
+        if let Some(values) = value.as_tuple() {
+            named_values_to_json(components, values)
+        } else if let Some(items) = value.as_array().or_else(|| value.as_fixed_array()) {
+            Value::Array(items.iter().map(|item| value_to_json(item, components)).collect())
+        } else if let Some((uint, _)) = value.as_uint() {
+            Value::String(uint.to_string())
+        } else if let Some((int, _)) = value.as_int() {
+            Value::String(int.to_string())
+        } else if let Some(address) = value.as_address() {
+            Value::String(address.to_string())
+        } else if let Some(b) = value.as_bool() {
+            Value::Bool(b)
+        } else if let Some(s) = value.as_str() {
+            Value::String(s.to_string())
+        } else if let Some((bytes, size)) = value.as_fixed_bytes() {
+            Value::String(format!("0x{}", hex::encode(&bytes[..size.min(bytes.len())])))
+        } else if let Some(bytes) = value.as_bytes() {
+            Value::String(format!("0x{}", hex::encode(bytes)))
+        } else if let Some(function) = value.as_function() {
+            Value::String(format!("0x{}", hex::encode(function)))
+        } else {
+            Value::Null
+        }
+    }
+
+    /// Object of the values keyed by the parameter names, index for unnamed parameters
+    fn named_values_to_json(params: &[Param], values: &[DynSolValue]) -> serde_json::Value {
+        let object = values
+            .iter()
+            .enumerate()
+            .map(|(i, value)| match params.get(i) {
+                Some(param) if !param.name.is_empty() => (param.name.clone(), value_to_json(value, &param.components)),
+                Some(param) => (i.to_string(), value_to_json(value, &param.components)),
+                None => (i.to_string(), value_to_json(value, &[])),
+            })
+            .collect();
+
+        serde_json::Value::Object(object)
+    }
+}
+
+/// Fee increased by the percentage, rounded up so the bump never falls below the threshold
+fn bump_fee(fee: u128, bump_percent: u64) -> u128 {
+    fee.saturating_add((fee.saturating_mul(bump_percent as u128)).div_ceil(100))
+}
+
+/// Address stored in the low 20 bytes of a storage slot, `None` if zero
+fn address_from_slot(value: B256) -> Option<Address> {
+    let address = Address::from_word(value);
+    (!address.is_zero()).then_some(address)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::test_utils::{contract_client, local_provider_manager};
+
+    #[test]
+    fn test_access_list_gas_delta() {
+        let mut estimate = AccessListEstimate {
+            access_list: AccessList::default(),
+            gas_with_access_list: 95_000,
+            gas_without_access_list: 100_000,
+        };
+        assert_eq!(estimate.gas_delta(), 5_000);
+
+        estimate.gas_with_access_list = 102_400;
+        assert_eq!(estimate.gas_delta(), -2_400);
+    }
+
+    #[test]
+    fn test_confirmation_config_for_chain() {
+        let mainnet = ConfirmationConfig::for_chain(1);
+        let base = ConfirmationConfig::for_chain(8453);
+        let anvil = ConfirmationConfig::for_chain(31337);
+        let unknown = ConfirmationConfig::for_chain(2442);
+
+        assert!(base.poll_interval < mainnet.poll_interval);
+        assert!(base.timeout < mainnet.timeout);
+        assert!(anvil.poll_interval < base.poll_interval);
+        assert_eq!(unknown.poll_interval, Duration::from_secs(1));
+        assert_eq!(unknown.timeout, Duration::from_secs(180));
+    }
+
+    fn order_param() -> Param {
+        serde_json::from_value(serde_json::json!({
+            "name": "order",
+            "type": "tuple",
+            "components": [
+                { "name": "maker", "type": "address" },
+                { "name": "amount", "type": "uint256" },
+                { "name": "fees", "type": "tuple[]", "components": [
+                    { "name": "recipient", "type": "address" },
+                    { "name": "bps", "type": "uint16" }
+                ]}
+            ]
+        })).unwrap()
+    }
+
+    #[test]
+    fn test_struct_from_json() {
+        let maker = Address::repeat_byte(0x11);
+        let recipient = Address::repeat_byte(0x22);
+        let value = value_helpers::from_json(&serde_json::json!({
+            "maker": maker.to_string(),
+            "amount": "1000000000000000000",
+            "fees": [{ "recipient": recipient.to_string(), "bps": 30 }]
+        }), &order_param()).unwrap();
+
+        assert_eq!(value, DynSolValue::Tuple(vec![
+            DynSolValue::Address(maker),
+            DynSolValue::Uint(U256::from(10u64).pow(U256::from(18)), 256),
+            DynSolValue::Array(vec![DynSolValue::Tuple(vec![
+                DynSolValue::Address(recipient),
+                DynSolValue::Uint(U256::from(30), 16),
+            ])]),
+        ]));
+    }
+
+    #[test]
+    fn test_struct_from_json_field_errors() {
+        let error = |value: serde_json::Value| value_helpers::from_json(&value, &order_param()).unwrap_err().to_string();
+        let maker = Address::repeat_byte(0x11).to_string();
+
+        assert!(error(serde_json::json!({ "amount": "1", "fees": [] })).contains("order.maker: missing struct field"));
+        assert!(error(serde_json::json!({ "maker": maker, "amount": "1", "fees": [], "taker": maker }))
+            .contains("order.taker: unknown struct field"));
+        assert!(error(serde_json::json!({ "maker": maker, "amount": "1", "fees": [{ "recipient": maker }] }))
+            .contains("order.fees[0].bps: missing struct field"));
+        assert!(error(serde_json::json!({ "maker": "0x1234", "amount": "1", "fees": [] })).contains("order.maker: invalid address"));
+    }
+
+    #[test]
+    fn test_struct_to_json_typed() {
+        #[derive(Debug, Deserialize, PartialEq)]
+        struct Fee {
+            recipient: Address,
+            bps: U256,
+        }
+
+        #[derive(Debug, Deserialize, PartialEq)]
+        struct Order {
+            maker: Address,
+            amount: U256,
+            fees: Vec<Fee>,
+        }
+
+        let maker = Address::repeat_byte(0x11);
+        let recipient = Address::repeat_byte(0x22);
+        let json = serde_json::json!({
+            "maker": maker.to_string(),
+            "amount": "1000000000000000000",
+            "fees": [{ "recipient": recipient.to_string(), "bps": "30" }]
+        });
+        let param = order_param();
+        let value = value_helpers::from_json(&json, &param).unwrap();
+
+        // Round trip
+        assert_eq!(value_helpers::to_json(&value, &param), json);
+
+        // Named outputs keyed by name, single unnamed output unwrapped
+        let outputs = vec![param.clone()];
+        let named = value_helpers::outputs_to_json(&outputs, std::slice::from_ref(&value));
+        assert_eq!(named["order"]["fees"][0]["bps"], "30");
+
+        let unnamed = Param { name: String::new(), ..param };
+        let order: Order = serde_json::from_value(value_helpers::outputs_to_json(&[unnamed], &[value])).unwrap();
+        assert_eq!(order, Order {
+            maker,
+            amount: U256::from(10u64).pow(U256::from(18)),
+            fees: vec![Fee { recipient, bps: U256::from(30) }],
+        });
+    }
+
+    #[test]
+    fn test_unnamed_outputs_to_json() {
+        let outputs: Vec<Param> = serde_json::from_value(serde_json::json!([
+            { "name": "", "type": "bool" },
+            { "name": "", "type": "bytes4" }
+        ])).unwrap();
+        let values = [DynSolValue::Bool(true), DynSolValue::FixedBytes(B256::ZERO, 4)];
+
+        assert_eq!(
+            value_helpers::outputs_to_json(&outputs, &values),
+            serde_json::json!({ "0": true, "1": "0x00000000" })
+        );
+    }
+
+    #[test]
+    fn test_bump_fee() {
+        assert_eq!(bump_fee(1_000_000_000, 10), 1_100_000_000);
+        assert_eq!(bump_fee(1_000_000_000, 25), 1_250_000_000);
+        // Rounded up, 10% of 15 is 1.5
+        assert_eq!(bump_fee(15, 10), 17);
+        assert_eq!(bump_fee(0, 10), 0);
+        assert_eq!(bump_fee(u128::MAX, 10), u128::MAX);
+    }
+
+    #[test]
+    fn test_eip1967_implementation_slot() {
+        let slot = U256::from_be_bytes(alloy_primitives::keccak256("eip1967.proxy.implementation").0) - U256::from(1);
+        assert_eq!(EIP1967_IMPLEMENTATION_SLOT, B256::from(slot.to_be_bytes::<32>()));
+
+        let implementation = Address::repeat_byte(0x11);
+        assert_eq!(address_from_slot(implementation.into_word()), Some(implementation));
+        assert_eq!(address_from_slot(B256::ZERO), None);
+    }
+
+    #[tokio::test]
+    async fn test_function_allowlist_and_denylist() {
+        let abi = r#"[{"type":"function","name":"setValue","stateMutability":"nonpayable","inputs":[{"name":"value","type":"uint256"}],"outputs":[]},{"type":"function","name":"withdraw","stateMutability":"nonpayable","inputs":[],"outputs":[]}]"#;
+        let provider_manager = local_provider_manager();
+
+        let unrestricted = contract_client(Address::repeat_byte(0x01), abi, Arc::clone(&provider_manager)).await;
+        assert!(unrestricted.is_function_permitted("setValue"));
+        assert!(unrestricted.is_function_permitted("withdraw"));
+
+        let allowlisted = contract_client(Address::repeat_byte(0x01), abi, provider_manager).await
+            .with_allowed_functions(vec!["setValue".to_string(), "withdraw".to_string()])
+            .with_denied_functions(vec!["withdraw".to_string()]);
+
+        assert!(allowlisted.encode_function_data("setValue", &[DynSolValue::Uint(U256::from(1), 256)]).is_ok());
+        assert!(matches!(
+            allowlisted.encode_function_data("withdraw", &[]),
+            Err(TxProducerError::FunctionNotPermitted { function, .. }) if function == "withdraw"
+        ));
+        // Rejected before any RPC request is made
+        assert!(matches!(
+            allowlisted.call_function("withdraw", &[]).await,
+            Err(TxProducerError::FunctionNotPermitted { .. })
+        ));
+        assert!(matches!(
+            allowlisted.send_transaction("unknown", &[]).await,
+            Err(TxProducerError::FunctionNotPermitted { .. })
+        ));
+    }
+
+    #[test]
+    fn test_verify_deployed_is_opt_in() {
+        let config: ContractConfig = serde_json::from_str(
+            r#"{"address":"0x0101010101010101010101010101010101010101","abi_path":"abi/MyContract.json"}"#,
+        ).unwrap();
+        assert!(!config.verify_deployed);
+        assert!(config.verify_deployed(true).verify_deployed);
+    }
 
     #[tokio::test]
     async fn test_load_abi_invalid_path() {
@@ -0,0 +1,92 @@
+//! ERC20 interface for transfers precheck
+
+use alloy_primitives::{Address, U256};
+use alloy_sol_types::{sol, SolCall};
+
+sol! {
+    interface IERC20 {
+        function balanceOf(address account) external view returns (uint256);
+        function allowance(address owner, address spender) external view returns (uint256);
+        function transfer(address to, uint256 amount) external returns (bool);
+        function transferFrom(address from, address to, uint256 amount) external returns (bool);
+    }
+}
+
+/// ERC20 transfer recognized by the calldata selector
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Erc20Transfer {
+    /// `transfer(address,uint256)` from the caller balance
+    Transfer { to: Address, amount: U256 },
+    /// `transferFrom(address,address,uint256)` from the `from` balance, within the caller allowance
+    TransferFrom { from: Address, to: Address, amount: U256 },
+}
+
+impl Erc20Transfer {
+    /// Recognize an ERC20 transfer calldata, `None` for any other call
+    pub fn from_calldata(calldata: &[u8]) -> Option<Self> {
+        let selector: [u8; 4] = calldata.get(..4)?.try_into().ok()?;
+
+        match selector {
+            IERC20::transferCall::SELECTOR => {
+                let call = IERC20::transferCall::abi_decode(calldata).ok()?;
+                Some(Self::Transfer { to: call.to, amount: call.amount })
+            }
+            IERC20::transferFromCall::SELECTOR => {
+                let call = IERC20::transferFromCall::abi_decode(calldata).ok()?;
+                Some(Self::TransferFrom { from: call.from, to: call.to, amount: call.amount })
+            }
+            _ => None,
+        }
+    }
+
+    /// Account whose balance is transferred
+    pub fn holder(&self, caller: Address) -> Address {
+        match self {
+            Self::Transfer { .. } => caller,
+            Self::TransferFrom { from, .. } => *from,
+        }
+    }
+
+    pub fn amount(&self) -> U256 {
+        match self {
+            Self::Transfer { amount, .. } | Self::TransferFrom { amount, .. } => *amount,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_selectors() {
+        assert_eq!(IERC20::transferCall::SELECTOR, [0xa9, 0x05, 0x9c, 0xbb]);
+        assert_eq!(IERC20::transferFromCall::SELECTOR, [0x23, 0xb8, 0x72, 0xdd]);
+    }
+
+    #[test]
+    fn test_transfer_from_calldata() {
+        let from = Address::repeat_byte(0x01);
+        let to = Address::repeat_byte(0x02);
+        let caller = Address::repeat_byte(0x03);
+        let amount = U256::from(1000);
+
+        let transfer = Erc20Transfer::from_calldata(&IERC20::transferCall { to, amount }.abi_encode()).unwrap();
+        assert_eq!(transfer, Erc20Transfer::Transfer { to, amount });
+        assert_eq!(transfer.holder(caller), caller);
+
+        let transfer_from = Erc20Transfer::from_calldata(&IERC20::transferFromCall { from, to, amount }.abi_encode()).unwrap();
+        assert_eq!(transfer_from, Erc20Transfer::TransferFrom { from, to, amount });
+        assert_eq!(transfer_from.holder(caller), from);
+        assert_eq!(transfer_from.amount(), amount);
+    }
+
+    #[test]
+    fn test_other_calldata_not_recognized() {
+        let approve = [0x09, 0x5e, 0xa7, 0xb3];
+        assert_eq!(Erc20Transfer::from_calldata(&approve), None);
+        assert_eq!(Erc20Transfer::from_calldata(&[0xa9, 0x05]), None);
+        // Recognized selector with malformed arguments
+        assert_eq!(Erc20Transfer::from_calldata(&IERC20::transferCall::SELECTOR), None);
+    }
+}
@@ -1,5 +1,7 @@
 //! Error types for the transaction producer library
 
+use alloy_primitives::{Address, B256, U256};
+use std::time::Duration;
 use thiserror::Error;
 
 /// Result type alias
@@ -44,6 +46,52 @@ pub enum TxProducerError {
     #[error("Invalid input: {0}")]
     InvalidInput(String),
 
+    /// ERC20 transfer amount exceeds the holder balance
+    #[error("Insufficient ERC20 balance of token {token}: holder {holder} has {balance}, transfer requires {required}")]
+    InsufficientBalance {
+        token: Address,
+        holder: Address,
+        balance: U256,
+        required: U256,
+    },
+
+    /// ERC20 `transferFrom` amount exceeds the spender allowance
+    #[error("Insufficient ERC20 allowance of token {token}: owner {owner} allows spender {spender} {allowance}, transfer requires {required}")]
+    InsufficientAllowance {
+        token: Address,
+        owner: Address,
+        spender: Address,
+        allowance: U256,
+        required: U256,
+    },
+
+    /// Sent transaction not mined within the confirmation timeout, it may still be pending
+    #[error("Transaction {tx_hash} not confirmed within {timeout:?}")]
+    ConfirmationTimeout {
+        tx_hash: B256,
+        timeout: Duration,
+    },
+
+    /// RPC circuit breaker is open after consecutive endpoint failures, the call wasn't made
+    #[error("RPC circuit breaker open after {failures} consecutive failures, retry in {retry_in:?}")]
+    CircuitOpen {
+        failures: u32,
+        retry_in: Duration,
+    },
+
+    /// Function excluded by the contract client allowlist or denylist, the call wasn't made
+    #[error("Function '{function}' is not permitted on contract {contract}")]
+    FunctionNotPermitted {
+        contract: Address,
+        function: String,
+    },
+
+    /// No contract code at the configured address, wrong network or not deployed yet
+    #[error("No contract code at {address}, wrong network or contract not deployed")]
+    NoContractCode {
+        address: Address,
+    },
+
     /// Internal error
     #[error("Internal error: {0}")]
     Internal(String),
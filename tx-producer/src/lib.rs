@@ -7,9 +7,19 @@
 //!
 //! - Universal contract interaction using JSON ABI
 //! - Transaction building and signing
-//! - **Batch transaction support** - Execute multiple transactions efficiently
+//! - **Batch transaction support** - Execute multiple transactions efficiently, across several contracts, with per-transaction gas caps
 //! - Provider management
 //! - Read and write operations
+//! - Batched reads via Multicall3
+//! - Opt-in ERC20 balance/allowance precheck before transfers
+//! - EIP-2930 access lists generation and inclusion
+//! - Pluggable nonce allocation, for processes sharing a signing key
+//! - Stuck transactions cancellation and speed up
+//! - Configurable transaction confirmation polling and timeout
+//! - Fee history based EIP-1559 fees suggestion
+//! - WebSocket subscriptions to pending transactions, logs and blocks
+//! - RPC circuit breaker, fast-failing calls to a failing endpoint
+//! - Raw storage reads and EIP-1967 proxy implementation lookup
 //! - Event handling
 //!
 //! # Example
@@ -25,6 +35,8 @@
 //!         rpc_url: "http://localhost:8545".to_string(),
 //!         chain_id: 1,
 //!         timeout_seconds: 30,
+//!         ws_url: None,
+//!         headers: Default::default(),
 //!     };
 //!
 //!     // Create provider manager with signer
@@ -35,6 +47,7 @@
 //!     let contract_config = ContractConfig {
 //!         address: "0x...".parse().unwrap(),
 //!         abi_path: "path/to/contract.json".to_string(),
+//!         verify_deployed: false,
 //!     };
 //!
 //!     // Create contract client
@@ -70,15 +83,31 @@
 //! }
 //! ```
 
+pub mod circuit_breaker;
 pub mod contract;
+pub mod erc20;
 pub mod error;
+pub mod multicall;
+pub mod nonce;
 pub mod provider;
+pub mod standard_events;
 pub mod transaction;
 
+#[cfg(test)]
+mod test_utils;
+
 // Re-export commonly used types
-pub use contract::{ContractClient, ContractConfig, value_helpers};
+pub use circuit_breaker::{CircuitBreaker, CircuitBreakerConfig, CircuitState};
+pub use contract::{
+    AccessListEstimate, ConfirmationConfig, ContractClient, ContractConfig, Replacement,
+    EIP1967_IMPLEMENTATION_SLOT, MIN_REPLACEMENT_FEE_BUMP_PERCENT, value_helpers,
+};
+pub use erc20::Erc20Transfer;
 pub use error::{Result, TxProducerError};
-pub use provider::{ProviderConfig, ProviderManager, TxProvider};
+pub use multicall::MULTICALL3_ADDRESS;
+pub use nonce::{NodeNonceManager, NonceManager};
+pub use provider::{ChunkedLogs, FeeSuggestion, LogsChunking, ProviderConfig, ProviderManager, TxProvider};
+pub use standard_events::StandardEvent;
 pub use transaction::{
     CallBuilder, TransactionBuilder, TransactionParams,
     BatchTransaction, BatchTransactionBuilder, BatchTransactionResult, BatchResult,
@@ -0,0 +1,68 @@
+//! Nonce allocation for sent transactions
+
+use alloy_primitives::Address;
+use alloy_provider::Provider;
+use async_trait::async_trait;
+use std::collections::HashMap;
+use std::sync::Arc;
+use tokio::sync::Mutex;
+
+use crate::error::{TxProducerError, Result};
+use crate::provider::TxProvider;
+
+/// Source of transaction nonces.
+///
+/// Every allocated nonce must be distinct, also across processes sharing the signing key,
+/// so implementations backed by a shared store (Redis, database) allocate nonces atomically.
+#[async_trait]
+pub trait NonceManager: Send + Sync {
+    /// Allocate the next nonce of the address
+    async fn next_nonce(&self, address: Address) -> Result<u64>;
+
+    /// Return a nonce allocated for a transaction which wasn't broadcast, no-op by default
+    async fn release_nonce(&self, _address: Address, _nonce: u64) -> Result<()> {
+        Ok(())
+    }
+}
+
+/// Default nonce manager: queries the node pending nonce on the first allocation of the address,
+/// then allocates the next nonces locally, so concurrent transactions of the process don't collide.
+/// Only safe when this process is the single writer of the signing key.
+pub struct NodeNonceManager {
+    provider: Arc<TxProvider>,
+    next_nonces: Mutex<HashMap<Address, u64>>,
+}
+
+impl NodeNonceManager {
+    pub fn new(provider: Arc<TxProvider>) -> Self {
+        Self {
+            provider,
+            next_nonces: Mutex::new(HashMap::new()),
+        }
+    }
+}
+
+#[async_trait]
+impl NonceManager for NodeNonceManager {
+    async fn next_nonce(&self, address: Address) -> Result<u64> {
+        let mut next_nonces = self.next_nonces.lock().await;
+
+        let nonce = match next_nonces.get(&address) {
+            Some(nonce) => *nonce,
+            None => self.provider
+                .get_transaction_count(address)
+                .pending()
+                .await
+                .map_err(|e| TxProducerError::Provider(format!("Failed to get nonce of {}: {}", address, e)))?,
+        };
+
+        next_nonces.insert(address, nonce + 1);
+        Ok(nonce)
+    }
+
+    /// Resynchronize with the node on the next allocation, as later nonces may be allocated already
+    async fn release_nonce(&self, address: Address, _nonce: u64) -> Result<()> {
+        self.next_nonces.lock().await.remove(&address);
+        Ok(())
+    }
+}
@@ -0,0 +1,150 @@
+//! Typed decoding of the ERC20, ERC721 and ERC1155 token standard events
+
+use alloy_primitives::{B256, Log, LogData};
+use alloy_sol_types::{sol, SolEvent};
+
+sol! {
+    #[sol(all_derives)]
+    interface ERC20 {
+        event Transfer(address indexed from, address indexed to, uint256 value);
+        event Approval(address indexed owner, address indexed spender, uint256 value);
+    }
+
+    #[sol(all_derives)]
+    interface ERC721 {
+        event Transfer(address indexed from, address indexed to, uint256 indexed tokenId);
+        event Approval(address indexed owner, address indexed approved, uint256 indexed tokenId);
+        event ApprovalForAll(address indexed owner, address indexed operator, bool approved);
+    }
+
+    #[sol(all_derives)]
+    interface ERC1155 {
+        event TransferSingle(address indexed operator, address indexed from, address indexed to, uint256 id, uint256 value);
+        event TransferBatch(address indexed operator, address indexed from, address indexed to, uint256[] ids, uint256[] values);
+    }
+}
+
+/// `Transfer(address,address,uint256)` topic0, of both ERC20 and ERC721
+pub const TRANSFER_TOPIC: B256 = ERC20::Transfer::SIGNATURE_HASH;
+/// `Approval(address,address,uint256)` topic0, of both ERC20 and ERC721
+pub const APPROVAL_TOPIC: B256 = ERC20::Approval::SIGNATURE_HASH;
+/// `ApprovalForAll(address,address,bool)` topic0, of both ERC721 and ERC1155
+pub const APPROVAL_FOR_ALL_TOPIC: B256 = ERC721::ApprovalForAll::SIGNATURE_HASH;
+/// `TransferSingle(address,address,address,uint256,uint256)` topic0 of ERC1155
+pub const TRANSFER_SINGLE_TOPIC: B256 = ERC1155::TransferSingle::SIGNATURE_HASH;
+/// `TransferBatch(address,address,address,uint256[],uint256[])` topic0 of ERC1155
+pub const TRANSFER_BATCH_TOPIC: B256 = ERC1155::TransferBatch::SIGNATURE_HASH;
+
+/// Token standard event decoded from a log.
+/// ERC20 and ERC721 `Transfer` and `Approval` share the signature, they're told apart
+/// by the indexed amount (ERC20) or token ID (ERC721), i.e. by the topics count.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum StandardEvent {
+    Erc20Transfer(ERC20::Transfer),
+    Erc20Approval(ERC20::Approval),
+    Erc721Transfer(ERC721::Transfer),
+    Erc721Approval(ERC721::Approval),
+    /// Operator approval of ERC721 or ERC1155, the same event in both standards
+    ApprovalForAll(ERC721::ApprovalForAll),
+    Erc1155TransferSingle(ERC1155::TransferSingle),
+    Erc1155TransferBatch(ERC1155::TransferBatch),
+}
+
+impl StandardEvent {
+    /// Decode the log data, `None` for any other event or a malformed standard event
+    pub fn from_log_data(data: &LogData) -> Option<Self> {
+        let topic0 = *data.topics().first()?;
+        let topics = data.topics().len();
+
+        match topic0 {
+            topic if topic == TRANSFER_TOPIC && topics == 3 => {
+                ERC20::Transfer::decode_log_data(data).ok().map(Self::Erc20Transfer)
+            }
+            topic if topic == TRANSFER_TOPIC && topics == 4 => {
+                ERC721::Transfer::decode_log_data(data).ok().map(Self::Erc721Transfer)
+            }
+            topic if topic == APPROVAL_TOPIC && topics == 3 => {
+                ERC20::Approval::decode_log_data(data).ok().map(Self::Erc20Approval)
+            }
+            topic if topic == APPROVAL_TOPIC && topics == 4 => {
+                ERC721::Approval::decode_log_data(data).ok().map(Self::Erc721Approval)
+            }
+            topic if topic == APPROVAL_FOR_ALL_TOPIC => {
+                ERC721::ApprovalForAll::decode_log_data(data).ok().map(Self::ApprovalForAll)
+            }
+            topic if topic == TRANSFER_SINGLE_TOPIC => {
+                ERC1155::TransferSingle::decode_log_data(data).ok().map(Self::Erc1155TransferSingle)
+            }
+            topic if topic == TRANSFER_BATCH_TOPIC => {
+                ERC1155::TransferBatch::decode_log_data(data).ok().map(Self::Erc1155TransferBatch)
+            }
+            _ => None,
+        }
+    }
+
+    /// Decode the log, the emitting token contract is `log.address`
+    pub fn from_log(log: &Log) -> Option<Self> {
+        Self::from_log_data(&log.data)
+    }
+
+    /// Decode the log of an RPC response or subscription
+    pub fn from_rpc_log(log: &alloy::rpc::types::Log) -> Option<Self> {
+        Self::from_log(&log.inner)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::{b256, Address, U256};
+
+    #[test]
+    fn test_topics() {
+        assert_eq!(TRANSFER_TOPIC, b256!("ddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef"));
+        assert_eq!(APPROVAL_TOPIC, b256!("8c5be1e5ebec7d5bd14f71427d1e84f3dd0314c0f7b2291e5b200ac8c7c3b925"));
+        assert_eq!(APPROVAL_FOR_ALL_TOPIC, b256!("17307eab39ab6107e8899845ad3d59bd9653f200f220920489ca2b5937696c31"));
+        assert_eq!(TRANSFER_SINGLE_TOPIC, b256!("c3d58168c5ae7397731d063d5bbf3d657854427343f4c083240f7aacaa2d0f62"));
+        assert_eq!(TRANSFER_BATCH_TOPIC, b256!("4a39dc06d4c0dbc64b70af90fd698a233a518aa5d07e595d983b8c0526c8f7fb"));
+        assert_eq!(ERC721::Transfer::SIGNATURE_HASH, TRANSFER_TOPIC);
+        assert_eq!(ERC721::Approval::SIGNATURE_HASH, APPROVAL_TOPIC);
+    }
+
+    #[test]
+    fn test_transfer_by_topics_count() {
+        let from = Address::repeat_byte(0x01);
+        let to = Address::repeat_byte(0x02);
+
+        let erc20 = ERC20::Transfer { from, to, value: U256::from(1000) };
+        assert_eq!(
+            StandardEvent::from_log_data(&erc20.encode_log_data()),
+            Some(StandardEvent::Erc20Transfer(erc20))
+        );
+
+        let erc721 = ERC721::Transfer { from, to, tokenId: U256::from(7) };
+        assert_eq!(
+            StandardEvent::from_log_data(&erc721.encode_log_data()),
+            Some(StandardEvent::Erc721Transfer(erc721))
+        );
+    }
+
+    #[test]
+    fn test_erc1155_transfer_batch() {
+        let event = ERC1155::TransferBatch {
+            operator: Address::repeat_byte(0x03),
+            from: Address::ZERO,
+            to: Address::repeat_byte(0x02),
+            ids: vec![U256::from(1), U256::from(2)],
+            values: vec![U256::from(10), U256::from(20)],
+        };
+        let log = Log { address: Address::repeat_byte(0x04), data: event.encode_log_data() };
+
+        assert_eq!(StandardEvent::from_log(&log), Some(StandardEvent::Erc1155TransferBatch(event)));
+    }
+
+    #[test]
+    fn test_other_events() {
+        let data = LogData::new_unchecked(vec![B256::repeat_byte(0xff)], Default::default());
+        assert_eq!(StandardEvent::from_log_data(&data), None);
+        assert_eq!(StandardEvent::from_log_data(&LogData::default()), None);
+    }
+}
@@ -0,0 +1,40 @@
+//! Fixtures shared by the unit tests
+
+use alloy_primitives::Address;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use crate::contract::{ContractClient, ContractConfig};
+use crate::provider::{ProviderConfig, ProviderManager};
+
+/// Provider config of a local dev node, without WebSocket endpoint and headers
+pub(crate) fn local_provider_config() -> ProviderConfig {
+    ProviderConfig {
+        rpc_url: "http://localhost:8545".to_string(),
+        chain_id: 31337,
+        timeout_seconds: 30,
+        ws_url: None,
+        headers: Default::default(),
+    }
+}
+
+/// Provider manager of a local dev node, no request is made on construction
+pub(crate) fn local_provider_manager() -> Arc<ProviderManager> {
+    Arc::new(ProviderManager::new(local_provider_config()).unwrap())
+}
+
+/// Temporary ABI files written so far, to name each one uniquely as tests run concurrently
+static ABI_FILES: AtomicUsize = AtomicUsize::new(0);
+
+/// Contract client at the address with the JSON ABI, loaded from a temporary ABI file
+pub(crate) async fn contract_client(address: Address, abi: &str, provider_manager: Arc<ProviderManager>) -> ContractClient {
+    let abi_file = ABI_FILES.fetch_add(1, Ordering::Relaxed);
+    let abi_path = std::env::temp_dir().join(format!("tx-producer-abi-{}-{}.json", std::process::id(), abi_file));
+    std::fs::write(&abi_path, abi).unwrap();
+    let contract = ContractClient::new(
+        ContractConfig { address, abi_path: abi_path.to_string_lossy().to_string(), verify_deployed: false },
+        provider_manager,
+    ).await.unwrap();
+    std::fs::remove_file(&abi_path).unwrap();
+    contract
+}
@@ -1,16 +1,19 @@
 //! Transaction building and signing
 
+use alloy::eips::eip2930::AccessList;
 use alloy_dyn_abi::DynSolValue;
 use alloy_primitives::{Address, B256, Bytes, U256};
 use alloy_provider::Provider;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::sync::Arc;
+use std::time::Duration;
 use tokio::sync::Semaphore;
 use tracing::{info, warn, error};
 
-use crate::contract::ContractClient;
+use crate::contract::{value_helpers, ConfirmationConfig, ContractClient, SendOptions};
 use crate::error::{TxProducerError, Result};
+use crate::provider::FeeSuggestion;
 
 /// Transaction parameters
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -31,6 +34,10 @@ pub struct TransactionParams {
 pub struct TransactionBuilder<'a> {
     contract: &'a ContractClient,
     params: TransactionParams,
+    erc20_precheck: bool,
+    access_list: Option<AccessList>,
+    fees: Option<FeeSuggestion>,
+    confirmation: ConfirmationConfig,
 }
 
 impl<'a> TransactionBuilder<'a> {
@@ -45,6 +52,10 @@ impl<'a> TransactionBuilder<'a> {
                 gas_price: None,
                 value: None,
             },
+            erc20_precheck: false,
+            access_list: None,
+            fees: None,
+            confirmation: contract.default_confirmation(),
         }
     }
 
@@ -78,13 +89,60 @@ impl<'a> TransactionBuilder<'a> {
         self
     }
 
+    /// Check the signer ERC20 balance (and allowance for `transferFrom`) before sending
+    /// `transfer`/`transferFrom` calls, failing with `InsufficientBalance`/`InsufficientAllowance`
+    /// instead of a reverted transaction. Opt-in, as not every `transfer` function is ERC20.
+    pub fn with_erc20_precheck(mut self, enabled: bool) -> Self {
+        self.erc20_precheck = enabled;
+        self
+    }
+
+    /// Include an EIP-2930 access list in the sent transaction,
+    /// e.g. generated by `ContractClient::generate_access_list`
+    pub fn with_access_list(mut self, access_list: AccessList) -> Self {
+        self.access_list = Some(access_list);
+        self
+    }
+
+    /// Send with the EIP-1559 fees, e.g. by `ProviderManager::suggest_fees`,
+    /// instead of the ones estimated by the provider gas filler
+    pub fn with_fees(mut self, fees: FeeSuggestion) -> Self {
+        self.fees = Some(fees);
+        self
+    }
+
+    /// Interval between receipt requests while waiting for the transaction to be mined,
+    /// by default chain-aware (see `ConfirmationConfig::for_chain`)
+    pub fn with_confirmation_poll_interval(mut self, poll_interval: Duration) -> Self {
+        self.confirmation.poll_interval = poll_interval;
+        self
+    }
+
+    /// Time to wait for the transaction to be mined, failing with `ConfirmationTimeout`
+    /// carrying the pending transaction hash, to keep waiting or replace it
+    pub fn with_confirmation_timeout(mut self, timeout: Duration) -> Self {
+        self.confirmation.timeout = timeout;
+        self
+    }
+
     /// Build and send the transaction
     pub async fn send(self) -> Result<B256> {
         // Convert JSON values to DynSolValue
         let args = self.json_to_dyn_sol_values(&self.params.args)?;
 
+        if self.erc20_precheck {
+            let calldata = self.contract.encode_function_data(&self.params.function_name, &args)?;
+            self.contract.erc20_precheck(&calldata).await?;
+        }
+
         // Send transaction
-        self.contract.send_transaction(&self.params.function_name, &args).await
+        self.contract
+            .send(&self.params.function_name, &args, SendOptions {
+                access_list: self.access_list,
+                fees: self.fees,
+                confirmation: Some(self.confirmation),
+            })
+            .await
     }
 
     /// Encode transaction data without sending
@@ -96,49 +154,11 @@ impl<'a> TransactionBuilder<'a> {
         self.contract.encode_function_data(&self.params.function_name, &args)
     }
 
-    /// Convert JSON values to DynSolValue
+    /// Convert JSON values to DynSolValues by the function ABI inputs, structs are
+    /// JSON objects keyed by their field names (see `value_helpers::args_from_json`)
     fn json_to_dyn_sol_values(&self, values: &[serde_json::Value]) -> Result<Vec<DynSolValue>> {
-        values
-            .iter()
-            .map(|v| self.json_to_dyn_sol_value(v))
-            .collect()
-    }
-
-    /// Convert a single JSON value to DynSolValue
-    fn json_to_dyn_sol_value(&self, value: &serde_json::Value) -> Result<DynSolValue> {
-        match value {
-            serde_json::Value::Bool(b) => Ok(DynSolValue::Bool(*b)),
-            serde_json::Value::Number(n) => {
-                if let Some(u) = n.as_u64() {
-                    Ok(DynSolValue::Uint(U256::from(u).into(), 256))
-                } else if let Some(i) = n.as_i64() {
-                    Ok(DynSolValue::Int(U256::from(i as u64).into(), 256))
-                } else {
-                    Err(TxProducerError::Encoding("Invalid number format".to_string()))
-                }
-            }
-            serde_json::Value::String(s) => {
-                // Try to parse as address
-                if s.starts_with("0x") && s.len() == 42 {
-                    let addr: Address = s.parse()
-                        .map_err(|e| TxProducerError::Encoding(format!("Invalid address: {}", e)))?;
-                    Ok(DynSolValue::Address(addr))
-                } else if s.starts_with("0x") {
-                    // Assume it's bytes
-                    let bytes = hex::decode(&s[2..])
-                        .map_err(|e| TxProducerError::Encoding(format!("Invalid hex: {}", e)))?;
-                    Ok(DynSolValue::Bytes(bytes))
-                } else {
-                    // String value
-                    Ok(DynSolValue::String(s.clone()))
-                }
-            }
-            serde_json::Value::Array(arr) => {
-                let values = self.json_to_dyn_sol_values(arr)?;
-                Ok(DynSolValue::Array(values))
-            }
-            _ => Err(TxProducerError::Encoding("Unsupported JSON value type".to_string())),
-        }
+        let function = self.contract.get_function(&self.params.function_name)?;
+        value_helpers::args_from_json(function, values)
     }
 }
 
@@ -187,7 +207,7 @@ impl<'a> CallBuilder<'a> {
 pub struct BatchTransaction {
     /// Unique identifier for this transaction
     pub id: String,
-    /// Contract address (if different from batch default)
+    /// Target contract address (if different from batch default), registered with `with_contract`
     pub contract_address: Option<Address>,
     /// Function name
     pub function_name: String,
@@ -199,6 +219,8 @@ pub struct BatchTransaction {
     pub gas_price: Option<U256>,
     /// Optional value to send
     pub value: Option<U256>,
+    /// Optional gas cap: the transaction is skipped, not sent, if its gas estimate exceeds it
+    pub max_gas: Option<u64>,
 }
 
 /// Result of a single transaction in a batch
@@ -208,7 +230,10 @@ pub struct BatchTransactionResult {
     pub id: String,
     /// Success status
     pub success: bool,
-    /// Transaction hash (if successful)
+    /// Not sent, its gas estimate exceeding its `max_gas`
+    #[serde(default)]
+    pub skipped: bool,
+    /// Transaction hash (if successful, or sent but not confirmed within the timeout)
     pub tx_hash: Option<B256>,
     /// Error message (if failed)
     pub error: Option<String>,
@@ -216,6 +241,27 @@ pub struct BatchTransactionResult {
     pub gas_used: Option<u64>,
 }
 
+impl BatchTransactionResult {
+    fn failed(tx: &BatchTransaction, error: String) -> Self {
+        Self {
+            id: tx.id.clone(),
+            success: false,
+            skipped: false,
+            tx_hash: None,
+            error: Some(error),
+            gas_used: None,
+        }
+    }
+
+    /// Skipped result of the transaction if the gas estimate exceeds the cap
+    fn over_gas_cap(tx: &BatchTransaction, estimate: u64, max_gas: u64) -> Option<Self> {
+        (estimate > max_gas).then(|| Self {
+            skipped: true,
+            ..Self::failed(tx, format!("Gas estimate {} exceeds max gas {}", estimate, max_gas))
+        })
+    }
+}
+
 /// Batch transaction execution result
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BatchResult {
@@ -225,6 +271,9 @@ pub struct BatchResult {
     pub successful: usize,
     /// Number of failed transactions
     pub failed: usize,
+    /// Number of transactions skipped, their gas estimate exceeding their `max_gas`
+    #[serde(default)]
+    pub skipped: usize,
     /// Individual transaction results
     pub results: Vec<BatchTransactionResult>,
     /// Total gas used
@@ -234,13 +283,23 @@ pub struct BatchResult {
 impl BatchResult {
     /// Check if all transactions succeeded
     pub fn all_succeeded(&self) -> bool {
-        self.failed == 0
+        self.failed == 0 && self.skipped == 0
     }
 
     /// Get successful transaction hashes
     pub fn successful_hashes(&self) -> Vec<B256> {
         self.results
             .iter()
+            .filter(|r| r.success)
+            .filter_map(|r| r.tx_hash)
+            .collect()
+    }
+
+    /// Get hashes of transactions not confirmed within the timeout, which may still be pending
+    pub fn pending_hashes(&self) -> Vec<B256> {
+        self.results
+            .iter()
+            .filter(|r| !r.success)
             .filter_map(|r| r.tx_hash)
             .collect()
     }
@@ -249,7 +308,16 @@ impl BatchResult {
     pub fn failed_ids(&self) -> Vec<String> {
         self.results
             .iter()
-            .filter(|r| !r.success)
+            .filter(|r| !r.success && !r.skipped)
+            .map(|r| r.id.clone())
+            .collect()
+    }
+
+    /// Get IDs of transactions skipped over their gas cap
+    pub fn skipped_ids(&self) -> Vec<String> {
+        self.results
+            .iter()
+            .filter(|r| r.skipped)
             .map(|r| r.id.clone())
             .collect()
     }
@@ -275,9 +343,12 @@ impl Default for BatchExecutionStrategy {
 /// Batch transaction builder
 pub struct BatchTransactionBuilder<'a> {
     contract: &'a ContractClient,
+    /// Other target contracts of the batch transactions, by address
+    contracts: HashMap<Address, &'a ContractClient>,
     transactions: Vec<BatchTransaction>,
     strategy: BatchExecutionStrategy,
     continue_on_error: bool,
+    confirmation: ConfirmationConfig,
 }
 
 impl<'a> BatchTransactionBuilder<'a> {
@@ -285,9 +356,11 @@ impl<'a> BatchTransactionBuilder<'a> {
     pub fn new(contract: &'a ContractClient) -> Self {
         Self {
             contract,
+            contracts: HashMap::new(),
             transactions: Vec::new(),
             strategy: BatchExecutionStrategy::default(),
             continue_on_error: true,
+            confirmation: contract.default_confirmation(),
         }
     }
 
@@ -313,10 +386,34 @@ impl<'a> BatchTransactionBuilder<'a> {
             gas_limit: None,
             gas_price: None,
             value: None,
+            max_gas: None,
+        });
+        self
+    }
+
+    /// Add a transaction to another contract, registered with `with_contract`
+    pub fn add_to(mut self, id: String, contract_address: Address, function_name: String, args: Vec<serde_json::Value>) -> Self {
+        self.transactions.push(BatchTransaction {
+            id,
+            contract_address: Some(contract_address),
+            function_name,
+            args,
+            gas_limit: None,
+            gas_price: None,
+            value: None,
+            max_gas: None,
         });
         self
     }
 
+    /// Register another target contract, for the batch transactions with its `contract_address`.
+    /// Its client should share the provider manager of the batch default contract,
+    /// so all transactions are signed by the same provider and allocated nonces by one nonce manager.
+    pub fn with_contract(mut self, contract: &'a ContractClient) -> Self {
+        self.contracts.insert(contract.address(), contract);
+        self
+    }
+
     /// Set execution strategy
     pub fn strategy(mut self, strategy: BatchExecutionStrategy) -> Self {
         self.strategy = strategy;
@@ -329,8 +426,26 @@ impl<'a> BatchTransactionBuilder<'a> {
         self
     }
 
-    /// Execute the batch
+    /// Interval between receipt requests while waiting for each transaction to be mined
+    pub fn with_confirmation_poll_interval(mut self, poll_interval: Duration) -> Self {
+        self.confirmation.poll_interval = poll_interval;
+        self
+    }
+
+    /// Time to wait for each transaction to be mined. Timed out transactions fail
+    /// with their pending hash, see `BatchResult::pending_hashes`.
+    pub fn with_confirmation_timeout(mut self, timeout: Duration) -> Self {
+        self.confirmation.timeout = timeout;
+        self
+    }
+
+    /// Execute the batch. Fails before sending any transaction if a target contract isn't registered,
+    /// or doesn't permit the transaction function.
     pub async fn execute(self) -> Result<BatchResult> {
+        for tx in &self.transactions {
+            self.target(tx)?.ensure_function_permitted(&tx.function_name)?;
+        }
+
         info!("Executing batch of {} transactions with strategy: {:?}",
               self.transactions.len(), self.strategy);
 
@@ -347,7 +462,8 @@ impl<'a> BatchTransactionBuilder<'a> {
         };
 
         let successful = results.iter().filter(|r| r.success).count();
-        let failed = results.len() - successful;
+        let skipped = results.iter().filter(|r| r.skipped).count();
+        let failed = results.len() - successful - skipped;
         let total_gas_used = results.iter()
             .filter_map(|r| r.gas_used)
             .sum();
@@ -356,12 +472,13 @@ impl<'a> BatchTransactionBuilder<'a> {
             total: results.len(),
             successful,
             failed,
+            skipped,
             results,
             total_gas_used,
         };
 
-        info!("Batch execution completed: {} successful, {} failed, {} total gas used",
-              batch_result.successful, batch_result.failed, batch_result.total_gas_used);
+        info!("Batch execution completed: {} successful, {} failed, {} skipped, {} total gas used",
+              batch_result.successful, batch_result.failed, batch_result.skipped, batch_result.total_gas_used);
 
         Ok(batch_result)
     }
@@ -376,7 +493,7 @@ impl<'a> BatchTransactionBuilder<'a> {
             match self.execute_single_transaction(tx).await {
                 Ok(result) => {
                     results.push(result.clone());
-                    if !result.success && !self.continue_on_error {
+                    if !result.success && !result.skipped && !self.continue_on_error {
                         error!("Transaction {} failed, stopping batch execution", tx.id);
                         break;
                     }
@@ -386,6 +503,7 @@ impl<'a> BatchTransactionBuilder<'a> {
                     results.push(BatchTransactionResult {
                         id: tx.id.clone(),
                         success: false,
+                        skipped: false,
                         tx_hash: None,
                         error: Some(e.to_string()),
                         gas_used: None,
@@ -435,6 +553,7 @@ impl<'a> BatchTransactionBuilder<'a> {
                     BatchTransactionResult {
                         id: "unknown".to_string(),
                         success: false,
+                        skipped: false,
                         tx_hash: None,
                         error: Some(e.to_string()),
                         gas_used: None,
@@ -444,14 +563,50 @@ impl<'a> BatchTransactionBuilder<'a> {
             .collect())
     }
 
+    /// Target contract of the transaction: the batch default contract, or the registered one
+    fn target(&self, tx: &BatchTransaction) -> Result<&'a ContractClient> {
+        match tx.contract_address {
+            None => Ok(self.contract),
+            Some(address) if address == self.contract.address() => Ok(self.contract),
+            Some(address) => self.contracts.get(&address).copied().ok_or_else(|| {
+                TxProducerError::InvalidInput(format!(
+                    "Target contract {} of batch transaction {} isn't registered with the batch", address, tx.id
+                ))
+            }),
+        }
+    }
+
     /// Execute a single transaction
     async fn execute_single_transaction(&self, tx: &BatchTransaction) -> Result<BatchTransactionResult> {
+        let contract = self.target(tx)?;
+
         // Convert JSON args to DynSolValue
-        let builder = TransactionBuilder::new(self.contract, tx.function_name.clone());
+        let builder = TransactionBuilder::new(contract, tx.function_name.clone());
         let args = builder.json_to_dyn_sol_values(&tx.args)?;
 
+        // Skip the transaction if its gas estimate exceeds its cap
+        if let Some(max_gas) = tx.max_gas {
+            let estimate = match contract.estimate_gas(&tx.function_name, &args).await {
+                Ok(estimate) => estimate,
+                Err(e) => {
+                    warn!("Transaction {} gas estimation failed: {}", tx.id, e);
+                    return Ok(BatchTransactionResult::failed(tx, e.to_string()));
+                }
+            };
+
+            if let Some(result) = BatchTransactionResult::over_gas_cap(tx, estimate, max_gas) {
+                warn!("Transaction {} skipped: {}", tx.id, result.error.as_deref().unwrap_or_default());
+                return Ok(result);
+            }
+        }
+
         // Execute transaction
-        match self.contract.send_transaction(&tx.function_name, &args).await {
+        let options = SendOptions {
+            confirmation: Some(self.confirmation),
+            ..Default::default()
+        };
+
+        match contract.send(&tx.function_name, &args, options).await {
             Ok(tx_hash) => {
                 info!("Transaction {} succeeded: 0x{}", tx.id, hex::encode(tx_hash));
 
@@ -459,6 +614,7 @@ impl<'a> BatchTransactionBuilder<'a> {
                 Ok(BatchTransactionResult {
                     id: tx.id.clone(),
                     success: true,
+                    skipped: false,
                     tx_hash: Some(tx_hash),
                     error: None,
                     gas_used: None, // Could be fetched from receipt
@@ -466,10 +622,15 @@ impl<'a> BatchTransactionBuilder<'a> {
             }
             Err(e) => {
                 warn!("Transaction {} failed: {}", tx.id, e);
+                let pending_hash = match &e {
+                    TxProducerError::ConfirmationTimeout { tx_hash, .. } => Some(*tx_hash),
+                    _ => None,
+                };
                 Ok(BatchTransactionResult {
                     id: tx.id.clone(),
                     success: false,
-                    tx_hash: None,
+                    skipped: false,
+                    tx_hash: pending_hash,
                     error: Some(e.to_string()),
                     gas_used: None,
                 })
@@ -482,9 +643,10 @@ impl<'a> BatchTransactionBuilder<'a> {
         let mut encoded = HashMap::new();
 
         for tx in &self.transactions {
-            let builder = TransactionBuilder::new(self.contract, tx.function_name.clone());
+            let contract = self.target(tx)?;
+            let builder = TransactionBuilder::new(contract, tx.function_name.clone());
             let args = builder.json_to_dyn_sol_values(&tx.args)?;
-            let data = self.contract.encode_function_data(&tx.function_name, &args)?;
+            let data = contract.encode_function_data(&tx.function_name, &args)?;
             encoded.insert(tx.id.clone(), data);
         }
 
@@ -573,6 +735,7 @@ impl<'a> BatchCallBuilder<'a> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::test_utils::{contract_client, local_provider_manager};
 
     #[test]
     fn test_batch_result_all_succeeded() {
@@ -580,10 +743,12 @@ mod tests {
             total: 3,
             successful: 3,
             failed: 0,
+            skipped: 0,
             results: vec![
                 BatchTransactionResult {
                     id: "1".to_string(),
                     success: true,
+                    skipped: false,
                     tx_hash: Some(B256::default()),
                     error: None,
                     gas_used: Some(21000),
@@ -591,6 +756,7 @@ mod tests {
                 BatchTransactionResult {
                     id: "2".to_string(),
                     success: true,
+                    skipped: false,
                     tx_hash: Some(B256::default()),
                     error: None,
                     gas_used: Some(21000),
@@ -598,6 +764,7 @@ mod tests {
                 BatchTransactionResult {
                     id: "3".to_string(),
                     success: true,
+                    skipped: false,
                     tx_hash: Some(B256::default()),
                     error: None,
                     gas_used: Some(21000),
@@ -617,10 +784,12 @@ mod tests {
             total: 3,
             successful: 2,
             failed: 1,
+            skipped: 0,
             results: vec![
                 BatchTransactionResult {
                     id: "1".to_string(),
                     success: true,
+                    skipped: false,
                     tx_hash: Some(B256::default()),
                     error: None,
                     gas_used: Some(21000),
@@ -628,6 +797,7 @@ mod tests {
                 BatchTransactionResult {
                     id: "2".to_string(),
                     success: false,
+                    skipped: false,
                     tx_hash: None,
                     error: Some("Gas limit exceeded".to_string()),
                     gas_used: None,
@@ -635,6 +805,7 @@ mod tests {
                 BatchTransactionResult {
                     id: "3".to_string(),
                     success: true,
+                    skipped: false,
                     tx_hash: Some(B256::default()),
                     error: None,
                     gas_used: Some(21000),
@@ -647,4 +818,162 @@ mod tests {
         assert_eq!(result.successful_hashes().len(), 2);
         assert_eq!(result.failed_ids(), vec!["2"]);
     }
+
+    #[test]
+    fn test_batch_result_with_confirmation_timeout() {
+        let pending = B256::repeat_byte(0x01);
+        let result = BatchResult {
+            total: 2,
+            successful: 1,
+            failed: 1,
+            skipped: 0,
+            results: vec![
+                BatchTransactionResult {
+                    id: "1".to_string(),
+                    success: true,
+                    skipped: false,
+                    tx_hash: Some(B256::default()),
+                    error: None,
+                    gas_used: Some(21000),
+                },
+                BatchTransactionResult {
+                    id: "2".to_string(),
+                    success: false,
+                    skipped: false,
+                    tx_hash: Some(pending),
+                    error: Some(TxProducerError::ConfirmationTimeout {
+                        tx_hash: pending,
+                        timeout: Duration::from_secs(60),
+                    }.to_string()),
+                    gas_used: None,
+                },
+            ],
+            total_gas_used: 21000,
+        };
+
+        assert_eq!(result.successful_hashes(), vec![B256::default()]);
+        assert_eq!(result.pending_hashes(), vec![pending]);
+        assert_eq!(result.failed_ids(), vec!["2"]);
+    }
+
+    #[test]
+    fn test_batch_result_with_skipped() {
+        let tx = BatchTransaction {
+            id: "2".to_string(),
+            contract_address: None,
+            function_name: "transfer".to_string(),
+            args: vec![],
+            gas_limit: None,
+            gas_price: None,
+            value: None,
+            max_gas: Some(50000),
+        };
+
+        assert!(BatchTransactionResult::over_gas_cap(&tx, 50000, 50000).is_none());
+        let skipped = BatchTransactionResult::over_gas_cap(&tx, 80000, 50000).unwrap();
+        assert!(skipped.skipped && !skipped.success);
+        assert_eq!(skipped.error.as_deref(), Some("Gas estimate 80000 exceeds max gas 50000"));
+
+        let result = BatchResult {
+            total: 3,
+            successful: 1,
+            failed: 1,
+            skipped: 1,
+            results: vec![
+                BatchTransactionResult {
+                    id: "1".to_string(),
+                    success: true,
+                    skipped: false,
+                    tx_hash: Some(B256::default()),
+                    error: None,
+                    gas_used: Some(21000),
+                },
+                skipped,
+                BatchTransactionResult {
+                    id: "3".to_string(),
+                    success: false,
+                    skipped: false,
+                    tx_hash: None,
+                    error: Some("execution reverted".to_string()),
+                    gas_used: None,
+                },
+            ],
+            total_gas_used: 21000,
+        };
+
+        assert!(!result.all_succeeded());
+        assert_eq!(result.failed_ids(), vec!["3"]);
+        assert_eq!(result.skipped_ids(), vec!["2"]);
+        assert!(result.pending_hashes().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_batch_spanning_contracts() {
+        let provider_manager = local_provider_manager();
+
+        let token = contract_client(
+            Address::repeat_byte(0x01),
+            r#"[{"type":"function","name":"approve","stateMutability":"nonpayable","inputs":[{"name":"spender","type":"address"},{"name":"amount","type":"uint256"}],"outputs":[{"name":"","type":"bool"}]}]"#,
+            Arc::clone(&provider_manager),
+        ).await;
+        let vault = contract_client(
+            Address::repeat_byte(0x02),
+            r#"[{"type":"function","name":"deposit","stateMutability":"nonpayable","inputs":[{"name":"amount","type":"uint256"}],"outputs":[]}]"#,
+            Arc::clone(&provider_manager),
+        ).await;
+
+        let encoded = BatchTransactionBuilder::new(&token)
+            .with_contract(&vault)
+            .add("approve".to_string(), "approve".to_string(), vec![serde_json::json!(vault.address().to_string()), serde_json::json!(100)])
+            .add_to("deposit".to_string(), vault.address(), "deposit".to_string(), vec![serde_json::json!(100)])
+            .encode_all()
+            .unwrap();
+        assert_eq!(encoded["deposit"], vault.encode_function_data("deposit", &[DynSolValue::Uint(U256::from(100), 256)]).unwrap());
+
+        // Unregistered target fails the whole batch before sending
+        let result = BatchTransactionBuilder::new(&token)
+            .add("approve".to_string(), "approve".to_string(), vec![serde_json::json!(vault.address().to_string()), serde_json::json!(100)])
+            .add_to("deposit".to_string(), vault.address(), "deposit".to_string(), vec![serde_json::json!(100)])
+            .execute()
+            .await;
+        assert!(matches!(result, Err(TxProducerError::InvalidInput(_))));
+
+        // Function not permitted by its target fails the whole batch before sending
+        let vault = vault.with_denied_functions(vec!["deposit".to_string()]);
+        let result = BatchTransactionBuilder::new(&token)
+            .with_contract(&vault)
+            .add("approve".to_string(), "approve".to_string(), vec![serde_json::json!(vault.address().to_string()), serde_json::json!(100)])
+            .add_to("deposit".to_string(), vault.address(), "deposit".to_string(), vec![serde_json::json!(100)])
+            .execute()
+            .await;
+        assert!(matches!(result, Err(TxProducerError::FunctionNotPermitted { function, .. }) if function == "deposit"));
+    }
+
+    #[tokio::test]
+    async fn test_struct_args_converted_by_abi() {
+        let provider_manager = local_provider_manager();
+
+        let exchange = contract_client(
+            Address::repeat_byte(0x03),
+            r#"[{"type":"function","name":"fillOrder","stateMutability":"nonpayable","inputs":[{"name":"order","type":"tuple","components":[{"name":"maker","type":"address"},{"name":"bps","type":"uint16"}]}],"outputs":[]}]"#,
+            provider_manager,
+        ).await;
+
+        let maker = Address::repeat_byte(0x11);
+        let encoded = TransactionBuilder::new(&exchange, "fillOrder".to_string())
+            .args(vec![serde_json::json!({ "maker": maker.to_string(), "bps": 30 })])
+            .encode()
+            .unwrap();
+        let expected = exchange.encode_function_data("fillOrder", &[DynSolValue::Tuple(vec![
+            DynSolValue::Address(maker),
+            DynSolValue::Uint(U256::from(30), 16),
+        ])]).unwrap();
+        assert_eq!(encoded, expected);
+
+        let error = TransactionBuilder::new(&exchange, "fillOrder".to_string())
+            .args(vec![serde_json::json!({ "maker": maker.to_string() })])
+            .encode()
+            .unwrap_err();
+        assert!(error.to_string().contains("order.bps: missing struct field"));
+    }
 }
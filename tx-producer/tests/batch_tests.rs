@@ -14,6 +14,7 @@ mod tests {
             gas_limit: Some(100000),
             gas_price: None,
             value: None,
+            max_gas: None,
         };
 
         assert_eq!(batch_tx.id, "test1");
@@ -27,6 +28,7 @@ mod tests {
             total: 10,
             successful: 8,
             failed: 2,
+            skipped: 0,
             results: vec![],
             total_gas_used: 1000000,
         };
@@ -60,10 +62,12 @@ mod tests {
             total: 3,
             successful: 2,
             failed: 1,
+            skipped: 0,
             results: vec![
                 BatchTransactionResult {
                     id: "tx1".to_string(),
                     success: true,
+                    skipped: false,
                     tx_hash: Some(hash1),
                     error: None,
                     gas_used: Some(21000),
@@ -71,6 +75,7 @@ mod tests {
                 BatchTransactionResult {
                     id: "tx2".to_string(),
                     success: false,
+                    skipped: false,
                     tx_hash: None,
                     error: Some("Out of gas".to_string()),
                     gas_used: None,
@@ -78,6 +83,7 @@ mod tests {
                 BatchTransactionResult {
                     id: "tx3".to_string(),
                     success: true,
+                    skipped: false,
                     tx_hash: Some(hash2),
                     error: None,
                     gas_used: Some(22000),